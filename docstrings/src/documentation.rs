@@ -7,5 +7,16 @@ pub use module::*;
 
 /// Represents a compiled project's entire documentation.
 pub struct Documentation {
-    modules: Vec<Module>,
+    /// The modules of the project, its root module first.
+    pub modules: Vec<Module>,
+}
+
+impl Documentation {
+    /// The name of the project, which is the name of its root module.
+    pub fn name(&self) -> &str {
+        self.modules
+            .first()
+            .and_then(|module| module.name.first())
+            .map_or("", String::as_str)
+    }
 }
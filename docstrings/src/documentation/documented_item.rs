@@ -2,6 +2,14 @@ use super::ItemType;
 
 /// Represents an item that has been documented.
 pub struct DocumentedItem {
-    item_type: ItemType,
-    documentation: String,
+    /// What kind of item this is.
+    pub item_type: ItemType,
+    /// The name of the item. For an `impl` block, this is the type it's for.
+    pub name: String,
+    /// The item's declaration as written in the source, without any body.
+    pub signature: String,
+    /// The item's doc comments, as markdown.
+    pub documentation: String,
+    /// The items declared inside this one, such as the methods of an ABI.
+    pub children: Vec<DocumentedItem>,
 }
@@ -1,9 +1,53 @@
 /// The type of the item being documented.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ItemType {
     /// A Sway function, starting with `fn`, that is _not_ a method, abi method, or interface surface item.
     Function,
     /// A Sway method, starting with `fn`, that is _not_ a top-level function or ABI method.
     Method,
+    /// A method of an ABI, which a contract implements.
+    AbiMethod,
     /// A Sway struct, denoted with `struct`.
     Struct,
+    /// A Sway enum, denoted with `enum`.
+    Enum,
+    /// A Sway trait, denoted with `trait`.
+    Trait,
+    /// A contract ABI, denoted with `abi`.
+    Abi,
+    /// An `impl` block, for a type or of a trait for a type.
+    Impl,
+    /// A constant, denoted with `const`.
+    Constant,
+    /// An associated type, declared in a trait or defined in an `impl` block.
+    Type,
+    /// A contract's storage declaration.
+    Storage,
+}
+
+impl ItemType {
+    /// A short, lowercase name for the item type, used in anchors and the search index.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemType::Function => "fn",
+            ItemType::Method => "method",
+            ItemType::AbiMethod => "abi-method",
+            ItemType::Struct => "struct",
+            ItemType::Enum => "enum",
+            ItemType::Trait => "trait",
+            ItemType::Abi => "abi",
+            ItemType::Impl => "impl",
+            ItemType::Constant => "const",
+            ItemType::Type => "type",
+            ItemType::Storage => "storage",
+        }
+    }
+
+    /// Whether other items can refer to items of this type by name, and so be linked to them.
+    pub fn is_type(&self) -> bool {
+        matches!(
+            self,
+            ItemType::Struct | ItemType::Enum | ItemType::Trait | ItemType::Abi
+        )
+    }
 }
@@ -9,7 +9,9 @@ pub struct Module {
     /// The name of a module.
     /// e.g. if module `root` contains a submodule `foo`, this would be
     /// vec!["root", "foo"]
-    name: Vec<String>,
+    pub name: Vec<String>,
+    /// The module's own documentation, from its `//!` comments.
+    pub documentation: String,
     /// The documented items contained in this module.
-    documented_items: Vec<DocumentedItem>,
+    pub documented_items: Vec<DocumentedItem>,
 }
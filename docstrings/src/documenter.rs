@@ -1,6 +1,12 @@
 mod source_code_module;
 use crate::*;
 pub use source_code_module::*;
+use std::path::{Path, PathBuf};
+use sway_ast::{
+    attribute::Annotated, AttributeDecl, Dependency, FnSignature, ItemConst, ItemImplItem,
+    ItemKind, ItemTraitItem, ModuleKind,
+};
+use sway_types::{Span, Spanned};
 
 /// A documentation parser and generator.
 pub struct Documenter {
@@ -8,13 +14,352 @@ pub struct Documenter {
 }
 
 impl Documenter {
-    /// Given input sway source code, generates [Documention] for it.
+    /// Given input sway source code, generates [Documentation] for it.
     pub fn generate_documentation(input: &str) -> Result<Documentation, DocumentationError> {
-        todo!("{}", input)
+        Documenter::new(vec![SourceCodeModule::new(vec!["root".into()], input)]).document()
     }
 
     /// Create a new [Documenter] from a mapping of module names to their source code.
-    pub fn new(_raw: impl Into<Vec<SourceCodeModule>>) -> Self {
-        todo!()
+    pub fn new(raw: impl Into<Vec<SourceCodeModule>>) -> Self {
+        Documenter {
+            modules: raw.into(),
+        }
+    }
+
+    /// Create a new [Documenter] for a project by reading its entry file, and then the files of
+    /// the modules which it declares as `dep`s, recursively. The root module is named
+    /// `project_name`, and the others by the library names they declare.
+    pub fn from_entry_file(
+        project_name: &str,
+        entry_path: &Path,
+    ) -> Result<Self, DocumentationError> {
+        let mut modules = Vec::new();
+        read_module_tree(
+            vec![project_name.to_owned()],
+            entry_path.to_owned(),
+            &mut modules,
+        )?;
+        Ok(Documenter::new(modules))
+    }
+
+    /// Parses each module and collects the documentation of the items it makes public.
+    pub fn document(&self) -> Result<Documentation, DocumentationError> {
+        let modules = self
+            .modules
+            .iter()
+            .map(|module| Ok(document_module(module.name().to_vec(), &module.parse()?)))
+            .collect::<Result<_, DocumentationError>>()?;
+        Ok(Documentation { modules })
+    }
+}
+
+fn read_module_tree(
+    name: Vec<String>,
+    path: PathBuf,
+    modules: &mut Vec<SourceCodeModule>,
+) -> Result<(), DocumentationError> {
+    let mut module = SourceCodeModule::read(name, path.clone())?;
+    let ast = module.parse()?;
+
+    // Submodules are named by the library names they declare, as they are by the compiler.
+    let mut name = module.name().to_vec();
+    if let (
+        ModuleKind::Library {
+            name: library_name, ..
+        },
+        [_, .., last],
+    ) = (&ast.kind, name.as_mut_slice())
+    {
+        *last = library_name.as_str().to_owned();
+        module.set_name(name.clone());
+    }
+    modules.push(module);
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    for dep in &ast.dependencies {
+        let mut dep_name = name.clone();
+        dep_name.push(dep.path.span().as_str().to_owned());
+        read_module_tree(dep_name, module_path(dir, dep), modules)?;
+    }
+    Ok(())
+}
+
+fn module_path(parent_module_dir: &Path, dep: &Dependency) -> PathBuf {
+    parent_module_dir
+        .iter()
+        .chain(dep.path.span().as_str().split('/').map(AsRef::as_ref))
+        .collect::<PathBuf>()
+        .with_extension("sw")
+}
+
+fn document_module(name: Vec<String>, module: &sway_ast::Module) -> Module {
+    Module {
+        name,
+        documentation: doc_comments(&module.attribute_list),
+        documented_items: module.items.iter().filter_map(document_item).collect(),
+    }
+}
+
+/// The text of the doc comments among the attributes, one line per comment.
+fn doc_comments(attribute_list: &[AttributeDecl]) -> String {
+    attribute_list
+        .iter()
+        .filter_map(AttributeDecl::doc_comment)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The source code from where `start` starts to where `end` starts, such as the header of an
+/// item up to its body.
+fn text_between(start: &Span, end: &Span) -> String {
+    start.src()[start.start()..end.start()]
+        .trim_end()
+        .to_owned()
+}
+
+/// Documents an item, or returns `None` if it's not public.
+fn document_item(item: &Annotated<ItemKind>) -> Option<DocumentedItem> {
+    let span = item.value.span();
+    let (item_type, name, signature, children) = match &item.value {
+        ItemKind::Fn(item_fn) => {
+            item_fn.fn_signature.visibility.as_ref()?;
+            return Some(document_fn(
+                ItemType::Function,
+                &item.attribute_list,
+                &item_fn.fn_signature,
+            ));
+        }
+        ItemKind::Struct(item_struct) => {
+            item_struct.visibility.as_ref()?;
+            let name = item_struct.name.as_str().to_owned();
+            (ItemType::Struct, name, span.as_str().to_owned(), Vec::new())
+        }
+        ItemKind::Enum(item_enum) => {
+            item_enum.visibility.as_ref()?;
+            let name = item_enum.name.as_str().to_owned();
+            (ItemType::Enum, name, span.as_str().to_owned(), Vec::new())
+        }
+        ItemKind::Trait(item_trait) => {
+            item_trait.visibility.as_ref()?;
+            let mut children = item_trait
+                .trait_items
+                .get()
+                .iter()
+                .map(|(trait_item, _semicolon_token)| document_trait_item(trait_item))
+                .collect::<Vec<_>>();
+            if let Some(trait_defs) = &item_trait.trait_defs_opt {
+                children.extend(trait_defs.get().iter().map(|item_fn| {
+                    document_fn(
+                        ItemType::Method,
+                        &item_fn.attribute_list,
+                        &item_fn.value.fn_signature,
+                    )
+                }));
+            }
+            let name = item_trait.name.as_str().to_owned();
+            let signature = text_between(&span, &item_trait.trait_items.span());
+            (ItemType::Trait, name, signature, children)
+        }
+        ItemKind::Abi(item_abi) => {
+            let mut children = item_abi
+                .abi_items
+                .get()
+                .iter()
+                .map(|(fn_signature, _semicolon_token)| {
+                    document_fn(
+                        ItemType::AbiMethod,
+                        &fn_signature.attribute_list,
+                        &fn_signature.value,
+                    )
+                })
+                .collect::<Vec<_>>();
+            if let Some(abi_defs) = &item_abi.abi_defs_opt {
+                children.extend(abi_defs.get().iter().map(|item_fn| {
+                    document_fn(
+                        ItemType::Method,
+                        &item_fn.attribute_list,
+                        &item_fn.value.fn_signature,
+                    )
+                }));
+            }
+            let name = item_abi.name.as_str().to_owned();
+            let signature = text_between(&span, &item_abi.abi_items.span());
+            (ItemType::Abi, name, signature, children)
+        }
+        ItemKind::Impl(item_impl) => {
+            // Everything in a trait impl is as public as the trait.
+            let is_trait_impl = item_impl.trait_opt.is_some();
+            let children = item_impl
+                .contents
+                .get()
+                .iter()
+                .filter_map(|impl_item| match &impl_item.value {
+                    ItemImplItem::Fn(item_fn) => {
+                        (is_trait_impl || item_fn.fn_signature.visibility.is_some()).then(|| {
+                            document_fn(
+                                ItemType::Method,
+                                &impl_item.attribute_list,
+                                &item_fn.fn_signature,
+                            )
+                        })
+                    }
+                    ItemImplItem::Const(item_const) => (is_trait_impl
+                        || item_const.visibility.is_some())
+                    .then(|| document_const(&impl_item.attribute_list, item_const)),
+                    ItemImplItem::Type(impl_type) => Some(DocumentedItem {
+                        item_type: ItemType::Type,
+                        name: impl_type.name.as_str().to_owned(),
+                        signature: impl_type.span().as_str().to_owned(),
+                        documentation: doc_comments(&impl_item.attribute_list),
+                        children: Vec::new(),
+                    }),
+                })
+                .collect::<Vec<_>>();
+            if children.is_empty() && !is_trait_impl {
+                return None;
+            }
+            let name = item_impl.ty.span().as_str().to_owned();
+            let signature = text_between(&span, &item_impl.contents.span());
+            (ItemType::Impl, name, signature, children)
+        }
+        ItemKind::Const(item_const) => {
+            item_const.visibility.as_ref()?;
+            return Some(document_const(&item.attribute_list, item_const));
+        }
+        ItemKind::Storage(_) => (
+            ItemType::Storage,
+            "storage".to_owned(),
+            span.as_str().to_owned(),
+            Vec::new(),
+        ),
+        ItemKind::Use(_) | ItemKind::Break(_) | ItemKind::Continue(_) | ItemKind::Error(_) => {
+            return None
+        }
+    };
+    Some(DocumentedItem {
+        item_type,
+        name,
+        signature,
+        documentation: doc_comments(&item.attribute_list),
+        children,
+    })
+}
+
+fn document_fn(
+    item_type: ItemType,
+    attribute_list: &[AttributeDecl],
+    fn_signature: &FnSignature,
+) -> DocumentedItem {
+    DocumentedItem {
+        item_type,
+        name: fn_signature.name.as_str().to_owned(),
+        signature: fn_signature.span().as_str().to_owned(),
+        documentation: doc_comments(attribute_list),
+        children: Vec::new(),
+    }
+}
+
+fn document_const(attribute_list: &[AttributeDecl], item_const: &ItemConst) -> DocumentedItem {
+    DocumentedItem {
+        item_type: ItemType::Constant,
+        name: item_const.name.as_str().to_owned(),
+        signature: text_between(&item_const.span(), &item_const.eq_token.span()),
+        documentation: doc_comments(attribute_list),
+        children: Vec::new(),
+    }
+}
+
+fn document_trait_item(trait_item: &Annotated<ItemTraitItem>) -> DocumentedItem {
+    let (item_type, name) = match &trait_item.value {
+        ItemTraitItem::Fn(fn_signature) => {
+            return document_fn(ItemType::Method, &trait_item.attribute_list, fn_signature)
+        }
+        ItemTraitItem::Const(trait_const) => (ItemType::Constant, &trait_const.name),
+        ItemTraitItem::Type(trait_type) => (ItemType::Type, &trait_type.name),
+    };
+    DocumentedItem {
+        item_type,
+        name: name.as_str().to_owned(),
+        signature: trait_item.value.span().as_str().to_owned(),
+        documentation: doc_comments(&trait_item.attribute_list),
+        children: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_public_items() {
+        let documentation = Documenter::generate_documentation(
+            r#"//! A library.
+library foo;
+
+/// A point.
+pub struct Point {
+    x: u64,
+    y: u64,
+}
+
+/// Private.
+struct Hidden {}
+
+impl Point {
+    /// The origin.
+    pub fn origin() -> Self {
+        Point { x: 0, y: 0 }
+    }
+    fn helper() {}
+}
+
+/// A thing which can be measured.
+pub trait Measure {
+    /// The size.
+    fn size(self) -> u64;
+}
+
+abi Counter {
+    /// Adds one.
+    #[storage(read, write)]
+    fn increment() -> u64;
+}
+"#,
+        )
+        .unwrap();
+
+        let module = &documentation.modules[0];
+        assert_eq!(documentation.name(), "root");
+        assert_eq!(module.documentation, "A library.");
+
+        let items = &module.documented_items;
+        let names = items
+            .iter()
+            .map(|item| (item.item_type, item.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                (ItemType::Struct, "Point"),
+                (ItemType::Impl, "Point"),
+                (ItemType::Trait, "Measure"),
+                (ItemType::Abi, "Counter"),
+            ]
+        );
+        assert_eq!(items[0].documentation, "A point.");
+        assert!(items[0].signature.starts_with("pub struct Point {"));
+
+        assert_eq!(items[1].children.len(), 1);
+        assert_eq!(items[1].children[0].name, "origin");
+        assert_eq!(items[1].children[0].signature, "pub fn origin() -> Self");
+        assert_eq!(items[1].signature, "impl Point");
+
+        assert_eq!(items[2].signature, "pub trait Measure");
+        assert_eq!(items[2].children[0].documentation, "The size.");
+
+        let increment = &items[3].children[0];
+        assert_eq!(increment.item_type, ItemType::AbiMethod);
+        assert_eq!(increment.signature, "fn increment() -> u64");
+        assert_eq!(increment.documentation, "Adds one.");
     }
 }
@@ -1,3 +1,6 @@
+use crate::DocumentationError;
+use std::{fs, path::PathBuf, sync::Arc};
+
 /// Represents a Sway module and its contents as a string.
 pub struct SourceCodeModule {
     /// The name of a module.
@@ -6,4 +9,54 @@ pub struct SourceCodeModule {
     name: Vec<String>,
     /// The raw source code contained in the module.
     source: String,
+    /// The file the source code was read from, if it was.
+    path: Option<PathBuf>,
+}
+
+impl SourceCodeModule {
+    /// Create a module from its name and source code.
+    pub fn new(name: Vec<String>, source: impl Into<String>) -> Self {
+        SourceCodeModule {
+            name,
+            source: source.into(),
+            path: None,
+        }
+    }
+
+    /// Read a module's source code from a file.
+    pub fn read(name: Vec<String>, path: PathBuf) -> Result<Self, DocumentationError> {
+        let source = fs::read_to_string(&path).map_err(|error| DocumentationError::Io {
+            path: path.clone(),
+            error,
+        })?;
+        Ok(SourceCodeModule {
+            name,
+            source,
+            path: Some(path),
+        })
+    }
+
+    /// The name of the module.
+    pub fn name(&self) -> &[String] {
+        &self.name
+    }
+
+    /// The file the source code was read from, if it was.
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    pub(crate) fn set_name(&mut self, name: Vec<String>) {
+        self.name = name;
+    }
+
+    pub(crate) fn parse(&self) -> Result<sway_ast::Module, DocumentationError> {
+        let path = self.path.clone().map(Arc::new);
+        sway_parse::parse_file(Arc::from(self.source.as_str()), path).map_err(|error| {
+            DocumentationError::Parse {
+                module: self.name.clone(),
+                error,
+            }
+        })
+    }
 }
@@ -1,2 +1,36 @@
+use std::{fmt, io, path::PathBuf};
+use sway_parse::ParseFileError;
+
 /// An error generated while documenting Sway code.
-pub struct DocumentationError {}
+#[derive(Debug)]
+pub enum DocumentationError {
+    /// A source file could not be read.
+    Io {
+        /// The file which could not be read.
+        path: PathBuf,
+        /// Why it could not be read.
+        error: io::Error,
+    },
+    /// A module could not be parsed.
+    Parse {
+        /// The name of the module, as in [crate::Module::name].
+        module: Vec<String>,
+        /// Why it could not be parsed.
+        error: ParseFileError,
+    },
+}
+
+impl fmt::Display for DocumentationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DocumentationError::Io { path, error } => {
+                write!(f, "failed to read {}: {}", path.display(), error)
+            }
+            DocumentationError::Parse { module, error } => {
+                write!(f, "failed to parse module {}: {}", module.join("::"), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DocumentationError {}
@@ -0,0 +1,332 @@
+use crate::*;
+use pulldown_cmark::{html::push_html, Parser};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const STYLE: &str = include_str!("html/style.css");
+const SEARCH_SCRIPT: &str = include_str!("html/search.js");
+
+/// The order in which the kinds of items are listed on a module's page, and their headings.
+const SECTIONS: [(ItemType, &str); 8] = [
+    (ItemType::Storage, "Storage"),
+    (ItemType::Abi, "ABIs"),
+    (ItemType::Trait, "Traits"),
+    (ItemType::Struct, "Structs"),
+    (ItemType::Enum, "Enums"),
+    (ItemType::Function, "Functions"),
+    (ItemType::Constant, "Constants"),
+    (ItemType::Impl, "Implementations"),
+];
+
+/// An entry of the search index, which is written out as JSON for the search box to filter.
+#[derive(Serialize)]
+struct SearchEntry {
+    name: String,
+    kind: &'static str,
+    module: String,
+    href: String,
+}
+
+/// Renders the documentation of projects as static HTML in `out_dir`, with one page per module,
+/// an index page listing the projects and a search index over all of their items.
+///
+/// Type names in signatures link to the documentation of the types. When projects declare types
+/// with the same name, links go to the type of the project which comes first in `projects`.
+pub fn render_html(projects: &[Documentation], out_dir: &Path) -> io::Result<()> {
+    let links = type_links(projects);
+    let mut search_index = Vec::new();
+    for project in projects {
+        for module in &project.modules {
+            let page = module_page(module);
+            let html = render_module(project, module, &links);
+            write_file(&out_dir.join(&page), &html)?;
+
+            let module_name = module.name.join("::");
+            for (ix, item) in module.documented_items.iter().enumerate() {
+                let anchor = anchor(item, ix);
+                search_index.push(SearchEntry {
+                    name: item.name.clone(),
+                    kind: item.item_type.as_str(),
+                    module: module_name.clone(),
+                    href: format!("{}#{}", page, anchor),
+                });
+                for child in &item.children {
+                    search_index.push(SearchEntry {
+                        name: format!("{}::{}", item.name, child.name),
+                        kind: child.item_type.as_str(),
+                        module: module_name.clone(),
+                        href: format!("{}#{}", page, child_anchor(&anchor, child)),
+                    });
+                }
+            }
+        }
+    }
+
+    write_file(&out_dir.join("index.html"), &render_index(projects))?;
+    let search_index = format!(
+        "const SEARCH_INDEX = {};\n",
+        serde_json::to_string(&search_index)?
+    );
+    write_file(&out_dir.join("search-index.js"), &search_index)?;
+    write_file(&out_dir.join("search.js"), SEARCH_SCRIPT)?;
+    write_file(&out_dir.join("style.css"), STYLE)
+}
+
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// The path of a module's page, relative to the output directory.
+fn module_page(module: &Module) -> String {
+    module
+        .name
+        .iter()
+        .collect::<PathBuf>()
+        .join("index.html")
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// The relative path from a page back to the output directory.
+fn root_of(page: &str) -> String {
+    "../".repeat(page.matches('/').count())
+}
+
+fn anchor(item: &DocumentedItem, ix: usize) -> String {
+    match item.item_type {
+        // A module may have any number of impls for the same type.
+        ItemType::Impl => format!("impl.{}", ix),
+        _ => format!("{}.{}", item.item_type.as_str(), item.name),
+    }
+}
+
+fn child_anchor(parent_anchor: &str, child: &DocumentedItem) -> String {
+    format!(
+        "{}.{}.{}",
+        parent_anchor,
+        child.item_type.as_str(),
+        child.name
+    )
+}
+
+/// Maps the names of the types declared by the projects to their documentation, relative to the
+/// output directory.
+fn type_links(projects: &[Documentation]) -> HashMap<String, String> {
+    let mut links = HashMap::new();
+    for project in projects {
+        for module in &project.modules {
+            let page = module_page(module);
+            for (ix, item) in module.documented_items.iter().enumerate() {
+                if item.item_type.is_type() {
+                    links
+                        .entry(item.name.clone())
+                        .or_insert_with(|| format!("{}#{}", page, anchor(item, ix)));
+                }
+            }
+        }
+    }
+    links
+}
+
+fn render_index(projects: &[Documentation]) -> String {
+    let mut main = String::from("<h1>Documentation</h1>\n");
+    for project in projects {
+        writeln!(main, "<h2>{}</h2>\n<ul>", escape(project.name())).unwrap();
+        for module in &project.modules {
+            writeln!(
+                main,
+                "<li><a href=\"{}\">{}</a></li>",
+                module_page(module),
+                escape(&module.name.join("::"))
+            )
+            .unwrap();
+        }
+        main.push_str("</ul>\n");
+    }
+    render_page("Documentation", "", &main)
+}
+
+fn render_module(
+    project: &Documentation,
+    module: &Module,
+    links: &HashMap<String, String>,
+) -> String {
+    let page = module_page(module);
+    let root = root_of(&page);
+    let title = module.name.join("::");
+
+    let mut main = String::new();
+    writeln!(main, "<h1>Module <code>{}</code></h1>", escape(&title)).unwrap();
+    main.push_str(&markdown(&module.documentation));
+
+    let submodules = project
+        .modules
+        .iter()
+        .filter(|submodule| {
+            submodule.name.len() == module.name.len() + 1
+                && submodule.name.starts_with(&module.name)
+        })
+        .collect::<Vec<_>>();
+    if !submodules.is_empty() {
+        main.push_str("<h2>Modules</h2>\n<ul>\n");
+        for submodule in submodules {
+            writeln!(
+                main,
+                "<li><a href=\"{}{}\">{}</a></li>",
+                root,
+                module_page(submodule),
+                escape(submodule.name.last().unwrap())
+            )
+            .unwrap();
+        }
+        main.push_str("</ul>\n");
+    }
+
+    for (item_type, heading) in SECTIONS {
+        let items = module
+            .documented_items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.item_type == item_type)
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            continue;
+        }
+        writeln!(main, "<h2>{}</h2>", heading).unwrap();
+        for (ix, item) in items {
+            let anchor = anchor(item, ix);
+            writeln!(
+                main,
+                "<section class=\"item\" id=\"{}\">\n<pre>{}</pre>\n{}",
+                escape(&anchor),
+                render_signature(&item.signature, links, &root),
+                markdown(&item.documentation)
+            )
+            .unwrap();
+            for child in &item.children {
+                writeln!(
+                    main,
+                    "<div class=\"child\" id=\"{}\">\n<pre>{}</pre>\n{}</div>",
+                    escape(&child_anchor(&anchor, child)),
+                    render_signature(&child.signature, links, &root),
+                    markdown(&child.documentation)
+                )
+                .unwrap();
+            }
+            main.push_str("</section>\n");
+        }
+    }
+
+    render_page(&title, &root, &main)
+}
+
+fn render_page(title: &str, root: &str, main: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<link rel="stylesheet" href="{root}style.css">
+</head>
+<body data-root="{root}">
+<nav>
+<a href="{root}index.html">Index</a>
+<input id="search" type="search" placeholder="Search">
+<ul id="search-results"></ul>
+</nav>
+<main>
+{main}</main>
+<script src="{root}search-index.js"></script>
+<script src="{root}search.js"></script>
+</body>
+</html>
+"#,
+        title = escape(title),
+        root = root,
+        main = main,
+    )
+}
+
+/// Escapes a signature for HTML and links the names of types in it to their documentation.
+/// Comments, such as the doc comments of struct fields, are left as they are.
+fn render_signature(signature: &str, links: &HashMap<String, String>, root: &str) -> String {
+    let mut html = String::new();
+    for (ix, line) in signature.lines().enumerate() {
+        if ix > 0 {
+            html.push('\n');
+        }
+        if line.trim_start().starts_with("//") {
+            html.push_str(&escape(line));
+            continue;
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find(|c: char| c.is_alphabetic() || c == '_') {
+            html.push_str(&escape(&rest[..start]));
+            rest = &rest[start..];
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            match links.get(word) {
+                Some(href) => write!(html, "<a href=\"{}{}\">{}</a>", root, href, word).unwrap(),
+                None => html.push_str(word),
+            }
+            rest = &rest[len..];
+        }
+        html.push_str(&escape(rest));
+    }
+    html
+}
+
+fn markdown(text: &str) -> String {
+    let mut html = String::new();
+    push_html(&mut html, Parser::new(text));
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_link_to_types() {
+        let links = HashMap::from([("Point".to_owned(), "geo/index.html#struct.Point".to_owned())]);
+        assert_eq!(
+            render_signature(
+                "pub fn f(p: Point) -> Vec<Points>\n    /// A Point.",
+                &links,
+                "../"
+            ),
+            "pub fn f(p: <a href=\"../geo/index.html#struct.Point\">Point</a>) -&gt; \
+             Vec&lt;Points&gt;\n    /// A Point."
+        );
+    }
+
+    #[test]
+    fn module_pages() {
+        let module = Module {
+            name: vec!["std".to_owned(), "address".to_owned()],
+            documentation: String::new(),
+            documented_items: Vec::new(),
+        };
+        let page = module_page(&module);
+        assert_eq!(page, "std/address/index.html");
+        assert_eq!(root_of(&page), "../../");
+    }
+}
@@ -0,0 +1,24 @@
+// Filters the entries of `SEARCH_INDEX`, from `search-index.js`, by the text in the search box.
+const search = document.getElementById("search");
+const results = document.getElementById("search-results");
+const root = document.body.dataset.root;
+
+search.addEventListener("input", () => {
+    const query = search.value.trim().toLowerCase();
+    results.replaceChildren();
+    if (query === "") {
+        return;
+    }
+    const matches = SEARCH_INDEX.filter((entry) => entry.name.toLowerCase().includes(query));
+    for (const entry of matches.slice(0, 50)) {
+        const kind = document.createElement("span");
+        kind.className = "kind";
+        kind.textContent = entry.kind;
+        const link = document.createElement("a");
+        link.href = root + entry.href;
+        link.textContent = `${entry.module}::${entry.name}`;
+        const result = document.createElement("li");
+        result.append(kind, " ", link);
+        results.append(result);
+    }
+});
@@ -1,6 +1,6 @@
 use crate::pkg::{manifest_file_missing, parsing_failed, wrong_program_type};
 use anyhow::{anyhow, bail, Result};
-use forc_util::{find_manifest_dir, println_yellow_err, validate_name};
+use forc_util::{find_manifest_dir, println_yellow_err, validate_name, MessageFormat};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
@@ -8,7 +8,10 @@ use std::{
     sync::Arc,
 };
 
-use sway_core::{parse, TreeType};
+use sway_core::{
+    lint::{self, LintLevel},
+    parse, OptLevel, TreeType,
+};
 use sway_utils::constants;
 
 type PatchMap = BTreeMap<String, Dependency>;
@@ -31,6 +34,8 @@ pub struct Manifest {
     pub dependencies: Option<BTreeMap<String, Dependency>>,
     pub patch: Option<BTreeMap<String, PatchMap>>,
     build_profile: Option<BTreeMap<String, BuildProfile>>,
+    /// The levels of lints by name, e.g. `dead_code = "allow"`.
+    pub lints: Option<BTreeMap<String, LintLevel>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -86,6 +91,15 @@ pub struct BuildProfile {
     pub print_intermediate_asm: bool,
     pub silent: bool,
     pub time_phases: bool,
+    #[serde(default)]
+    pub opt_level: OptLevel,
+    /// Report warnings as errors, unless their lint is allowed or warned about explicitly.
+    #[serde(default)]
+    pub deny_warnings: bool,
+    /// The format in which diagnostics are printed. This is chosen on the command line rather
+    /// than in the manifest.
+    #[serde(skip)]
+    pub message_format: MessageFormat,
 }
 
 impl Dependency {
@@ -258,6 +272,11 @@ impl Manifest {
         if let Some(ref org) = self.project.organization {
             validate_name(org, "organization name")?;
         }
+        for name in self.lints.iter().flat_map(|lints| lints.keys()) {
+            if !lint::is_lint_name(name) {
+                bail!("unknown lint {:?} in the `[lints]` table", name);
+            }
+        }
         Ok(())
     }
 
@@ -396,6 +415,9 @@ impl BuildProfile {
             print_intermediate_asm: false,
             silent: false,
             time_phases: false,
+            opt_level: OptLevel::Opt0,
+            deny_warnings: false,
+            message_format: MessageFormat::default(),
         }
     }
 
@@ -407,6 +429,9 @@ impl BuildProfile {
             print_intermediate_asm: false,
             silent: false,
             time_phases: false,
+            opt_level: OptLevel::Opt1,
+            deny_warnings: false,
+            message_format: MessageFormat::default(),
         }
     }
 }
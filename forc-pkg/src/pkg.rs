@@ -1414,22 +1414,26 @@ fn dep_to_source_patched(
     apply_patch(manifest, dep_name, &unpatched)
 }
 
-/// Given a `forc_pkg::BuildProfile`, produce the necessary `sway_core::BuildConfig` required for
-/// compilation.
+/// Given a package's manifest and a `forc_pkg::BuildProfile`, produce the necessary
+/// `sway_core::BuildConfig` required for compilation.
 pub fn sway_build_config(
-    manifest_dir: &Path,
-    entry_path: &Path,
+    manifest: &ManifestFile,
     build_profile: &BuildProfile,
 ) -> Result<sway_core::BuildConfig> {
     // Prepare the build config to pass through to the compiler.
-    let file_name = find_file_name(manifest_dir, entry_path)?;
+    let manifest_dir = manifest.dir();
+    let entry_path = manifest.entry_path();
+    let file_name = find_file_name(manifest_dir, &entry_path)?;
     let build_config = sway_core::BuildConfig::root_from_file_name_and_manifest_path(
         file_name.to_path_buf(),
         manifest_dir.to_path_buf(),
     )
     .print_finalized_asm(build_profile.print_finalized_asm)
     .print_intermediate_asm(build_profile.print_intermediate_asm)
-    .print_ir(build_profile.print_ir);
+    .print_ir(build_profile.print_ir)
+    .opt_level(build_profile.opt_level)
+    .lints(manifest.lints.clone().unwrap_or_default())
+    .deny_warnings(build_profile.deny_warnings);
     Ok(build_config)
 }
 
@@ -1513,8 +1517,7 @@ pub fn compile_ast(
     namespace: namespace::Module,
 ) -> Result<CompileAstResult> {
     let source = manifest.entry_string()?;
-    let sway_build_config =
-        sway_build_config(manifest.dir(), &manifest.entry_path(), build_profile)?;
+    let sway_build_config = sway_build_config(manifest, build_profile)?;
     let ast_res = sway_core::compile_to_ast(source, namespace, Some(&sway_build_config));
     Ok(ast_res)
 }
@@ -1562,12 +1565,12 @@ pub fn compile(
         }};
     }
 
-    let entry_path = manifest.entry_path();
     let sway_build_config = time_expr!(
         "produce `sway_core::BuildConfig`",
-        sway_build_config(manifest.dir(), &entry_path, build_profile)?
+        sway_build_config(manifest, build_profile)?
     );
     let silent_mode = build_profile.silent;
+    let message_format = build_profile.message_format;
 
     // First, compile to an AST. We'll update the namespace and check for JSON ABI output.
     let ast_res = time_expr!(
@@ -1576,7 +1579,7 @@ pub fn compile(
     );
     match &ast_res {
         CompileAstResult::Failure { warnings, errors } => {
-            print_on_failure(silent_mode, warnings, errors);
+            print_on_failure(silent_mode, message_format, &pkg.name, warnings, errors);
             bail!("Failed to compile {}", pkg.name);
         }
         CompileAstResult::Success {
@@ -1594,7 +1597,7 @@ pub fn compile(
                 // If we're compiling a library, we don't need to compile any further.
                 // Instead, we update the namespace with the library's top-level module.
                 TreeType::Library { .. } => {
-                    print_on_success_library(silent_mode, &pkg.name, warnings);
+                    print_on_success_library(silent_mode, message_format, &pkg.name, warnings);
                     let bytecode = vec![];
                     let lib_namespace = typed_program.root.namespace.clone();
                     let compiled = Compiled {
@@ -1618,7 +1621,13 @@ pub fn compile(
                     );
                     match bc_res {
                         BytecodeCompilationResult::Success { bytes, warnings } => {
-                            print_on_success(silent_mode, &pkg.name, &warnings, &tree_type);
+                            print_on_success(
+                                silent_mode,
+                                message_format,
+                                &pkg.name,
+                                &warnings,
+                                &tree_type,
+                            );
                             let bytecode = bytes;
                             let compiled = Compiled {
                                 json_abi,
@@ -1632,7 +1641,13 @@ pub fn compile(
                             unreachable!("compilation of library program types is handled above")
                         }
                         BytecodeCompilationResult::Failure { errors, warnings } => {
-                            print_on_failure(silent_mode, &warnings, &errors);
+                            print_on_failure(
+                                silent_mode,
+                                message_format,
+                                &pkg.name,
+                                &warnings,
+                                &errors,
+                            );
                             bail!("Failed to compile {}", pkg.name);
                         }
                     }
@@ -1657,10 +1672,20 @@ pub fn build(plan: &BuildPlan, profile: &BuildProfile) -> anyhow::Result<(Compil
     let mut storage_slots = vec![];
     let mut bytecode = vec![];
     let mut tree_type = None;
-    for &node in &plan.compilation_order {
+    // Warnings are only denied in the package being built, not in its dependencies.
+    let dep_profile = BuildProfile {
+        deny_warnings: false,
+        ..profile.clone()
+    };
+    for (i, &node) in plan.compilation_order.iter().enumerate() {
         let dep_namespace = dependency_namespace(&namespace_map, &plan.graph, node);
         let pkg = &plan.graph()[node];
         let manifest = &plan.manifest_map()[&pkg.id()];
+        let profile = if i == plan.compilation_order.len() - 1 {
+            profile
+        } else {
+            &dep_profile
+        };
         let res = compile(pkg, manifest, profile, dep_namespace, &mut source_map)?;
         let (compiled, maybe_namespace) = res;
         if let Some(namespace) = maybe_namespace {
@@ -1684,6 +1709,9 @@ pub fn build(plan: &BuildPlan, profile: &BuildProfile) -> anyhow::Result<(Compil
 }
 
 /// Compile the entire forc package and return a CompileAstResult.
+///
+/// The syntax errors of the package are in the returned parse result. The CompileAstResult is of
+/// type checking the parts of the package which parsed, see [check_result] for both.
 pub fn check(
     plan: &BuildPlan,
     silent_mode: bool,
@@ -1698,14 +1726,28 @@ pub fn check(
         let manifest = &plan.manifest_map()[&pkg.id()];
         let parsed_result = parse(manifest, silent_mode)?;
 
+        // The items and statements of the checked package which did parse are type checked even
+        // if the parser skipped over others, whereas the syntax errors of its dependencies end
+        // the check.
+        if i != plan.compilation_order.len() - 1 && !parsed_result.errors.is_empty() {
+            bail!("unable to parse");
+        }
+
         let parse_program = match &parsed_result.value {
             None => bail!("unable to parse"),
             Some(program) => program,
         };
 
-        let ast_result = sway_core::parsed_to_ast(parse_program, dep_namespace);
+        let sway_build_config = sway_build_config(manifest, &BuildProfile::debug())?;
+        let ast_result =
+            sway_core::parsed_to_ast(parse_program, dep_namespace, Some(&sway_build_config));
 
+        // The type errors of the checked package are returned for reporting, whereas those of
+        // its dependencies end the check.
         let typed_program = match &ast_result {
+            CompileAstResult::Failure { .. } if i == plan.compilation_order.len() - 1 => {
+                return Ok((parsed_result, ast_result));
+            }
             CompileAstResult::Failure { .. } => bail!("unable to type check"),
             CompileAstResult::Success { typed_program, .. } => typed_program,
         };
@@ -1724,6 +1766,24 @@ pub fn check(
     bail!("unable to check sway program: build plan contains no packages")
 }
 
+/// Combines the results returned by [check], failing if either parsing or type checking the
+/// package did. The syntax errors are reported first.
+pub fn check_result(
+    parsed_result: &CompileResult<ParseProgram>,
+    ast_result: CompileAstResult,
+) -> CompileAstResult {
+    if parsed_result.errors.is_empty() {
+        return ast_result;
+    }
+    let (warnings, type_errors) = match ast_result {
+        CompileAstResult::Success { warnings, .. } => (warnings, vec![]),
+        CompileAstResult::Failure { warnings, errors } => (warnings, errors),
+    };
+    let mut errors = parsed_result.errors.clone();
+    errors.extend(type_errors);
+    CompileAstResult::Failure { warnings, errors }
+}
+
 /// Returns a parsed AST from the supplied [ManifestFile]
 pub fn parse(
     manifest: &ManifestFile,
@@ -1734,7 +1794,7 @@ pub fn parse(
         ..BuildProfile::debug()
     };
     let source = manifest.entry_string()?;
-    let sway_build_config = sway_build_config(manifest.dir(), &manifest.entry_path(), &profile)?;
+    let sway_build_config = sway_build_config(manifest, &profile)?;
     Ok(sway_core::parse(source, Some(&sway_build_config)))
 }
 
@@ -0,0 +1,83 @@
+//! A `forc` plugin for generating the documentation of a Sway package and its dependencies.
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use docstrings::{render_html, Documenter};
+use forc_pkg::{self as pkg, ManifestFile};
+use forc_util::{default_output_directory, init_tracing_subscriber};
+use std::path::PathBuf;
+use sway_core::CompileAstResult;
+use tracing::{error, info};
+
+#[derive(Debug, Parser)]
+#[clap(
+    name = "forc-doc",
+    about = "Forc plugin for generating the documentation of a Sway package.",
+    version
+)]
+pub struct App {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+    /// Only document the package itself, not its dependencies.
+    #[clap(long)]
+    pub no_deps: bool,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+    /// Requires that the Forc.lock file is up-to-date. If the lock file is missing, or it
+    /// needs to be updated, Forc will exit with an error
+    #[clap(long)]
+    pub locked: bool,
+    /// Silent mode. Don't output any warnings or errors to the command line.
+    #[clap(long = "silent", short = 's')]
+    pub silent_mode: bool,
+}
+
+fn main() {
+    init_tracing_subscriber();
+    if let Err(err) = run() {
+        error!("Error: {:?}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<()> {
+    let app = App::parse();
+    let dir = match app.path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let manifest = ManifestFile::from_dir(&dir)?;
+    let plan = pkg::BuildPlan::from_lock_and_manifest(&manifest, app.locked, app.offline_mode)?;
+
+    // Only packages which type check are documented.
+    let (parsed_res, ast_res) = pkg::check(&plan, app.silent_mode)?;
+    if let CompileAstResult::Failure { .. } = pkg::check_result(&parsed_res, ast_res) {
+        bail!("unable to type check");
+    }
+
+    // The package itself comes first, so that links to types prefer its own over those of its
+    // dependencies.
+    let mut projects = Vec::new();
+    for &node in plan.compilation_order().iter().rev() {
+        let pinned = &plan.graph()[node];
+        let pkg_manifest = &plan.manifest_map()[&pinned.id()];
+        if app.no_deps && pkg_manifest.dir() != manifest.dir() {
+            continue;
+        }
+        let documenter = Documenter::from_entry_file(&pinned.name, &pkg_manifest.entry_path())?;
+        projects.push(documenter.document()?);
+    }
+
+    let out_dir = default_output_directory(manifest.dir()).join("doc");
+    render_html(&projects, &out_dir)?;
+    info!(
+        "  Documented {} package{} in {}",
+        projects.len(),
+        if projects.len() == 1 { "" } else { "s" },
+        out_dir.join("index.html").display()
+    );
+    Ok(())
+}
@@ -5,14 +5,16 @@ use clap::Parser;
 use prettydiff::{basic::DiffOp, diff_lines};
 use std::{
     default::Default,
+    fmt::Write,
     fs,
+    ops::Range,
     path::{Path, PathBuf},
     sync::Arc,
 };
 use taplo::formatter as taplo_fmt;
 use tracing::{error, info};
 
-use forc_util::{find_manifest_dir, init_tracing_subscriber, println_green, println_red};
+use forc_util::{find_manifest_dir, init_tracing_subscriber};
 use sway_core::BuildConfig;
 use sway_fmt_v2::Formatter;
 use sway_utils::{constants, get_sway_files};
@@ -27,7 +29,7 @@ pub struct App {
     /// Run in 'check' mode.
     ///
     /// - Exits with `0` if input is formatted correctly.
-    /// - Exits with `1` and prints a diff if formatting is required.
+    /// - Exits with `1` and prints a unified diff of each file if formatting is required.
     #[clap(short, long)]
     pub check: bool,
     /// Path to the project, if not specified, current working directory will be used.
@@ -74,8 +76,7 @@ fn format_pkg_at_dir(app: App, dir: &Path, config: &mut Formatter) -> Result<()>
                             if app.check {
                                 if *file_content != formatted_content {
                                     contains_edits = true;
-                                    info!("\n{:?}\n", file);
-                                    display_file_diff(&file_content, &formatted_content)?;
+                                    display_file_diff(&file, &file_content, &formatted_content)?;
                                 }
                             } else {
                                 format_file(&file, &formatted_content)?;
@@ -101,7 +102,7 @@ fn format_pkg_at_dir(app: App, dir: &Path, config: &mut Formatter) -> Result<()>
                 } else if formatted_content != file_content {
                     contains_edits = true;
                     error!("\nManifest Forc.toml improperly formatted");
-                    display_file_diff(&file_content, &formatted_content)?;
+                    display_file_diff(&manifest_file, &file_content, &formatted_content)?;
                 } else {
                     info!("\nManifest Forc.toml properly formatted")
                 }
@@ -123,44 +124,73 @@ fn format_pkg_at_dir(app: App, dir: &Path, config: &mut Formatter) -> Result<()>
     }
 }
 
-fn display_file_diff(file_content: &str, formatted_content: &str) -> Result<()> {
+/// The number of unchanged lines shown around each change in a diff.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+fn display_file_diff(file: &Path, file_content: &str, formatted_content: &str) -> Result<()> {
+    print!("{}", unified_diff(file, file_content, formatted_content)?);
+    Ok(())
+}
+
+/// Renders the changes formatting makes to `file` as a unified diff.
+fn unified_diff(file: &Path, file_content: &str, formatted_content: &str) -> Result<String> {
     let changeset = diff_lines(file_content, formatted_content);
-    let mut count_of_updates = 0;
+    // Every line of both versions, marked as unchanged (' '), removed ('-') or inserted ('+').
+    let mut lines: Vec<(char, &str)> = Vec::new();
     for diff in changeset.diff() {
-        // max 100 updates
-        if count_of_updates >= 100 {
-            break;
-        }
         match diff {
-            DiffOp::Equal(old) => {
-                for o in old {
-                    info!("{}", o)
-                }
-            }
-            DiffOp::Insert(new) => {
-                count_of_updates += 1;
-                for n in new {
-                    println_green(&format!("+{}", n));
-                }
-            }
-            DiffOp::Remove(old) => {
-                count_of_updates += 1;
-                for o in old {
-                    println_red(&format!("-{}", o));
-                }
-            }
+            DiffOp::Equal(old) => lines.extend(old.iter().map(|o| (' ', *o))),
+            DiffOp::Insert(new) => lines.extend(new.iter().map(|n| ('+', *n))),
+            DiffOp::Remove(old) => lines.extend(old.iter().map(|o| ('-', *o))),
             DiffOp::Replace(old, new) => {
-                count_of_updates += 1;
-                for o in old {
-                    println_red(&format!("-{}", o));
-                }
-                for n in new {
-                    println_green(&format!("+{}", n));
-                }
+                lines.extend(old.iter().map(|o| ('-', *o)));
+                lines.extend(new.iter().map(|n| ('+', *n)));
             }
         }
     }
-    Ok(())
+
+    // Changes closer together than twice the context are shown in the same hunk.
+    let mut hunks: Vec<Range<usize>> = Vec::new();
+    for (ix, _) in lines.iter().enumerate().filter(|(_, (tag, _))| *tag != ' ') {
+        let start = ix.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (ix + 1 + DIFF_CONTEXT_LINES).min(lines.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.end => hunk.end = end,
+            _ => hunks.push(start..end),
+        }
+    }
+
+    let mut diff = String::new();
+    if hunks.is_empty() {
+        return Ok(diff);
+    }
+    writeln!(diff, "--- {}", file.display())?;
+    writeln!(diff, "+++ {}", file.display())?;
+    let count = |lines: &[(char, &str)], skipped: char| {
+        lines.iter().filter(|(tag, _)| *tag != skipped).count()
+    };
+    for hunk in hunks {
+        let before = &lines[..hunk.start];
+        let lines = &lines[hunk];
+        writeln!(
+            diff,
+            "@@ -{} +{} @@",
+            hunk_range(count(before, '+'), count(lines, '+')),
+            hunk_range(count(before, '-'), count(lines, '-'))
+        )?;
+        for (tag, line) in lines {
+            writeln!(diff, "{}{}", tag, line)?;
+        }
+    }
+    Ok(diff)
+}
+
+/// Formats the line range of a hunk, given the number of lines before it and in it.
+fn hunk_range(before: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", before),
+        _ => format!("{},{}", before + 1, len),
+    }
 }
 
 fn format_file(file: &Path, formatted_content: &str) -> Result<()> {
@@ -171,8 +201,36 @@ fn format_file(file: &Path, formatted_content: &str) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::taplo_fmt;
-    use std::default::Default;
+    use super::{taplo_fmt, unified_diff};
+    use std::{default::Default, path::Path};
+
+    #[test]
+    fn test_unified_diff() {
+        let file_content =
+            "contract;\n\nfn a(){}\nfn b() {}\n// 1\n// 2\n// 3\n// 4\n// 5\n// 6\n// 7\nfn c(){}";
+        let formatted_content = "contract;\n\nfn a() {}\nfn b() {}\n// 1\n// 2\n// 3\n// 4\n// 5\n// 6\n// 7\nfn c() {}";
+        let correct_diff = r#"--- src/main.sw
++++ src/main.sw
+@@ -1,6 +1,6 @@
+ contract;
+ 
+-fn a(){}
++fn a() {}
+ fn b() {}
+ // 1
+ // 2
+@@ -9,4 +9,4 @@
+ // 5
+ // 6
+ // 7
+-fn c(){}
++fn c() {}
+"#;
+        let diff = unified_diff(Path::new("src/main.sw"), file_content, formatted_content).unwrap();
+        assert_eq!(diff, correct_diff);
+        let diff = unified_diff(Path::new("src/main.sw"), file_content, file_content).unwrap();
+        assert!(diff.is_empty());
+    }
 
     #[test]
     fn test_forc_indentation() {
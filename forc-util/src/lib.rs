@@ -5,19 +5,32 @@ use annotate_snippets::{
     snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation},
 };
 use anyhow::{bail, Result};
+use message_format::{print_json_diagnostic, print_json_summary};
 use std::env;
 use std::ffi::OsStr;
-use std::io::Write;
+use std::io::{self, Write};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::str;
-use sway_core::{error::LineCol, CompileError, CompileWarning, TreeType};
-use sway_types::Spanned;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use sway_core::{
+    diagnostic::{Diagnostic, Severity},
+    error::LineCol,
+    CompileAstResult, CompileError, CompileWarning, TreeType,
+};
+use sway_types::{Position, Span};
 use sway_utils::constants;
 use termcolor::{self, Color as TermColor, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use tracing_subscriber::filter::EnvFilter;
 
+mod message_format;
 pub mod restricted;
 
+pub use message_format::MessageFormat;
+
 pub const DEFAULT_OUTPUT_DIRECTORY: &str = "out";
 
 /// Continually go up in the file tree until a specified file is found.
@@ -132,16 +145,17 @@ pub fn git_checkouts_directory() -> PathBuf {
 
 pub fn print_on_success(
     silent_mode: bool,
+    message_format: MessageFormat,
     proj_name: &str,
     warnings: &[CompileWarning],
     tree_type: &TreeType,
 ) {
-    let type_str = match &tree_type {
-        TreeType::Script {} => "script",
-        TreeType::Contract {} => "contract",
-        TreeType::Predicate {} => "predicate",
-        TreeType::Library { .. } => "library",
-    };
+    let type_str = program_kind(tree_type);
+
+    if message_format == MessageFormat::Json {
+        print_json(silent_mode, proj_name, Some(type_str), warnings, &[]);
+        return;
+    }
 
     if !silent_mode {
         warnings.iter().for_each(format_warning);
@@ -164,7 +178,17 @@ pub fn print_on_success(
     }
 }
 
-pub fn print_on_success_library(silent_mode: bool, proj_name: &str, warnings: &[CompileWarning]) {
+pub fn print_on_success_library(
+    silent_mode: bool,
+    message_format: MessageFormat,
+    proj_name: &str,
+    warnings: &[CompileWarning],
+) {
+    if message_format == MessageFormat::Json {
+        print_json(silent_mode, proj_name, Some("library"), warnings, &[]);
+        return;
+    }
+
     if !silent_mode {
         warnings.iter().for_each(format_warning);
     }
@@ -185,9 +209,20 @@ pub fn print_on_success_library(silent_mode: bool, proj_name: &str, warnings: &[
     }
 }
 
-pub fn print_on_failure(silent_mode: bool, warnings: &[CompileWarning], errors: &[CompileError]) {
+pub fn print_on_failure(
+    silent_mode: bool,
+    message_format: MessageFormat,
+    proj_name: &str,
+    warnings: &[CompileWarning],
+    errors: &[CompileError],
+) {
     let e_len = errors.len();
 
+    if message_format == MessageFormat::Json {
+        print_json(silent_mode, proj_name, None, warnings, errors);
+        return;
+    }
+
     if !silent_mode {
         warnings.iter().for_each(format_warning);
         errors.iter().for_each(format_err);
@@ -198,6 +233,42 @@ pub fn print_on_failure(silent_mode: bool, warnings: &[CompileWarning], errors:
         e_len,
         if e_len > 1 { "errors" } else { "error" }
     ));
+
+    if !silent_mode && e_len > 0 {
+        tracing::info!("For more information about an error, try `forc explain <code>`.");
+    }
+}
+
+/// Prints the diagnostics and the summary of a package checked by `forc check` as JSON, see
+/// [MessageFormat::Json].
+pub fn print_check_result_json(silent_mode: bool, proj_name: &str, ast_res: &CompileAstResult) {
+    match ast_res {
+        CompileAstResult::Success {
+            typed_program,
+            warnings,
+        } => {
+            let tree_type = typed_program.kind.tree_type();
+            print_json(
+                silent_mode,
+                proj_name,
+                Some(program_kind(&tree_type)),
+                warnings,
+                &[],
+            );
+        }
+        CompileAstResult::Failure { warnings, errors } => {
+            print_json(silent_mode, proj_name, None, warnings, errors);
+        }
+    }
+}
+
+fn program_kind(tree_type: &TreeType) -> &'static str {
+    match tree_type {
+        TreeType::Script {} => "script",
+        TreeType::Contract {} => "contract",
+        TreeType::Predicate {} => "predicate",
+        TreeType::Library { .. } => "library",
+    }
 }
 
 pub fn println_red(txt: &str) {
@@ -262,122 +333,159 @@ fn println_with_color(txt: &str, color: TermColor, stream: StandardStream) {
     stream.reset().expect("internal printing error");
 }
 
-fn format_err(err: &sway_core::CompileError) {
-    let span = err.span();
-    let input = span.input();
-    let path = err.path();
-    let path_str = path.as_ref().map(|path| path.to_string_lossy());
-    let mut start_pos = span.start();
-    let mut end_pos = span.end();
-
-    let friendly_str = maybe_uwuify(&format!("{}", err));
-    let (snippet_title, snippet_slices) = if start_pos < end_pos {
-        let title = Some(Annotation {
-            label: None,
-            id: None,
-            annotation_type: AnnotationType::Error,
-        });
-
-        let (mut start, end) = err.line_col();
-        let input = construct_window(&mut start, end, &mut start_pos, &mut end_pos, input);
-        let slices = vec![Slice {
-            source: input,
-            line_start: start.line,
-            origin: path_str.as_deref(),
-            fold: false,
-            annotations: vec![SourceAnnotation {
-                label: &friendly_str,
-                annotation_type: AnnotationType::Error,
-                range: (start_pos, end_pos),
-            }],
-        }];
-
-        (title, slices)
-    } else {
-        (
-            Some(Annotation {
-                label: Some(friendly_str.as_str()),
-                id: None,
-                annotation_type: AnnotationType::Error,
-            }),
-            Vec::new(),
-        )
-    };
+fn print_json(
+    silent_mode: bool,
+    proj_name: &str,
+    program_kind: Option<&str>,
+    warnings: &[CompileWarning],
+    errors: &[CompileError],
+) {
+    if !silent_mode {
+        let diagnostics = warnings
+            .iter()
+            .map(CompileWarning::diagnostic)
+            .chain(errors.iter().map(CompileError::diagnostic));
+        for diagnostic in diagnostics {
+            print_json_diagnostic(&diagnostic);
+        }
+    }
+    print_json_summary(proj_name, program_kind, warnings.len(), errors.len());
+}
 
-    let snippet = Snippet {
-        title: snippet_title,
-        footer: vec![],
-        slices: snippet_slices,
-        opt: FormatOptions {
-            color: true,
-            ..Default::default()
-        },
-    };
-    tracing::error!("{}\n____\n", DisplayList::from(snippet))
+fn format_err(err: &sway_core::CompileError) {
+    tracing::error!("{}\n____\n", format_diagnostic(&err.diagnostic()))
 }
 
-fn format_warning(err: &sway_core::CompileWarning) {
-    let span = err.span();
-    let input = span.input();
-    let path = err.path();
-    let path_str = path.as_ref().map(|path| path.to_string_lossy());
+fn format_warning(warning: &sway_core::CompileWarning) {
+    tracing::warn!("{}\n____\n", format_diagnostic(&warning.diagnostic()))
+}
 
-    let friendly_str = maybe_uwuify(&err.to_friendly_warning_string());
-    let mut start_pos = span.start();
-    let mut end_pos = span.end();
-    if start_pos == end_pos {
-        // if start/pos are same we will not get that arrow pointing to code, so we add +1.
-        end_pos += 1;
+/// Renders a diagnostic: its code, the source around its labels with the labelled code underlined,
+/// and its notes and help.
+fn format_diagnostic(diagnostic: &Diagnostic) -> String {
+    let annotation_type = match diagnostic.severity {
+        Severity::Error => AnnotationType::Error,
+        Severity::Warning => AnnotationType::Warning,
+    };
+    let message = maybe_uwuify(&diagnostic.message);
+    let notes: Vec<String> = diagnostic.notes.iter().map(|n| maybe_uwuify(n)).collect();
+    let help: Vec<String> = diagnostic.help.iter().map(|h| maybe_uwuify(h)).collect();
+
+    let primary_shown = label_range(&diagnostic.primary.span).is_some();
+    // The labels to show, grouped by the source they point into. The primary label comes first.
+    let mut groups: Vec<Vec<(Range<usize>, AnnotationType, String)>> = Vec::new();
+    let mut group_spans: Vec<&Span> = Vec::new();
+    let labels = std::iter::once((&diagnostic.primary, annotation_type)).chain(
+        diagnostic
+            .secondary
+            .iter()
+            .map(|label| (label, AnnotationType::Info)),
+    );
+    for (label, label_type) in labels {
+        let range = match label_range(&label.span) {
+            Some(range) => range,
+            None => continue,
+        };
+        let entry = (range, label_type, maybe_uwuify(&label.message));
+        match group_spans
+            .iter()
+            .position(|span| Arc::ptr_eq(span.src(), label.span.src()))
+        {
+            Some(ix) => groups[ix].push(entry),
+            None => {
+                group_spans.push(&label.span);
+                groups.push(vec![entry]);
+            }
+        }
     }
 
-    let (mut start, end) = err.line_col();
-    let input = construct_window(&mut start, end, &mut start_pos, &mut end_pos, input);
+    let origins: Vec<_> = group_spans.iter().map(|span| span.path_str()).collect();
+    let slices: Vec<Slice> = groups
+        .iter()
+        .zip(&group_spans)
+        .zip(&origins)
+        .map(|((labels, span), origin)| {
+            let input = span.input();
+            let start = labels.iter().map(|(range, ..)| range.start).min().unwrap();
+            let end = labels.iter().map(|(range, ..)| range.end).max().unwrap();
+            let (window, window_start, line_start) = construct_window(span.src(), start, end);
+            // The library expects ranges in chars relative to the window, not in bytes.
+            let char_ix = |ix: usize| input[window_start..ix.max(window_start)].chars().count();
+            Slice {
+                source: window,
+                line_start,
+                origin: origin.as_deref(),
+                fold: false,
+                annotations: labels
+                    .iter()
+                    .map(|(range, label_type, label)| SourceAnnotation {
+                        label,
+                        annotation_type: *label_type,
+                        range: (char_ix(range.start), char_ix(range.end)),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let footer = notes
+        .iter()
+        .map(|note| (note, AnnotationType::Note))
+        .chain(help.iter().map(|help| (help, AnnotationType::Help)))
+        .map(|(label, annotation_type)| Annotation {
+            id: None,
+            label: Some(label.as_str()),
+            annotation_type,
+        })
+        .collect();
+
     let snippet = Snippet {
         title: Some(Annotation {
-            label: None,
-            id: None,
-            annotation_type: AnnotationType::Warning,
+            id: Some(diagnostic.code),
+            // The message is shown next to the code, unless it is the label of the primary span.
+            label: (diagnostic.primary.message != diagnostic.message || !primary_shown)
+                .then(|| message.as_str()),
+            annotation_type,
         }),
-        footer: vec![],
-        slices: vec![Slice {
-            source: input,
-            line_start: start.line,
-            origin: path_str.as_deref(),
-            fold: false,
-            annotations: vec![SourceAnnotation {
-                label: &friendly_str,
-                annotation_type: AnnotationType::Warning,
-                range: (start_pos, end_pos),
-            }],
-        }],
+        footer,
+        slices,
         opt: FormatOptions {
             color: true,
             ..Default::default()
         },
     };
-    tracing::warn!("{}\n____\n", DisplayList::from(snippet))
+    DisplayList::from(snippet).to_string()
+}
+
+/// The byte range of the input to underline for the given span, or `None` if there is nothing to
+/// point at. An empty span is widened to the character following it, so that it gets an arrow.
+fn label_range(span: &Span) -> Option<Range<usize>> {
+    let (start, mut end) = (span.start(), span.end());
+    if start == end {
+        end += span.input()[end..].chars().next()?.len_utf8();
+    }
+    Some(start..end)
 }
 
-/// Given a start and an end position and an input, determine how much of a window to show in the
-/// error.
-/// Mutates the start and end indexes to be in line with the new slice length.
+/// Given the byte range of the highlighted code within the input, determine how much of a window
+/// to show around it. Returns the window, the byte offset it starts at in the input, and the
+/// number of the line it starts on.
 ///
 /// The library we use doesn't handle auto-windowing and line numbers, so we must manually
-/// calculate the line numbers and match them up with the input window. It is a bit fiddly.t
-fn construct_window<'a>(
-    start: &mut LineCol,
-    end: LineCol,
-    start_ix: &mut usize,
-    end_ix: &mut usize,
-    input: &'a str,
-) -> &'a str {
+/// calculate the line numbers and match them up with the input window. It is a bit fiddly.
+fn construct_window(input: &Arc<str>, start_ix: usize, end_ix: usize) -> (&str, usize, usize) {
     // how many lines to prepend or append to the highlighted region in the window
     const NUM_LINES_BUFFER: usize = 2;
 
-    let total_lines_in_input = input.chars().filter(|x| *x == '\n').count();
+    let start: LineCol = Position::new(input.clone(), start_ix)
+        .map(|position| position.line_col())
+        .unwrap_or((1, 1))
+        .into();
+    let end: LineCol = Position::new(input.clone(), end_ix)
+        .map(|position| position.line_col())
+        .unwrap_or((1, 1))
+        .into();
     debug_assert!(end.line >= start.line);
-    let total_lines_of_highlight = end.line - start.line;
-    debug_assert!(total_lines_in_input >= total_lines_of_highlight);
 
     let mut current_line = 0;
     let mut lines_to_start_of_snippet = 0;
@@ -406,22 +514,25 @@ fn construct_window<'a>(
     let calculated_start_ix = calculated_start_ix.unwrap_or(0);
     let calculated_end_ix = calculated_end_ix.unwrap_or(input.len());
 
-    let start_ix_bytes = *start_ix - std::cmp::min(calculated_start_ix, *start_ix);
-    let end_ix_bytes = *end_ix - std::cmp::min(calculated_start_ix, *end_ix);
-    // We want the start_ix and end_ix in terms of chars and not bytes, so translate.
-    *start_ix = input[calculated_start_ix..(calculated_start_ix + start_ix_bytes)]
-        .chars()
-        .count();
-    *end_ix = input[calculated_start_ix..(calculated_start_ix + end_ix_bytes)]
-        .chars()
-        .count();
-
-    start.line = lines_to_start_of_snippet;
-    &input[calculated_start_ix..calculated_end_ix]
+    (
+        &input[calculated_start_ix..calculated_end_ix],
+        calculated_start_ix,
+        lines_to_start_of_snippet,
+    )
 }
 
 const LOG_FILTER: &str = "RUST_LOG";
 
+/// Whether the tracing subscriber writes to stderr rather than stdout, see [log_to_stderr].
+static LOG_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Redirects the output of the subscriber set up by [init_tracing_subscriber] to stderr.
+///
+/// This is used with [MessageFormat::Json], so that stdout only holds JSON objects.
+pub fn log_to_stderr() {
+    LOG_TO_STDERR.store(true, Ordering::Relaxed);
+}
+
 /// A subscriber built from default `tracing_subscriber::fmt::SubscriberBuilder` such that it would match directly using `println!` throughout the repo.
 ///
 /// `RUST_LOG` environment variable can be used to set different minimum level for the subscriber, default is `INFO`.
@@ -439,6 +550,13 @@ pub fn init_tracing_subscriber() {
         .with_line_number(false)
         .without_time()
         .with_target(false)
+        .with_writer(|| -> Box<dyn Write> {
+            if LOG_TO_STDERR.load(Ordering::Relaxed) {
+                Box::new(io::stderr())
+            } else {
+                Box::new(io::stdout())
+            }
+        })
         .init();
 }
 
@@ -0,0 +1,225 @@
+//! Machine-readable output of diagnostics and build summaries, selected with
+//! `--message-format json`.
+//!
+//! Every message is a JSON object printed on a line of its own. Objects with `"type":
+//! "diagnostic"` describe an error or warning, and an object with `"type": "summary"` is printed
+//! once a package has been compiled, whether or not compilation succeeded.
+
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+use sway_core::diagnostic::{Diagnostic, Severity};
+use sway_types::Span;
+
+/// The format in which diagnostics and build summaries are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Rendered with source snippets, for people.
+    Human,
+    /// One JSON object per message, for tools.
+    Json,
+}
+
+impl Default for MessageFormat {
+    fn default() -> Self {
+        MessageFormat::Human
+    }
+}
+
+impl FromStr for MessageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(format!(
+                "invalid message format `{s}`, expected `human` or `json`"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for MessageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageFormat::Human => write!(f, "human"),
+            MessageFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum Message<'a> {
+    Diagnostic(JsonDiagnostic),
+    Summary {
+        package: &'a str,
+        /// The kind of program, unless compilation failed before it was known.
+        program_kind: Option<&'a str>,
+        success: bool,
+        warnings: usize,
+        errors: usize,
+    },
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic {
+    severity: &'static str,
+    code: &'static str,
+    message: String,
+    /// The primary span comes first.
+    spans: Vec<JsonSpan>,
+    notes: Vec<String>,
+    help: Vec<String>,
+    suggestions: Vec<JsonSuggestion>,
+}
+
+#[derive(Serialize)]
+struct JsonSpan {
+    #[serde(flatten)]
+    location: JsonLocation,
+    label: String,
+    primary: bool,
+}
+
+#[derive(Serialize)]
+struct JsonSuggestion {
+    message: String,
+    #[serde(flatten)]
+    location: JsonLocation,
+    replacement: String,
+}
+
+/// A range of a source file. Lines and columns start at 1, and the end is exclusive.
+#[derive(Serialize)]
+struct JsonLocation {
+    file: Option<String>,
+    start: JsonPosition,
+    end: JsonPosition,
+}
+
+#[derive(Serialize)]
+struct JsonPosition {
+    line: usize,
+    column: usize,
+}
+
+impl From<&Span> for JsonLocation {
+    fn from(span: &Span) -> Self {
+        let (start, end) = span.split();
+        let position = |(line, column): (usize, usize)| JsonPosition { line, column };
+        JsonLocation {
+            file: span.path_str().map(|path| path.into_owned()),
+            start: position(start.line_col()),
+            end: position(end.line_col()),
+        }
+    }
+}
+
+impl From<&Diagnostic> for JsonDiagnostic {
+    fn from(diagnostic: &Diagnostic) -> Self {
+        let primary = std::iter::once((&diagnostic.primary, true));
+        let secondary = diagnostic.secondary.iter().map(|label| (label, false));
+        JsonDiagnostic {
+            severity: match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+            code: diagnostic.code,
+            message: diagnostic.message.clone(),
+            spans: primary
+                .chain(secondary)
+                .map(|(label, primary)| JsonSpan {
+                    location: (&label.span).into(),
+                    label: label.message.clone(),
+                    primary,
+                })
+                .collect(),
+            notes: diagnostic.notes.clone(),
+            help: diagnostic.help.clone(),
+            suggestions: diagnostic
+                .suggestions
+                .iter()
+                .map(|suggestion| JsonSuggestion {
+                    message: suggestion.message.clone(),
+                    location: (&suggestion.span).into(),
+                    replacement: suggestion.replacement.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn print_message(message: &Message) {
+    println!(
+        "{}",
+        serde_json::to_string(message).expect("JSON serialization failed")
+    );
+}
+
+pub(crate) fn print_json_diagnostic(diagnostic: &Diagnostic) {
+    print_message(&Message::Diagnostic(diagnostic.into()));
+}
+
+pub(crate) fn print_json_summary(
+    package: &str,
+    program_kind: Option<&str>,
+    warnings: usize,
+    errors: usize,
+) {
+    print_message(&Message::Summary {
+        package,
+        program_kind,
+        success: errors == 0,
+        warnings,
+        errors,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonDiagnostic, Message};
+    use std::sync::Arc;
+    use sway_core::diagnostic::{Diagnostic, Label, Severity};
+    use sway_types::Span;
+
+    #[test]
+    fn diagnostic_to_json() {
+        let src: Arc<str> = Arc::from("fn main() {\n    x = 1;\n}\n");
+        let span = Span::new(src, 16, 17, Some(Arc::new("main.sw".into()))).unwrap();
+        let diagnostic = Diagnostic {
+            severity: Severity::Error,
+            code: "E0001",
+            message: "Variable \"x\" does not exist in this scope.".into(),
+            primary: Label {
+                span,
+                message: "not found".into(),
+            },
+            secondary: vec![],
+            notes: vec![],
+            help: vec!["declare it with `let`".into()],
+            suggestions: vec![],
+        };
+        let json =
+            serde_json::to_value(&Message::Diagnostic(JsonDiagnostic::from(&diagnostic))).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "diagnostic",
+                "severity": "error",
+                "code": "E0001",
+                "message": "Variable \"x\" does not exist in this scope.",
+                "spans": [{
+                    "file": "main.sw",
+                    "start": { "line": 2, "column": 5 },
+                    "end": { "line": 2, "column": 6 },
+                    "label": "not found",
+                    "primary": true,
+                }],
+                "notes": [],
+                "help": ["declare it with `let`"],
+                "suggestions": [],
+            })
+        );
+    }
+}
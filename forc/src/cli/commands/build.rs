@@ -1,6 +1,7 @@
 use crate::ops::forc_build;
 use anyhow::Result;
 use clap::Parser;
+use forc_util::MessageFormat;
 
 /// Compile the current or target project.
 ///
@@ -51,6 +52,18 @@ pub struct Command {
     /// Silent mode. Don't output any warnings or errors to the command line.
     #[clap(long = "silent", short = 's')]
     pub silent_mode: bool,
+    /// The format of diagnostics, either `human` or `json`.
+    ///
+    /// With `json`, each error and warning is printed to stdout as a JSON object on a line of its
+    /// own, followed by a summary object for each compiled package. Any other output goes to
+    /// stderr.
+    #[clap(long, default_value = "human")]
+    pub message_format: MessageFormat,
+    /// Report warnings as errors, unless their lint is allowed or warned about explicitly.
+    ///
+    /// Only the warnings of the package itself are affected, not those of its dependencies.
+    #[clap(long)]
+    pub deny_warnings: bool,
     /// The directory in which the sway compiler output artifacts are placed.
     ///
     /// By default, this is `<project-root>/out`.
@@ -80,6 +93,14 @@ pub struct Command {
     /// Output the time elapsed over each part of the compilation process.
     #[clap(long)]
     pub time_phases: bool,
+    /// Print the number of bytes and the gas used by each function of the program.
+    ///
+    /// The report is also written in JSON format to `<project-name>-size-report.json` in the
+    /// output directory. The gas of a function is the base cost of executing each of its
+    /// instructions once. Instructions which are also charged for the memory they use, such as
+    /// `MCP`, only count their base cost, so the gas is a lower bound even without loops.
+    #[clap(long)]
+    pub size_report: bool,
 }
 
 pub(crate) fn exec(command: Command) -> Result<()> {
@@ -1,6 +1,8 @@
 use crate::ops::forc_check;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Parser;
+use forc_util::MessageFormat;
+use sway_core::CompileAstResult;
 
 /// Check the current or target project and all of its dependencies for errors.
 ///
@@ -18,6 +20,13 @@ pub struct Command {
     /// Silent mode. Don't output any warnings or errors to the command line.
     #[clap(long = "silent", short = 's')]
     pub silent_mode: bool,
+    /// The format of diagnostics, either `human` or `json`.
+    ///
+    /// With `json`, each error and warning is printed to stdout as a JSON object on a line of its
+    /// own, followed by a summary object for each compiled package. Any other output goes to
+    /// stderr.
+    #[clap(long, default_value = "human")]
+    pub message_format: MessageFormat,
     /// Requires that the Forc.lock file is up-to-date. If the lock file is missing, or it
     /// needs to be updated, Forc will exit with an error
     #[clap(long)]
@@ -25,6 +34,8 @@ pub struct Command {
 }
 
 pub(crate) fn exec(command: Command) -> Result<()> {
-    forc_check::check(command)?;
-    Ok(())
+    match forc_check::check(command)? {
+        CompileAstResult::Success { .. } => Ok(()),
+        CompileAstResult::Failure { .. } => bail!("Failed to check the project"),
+    }
 }
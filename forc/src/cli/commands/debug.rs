@@ -0,0 +1,49 @@
+use crate::ops::forc_debug;
+use anyhow::Result;
+use clap::Parser;
+
+/// Debug a script or contract project in an in-process Fuel VM.
+///
+/// The project is built and run step by step, stopping at breakpoints given as `<file>:<line>`.
+/// Unless a file of commands is given with `--commands`, the debugger is interactive. Type `help`
+/// at the prompt for the list of commands.
+#[derive(Debug, Default, Parser)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+
+    /// Path to a script project which calls the contract, when debugging a contract project.
+    #[clap(long)]
+    pub caller: Option<String>,
+
+    /// Hex string of data to input to the script.
+    #[clap(short, long)]
+    pub data: Option<String>,
+
+    /// Set a breakpoint at `<file>:<line>` before the program starts. May be repeated.
+    #[clap(short, long = "break")]
+    pub breakpoints: Vec<String>,
+
+    /// Run the debugger commands in this file, one per line, rather than prompting for them.
+    #[clap(long)]
+    pub commands: Option<String>,
+
+    /// Set the transaction gas limit. Defaults to the maximum gas limit.
+    #[clap(long)]
+    pub gas_limit: Option<u64>,
+
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+
+    /// Requires that the Forc.lock file is up-to-date. If the lock file is missing, or it
+    /// needs to be updated, Forc will exit with an error
+    #[clap(long)]
+    pub locked: bool,
+}
+
+pub(crate) fn exec(command: Command) -> Result<()> {
+    forc_debug::debug(command)
+}
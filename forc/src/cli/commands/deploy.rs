@@ -1,6 +1,7 @@
 use crate::ops::forc_deploy;
 use anyhow::{bail, Result};
 use clap::Parser;
+use forc_util::MessageFormat;
 
 /// Deploy contract project.
 /// Crafts a contract deployment transaction then sends it to a running node.
@@ -39,6 +40,18 @@ pub struct Command {
     /// Silent mode. Don't output any warnings or errors to the command line.
     #[clap(long = "silent", short = 's')]
     pub silent_mode: bool,
+    /// The format of diagnostics, either `human` or `json`.
+    ///
+    /// With `json`, each error and warning is printed to stdout as a JSON object on a line of its
+    /// own, followed by a summary object for each compiled package. Any other output goes to
+    /// stderr.
+    #[clap(long, default_value = "human")]
+    pub message_format: MessageFormat,
+    /// Report warnings as errors, unless their lint is allowed or warned about explicitly.
+    ///
+    /// Only the warnings of the package itself are affected, not those of its dependencies.
+    #[clap(long)]
+    pub deny_warnings: bool,
     /// The directory in which the sway compiler output artifacts are placed.
     ///
     /// By default, this is `<project-root>/out`.
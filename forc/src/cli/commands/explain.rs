@@ -0,0 +1,21 @@
+use anyhow::{bail, Result};
+use clap::Parser;
+
+/// Print a detailed explanation of an error or warning code.
+///
+/// The codes are shown in the output of the compiler, e.g. `error[E0022]`.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The code of the error or warning, e.g. `E0022` or `W0012`.
+    pub code: String,
+}
+
+pub fn exec(command: Command) -> Result<()> {
+    match sway_core::diagnostic::explain(&command.code) {
+        Some(explanation) => {
+            print!("{}", explanation);
+            Ok(())
+        }
+        None => bail!("`{}` is not a valid error or warning code", command.code),
+    }
+}
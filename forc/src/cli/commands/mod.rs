@@ -3,7 +3,9 @@ pub mod build;
 pub mod check;
 pub mod clean;
 pub mod completions;
+pub mod debug;
 pub mod deploy;
+pub mod explain;
 pub mod init;
 pub mod json_abi;
 pub mod new;
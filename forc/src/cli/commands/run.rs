@@ -1,6 +1,7 @@
 use crate::ops::forc_run;
 use anyhow::{bail, Result};
 use clap::Parser;
+use forc_util::MessageFormat;
 
 /// Run script project.
 /// Crafts a script transaction then sends it to a running node.
@@ -64,6 +65,20 @@ pub struct Command {
     #[clap(long = "silent", short = 's')]
     pub silent_mode: bool,
 
+    /// The format of diagnostics, either `human` or `json`.
+    ///
+    /// With `json`, each error and warning is printed to stdout as a JSON object on a line of its
+    /// own, followed by a summary object for each compiled package. Any other output goes to
+    /// stderr.
+    #[clap(long, default_value = "human")]
+    pub message_format: MessageFormat,
+
+    /// Report warnings as errors, unless their lint is allowed or warned about explicitly.
+    ///
+    /// Only the warnings of the package itself are affected, not those of its dependencies.
+    #[clap(long)]
+    pub deny_warnings: bool,
+
     /// Output the time elapsed over each part of the compilation process.
     #[clap(long)]
     pub time_phases: bool,
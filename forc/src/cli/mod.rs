@@ -1,6 +1,6 @@
 use self::commands::{
-    addr2line, build, check, clean, completions, deploy, init, json_abi, new, parse_bytecode,
-    plugins, run, template, test, update,
+    addr2line, build, check, clean, completions, debug, deploy, explain, init, json_abi, new,
+    parse_bytecode, plugins, run, template, test, update,
 };
 use addr2line::Command as Addr2LineCommand;
 use anyhow::{anyhow, Result};
@@ -9,7 +9,9 @@ pub use check::Command as CheckCommand;
 use clap::Parser;
 pub use clean::Command as CleanCommand;
 pub use completions::Command as CompletionsCommand;
+pub use debug::Command as DebugCommand;
 pub use deploy::Command as DeployCommand;
+pub use explain::Command as ExplainCommand;
 pub use init::Command as InitCommand;
 pub use json_abi::Command as JsonAbiCommand;
 pub use new::Command as NewCommand;
@@ -40,7 +42,9 @@ enum Forc {
     Check(CheckCommand),
     Clean(CleanCommand),
     Completions(CompletionsCommand),
+    Debug(DebugCommand),
     Deploy(DeployCommand),
+    Explain(ExplainCommand),
     New(NewCommand),
     Init(InitCommand),
     ParseBytecode(ParseBytecodeCommand),
@@ -71,7 +75,9 @@ pub async fn run_cli() -> Result<()> {
         Forc::Check(command) => check::exec(command),
         Forc::Clean(command) => clean::exec(command),
         Forc::Completions(command) => completions::exec(command),
+        Forc::Debug(command) => debug::exec(command),
         Forc::Deploy(command) => deploy::exec(command).await,
+        Forc::Explain(command) => explain::exec(command),
         Forc::Init(command) => init::exec(command),
         Forc::New(command) => new::exec(command),
         Forc::ParseBytecode(command) => parse_bytecode::exec(command),
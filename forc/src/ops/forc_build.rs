@@ -4,13 +4,13 @@ use crate::{
 };
 use anyhow::Result;
 use forc_pkg::{self as pkg, ManifestFile};
-use forc_util::default_output_directory;
+use forc_util::{default_output_directory, log_to_stderr, MessageFormat};
 use fuel_tx::Contract;
 use std::{
     fs::{self, File},
     path::PathBuf,
 };
-use sway_core::TreeType;
+use sway_core::{size_report::SizeReport, TreeType};
 use tracing::{info, warn};
 
 pub fn build(command: BuildCommand) -> Result<pkg::Compiled> {
@@ -24,6 +24,8 @@ pub fn build(command: BuildCommand) -> Result<pkg::Compiled> {
         print_ir,
         offline_mode: offline,
         silent_mode,
+        message_format,
+        deny_warnings,
         output_directory,
         minify_json_abi,
         minify_json_storage_slots,
@@ -31,8 +33,14 @@ pub fn build(command: BuildCommand) -> Result<pkg::Compiled> {
         build_profile,
         release,
         time_phases,
+        size_report,
     } = command;
 
+    // Leave stdout to the JSON messages.
+    if message_format == MessageFormat::Json {
+        log_to_stderr();
+    }
+
     let key_debug: String = "debug".to_string();
     let key_release: String = "release".to_string();
 
@@ -78,6 +86,8 @@ pub fn build(command: BuildCommand) -> Result<pkg::Compiled> {
     profile.print_finalized_asm |= print_finalized_asm;
     profile.print_intermediate_asm |= print_intermediate_asm;
     profile.silent |= silent_mode;
+    profile.message_format = message_format;
+    profile.deny_warnings |= deny_warnings;
     profile.time_phases |= time_phases;
 
     // Build it!
@@ -119,6 +129,15 @@ pub fn build(command: BuildCommand) -> Result<pkg::Compiled> {
 
     info!("  Bytecode size is {} bytes.", compiled.bytecode.len());
 
+    if size_report && !matches!(compiled.tree_type, TreeType::Library { .. }) {
+        let report = SizeReport::new(&compiled.bytecode, &source_map);
+        info!("{}", report);
+        let report_stem = format!("{}-size-report", manifest.project.name);
+        let report_path = output_dir.join(&report_stem).with_extension("json");
+        let file = File::create(report_path)?;
+        serde_json::to_writer_pretty(&file, &report)?;
+    }
+
     // Additional ops required depending on the program type
     match compiled.tree_type {
         TreeType::Contract => {
@@ -1,6 +1,7 @@
 use crate::cli::CheckCommand;
 use anyhow::Result;
 use forc_pkg::{self as pkg, ManifestFile};
+use forc_util::{log_to_stderr, print_check_result_json, MessageFormat};
 use std::path::PathBuf;
 
 pub fn check(command: CheckCommand) -> Result<sway_core::CompileAstResult> {
@@ -8,9 +9,15 @@ pub fn check(command: CheckCommand) -> Result<sway_core::CompileAstResult> {
         path,
         offline_mode: offline,
         silent_mode,
+        message_format,
         locked,
     } = command;
 
+    // Leave stdout to the JSON messages.
+    if message_format == MessageFormat::Json {
+        log_to_stderr();
+    }
+
     let this_dir = if let Some(ref path) = path {
         PathBuf::from(path)
     } else {
@@ -19,6 +26,10 @@ pub fn check(command: CheckCommand) -> Result<sway_core::CompileAstResult> {
     let manifest = ManifestFile::from_dir(&this_dir)?;
     let plan = pkg::BuildPlan::from_lock_and_manifest(&manifest, locked, offline)?;
 
-    let (_, ast_res) = pkg::check(&plan, silent_mode)?;
+    let (parsed_res, ast_res) = pkg::check(&plan, silent_mode)?;
+    let ast_res = pkg::check_result(&parsed_res, ast_res);
+    if message_format == MessageFormat::Json {
+        print_check_result_json(silent_mode, &manifest.project.name, &ast_res);
+    }
     Ok(ast_res)
 }
@@ -0,0 +1,744 @@
+use crate::{
+    cli::DebugCommand,
+    ops::{
+        forc_deploy::create_contract_tx,
+        forc_run::{format_hex_data, get_tx_inputs_and_outputs},
+    },
+};
+use anyhow::{anyhow, bail, Result};
+use forc_pkg::{self as pkg, ManifestFile};
+use fuel_tx::{ConsensusParameters, ContractId, Transaction};
+use fuel_vm::prelude::*;
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+use sway_core::{
+    source_map::{LocationRange, SourceMap, VariableLocation},
+    TreeType,
+};
+use tracing::{error, info};
+
+const PROMPT: &str = "(forc-debug) ";
+
+const HELP: &str = "\
+Commands:
+  break, b <file>:<line>    stop when the code of a source line is about to run
+  continue, c               run until the next breakpoint or the end of the program
+  step, s                   run a single instruction
+  registers, regs [reg...]  print the given registers, or all of the non-zero ones
+  memory, mem <addr> [len]  print `len` bytes of memory from `addr`, 32 by default
+  backtrace, bt             print the call frames
+  locals                    print the local variables in scope
+  help, h                   print this message
+  quit, q                   stop debugging";
+
+/// The names of the reserved registers, by index.  The others are general purpose registers.
+const REGISTER_NAMES: [&str; 16] = [
+    "zero", "one", "of", "pc", "ssp", "sp", "fp", "hp", "err", "ggas", "cgas", "bal", "is", "ret",
+    "retl", "flag",
+];
+
+pub fn debug(command: DebugCommand) -> Result<()> {
+    let DebugCommand {
+        path,
+        caller,
+        data,
+        breakpoints,
+        commands,
+        gas_limit,
+        offline_mode,
+        locked,
+    } = command;
+
+    let dir = match path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let script_data = hex::decode(format_hex_data(&data.unwrap_or_default()))
+        .map_err(|err| anyhow!("invalid script data: {}", err))?;
+    let gas_limit = gas_limit.unwrap_or(ConsensusParameters::DEFAULT.max_gas_per_tx);
+    let mut debugger = debugger(
+        &dir,
+        caller.as_deref().map(Path::new),
+        script_data,
+        gas_limit,
+        offline_mode,
+        locked,
+    )?;
+
+    for breakpoint in breakpoints {
+        info!("{}", debugger.set_breakpoint(&breakpoint)?);
+    }
+
+    match commands {
+        // In scripted mode any failing command fails the whole run.
+        Some(commands) => {
+            let commands = fs::read_to_string(&commands)
+                .map_err(|err| anyhow!("{}: could not read: {}", commands, err))?;
+            for line in commands.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                info!("{}{}", PROMPT, line);
+                match debugger.exec(line)? {
+                    Some(output) => info!("{}", output),
+                    None => break,
+                }
+            }
+        }
+        None => {
+            let stdin = io::stdin();
+            let mut lines = stdin.lock().lines();
+            loop {
+                print!("{}", PROMPT);
+                io::stdout().flush()?;
+                let line = match lines.next() {
+                    Some(line) => line?,
+                    None => break,
+                };
+                match debugger.exec(line.trim()) {
+                    Ok(Some(output)) => info!("{}", output),
+                    Ok(None) => break,
+                    Err(err) => error!("{}", err),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Builds the script or the contract in `dir`, and a debugger for it.  A contract is deployed
+/// first, and then called by the script in `caller`.
+fn debugger(
+    dir: &Path,
+    caller: Option<&Path>,
+    script_data: Vec<u8>,
+    gas_limit: u64,
+    offline: bool,
+    locked: bool,
+) -> Result<Debugger> {
+    let (compiled, source_map, manifest) = build(dir, offline, locked)?;
+    match compiled.tree_type {
+        TreeType::Script => {
+            let tx = script_tx(compiled.bytecode, script_data, gas_limit, vec![]);
+            let script = Program::new(None, source_map, &manifest);
+            Ok(Debugger::new(vec![script], tx))
+        }
+        TreeType::Contract => {
+            let caller = caller.ok_or_else(|| {
+                anyhow!("A script which calls the contract must be given with `--caller`")
+            })?;
+            let (caller_compiled, caller_source_map, caller_manifest) =
+                build(caller, offline, locked)?;
+            caller_manifest.check_program_type(vec![TreeType::Script])?;
+
+            let (create_tx, contract_id) =
+                create_contract_tx(compiled.bytecode, vec![], vec![], compiled.storage_slots);
+            let tx = script_tx(
+                caller_compiled.bytecode,
+                script_data,
+                gas_limit,
+                vec![format!("{:#x}", contract_id)],
+            );
+            let script = Program::new(None, caller_source_map, &caller_manifest);
+            let contract = Program::new(Some(contract_id), source_map, &manifest);
+            let mut debugger = Debugger::new(vec![script, contract], tx);
+            debugger.deploy(create_tx)?;
+            Ok(debugger)
+        }
+        _ => bail!("Only scripts and contracts can be debugged"),
+    }
+}
+
+fn build(
+    dir: &Path,
+    offline: bool,
+    locked: bool,
+) -> Result<(pkg::Compiled, SourceMap, ManifestFile)> {
+    let manifest = ManifestFile::from_dir(dir)?;
+    let plan = pkg::BuildPlan::from_lock_and_manifest(&manifest, locked, offline)?;
+    let profile = manifest.build_profile("debug").cloned().unwrap_or_default();
+    let (compiled, source_map) = pkg::build(&plan, &profile)?;
+    Ok((compiled, source_map, manifest))
+}
+
+fn script_tx(
+    script: Vec<u8>,
+    script_data: Vec<u8>,
+    gas_limit: u64,
+    contracts: Vec<String>,
+) -> Transaction {
+    let (inputs, outputs) = get_tx_inputs_and_outputs(contracts);
+    Transaction::script(
+        0,
+        gas_limit,
+        0,
+        0,
+        script,
+        script_data,
+        inputs,
+        outputs,
+        vec![],
+    )
+}
+
+/// A program being debugged, along with what's needed to map it back to its source.
+struct Program {
+    /// The contract which the program was deployed as, or `None` for the script.
+    contract_id: Option<ContractId>,
+    source_map: SourceMap,
+    /// The project directory, which relative source paths are resolved against.
+    dir: PathBuf,
+}
+
+impl Program {
+    fn new(
+        contract_id: Option<ContractId>,
+        source_map: SourceMap,
+        manifest: &ManifestFile,
+    ) -> Self {
+        Program {
+            contract_id,
+            source_map,
+            dir: manifest.dir().to_owned(),
+        }
+    }
+
+    fn source_path(&self, path: &Path) -> PathBuf {
+        if path.is_relative() {
+            self.dir.join(path)
+        } else {
+            path.to_owned()
+        }
+    }
+
+    /// The source file and line of the instruction at byte offset `pc` into the program.
+    fn source_location(&self, pc: Word) -> Option<String> {
+        let (path, range) = self.source_map.addr_to_span(pc as usize / 4)?;
+        let path = self.source_path(&path);
+        let src = fs::read_to_string(&path).ok()?;
+        Some(format!("{}:{}", path.display(), line_of(&src, range.start)))
+    }
+}
+
+/// Runs a transaction in an in-process VM, stopping at breakpoints along the way.
+struct Debugger {
+    vm: Interpreter<MemoryStorage>,
+    programs: Vec<Program>,
+    tx: Transaction,
+    started: bool,
+    finished: bool,
+}
+
+impl Debugger {
+    fn new(programs: Vec<Program>, tx: Transaction) -> Self {
+        Debugger {
+            vm: Interpreter::with_storage(MemoryStorage::default(), Default::default()),
+            programs,
+            tx,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Runs the transaction which deploys a contract, without stopping.
+    fn deploy(&mut self, create_tx: Transaction) -> Result<()> {
+        let state = *self
+            .vm
+            .transact(create_tx)
+            .map_err(|err| anyhow!("Failed to deploy the contract: {:?}", err))?
+            .state();
+        match state {
+            ProgramState::Return(_) => Ok(()),
+            state => bail!("Failed to deploy the contract: {:?}", state),
+        }
+    }
+
+    /// Runs a debugger command and returns its output, or `None` once the user has quit.
+    fn exec(&mut self, line: &str) -> Result<Option<String>> {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => return Ok(Some(String::new())),
+        };
+        let args = words.collect::<Vec<_>>();
+        let output = match (command, &args[..]) {
+            ("break" | "b", [location]) => self.set_breakpoint(location)?,
+            ("continue" | "c", []) => self.resume(false)?,
+            ("step" | "s", []) => self.resume(true)?,
+            ("registers" | "regs", names) => self.registers(names)?,
+            ("memory" | "mem", [addr]) => self.memory(parse_number(addr)?, 32)?,
+            ("memory" | "mem", [addr, len]) => {
+                self.memory(parse_number(addr)?, parse_number(len)?)?
+            }
+            ("backtrace" | "bt", []) => self.backtrace(),
+            ("locals", []) => self.locals()?,
+            ("help" | "h", []) => HELP.to_owned(),
+            ("quit" | "q", []) => return Ok(None),
+            _ => bail!(
+                "Invalid command `{}`, type `help` for the list of commands",
+                line
+            ),
+        };
+        Ok(Some(output))
+    }
+
+    /// Sets a breakpoint at every place where the code for a source line starts, in any of the
+    /// programs.
+    fn set_breakpoint(&mut self, location: &str) -> Result<String> {
+        let (file, line) = location
+            .rsplit_once(':')
+            .and_then(|(file, line)| Some((Path::new(file), line.parse::<usize>().ok()?)))
+            .ok_or_else(|| anyhow!("Breakpoints must be given as `<file>:<line>`"))?;
+
+        let mut breakpoints = vec![];
+        for program in &self.programs {
+            let src = match fs::read_to_string(program.source_path(file)) {
+                Ok(src) => src,
+                Err(_) => continue,
+            };
+            let range = match line_range(&src, line) {
+                Some(range) => range,
+                None => continue,
+            };
+            let pcs = program.source_map.span_to_addrs(file, range);
+
+            // Only stop at the first instruction of each run of instructions for the line.
+            for (ix, pc) in pcs.iter().enumerate() {
+                if ix > 0 && pcs[ix - 1] + 1 == *pc {
+                    continue;
+                }
+                let pc = *pc as Word * 4;
+                breakpoints.push(match program.contract_id {
+                    Some(contract_id) => Breakpoint::new(contract_id, pc),
+                    None => Breakpoint::script(pc),
+                });
+            }
+        }
+        let count = breakpoints.len();
+        if count == 0 {
+            bail!("There is no code at {}", location);
+        }
+        for breakpoint in breakpoints {
+            self.vm.set_breakpoint(breakpoint);
+        }
+        Ok(format!(
+            "Breakpoint at {} ({} location{})",
+            location,
+            count,
+            if count == 1 { "" } else { "s" }
+        ))
+    }
+
+    /// Starts or resumes the program, until it hits a breakpoint or ends.
+    fn resume(&mut self, single_stepping: bool) -> Result<String> {
+        if self.finished {
+            bail!("The program has finished");
+        }
+        self.vm.set_single_stepping(single_stepping);
+        let state = if self.started {
+            self.vm.resume()
+        } else {
+            self.started = true;
+            self.vm
+                .transact(self.tx.clone())
+                .map(|transition| *transition.state())
+        }
+        .map_err(|err| anyhow!("The VM failed: {:?}", err))?;
+        self.vm.set_single_stepping(false);
+
+        Ok(match state {
+            ProgramState::Return(value) => {
+                self.finished = true;
+                format!("Program returned {}", value)
+            }
+            ProgramState::ReturnData(digest) => {
+                self.finished = true;
+                format!("Program returned data with digest {:#x}", digest)
+            }
+            ProgramState::Revert(value) => {
+                self.finished = true;
+                format!("Program reverted with {}", value)
+            }
+            ProgramState::RunProgram(DebugEval::Breakpoint(breakpoint))
+            | ProgramState::VerifyPredicate(DebugEval::Breakpoint(breakpoint)) => {
+                format!(
+                    "Stopped at {}",
+                    self.location(breakpoint.contract(), breakpoint.pc())
+                )
+            }
+            ProgramState::RunProgram(DebugEval::Continue)
+            | ProgramState::VerifyPredicate(DebugEval::Continue) => "Program is running".into(),
+        })
+    }
+
+    fn program(&self, contract_id: &ContractId) -> Option<&Program> {
+        self.programs
+            .iter()
+            .find(|program| match &program.contract_id {
+                Some(id) => id == contract_id,
+                None => *contract_id == ContractId::default(),
+            })
+    }
+
+    /// Describes the instruction at byte offset `pc` into the script or a contract.
+    fn location(&self, contract_id: &ContractId, pc: Word) -> String {
+        let mut location = format!("pc {:#x}", pc);
+        if *contract_id != ContractId::default() {
+            write!(location, " of contract {:#x}", contract_id).unwrap();
+        }
+        if let Some(source) = self
+            .program(contract_id)
+            .and_then(|program| program.source_location(pc))
+        {
+            write!(location, " in {}", source).unwrap();
+        }
+        location
+    }
+
+    fn register(&self, name: &str) -> Result<Word> {
+        let ix = REGISTER_NAMES
+            .iter()
+            .position(|reg| *reg == name)
+            .or_else(|| name.strip_prefix('r')?.parse().ok())
+            .filter(|ix| *ix < self.vm.registers().len())
+            .ok_or_else(|| anyhow!("Unknown register `{}`", name))?;
+        Ok(self.vm.registers()[ix])
+    }
+
+    fn registers(&self, names: &[&str]) -> Result<String> {
+        let names = if names.is_empty() {
+            (0..self.vm.registers().len())
+                .filter(|ix| self.vm.registers()[*ix] != 0)
+                .map(|ix| match REGISTER_NAMES.get(ix) {
+                    Some(name) => name.to_string(),
+                    None => format!("r{}", ix),
+                })
+                .collect::<Vec<_>>()
+        } else {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        let mut output = String::new();
+        for name in names {
+            let value = self.register(&name)?;
+            writeln!(output, "{:>5} = {:<20} ({:#x})", name, value, value).unwrap();
+        }
+        Ok(output.trim_end().to_owned())
+    }
+
+    fn memory(&self, addr: usize, len: usize) -> Result<String> {
+        let bytes = addr
+            .checked_add(len)
+            .and_then(|end| self.vm.memory().get(addr..end))
+            .ok_or_else(|| {
+                anyhow!(
+                    "{:#x}..{:#x} is out of bounds",
+                    addr,
+                    addr.saturating_add(len)
+                )
+            })?;
+        let mut output = String::new();
+        for (ix, line) in bytes.chunks(16).enumerate() {
+            writeln!(
+                output,
+                "{:#010x}: {}",
+                addr + ix * 16,
+                line.iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+            .unwrap();
+        }
+        Ok(output.trim_end().to_owned())
+    }
+
+    /// The contract being run, or the default ID for the script.
+    fn current_contract(&self) -> ContractId {
+        self.vm
+            .call_stack()
+            .last()
+            .map(|frame| *frame.to())
+            .unwrap_or_default()
+    }
+
+    fn current_pc(&self) -> Word {
+        let registers = self.vm.registers();
+        registers[3] - registers[12]
+    }
+
+    fn backtrace(&self) -> String {
+        let mut frames = vec![self.location(&self.current_contract(), self.current_pc())];
+
+        // Each call frame saves the registers of its caller.
+        let call_stack = self.vm.call_stack();
+        for (ix, frame) in call_stack.iter().enumerate().rev() {
+            let caller = match ix {
+                0 => ContractId::default(),
+                _ => *call_stack[ix - 1].to(),
+            };
+            let registers = frame.registers();
+            frames.push(self.location(&caller, registers[3] - registers[12]));
+        }
+        frames
+            .iter()
+            .enumerate()
+            .map(|(ix, frame)| format!("#{} {}", ix, frame))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn locals(&self) -> Result<String> {
+        let program = self
+            .program(&self.current_contract())
+            .ok_or_else(|| anyhow!("There is no debug information for the current program"))?;
+        let registers = self.vm.registers();
+        let ssp = self.register("ssp")? as usize;
+        let mut output = String::new();
+        for var in program
+            .source_map
+            .addr_to_variables(self.current_pc() as usize / 4)
+        {
+            let addr = match var.location {
+                VariableLocation::Stack { offset } => ssp + offset as usize,
+                VariableLocation::Memory { register, offset } => {
+                    registers[register as usize] as usize + offset as usize
+                }
+                VariableLocation::Register { register } => {
+                    let value = registers[register as usize];
+                    writeln!(output, "{}: {} = {}", var.name, var.ty, value).unwrap();
+                    continue;
+                }
+            };
+            match (var.ty.as_str(), self.vm.memory().get(addr..addr + 8)) {
+                ("u64" | "u32" | "u16" | "u8" | "bool", Some(word)) => {
+                    let value = u64::from_be_bytes(word.try_into().unwrap());
+                    writeln!(output, "{}: {} = {}", var.name, var.ty, value).unwrap();
+                }
+                _ => writeln!(output, "{}: {} at {:#x}", var.name, var.ty, addr).unwrap(),
+            }
+        }
+        Ok(output.trim_end().to_owned())
+    }
+}
+
+fn parse_number(number: &str) -> Result<usize> {
+    match number.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => number.parse(),
+    }
+    .map_err(|_| anyhow!("Invalid number `{}`", number))
+}
+
+/// The byte range of the 1-based `line` in `src`, without the newline.
+fn line_range(src: &str, line: usize) -> Option<LocationRange> {
+    let start = match line {
+        0 => return None,
+        1 => 0,
+        _ => src.match_indices('\n').nth(line - 2)?.0 + 1,
+    };
+    let end = src[start..].find('\n').map_or(src.len(), |len| start + len);
+    Some(LocationRange { start, end })
+}
+
+/// The 1-based line of the byte at `offset` in `src`.
+fn line_of(src: &str, offset: usize) -> usize {
+    src.as_bytes()[..offset.min(src.len())]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_asm::Opcode;
+    use std::{io::Read, sync::Arc};
+    use sway_types::Span;
+
+    /// Writes a project named `name` with the source `src`, without the standard library so that
+    /// it builds offline.
+    fn write_project(dir: &Path, name: &str, src: &str) -> PathBuf {
+        let project = dir.join(name);
+        fs::create_dir_all(project.join("src")).unwrap();
+        let manifest = format!(
+            "[project]\nauthors = [\"Fuel Labs <contact@fuel.sh>\"]\nentry = \"main.sw\"\n\
+             license = \"Apache-2.0\"\nname = \"{}\"\nimplicit-std = false\n",
+            name
+        );
+        fs::write(project.join("Forc.toml"), manifest).unwrap();
+        fs::write(project.join("src/main.sw"), src).unwrap();
+        project
+    }
+
+    const SRC: &str =
+        "script;\n\nfn main() -> u64 {\n    let a = 1;\n    let b = 2;\n    a + b\n}\n";
+
+    #[test]
+    fn lines() {
+        assert_eq!(line_range(SRC, 1).map(|r| (r.start, r.end)), Some((0, 7)));
+        assert_eq!(line_range(SRC, 2).map(|r| (r.start, r.end)), Some((8, 8)));
+        assert_eq!(line_range(SRC, 9), None);
+        assert_eq!(line_range(SRC, 0), None);
+        assert_eq!(line_of(SRC, 0), 1);
+        assert_eq!(line_of(SRC, 8), 2);
+        assert_eq!(line_of(SRC, SRC.find("a + b").unwrap()), 6);
+    }
+
+    #[test]
+    fn scripted_session() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        let path = Arc::new(dir.path().join("src/main.sw"));
+        fs::write(&*path, SRC).unwrap();
+
+        // `let a = 1; let b = 2; a + b` by hand, one instruction per line for the declarations.
+        let ops = vec![
+            Opcode::MOVI(0x10, 1),
+            Opcode::MOVI(0x11, 2),
+            Opcode::ADD(0x12, 0x10, 0x11),
+            Opcode::RET(0x12),
+        ];
+        let mut bytecode = vec![0; ops.len() * 4];
+        for (ix, mut op) in ops.into_iter().enumerate() {
+            op.read_exact(&mut bytecode[ix * 4..ix * 4 + 4]).unwrap();
+        }
+        let span = |text: &str| {
+            let start = SRC.find(text).unwrap();
+            Span::new(SRC.into(), start, start + text.len(), Some(path.clone())).unwrap()
+        };
+        let mut source_map = SourceMap::new();
+        source_map.insert(0, &span("let a = 1;"));
+        source_map.insert(1, &span("let b = 2;"));
+        source_map.insert(2, &span("a + b"));
+        source_map.insert(3, &span("a + b"));
+
+        let script = Program {
+            contract_id: None,
+            source_map,
+            dir: dir.path().to_owned(),
+        };
+        let tx = script_tx(
+            bytecode,
+            vec![],
+            ConsensusParameters::DEFAULT.max_gas_per_tx,
+            vec![],
+        );
+        let mut debugger = Debugger::new(vec![script], tx);
+        let mut exec = |line: &str| debugger.exec(line).unwrap().unwrap();
+
+        assert_eq!(
+            exec("break src/main.sw:5"),
+            "Breakpoint at src/main.sw:5 (1 location)"
+        );
+        assert!(exec("continue").ends_with("src/main.sw:5"));
+        assert!(exec("regs r16 r17").starts_with("  r16 = 1 "));
+        assert!(exec("step").ends_with("src/main.sw:6"));
+        assert!(exec("bt").starts_with("#0 pc 0x8 "));
+        assert_eq!(exec("continue"), "Program returned 3");
+        assert!(debugger.exec("continue").is_err());
+        assert!(debugger.exec("quit").unwrap().is_none());
+    }
+
+    #[test]
+    fn script_locals() {
+        // Without the standard library there are no operators, so the sum is done in assembly.
+        let src = "\
+script;
+
+fn main() -> u64 {
+    let a = 1;
+    let b = 2;
+    let c = asm(r1: a, r2: b, r3) {
+        add r3 r1 r2;
+        r3: u64
+    };
+    c
+}
+";
+        let dir = tempfile::tempdir().unwrap();
+        let script = write_project(dir.path(), "script", src);
+        let gas_limit = ConsensusParameters::DEFAULT.max_gas_per_tx;
+        let mut debugger = debugger(&script, None, vec![], gas_limit, true, false).unwrap();
+        let mut exec = |line: &str| debugger.exec(line).unwrap().unwrap();
+
+        exec("break src/main.sw:10");
+        assert!(exec("continue").ends_with("src/main.sw:10"));
+        let locals = exec("locals");
+        assert!(locals.contains("a: u64 = 1"), "{}", locals);
+        assert!(locals.contains("b: u64 = 2"), "{}", locals);
+        assert!(locals.contains("c: u64 = 3"), "{}", locals);
+        assert_eq!(exec("continue"), "Program returned 3");
+    }
+
+    #[test]
+    fn contract_session() {
+        let contract_src = "\
+contract;
+
+abi Adder {
+    fn add(x: u64, y: u64) -> u64;
+}
+
+impl Adder for Contract {
+    fn add(x: u64, y: u64) -> u64 {
+        let sum = asm(r1: x, r2: y, r3) {
+            add r3 r1 r2;
+            r3: u64
+        };
+        sum
+    }
+}
+";
+        let dir = tempfile::tempdir().unwrap();
+        let contract = write_project(dir.path(), "adder", contract_src);
+
+        // The caller needs the ID which the contract is deployed with.
+        let (compiled, _, _) = build(&contract, true, false).unwrap();
+        let (_, contract_id) =
+            create_contract_tx(compiled.bytecode, vec![], vec![], compiled.storage_slots);
+        let caller_src = format!(
+            "\
+script;
+
+abi Adder {{
+    fn add(x: u64, y: u64) -> u64;
+}}
+
+fn main() -> u64 {{
+    let adder = abi(Adder, {:#x});
+    adder.add(2, 3)
+}}
+",
+            contract_id
+        );
+        let caller = write_project(dir.path(), "caller", &caller_src);
+
+        let gas_limit = ConsensusParameters::DEFAULT.max_gas_per_tx;
+        let mut debugger =
+            debugger(&contract, Some(&caller), vec![], gas_limit, true, false).unwrap();
+        let mut exec = |line: &str| debugger.exec(line).unwrap().unwrap();
+
+        // Both programs have a `src/main.sw`, so the contract's is given in full.
+        let breakpoint = format!("{}:13", contract.join("src/main.sw").display());
+        assert!(exec(&format!("break {}", breakpoint)).starts_with("Breakpoint at"));
+        let stopped = exec("continue");
+        assert!(
+            stopped.contains(&format!("of contract {:#x}", contract_id)),
+            "{}",
+            stopped
+        );
+        assert!(stopped.ends_with(&breakpoint), "{}", stopped);
+        let locals = exec("locals");
+        assert!(locals.contains("sum: u64 = 5"), "{}", locals);
+        assert!(exec("bt").lines().count() >= 2);
+        assert_eq!(exec("continue"), "Program returned 5");
+    }
+}
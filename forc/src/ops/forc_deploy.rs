@@ -2,6 +2,7 @@ use crate::cli::{BuildCommand, DeployCommand};
 use crate::ops::forc_build;
 use anyhow::{bail, Result};
 use forc_pkg::ManifestFile;
+use forc_util::{log_to_stderr, MessageFormat};
 use fuel_gql_client::client::FuelClient;
 use fuel_tx::{Output, Salt, StorageSlot, Transaction};
 use fuel_vm::prelude::*;
@@ -11,6 +12,11 @@ use sway_utils::constants::DEFAULT_NODE_URL;
 use tracing::info;
 
 pub async fn deploy(command: DeployCommand) -> Result<fuel_tx::ContractId> {
+    // Leave stdout to the JSON messages.
+    if command.message_format == MessageFormat::Json {
+        log_to_stderr();
+    }
+
     let curr_dir = if let Some(ref path) = command.path {
         PathBuf::from(path)
     } else {
@@ -29,6 +35,8 @@ pub async fn deploy(command: DeployCommand) -> Result<fuel_tx::ContractId> {
         debug_outfile,
         offline_mode,
         silent_mode,
+        message_format,
+        deny_warnings,
         output_directory,
         minify_json_abi,
         minify_json_storage_slots,
@@ -49,6 +57,8 @@ pub async fn deploy(command: DeployCommand) -> Result<fuel_tx::ContractId> {
         offline_mode,
         debug_outfile,
         silent_mode,
+        message_format,
+        deny_warnings,
         output_directory,
         minify_json_abi,
         minify_json_storage_slots,
@@ -56,6 +66,7 @@ pub async fn deploy(command: DeployCommand) -> Result<fuel_tx::ContractId> {
         build_profile,
         release,
         time_phases,
+        size_report: false,
     };
 
     let compiled = forc_build::build(build_command)?;
@@ -84,7 +95,7 @@ pub async fn deploy(command: DeployCommand) -> Result<fuel_tx::ContractId> {
     }
 }
 
-fn create_contract_tx(
+pub(crate) fn create_contract_tx(
     compiled_contract: Vec<u8>,
     inputs: Vec<Input>,
     outputs: Vec<Output>,
@@ -4,6 +4,7 @@ use crate::utils::defaults::NODE_URL;
 use crate::utils::parameters::TxParameters;
 use anyhow::{anyhow, bail, Result};
 use forc_pkg::{fuel_core_not_running, ManifestFile};
+use forc_util::{log_to_stderr, MessageFormat};
 use fuel_gql_client::client::FuelClient;
 use fuel_tx::Transaction;
 use futures::TryFutureExt;
@@ -13,6 +14,11 @@ use sway_core::TreeType;
 use tracing::info;
 
 pub async fn run(command: RunCommand) -> Result<Vec<fuel_tx::Receipt>> {
+    // Leave stdout to the JSON messages.
+    if command.message_format == MessageFormat::Json {
+        log_to_stderr();
+    }
+
     let path_dir = if let Some(path) = &command.path {
         PathBuf::from(path)
     } else {
@@ -35,6 +41,8 @@ pub async fn run(command: RunCommand) -> Result<Vec<fuel_tx::Receipt>> {
         debug_outfile: command.debug_outfile,
         offline_mode: false,
         silent_mode: command.silent_mode,
+        message_format: command.message_format,
+        deny_warnings: command.deny_warnings,
         output_directory: command.output_directory,
         minify_json_abi: command.minify_json_abi,
         minify_json_storage_slots: command.minify_json_storage_slots,
@@ -42,6 +50,7 @@ pub async fn run(command: RunCommand) -> Result<Vec<fuel_tx::Receipt>> {
         build_profile: None,
         release: false,
         time_phases: command.time_phases,
+        size_report: false,
     };
 
     let compiled = forc_build::build(build_command)?;
@@ -140,7 +149,7 @@ fn create_tx_with_script_and_data(
 }
 
 // cut '0x' from the start
-fn format_hex_data(data: &str) -> &str {
+pub(crate) fn format_hex_data(data: &str) -> &str {
     data.strip_prefix("0x").unwrap_or(data)
 }
 
@@ -162,7 +171,7 @@ fn construct_output_from_contract((idx, _contract): (usize, &String)) -> fuel_tx
 }
 
 /// Given some contracts, constructs the most basic input and output set that satisfies validation.
-fn get_tx_inputs_and_outputs(
+pub(crate) fn get_tx_inputs_and_outputs(
     contracts: Vec<String>,
 ) -> (Vec<fuel_tx::Input>, Vec<fuel_tx::Output>) {
     let inputs = contracts
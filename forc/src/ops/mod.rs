@@ -2,6 +2,7 @@ pub mod forc_abi_json;
 pub mod forc_build;
 pub mod forc_check;
 pub mod forc_clean;
+pub mod forc_debug;
 pub mod forc_deploy;
 pub mod forc_init;
 pub mod forc_run;
@@ -0,0 +1,69 @@
+//! The debug info that `forc build --debug-outfile` writes, which describes where each local
+//! variable is kept at runtime.
+
+use std::{path::Path, process::Command};
+
+/// Builds the test program at `path` and returns its source map as JSON.
+fn source_map(path: &str) -> serde_json::Value {
+    let dir = tempfile::tempdir().expect("failed to create a temporary directory");
+    let debug_outfile = dir.path().join("debug.json");
+    let status = Command::new(env!("CARGO_BIN_EXE_forc"))
+        .arg("build")
+        .arg("--path")
+        .arg(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../test/src/e2e_vm_tests/test_programs")
+                .join(path),
+        )
+        .arg("--output-directory")
+        .arg(dir.path().join("out"))
+        .arg("--debug-outfile")
+        .arg(&debug_outfile)
+        .args(["--offline", "--silent"])
+        .status()
+        .expect("failed to run forc");
+    assert!(status.success(), "failed to build {path}");
+    let json = std::fs::read(&debug_outfile).expect("failed to read the debug info");
+    serde_json::from_slice(&json).expect("the debug info is not JSON")
+}
+
+/// The locations of the variables named `name`.
+fn locations<'a>(source_map: &'a serde_json::Value, name: &str) -> Vec<&'a serde_json::Value> {
+    source_map["variables"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|var| var["name"] == name)
+        .map(|var| &var["location"])
+        .collect()
+}
+
+#[test]
+fn variable_locations() {
+    let source_map = source_map("should_pass/language/indirect_call_stack");
+
+    // The locals of the entry function are kept above `$ssp`.
+    let sum = locations(&source_map, "sum");
+    assert_eq!(sum.len(), 1);
+    assert!(sum[0]["Stack"]["offset"].is_u64());
+
+    // Both callees are called through a function pointer, so their locals are kept above the
+    // `$sp` they were entered with, in some other register.  The `pair` of `main` is on the stack.
+    let pairs = locations(&source_map, "pair");
+    assert_eq!(pairs.len(), 3);
+    let (stack, memory): (Vec<_>, Vec<_>) = pairs
+        .into_iter()
+        .partition(|pair| pair["Stack"].is_object());
+    assert_eq!(stack.len(), 1);
+    for pair in memory {
+        assert!(pair["Memory"]["register"].is_u64(), "{pair}");
+        assert!(pair["Memory"]["offset"].is_u64(), "{pair}");
+    }
+
+    // The arguments of the callees are copy types, which are held in registers.
+    let args = locations(&source_map, "x");
+    assert!(!args.is_empty());
+    for arg in args {
+        assert!(arg["Register"]["register"].is_u64(), "{arg}");
+    }
+}
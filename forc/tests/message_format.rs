@@ -0,0 +1,78 @@
+//! With `--message-format json`, every line that forc prints to stdout is a JSON object.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn test_program(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../test/src/e2e_vm_tests/test_programs")
+        .join(path)
+}
+
+/// Runs forc with `args` in JSON mode and returns the JSON objects printed to stdout.
+fn forc_json(args: &[&str], path: &str) -> Vec<serde_json::Value> {
+    let output = Command::new(env!("CARGO_BIN_EXE_forc"))
+        .args(args)
+        .arg("--path")
+        .arg(test_program(path))
+        .args(["--offline", "--message-format", "json"])
+        .output()
+        .expect("failed to run forc");
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not UTF-8");
+    stdout
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line)
+                .unwrap_or_else(|error| panic!("stdout line {line:?} is not JSON: {error}"))
+        })
+        .collect()
+}
+
+fn has_summary(messages: &[serde_json::Value], success: bool) -> bool {
+    messages
+        .iter()
+        .any(|message| message["type"] == "summary" && message["success"] == success)
+}
+
+#[test]
+fn build_prints_only_json_to_stdout() {
+    let output_dir = std::env::temp_dir().join("forc-message-format-build");
+    let messages = forc_json(
+        &["build", "--output-directory", output_dir.to_str().unwrap()],
+        "should_pass/language/binary_and_hex_literals",
+    );
+    assert!(has_summary(&messages, true));
+}
+
+#[test]
+fn check_prints_only_json_to_stdout() {
+    let messages = forc_json(&["check"], "should_fail/same_named_fn_params");
+    assert!(messages
+        .iter()
+        .any(|message| message["type"] == "diagnostic" && message["severity"] == "error"));
+    assert!(has_summary(&messages, false));
+}
+
+#[test]
+fn whole_use_statement_is_only_removed_when_nothing_else_is_imported() {
+    let messages = forc_json(&["check"], "should_fail/unused_imports_and_variables");
+    let removes_use_statement = |name: &str| {
+        let message = format!("Import \"{name}\" is never used.");
+        let diagnostic = messages
+            .iter()
+            .find(|diagnostic| diagnostic["message"] == message.as_str())
+            .unwrap_or_else(|| panic!("no diagnostic for {name:?}"));
+        diagnostic["suggestions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|suggestion| suggestion["message"] == "remove the `use` statement")
+    };
+    assert!(removes_use_statement("sha256"));
+    // Each of these shares its statement with an import which is used.
+    assert!(!removes_use_statement("assert"));
+    assert!(!removes_use_statement("keccak256"));
+    assert!(!removes_use_statement("log"));
+}
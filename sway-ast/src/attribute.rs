@@ -1,5 +1,8 @@
 use crate::priv_prelude::*;
 
+/// The name of the attributes which doc comments are desugared into.
+pub const DOC_ATTRIBUTE_NAME: &str = "doc";
+
 #[derive(Clone, Debug)]
 pub struct Annotated<T> {
     pub attribute_list: Vec<AttributeDecl>,
@@ -25,6 +28,37 @@ impl Spanned for AttributeDecl {
     }
 }
 
+impl AttributeDecl {
+    /// Desugars a doc comment into a `#[doc(..)]` attribute whose one argument spans the comment.
+    pub fn from_doc_comment(doc_comment: &DocComment) -> AttributeDecl {
+        let span = doc_comment.span();
+        let attribute = Attribute {
+            name: Ident::new_with_override(DOC_ATTRIBUTE_NAME, span.clone()),
+            args: Some(Parens::new(
+                Punctuated {
+                    value_separator_pairs: Vec::new(),
+                    final_value_opt: Some(Box::new(Ident::new(span.clone()))),
+                },
+                span.clone(),
+            )),
+        };
+        AttributeDecl {
+            hash_token: HashToken::new(span.clone()),
+            attribute: SquareBrackets::new(attribute, span),
+        }
+    }
+
+    /// The text of the doc comment which this attribute was desugared from, if it was.
+    pub fn doc_comment(&self) -> Option<&str> {
+        let attribute = self.attribute.get();
+        if attribute.name.as_str() != DOC_ATTRIBUTE_NAME {
+            return None;
+        }
+        let comment = attribute.args.as_ref()?.get().into_iter().next()?;
+        Some(doc_comment_content(comment.as_str()))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Attribute {
     pub name: Ident,
@@ -32,7 +32,14 @@ pub enum Expr {
     },
     While {
         while_token: WhileToken,
-        condition: Box<Expr>,
+        condition: IfCondition,
+        block: Braces<CodeBlockContents>,
+    },
+    For {
+        for_token: ForToken,
+        value_pattern: Pattern,
+        in_token: InToken,
+        iterator: Box<Expr>,
         block: Braces<CodeBlockContents>,
     },
     FuncApp {
@@ -163,6 +170,11 @@ pub enum Expr {
         double_pipe_token: DoublePipeToken,
         rhs: Box<Expr>,
     },
+    Range {
+        start: Box<Expr>,
+        double_dot_token: DoubleDotToken,
+        end: Box<Expr>,
+    },
     Reassignment {
         assignable: Assignable,
         reassignment_op: ReassignmentOp,
@@ -202,6 +214,9 @@ impl Spanned for Expr {
             Expr::While {
                 while_token, block, ..
             } => Span::join(while_token.span(), block.span()),
+            Expr::For {
+                for_token, block, ..
+            } => Span::join(for_token.span(), block.span()),
             Expr::FuncApp { func, args } => Span::join(func.span(), args.span()),
             Expr::Index { target, arg } => Span::join(target.span(), arg.span()),
             Expr::MethodCall { target, args, .. } => Span::join(target.span(), args.span()),
@@ -230,6 +245,7 @@ impl Spanned for Expr {
             Expr::GreaterThanEq { lhs, rhs, .. } => Span::join(lhs.span(), rhs.span()),
             Expr::LogicalAnd { lhs, rhs, .. } => Span::join(lhs.span(), rhs.span()),
             Expr::LogicalOr { lhs, rhs, .. } => Span::join(lhs.span(), rhs.span()),
+            Expr::Range { start, end, .. } => Span::join(start.span(), end.span()),
             Expr::Reassignment {
                 assignable, expr, ..
             } => Span::join(assignable.span(), expr.span()),
@@ -459,7 +475,8 @@ impl Expr {
                 | Expr::Asm(..)
                 | Expr::If(..)
                 | Expr::Match { .. }
-                | Expr::While { .. },
+                | Expr::While { .. }
+                | Expr::For { .. },
         )
     }
 }
@@ -7,7 +7,7 @@ pub struct ItemImpl {
     pub trait_opt: Option<(PathType, ForToken)>,
     pub ty: Ty,
     pub where_clause_opt: Option<WhereClause>,
-    pub contents: Braces<Vec<Annotated<ItemFn>>>,
+    pub contents: Braces<Vec<Annotated<ItemImplItem>>>,
 }
 
 impl Spanned for ItemImpl {
@@ -15,3 +15,36 @@ impl Spanned for ItemImpl {
         Span::join(self.impl_token.span(), self.contents.span())
     }
 }
+
+#[derive(Clone, Debug)]
+pub enum ItemImplItem {
+    Fn(ItemFn),
+    Const(ItemConst),
+    Type(ImplType),
+}
+
+impl Spanned for ItemImplItem {
+    fn span(&self) -> Span {
+        match self {
+            ItemImplItem::Fn(item_fn) => item_fn.span(),
+            ItemImplItem::Const(item_const) => item_const.span(),
+            ItemImplItem::Type(impl_type) => impl_type.span(),
+        }
+    }
+}
+
+/// The definition of an associated type within a trait impl, e.g. `type Item = u64;`.
+#[derive(Clone, Debug)]
+pub struct ImplType {
+    pub type_token: TypeToken,
+    pub name: Ident,
+    pub eq_token: EqToken,
+    pub ty: Ty,
+    pub semicolon_token: SemicolonToken,
+}
+
+impl Spanned for ImplType {
+    fn span(&self) -> Span {
+        Span::join(self.type_token.span(), self.semicolon_token.span())
+    }
+}
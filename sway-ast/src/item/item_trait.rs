@@ -5,8 +5,9 @@ pub struct ItemTrait {
     pub visibility: Option<PubToken>,
     pub trait_token: TraitToken,
     pub name: Ident,
+    pub generics: Option<GenericParams>,
     pub super_traits: Option<(ColonToken, Traits)>,
-    pub trait_items: Braces<Vec<(Annotated<FnSignature>, SemicolonToken)>>,
+    pub trait_items: Braces<Vec<(Annotated<ItemTraitItem>, SemicolonToken)>>,
     pub trait_defs_opt: Option<Braces<Vec<Annotated<ItemFn>>>>,
 }
 
@@ -24,6 +25,51 @@ impl Spanned for ItemTrait {
     }
 }
 
+#[derive(Clone, Debug)]
+pub enum ItemTraitItem {
+    Fn(FnSignature),
+    Const(TraitConst),
+    Type(TraitType),
+}
+
+impl Spanned for ItemTraitItem {
+    fn span(&self) -> Span {
+        match self {
+            ItemTraitItem::Fn(fn_signature) => fn_signature.span(),
+            ItemTraitItem::Const(trait_const) => trait_const.span(),
+            ItemTraitItem::Type(trait_type) => trait_type.span(),
+        }
+    }
+}
+
+/// An associated constant declared by a trait, e.g. `const ID: u64;`.
+#[derive(Clone, Debug)]
+pub struct TraitConst {
+    pub const_token: ConstToken,
+    pub name: Ident,
+    pub colon_token: ColonToken,
+    pub ty: Ty,
+}
+
+impl Spanned for TraitConst {
+    fn span(&self) -> Span {
+        Span::join(self.const_token.span(), self.ty.span())
+    }
+}
+
+/// An associated type declared by a trait, e.g. `type Item;`.
+#[derive(Clone, Debug)]
+pub struct TraitType {
+    pub type_token: TypeToken,
+    pub name: Ident,
+}
+
+impl Spanned for TraitType {
+    fn span(&self) -> Span {
+        Span::join(self.type_token.span(), self.name.span())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Traits {
     pub prefix: PathType,
@@ -36,6 +36,8 @@ pub enum ItemKind {
     Storage(ItemStorage),
     Break(ItemBreak),
     Continue(ItemContinue),
+    /// An item which failed to parse, spanning the tokens skipped to recover from the error.
+    Error(Span),
 }
 
 impl Spanned for ItemKind {
@@ -52,6 +54,7 @@ impl Spanned for ItemKind {
             ItemKind::Storage(item_storage) => item_storage.span(),
             ItemKind::Break(item_break) => item_break.span(),
             ItemKind::Continue(item_continue) => item_continue.span(),
+            ItemKind::Error(span) => span.clone(),
         }
     }
 }
@@ -79,6 +79,8 @@ define_keyword!(TrueToken, "true");
 define_keyword!(FalseToken, "false");
 define_keyword!(BreakToken, "break");
 define_keyword!(ContinueToken, "continue");
+define_keyword!(InToken, "in");
+define_keyword!(TypeToken, "type");
 
 /// The type is a keyword.
 pub trait Token: Spanned + Sized {
@@ -174,7 +176,7 @@ define_token!(
     [Equals, GreaterThan],
     [GreaterThan, Equals]
 );
-define_token!(DotToken, "`.`", [Dot], []);
+define_token!(DotToken, "`.`", [Dot], [Dot]);
 define_token!(DoubleDotToken, "`..`", [Dot, Dot], [Dot]);
 define_token!(BangToken, "`!`", [Bang], [Equals]);
 define_token!(PercentToken, "`%`", [Percent], []);
@@ -37,10 +37,10 @@ pub use crate::{
         item_control_flow::{ItemBreak, ItemContinue},
         item_enum::ItemEnum,
         item_fn::ItemFn,
-        item_impl::ItemImpl,
+        item_impl::{ImplType, ItemImpl, ItemImplItem},
         item_storage::{ItemStorage, StorageField},
         item_struct::ItemStruct,
-        item_trait::{ItemTrait, Traits},
+        item_trait::{ItemTrait, ItemTraitItem, TraitConst, TraitType, Traits},
         item_use::{ItemUse, UseTree},
         FnArg, FnArgs, FnSignature, Item, ItemKind, TypeField,
     },
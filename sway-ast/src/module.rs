@@ -1,6 +1,9 @@
 use crate::priv_prelude::*;
 
 pub struct Module {
+    /// The module's doc comments, desugared into attributes, and the attributes written before
+    /// its declaration.
+    pub attribute_list: Vec<AttributeDecl>,
     pub kind: ModuleKind,
     pub semicolon_token: SemicolonToken,
     pub dependencies: Vec<Dependency>,
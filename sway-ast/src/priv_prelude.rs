@@ -17,10 +17,10 @@ pub use {
             item_control_flow::{ItemBreak, ItemContinue},
             item_enum::ItemEnum,
             item_fn::ItemFn,
-            item_impl::ItemImpl,
+            item_impl::{ImplType, ItemImpl, ItemImplItem},
             item_storage::ItemStorage,
             item_struct::ItemStruct,
-            item_trait::{ItemTrait, Traits},
+            item_trait::{ItemTrait, ItemTraitItem, TraitConst, TraitType, Traits},
             item_use::ItemUse,
             FnSignature, Item, ItemKind, TypeField,
         },
@@ -30,7 +30,10 @@ pub use {
         pattern::Pattern,
         punctuated::Punctuated,
         statement::{Statement, StatementLet},
-        token::{Delimiter, Group, Punct, PunctKind, Spacing, TokenStream, TokenTree},
+        token::{
+            doc_comment_content, Delimiter, DocComment, DocStyle, Group, Punct, PunctKind, Spacing,
+            TokenStream, TokenTree,
+        },
         ty::Ty,
         where_clause::{WhereBound, WhereClause},
     },
@@ -9,6 +9,8 @@ pub enum Statement {
         expr: Expr,
         semicolon_token_opt: Option<SemicolonToken>,
     },
+    /// A statement which failed to parse, spanning the tokens skipped to recover from the error.
+    Error(Span),
 }
 
 #[derive(Clone, Debug)]
@@ -33,6 +35,7 @@ impl Spanned for Statement {
                 None => expr.span(),
                 Some(semicolon_token) => Span::join(expr.span(), semicolon_token.span()),
             },
+            Statement::Error(span) => span.clone(),
         }
     }
 }
@@ -119,6 +119,60 @@ impl Spanned for Comment {
     }
 }
 
+impl Comment {
+    /// Whether this is a `///` or `//!` doc comment, and which.
+    pub fn doc_style(&self) -> Option<DocStyle> {
+        let text = self.span.as_str();
+        if text.starts_with("///") && !text.starts_with("////") {
+            Some(DocStyle::Outer)
+        } else if text.starts_with("//!") {
+            Some(DocStyle::Inner)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DocStyle {
+    /// A `///` comment, documenting the item which follows it.
+    Outer,
+    /// A `//!` comment, documenting the module it is in.
+    Inner,
+}
+
+/// A doc comment which is kept when comments are stripped from a token stream, so that the parser
+/// can attach it to the item which follows it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct DocComment {
+    pub span: Span,
+    pub style: DocStyle,
+    /// Where the token tree which follows the comment starts.
+    pub target: usize,
+}
+
+impl DocComment {
+    /// The text of the comment, without the leading `///` or `//!` and the space after it.
+    pub fn content(&self) -> &str {
+        doc_comment_content(self.span.as_str())
+    }
+}
+
+/// Strips the leading `///` or `//!`, and the space after it, from the text of a doc comment.
+pub fn doc_comment_content(text: &str) -> &str {
+    let text = text
+        .strip_prefix("///")
+        .or_else(|| text.strip_prefix("//!"))
+        .unwrap_or(text);
+    text.strip_prefix(' ').unwrap_or(text)
+}
+
+impl Spanned for DocComment {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
 /// Allows for generalizing over commented and uncommented token streams.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
 pub enum GenericTokenTree<T> {
@@ -207,6 +261,7 @@ impl From<CommentedTree> for CommentedTokenTree {
 pub struct TokenStream {
     token_trees: Vec<TokenTree>,
     full_span: Span,
+    doc_comments: Vec<DocComment>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
@@ -288,6 +343,10 @@ impl TokenStream {
     pub fn token_trees(&self) -> &[TokenTree] {
         &self.token_trees
     }
+
+    pub fn doc_comments(&self) -> &[DocComment] {
+        &self.doc_comments
+    }
 }
 
 impl Spanned for TokenStream {
@@ -318,14 +377,35 @@ impl CommentedTokenStream {
     }
 
     pub fn strip_comments(self) -> TokenStream {
-        let token_trees = self
-            .token_trees
-            .into_iter()
-            .filter_map(|tree| tree.strip_comments())
-            .collect();
+        let mut token_trees = Vec::new();
+        let mut doc_comments = Vec::new();
+        let mut pending_doc_comments = Vec::new();
+        for tree in self.token_trees {
+            match tree {
+                CommentedTokenTree::Comment(comment) => {
+                    if let Some(style) = comment.doc_style() {
+                        pending_doc_comments.push((comment.span, style));
+                    }
+                }
+                CommentedTokenTree::Tree(..) => {
+                    if let Some(tree) = tree.strip_comments() {
+                        let target = tree.span().start();
+                        doc_comments.extend(pending_doc_comments.drain(..).map(|(span, style)| {
+                            DocComment {
+                                span,
+                                style,
+                                target,
+                            }
+                        }));
+                        token_trees.push(tree);
+                    }
+                }
+            }
+        }
         TokenStream {
             token_trees,
             full_span: self.full_span,
+            doc_comments,
         }
     }
 }
@@ -1,13 +1,20 @@
 use crate::{
-    asm_generation::{register_allocator, DataSection, InstructionSet, RegisterSequencer},
+    asm_generation::{
+        compiler_constants, register_allocator, DataSection, InstructionSet, RegisterSequencer,
+    },
     asm_lang::{
-        allocated_ops::AllocatedOp, Label, Op, OrganizationalOp, RealizedOp, VirtualImmediate12,
-        VirtualImmediate18, VirtualImmediate24, VirtualOp,
+        allocated_ops::AllocatedOp, ConstantRegister, DebugVariable, Label, Op, OrganizationalOp,
+        RealizedOp, VirtualImmediate12, VirtualImmediate18, VirtualImmediate24, VirtualOp,
+        VirtualRegister,
     },
 };
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
 use either::Either;
+use sway_types::Span;
 
 /// An [AbstractInstructionSet] is a set of instructions that use entirely virtual registers
 /// and excessive moves, with the intention of later optimizing it.
@@ -66,43 +73,48 @@ impl AbstractInstructionSet {
 
     /// Runs two passes -- one to get the instruction offsets of the labels
     /// and one to replace the labels in the organizational ops
+    ///
+    /// Jumps whose target does not fit in the immediate of `JI`, `JNEI` or `JNZI` are realized as
+    /// long jumps, which load the target from the data section and take one more instruction.
+    /// Growing a jump shifts the labels after it, which may push other targets out of range, so
+    /// the offsets are recomputed until every remaining short jump fits.
     pub(crate) fn realize_labels(
         self,
         data_section: &DataSection,
     ) -> RealizedAbstractInstructionSet {
-        let mut label_namespace: HashMap<&Label, u64> = Default::default();
-        let mut offset_map = vec![];
-        let mut counter = 0;
-        for op in &self.ops {
-            offset_map.push(counter);
-            match op.opcode {
-                Either::Right(OrganizationalOp::Label(ref lab)) => {
-                    label_namespace.insert(lab, counter);
-                }
-                // A special case for LWDataId which may be 1 or 2 ops, depending on the source size.
-                Either::Left(VirtualOp::LWDataId(_, ref data_id)) => {
-                    let type_of_data = data_section.type_of_data(data_id).expect(
-                        "Internal miscalculation in data section -- data id did not match up to any actual data",
-                    );
-                    counter += if type_of_data.is_copy_type() { 1 } else { 2 };
-                }
-                // these ops will end up being exactly one op, so the counter goes up one
-                Either::Right(OrganizationalOp::Jump(..))
-                | Either::Right(OrganizationalOp::JumpIfNotEq(..))
-                | Either::Right(OrganizationalOp::JumpIfNotZero(..))
-                | Either::Left(_) => {
-                    counter += 1;
-                }
-                Either::Right(OrganizationalOp::Comment) => (),
-                Either::Right(OrganizationalOp::DataSectionOffsetPlaceholder) => {
-                    // If the placeholder is 32 bits, this is 1. if 64, this should be 2. We use LW
-                    // to load the data, which loads a whole word, so for now this is 2.
-                    counter += 2
-                }
+        let mut long_jumps = HashSet::new();
+        let (label_namespace, offset_map) = loop {
+            let (label_namespace, offset_map) = self.label_offsets(data_section, &long_jumps);
+            let out_of_range = self
+                .ops
+                .iter()
+                .enumerate()
+                .filter(|(ix, op)| {
+                    !long_jumps.contains(ix)
+                        && match op.opcode {
+                            Either::Right(OrganizationalOp::Jump(ref lab)) => {
+                                label_namespace[lab] > compiler_constants::TWENTY_FOUR_BITS
+                            }
+                            Either::Right(OrganizationalOp::JumpIfNotEq(_, _, ref lab)) => {
+                                label_namespace[lab] > compiler_constants::TWELVE_BITS
+                            }
+                            Either::Right(OrganizationalOp::JumpIfNotZero(_, ref lab)) => {
+                                label_namespace[lab] > compiler_constants::EIGHTEEN_BITS
+                            }
+                            _ => false,
+                        }
+                })
+                .map(|(ix, _)| ix)
+                .collect::<Vec<_>>();
+            if out_of_range.is_empty() {
+                break (label_namespace, offset_map);
             }
-        }
+            long_jumps.extend(out_of_range);
+        };
 
         let mut realized_ops = vec![];
+        let mut debug_variables = vec![];
+        let mut variables_in_scope = vec![];
         for (
             ix,
             Op {
@@ -113,6 +125,13 @@ impl AbstractInstructionSet {
         ) in self.ops.clone().into_iter().enumerate()
         {
             let offset = offset_map[ix];
+            if let Some(span) = &owning_span {
+                for (var, last_ix) in &mut variables_in_scope {
+                    if matches!(&var.lexical_scope, Some(scope) if span_contains(scope, span)) {
+                        *last_ix = Some(ix);
+                    }
+                }
+            }
             match opcode {
                 Either::Left(op) => realized_ops.push(RealizedOp {
                     opcode: op,
@@ -122,36 +141,70 @@ impl AbstractInstructionSet {
                 }),
                 Either::Right(org_op) => match org_op {
                     OrganizationalOp::Jump(ref lab) => {
-                        let imm = VirtualImmediate24::new_unchecked(
-                            *label_namespace.get(lab).unwrap(),
-                            "Programs with more than 2^24 labels are unsupported right now",
-                        );
+                        let target = label_namespace[lab];
+                        let opcode = if long_jumps.contains(&ix) {
+                            VirtualOp::LongJump(target)
+                        } else {
+                            VirtualOp::JI(VirtualImmediate24::new_unchecked(
+                                target,
+                                "out of range jumps are realized as long jumps",
+                            ))
+                        };
                         realized_ops.push(RealizedOp {
-                            opcode: VirtualOp::JI(imm),
+                            opcode,
                             owning_span,
                             comment,
                             offset,
                         });
                     }
                     OrganizationalOp::JumpIfNotEq(r1, r2, ref lab) => {
-                        let imm = VirtualImmediate12::new_unchecked(
-                            *label_namespace.get(lab).unwrap(),
-                            "Programs with more than 2^12 labels are unsupported right now",
-                        );
+                        let target = label_namespace[lab];
+                        let opcode = if long_jumps.contains(&ix) {
+                            VirtualOp::LongJumpIfNotEq(r1, r2, target)
+                        } else {
+                            VirtualOp::JNEI(
+                                r1,
+                                r2,
+                                VirtualImmediate12::new_unchecked(
+                                    target,
+                                    "out of range jumps are realized as long jumps",
+                                ),
+                            )
+                        };
                         realized_ops.push(RealizedOp {
-                            opcode: VirtualOp::JNEI(r1, r2, imm),
+                            opcode,
                             owning_span,
                             comment,
                             offset,
                         });
                     }
                     OrganizationalOp::JumpIfNotZero(r1, ref lab) => {
-                        let imm = VirtualImmediate18::new_unchecked(
-                            *label_namespace.get(lab).unwrap(),
-                            "Programs with more than 2^18 labels are unsupported right now",
-                        );
+                        let target = label_namespace[lab];
+                        let opcode = if long_jumps.contains(&ix) {
+                            VirtualOp::LongJumpIfNotEq(
+                                r1,
+                                VirtualRegister::Constant(ConstantRegister::Zero),
+                                target,
+                            )
+                        } else {
+                            VirtualOp::JNZI(
+                                r1,
+                                VirtualImmediate18::new_unchecked(
+                                    target,
+                                    "out of range jumps are realized as long jumps",
+                                ),
+                            )
+                        };
+                        realized_ops.push(RealizedOp {
+                            opcode,
+                            owning_span,
+                            comment,
+                            offset,
+                        });
+                    }
+                    OrganizationalOp::LoadLabelOffset(r1, ref lab) => {
                         realized_ops.push(RealizedOp {
-                            opcode: VirtualOp::JNZI(r1, imm),
+                            opcode: VirtualOp::LoadInstructionOffset(r1, label_namespace[lab]),
                             owning_span,
                             comment,
                             offset,
@@ -165,12 +218,85 @@ impl AbstractInstructionSet {
                             offset,
                         });
                     }
+                    OrganizationalOp::DebugVariable(mut var) => {
+                        var.scope = offset..offset;
+                        variables_in_scope.push((*var, None));
+                    }
+                    OrganizationalOp::DebugScopeEnd => {
+                        // A variable with a lexical scope ends after the last instruction within
+                        // it, or where it starts if there is none.
+                        debug_variables.extend(variables_in_scope.drain(..).map(
+                            |(mut var, last_ix)| {
+                                var.scope.end = match (&var.lexical_scope, last_ix) {
+                                    (None, _) => offset,
+                                    (Some(_), None) => var.scope.start,
+                                    (Some(_), Some(last_ix)) => offset_map[last_ix + 1],
+                                };
+                                var
+                            },
+                        ));
+                    }
                     OrganizationalOp::Comment => continue,
                     OrganizationalOp::Label(..) => continue,
                 },
             };
         }
-        RealizedAbstractInstructionSet { ops: realized_ops }
+        RealizedAbstractInstructionSet {
+            ops: realized_ops,
+            debug_variables,
+        }
+    }
+
+    /// Computes the instruction offset of each label and of each op, given the indices of the
+    /// jumps which are realized as long jumps.
+    fn label_offsets(
+        &self,
+        data_section: &DataSection,
+        long_jumps: &HashSet<usize>,
+    ) -> (HashMap<&Label, u64>, Vec<u64>) {
+        let mut label_namespace: HashMap<&Label, u64> = Default::default();
+        let mut offset_map = vec![];
+        let mut counter = 0;
+        for (ix, op) in self.ops.iter().enumerate() {
+            offset_map.push(counter);
+            match op.opcode {
+                Either::Right(OrganizationalOp::Label(ref lab)) => {
+                    label_namespace.insert(lab, counter);
+                }
+                // A special case for LWDataId which may be 1 or 2 ops, depending on the source size.
+                Either::Left(VirtualOp::LWDataId(_, ref data_id)) => {
+                    let type_of_data = data_section.type_of_data(data_id).expect(
+                        "Internal miscalculation in data section -- data id did not match up to any actual data",
+                    );
+                    counter += if type_of_data.is_copy_type() { 1 } else { 2 };
+                }
+                // Long jumps load their target into a register before jumping, so they are 2 ops.
+                Either::Right(OrganizationalOp::Jump(..))
+                | Either::Right(OrganizationalOp::JumpIfNotEq(..))
+                | Either::Right(OrganizationalOp::JumpIfNotZero(..))
+                    if long_jumps.contains(&ix) =>
+                {
+                    counter += 2;
+                }
+                // these ops will end up being exactly one op, so the counter goes up one
+                Either::Right(OrganizationalOp::Jump(..))
+                | Either::Right(OrganizationalOp::JumpIfNotEq(..))
+                | Either::Right(OrganizationalOp::JumpIfNotZero(..))
+                | Either::Right(OrganizationalOp::LoadLabelOffset(..))
+                | Either::Left(_) => {
+                    counter += 1;
+                }
+                Either::Right(OrganizationalOp::Comment)
+                | Either::Right(OrganizationalOp::DebugVariable(_))
+                | Either::Right(OrganizationalOp::DebugScopeEnd) => (),
+                Either::Right(OrganizationalOp::DataSectionOffsetPlaceholder) => {
+                    // If the placeholder is 32 bits, this is 1. if 64, this should be 2. We use LW
+                    // to load the data, which loads a whole word, so for now this is 2.
+                    counter += 2
+                }
+            }
+        }
+        (label_namespace, offset_map)
     }
 }
 
@@ -192,6 +318,7 @@ impl fmt::Display for AbstractInstructionSet {
 /// ops or labels. In this struct, they are all "realized" to offsets.
 pub struct RealizedAbstractInstructionSet {
     ops: Vec<RealizedOp>,
+    debug_variables: Vec<DebugVariable>,
 }
 
 impl RealizedAbstractInstructionSet {
@@ -201,7 +328,7 @@ impl RealizedAbstractInstructionSet {
     /// the algorithm are thoroughly explained in register_allocator.rs.
     ///
     pub(crate) fn allocate_registers(
-        self,
+        mut self,
         register_sequencer: &mut RegisterSequencer,
     ) -> InstructionSet {
         // Step 1: Liveness Analysis.
@@ -217,6 +344,7 @@ impl RealizedAbstractInstructionSet {
             &mut interference_graph,
             &mut reg_to_node_ix,
             register_sequencer,
+            &mut self.debug_variables,
         );
 
         // Step 4: Simplify - i.e. color the interference graph and return a stack that contains
@@ -236,16 +364,29 @@ impl RealizedAbstractInstructionSet {
             })
         }
 
-        InstructionSet { ops: buf }
+        InstructionSet {
+            ops: buf,
+            debug_variables: self
+                .debug_variables
+                .into_iter()
+                .filter_map(|var| var.allocate_registers(&pool))
+                .collect(),
+        }
     }
 }
 
+/// Whether `inner` is within `outer`, in the same source file.
+fn span_contains(outer: &Span, inner: &Span) -> bool {
+    outer.path() == inner.path() && outer.start() <= inner.start() && inner.end() <= outer.end()
+}
+
 /// helper function to check if a label is used in a given buffer of ops
 fn label_is_used(buf: &[Op], label: &Label) -> bool {
     buf.iter().any(|Op { ref opcode, .. }| match opcode {
         Either::Right(OrganizationalOp::Jump(ref l)) if label == l => true,
         Either::Right(OrganizationalOp::JumpIfNotEq(_, _, ref l)) if label == l => true,
         Either::Right(OrganizationalOp::JumpIfNotZero(_, ref l)) if label == l => true,
+        Either::Right(OrganizationalOp::LoadLabelOffset(_, ref l)) if label == l => true,
         _ => false,
     })
 }
@@ -11,7 +11,9 @@ pub(crate) const SIX_BITS: u64 = 0b11_1111;
 /// reservation must be made.
 /// So far, the compiler-reserved registers are:
 /// 1. DATA_SECTION_BEGIN
-const NUM_COMPILER_RESERVED_REGISTERS: u8 = 1;
+/// 2. SCRATCH, used to hold the target of jumps which do not fit in an immediate
+const NUM_COMPILER_RESERVED_REGISTERS: u8 = 2;
 pub(crate) const DATA_SECTION_REGISTER: u8 = NUM_TOTAL_REGISTERS - 1;
+pub(crate) const SCRATCH_REGISTER: u8 = NUM_TOTAL_REGISTERS - 2;
 pub(crate) const NUM_ALLOCATABLE_REGISTERS: u8 =
     NUM_FREE_REGISTERS - NUM_COMPILER_RESERVED_REGISTERS;
@@ -1,10 +1,11 @@
 use crate::{parse_tree::*, type_engine::*};
-use std::fmt::{self, Write};
-
-type Data = Literal;
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
 
 /// An address which refers to a value in the data section of the asm.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub(crate) struct DataId(pub(crate) u32);
 
 impl fmt::Display for DataId {
@@ -13,46 +14,120 @@ impl fmt::Display for DataId {
     }
 }
 
+/// A single value in the data section.
+#[derive(Clone, Debug)]
+pub(crate) enum Data {
+    /// A literal value.
+    Literal(Literal),
+    /// A pointer to another entry in the data section, stored as the offset in bytes of that entry
+    /// from the start of the data section. These are used to load the address of data which does
+    /// not fit in a register.
+    Pointer(DataId),
+}
+
+impl Data {
+    fn is_word_sized(&self) -> bool {
+        match self {
+            Data::Literal(lit) => lit.as_type().is_copy_type(),
+            Data::Pointer(_) => true,
+        }
+    }
+
+    /// The size of this value in bytes, including the padding to a whole number of words.
+    fn size_in_bytes(&self) -> usize {
+        match self {
+            Data::Literal(lit) => lit.to_bytes().len(),
+            Data::Pointer(_) => 8,
+        }
+    }
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct DataSection {
-    /// the data to be put in the data section of the asm
-    pub value_pairs: Vec<Data>,
+    /// the data to be put in the data section of the asm, indexed by [DataId]
+    pub(crate) value_pairs: Vec<Data>,
+    /// Interned literals, keyed by whether they are word-sized and their serialized bytes. Two
+    /// literals with the same key are interchangeable, even if their types differ.
+    literals: HashMap<(bool, Vec<u8>), u32>,
+    /// Interned pointers, keyed by the index of the data they point to.
+    pointers: HashMap<u32, u32>,
+    /// The offset of each entry from the start of the entries of its kind, see [Self::layout].
+    /// Since the word-sized entries come first, only the offsets of the larger entries move as
+    /// entries are added, and they all move by the size of the word-sized entries.
+    offsets: Vec<usize>,
+    /// The total size in bytes of the word-sized entries.
+    words_size: usize,
+    /// The total size in bytes of the larger entries.
+    others_size: usize,
 }
 
 impl DataSection {
     /// Given a [DataId], calculate the offset _from the beginning of the data section_ to the data
     /// in bytes.
     pub(crate) fn offset_to_id(&self, id: &DataId) -> usize {
-        self.value_pairs
-            .iter()
-            .take(id.0 as usize)
-            .map(|x| x.to_bytes().len())
-            .sum()
+        let ix = id.0 as usize;
+        if self.value_pairs[ix].is_word_sized() {
+            self.offsets[ix]
+        } else {
+            self.words_size + self.offsets[ix]
+        }
+    }
+
+    /// Adds `data` to the end of the entries of its kind, returning its [DataId].
+    fn push(&mut self, data: Data) -> u32 {
+        let size = if data.is_word_sized() {
+            &mut self.words_size
+        } else {
+            &mut self.others_size
+        };
+        self.offsets.push(*size);
+        *size += data.size_in_bytes();
+        self.value_pairs.push(data);
+        self.value_pairs.len() as u32 - 1
+    }
+
+    /// The indices of the entries in the order they are placed in the data section. Word-sized
+    /// entries come first, so that they are all word-aligned without padding between them and
+    /// stay within reach of the immediate of `LW`, followed by the larger entries which are only
+    /// ever loaded through a pointer. Otherwise, entries are kept in the order they were inserted.
+    fn layout(&self) -> impl Iterator<Item = usize> + '_ {
+        let (words, others): (Vec<_>, Vec<_>) =
+            (0..self.value_pairs.len()).partition(|ix| self.value_pairs[*ix].is_word_sized());
+        words.into_iter().chain(others.into_iter())
     }
 
     pub(crate) fn serialize_to_bytes(&self) -> Vec<u8> {
         // not the exact right capacity but serves as a lower bound
         let mut buf = Vec::with_capacity(self.value_pairs.len());
-        for val in &self.value_pairs {
-            buf.append(&mut val.to_bytes().to_vec());
+        for ix in self.layout() {
+            match &self.value_pairs[ix] {
+                Data::Literal(lit) => buf.append(&mut lit.to_bytes()),
+                Data::Pointer(id) => buf.extend((self.offset_to_id(id) as u64).to_be_bytes()),
+            }
         }
         buf
     }
 
     /// Calculates the return type of the data held at a specific [DataId].
     pub(crate) fn type_of_data(&self, id: &DataId) -> Option<ResolvedType> {
-        self.value_pairs.get(id.0 as usize).map(|x| x.as_type())
+        self.value_pairs.get(id.0 as usize).map(|x| match x {
+            Data::Literal(lit) => lit.as_type(),
+            Data::Pointer(_) => ResolvedType::UnsignedInteger(IntegerBits::SixtyFour),
+        })
     }
 
     /// When generating code, sometimes a hard-coded data pointer is needed to reference
     /// static values that have a length longer than one word.
-    /// This method appends pointers to the end of the data section (thus, not altering the data
-    /// offsets of previous data).
-    /// `pointer_value` is in _bytes_ and refers to the offset from instruction start to the data
-    /// in question.
-    pub(crate) fn append_pointer(&mut self, pointer_value: u64) -> DataId {
-        let pointer_as_data = Literal::new_pointer_literal(pointer_value);
-        self.insert_data_value(&pointer_as_data)
+    /// This method returns an entry holding the offset in bytes of `data_id` from the start of the
+    /// data section. Pointers to the same data share a single entry, and since they are word-sized
+    /// they do not alter the offsets of the data they point to.
+    pub(crate) fn append_pointer(&mut self, data_id: &DataId) -> DataId {
+        if let Some(ix) = self.pointers.get(&data_id.0) {
+            return DataId(*ix);
+        }
+        let ix = self.push(Data::Pointer(data_id.clone()));
+        self.pointers.insert(data_id.0, ix);
+        DataId(ix)
     }
 
     /// Given any data in the form of a [Literal] (using this type mainly because it includes type
@@ -60,14 +135,13 @@ impl DataSection {
     /// [DataId].
     pub(crate) fn insert_data_value(&mut self, data: &Literal) -> DataId {
         // if there is an identical data value, use the same id
-        match self.value_pairs.iter().position(|x| x == data) {
-            Some(num) => DataId(num as u32),
-            None => {
-                self.value_pairs.push(data.clone());
-                // the index of the data section where the value is stored
-                DataId((self.value_pairs.len() - 1) as u32)
-            }
+        let key = (data.as_type().is_copy_type(), data.to_bytes());
+        if let Some(ix) = self.literals.get(&key) {
+            return DataId(*ix);
         }
+        let ix = self.push(Data::Literal(data.clone()));
+        self.literals.insert(key, ix);
+        DataId(ix)
     }
 }
 
@@ -76,26 +150,99 @@ impl fmt::Display for DataSection {
         let mut data_buf = String::new();
         for (ix, data) in self.value_pairs.iter().enumerate() {
             let data_val = match data {
-                Literal::U8(num) => format!(".u8 {:#04x}", num),
-                Literal::U16(num) => format!(".u16 {:#04x}", num),
-                Literal::U32(num) => format!(".u32 {:#04x}", num),
-                Literal::U64(num) => format!(".u64 {:#04x}", num),
-                Literal::Numeric(num) => format!(".u64 {:#04x}", num),
-                Literal::Boolean(b) => format!(".bool {}", if *b { "0x01" } else { "0x00" }),
-                Literal::String(st) => format!(".str \"{}\"", st.as_str()),
-                Literal::Byte(b) => format!(".byte {:#08b}", b),
-                Literal::B256(b) => format!(
+                Data::Literal(Literal::U8(num)) => format!(".u8 {:#04x}", num),
+                Data::Literal(Literal::U16(num)) => format!(".u16 {:#04x}", num),
+                Data::Literal(Literal::U32(num)) => format!(".u32 {:#04x}", num),
+                Data::Literal(Literal::U64(num)) => format!(".u64 {:#04x}", num),
+                Data::Literal(Literal::Numeric(num)) => format!(".u64 {:#04x}", num),
+                Data::Literal(Literal::Boolean(b)) => {
+                    format!(".bool {}", if *b { "0x01" } else { "0x00" })
+                }
+                Data::Literal(Literal::String(st)) => format!(".str \"{}\"", st.as_str()),
+                Data::Literal(Literal::Byte(b)) => format!(".byte {:#08b}", b),
+                Data::Literal(Literal::B256(b)) => format!(
                     ".b256 0x{}",
                     b.iter()
                         .map(|x| format!("{:02x}", x))
                         .collect::<Vec<_>>()
                         .join("")
                 ),
+                Data::Pointer(id) => format!(".ptr {}", id),
             };
             let data_label = DataId(ix as u32);
             writeln!(data_buf, "{} {}", data_label, data_val)?;
         }
 
-        write!(f, ".data:\n{}", data_buf)
+        let num_words = self
+            .value_pairs
+            .iter()
+            .filter(|data| data.is_word_sized())
+            .count();
+        let num_bytes: usize = self.value_pairs.iter().map(Data::size_in_bytes).sum();
+        writeln!(
+            f,
+            ".data:\n{};; {} entries ({} word-sized), {} bytes",
+            data_buf,
+            self.value_pairs.len(),
+            num_words,
+            num_bytes
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_literals_are_interned() {
+        let mut data_section = DataSection::default();
+        let one = data_section.insert_data_value(&Literal::U64(1));
+        let b256 = data_section.insert_data_value(&Literal::B256([1; 32]));
+        assert_eq!(
+            data_section.insert_data_value(&Literal::Numeric(1)).0,
+            one.0
+        );
+        assert_eq!(
+            data_section.insert_data_value(&Literal::Boolean(true)).0,
+            one.0
+        );
+        assert_eq!(
+            data_section.insert_data_value(&Literal::B256([1; 32])).0,
+            b256.0
+        );
+        assert_eq!(data_section.value_pairs.len(), 2);
+
+        let pointer = data_section.append_pointer(&b256);
+        assert_eq!(data_section.append_pointer(&b256).0, pointer.0);
+        assert_eq!(data_section.value_pairs.len(), 3);
+    }
+
+    #[test]
+    fn word_sized_entries_come_first() {
+        let mut data_section = DataSection::default();
+        let b256 = data_section.insert_data_value(&Literal::B256([2; 32]));
+        let word = data_section.insert_data_value(&Literal::U64(42));
+        let pointer = data_section.append_pointer(&b256);
+        assert_eq!(data_section.offset_to_id(&word), 0);
+        assert_eq!(data_section.offset_to_id(&pointer), 8);
+        assert_eq!(data_section.offset_to_id(&b256), 16);
+
+        let bytes = data_section.serialize_to_bytes();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(bytes[0..8], 42u64.to_be_bytes());
+        assert_eq!(bytes[8..16], 16u64.to_be_bytes());
+        assert_eq!(bytes[16..48], [2; 32]);
+
+        // Another word moves the larger entries along, but not the other words.
+        let other_word = data_section.insert_data_value(&Literal::U64(7));
+        assert_eq!(data_section.offset_to_id(&word), 0);
+        assert_eq!(data_section.offset_to_id(&pointer), 8);
+        assert_eq!(data_section.offset_to_id(&other_word), 16);
+        assert_eq!(data_section.offset_to_id(&b256), 24);
+        assert_eq!(
+            data_section.serialize_to_bytes()[8..16],
+            24u64.to_be_bytes()
+        );
     }
 }
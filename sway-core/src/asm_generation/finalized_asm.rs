@@ -1,7 +1,10 @@
 use super::{DataSection, InstructionSet};
-use crate::asm_lang::allocated_ops::AllocatedOpcode;
+use crate::asm_lang::{
+    allocated_ops::{AllocatedOpcode, AllocatedRegister},
+    ConstantRegister, DebugLocation,
+};
 use crate::error::*;
-use crate::source_map::SourceMap;
+use crate::source_map::{SourceMap, VariableLocation};
 
 use sway_types::span::Span;
 
@@ -105,6 +108,8 @@ fn to_bytecode_mut(
                 {
                     acc + 8
                 }
+                // Long jumps load their target from the data section before jumping.
+                AllocatedOpcode::LongJump(..) | AllocatedOpcode::LongJumpIfNotEq(..) => acc + 8,
                 _ => acc + 4,
             })
             + 4;
@@ -139,6 +144,29 @@ fn to_bytecode_mut(
         }
     }
 
+    for var in &program_section.debug_variables {
+        let location = match &var.location {
+            DebugLocation::Memory {
+                base: AllocatedRegister::Constant(ConstantRegister::StackStartPointer),
+                offset,
+            } => VariableLocation::Stack { offset: *offset },
+            DebugLocation::Memory { base, offset } => VariableLocation::Memory {
+                register: base.to_register_id() as u8,
+                offset: *offset,
+            },
+            DebugLocation::Register(register) => VariableLocation::Register {
+                register: register.to_register_id() as u8,
+            },
+        };
+        source_map.insert_variable(
+            &var.name,
+            &var.ty,
+            &var.span,
+            location,
+            var.scope.start as usize..var.scope.end as usize,
+        );
+    }
+
     let mut data_section = data_section.serialize_to_bytes();
 
     buf.append(&mut data_section);
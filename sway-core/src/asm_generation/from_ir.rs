@@ -8,7 +8,11 @@
 // - AsmNamespace is tied to data structures from other stages like Ident and Literal.
 
 use fuel_crypto::Hasher;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    sync::Arc,
+};
 
 use crate::{
     asm_generation::{
@@ -17,13 +21,13 @@ use crate::{
         AbstractInstructionSet, DataId, DataSection, SwayAsmSet,
     },
     asm_lang::{
-        virtual_register::*, Label, Op, VirtualImmediate12, VirtualImmediate18, VirtualImmediate24,
-        VirtualOp,
+        virtual_register::*, DebugLocation, DebugVariable, Label, Op, OrganizationalOp,
+        VirtualImmediate12, VirtualImmediate18, VirtualImmediate24, VirtualOp,
     },
     error::*,
     metadata::MetadataManager,
     parse_tree::Literal,
-    BuildConfig,
+    BuildConfig, OptLevel,
 };
 
 use sway_ir::*;
@@ -46,8 +50,9 @@ pub fn compile_ir_to_asm(
     // of libraries and link against them, rather than recompile everything each time.
     assert!(ir.module_iter().count() == 1);
     let module = ir.module_iter().next().unwrap();
+    let opt_level = build_config.map(|cfg| cfg.opt_level).unwrap_or_default();
     let (data_section, mut ops, mut reg_seqr) = check!(
-        compile_module_to_asm(reg_seqr, ir, module),
+        compile_module_to_asm(reg_seqr, ir, module, opt_level),
         return err(warnings, errors),
         warnings,
         errors
@@ -75,8 +80,9 @@ pub fn compile_ir_to_asm(
 
     let finalized_asm = asm
         .remove_unnecessary_jumps()
+        .optimize(opt_level)
         .allocate_registers(&mut reg_seqr)
-        .optimize();
+        .optimize(opt_level);
 
     if build_config
         .map(|cfg| cfg.print_finalized_asm)
@@ -95,21 +101,63 @@ pub fn compile_ir_to_asm(
     ok(finalized_asm, warnings, errors)
 }
 
+/// The names and source locations of all the functions in the IR, including those which have
+/// since been inlined.  Once inlined, the instructions of a function keep the source locations
+/// from its body, so these allow the bytecode to be attributed back to the Sway functions.
+pub(crate) fn function_spans(ir: &Context) -> Vec<(String, Span)> {
+    let mut md_mgr = MetadataManager::default();
+    ir.module_iter()
+        .flat_map(|module| module.function_iter(ir))
+        .filter_map(|function| {
+            md_mgr
+                .md_to_span(ir, ir.functions[function.0].metadata)
+                .map(|span| (function.get_name(ir).to_owned(), span))
+        })
+        .collect()
+}
+
+/// The functions whose addresses are used by `function`.  Calls through these addresses which
+/// can't be resolved statically aren't inlined, so the functions must be compiled on their own to
+/// be called with a register jump.
+pub(crate) fn indirect_callees(context: &Context, function: Function) -> Vec<Function> {
+    let instruction = |value: Value| match &context.values[value.0].value {
+        ValueDatum::Instruction(instruction) => Some(instruction),
+        _otherwise => None,
+    };
+    let operands = function
+        .instruction_iter(context)
+        .filter_map(|(_, value)| instruction(value))
+        .flat_map(|instruction| instruction.get_operands())
+        .collect::<HashSet<_>>();
+    let mut callees = Vec::new();
+    for (_, value) in function.instruction_iter(context) {
+        if let Some(Instruction::FnAddr(callee)) = instruction(value) {
+            if operands.contains(&value) && !callees.contains(callee) {
+                callees.push(*callee);
+            }
+        }
+    }
+    callees
+}
+
 fn compile_module_to_asm(
     reg_seqr: RegisterSequencer,
     context: &Context,
     module: Module,
+    opt_level: OptLevel,
 ) -> CompileResult<(DataSection, Vec<Op>, RegisterSequencer)> {
-    let mut builder = AsmBuilder::new(DataSection::default(), reg_seqr, context);
+    let mut builder = AsmBuilder::new(DataSection::default(), reg_seqr, context, opt_level);
     match module.get_kind(context) {
         Kind::Script => {
-            // We can't do function calls yet, so we expect everything to be inlined into `main`.
+            // Everything is inlined into `main`, except for the functions called through function
+            // pointers which can't be resolved statically.
             let function = module
                 .function_iter(context)
                 .find(|func| &context.functions[func.0].name == "main")
                 .expect("Can't find main function!");
             builder
-                .compile_function(function)
+                .compile_function(function, false)
+                .flat_map(|_| builder.compile_indirect_callees())
                 .flat_map(|_| builder.finalize())
         }
         Kind::Contract => {
@@ -124,7 +172,7 @@ fn compile_module_to_asm(
                     let selector = function.get_selector(context).unwrap();
                     let label = builder.add_label();
                     check!(
-                        builder.compile_function(function),
+                        builder.compile_function(function, false),
                         return err(warnings, errors),
                         warnings,
                         errors
@@ -132,6 +180,12 @@ fn compile_module_to_asm(
                     selectors_and_labels.push((selector, label));
                 }
             }
+            check!(
+                builder.compile_indirect_callees(),
+                return err(warnings, errors),
+                warnings,
+                errors
+            );
             let (mut data_section, mut funcs_bytecode, mut reg_seqr) = check!(
                 builder.finalize(),
                 return err(warnings, errors),
@@ -167,6 +221,11 @@ macro_rules! size_bytes_round_up_to_word_alignment {
     };
 }
 
+// The number of live IR values above which constants are no longer kept in registers for reuse.
+// This leaves plenty of room for the temporaries needed to compile each instruction.
+const CONSTANT_REUSE_PRESSURE_LIMIT: usize =
+    compiler_constants::NUM_ALLOCATABLE_REGISTERS as usize / 2;
+
 struct AsmBuilder<'ir> {
     // Data section is used by the rest of code gen to layout const memory.
     data_section: DataSection,
@@ -185,16 +244,55 @@ struct AsmBuilder<'ir> {
     // Stack base register, copied from $SP at the start, but only if we have stack storage.
     stack_base_reg: Option<VirtualRegister>,
 
+    // Constants which have been loaded into a register, by their data section entry, along with
+    // the block they were loaded in.  See `reuse_or_initialise_constant()`.
+    const_reg_map: HashMap<DataId, (Block, VirtualRegister)>,
+
+    // The block and instruction being compiled, and the analyses of the function they're in.
+    current_instr: Option<(Block, Value)>,
+    dom_tree: Option<Rc<DomTree>>,
+    liveness: Option<Rc<Liveness>>,
+
     // IR context we're compiling.
     context: &'ir Context,
 
+    // Constants are only reused between instructions when optimizing.
+    opt_level: OptLevel,
+
     // Metadata manager for converting metadata to Spans, etc.
     md_mgr: MetadataManager,
 
+    // Functions which are called through function pointers, with the labels of their entries, in
+    // the order their addresses are first taken.  See `compile_call_indirect()`.
+    indirect_callees: Vec<(Function, Label)>,
+
+    // The registers through which the arguments, the return address and the return value are
+    // passed to and from the functions called through function pointers.
+    call_arg_regs: Vec<VirtualRegister>,
+    call_return_address_reg: VirtualRegister,
+    call_return_value_reg: VirtualRegister,
+
+    // Where to return to, when compiling a function which is called through function pointers.
+    return_site: Option<ReturnSite>,
+
+    // The word offsets of the stack buffers into which calls through function pointers copy
+    // their aggregate return values, see `compile_call_indirect()`.
+    return_buffers: HashMap<Value, u64>,
+
     // Final resulting VM bytecode ops.
     bytecode: Vec<Op>,
 }
 
+// A function called through function pointers frees its locals before jumping back to the
+// return address.  Aggregates are returned by reference, so they're first copied to a buffer
+// owned by the caller, whose address is passed in the return value register.
+#[derive(Clone, Debug)]
+struct ReturnSite {
+    return_address_reg: VirtualRegister,
+    return_buffer_reg: Option<VirtualRegister>,
+    locals_size: u64,
+}
+
 // NOTE: For stack storage we need to be aware:
 // - sizes are in bytes; CFEI reserves in bytes.
 // - offsets are in 64-bit words; LW/SW reads/writes to word offsets. XXX Wrap in a WordOffset struct.
@@ -211,7 +309,14 @@ pub enum StateAccessType {
 }
 
 impl<'ir> AsmBuilder<'ir> {
-    fn new(data_section: DataSection, reg_seqr: RegisterSequencer, context: &'ir Context) -> Self {
+    fn new(
+        data_section: DataSection,
+        mut reg_seqr: RegisterSequencer,
+        context: &'ir Context,
+        opt_level: OptLevel,
+    ) -> Self {
+        let call_return_address_reg = reg_seqr.next();
+        let call_return_value_reg = reg_seqr.next();
         AsmBuilder {
             data_section,
             reg_seqr,
@@ -219,8 +324,19 @@ impl<'ir> AsmBuilder<'ir> {
             reg_map: HashMap::new(),
             ptr_map: HashMap::new(),
             stack_base_reg: None,
+            const_reg_map: HashMap::new(),
+            current_instr: None,
+            dom_tree: None,
+            liveness: None,
             context,
+            opt_level,
             md_mgr: MetadataManager::default(),
+            indirect_callees: Vec::new(),
+            call_arg_regs: Vec::new(),
+            call_return_address_reg,
+            call_return_value_reg,
+            return_site: None,
+            return_buffers: HashMap::new(),
             bytecode: Vec::new(),
         }
     }
@@ -252,6 +368,7 @@ impl<'ir> AsmBuilder<'ir> {
                 let (_, val) = function.args_iter(self.context).next().unwrap();
                 let single_arg_reg = self.value_to_register(val);
                 self.read_args_value_from_frame(&single_arg_reg);
+                self.debug_argument(val, &single_arg_reg);
             }
 
             // Otherwise, the args are bundled together and pointed to by the base register.
@@ -326,12 +443,50 @@ impl<'ir> AsmBuilder<'ir> {
                         });
                     }
 
+                    self.debug_argument(val, &current_arg_reg);
                     arg_word_offset += size_bytes_in_words!(arg_type_size_bytes);
                 }
             }
         }
     }
 
+    // Describe the argument `val` in `arg_reg` to debuggers.  Copy types are held in the register
+    // itself, and others are referred to by the address in it.
+    fn debug_argument(&mut self, val: &Value, arg_reg: &VirtualRegister) {
+        let var_decl = self
+            .md_mgr
+            .val_to_var_decl(self.context, *val)
+            .zip(self.md_mgr.val_to_span(self.context, *val));
+        if let Some(((name, ty, _), span)) = var_decl {
+            let location = if val.get_type(self.context).unwrap().is_copy_type() {
+                DebugLocation::Register(arg_reg.clone())
+            } else {
+                DebugLocation::Memory {
+                    base: arg_reg.clone(),
+                    offset: 0,
+                }
+            };
+            self.bytecode.push(Op::debug_variable(DebugVariable {
+                name,
+                ty,
+                span,
+                location,
+                lexical_scope: None,
+                scope: 0..0,
+            }));
+        }
+    }
+
+    // The register holding the address above which the locals are kept, for debuggers.  Entry
+    // functions keep them above `$ssp`, and functions called through function pointers above
+    // the `$sp` they were entered with, which is saved in the locals base register.
+    fn locals_debug_base(&self) -> VirtualRegister {
+        match (&self.return_site, &self.stack_base_reg) {
+            (Some(_), Some(base_reg)) => base_reg.clone(),
+            _otherwise => VirtualRegister::Constant(ConstantRegister::StackStartPointer),
+        }
+    }
+
     // Read the argument(s) base from the call frame.
     fn read_args_value_from_frame(&mut self, reg: &VirtualRegister) {
         self.bytecode.push(Op {
@@ -346,7 +501,8 @@ impl<'ir> AsmBuilder<'ir> {
         });
     }
 
-    fn add_locals(&mut self, function: Function) {
+    // Returns the number of bytes allocated on the stack for the locals.
+    fn add_locals(&mut self, function: Function) -> u64 {
         // If they're immutable and have a constant initialiser then they go in the data section.
         // Otherwise they go in runtime allocated space, either a register or on the stack.
         //
@@ -390,8 +546,24 @@ impl<'ir> AsmBuilder<'ir> {
             }
         }
 
+        // Reserve a buffer for the aggregate returned by each call through a function pointer.
+        self.return_buffers.clear();
+        for block in function.block_iter(self.context) {
+            for instr_val in block.instruction_iter(self.context) {
+                if let ValueDatum::Instruction(Instruction::CallIndirect { return_type, .. }) =
+                    &self.context.values[instr_val.0].value
+                {
+                    if !return_type.is_copy_type() {
+                        self.return_buffers.insert(instr_val, stack_base);
+                        stack_base +=
+                            size_bytes_in_words!(ir_type_size_in_bytes(self.context, return_type));
+                    }
+                }
+            }
+        }
+
         // Reserve space on the stack for ALL our locals which require it.
-        if !self.ptr_map.is_empty() {
+        if !self.ptr_map.is_empty() || !self.return_buffers.is_empty() {
             let base_reg = self.reg_seqr.next();
             self.bytecode.push(Op::unowned_register_move_comment(
                 base_reg.clone(),
@@ -412,6 +584,81 @@ impl<'ir> AsmBuilder<'ir> {
             }
             self.stack_base_reg = Some(base_reg);
         }
+        stack_base * 8
+    }
+
+    // Read the arguments, the return address and, for aggregate return values, the address of the
+    // caller's return buffer of a function which is called through function pointers from the
+    // registers they're passed in.
+    fn compile_indirect_callee_args(&mut self, function: Function, locals_size: u64) -> ReturnSite {
+        for (ix, (name, val)) in function.args_iter(self.context).enumerate() {
+            let arg_reg = self.value_to_register(val);
+            let call_arg_reg = self.call_arg_reg(ix);
+            self.bytecode.push(Op::unowned_register_move_comment(
+                arg_reg.clone(),
+                call_arg_reg,
+                format!("get arg {}", name),
+            ));
+            self.debug_argument(val, &arg_reg);
+        }
+        let return_address_reg = self.reg_seqr.next();
+        self.bytecode.push(Op::unowned_register_move_comment(
+            return_address_reg.clone(),
+            self.call_return_address_reg.clone(),
+            "save return address",
+        ));
+        let return_buffer_reg = if self.context.functions[function.0]
+            .return_type
+            .is_copy_type()
+        {
+            None
+        } else {
+            let return_buffer_reg = self.reg_seqr.next();
+            self.bytecode.push(Op::unowned_register_move_comment(
+                return_buffer_reg.clone(),
+                self.call_return_value_reg.clone(),
+                "save return buffer address",
+            ));
+            Some(return_buffer_reg)
+        };
+        ReturnSite {
+            return_address_reg,
+            return_buffer_reg,
+            locals_size,
+        }
+    }
+
+    // The register in which argument number `ix` is passed to functions called through function
+    // pointers.
+    fn call_arg_reg(&mut self, ix: usize) -> VirtualRegister {
+        while self.call_arg_regs.len() <= ix {
+            let reg = self.reg_seqr.next();
+            self.call_arg_regs.push(reg);
+        }
+        self.call_arg_regs[ix].clone()
+    }
+
+    // Compile the functions whose addresses are taken by the code compiled so far, and in turn by
+    // those functions.  Each is entered at its label by a register jump, see
+    // `compile_call_indirect()`.
+    fn compile_indirect_callees(&mut self) -> CompileResult<()> {
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+        let mut ix = 0;
+        while let Some((function, label)) = self.indirect_callees.get(ix).cloned() {
+            self.bytecode.push(Op::unowned_jump_label_comment(
+                label,
+                format!("function: {}", function.get_name(self.context)),
+            ));
+            check!(
+                self.compile_function(function, true),
+                return err(warnings, errors),
+                warnings,
+                errors
+            );
+            ix += 1;
+        }
+        ok((), warnings, errors)
     }
 
     fn add_block_label(&mut self, block: Block) {
@@ -436,8 +683,12 @@ impl<'ir> AsmBuilder<'ir> {
         )
     }
 
-    fn compile_function(&mut self, function: Function) -> CompileResult<()> {
-        if function.has_selector(self.context) {
+    fn compile_function(
+        &mut self,
+        function: Function,
+        is_indirect_callee: bool,
+    ) -> CompileResult<()> {
+        if !is_indirect_callee && function.has_selector(self.context) {
             // Add a comment noting that this is a named contract method.
             self.bytecode.push(Op::new_comment(format!(
                 "contract method: {}, selector: 0x{}",
@@ -451,14 +702,35 @@ impl<'ir> AsmBuilder<'ir> {
             )));
         }
 
+        // Constants can't be reused across functions, and without the analyses they're reloaded
+        // at every use.  A function may be compiled both as an entry and as an indirect callee, so
+        // its values and blocks are given new registers and labels each time.
+        self.const_reg_map.clear();
+        self.reg_map.clear();
+        self.label_map.clear();
+        self.current_instr = None;
+        if self.opt_level == OptLevel::Opt0 {
+            self.dom_tree = None;
+            self.liveness = None;
+        } else {
+            self.dom_tree = Some(function.dominator_tree(self.context));
+            self.liveness = Some(function.liveness(self.context));
+        }
+
         // Compile instructions.
-        self.add_locals(function);
-        self.compile_fn_args(function);
+        let locals_size = self.add_locals(function);
+        if is_indirect_callee {
+            self.return_site = Some(self.compile_indirect_callee_args(function, locals_size));
+        } else {
+            self.compile_fn_args(function);
+            self.return_site = None;
+        }
         let mut warnings = Vec::new();
         let mut errors = Vec::new();
         for block in function.block_iter(self.context) {
             self.add_block_label(block);
             for instr_val in block.instruction_iter(self.context) {
+                self.current_instr = Some((block, instr_val));
                 check!(
                     self.compile_instruction(&block, &instr_val),
                     return err(warnings, errors),
@@ -467,6 +739,7 @@ impl<'ir> AsmBuilder<'ir> {
                 );
             }
         }
+        self.bytecode.push(Op::debug_scope_end());
         ok((), warnings, errors)
     }
 
@@ -495,6 +768,9 @@ impl<'ir> AsmBuilder<'ir> {
                     ));
                     return err(warnings, errors);
                 }
+                Instruction::CallIndirect { callee, args, .. } => {
+                    self.compile_call_indirect(instr_val, callee, args)
+                }
                 Instruction::Cmp(pred, lhs_value, rhs_value) => {
                     self.compile_cmp(instr_val, pred, lhs_value, rhs_value)
                 }
@@ -518,6 +794,14 @@ impl<'ir> AsmBuilder<'ir> {
                 Instruction::ExtractValue {
                     aggregate, indices, ..
                 } => self.compile_extract_value(instr_val, aggregate, indices),
+                Instruction::FnAddr(function) => {
+                    check!(
+                        self.compile_fn_addr(instr_val, function),
+                        return err(warnings, errors),
+                        warnings,
+                        errors
+                    )
+                }
                 Instruction::GetStorageKey => {
                     check!(
                         self.compile_get_storage_key(instr_val),
@@ -637,7 +921,7 @@ impl<'ir> AsmBuilder<'ir> {
                 }
             );
             let arg_reg = initializer
-                .map(|init_val| self.value_to_register(&init_val))
+                .map(|init_val| self.asm_arg_to_register(&init_val))
                 .unwrap_or_else(|| self.reg_seqr.next());
             inline_reg_map.insert(name.as_str(), arg_reg);
         }
@@ -776,6 +1060,69 @@ impl<'ir> AsmBuilder<'ir> {
         self.bytecode.push(Op::jump_to_label(label));
     }
 
+    // Calls through function pointers are converted to direct calls and inlined when the callee is
+    // known.  Any others jump to the address of the callee, after passing the arguments and the
+    // return address in the registers which the callee reads them from, see
+    // `compile_indirect_callee_args()`.  The callee jumps back to the return address with the
+    // return value in another register.  Aggregates are copied by the callee into a buffer in the
+    // caller's locals, whose address is passed in that register, so the callee can free its own.
+    //
+    // The registers of the callee are allocated along with those of its callers, so calls through
+    // function pointers are not reentrant and recursion through them isn't supported.
+    fn compile_call_indirect(&mut self, instr_val: &Value, callee: &Value, args: &[Value]) {
+        let owning_span = self.md_mgr.val_to_span(self.context, *instr_val);
+        let callee_reg = self.value_to_register(callee);
+        for (ix, arg) in args.iter().enumerate() {
+            let arg_reg = self.value_to_register(arg);
+            let call_arg_reg = self.call_arg_reg(ix);
+            self.bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::MOVE(call_arg_reg, arg_reg)),
+                comment: format!("pass arg {}", ix),
+                owning_span: owning_span.clone(),
+            });
+        }
+
+        if let Some(word_offs) = self.return_buffers.get(instr_val).copied() {
+            let base_reg = self.stack_base_reg.as_ref().unwrap().clone();
+            let buffer_reg = self.offset_reg(&base_reg, word_offs * 8, owning_span.clone());
+            self.bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::MOVE(
+                    self.call_return_value_reg.clone(),
+                    buffer_reg,
+                )),
+                comment: "pass return buffer".into(),
+                owning_span: owning_span.clone(),
+            });
+        }
+
+        let return_label = self.reg_seqr.get_label();
+        self.bytecode.push(Op {
+            opcode: Either::Right(OrganizationalOp::LoadLabelOffset(
+                self.call_return_address_reg.clone(),
+                return_label.clone(),
+            )),
+            comment: "return address".into(),
+            owning_span: owning_span.clone(),
+        });
+        self.bytecode.push(Op {
+            opcode: Either::Left(VirtualOp::JMP(callee_reg)),
+            comment: "call through function pointer".into(),
+            owning_span: owning_span.clone(),
+        });
+        self.bytecode.push(Op::unowned_jump_label(return_label));
+
+        let instr_reg = self.reg_seqr.next();
+        self.bytecode.push(Op {
+            opcode: Either::Left(VirtualOp::MOVE(
+                instr_reg.clone(),
+                self.call_return_value_reg.clone(),
+            )),
+            comment: "get return value".into(),
+            owning_span,
+        });
+        self.reg_map.insert(*instr_val, instr_reg);
+    }
+
     fn compile_cmp(
         &mut self,
         instr_val: &Value,
@@ -889,10 +1236,13 @@ impl<'ir> AsmBuilder<'ir> {
         let elem_type = ty.get_elem_type(self.context).unwrap();
         let elem_size = ir_type_size_in_bytes(self.context, &elem_type);
         if elem_type.is_copy_type() {
+            // The index register may be shared with other uses of the index, so mustn't be
+            // modified.
+            let rel_offs_reg = self.reg_seqr.next();
             self.bytecode.push(Op {
                 opcode: Either::Left(VirtualOp::MULI(
-                    index_reg.clone(),
-                    index_reg.clone(),
+                    rel_offs_reg.clone(),
+                    index_reg,
                     VirtualImmediate12 { value: 8 },
                 )),
                 comment: "extract_element relative offset".into(),
@@ -900,7 +1250,11 @@ impl<'ir> AsmBuilder<'ir> {
             });
             let elem_offs_reg = self.reg_seqr.next();
             self.bytecode.push(Op {
-                opcode: Either::Left(VirtualOp::ADD(elem_offs_reg.clone(), base_reg, index_reg)),
+                opcode: Either::Left(VirtualOp::ADD(
+                    elem_offs_reg.clone(),
+                    base_reg,
+                    rel_offs_reg,
+                )),
                 comment: "extract_element absolute offset".into(),
                 owning_span: owning_span.clone(),
             });
@@ -1049,6 +1403,55 @@ impl<'ir> AsmBuilder<'ir> {
         self.reg_map.insert(*instr_val, instr_reg);
     }
 
+    fn compile_fn_addr(&mut self, instr_val: &Value, function: &Function) -> CompileResult<()> {
+        let owning_span = self.md_mgr.val_to_span(self.context, *instr_val);
+        if !self
+            .context
+            .module_iter()
+            .flat_map(|module| module.function_iter(self.context))
+            .any(|func| func == *function)
+        {
+            return err(
+                Vec::new(),
+                vec![CompileError::Internal(
+                    "Function pointer refers to a function which is not in the module.",
+                    owning_span.unwrap_or_else(Self::empty_span),
+                )],
+            );
+        }
+
+        // The address of a function which is only ever called directly, after the inliner has
+        // resolved the calls through it, isn't needed and the function isn't compiled on its own.
+        let (block, _) = self
+            .current_instr
+            .expect("an instruction is always being compiled");
+        if !indirect_callees(self.context, block.get_function(self.context)).contains(function) {
+            return ok((), Vec::new(), Vec::new());
+        }
+
+        // A function pointer is the instruction offset of the entry of the function.
+        let label = match self
+            .indirect_callees
+            .iter()
+            .find(|(callee, _)| callee == function)
+        {
+            Some((_, label)) => label.clone(),
+            None => {
+                let label = self.reg_seqr.get_label();
+                self.indirect_callees.push((*function, label.clone()));
+                label
+            }
+        };
+        let reg = self.reg_seqr.next();
+        self.bytecode.push(Op {
+            opcode: Either::Right(OrganizationalOp::LoadLabelOffset(reg.clone(), label)),
+            comment: "function pointer".into(),
+            owning_span,
+        });
+        self.reg_map.insert(*instr_val, reg);
+        ok((), Vec::new(), Vec::new())
+    }
+
     fn compile_get_storage_key(&mut self, instr_val: &Value) -> CompileResult<()> {
         let warnings: Vec<CompileWarning> = Vec::new();
         let mut errors: Vec<CompileError> = Vec::new();
@@ -1178,10 +1581,13 @@ impl<'ir> AsmBuilder<'ir> {
         let elem_type = ty.get_elem_type(self.context).unwrap();
         let elem_size = ir_type_size_in_bytes(self.context, &elem_type);
         if elem_type.is_copy_type() {
+            // The index register may be shared with other uses of the index, so mustn't be
+            // modified.
+            let rel_offs_reg = self.reg_seqr.next();
             self.bytecode.push(Op {
                 opcode: Either::Left(VirtualOp::MULI(
-                    index_reg.clone(),
-                    index_reg.clone(),
+                    rel_offs_reg.clone(),
+                    index_reg,
                     VirtualImmediate12 { value: 8 },
                 )),
                 comment: "insert_element relative offset".into(),
@@ -1192,7 +1598,7 @@ impl<'ir> AsmBuilder<'ir> {
                 opcode: Either::Left(VirtualOp::ADD(
                     elem_offs_reg.clone(),
                     base_reg.clone(),
-                    index_reg,
+                    rel_offs_reg,
                 )),
                 comment: "insert_element absolute offset".into(),
                 owning_span: owning_span.clone(),
@@ -1501,6 +1907,77 @@ impl<'ir> AsmBuilder<'ir> {
 
     fn compile_ret(&mut self, instr_val: &Value, ret_val: &Value, ret_type: &Type) {
         let owning_span = self.md_mgr.val_to_span(self.context, *instr_val);
+        if let Some(ReturnSite {
+            return_address_reg,
+            return_buffer_reg,
+            locals_size,
+        }) = self.return_site.clone()
+        {
+            // Returning from a call through a function pointer.  Aggregates are returned by
+            // reference, so they're copied out of the locals before freeing them.
+            if let Some(return_buffer_reg) = return_buffer_reg {
+                let ret_reg = self.value_to_register(ret_val);
+                let size_in_bytes = ir_type_size_in_bytes(self.context, ret_type);
+                if size_in_bytes > compiler_constants::TWELVE_BITS {
+                    let size_reg = self.reg_seqr.next();
+                    self.number_to_reg(size_in_bytes, &size_reg, owning_span.clone());
+                    self.bytecode.push(Op {
+                        opcode: Either::Left(VirtualOp::MCP(
+                            return_buffer_reg.clone(),
+                            ret_reg,
+                            size_reg,
+                        )),
+                        comment: "copy return value to caller".into(),
+                        owning_span: owning_span.clone(),
+                    });
+                } else {
+                    self.bytecode.push(Op {
+                        opcode: Either::Left(VirtualOp::MCPI(
+                            return_buffer_reg.clone(),
+                            ret_reg,
+                            VirtualImmediate12 {
+                                value: size_in_bytes as u16,
+                            },
+                        )),
+                        comment: "copy return value to caller".into(),
+                        owning_span: owning_span.clone(),
+                    });
+                }
+                self.bytecode.push(Op {
+                    opcode: Either::Left(VirtualOp::MOVE(
+                        self.call_return_value_reg.clone(),
+                        return_buffer_reg,
+                    )),
+                    comment: "set return value".into(),
+                    owning_span: owning_span.clone(),
+                });
+            } else if !ret_type.eq(self.context, &Type::Unit) {
+                let ret_reg = self.value_to_register(ret_val);
+                self.bytecode.push(Op {
+                    opcode: Either::Left(VirtualOp::MOVE(
+                        self.call_return_value_reg.clone(),
+                        ret_reg,
+                    )),
+                    comment: "set return value".into(),
+                    owning_span: owning_span.clone(),
+                });
+            }
+            if locals_size != 0 {
+                self.bytecode.push(Op {
+                    opcode: Either::Left(VirtualOp::CFSI(VirtualImmediate24 {
+                        value: locals_size as u32,
+                    })),
+                    comment: format!("free {} bytes for all locals", locals_size),
+                    owning_span: owning_span.clone(),
+                });
+            }
+            self.bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::JMP(return_address_reg)),
+                comment: "return to caller".into(),
+                owning_span,
+            });
+            return;
+        }
         if ret_type.eq(self.context, &Type::Unit) {
             // Unit returns should always be zero, although because they can be omitted from
             // functions, the register is sometimes uninitialized. Manually return zero in this
@@ -1735,6 +2212,10 @@ impl<'ir> AsmBuilder<'ir> {
         let stored_reg = self.value_to_register(stored_val);
         let is_aggregate_ptr = ptr.is_aggregate_ptr(self.context);
         let owning_span = self.md_mgr.val_to_span(self.context, *instr_val);
+        let var_decl = self
+            .md_mgr
+            .val_to_var_decl(self.context, *instr_val)
+            .zip(owning_span.clone());
         match self.ptr_map.get(&ptr) {
             None => unreachable!("Bug! Trying to store to an unknown pointer."),
             Some(storage) => match storage {
@@ -1863,6 +2344,24 @@ impl<'ir> AsmBuilder<'ir> {
                             });
                         }
                     }
+
+                    // The variable is in scope from the store of its initial value.
+                    if let Some(((name, ty, scope_end), span)) = var_decl {
+                        let lexical_scope = scope_end.and_then(|end| {
+                            Span::new(span.src().clone(), span.start(), end, span.path().cloned())
+                        });
+                        self.bytecode.push(Op::debug_variable(DebugVariable {
+                            name,
+                            ty,
+                            span,
+                            location: DebugLocation::Memory {
+                                base: self.locals_debug_base(),
+                                offset: word_offs * 8,
+                            },
+                            lexical_scope,
+                            scope: 0..0,
+                        }));
+                    }
                 }
             },
         };
@@ -1917,14 +2416,78 @@ impl<'ir> AsmBuilder<'ir> {
         // `self.reg_map` will have a potentially uninitialised register.
         //
         // By not putting it in the map we recreate the `LW` each time it's
-        // used, which also isn't ideal.  A better solution is to put this
-        // initialisation into the IR itself, and allow for analysis there
-        // to determine when it may be initialised and/or reused.
+        // used, which also isn't ideal.  `reuse_or_initialise_constant()`
+        // uses the dominator tree to reuse the register where that is safe.
 
         // Return register.
         reg
     }
 
+    // Get a copy type constant into a register, reusing the register it was already loaded into
+    // if that load dominates the current instruction, and so must have been executed.
+    //
+    // Keeping a constant in a register for reuse lengthens its live range, and the register
+    // allocator can't spill, so new loads are only kept for reuse while the register pressure is
+    // low.  Registers holding constants must never be written to, see `asm_arg_to_register()`.
+    fn reuse_or_initialise_constant(
+        &mut self,
+        constant: &Constant,
+        span: Option<Span>,
+    ) -> VirtualRegister {
+        let (block, data_id) = match (&self.current_instr, &self.dom_tree, &self.liveness) {
+            (Some((block, instr_val)), Some(dom_tree), Some(liveness)) => {
+                let data_id = self
+                    .data_section
+                    .insert_data_value(&ir_constant_to_ast_literal(constant));
+                if let Some((load_block, reg)) = self.const_reg_map.get(&data_id) {
+                    if dom_tree.dominates(load_block, block) {
+                        return reg.clone();
+                    }
+                }
+                if liveness.register_pressure(instr_val) + self.const_reg_map.len()
+                    >= CONSTANT_REUSE_PRESSURE_LIMIT
+                {
+                    return self.initialise_non_aggregate_type(constant, span);
+                }
+                (*block, data_id)
+            }
+            _otherwise => return self.initialise_non_aggregate_type(constant, span),
+        };
+
+        let reg = self.initialise_non_aggregate_type(constant, span);
+        self.const_reg_map.insert(data_id, (block, reg.clone()));
+        reg
+    }
+
+    // ASM blocks may write to their argument registers, so an argument mustn't share a register
+    // with a constant which may be reused.
+    fn asm_arg_to_register(&mut self, init_val: &Value) -> VirtualRegister {
+        if let ValueDatum::Constant(constant) = &self.context.values[init_val.0].value {
+            if constant.ty.is_copy_type() {
+                let span = self.md_mgr.val_to_span(self.context, *init_val);
+                return self.initialise_non_aggregate_type(constant, span);
+            }
+        }
+
+        // The value may still be an alias of a reused constant, e.g., via a `bitcast`.
+        let reg = self.value_to_register(init_val);
+        if self
+            .const_reg_map
+            .values()
+            .any(|(_, const_reg)| const_reg == &reg)
+        {
+            let copy_reg = self.reg_seqr.next();
+            self.bytecode.push(Op::unowned_register_move_comment(
+                copy_reg.clone(),
+                reg,
+                "copy constant for asm block",
+            ));
+            copy_reg
+        } else {
+            reg
+        }
+    }
+
     fn initialise_aggregate_type(
         &mut self,
         constant: &Constant,
@@ -1990,11 +2553,10 @@ impl<'ir> AsmBuilder<'ir> {
                     ValueDatum::Constant(constant) => {
                         let span = self.md_mgr.val_to_span(self.context, *value);
                         match &value_type {
-                            Type::Unit
-                            | Type::Bool
-                            | Type::Uint(_)
-                            | Type::B256
-                            | Type::String(_) => {
+                            Type::Unit | Type::Bool | Type::Uint(_) => {
+                                Some(self.reuse_or_initialise_constant(constant, span))
+                            }
+                            Type::B256 | Type::String(_) => {
                                 Some(self.initialise_non_aggregate_type(constant, span))
                             }
                             Type::Array(_) | Type::Struct(_) | Type::Union(_) => {
@@ -2414,6 +2976,7 @@ pub fn aggregate_idcs_to_field_layout(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{asm_lang::allocated_ops::AllocatedOpcode, source_map::SourceMap};
     use sway_ir::parser::parse;
 
     use std::path::PathBuf;
@@ -2465,6 +3028,77 @@ mod tests {
             panic!();
         }
     }
+
+    #[test]
+    fn long_jumps_in_huge_function() {
+        // A `cbr` is compiled to a JNZI, which can only jump to the first 2^18 instructions. Put
+        // the true branch after a block which is larger than that.
+        let noops = "            noop\n".repeat(1 << 18);
+        let input = format!(
+            "script {{
+    fn main() -> u64 {{
+        entry:
+        v0 = const bool true
+        cbr v0, block1, block0
+
+        block0:
+        v1 = asm(r1) -> u64 r1 {{
+{}        }}
+        ret u64 v1
+
+        block1:
+        v2 = const u64 42
+        ret u64 v2
+    }}
+}}",
+            noops
+        );
+
+        let ir = parse(&input).expect("parsed ir");
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+        let mut asm = compile_ir_to_asm(&ir, None).unwrap(&mut warnings, &mut errors);
+        assert!(warnings.is_empty() && errors.is_empty());
+
+        let (ops, data_section) = match &asm {
+            FinalizedAsm::ScriptMain {
+                program_section,
+                data_section,
+            } => (&program_section.ops, data_section),
+            _ => unreachable!(),
+        };
+
+        // Find the offset of every instruction, and the target of the long jump.
+        let mut offset = 0;
+        let mut offsets = HashMap::new();
+        let mut long_jump_target = None;
+        for (ix, op) in ops.iter().enumerate() {
+            offsets.insert(offset, ix);
+            offset += match &op.opcode {
+                AllocatedOpcode::LWDataId(_, data_id)
+                    if !data_section.type_of_data(data_id).unwrap().is_copy_type() =>
+                {
+                    2
+                }
+                AllocatedOpcode::LongJumpIfNotEq(_, _, target) => {
+                    long_jump_target = Some(*target);
+                    2
+                }
+                AllocatedOpcode::DataSectionOffsetPlaceholder | AllocatedOpcode::LongJump(_) => 2,
+                _ => 1,
+            };
+        }
+
+        // The long jump must land after the block of noops, on the code of the true branch.
+        let target = long_jump_target.expect("cbr should have been realized as a long jump");
+        assert!(target > compiler_constants::EIGHTEEN_BITS);
+        let target_ix = offsets[&target];
+        assert!(matches!(ops[target_ix - 1].opcode, AllocatedOpcode::RET(_)));
+
+        asm.to_bytecode_mut(&mut SourceMap::new())
+            .unwrap(&mut warnings, &mut errors);
+        assert!(warnings.is_empty() && errors.is_empty());
+    }
 }
 
 // =================================================================================================
@@ -1,10 +1,14 @@
-use crate::asm_lang::allocated_ops::AllocatedOp;
+use crate::asm_lang::{
+    allocated_ops::{AllocatedOp, AllocatedRegister},
+    DebugVariable,
+};
 use std::fmt;
 
 /// An [InstructionSet] is produced by allocating registers on an [AbstractInstructionSet].
 #[derive(Clone)]
 pub struct InstructionSet {
     pub(crate) ops: Vec<AllocatedOp>,
+    pub(crate) debug_variables: Vec<DebugVariable<AllocatedRegister>>,
 }
 
 impl fmt::Display for InstructionSet {
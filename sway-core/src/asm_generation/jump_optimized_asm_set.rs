@@ -1,5 +1,8 @@
-use crate::asm_generation::{
-    AbstractInstructionSet, DataSection, RegisterAllocatedAsmSet, RegisterSequencer,
+use crate::{
+    asm_generation::{
+        peephole, AbstractInstructionSet, DataSection, RegisterAllocatedAsmSet, RegisterSequencer,
+    },
+    OptLevel,
 };
 use std::fmt;
 
@@ -22,6 +25,40 @@ pub enum JumpOptimizedAsmSet {
 }
 
 impl JumpOptimizedAsmSet {
+    /// Runs the peephole optimizer over the virtual ops, unless optimizations are disabled.
+    pub(crate) fn optimize(self, opt_level: OptLevel) -> JumpOptimizedAsmSet {
+        if opt_level == OptLevel::Opt0 {
+            return self;
+        }
+        let optimize = |program_section: AbstractInstructionSet| AbstractInstructionSet {
+            ops: peephole::optimize_virtual_ops(program_section.ops),
+        };
+        match self {
+            JumpOptimizedAsmSet::Library => JumpOptimizedAsmSet::Library,
+            JumpOptimizedAsmSet::ScriptMain {
+                data_section,
+                program_section,
+            } => JumpOptimizedAsmSet::ScriptMain {
+                data_section,
+                program_section: optimize(program_section),
+            },
+            JumpOptimizedAsmSet::PredicateMain {
+                data_section,
+                program_section,
+            } => JumpOptimizedAsmSet::PredicateMain {
+                data_section,
+                program_section: optimize(program_section),
+            },
+            JumpOptimizedAsmSet::ContractAbi {
+                data_section,
+                program_section,
+            } => JumpOptimizedAsmSet::ContractAbi {
+                data_section,
+                program_section: optimize(program_section),
+            },
+        }
+    }
+
     pub(crate) fn allocate_registers(
         self,
         register_sequencer: &mut RegisterSequencer,
@@ -17,6 +17,7 @@ mod finalized_asm;
 pub mod from_ir;
 mod instruction_set;
 mod jump_optimized_asm_set;
+mod peephole;
 mod register_allocated_asm_set;
 pub(crate) mod register_allocator;
 mod register_sequencer;
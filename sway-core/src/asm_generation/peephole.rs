@@ -0,0 +1,929 @@
+//! A peephole optimizer for the generated ASM.
+//!
+//! The optimizer slides a window over consecutive instructions and replaces the window whenever
+//! one of the rules in a table of rewrite rules matches it, until no rule matches anymore. It runs
+//! twice: over the [VirtualOp]s before register allocation, where every value lives in its own
+//! virtual register which makes it easy to tell whether a value is used at all, and over the
+//! [AllocatedOp]s after register allocation, which introduces redundant moves of its own.
+//!
+//! Every ALU instruction, including `MOVE` and `MOVI`, clears `$of` and `$err`. Rewrites are
+//! therefore never applied right before an instruction which reads one of these registers.
+
+use super::{compiler_constants::TWELVE_BITS, DataSection};
+use crate::asm_lang::{
+    allocated_ops::{AllocatedOp, AllocatedOpcode, AllocatedRegister},
+    ConstantRegister, DebugVariable, Op, OrganizationalOp, VirtualImmediate12, VirtualImmediate18,
+    VirtualImmediate24, VirtualOp, VirtualRegister,
+};
+
+use either::Either;
+use std::collections::{HashMap, HashSet};
+
+/// A rewrite rule of the peephole optimizer.
+struct Rule<O, C> {
+    /// The number of consecutive instructions the rule looks at.
+    window: usize,
+    /// Returns the instructions which replace the window, or `None` if the rule does not apply.
+    /// The rule may also consult some context `C` about the whole program.
+    rewrite: fn(&[O], &C) -> Option<Vec<O>>,
+}
+
+/// The rules applied to the [VirtualOp]s, in order of precedence.
+const VIRTUAL_RULES: &[Rule<VirtualOp, RegisterReads>] = &[
+    // `move $r0 $r0` does nothing.
+    Rule {
+        window: 1,
+        rewrite: virtual_rules::remove_self_move,
+    },
+    // `addi $r0 $r1 i0` is a move, or nothing at all.
+    Rule {
+        window: 1,
+        rewrite: virtual_rules::remove_identity_arithmetic,
+    },
+    // `move $r0 $r1; move $r1 $r0` only needs the first move.
+    Rule {
+        window: 2,
+        rewrite: virtual_rules::remove_move_back,
+    },
+    // `move $r0 $r1; move $r2 $r0` can read `$r1` twice, which may leave the first move dead.
+    Rule {
+        window: 2,
+        rewrite: virtual_rules::propagate_move,
+    },
+    // `sw $r0 $r1 i0; lw $r2 $r0 i0` loads the value which is still in `$r1`.
+    Rule {
+        window: 2,
+        rewrite: virtual_rules::forward_stored_word,
+    },
+    // `movi $r0 i5; add $r1 $r2 $r0` can use `addi`, which may leave the `movi` dead.
+    Rule {
+        window: 2,
+        rewrite: virtual_rules::fold_immediate_operand,
+    },
+    // A `move` or `movi` to a register which is never read is dead.
+    Rule {
+        window: 1,
+        rewrite: virtual_rules::remove_dead_definition,
+    },
+];
+
+/// The rules applied to the [AllocatedOp]s, in order of precedence.
+const ALLOCATED_RULES: &[Rule<AllocatedOpcode, ()>] = &[
+    // `move $r0 $r0` does nothing.
+    Rule {
+        window: 1,
+        rewrite: allocated_rules::remove_self_move,
+    },
+    // `addi $r0 $r1 i0` is a move, or nothing at all.
+    Rule {
+        window: 1,
+        rewrite: allocated_rules::remove_identity_arithmetic,
+    },
+    // `move $r0 $r1; move $r1 $r0` only needs the first move.
+    Rule {
+        window: 2,
+        rewrite: allocated_rules::remove_move_back,
+    },
+    // `sw $r0 $r1 i0; lw $r2 $r0 i0` loads the value which is still in `$r1`.
+    Rule {
+        window: 2,
+        rewrite: allocated_rules::forward_stored_word,
+    },
+];
+
+/// Applies the [VIRTUAL_RULES] to `ops` until none of them matches anymore.
+///
+/// Only windows of consecutive [VirtualOp]s are rewritten, so no window ever spans a label.  The
+/// debug markers don't generate any code though, so they are moved in front of the replacement of
+/// any window they are in rather than getting in the way of the optimizer.
+pub(crate) fn optimize_virtual_ops(mut ops: Vec<Op>) -> Vec<Op> {
+    let mut reads = RegisterReads::new(&ops);
+    loop {
+        let mut changed = false;
+        let mut buf = Vec::with_capacity(ops.len());
+        let mut ix = 0;
+        'ops: while ix < ops.len() {
+            for rule in VIRTUAL_RULES {
+                let (indices, window) = match virtual_window(&ops, ix, rule.window) {
+                    Some(window) => window,
+                    None => continue,
+                };
+                let end = indices[indices.len() - 1] + 1;
+                if next_virtual_op_reads_flags(&ops[end..]) {
+                    continue;
+                }
+                if let Some(replacement) = (rule.rewrite)(&window, &reads) {
+                    window.iter().for_each(|op| reads.remove(op));
+                    replacement.iter().for_each(|op| reads.add(op));
+                    buf.extend(
+                        ops[ix..end]
+                            .iter()
+                            .filter(|op| op.opcode.is_right())
+                            .cloned(),
+                    );
+                    for (offset, opcode) in replacement.into_iter().enumerate() {
+                        let original = &ops[indices[offset.min(indices.len() - 1)]];
+                        buf.push(Op {
+                            opcode: Either::Left(opcode),
+                            comment: original.comment.clone(),
+                            owning_span: original.owning_span.clone(),
+                        });
+                    }
+                    changed = true;
+                    ix = end;
+                    continue 'ops;
+                }
+            }
+            buf.push(ops[ix].clone());
+            ix += 1;
+        }
+        ops = buf;
+        if !changed {
+            return ops;
+        }
+    }
+}
+
+/// Applies the [ALLOCATED_RULES] to `ops` until none of them matches anymore.
+///
+/// The jumps have already been realized to instruction offsets at this point, so no window may
+/// contain the target of a jump past its first instruction, and the targets of all the jumps are
+/// updated after instructions are removed, as are the scopes of the `debug_variables`.
+pub(crate) fn optimize_allocated_ops(
+    mut ops: Vec<AllocatedOp>,
+    data_section: &DataSection,
+    debug_variables: &mut [DebugVariable<AllocatedRegister>],
+) -> Vec<AllocatedOp> {
+    loop {
+        let offsets = instruction_offsets(&ops, data_section);
+        let jump_targets = ops
+            .iter()
+            .filter_map(|op| jump_target(&op.opcode))
+            .collect::<HashSet<_>>();
+
+        // maps the old offset of each instruction to its new offset
+        let mut offset_map: HashMap<u64, u64> = HashMap::new();
+        let mut new_offset = 0;
+        let mut changed = false;
+        let mut buf = Vec::with_capacity(ops.len());
+        let mut ix = 0;
+        'ops: while ix < ops.len() {
+            offset_map.insert(offsets[ix], new_offset);
+            for rule in ALLOCATED_RULES {
+                let end = ix + rule.window;
+                if end > ops.len()
+                    || (ix + 1..end).any(|i| jump_targets.contains(&offsets[i]))
+                    || ops
+                        .get(end)
+                        .map_or(false, |op| allocated_op_reads_flags(&op.opcode))
+                {
+                    continue;
+                }
+                let window = ops[ix..end]
+                    .iter()
+                    .map(|op| op.opcode.clone())
+                    .collect::<Vec<_>>();
+                if let Some(replacement) = (rule.rewrite)(&window, &()) {
+                    for (offset, opcode) in replacement.into_iter().enumerate() {
+                        let original = &ops[(ix + offset).min(end - 1)];
+                        new_offset += instruction_size(&opcode, data_section);
+                        buf.push(AllocatedOp {
+                            opcode,
+                            comment: original.comment.clone(),
+                            owning_span: original.owning_span.clone(),
+                        });
+                    }
+                    changed = true;
+                    ix = end;
+                    continue 'ops;
+                }
+            }
+            new_offset += instruction_size(&ops[ix].opcode, data_section);
+            buf.push(ops[ix].clone());
+            ix += 1;
+        }
+        offset_map.insert(offsets[ops.len()], new_offset);
+
+        if !changed {
+            return buf;
+        }
+
+        // A scope may start or end inside a window which was replaced, in which case it's moved
+        // to the start of the replacement.
+        let new_offset = |offset: u64| {
+            offsets
+                .iter()
+                .rev()
+                .filter(|old_offset| **old_offset <= offset)
+                .find_map(|old_offset| offset_map.get(old_offset))
+                .copied()
+                .unwrap_or(offset)
+        };
+        for var in debug_variables.iter_mut() {
+            var.scope = new_offset(var.scope.start)..new_offset(var.scope.end);
+        }
+
+        ops = buf
+            .into_iter()
+            .map(|op| AllocatedOp {
+                opcode: update_jump_target(op.opcode, &offset_map),
+                ..op
+            })
+            .collect();
+    }
+}
+
+/// The number of times each register is read in a program. A register which is never read holds
+/// a dead value.
+struct RegisterReads(HashMap<VirtualRegister, usize>);
+
+impl RegisterReads {
+    fn new(ops: &[Op]) -> Self {
+        let mut reads = RegisterReads(HashMap::new());
+        for op in ops {
+            let registers = match &op.opcode {
+                Either::Left(op) => op.use_registers().into_iter().collect::<Vec<_>>(),
+                Either::Right(org_op) => org_op.registers().into_iter().collect(),
+            };
+            for reg in registers {
+                *reads.0.entry(reg.clone()).or_default() += 1;
+            }
+        }
+        reads
+    }
+
+    fn add(&mut self, op: &VirtualOp) {
+        for reg in op.use_registers() {
+            *self.0.entry(reg.clone()).or_default() += 1;
+        }
+    }
+
+    fn remove(&mut self, op: &VirtualOp) {
+        for reg in op.use_registers() {
+            if let Some(count) = self.0.get_mut(reg) {
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+
+    fn is_read(&self, reg: &VirtualRegister) -> bool {
+        self.0.get(reg).map_or(false, |count| *count > 0)
+    }
+}
+
+/// Returns the `len` [VirtualOp]s starting at `start`, along with their indices, if there are no
+/// organizational ops among them other than debug markers.
+fn virtual_window(ops: &[Op], start: usize, len: usize) -> Option<(Vec<usize>, Vec<VirtualOp>)> {
+    let mut indices = Vec::with_capacity(len);
+    let mut window = Vec::with_capacity(len);
+    for (ix, op) in ops.iter().enumerate().skip(start) {
+        if window.len() == len {
+            break;
+        }
+        match &op.opcode {
+            Either::Left(op) => {
+                indices.push(ix);
+                window.push(op.clone());
+            }
+            Either::Right(org_op) if !window.is_empty() && is_debug_marker(org_op) => (),
+            Either::Right(_) => return None,
+        }
+    }
+    (window.len() == len).then(|| (indices, window))
+}
+
+fn is_debug_marker(op: &OrganizationalOp) -> bool {
+    matches!(
+        op,
+        OrganizationalOp::DebugVariable(_) | OrganizationalOp::DebugScopeEnd
+    )
+}
+
+/// Whether the first instruction in `ops`, ignoring comments and debug markers, reads `$of` or
+/// `$err`.
+fn next_virtual_op_reads_flags(ops: &[Op]) -> bool {
+    let next = ops.iter().find(|op| match &op.opcode {
+        Either::Right(org_op) => {
+            !matches!(org_op, OrganizationalOp::Comment) && !is_debug_marker(org_op)
+        }
+        Either::Left(_) => true,
+    });
+    let registers = match next.map(|op| &op.opcode) {
+        Some(Either::Left(op)) => op.registers().into_iter().collect::<Vec<_>>(),
+        Some(Either::Right(org_op)) => org_op.registers().into_iter().collect(),
+        None => vec![],
+    };
+    registers.into_iter().any(|reg| match reg {
+        VirtualRegister::Constant(reg) => is_flag_register(reg),
+        VirtualRegister::Virtual(_) => false,
+    })
+}
+
+fn allocated_op_reads_flags(op: &AllocatedOpcode) -> bool {
+    op.registers().into_iter().any(|reg| match reg {
+        AllocatedRegister::Constant(reg) => is_flag_register(reg),
+        AllocatedRegister::Allocated(_) => false,
+    })
+}
+
+fn is_flag_register(reg: &ConstantRegister) -> bool {
+    matches!(reg, ConstantRegister::Overflow | ConstantRegister::Error)
+}
+
+/// Whether a register keeps its value from one instruction to the next, unless the first
+/// instruction writes to it. Registers like `$pc` and `$ggas` change with every instruction.
+fn is_stable_constant(reg: &ConstantRegister) -> bool {
+    use ConstantRegister::*;
+    matches!(
+        reg,
+        Zero | One | StackStartPointer | InstructionStart | DataSectionStart
+    )
+}
+
+/// The offset, in instructions, of each of `ops` followed by the offset of the end of `ops`. This
+/// mirrors the way offsets are computed when the labels are realized.
+fn instruction_offsets(ops: &[AllocatedOp], data_section: &DataSection) -> Vec<u64> {
+    let mut offsets = Vec::with_capacity(ops.len() + 1);
+    let mut offset = 0;
+    for op in ops {
+        offsets.push(offset);
+        offset += instruction_size(&op.opcode, data_section);
+    }
+    offsets.push(offset);
+    offsets
+}
+
+fn instruction_size(op: &AllocatedOpcode, data_section: &DataSection) -> u64 {
+    match op {
+        // A special case for LWDataId which may be 1 or 2 ops, depending on the source size.
+        AllocatedOpcode::LWDataId(_, data_id) => {
+            let type_of_data = data_section.type_of_data(data_id).expect(
+                "Internal miscalculation in data section -- data id did not match up to any actual data",
+            );
+            if type_of_data.is_copy_type() {
+                1
+            } else {
+                2
+            }
+        }
+        AllocatedOpcode::DataSectionOffsetPlaceholder
+        | AllocatedOpcode::LongJump(..)
+        | AllocatedOpcode::LongJumpIfNotEq(..) => 2,
+        _ => 1,
+    }
+}
+
+fn jump_target(op: &AllocatedOpcode) -> Option<u64> {
+    match op {
+        AllocatedOpcode::JI(imm) => Some(imm.value as u64),
+        AllocatedOpcode::JNEI(_, _, imm) => Some(imm.value as u64),
+        AllocatedOpcode::JNZI(_, imm) => Some(imm.value as u64),
+        AllocatedOpcode::LongJump(target)
+        | AllocatedOpcode::LongJumpIfNotEq(_, _, target)
+        | AllocatedOpcode::LoadInstructionOffset(_, target) => Some(*target),
+        _ => None,
+    }
+}
+
+fn update_jump_target(op: AllocatedOpcode, offset_map: &HashMap<u64, u64>) -> AllocatedOpcode {
+    let new_target = |target: u64| {
+        *offset_map
+            .get(&target)
+            .expect("jump targets should be the offset of an instruction")
+    };
+    match op {
+        AllocatedOpcode::JI(imm) => AllocatedOpcode::JI(VirtualImmediate24::new_unchecked(
+            new_target(imm.value as u64),
+            "jump targets only move backwards",
+        )),
+        AllocatedOpcode::JNEI(r1, r2, imm) => AllocatedOpcode::JNEI(
+            r1,
+            r2,
+            VirtualImmediate12::new_unchecked(
+                new_target(imm.value as u64),
+                "jump targets only move backwards",
+            ),
+        ),
+        AllocatedOpcode::JNZI(r1, imm) => AllocatedOpcode::JNZI(
+            r1,
+            VirtualImmediate18::new_unchecked(
+                new_target(imm.value as u64),
+                "jump targets only move backwards",
+            ),
+        ),
+        AllocatedOpcode::LongJump(target) => AllocatedOpcode::LongJump(new_target(target)),
+        AllocatedOpcode::LongJumpIfNotEq(r1, r2, target) => {
+            AllocatedOpcode::LongJumpIfNotEq(r1, r2, new_target(target))
+        }
+        AllocatedOpcode::LoadInstructionOffset(r1, target) => {
+            AllocatedOpcode::LoadInstructionOffset(r1, new_target(target))
+        }
+        op => op,
+    }
+}
+
+/// The rules over [VirtualOp]s.
+mod virtual_rules {
+    use super::*;
+
+    fn is_stable(reg: &VirtualRegister) -> bool {
+        match reg {
+            VirtualRegister::Virtual(_) => true,
+            VirtualRegister::Constant(reg) => is_stable_constant(reg),
+        }
+    }
+
+    pub(super) fn remove_self_move(ops: &[VirtualOp], _: &RegisterReads) -> Option<Vec<VirtualOp>> {
+        match &ops[0] {
+            VirtualOp::MOVE(r1, r2) if r1 == r2 => Some(vec![]),
+            _ => None,
+        }
+    }
+
+    pub(super) fn remove_identity_arithmetic(
+        ops: &[VirtualOp],
+        _: &RegisterReads,
+    ) -> Option<Vec<VirtualOp>> {
+        use VirtualOp::*;
+        let (r1, r2) = match &ops[0] {
+            ADDI(r1, r2, imm)
+            | SUBI(r1, r2, imm)
+            | ORI(r1, r2, imm)
+            | XORI(r1, r2, imm)
+            | SLLI(r1, r2, imm)
+            | SRLI(r1, r2, imm)
+                if imm.value == 0 =>
+            {
+                (r1, r2)
+            }
+            MULI(r1, r2, imm) | DIVI(r1, r2, imm) | EXPI(r1, r2, imm) if imm.value == 1 => (r1, r2),
+            _ => return None,
+        };
+        Some(if r1 == r2 {
+            vec![]
+        } else {
+            vec![MOVE(r1.clone(), r2.clone())]
+        })
+    }
+
+    pub(super) fn remove_move_back(ops: &[VirtualOp], _: &RegisterReads) -> Option<Vec<VirtualOp>> {
+        match (&ops[0], &ops[1]) {
+            (VirtualOp::MOVE(r1, r2), VirtualOp::MOVE(r3, r4))
+                if r1 != r2 && r3 == r2 && r4 == r1 && is_stable(r2) =>
+            {
+                Some(vec![ops[0].clone()])
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn propagate_move(ops: &[VirtualOp], _: &RegisterReads) -> Option<Vec<VirtualOp>> {
+        match (&ops[0], &ops[1]) {
+            (VirtualOp::MOVE(r1, r2), VirtualOp::MOVE(r3, r4))
+                if r1 != r2 && r4 == r1 && is_stable(r2) =>
+            {
+                Some(vec![
+                    ops[0].clone(),
+                    VirtualOp::MOVE(r3.clone(), r2.clone()),
+                ])
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn forward_stored_word(
+        ops: &[VirtualOp],
+        _: &RegisterReads,
+    ) -> Option<Vec<VirtualOp>> {
+        match (&ops[0], &ops[1]) {
+            (VirtualOp::SW(base, src, imm), VirtualOp::LW(dst, base2, imm2))
+                if base == base2
+                    && imm.value == imm2.value
+                    && is_stable(base)
+                    && is_stable(src) =>
+            {
+                Some(vec![
+                    ops[0].clone(),
+                    VirtualOp::MOVE(dst.clone(), src.clone()),
+                ])
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn fold_immediate_operand(
+        ops: &[VirtualOp],
+        _: &RegisterReads,
+    ) -> Option<Vec<VirtualOp>> {
+        use VirtualOp::*;
+        let (reg, imm) = match &ops[0] {
+            MOVI(reg, imm) if imm.value as u64 <= TWELVE_BITS => (
+                reg,
+                VirtualImmediate12::new_unchecked(imm.value as u64, "checked above"),
+            ),
+            _ => return None,
+        };
+        let folded = match &ops[1] {
+            // commutative operations may have the immediate on either side
+            ADD(r1, r2, r3) if r3 == reg => ADDI(r1.clone(), r2.clone(), imm),
+            ADD(r1, r2, r3) if r2 == reg => ADDI(r1.clone(), r3.clone(), imm),
+            MUL(r1, r2, r3) if r3 == reg => MULI(r1.clone(), r2.clone(), imm),
+            MUL(r1, r2, r3) if r2 == reg => MULI(r1.clone(), r3.clone(), imm),
+            AND(r1, r2, r3) if r3 == reg => ANDI(r1.clone(), r2.clone(), imm),
+            AND(r1, r2, r3) if r2 == reg => ANDI(r1.clone(), r3.clone(), imm),
+            OR(r1, r2, r3) if r3 == reg => ORI(r1.clone(), r2.clone(), imm),
+            OR(r1, r2, r3) if r2 == reg => ORI(r1.clone(), r3.clone(), imm),
+            XOR(r1, r2, r3) if r3 == reg => XORI(r1.clone(), r2.clone(), imm),
+            XOR(r1, r2, r3) if r2 == reg => XORI(r1.clone(), r3.clone(), imm),
+            SUB(r1, r2, r3) if r3 == reg => SUBI(r1.clone(), r2.clone(), imm),
+            DIV(r1, r2, r3) if r3 == reg => DIVI(r1.clone(), r2.clone(), imm),
+            MOD(r1, r2, r3) if r3 == reg => MODI(r1.clone(), r2.clone(), imm),
+            SLL(r1, r2, r3) if r3 == reg => SLLI(r1.clone(), r2.clone(), imm),
+            SRL(r1, r2, r3) if r3 == reg => SRLI(r1.clone(), r2.clone(), imm),
+            _ => return None,
+        };
+        Some(vec![ops[0].clone(), folded])
+    }
+
+    pub(super) fn remove_dead_definition(
+        ops: &[VirtualOp],
+        reads: &RegisterReads,
+    ) -> Option<Vec<VirtualOp>> {
+        match &ops[0] {
+            VirtualOp::MOVE(reg @ VirtualRegister::Virtual(_), _)
+            | VirtualOp::MOVI(reg @ VirtualRegister::Virtual(_), _)
+                if !reads.is_read(reg) =>
+            {
+                Some(vec![])
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The rules over [AllocatedOpcode]s. These mirror the [virtual_rules] which are still useful
+/// after register allocation.
+mod allocated_rules {
+    use super::*;
+
+    fn is_stable(reg: &AllocatedRegister) -> bool {
+        match reg {
+            AllocatedRegister::Allocated(_) => true,
+            AllocatedRegister::Constant(reg) => is_stable_constant(reg),
+        }
+    }
+
+    pub(super) fn remove_self_move(
+        ops: &[AllocatedOpcode],
+        _: &(),
+    ) -> Option<Vec<AllocatedOpcode>> {
+        match &ops[0] {
+            AllocatedOpcode::MOVE(r1, r2) if r1 == r2 => Some(vec![]),
+            _ => None,
+        }
+    }
+
+    pub(super) fn remove_identity_arithmetic(
+        ops: &[AllocatedOpcode],
+        _: &(),
+    ) -> Option<Vec<AllocatedOpcode>> {
+        use AllocatedOpcode::*;
+        let (r1, r2) = match &ops[0] {
+            ADDI(r1, r2, imm)
+            | SUBI(r1, r2, imm)
+            | ORI(r1, r2, imm)
+            | XORI(r1, r2, imm)
+            | SLLI(r1, r2, imm)
+            | SRLI(r1, r2, imm)
+                if imm.value == 0 =>
+            {
+                (r1, r2)
+            }
+            MULI(r1, r2, imm) | DIVI(r1, r2, imm) | EXPI(r1, r2, imm) if imm.value == 1 => (r1, r2),
+            _ => return None,
+        };
+        Some(if r1 == r2 {
+            vec![]
+        } else {
+            vec![MOVE(r1.clone(), r2.clone())]
+        })
+    }
+
+    pub(super) fn remove_move_back(
+        ops: &[AllocatedOpcode],
+        _: &(),
+    ) -> Option<Vec<AllocatedOpcode>> {
+        match (&ops[0], &ops[1]) {
+            (AllocatedOpcode::MOVE(r1, r2), AllocatedOpcode::MOVE(r3, r4))
+                if r1 != r2 && r3 == r2 && r4 == r1 && is_stable(r2) =>
+            {
+                Some(vec![ops[0].clone()])
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn forward_stored_word(
+        ops: &[AllocatedOpcode],
+        _: &(),
+    ) -> Option<Vec<AllocatedOpcode>> {
+        match (&ops[0], &ops[1]) {
+            (AllocatedOpcode::SW(base, src, imm), AllocatedOpcode::LW(dst, base2, imm2))
+                if base == base2
+                    && imm.value == imm2.value
+                    && is_stable(base)
+                    && is_stable(src) =>
+            {
+                Some(vec![
+                    ops[0].clone(),
+                    AllocatedOpcode::MOVE(dst.clone(), src.clone()),
+                ])
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        asm_generation::{
+            build_preamble, AbstractInstructionSet, FinalizedAsm, RegisterSequencer, SwayAsmSet,
+        },
+        asm_lang::DebugLocation,
+        source_map::SourceMap,
+        OptLevel,
+    };
+
+    use fuel_tx::ConsensusParameters;
+    use fuel_vm::interpreter::Interpreter;
+    use fuel_vm::prelude::*;
+    use std::io::Read;
+
+    fn virtual_op(opcode: VirtualOp) -> Op {
+        Op {
+            opcode: Either::Left(opcode),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    fn allocated_op(opcode: AllocatedOpcode) -> AllocatedOp {
+        AllocatedOp {
+            opcode,
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    fn reg(ix: u8) -> AllocatedRegister {
+        AllocatedRegister::Allocated(ix)
+    }
+
+    fn imm12(value: u64) -> VirtualImmediate12 {
+        VirtualImmediate12::new_unchecked(value, "test immediate")
+    }
+
+    fn imm18(value: u64) -> VirtualImmediate18 {
+        VirtualImmediate18::new_unchecked(value, "test immediate")
+    }
+
+    /// Compiles a script consisting of the preamble followed by the ops produced by `build`,
+    /// which is given a fresh register sequencer. Returns the bytecode and the number of
+    /// instructions.
+    fn compile_script(
+        opt_level: OptLevel,
+        build: impl Fn(&mut RegisterSequencer) -> Vec<Op>,
+    ) -> (Vec<u8>, usize) {
+        let mut reg_seqr = RegisterSequencer::new();
+        let mut ops = build_preamble(&mut reg_seqr).to_vec();
+        ops.append(&mut build(&mut reg_seqr));
+        let asm = SwayAsmSet::ScriptMain {
+            program_section: AbstractInstructionSet { ops },
+            data_section: DataSection::default(),
+        };
+        let mut finalized_asm = asm
+            .remove_unnecessary_jumps()
+            .optimize(opt_level)
+            .allocate_registers(&mut reg_seqr)
+            .optimize(opt_level);
+        let num_ops = match &finalized_asm {
+            FinalizedAsm::ScriptMain {
+                program_section, ..
+            } => program_section.ops.len(),
+            _ => unreachable!(),
+        };
+        let bytecode = finalized_asm
+            .to_bytecode_mut(&mut SourceMap::new())
+            .unwrap(&mut vec![], &mut vec![]);
+        (bytecode, num_ops)
+    }
+
+    fn run(bytecode: Vec<u8>) -> ProgramState {
+        let tx = Transaction::script(
+            0,
+            ConsensusParameters::DEFAULT.max_gas_per_tx,
+            0,
+            0,
+            bytecode,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        );
+        let mut interpreter =
+            Interpreter::with_storage(MemoryStorage::default(), Default::default());
+        *interpreter.transact(tx).unwrap().state()
+    }
+
+    /// Compiles the program with and without the peephole optimizer and checks that both versions
+    /// return `expected`. Returns the number of instructions of both versions.
+    fn check_script(
+        expected: u64,
+        build: impl Fn(&mut RegisterSequencer) -> Vec<Op>,
+    ) -> (usize, usize) {
+        let (unoptimized, unoptimized_len) = compile_script(OptLevel::Opt0, &build);
+        let (optimized, optimized_len) = compile_script(OptLevel::Opt1, &build);
+        assert_eq!(run(unoptimized), ProgramState::Return(expected));
+        assert_eq!(run(optimized), ProgramState::Return(expected));
+        (unoptimized_len, optimized_len)
+    }
+
+    /// Runs `ops` directly and returns the state of the VM.
+    fn run_allocated(ops: &[AllocatedOp]) -> ProgramState {
+        let mut data_section = DataSection::default();
+        let mut bytecode = vec![];
+        for op in ops {
+            let vm_ops = match op.to_fuel_asm(0, &mut data_section) {
+                Either::Left(vm_ops) => vm_ops,
+                Either::Right(_) => unreachable!("no data in these tests"),
+            };
+            for mut vm_op in vm_ops {
+                let mut buf = [0; 4];
+                vm_op
+                    .read_exact(&mut buf)
+                    .expect("Failed to write to in-memory buffer.");
+                bytecode.extend(buf);
+            }
+        }
+        run(bytecode)
+    }
+
+    #[test]
+    fn move_chains_and_identity_arithmetic() {
+        let (unoptimized_len, optimized_len) = check_script(42, |reg_seqr| {
+            let a = reg_seqr.next();
+            let b = reg_seqr.next();
+            let c = reg_seqr.next();
+            let d = reg_seqr.next();
+            let e = reg_seqr.next();
+            let t = reg_seqr.next();
+            vec![
+                virtual_op(VirtualOp::MOVI(a.clone(), imm18(20))),
+                virtual_op(VirtualOp::MOVE(b.clone(), a)),
+                virtual_op(VirtualOp::MOVE(c.clone(), b)),
+                virtual_op(VirtualOp::ADDI(c.clone(), c.clone(), imm12(0))),
+                virtual_op(VirtualOp::MULI(d.clone(), c, imm12(1))),
+                virtual_op(VirtualOp::MOVI(t.clone(), imm18(22))),
+                virtual_op(VirtualOp::ADD(e.clone(), d, t)),
+                virtual_op(VirtualOp::RET(e)),
+            ]
+        });
+        assert!(
+            optimized_len < unoptimized_len,
+            "expected fewer than {} instructions, found {}",
+            unoptimized_len,
+            optimized_len
+        );
+    }
+
+    #[test]
+    fn store_load_forwarding() {
+        let build = |reg_seqr: &mut RegisterSequencer| {
+            let stack_start = VirtualRegister::Constant(ConstantRegister::StackStartPointer);
+            let a = reg_seqr.next();
+            let b = reg_seqr.next();
+            vec![
+                virtual_op(VirtualOp::MOVI(a.clone(), imm18(42))),
+                virtual_op(VirtualOp::CFEI(VirtualImmediate24::new_unchecked(
+                    8,
+                    "test immediate",
+                ))),
+                virtual_op(VirtualOp::SW(stack_start.clone(), a, imm12(0))),
+                virtual_op(VirtualOp::LW(b.clone(), stack_start, imm12(0))),
+                virtual_op(VirtualOp::RET(b)),
+            ]
+        };
+        check_script(42, &build);
+        let optimized = optimize_virtual_ops(build(&mut RegisterSequencer::new()));
+        assert!(!optimized
+            .iter()
+            .any(|op| matches!(op.opcode, Either::Left(VirtualOp::LW(..)))));
+    }
+
+    #[test]
+    fn flags_are_preserved() {
+        // the `move` right before reading `$of` must stay, as it clears `$of`
+        let ops = vec![
+            virtual_op(VirtualOp::MOVE(
+                VirtualRegister::Virtual("0".into()),
+                VirtualRegister::Virtual("0".into()),
+            )),
+            virtual_op(VirtualOp::MOVE(
+                VirtualRegister::Virtual("1".into()),
+                VirtualRegister::Constant(ConstantRegister::Overflow),
+            )),
+            virtual_op(VirtualOp::RET(VirtualRegister::Virtual("1".into()))),
+        ];
+        assert_eq!(optimize_virtual_ops(ops).len(), 3);
+    }
+
+    #[test]
+    fn jump_targets_are_updated() {
+        // sums the numbers below 5 in a loop, with some redundant instructions before and inside
+        // the loop
+        let ops = vec![
+            allocated_op(AllocatedOpcode::MOVI(reg(0), imm18(0))),
+            allocated_op(AllocatedOpcode::MOVI(reg(1), imm18(0))),
+            allocated_op(AllocatedOpcode::MOVI(reg(2), imm18(5))),
+            allocated_op(AllocatedOpcode::MOVE(reg(3), reg(3))),
+            // loop start
+            allocated_op(AllocatedOpcode::ADD(reg(1), reg(1), reg(0))),
+            allocated_op(AllocatedOpcode::ADDI(reg(0), reg(0), imm12(1))),
+            allocated_op(AllocatedOpcode::ADDI(reg(1), reg(1), imm12(0))),
+            allocated_op(AllocatedOpcode::JNEI(reg(0), reg(2), imm12(4))),
+            allocated_op(AllocatedOpcode::RET(reg(1))),
+        ];
+        let optimized = optimize_allocated_ops(ops.clone(), &DataSection::default(), &mut []);
+        assert_eq!(optimized.len(), ops.len() - 2);
+        assert!(matches!(
+            optimized[5].opcode,
+            AllocatedOpcode::JNEI(_, _, ref imm) if imm.value == 3
+        ));
+        assert_eq!(run_allocated(&ops), ProgramState::Return(10));
+        assert_eq!(run_allocated(&optimized), ProgramState::Return(10));
+    }
+
+    fn debug_variable<R>(base: R, scope: std::ops::Range<u64>) -> DebugVariable<R> {
+        DebugVariable {
+            name: "x".into(),
+            ty: "u64".into(),
+            span: sway_types::Span::dummy(),
+            location: DebugLocation::Memory { base, offset: 0 },
+            lexical_scope: None,
+            scope,
+        }
+    }
+
+    #[test]
+    fn debug_markers_do_not_block_rewrites() {
+        let stack_start = VirtualRegister::Constant(ConstantRegister::StackStartPointer);
+        let a = VirtualRegister::Virtual("a".into());
+        let b = VirtualRegister::Virtual("b".into());
+        let ops = vec![
+            virtual_op(VirtualOp::MOVI(a.clone(), imm18(42))),
+            virtual_op(VirtualOp::SW(stack_start.clone(), a, imm12(0))),
+            Op::debug_variable(debug_variable(stack_start.clone(), 0..0)),
+            virtual_op(VirtualOp::LW(b.clone(), stack_start, imm12(0))),
+            virtual_op(VirtualOp::RET(b)),
+            Op::debug_scope_end(),
+        ];
+        let optimized = optimize_virtual_ops(ops);
+        assert!(!optimized
+            .iter()
+            .any(|op| matches!(op.opcode, Either::Left(VirtualOp::LW(..)))));
+        assert_eq!(
+            optimized
+                .iter()
+                .filter(|op| matches!(op.opcode, Either::Right(ref op) if is_debug_marker(op)))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn debug_variable_scopes_are_updated() {
+        // the loop from `jump_targets_are_updated`, with a variable in scope from the loop start
+        let ops = vec![
+            allocated_op(AllocatedOpcode::MOVI(reg(0), imm18(0))),
+            allocated_op(AllocatedOpcode::MOVI(reg(1), imm18(0))),
+            allocated_op(AllocatedOpcode::MOVI(reg(2), imm18(5))),
+            allocated_op(AllocatedOpcode::MOVE(reg(3), reg(3))),
+            // loop start
+            allocated_op(AllocatedOpcode::ADD(reg(1), reg(1), reg(0))),
+            allocated_op(AllocatedOpcode::ADDI(reg(0), reg(0), imm12(1))),
+            allocated_op(AllocatedOpcode::ADDI(reg(1), reg(1), imm12(0))),
+            allocated_op(AllocatedOpcode::JNEI(reg(0), reg(2), imm12(4))),
+            allocated_op(AllocatedOpcode::RET(reg(1))),
+        ];
+        let stack_start = AllocatedRegister::Constant(ConstantRegister::StackStartPointer);
+        let mut debug_variables = vec![
+            debug_variable(stack_start.clone(), 4..9),
+            debug_variable(stack_start, 6..8),
+        ];
+        optimize_allocated_ops(ops, &DataSection::default(), &mut debug_variables);
+        assert_eq!(debug_variables[0].scope, 3..7);
+        assert_eq!(debug_variables[1].scope, 5..6);
+    }
+}
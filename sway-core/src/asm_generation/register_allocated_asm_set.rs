@@ -1,6 +1,7 @@
 use crate::{
-    asm_generation::{DataSection, FinalizedAsm, InstructionSet},
+    asm_generation::{peephole, DataSection, FinalizedAsm, InstructionSet},
     asm_lang::allocated_ops::AllocatedOp,
+    OptLevel,
 };
 use std::fmt;
 
@@ -23,14 +24,27 @@ pub enum RegisterAllocatedAsmSet {
 }
 
 impl RegisterAllocatedAsmSet {
-    pub(crate) fn optimize(self) -> FinalizedAsm {
-        // TODO implement this -- noop for now
+    pub(crate) fn optimize(self, opt_level: OptLevel) -> FinalizedAsm {
+        let optimize = |mut program_section: InstructionSet, data_section: &DataSection| {
+            if opt_level == OptLevel::Opt0 {
+                return program_section;
+            }
+            InstructionSet {
+                ops: peephole::optimize_allocated_ops(
+                    program_section.ops,
+                    data_section,
+                    &mut program_section.debug_variables,
+                ),
+                debug_variables: program_section.debug_variables,
+            }
+        };
         match self {
             RegisterAllocatedAsmSet::Library => FinalizedAsm::Library,
             RegisterAllocatedAsmSet::ScriptMain {
-                mut program_section,
+                program_section,
                 data_section,
             } => {
+                let mut program_section = optimize(program_section, &data_section);
                 // ensure there's an even number of ops so the
                 // data section offset is valid
                 if program_section.ops.len() & 1 != 0 {
@@ -46,9 +60,10 @@ impl RegisterAllocatedAsmSet {
                 }
             }
             RegisterAllocatedAsmSet::PredicateMain {
-                mut program_section,
+                program_section,
                 data_section,
             } => {
+                let mut program_section = optimize(program_section, &data_section);
                 // ensure there's an even number of ops so the
                 // data section offset is valid
                 if program_section.ops.len() & 1 != 0 {
@@ -64,9 +79,10 @@ impl RegisterAllocatedAsmSet {
                 }
             }
             RegisterAllocatedAsmSet::ContractAbi {
-                mut program_section,
+                program_section,
                 data_section,
             } => {
+                let mut program_section = optimize(program_section, &data_section);
                 // ensure there's an even number of ops so the
                 // data section offset is valid
                 if program_section.ops.len() & 1 != 0 {
@@ -1,7 +1,7 @@
 use crate::asm_generation::{
     register_sequencer::RegisterSequencer, RegisterAllocationStatus, RegisterPool,
 };
-use crate::asm_lang::{virtual_register::*, RealizedOp, VirtualOp};
+use crate::asm_lang::{virtual_register::*, DebugVariable, RealizedOp, VirtualOp};
 use petgraph::graph::NodeIndex;
 use std::collections::{BTreeSet, HashMap};
 
@@ -199,13 +199,14 @@ pub(crate) fn create_interference_graph(
 ///   register sequencer) is created in the interference graph.
 /// * When a MOVE instruction is removed, the offset of each subsequent instruction has to be
 /// updated, as well as the immediate values for some or all jump instructions (`ji`, `jnei`, and
-/// `jnzi for now).
+/// `jnzi for now) and the scopes of the `debug_variables`.
 ///
 pub(crate) fn coalesce_registers(
     ops: &[RealizedOp],
     interference_graph: &mut InterferenceGraph,
     reg_to_node_map: &mut HashMap<VirtualRegister, NodeIndex>,
     register_sequencer: &mut RegisterSequencer,
+    debug_variables: &mut [DebugVariable],
 ) -> Vec<RealizedOp> {
     // A map from the virtual registers that are removed to the virtual registers that they are
     // replaced with during the coalescing process.
@@ -324,6 +325,18 @@ pub(crate) fn coalesce_registers(
         new_op.opcode = new_op.opcode.update_jump_immediate_values(&offset_map);
     }
 
+    // Likewise for the scopes of the debug variables.  A scope may also end after the last
+    // instruction, past all the removed moves.
+    let new_offset = |offset: u64| {
+        offset_map
+            .get(&offset)
+            .copied()
+            .unwrap_or(offset - num_moves_removed)
+    };
+    for var in debug_variables.iter_mut() {
+        var.scope = new_offset(var.scope.start)..new_offset(var.scope.end);
+    }
+
     // Create a *final* reg-to-reg map that We keep looking for mappings within reg_to_reg_map
     // until we find a register that doesn't map to any other.
     let mut final_reg_to_reg_map: HashMap<VirtualRegister, VirtualRegister> = HashMap::new();
@@ -339,6 +352,9 @@ pub(crate) fn coalesce_registers(
     for new_op in &mut reduced_ops {
         new_op.opcode = new_op.opcode.update_register(&final_reg_to_reg_map);
     }
+    for var in debug_variables {
+        var.update_register(&final_reg_to_reg_map);
+    }
 
     reduced_ops
 }
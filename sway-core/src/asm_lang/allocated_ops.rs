@@ -11,7 +11,7 @@
 
 use super::DataId;
 use super::*;
-use crate::asm_generation::DataSection;
+use crate::{asm_generation::DataSection, parse_tree::Literal};
 use either::Either;
 use fuel_asm::Opcode as VmOp;
 use std::fmt::{self, Write};
@@ -39,7 +39,7 @@ impl fmt::Display for AllocatedRegister {
 }
 
 impl AllocatedRegister {
-    fn to_register_id(&self) -> fuel_asm::RegisterId {
+    pub(crate) fn to_register_id(&self) -> fuel_asm::RegisterId {
         match self {
             AllocatedRegister::Allocated(a) => (a + 16) as fuel_asm::RegisterId,
             AllocatedRegister::Constant(constant) => constant.to_register_id(),
@@ -95,6 +95,10 @@ pub(crate) enum AllocatedOpcode {
     JI(VirtualImmediate24),
     JNEI(AllocatedRegister, AllocatedRegister, VirtualImmediate12),
     JNZI(AllocatedRegister, VirtualImmediate18),
+    LongJump(u64),
+    LongJumpIfNotEq(AllocatedRegister, AllocatedRegister, u64),
+    JMP(AllocatedRegister),
+    LoadInstructionOffset(AllocatedRegister, u64),
     RET(AllocatedRegister),
     RETD(AllocatedRegister, AllocatedRegister),
     CFEI(VirtualImmediate24),
@@ -225,6 +229,10 @@ impl fmt::Display for AllocatedOp {
             JI(a)           => format!("ji   {}", a),
             JNEI(a, b, c)   => format!("jnei {} {} {}", a, b, c),
             JNZI(a, b)      => format!("jnzi {} {}", a, b),
+            LongJump(a)     => format!("long jump {}", a),
+            LongJumpIfNotEq(a, b, c) => format!("long jnei {} {} {}", a, b, c),
+            JMP(a)          => format!("jmp  {}", a),
+            LoadInstructionOffset(a, b) => format!("lw   {} offset {}", a, b),
             RET(a)          => format!("ret  {}", a),
             RETD(a, b)      => format!("retd  {} {}", a, b),
             CFEI(a)         => format!("cfei {}", a),
@@ -290,6 +298,106 @@ impl fmt::Display for AllocatedOp {
     }
 }
 
+impl AllocatedOpcode {
+    /// Returns a list of all registers used by instruction `self`.
+    pub(crate) fn registers(&self) -> Vec<&AllocatedRegister> {
+        use AllocatedOpcode::*;
+        match self {
+            ADD(r1, r2, r3) => vec![r1, r2, r3],
+            ADDI(r1, r2, _i) => vec![r1, r2],
+            AND(r1, r2, r3) => vec![r1, r2, r3],
+            ANDI(r1, r2, _i) => vec![r1, r2],
+            DIV(r1, r2, r3) => vec![r1, r2, r3],
+            DIVI(r1, r2, _i) => vec![r1, r2],
+            EQ(r1, r2, r3) => vec![r1, r2, r3],
+            EXP(r1, r2, r3) => vec![r1, r2, r3],
+            EXPI(r1, r2, _i) => vec![r1, r2],
+            GT(r1, r2, r3) => vec![r1, r2, r3],
+            GTF(r1, r2, _i) => vec![r1, r2],
+            LT(r1, r2, r3) => vec![r1, r2, r3],
+            MLOG(r1, r2, r3) => vec![r1, r2, r3],
+            MROO(r1, r2, r3) => vec![r1, r2, r3],
+            MOD(r1, r2, r3) => vec![r1, r2, r3],
+            MODI(r1, r2, _i) => vec![r1, r2],
+            MOVE(r1, r2) => vec![r1, r2],
+            MOVI(r1, _i) => vec![r1],
+            MUL(r1, r2, r3) => vec![r1, r2, r3],
+            MULI(r1, r2, _i) => vec![r1, r2],
+            NOT(r1, r2) => vec![r1, r2],
+            OR(r1, r2, r3) => vec![r1, r2, r3],
+            ORI(r1, r2, _i) => vec![r1, r2],
+            SLL(r1, r2, r3) => vec![r1, r2, r3],
+            SLLI(r1, r2, _i) => vec![r1, r2],
+            SMO(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            SRL(r1, r2, r3) => vec![r1, r2, r3],
+            SRLI(r1, r2, _i) => vec![r1, r2],
+            SUB(r1, r2, r3) => vec![r1, r2, r3],
+            SUBI(r1, r2, _i) => vec![r1, r2],
+            XOR(r1, r2, r3) => vec![r1, r2, r3],
+            XORI(r1, r2, _i) => vec![r1, r2],
+            JI(_im) => vec![],
+            JNEI(r1, r2, _i) => vec![r1, r2],
+            JNZI(r1, _i) => vec![r1],
+            LongJump(_i) => vec![],
+            LongJumpIfNotEq(r1, r2, _i) => vec![r1, r2],
+            JMP(r1) => vec![r1],
+            LoadInstructionOffset(r1, _i) => vec![r1],
+            RET(r1) => vec![r1],
+            RETD(r1, r2) => vec![r1, r2],
+            CFEI(_imm) => vec![],
+            CFSI(_imm) => vec![],
+            LB(r1, r2, _i) => vec![r1, r2],
+            LWDataId(r1, _i) => vec![r1],
+            LW(r1, r2, _i) => vec![r1, r2],
+            ALOC(r1) => vec![r1],
+            MCL(r1, r2) => vec![r1, r2],
+            MCLI(r1, _imm) => vec![r1],
+            MCP(r1, r2, r3) => vec![r1, r2, r3],
+            MEQ(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            MCPI(r1, r2, _imm) => vec![r1, r2],
+            SB(r1, r2, _i) => vec![r1, r2],
+            SW(r1, r2, _i) => vec![r1, r2],
+            BAL(r1, r2, r3) => vec![r1, r2, r3],
+            BHSH(r1, r2) => vec![r1, r2],
+            BHEI(r1) => vec![r1],
+            BURN(r1) => vec![r1],
+            CALL(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            CCP(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            CROO(r1, r2) => vec![r1, r2],
+            CSIZ(r1, r2) => vec![r1, r2],
+            CB(r1) => vec![r1],
+            LDC(r1, r2, r3) => vec![r1, r2, r3],
+            LOG(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            LOGD(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            MINT(r1) => vec![r1],
+            RVRT(r1) => vec![r1],
+            SRW(r1, r2) => vec![r1, r2],
+            SRWQ(r1, r2) => vec![r1, r2],
+            SWW(r1, r2) => vec![r1, r2],
+            SWWQ(r1, r2) => vec![r1, r2],
+            TR(r1, r2, r3) => vec![r1, r2, r3],
+            TRO(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            ECR(r1, r2, r3) => vec![r1, r2, r3],
+            K256(r1, r2, r3) => vec![r1, r2, r3],
+            S256(r1, r2, r3) => vec![r1, r2, r3],
+            XIL(r1, r2) => vec![r1, r2],
+            XIS(r1, r2) => vec![r1, r2],
+            XOL(r1, r2) => vec![r1, r2],
+            XOS(r1, r2) => vec![r1, r2],
+            XWL(r1, r2) => vec![r1, r2],
+            XWS(r1, r2) => vec![r1, r2],
+            NOOP => vec![],
+            FLAG(r1) => vec![r1],
+            GM(r1, _imm) => vec![r1],
+            Undefined | DataSectionOffsetPlaceholder => vec![],
+            DataSectionRegisterLoadPlaceholder => vec![
+                &AllocatedRegister::Constant(ConstantRegister::DataSectionStart),
+                &AllocatedRegister::Constant(ConstantRegister::InstructionStart),
+            ],
+        }
+    }
+}
+
 type DoubleWideData = [u8; 8];
 
 impl AllocatedOp {
@@ -336,12 +444,19 @@ impl AllocatedOp {
             JI  (a)         => VmOp::JI  (a.value),
             JNEI(a, b, c)   => VmOp::JNEI(a.to_register_id(), b.to_register_id(), c.value),
             JNZI(a, b)      => VmOp::JNZI(a.to_register_id(), b.value),
+            LongJump(a)     => return Either::Left(realize_long_jump(None, *a, data_section)),
+            LongJumpIfNotEq(a, b, c) => return Either::Left(realize_long_jump(Some((a, b)), *c, data_section)),
+            JMP(a)          => VmOp::JMP(a.to_register_id()),
+            LoadInstructionOffset(a, b) => {
+                let data_id = data_section.insert_data_value(&Literal::U64(*b));
+                return Either::Left(realize_lw(a, &data_id, data_section))
+            }
             RET (a)         => VmOp::RET (a.to_register_id()),
             RETD(a, b)      => VmOp::RETD (a.to_register_id(), b.to_register_id()),
             CFEI(a)         => VmOp::CFEI(a.value),
             CFSI(a)         => VmOp::CFSI(a.value),
             LB  (a, b, c)   => VmOp::LB  (a.to_register_id(), b.to_register_id(), c.value),
-            LWDataId  (a, b)=> return Either::Left(realize_lw(a, b, data_section, offset_to_data_section)),
+            LWDataId  (a, b)=> return Either::Left(realize_lw(a, b, data_section)),
             LW (a, b, c)    => VmOp::LW(a.to_register_id(), b.to_register_id(), c.value),
             ALOC(a)         => VmOp::ALOC(a.to_register_id()),
             MCL (a, b)      => VmOp::MCL (a.to_register_id(), b.to_register_id()),
@@ -391,53 +506,64 @@ impl AllocatedOp {
     }
 }
 
+/// Converts a long jump into a load of the target offset from the data section into the scratch
+/// register, followed by a register-based jump. If `condition` is provided, the jump is only taken
+/// if the two registers are different.
+fn realize_long_jump(
+    condition: Option<(&AllocatedRegister, &AllocatedRegister)>,
+    target: u64,
+    data_section: &mut DataSection,
+) -> Vec<VmOp> {
+    let scratch = AllocatedRegister::Constant(ConstantRegister::Scratch);
+    let data_id = data_section.insert_data_value(&Literal::U64(target));
+    let mut buf = realize_lw(&scratch, &data_id, data_section);
+    buf.push(match condition {
+        Some((a, b)) => VmOp::JNE(
+            a.to_register_id(),
+            b.to_register_id(),
+            scratch.to_register_id(),
+        ),
+        None => VmOp::JMP(scratch.to_register_id()),
+    });
+    buf
+}
+
 /// Converts a virtual load word instruction which uses data labels into one which uses
 /// actual bytewise offsets for use in bytecode.
 /// Returns one op if the type is less than one word big, but two ops if it has to construct
-/// a pointer and add it to $is.
+/// a pointer and add it to $ds.
 fn realize_lw(
     dest: &AllocatedRegister,
     data_id: &DataId,
     data_section: &mut DataSection,
-    offset_to_data_section: u64,
 ) -> Vec<VmOp> {
-    // all data is word-aligned right now, and `offset_to_id` returns the offset in bytes
-    let offset_bytes = data_section.offset_to_id(data_id) as u64;
-    let offset_words = offset_bytes / 8;
-    let offset = match VirtualImmediate12::new(offset_words, Span::new(" ".into(), 0, 0, None).unwrap()) {
-        Ok(value) => value,
-        Err(_) => panic!("Unable to offset into the data section more than 2^12 bits. Unsupported data section length.")
-    };
     // if this data is larger than a word, instead of loading the data directly
     // into the register, we want to load a pointer to the data into the register
-    // this appends onto the data section and mutates it by adding the pointer as a literal
+    // this appends onto the data section and mutates it by adding the pointer as an entry
     let type_of_data = data_section.type_of_data(data_id).expect(
         "Internal miscalculation in data section -- data id did not match up to any actual data",
     );
     if !type_of_data.is_copy_type() {
-        // load the pointer itself into the register
-        // `offset_to_data_section` is in bytes. We want a byte
-        // address here
-        let pointer_offset_from_instruction_start = offset_to_data_section + offset_bytes;
-        // insert the pointer as bytes as a new data section entry at the end of the data
-        let data_id_for_pointer =
-            data_section.append_pointer(pointer_offset_from_instruction_start);
+        // the pointer holds the offset of the data from the start of the data section, in bytes
+        let data_id_for_pointer = data_section.append_pointer(data_id);
         // now load the pointer we just created into the `dest`ination
         let mut buf = Vec::with_capacity(2);
-        buf.append(&mut realize_lw(
-            dest,
-            &data_id_for_pointer,
-            data_section,
-            offset_to_data_section,
-        ));
-        // add $is to the pointer since it is relative to the data section
+        buf.append(&mut realize_lw(dest, &data_id_for_pointer, data_section));
+        // add $ds to the pointer since it is relative to the data section
         buf.push(VmOp::ADD(
             dest.to_register_id(),
             dest.to_register_id(),
-            ConstantRegister::InstructionStart.to_register_id(),
+            ConstantRegister::DataSectionStart.to_register_id(),
         ));
         buf
     } else {
+        // all data is word-aligned right now, and `offset_to_id` returns the offset in bytes
+        let offset_bytes = data_section.offset_to_id(data_id) as u64;
+        let offset_words = offset_bytes / 8;
+        let offset = match VirtualImmediate12::new(offset_words, Span::new(" ".into(), 0, 0, None).unwrap()) {
+            Ok(value) => value,
+            Err(_) => panic!("Unable to offset into the data section more than 2^12 bits. Unsupported data section length.")
+        };
         vec![VmOp::LW(
             dest.to_register_id(),
             crate::asm_generation::compiler_constants::DATA_SECTION_REGISTER as usize,
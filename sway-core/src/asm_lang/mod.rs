@@ -13,14 +13,21 @@ pub(crate) use virtual_immediate::*;
 pub(crate) use virtual_ops::*;
 pub(crate) use virtual_register::*;
 
-use crate::{asm_generation::DataId, error::*, parse_tree::AsmRegister, Ident};
+use crate::{
+    asm_generation::{DataId, RegisterPool},
+    error::*,
+    parse_tree::AsmRegister,
+    Ident,
+};
+use allocated_ops::AllocatedRegister;
 
 use sway_types::{span::Span, Spanned};
 
 use either::Either;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fmt::{self, Write},
+    ops::Range,
 };
 
 /// The column where the ; for comments starts
@@ -49,6 +56,76 @@ pub(crate) struct RealizedOp {
     pub(crate) offset: u64,
 }
 
+/// A local variable or an argument of the program, described for debuggers.  `R` is the type of
+/// the registers, which are virtual until registers are allocated.
+#[derive(Clone, Debug)]
+pub(crate) struct DebugVariable<R = VirtualRegister> {
+    pub(crate) name: String,
+    /// The type of the variable, as written in the source.
+    pub(crate) ty: String,
+    /// The declaration of the variable.
+    pub(crate) span: Span,
+    pub(crate) location: DebugLocation<R>,
+    /// The code from the declaration to the end of the block the variable is declared in, if it
+    /// goes out of scope before the end of the function.
+    pub(crate) lexical_scope: Option<Span>,
+    /// The instruction offsets at which the variable is in scope.  These are only known once the
+    /// labels have been realized.
+    pub(crate) scope: Range<u64>,
+}
+
+/// Where the value of a [DebugVariable] is kept.
+#[derive(Clone, Debug)]
+pub(crate) enum DebugLocation<R> {
+    /// In memory, `offset` bytes above the address held in `base`.  Entry functions keep their
+    /// locals above the stack start pointer `$ssp`, and functions called through function
+    /// pointers above the stack pointer `$sp` as it was when they were entered.
+    Memory { base: R, offset: u64 },
+    /// In the register itself.
+    Register(R),
+}
+
+impl DebugVariable {
+    /// Replaces the registers which were coalesced with others.
+    pub(crate) fn update_register(
+        &mut self,
+        reg_to_reg_map: &HashMap<VirtualRegister, VirtualRegister>,
+    ) {
+        let reg = match &mut self.location {
+            DebugLocation::Memory { base: reg, .. } | DebugLocation::Register(reg) => reg,
+        };
+        if let Some(new_reg) = reg_to_reg_map.get(reg) {
+            *reg = new_reg.clone();
+        }
+    }
+
+    /// The variable with its register allocated, if the register is used at all.
+    pub(crate) fn allocate_registers(
+        self,
+        pool: &RegisterPool,
+    ) -> Option<DebugVariable<AllocatedRegister>> {
+        let allocate = |reg: VirtualRegister| match reg {
+            VirtualRegister::Constant(constant) => Some(AllocatedRegister::Constant(constant)),
+            VirtualRegister::Virtual(_) => pool.get_register(&reg),
+        };
+        let location = match self.location {
+            DebugLocation::Memory { base, offset } => DebugLocation::Memory {
+                base: allocate(base)?,
+                offset,
+            },
+            DebugLocation::Register(reg) => DebugLocation::Register(allocate(reg)?),
+        };
+        Some(DebugVariable {
+            name: self.name,
+            ty: self.ty,
+            span: self.span,
+            location,
+            lexical_scope: self.lexical_scope,
+            scope: self.scope,
+        })
+    }
+}
+
 impl Op {
     /// Write value in given [VirtualRegister] `value_to_write` to given memory address that is held within the
     /// [VirtualRegister] `destination_address`
@@ -218,6 +295,25 @@ impl Op {
         }
     }
 
+    /// Marks where the local variable `var` comes into scope.
+    pub(crate) fn debug_variable(var: DebugVariable) -> Self {
+        Op {
+            opcode: Either::Right(OrganizationalOp::DebugVariable(Box::new(var))),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    /// Marks where all the local variables in scope go out of scope, at the end of a function.
+    /// Variables with a lexical scope go out of scope after the last instruction within it.
+    pub(crate) fn debug_scope_end() -> Self {
+        Op {
+            opcode: Either::Right(OrganizationalOp::DebugScopeEnd),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
     pub(crate) fn new_comment(comm: impl Into<String>) -> Self {
         Op {
             opcode: Either::Right(OrganizationalOp::Comment),
@@ -1372,6 +1468,10 @@ impl fmt::Display for Op {
                 JI(a) => format!("ji {}", a),
                 JNEI(a, b, c) => format!("jnei {} {} {}", a, b, c),
                 JNZI(a, b) => format!("jnzi {} {}", a, b),
+                LongJump(a) => format!("long jump {}", a),
+                LongJumpIfNotEq(a, b, c) => format!("long jnei {} {} {}", a, b, c),
+                JMP(a) => format!("jmp {}", a),
+                LoadInstructionOffset(a, b) => format!("lw {} offset {}", a, b),
                 RET(a) => format!("ret {}", a),
                 RETD(a, b) => format!("retd {} {}", a, b),
                 CFEI(a) => format!("cfei {}", a),
@@ -1431,9 +1531,14 @@ impl fmt::Display for Op {
                 Jump(label) => format!("jump {}", label),
                 JumpIfNotEq(reg0, reg1, label) => format!("jnei {} {} {}", reg0, reg1, label),
                 JumpIfNotZero(reg0, label) => format!("jnzi {} {}", reg0, label),
+                LoadLabelOffset(reg0, label) => format!("lw {} offset {}", reg0, label),
                 OrganizationalOp::DataSectionOffsetPlaceholder => {
                     "data section offset placeholder".into()
                 }
+                OrganizationalOp::DebugVariable(var) => {
+                    format!("debug variable {}: {}", var.name, var.ty)
+                }
+                DebugScopeEnd => "debug scope end".into(),
             },
         };
         // we want the comment to always be 40 characters offset to the right
@@ -1464,8 +1569,14 @@ pub(crate) enum OrganizationalOp {
     JumpIfNotEq(VirtualRegister, VirtualRegister, Label),
     // Jumps to a label if the register is not equal to zero
     JumpIfNotZero(VirtualRegister, Label),
+    // Loads the instruction offset of a label into the register, for jumping to with JMP
+    LoadLabelOffset(VirtualRegister, Label),
     // placeholder for the DataSection offset
     DataSectionOffsetPlaceholder,
+    // Marks where a local variable comes into scope, for debuggers
+    DebugVariable(Box<DebugVariable>),
+    // Marks where all the local variables in scope go out of scope, for debuggers
+    DebugScopeEnd,
 }
 impl fmt::Display for OrganizationalOp {
     fn fmt(&self, fmtr: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1479,8 +1590,13 @@ impl fmt::Display for OrganizationalOp {
                 Comment => "".into(),
                 JumpIfNotEq(r1, r2, lab) => format!("jnei {} {} {}", r1, r2, lab),
                 JumpIfNotZero(r1, lab) => format!("jnzi {} {}", r1, lab),
+                LoadLabelOffset(r1, lab) => format!("lw {} offset {}", r1, lab),
                 DataSectionOffsetPlaceholder =>
                     "DATA SECTION OFFSET[0..32]\nDATA SECTION OFFSET[32..64]".into(),
+                OrganizationalOp::DebugVariable(var) => {
+                    format!("debug variable {}: {}", var.name, var.ty)
+                }
+                DebugScopeEnd => "debug scope end".into(),
             }
         )
     }
@@ -1490,9 +1606,14 @@ impl OrganizationalOp {
     pub(crate) fn registers(&self) -> HashSet<&VirtualRegister> {
         use OrganizationalOp::*;
         (match self {
-            Label(_) | Comment | Jump(_) | DataSectionOffsetPlaceholder => vec![],
+            Label(_)
+            | Comment
+            | Jump(_)
+            | DataSectionOffsetPlaceholder
+            | OrganizationalOp::DebugVariable(_)
+            | DebugScopeEnd => vec![],
             JumpIfNotEq(r1, r2, _) => vec![r1, r2],
-            JumpIfNotZero(r1, _) => vec![r1],
+            JumpIfNotZero(r1, _) | LoadLabelOffset(r1, _) => vec![r1],
         })
         .into_iter()
         .collect()
@@ -63,6 +63,17 @@ pub(crate) enum VirtualOp {
     JI(VirtualImmediate24),
     JNEI(VirtualRegister, VirtualRegister, VirtualImmediate12),
     JNZI(VirtualRegister, VirtualImmediate18),
+    // A jump to an instruction offset which does not fit in the immediate of JI. The offset is
+    // loaded from the data section into the scratch register, which is then used with JMP.
+    LongJump(u64),
+    // Like LongJump, but jumps with JNE only if the two registers are different.
+    LongJumpIfNotEq(VirtualRegister, VirtualRegister, u64),
+    // A jump to the instruction offset held in the register, used for calls through function
+    // pointers and for the returns from the functions called that way.
+    JMP(VirtualRegister),
+    // Loads an instruction offset, either of a function or of the return site of a call, from
+    // the data section so that it can be jumped to with JMP.
+    LoadInstructionOffset(VirtualRegister, u64),
     RET(VirtualRegister),
     RETD(VirtualRegister, VirtualRegister),
     CFEI(VirtualImmediate24),
@@ -190,6 +201,10 @@ impl VirtualOp {
             JI(_im) => vec![],
             JNEI(r1, r2, _i) => vec![r1, r2],
             JNZI(r1, _i) => vec![r1],
+            LongJump(_i) => vec![],
+            LongJumpIfNotEq(r1, r2, _i) => vec![r1, r2],
+            JMP(r1) => vec![r1],
+            LoadInstructionOffset(r1, _i) => vec![r1],
             RET(r1) => vec![r1],
             RETD(r1, r2) => vec![r1, r2],
             CFEI(_imm) => vec![],
@@ -286,6 +301,10 @@ impl VirtualOp {
             JI(_im) => vec![],
             JNEI(r1, r2, _i) => vec![r1, r2],
             JNZI(r1, _i) => vec![r1],
+            LongJump(_i) => vec![],
+            LongJumpIfNotEq(r1, r2, _i) => vec![r1, r2],
+            JMP(r1) => vec![r1],
+            LoadInstructionOffset(_r1, _i) => vec![],
             RET(r1) => vec![r1],
             RETD(r1, r2) => vec![r1, r2],
             CFEI(_imm) => vec![],
@@ -382,6 +401,10 @@ impl VirtualOp {
             JI(_im) => vec![],
             JNEI(_r1, _r2, _i) => vec![],
             JNZI(_r1, _i) => vec![],
+            LongJump(_i) => vec![],
+            LongJumpIfNotEq(_r1, _r2, _i) => vec![],
+            JMP(_r1) => vec![],
+            LoadInstructionOffset(r1, _i) => vec![r1],
             RET(_r1) => vec![],
             RETD(_r1, _r2) => vec![],
             CFEI(_imm) => vec![],
@@ -491,6 +514,36 @@ impl VirtualOp {
                         .collect()
                 }
             }
+            LongJump(i) => {
+                // Same as JI, except that the offset is not wrapped in an immediate.
+                if *offset_to_ix.get(i).unwrap() >= ops.len() {
+                    vec![]
+                } else {
+                    vec![*offset_to_ix.get(i).unwrap()]
+                }
+            }
+            LongJumpIfNotEq(_, _, i) => {
+                // Same as JNEI, except that the offset is not wrapped in an immediate.
+                if *offset_to_ix.get(i).unwrap() >= ops.len() {
+                    vec![].into_iter().chain(next_op.into_iter()).collect()
+                } else {
+                    vec![*offset_to_ix.get(i).unwrap()]
+                        .into_iter()
+                        .chain(next_op.into_iter())
+                        .collect()
+                }
+            }
+            JMP(_) => {
+                // The target is only known at runtime, but it's always one of the offsets loaded
+                // by some LoadInstructionOffset, so all of those are possible successors.
+                ops.iter()
+                    .filter_map(|op| match op.opcode {
+                        LoadInstructionOffset(_, i) => offset_to_ix.get(&i).copied(),
+                        _ => None,
+                    })
+                    .filter(|ix| *ix < ops.len())
+                    .collect()
+            }
             _ => next_op,
         }
     }
@@ -663,6 +716,16 @@ impl VirtualOp {
                 i.clone(),
             ),
             JNZI(r1, i) => Self::JNZI(update_reg(reg_to_reg_map, r1), i.clone()),
+            LongJump(_) => self.clone(),
+            LongJumpIfNotEq(r1, r2, i) => Self::LongJumpIfNotEq(
+                update_reg(reg_to_reg_map, r1),
+                update_reg(reg_to_reg_map, r2),
+                *i,
+            ),
+            JMP(r1) => Self::JMP(update_reg(reg_to_reg_map, r1)),
+            LoadInstructionOffset(r1, i) => {
+                Self::LoadInstructionOffset(update_reg(reg_to_reg_map, r1), *i)
+            }
             RET(r1) => Self::RET(update_reg(reg_to_reg_map, r1)),
             RETD(r1, r2) => Self::RETD(
                 update_reg(reg_to_reg_map, r1),
@@ -874,6 +937,16 @@ impl VirtualOp {
                 )
                 .unwrap(),
             ),
+            LongJump(i) => Self::LongJump(*offset_map.get(i).expect("new offset should be valid")),
+            LongJumpIfNotEq(r1, r2, i) => Self::LongJumpIfNotEq(
+                r1.clone(),
+                r2.clone(),
+                *offset_map.get(i).expect("new offset should be valid"),
+            ),
+            LoadInstructionOffset(r1, i) => Self::LoadInstructionOffset(
+                r1.clone(),
+                *offset_map.get(i).expect("new offset should be valid"),
+            ),
 
             _ => self.clone(),
         }
@@ -1068,6 +1141,16 @@ impl VirtualOp {
                 imm.clone(),
             ),
             JNZI(reg1, imm) => AllocatedOpcode::JNZI(map_reg(&mapping, reg1), imm.clone()),
+            LongJump(imm) => AllocatedOpcode::LongJump(*imm),
+            LongJumpIfNotEq(reg1, reg2, imm) => AllocatedOpcode::LongJumpIfNotEq(
+                map_reg(&mapping, reg1),
+                map_reg(&mapping, reg2),
+                *imm,
+            ),
+            JMP(reg) => AllocatedOpcode::JMP(map_reg(&mapping, reg)),
+            LoadInstructionOffset(reg, imm) => {
+                AllocatedOpcode::LoadInstructionOffset(map_reg(&mapping, reg), *imm)
+            }
             RET(reg) => AllocatedOpcode::RET(map_reg(&mapping, reg)),
             RETD(reg1, reg2) => {
                 AllocatedOpcode::RETD(map_reg(&mapping, reg1), map_reg(&mapping, reg2))
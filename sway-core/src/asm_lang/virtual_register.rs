@@ -48,6 +48,7 @@ pub enum ConstantRegister {
     Flags,
     // Below are compiler-reserved registers
     DataSectionStart,
+    Scratch,
 }
 
 impl ConstantRegister {
@@ -75,6 +76,10 @@ impl ConstantRegister {
                 (crate::asm_generation::compiler_constants::DATA_SECTION_REGISTER)
                     as fuel_asm::RegisterId
             }
+            Scratch => {
+                (crate::asm_generation::compiler_constants::SCRATCH_REGISTER)
+                    as fuel_asm::RegisterId
+            }
         }
     }
 }
@@ -102,6 +107,7 @@ impl fmt::Display for ConstantRegister {
             // two `$` signs denotes this is a compiler-reserved register and not a
             // VM-reserved register
             DataSectionStart => "$$ds",
+            Scratch => "$$scratch",
         };
         write!(f, "{}", text)
     }
@@ -1,4 +1,6 @@
-use std::{path::PathBuf, sync::Arc};
+use crate::lint::LintLevel;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 
 /// Configuration for the overall build and compilation process.
 #[derive(Clone)]
@@ -9,6 +11,46 @@ pub struct BuildConfig {
     pub(crate) print_intermediate_asm: bool,
     pub(crate) print_finalized_asm: bool,
     pub(crate) print_ir: bool,
+    pub(crate) opt_level: OptLevel,
+    pub(crate) lints: BTreeMap<String, LintLevel>,
+    pub(crate) deny_warnings: bool,
+}
+
+/// How much effort the compiler spends on optimizing the generated code.
+///
+/// In a build profile this is written as a number, e.g. `opt-level = 1`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum OptLevel {
+    /// No optimizations beyond those required to generate correct code.
+    #[default]
+    Opt0,
+    /// Additionally runs the peephole optimizer over the generated ASM.
+    Opt1,
+}
+
+impl TryFrom<u8> for OptLevel {
+    type Error = String;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        match level {
+            0 => Ok(OptLevel::Opt0),
+            1 => Ok(OptLevel::Opt1),
+            _ => Err(format!(
+                "invalid optimization level {}, expected 0 or 1",
+                level
+            )),
+        }
+    }
+}
+
+impl From<OptLevel> for u8 {
+    fn from(level: OptLevel) -> Self {
+        match level {
+            OptLevel::Opt0 => 0,
+            OptLevel::Opt1 => 1,
+        }
+    }
 }
 
 impl BuildConfig {
@@ -46,6 +88,9 @@ impl BuildConfig {
             print_intermediate_asm: false,
             print_finalized_asm: false,
             print_ir: false,
+            opt_level: OptLevel::default(),
+            lints: BTreeMap::new(),
+            deny_warnings: false,
         }
     }
 
@@ -70,6 +115,24 @@ impl BuildConfig {
         }
     }
 
+    pub fn opt_level(self, opt_level: OptLevel) -> Self {
+        Self { opt_level, ..self }
+    }
+
+    /// The levels of lints by name, e.g. from the `[lints]` table of the package manifest. The
+    /// attributes of the program take precedence over these.
+    pub fn lints(self, lints: BTreeMap<String, LintLevel>) -> Self {
+        Self { lints, ..self }
+    }
+
+    /// Deny every lint which isn't otherwise allowed or warned about by the program.
+    pub fn deny_warnings(self, deny_warnings: bool) -> Self {
+        Self {
+            deny_warnings,
+            ..self
+        }
+    }
+
     pub fn canonical_root_module(&self) -> Arc<PathBuf> {
         self.canonical_root_module.clone()
     }
@@ -24,6 +24,13 @@ pub const TUPLE_NAME_PREFIX: &str = "__tuple_";
 // The default prefix for the compiler generated names of struct fields
 pub const DESTRUCTURE_PREFIX: &str = "__destructure_";
 
+/// The default prefix for the compiler generated names of values bound by refutable `let`
+/// patterns and destructured function parameters
+pub const PATTERN_NAME_PREFIX: &str = "__pattern_";
+
+/// The default prefix for the compiler generated names of `for` loop iterators
+pub const FOR_ITERATOR_NAME_PREFIX: &str = "__for_iterator_";
+
 /// The default prefix for the compiler generated names of match
 pub const MATCH_RETURN_VAR_NAME_PREFIX: &str = "__match_return_var_name_";
 
@@ -31,3 +38,8 @@ pub const MATCH_RETURN_VAR_NAME_PREFIX: &str = "__match_return_var_name_";
 pub const STORAGE_PURITY_ATTRIBUTE_NAME: &str = "storage";
 pub const STORAGE_PURITY_READ_NAME: &str = "read";
 pub const STORAGE_PURITY_WRITE_NAME: &str = "write";
+
+/// The attributes which set the level of lints, e.g. `#[allow(dead_code)]`.
+pub const ALLOW_ATTRIBUTE_NAME: &str = "allow";
+pub const WARN_ATTRIBUTE_NAME: &str = "warn";
+pub const DENY_ATTRIBUTE_NAME: &str = "deny";
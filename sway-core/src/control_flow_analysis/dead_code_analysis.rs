@@ -78,6 +78,10 @@ impl ControlFlowGraph {
                     span: field_name.span(),
                     warning_content: Warning::DeadStorageDeclaration,
                 }),
+                ControlFlowGraphNode::Variable { name } => Some(CompileWarning {
+                    span: name.span(),
+                    warning_content: Warning::UnusedVariable { name: name.clone() },
+                }),
                 ControlFlowGraphNode::OrganizationalDominator(..) => None,
             })
             .collect::<Vec<_>>();
@@ -262,7 +266,7 @@ fn connect_node(
             }
             (return_contents, None)
         }
-        TypedAstNodeContent::WhileLoop(TypedWhileLoop { body, .. }) => {
+        TypedAstNodeContent::WhileLoop(TypedWhileLoop { condition, body }) => {
             // a while loop can loop back to the beginning,
             // or it can terminate.
             // so we connect the _end_ of the while loop _both_ to its beginning and the next node.
@@ -280,7 +284,16 @@ fn connect_node(
                 while_loop_exit,
                 "condition is initially false".into(),
             );
-            let mut leaves = vec![entry];
+            // the condition is evaluated before every iteration
+            let mut leaves = connect_expression(
+                &condition.expression,
+                graph,
+                &[entry],
+                exit_node,
+                "while loop condition",
+                tree_type,
+                condition.span.clone(),
+            )?;
             let (l_leaves, _l_exit_node) =
                 depth_first_insertion_code_block(body, graph, &leaves, exit_node, tree_type)?;
             // insert edges from end of block back to beginning of it
@@ -355,7 +368,7 @@ fn connect_declaration(
                 graph.namespace.insert_constant(name.clone(), entry_node);
                 Ok(leaves.to_vec())
             } else {
-                connect_expression(
+                let leaves = connect_expression(
                     &body.expression,
                     graph,
                     &[entry_node],
@@ -363,7 +376,10 @@ fn connect_declaration(
                     "variable instantiation",
                     tree_type,
                     body.clone().span,
-                )
+                )?;
+                // the variable is only in scope after its initializer
+                insert_variable(name, graph);
+                Ok(leaves)
             }
         }
         ConstantDeclaration(TypedConstantDeclaration { name, value, .. }) => {
@@ -605,6 +621,10 @@ fn connect_typed_fn_decl(
     tree_type: &TreeType,
 ) -> Result<(), CompileError> {
     let fn_exit_node = graph.add_node(format!("\"{}\" fn exit", fn_decl.name.as_str()).into());
+    let scope = graph.namespace.variables.len();
+    for param in &fn_decl.parameters {
+        insert_variable(&param.name, graph);
+    }
     let (_exit_nodes, _exit_node) = depth_first_insertion_code_block(
         &fn_decl.body,
         graph,
@@ -612,6 +632,7 @@ fn connect_typed_fn_decl(
         Some(fn_exit_node),
         tree_type,
     )?;
+    graph.namespace.variables.truncate(scope);
     if let Some(exit_node) = exit_node {
         graph.add_edge(fn_exit_node, exit_node, "".into());
     }
@@ -642,14 +663,28 @@ fn depth_first_insertion_code_block(
 ) -> Result<(Vec<NodeIndex>, Option<NodeIndex>), CompileError> {
     let mut leaves = leaves.to_vec();
     let mut exit_node = exit_node;
+    let scope = graph.namespace.variables.len();
     for node in node_content.contents.iter() {
         let (this_node, l_exit_node) = connect_node(node, graph, &leaves, exit_node, tree_type)?;
         leaves = this_node;
         exit_node = l_exit_node;
     }
+    graph.namespace.variables.truncate(scope);
     Ok((leaves, exit_node))
 }
 
+/// Brings a local variable or function parameter into scope, as a node which its uses connect to
+/// so that it is reported if it is never used.
+///
+/// `self` and names starting with `_` are never reported, so they are not tracked.
+fn insert_variable(name: &Ident, graph: &mut ControlFlowGraph) {
+    if name.as_str() == "self" || name.as_str().starts_with('_') {
+        return;
+    }
+    let node = graph.add_node(ControlFlowGraphNode::Variable { name: name.clone() });
+    graph.namespace.insert_variable(name.clone(), node);
+}
+
 /// connects any inner parts of an expression to the graph
 /// note the main expression node has already been inserted
 fn connect_expression(
@@ -666,6 +701,8 @@ fn connect_expression(
         FunctionApplication {
             call_path: name,
             arguments,
+            contract_call_params,
+            selector,
             ..
         } => {
             let mut is_external = false;
@@ -706,6 +743,22 @@ fn connect_expression(
                     arg.clone().span,
                 )?;
             }
+            // as well as the address and the parameters of a contract call
+            let contract_call_exprs = selector
+                .iter()
+                .map(|selector| &*selector.contract_address)
+                .chain(contract_call_params.values());
+            for expr in contract_call_exprs {
+                current_leaf = connect_expression(
+                    &expr.expression,
+                    graph,
+                    &current_leaf,
+                    exit_node,
+                    "contract call param eval",
+                    tree_type,
+                    expr.span.clone(),
+                )?;
+            }
             // connect final leaf to fn exit
             for leaf in current_leaf {
                 graph.add_edge(leaf, fn_exit_point, "".into());
@@ -751,6 +804,12 @@ fn connect_expression(
             Ok(vec![node])
         }
         VariableExpression { name, .. } => {
+            if let Some(node) = graph.namespace.get_variable(name).cloned() {
+                for leaf in leaves {
+                    graph.add_edge(*leaf, node, "variable use".into());
+                }
+                return Ok(leaves.to_vec());
+            }
             // Variables may refer to global const declarations.
             Ok(graph
                 .namespace
@@ -863,10 +922,20 @@ fn connect_expression(
             Ok(vec![exit])
         }
         StructFieldAccess {
+            prefix,
             field_to_access,
             resolved_type_of_parent,
             ..
         } => {
+            let leaves = connect_expression(
+                &prefix.expression,
+                graph,
+                leaves,
+                exit_node,
+                "struct field access prefix",
+                tree_type,
+                prefix.span.clone(),
+            )?;
             let resolved_type_of_parent =
                 resolve_type(*resolved_type_of_parent, &field_to_access.span)
                     .unwrap_or_else(|_| TypeInfo::Tuple(Vec::new()));
@@ -894,7 +963,7 @@ fn connect_expression(
                 .into(),
             );
             for leaf in leaves {
-                graph.add_edge(*leaf, this_ix, "".into());
+                graph.add_edge(leaf, this_ix, "".into());
             }
             graph.add_edge(this_ix, field_ix, "".into());
             Ok(vec![this_ix])
@@ -1065,6 +1134,40 @@ fn connect_expression(
             tree_type,
             exp.span.clone(),
         ),
+        FunctionReference { call_path, .. } => {
+            // taking a pointer to a function makes it reachable, although it is not called here
+            if let Some(FunctionNamespaceEntry { entry_point, .. }) =
+                graph.namespace.get_function(&call_path.suffix).cloned()
+            {
+                for leaf in leaves {
+                    graph.add_edge(*leaf, entry_point, "fn reference".into());
+                }
+            }
+            Ok(leaves.to_vec())
+        }
+        IndirectFunctionApplication { callee, arguments } => {
+            let mut current_leaf = connect_expression(
+                &callee.expression,
+                graph,
+                leaves,
+                exit_node,
+                "indirect callee",
+                tree_type,
+                callee.span.clone(),
+            )?;
+            for arg in arguments {
+                current_leaf = connect_expression(
+                    &arg.expression,
+                    graph,
+                    &current_leaf,
+                    exit_node,
+                    "arg eval",
+                    tree_type,
+                    arg.span.clone(),
+                )?;
+            }
+            Ok(current_leaf)
+        }
     }
 }
 
@@ -1111,9 +1214,11 @@ fn connect_code_block(
         graph.add_edge(*leaf, block_entry, "".into());
     }
     let mut current_leaf = vec![block_entry];
+    let scope = graph.namespace.variables.len();
     for node in contents {
         current_leaf = connect_node(node, graph, &current_leaf, exit_node, tree_type)?.0;
     }
+    graph.namespace.variables.truncate(scope);
 
     let block_exit = graph.add_node("Code block exit".into());
     for leaf in current_leaf {
@@ -66,6 +66,10 @@ pub enum ControlFlowGraphNode {
     StorageField {
         field_name: Ident,
     },
+    /// A local variable or function parameter, connected from every expression which reads it.
+    Variable {
+        name: Ident,
+    },
 }
 
 impl std::fmt::Debug for ControlFlowGraphNode {
@@ -87,6 +91,7 @@ impl std::fmt::Debug for ControlFlowGraphNode {
             ControlFlowGraphNode::StorageField { field_name } => {
                 format!("Storage field {}", field_name.as_str())
             }
+            ControlFlowGraphNode::Variable { name } => format!("Variable {}", name.as_str()),
         };
         f.write_str(&text)
     }
@@ -40,6 +40,10 @@ pub struct ControlFlowNamespace {
     pub(crate) struct_namespace: HashMap<String, StructNamespaceEntry>,
     pub(crate) const_namespace: HashMap<Ident, NodeIndex>,
     pub(crate) storage: HashMap<Ident, NodeIndex>,
+    /// The local variables and function parameters in scope, the innermost last.
+    ///
+    /// Scopes are exited by truncating this back to the length it had when they were entered.
+    pub(crate) variables: Vec<(Ident, NodeIndex)>,
 }
 
 impl ControlFlowNamespace {
@@ -55,6 +59,16 @@ impl ControlFlowNamespace {
     pub(crate) fn insert_constant(&mut self, const_name: Ident, declaration_node: NodeIndex) {
         self.const_namespace.insert(const_name, declaration_node);
     }
+    pub(crate) fn get_variable(&self, ident: &Ident) -> Option<&NodeIndex> {
+        self.variables
+            .iter()
+            .rev()
+            .find(|(name, _)| name == ident)
+            .map(|(_, ix)| ix)
+    }
+    pub(crate) fn insert_variable(&mut self, ident: Ident, node: NodeIndex) {
+        self.variables.push((ident, node));
+    }
     pub(crate) fn insert_enum(
         &mut self,
         enum_name: Ident,
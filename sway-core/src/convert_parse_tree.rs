@@ -7,16 +7,17 @@ use {
         constants::{
             STORAGE_PURITY_ATTRIBUTE_NAME, STORAGE_PURITY_READ_NAME, STORAGE_PURITY_WRITE_NAME,
         },
-        error::{err, ok, CompileError, CompileResult, CompileWarning},
+        error::{err, ok, CompileError, CompileResult, CompileWarning, Warning},
+        lint::{self, LintLevel, LintScope},
         type_engine::{insert_type, AbiName, IntegerBits},
         AbiDeclaration, AsmExpression, AsmOp, AsmRegister, AsmRegisterDeclaration, AstNode,
         AstNodeContent, CallPath, CodeBlock, ConstantDeclaration, Declaration, EnumDeclaration,
         EnumVariant, Expression, FunctionDeclaration, FunctionParameter, ImplSelf, ImplTrait,
-        ImportType, IncludeStatement, LazyOp, Literal, MatchBranch, MethodName, ParseTree, Purity,
-        Reassignment, ReassignmentTarget, ReturnStatement, Scrutinee, StorageDeclaration,
-        StorageField, StructDeclaration, StructExpressionField, StructField, StructScrutineeField,
-        Supertrait, TraitDeclaration, TraitFn, TreeType, TypeInfo, UseStatement,
-        VariableDeclaration, Visibility, WhileLoop,
+        ImportType, IncludeStatement, LazyOp, Literal, MatchBranch, MatchSource, MethodName,
+        ParseTree, Purity, Reassignment, ReassignmentTarget, ReturnStatement, Scrutinee,
+        StorageDeclaration, StorageField, StructDeclaration, StructExpressionField, StructField,
+        StructScrutineeField, Supertrait, TraitConstant, TraitDeclaration, TraitFn, TreeType,
+        TypeInfo, UseStatement, VariableDeclaration, Visibility, WhileLoop,
     },
     std::{
         collections::HashMap,
@@ -33,10 +34,10 @@ use {
         Dependency, DoubleColonToken, Expr, ExprArrayDescriptor, ExprStructField,
         ExprTupleDescriptor, FnArg, FnArgs, FnSignature, GenericArgs, GenericParams, IfCondition,
         IfExpr, Instruction, Intrinsic, Item, ItemAbi, ItemConst, ItemEnum, ItemFn, ItemImpl,
-        ItemKind, ItemStorage, ItemStruct, ItemTrait, ItemUse, LitInt, LitIntType, MatchBranchKind,
-        Module, ModuleKind, PathExpr, PathExprSegment, PathType, PathTypeSegment, Pattern,
-        PatternStructField, PubToken, QualifiedPathRoot, Statement, StatementLet, Traits, Ty,
-        TypeField, UseTree, WhereClause,
+        ItemImplItem, ItemKind, ItemStorage, ItemStruct, ItemTrait, ItemTraitItem, ItemUse, LitInt,
+        LitIntType, MatchBranchKind, Module, ModuleKind, PathExpr, PathExprSegment, PathType,
+        PathTypeSegment, Pattern, PatternStructField, PubToken, QualifiedPathRoot, Statement,
+        StatementLet, Traits, Ty, TypeField, UseTree, WhereClause,
     },
     sway_types::{Ident, Span, Spanned},
     thiserror::Error,
@@ -49,6 +50,8 @@ use {
 pub struct ErrorContext {
     warnings: Vec<CompileWarning>,
     errors: Vec<CompileError>,
+    /// The spans of the items and statements which the parser failed to parse, and skipped over.
+    error_spans: Vec<Span>,
 }
 
 #[derive(Debug)]
@@ -60,7 +63,6 @@ pub struct ErrorEmitted {
 }
 
 impl ErrorContext {
-    #[allow(dead_code)]
     pub fn warning<W>(&mut self, warning: W)
     where
         W: Into<CompileWarning>,
@@ -140,18 +142,10 @@ pub enum ConvertParseTreeError {
     U64LiteralOutOfRange { span: Span },
     #[error("signed integers are not supported")]
     SignedIntegersNotSupported { span: Span },
-    #[error("literal patterns not supported in this position")]
-    LiteralPatternsNotSupportedHere { span: Span },
-    #[error("constant patterns not supported in this position")]
-    ConstantPatternsNotSupportedHere { span: Span },
-    #[error("constructor patterns not supported in this position")]
-    ConstructorPatternsNotSupportedHere { span: Span },
-    #[error("struct patterns not supported in this position")]
-    StructPatternsNotSupportedHere { span: Span },
-    #[error("wildcard patterns not supported in this position")]
-    WildcardPatternsNotSupportedHere { span: Span },
-    #[error("tuple patterns not supported in this position")]
-    TuplePatternsNotSupportedHere { span: Span },
+    #[error("patterns are not allowed in parameters of functions without bodies")]
+    PatternsNotSupportedWithoutBody { span: Span },
+    #[error("associated types are only allowed in trait implementations")]
+    AssociatedTypesNotSupportedHere { span: Span },
     #[error("constructor patterns require a single argument")]
     ConstructorPatternOneArg { span: Span },
     #[error("mutable bindings are not supported in this position")]
@@ -211,12 +205,8 @@ impl Spanned for ConvertParseTreeError {
             ConvertParseTreeError::U32LiteralOutOfRange { span } => span.clone(),
             ConvertParseTreeError::U64LiteralOutOfRange { span } => span.clone(),
             ConvertParseTreeError::SignedIntegersNotSupported { span } => span.clone(),
-            ConvertParseTreeError::LiteralPatternsNotSupportedHere { span } => span.clone(),
-            ConvertParseTreeError::ConstantPatternsNotSupportedHere { span } => span.clone(),
-            ConvertParseTreeError::ConstructorPatternsNotSupportedHere { span } => span.clone(),
-            ConvertParseTreeError::StructPatternsNotSupportedHere { span } => span.clone(),
-            ConvertParseTreeError::WildcardPatternsNotSupportedHere { span } => span.clone(),
-            ConvertParseTreeError::TuplePatternsNotSupportedHere { span } => span.clone(),
+            ConvertParseTreeError::PatternsNotSupportedWithoutBody { span } => span.clone(),
+            ConvertParseTreeError::AssociatedTypesNotSupportedHere { span } => span.clone(),
             ConvertParseTreeError::ConstructorPatternOneArg { span } => span.clone(),
             ConvertParseTreeError::MutableBindingsNotSupportedHere { span } => span.clone(),
             ConvertParseTreeError::ConstructorPatternSubPatterns { span } => span.clone(),
@@ -240,6 +230,7 @@ pub fn convert_parse_tree(module: Module) -> CompileResult<(TreeType, ParseTree)
     let mut ec = ErrorContext {
         warnings: Vec::new(),
         errors: Vec::new(),
+        error_spans: Vec::new(),
     };
     let tree_type = match module.kind {
         ModuleKind::Script { .. } => TreeType::Script,
@@ -248,7 +239,9 @@ pub fn convert_parse_tree(module: Module) -> CompileResult<(TreeType, ParseTree)
         ModuleKind::Library { ref name, .. } => TreeType::Library { name: name.clone() },
     };
     let res = module_to_sway_parse_tree(&mut ec, module);
-    let ErrorContext { warnings, errors } = ec;
+    let ErrorContext {
+        warnings, errors, ..
+    } = ec;
     match res {
         Ok(parse_tree) => ok((tree_type, parse_tree), warnings, errors),
         Err(_error_emitted) => err(warnings, errors),
@@ -260,6 +253,7 @@ pub fn module_to_sway_parse_tree(
     module: Module,
 ) -> Result<ParseTree, ErrorEmitted> {
     let span = module.span();
+    let lint_scopes = module_lint_scopes(ec, &module);
     let root_nodes = {
         let mut root_nodes: Vec<AstNode> = {
             module
@@ -279,7 +273,95 @@ pub fn module_to_sway_parse_tree(
         }
         root_nodes
     };
-    Ok(ParseTree { span, root_nodes })
+    Ok(ParseTree {
+        span,
+        root_nodes,
+        lint_scopes,
+        error_spans: std::mem::take(&mut ec.error_spans),
+    })
+}
+
+/// Collects the lint levels set by the attributes of the module, which apply to the whole file,
+/// and by the attributes of its items and of the functions and constants within them.
+fn module_lint_scopes(ec: &mut ErrorContext, module: &Module) -> Vec<LintScope> {
+    let kind_span = module.kind.span();
+    let src = kind_span.src().clone();
+    let file_span = Span::new(src.clone(), 0, src.len(), kind_span.path().cloned())
+        .expect("the whole file is a valid span");
+    let mut scopes = Vec::new();
+    scopes.extend(lint_scope(ec, &module.attribute_list, file_span));
+    for item in &module.items {
+        scopes.extend(lint_scope(ec, &item.attribute_list, item.span()));
+        let nested: Vec<(&[AttributeDecl], Span)> = match &item.value {
+            ItemKind::Impl(item_impl) => item_impl
+                .contents
+                .get()
+                .iter()
+                .map(|item| (&item.attribute_list[..], item.value.span()))
+                .collect(),
+            ItemKind::Trait(item_trait) => {
+                let decls = item_trait
+                    .trait_items
+                    .get()
+                    .iter()
+                    .map(|(item, _)| (&item.attribute_list[..], item.value.span()));
+                let defs = item_trait
+                    .trait_defs_opt
+                    .iter()
+                    .flat_map(|defs| defs.get())
+                    .map(|item| (&item.attribute_list[..], item.value.span()));
+                decls.chain(defs).collect()
+            }
+            ItemKind::Abi(item_abi) => {
+                let decls = item_abi
+                    .abi_items
+                    .get()
+                    .iter()
+                    .map(|(item, _)| (&item.attribute_list[..], item.value.span()));
+                let defs = item_abi
+                    .abi_defs_opt
+                    .iter()
+                    .flat_map(|defs| defs.get())
+                    .map(|item| (&item.attribute_list[..], item.value.span()));
+                decls.chain(defs).collect()
+            }
+            _ => Vec::new(),
+        };
+        for (attribute_list, span) in nested {
+            scopes.extend(lint_scope(ec, attribute_list, span));
+        }
+    }
+    scopes
+}
+
+/// The lint levels set by the `#[allow(..)]`, `#[warn(..)]` and `#[deny(..)]` attributes in the
+/// given list, if there are any. Lints which don't exist are warned about.
+fn lint_scope(
+    ec: &mut ErrorContext,
+    attribute_list: &[AttributeDecl],
+    span: Span,
+) -> Option<LintScope> {
+    let mut levels = Vec::new();
+    for attr_decl in attribute_list {
+        let attr = attr_decl.attribute.get();
+        let level = match LintLevel::from_attribute_name(attr.name.as_str()) {
+            Some(level) => level,
+            None => continue,
+        };
+        for name in attr.args.iter().flat_map(|parens| parens.get()) {
+            if !lint::is_lint_name(name.as_str()) {
+                ec.warning(CompileWarning {
+                    span: name.span(),
+                    warning_content: Warning::UnknownLint { name: name.clone() },
+                });
+            }
+            levels.push((name.clone(), level));
+        }
+    }
+    if levels.is_empty() {
+        return None;
+    }
+    Some(LintScope { span, levels })
 }
 
 fn item_to_ast_nodes(ec: &mut ErrorContext, item: Item) -> Result<Vec<AstNode>, ErrorEmitted> {
@@ -350,6 +432,11 @@ fn item_to_ast_nodes(ec: &mut ErrorContext, item: Item) -> Result<Vec<AstNode>,
                 span: span.clone(),
             })]
         }
+        // The syntax error was reported by the parser, which skipped over the broken item.
+        ItemKind::Error(span) => {
+            ec.error_spans.push(span);
+            Vec::new()
+        }
     };
     Ok(contents
         .into_iter()
@@ -589,15 +676,16 @@ fn item_fn_to_function_declaration(
         Some((_right_arrow_token, ty)) => ty.span(),
         None => item_fn.fn_signature.span(),
     };
+    let (parameters, destructurings) =
+        fn_args_to_function_parameters(ec, item_fn.fn_signature.arguments.into_inner())?;
+    let mut body = braced_code_block_contents_to_code_block(ec, item_fn.body)?;
+    body.contents.splice(0..0, destructurings);
     Ok(FunctionDeclaration {
         purity: get_attributed_purity(ec, attributes)?,
         name: item_fn.fn_signature.name,
         visibility: pub_token_opt_to_visibility(item_fn.fn_signature.visibility),
-        body: braced_code_block_contents_to_code_block(ec, item_fn.body)?,
-        parameters: fn_args_to_function_parameters(
-            ec,
-            item_fn.fn_signature.arguments.into_inner(),
-        )?,
+        body,
+        parameters,
         span,
         return_type: match item_fn.fn_signature.return_type_opt {
             Some((_right_arrow, ty)) => ty_to_type_info(ec, ty)?,
@@ -649,17 +737,26 @@ fn item_trait_to_trait_declaration(
     item_trait: ItemTrait,
 ) -> Result<TraitDeclaration, ErrorEmitted> {
     let name = item_trait.name;
-    let interface_surface = {
-        item_trait
-            .trait_items
-            .into_inner()
-            .into_iter()
-            .map(|(fn_signature, _semicolon_token)| {
-                let attributes = item_attrs_to_map(&fn_signature.attribute_list)?;
-                fn_signature_to_trait_fn(ec, fn_signature.value, &attributes)
-            })
-            .collect::<Result<_, _>>()?
-    };
+    let type_parameters = generic_params_opt_to_type_parameters(ec, item_trait.generics, None)?;
+    let mut interface_surface = Vec::new();
+    let mut associated_types = Vec::new();
+    let mut associated_consts = Vec::new();
+    for (trait_item, _semicolon_token) in item_trait.trait_items.into_inner() {
+        match trait_item.value {
+            ItemTraitItem::Fn(fn_signature) => {
+                let attributes = item_attrs_to_map(&trait_item.attribute_list)?;
+                interface_surface.push(fn_signature_to_trait_fn(ec, fn_signature, &attributes)?);
+            }
+            ItemTraitItem::Const(trait_const) => {
+                associated_consts.push(TraitConstant {
+                    name: trait_const.name,
+                    type_span: trait_const.ty.span(),
+                    type_ascription: ty_to_type_info(ec, trait_const.ty)?,
+                });
+            }
+            ItemTraitItem::Type(trait_type) => associated_types.push(trait_type.name),
+        }
+    }
     let methods = match item_trait.trait_defs_opt {
         None => Vec::new(),
         Some(trait_defs) => trait_defs
@@ -678,6 +775,9 @@ fn item_trait_to_trait_declaration(
     let visibility = pub_token_opt_to_visibility(item_trait.visibility);
     Ok(TraitDeclaration {
         name,
+        type_parameters,
+        associated_types,
+        associated_consts,
         interface_surface,
         methods,
         supertraits,
@@ -692,17 +792,23 @@ fn item_impl_to_declaration(
     let block_span = item_impl.span();
     let type_implementing_for_span = item_impl.ty.span();
     let type_implementing_for = ty_to_type_info(ec, item_impl.ty)?;
-    let functions = {
-        item_impl
-            .contents
-            .into_inner()
-            .into_iter()
-            .map(|item| {
+    let mut functions = Vec::new();
+    let mut associated_types = Vec::new();
+    let mut associated_consts = Vec::new();
+    for item in item_impl.contents.into_inner() {
+        match item.value {
+            ItemImplItem::Fn(item_fn) => {
                 let attributes = item_attrs_to_map(&item.attribute_list)?;
-                item_fn_to_function_declaration(ec, item.value, &attributes)
-            })
-            .collect::<Result<_, _>>()?
-    };
+                functions.push(item_fn_to_function_declaration(ec, item_fn, &attributes)?);
+            }
+            ItemImplItem::Const(item_const) => {
+                associated_consts.push(item_const_to_constant_declaration(ec, item_const)?);
+            }
+            ItemImplItem::Type(impl_type) => {
+                associated_types.push((impl_type.name, ty_to_type_argument(ec, impl_type.ty)?));
+            }
+        }
+    }
 
     let type_parameters = generic_params_opt_to_type_parameters(
         ec,
@@ -712,22 +818,34 @@ fn item_impl_to_declaration(
 
     match item_impl.trait_opt {
         Some((path_type, _for_token)) => {
+            let (trait_name, trait_type_arguments) =
+                path_type_to_call_path_and_type_arguments(ec, path_type)?;
             let impl_trait = ImplTrait {
-                trait_name: path_type_to_call_path(ec, path_type)?,
+                trait_name,
+                trait_type_arguments,
                 type_implementing_for,
                 type_implementing_for_span,
                 type_parameters,
                 functions,
+                associated_types,
+                associated_consts,
                 block_span,
             };
             Ok(Declaration::ImplTrait(impl_trait))
         }
         None => {
+            if let Some((name, type_argument)) = associated_types.first() {
+                let error = ConvertParseTreeError::AssociatedTypesNotSupportedHere {
+                    span: Span::join(name.span(), type_argument.span.clone()),
+                };
+                return Err(ec.error(error));
+            }
             let impl_self = ImplSelf {
                 type_implementing_for,
                 type_implementing_for_span,
                 type_parameters,
                 functions,
+                associated_consts,
                 block_span,
             };
             Ok(Declaration::ImplSelf(impl_self))
@@ -940,35 +1058,35 @@ fn braced_code_block_contents_to_code_block(
     })
 }
 
+/// Converts function arguments to function parameters, along with the variable declarations
+/// which destructure any parameters bound to patterns.
 fn fn_args_to_function_parameters(
     ec: &mut ErrorContext,
     fn_args: FnArgs,
-) -> Result<Vec<FunctionParameter>, ErrorEmitted> {
-    let function_parameters = match fn_args {
-        FnArgs::Static(args) => args
-            .into_iter()
-            .map(|fn_arg| fn_arg_to_function_parameter(ec, fn_arg))
-            .collect::<Result<_, _>>()?,
+) -> Result<(Vec<FunctionParameter>, Vec<AstNode>), ErrorEmitted> {
+    let mut function_parameters = Vec::new();
+    let mut destructurings = Vec::new();
+    let args = match fn_args {
+        FnArgs::Static(args) => Some(args),
         FnArgs::NonStatic {
             self_token,
             mutable_self,
             args_opt,
         } => {
-            let mut function_parameters = vec![FunctionParameter {
+            function_parameters.push(FunctionParameter {
                 name: Ident::new(self_token.span()),
                 is_mutable: mutable_self.is_some(),
                 type_id: insert_type(TypeInfo::SelfType),
                 type_span: self_token.span(),
-            }];
-            if let Some((_comma_token, args)) = args_opt {
-                for arg in args {
-                    let function_parameter = fn_arg_to_function_parameter(ec, arg)?;
-                    function_parameters.push(function_parameter);
-                }
-            }
-            function_parameters
+            });
+            args_opt.map(|(_comma_token, args)| args)
         }
     };
+    for arg in args.into_iter().flatten() {
+        let (function_parameter, destructuring) = fn_arg_to_function_parameter(ec, arg)?;
+        function_parameters.push(function_parameter);
+        destructurings.extend(destructuring);
+    }
 
     let mut unique_params = HashSet::<Ident>::default();
     for fn_param in &function_parameters {
@@ -983,7 +1101,7 @@ fn fn_args_to_function_parameters(
         }
     }
 
-    Ok(function_parameters)
+    Ok((function_parameters, destructurings))
 }
 
 fn type_name_to_type_info_opt(name: &Ident) -> Option<TypeInfo> {
@@ -1020,6 +1138,28 @@ fn ty_to_type_info(ec: &mut ErrorContext, ty: Ty) -> Result<TypeInfo, ErrorEmitt
         }
         Ty::Str { length, .. } => TypeInfo::Str(expr_to_u64(ec, *length.into_inner())?),
         Ty::Infer { .. } => TypeInfo::Unknown,
+        Ty::Fn {
+            fn_token,
+            arguments,
+            return_type_opt,
+        } => {
+            let parameters = arguments
+                .into_inner()
+                .into_iter()
+                .map(|ty| ty_to_type_argument(ec, ty))
+                .collect::<Result<Vec<_>, _>>()?;
+            let return_type = match return_type_opt {
+                Some((_right_arrow_token, ty)) => ty_to_type_argument(ec, *ty)?,
+                None => TypeArgument {
+                    type_id: insert_type(TypeInfo::Tuple(Vec::new())),
+                    span: fn_token.span(),
+                },
+            };
+            TypeInfo::Function {
+                parameters,
+                return_type,
+            }
+        }
     };
     Ok(type_info)
 }
@@ -1042,10 +1182,18 @@ fn fn_signature_to_trait_fn(
         Some((_right_arrow_token, ty)) => ty.span(),
         None => fn_signature.span(),
     };
+    let (parameters, destructurings) =
+        fn_args_to_function_parameters(ec, fn_signature.arguments.into_inner())?;
+    if let Some(destructuring) = destructurings.first() {
+        let error = ConvertParseTreeError::PatternsNotSupportedWithoutBody {
+            span: destructuring.span.clone(),
+        };
+        return Err(ec.error(error));
+    }
     let trait_fn = TraitFn {
         name: fn_signature.name,
         purity: get_attributed_purity(ec, attributes)?,
-        parameters: fn_args_to_function_parameters(ec, fn_signature.arguments.into_inner())?,
+        parameters,
         return_type: match fn_signature.return_type_opt {
             Some((_right_arrow_token, ty)) => ty_to_type_info(ec, ty)?,
             None => TypeInfo::Tuple(Vec::new()),
@@ -1111,6 +1259,26 @@ fn path_type_to_call_path(
     Ok(call_path)
 }
 
+/// Like [path_type_to_call_path], but allows type arguments on the final segment of the path,
+/// as in `impl From<u64> for Foo`.
+fn path_type_to_call_path_and_type_arguments(
+    ec: &mut ErrorContext,
+    mut path_type: PathType,
+) -> Result<(CallPath, Vec<TypeArgument>), ErrorEmitted> {
+    let last_segment = match path_type.suffix.last_mut() {
+        Some((_double_colon_token, segment)) => segment,
+        None => &mut path_type.prefix,
+    };
+    let type_arguments = match last_segment.generics_opt.take() {
+        Some((_double_colon_token, generic_args)) => {
+            generic_args_to_type_arguments(ec, generic_args)?
+        }
+        None => Vec::new(),
+    };
+    let call_path = path_type_to_call_path(ec, path_type)?;
+    Ok((call_path, type_arguments))
+}
+
 fn expr_to_ast_node(
     ec: &mut ErrorContext,
     expr: Expr,
@@ -1134,10 +1302,11 @@ fn expr_to_ast_node(
         Expr::While {
             condition, block, ..
         } => AstNode {
-            content: AstNodeContent::WhileLoop(WhileLoop {
-                condition: expr_to_expression(ec, *condition)?,
-                body: braced_code_block_contents_to_code_block(ec, block)?,
-            }),
+            content: AstNodeContent::WhileLoop(while_to_while_loop(ec, condition, block, &span)?),
+            span,
+        },
+        expr @ Expr::For { .. } => AstNode {
+            content: AstNodeContent::Expression(expr_to_expression(ec, expr)?),
             span,
         },
         Expr::Reassignment {
@@ -1323,6 +1492,7 @@ fn expr_to_expression(ec: &mut ErrorContext, expr: Expr) -> Result<Expression, E
                                 Expression::MatchExp {
                                     value: Box::new(var_decl_exp),
                                     branches,
+                                    source: MatchSource::Match,
                                     span: span.clone(),
                                 },
                             ),
@@ -1340,6 +1510,48 @@ fn expr_to_expression(ec: &mut ErrorContext, expr: Expr) -> Result<Expression, E
             };
             return Err(ec.error(error));
         }
+        Expr::For {
+            value_pattern,
+            iterator,
+            block,
+            ..
+        } => for_to_expression(ec, value_pattern, *iterator, block, span)?,
+        Expr::Range { start, end, .. } => {
+            let field = |ec: &mut ErrorContext,
+                         name: &'static str,
+                         expr: Box<Expr>|
+             -> Result<StructExpressionField, ErrorEmitted> {
+                let span = expr.span();
+                Ok(StructExpressionField {
+                    name: Ident::new_with_override(name, span.clone()),
+                    value: expr_to_expression(ec, *expr)?,
+                    span,
+                })
+            };
+            let call_path_binding = TypeBinding {
+                inner: CallPath {
+                    prefixes: vec![
+                        Ident::new_with_override("core", span.clone()),
+                        Ident::new_with_override("ops", span.clone()),
+                    ],
+                    suffix: (
+                        TypeInfo::Custom {
+                            name: Ident::new_with_override("Range", span.clone()),
+                            type_arguments: None,
+                        },
+                        span.clone(),
+                    ),
+                    is_absolute: true,
+                },
+                type_arguments: vec![],
+                span: span.clone(),
+            };
+            Expression::StructExpression {
+                call_path_binding,
+                fields: vec![field(ec, "start", start)?, field(ec, "end", end)?],
+                span,
+            }
+        }
         Expr::FuncApp { func, args } => {
             let path_expr = match *func {
                 Expr::Path(path_expr) => path_expr,
@@ -1897,21 +2109,26 @@ fn statement_to_ast_nodes(
         Statement::Let(statement_let) => statement_let_to_ast_nodes(ec, statement_let)?,
         Statement::Item(item) => item_to_ast_nodes(ec, item)?,
         Statement::Expr { expr, .. } => vec![expr_to_ast_node(ec, expr, true)?],
+        // The syntax error was reported by the parser, which skipped over the broken statement.
+        Statement::Error(span) => {
+            ec.error_spans.push(span);
+            Vec::new()
+        }
     };
     Ok(ast_nodes)
 }
 
+/// Converts a function argument to a function parameter.
+///
+/// An argument with a destructuring pattern is given a generated name, and the variable
+/// declarations which destructure it are returned to be prepended to the function body.
 fn fn_arg_to_function_parameter(
     ec: &mut ErrorContext,
     fn_arg: FnArg,
-) -> Result<FunctionParameter, ErrorEmitted> {
+) -> Result<(FunctionParameter, Vec<AstNode>), ErrorEmitted> {
     let type_span = fn_arg.ty.span();
     let pat_span = fn_arg.pattern.span();
-    let name = match fn_arg.pattern {
-        Pattern::Wildcard { .. } => {
-            let error = ConvertParseTreeError::WildcardPatternsNotSupportedHere { span: pat_span };
-            return Err(ec.error(error));
-        }
+    let (name, destructuring) = match fn_arg.pattern {
         Pattern::Var { mutable, name } => {
             if let Some(mut_token) = mutable {
                 let error = ConvertParseTreeError::MutableBindingsNotSupportedHere {
@@ -1919,28 +2136,26 @@ fn fn_arg_to_function_parameter(
                 };
                 return Err(ec.error(error));
             }
-            name
-        }
-        Pattern::Literal(..) => {
-            let error = ConvertParseTreeError::LiteralPatternsNotSupportedHere { span: pat_span };
-            return Err(ec.error(error));
-        }
-        Pattern::Constant(..) => {
-            let error = ConvertParseTreeError::ConstantPatternsNotSupportedHere { span: pat_span };
-            return Err(ec.error(error));
-        }
-        Pattern::Constructor { .. } => {
-            let error =
-                ConvertParseTreeError::ConstructorPatternsNotSupportedHere { span: pat_span };
-            return Err(ec.error(error));
-        }
-        Pattern::Struct { .. } => {
-            let error = ConvertParseTreeError::StructPatternsNotSupportedHere { span: pat_span };
-            return Err(ec.error(error));
-        }
-        Pattern::Tuple(..) => {
-            let error = ConvertParseTreeError::TuplePatternsNotSupportedHere { span: pat_span };
-            return Err(ec.error(error));
+            (name, Vec::new())
+        }
+        Pattern::Wildcard { .. } => (
+            generate_unique_name(crate::constants::PATTERN_NAME_PREFIX, pat_span),
+            Vec::new(),
+        ),
+        pattern => {
+            let name =
+                generate_unique_name(crate::constants::PATTERN_NAME_PREFIX, pat_span.clone());
+            let destructuring = pattern_to_ast_nodes(
+                ec,
+                pattern,
+                None,
+                Expression::VariableExpression {
+                    name: name.clone(),
+                    span: pat_span.clone(),
+                },
+                pat_span,
+            )?;
+            (name, destructuring)
         }
     };
     let function_parameter = FunctionParameter {
@@ -1949,7 +2164,7 @@ fn fn_arg_to_function_parameter(
         type_id: insert_type(ty_to_type_info(ec, fn_arg.ty)?),
         type_span,
     };
-    Ok(function_parameter)
+    Ok((function_parameter, destructuring))
 }
 
 fn expr_to_usize(ec: &mut ErrorContext, expr: Expr) -> Result<usize, ErrorEmitted> {
@@ -2243,6 +2458,7 @@ fn if_expr_to_expression(
             Expression::MatchExp {
                 value: Box::new(expr_to_expression(ec, *rhs)?),
                 branches,
+                source: MatchSource::IfLet,
                 span,
             }
         }
@@ -2250,6 +2466,157 @@ fn if_expr_to_expression(
     Ok(expression)
 }
 
+/// Desugars a `for` loop into a `while` loop using the `has_next` and `next` methods of the
+/// `core::iter::Iterator` trait:
+///
+/// ```ignore
+/// {
+///     let mut __for_iterator_0 = iterator;
+///     while __for_iterator_0.has_next() {
+///         let value_pattern = __for_iterator_0.next();
+///         ...
+///     }
+/// }
+/// ```
+fn for_to_expression(
+    ec: &mut ErrorContext,
+    value_pattern: Pattern,
+    iterator: Expr,
+    block: Braces<CodeBlockContents>,
+    span: Span,
+) -> Result<Expression, ErrorEmitted> {
+    let iterator_span = iterator.span();
+    let iterator_name = generate_unique_name(
+        crate::constants::FOR_ITERATOR_NAME_PREFIX,
+        iterator_span.clone(),
+    );
+    let method_call = |receiver: &Ident, method_name| Expression::MethodApplication {
+        method_name_binding: TypeBinding {
+            inner: MethodName::FromModule {
+                method_name: Ident::new_with_override(method_name, iterator_span.clone()),
+            },
+            type_arguments: vec![],
+            span: iterator_span.clone(),
+        },
+        contract_call_params: Vec::new(),
+        arguments: vec![Expression::VariableExpression {
+            name: receiver.clone(),
+            span: iterator_span.clone(),
+        }],
+        span: iterator_span.clone(),
+    };
+
+    // Bind the next value to the pattern at the start of each iteration.
+    let pattern_span = value_pattern.span();
+    let mut body = braced_code_block_contents_to_code_block(ec, block)?;
+    let value_declarations = pattern_to_ast_nodes(
+        ec,
+        value_pattern,
+        None,
+        method_call(&iterator_name, "next"),
+        pattern_span,
+    )?;
+    body.contents.splice(0..0, value_declarations);
+
+    let iterator_declaration = AstNode {
+        content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
+            VariableDeclaration {
+                name: iterator_name.clone(),
+                type_ascription: TypeInfo::Unknown,
+                type_ascription_span: None,
+                body: expr_to_expression(ec, iterator)?,
+                is_mutable: true,
+            },
+        )),
+        span: iterator_span.clone(),
+    };
+    let while_loop = AstNode {
+        content: AstNodeContent::WhileLoop(WhileLoop {
+            condition: method_call(&iterator_name, "has_next"),
+            body,
+        }),
+        span: span.clone(),
+    };
+    Ok(Expression::CodeBlock {
+        contents: CodeBlock {
+            contents: vec![iterator_declaration, while_loop],
+            whole_block_span: span.clone(),
+        },
+        span,
+    })
+}
+
+/// Converts a `while` loop, desugaring a `while let` loop into a `while true` loop whose body is a
+/// match expression, which runs the original body if the pattern matches and breaks otherwise.
+fn while_to_while_loop(
+    ec: &mut ErrorContext,
+    condition: IfCondition,
+    block: Braces<CodeBlockContents>,
+    span: &Span,
+) -> Result<WhileLoop, ErrorEmitted> {
+    let while_loop = match condition {
+        IfCondition::Expr(condition) => WhileLoop {
+            condition: expr_to_expression(ec, *condition)?,
+            body: braced_code_block_contents_to_code_block(ec, block)?,
+        },
+        IfCondition::Let { lhs, rhs, .. } => {
+            let body_span = block.span();
+            let body = Expression::CodeBlock {
+                contents: braced_code_block_contents_to_code_block(ec, block)?,
+                span: body_span.clone(),
+            };
+            let scrutinee = pattern_to_scrutinee(ec, *lhs)?;
+            let scrutinee_span = scrutinee.span();
+            let branches = vec![
+                MatchBranch {
+                    scrutinee,
+                    result: body,
+                    span: Span::join(scrutinee_span, body_span.clone()),
+                },
+                // If the pattern doesn't match then the loop is finished.
+                MatchBranch {
+                    scrutinee: Scrutinee::CatchAll {
+                        span: body_span.clone(),
+                    },
+                    result: Expression::CodeBlock {
+                        contents: CodeBlock {
+                            contents: vec![AstNode {
+                                content: AstNodeContent::Declaration(Declaration::Break {
+                                    span: body_span.clone(),
+                                }),
+                                span: body_span.clone(),
+                            }],
+                            whole_block_span: body_span.clone(),
+                        },
+                        span: body_span.clone(),
+                    },
+                    span: body_span,
+                },
+            ];
+            let match_exp = Expression::MatchExp {
+                value: Box::new(expr_to_expression(ec, *rhs)?),
+                branches,
+                source: MatchSource::WhileLet,
+                span: span.clone(),
+            };
+            WhileLoop {
+                condition: Expression::Literal {
+                    value: Literal::Boolean(true),
+                    span: span.clone(),
+                },
+                body: CodeBlock {
+                    contents: vec![AstNode {
+                        content: AstNodeContent::Expression(match_exp),
+                        span: span.clone(),
+                    }],
+                    whole_block_span: span.clone(),
+                },
+            }
+        }
+    };
+    Ok(while_loop)
+}
+
 fn path_root_opt_to_bool(
     ec: &mut ErrorContext,
     root_opt: Option<(Option<AngleBrackets<QualifiedPathRoot>>, DoubleColonToken)>,
@@ -2585,235 +2952,379 @@ fn statement_let_to_ast_nodes(
     ec: &mut ErrorContext,
     statement_let: StatementLet,
 ) -> Result<Vec<AstNode>, ErrorEmitted> {
-    fn unfold(
-        ec: &mut ErrorContext,
-        pattern: Pattern,
-        ty_opt: Option<Ty>,
-        expression: Expression,
-        span: Span,
-    ) -> Result<Vec<AstNode>, ErrorEmitted> {
-        let ast_nodes = match pattern {
-            Pattern::Wildcard { .. } | Pattern::Var { .. } => {
-                let (mutable, name) = match pattern {
-                    Pattern::Var { mutable, name } => (mutable, name),
-                    Pattern::Wildcard { .. } => (None, Ident::new_no_span("_")),
-                    _ => unreachable!(),
-                };
-                let (type_ascription, type_ascription_span) = match ty_opt {
-                    Some(ty) => {
-                        let type_ascription_span = ty.span();
-                        let type_ascription = ty_to_type_info(ec, ty)?;
-                        (type_ascription, Some(type_ascription_span))
+    let span = statement_let.span();
+    let initial_expression = expr_to_expression(ec, statement_let.expr)?;
+    pattern_to_ast_nodes(
+        ec,
+        statement_let.pattern,
+        statement_let.ty_opt.map(|(_colon_token, ty)| ty),
+        initial_expression,
+        span,
+    )
+}
+
+/// Creates the variable declarations for the variables bound by matching `pattern` against
+/// `expression`.
+fn pattern_to_ast_nodes(
+    ec: &mut ErrorContext,
+    pattern: Pattern,
+    ty_opt: Option<Ty>,
+    expression: Expression,
+    span: Span,
+) -> Result<Vec<AstNode>, ErrorEmitted> {
+    let ast_nodes = match pattern {
+        Pattern::Wildcard { .. } | Pattern::Var { .. } => {
+            let (mutable, name) = match pattern {
+                Pattern::Var { mutable, name } => (mutable, name),
+                Pattern::Wildcard { .. } => (None, Ident::new_no_span("_")),
+                _ => unreachable!(),
+            };
+            let (type_ascription, type_ascription_span) = match ty_opt {
+                Some(ty) => {
+                    let type_ascription_span = ty.span();
+                    let type_ascription = ty_to_type_info(ec, ty)?;
+                    (type_ascription, Some(type_ascription_span))
+                }
+                None => (TypeInfo::Unknown, None),
+            };
+            let ast_node = AstNode {
+                content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
+                    VariableDeclaration {
+                        name,
+                        type_ascription,
+                        type_ascription_span,
+                        body: expression,
+                        is_mutable: mutable.is_some(),
+                    },
+                )),
+                span,
+            };
+            vec![ast_node]
+        }
+        Pattern::Literal(..) | Pattern::Constant(..) | Pattern::Constructor { .. } => {
+            refutable_pattern_to_ast_nodes(ec, pattern, ty_opt, expression, span)?
+        }
+        Pattern::Struct { fields, .. } => {
+            let mut ast_nodes = Vec::new();
+
+            // Generate a deterministic name for the destructured struct
+            // Because the parser is single threaded, the name generated below will be stable.
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let destructured_name = format!(
+                "{}{}",
+                crate::constants::DESTRUCTURE_PREFIX,
+                COUNTER.load(Ordering::SeqCst)
+            );
+            COUNTER.fetch_add(1, Ordering::SeqCst);
+            let destructure_name = Ident::new_with_override(
+                Box::leak(destructured_name.into_boxed_str()),
+                span.clone(),
+            );
+
+            // Parse the type ascription and the type ascription span.
+            // In the event that the user did not provide a type ascription,
+            // it is set to TypeInfo::Unknown and the span to None.
+            let (type_ascription, type_ascription_span) = match &ty_opt {
+                Some(ty) => {
+                    let type_ascription_span = ty.span();
+                    let type_ascription = ty_to_type_info(ec, ty.clone())?;
+                    (type_ascription, Some(type_ascription_span))
+                }
+                None => (TypeInfo::Unknown, None),
+            };
+
+            // Save the destructure to the new name as a new variable declaration
+            let save_body_first = VariableDeclaration {
+                name: destructure_name.clone(),
+                type_ascription,
+                type_ascription_span,
+                body: expression,
+                is_mutable: false,
+            };
+            ast_nodes.push(AstNode {
+                content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
+                    save_body_first,
+                )),
+                span: span.clone(),
+            });
+
+            // create a new variable expression that points to the new destructured struct name that we just created
+            let new_expr = Expression::VariableExpression {
+                name: destructure_name,
+                span: span.clone(),
+            };
+
+            // for all of the fields of the struct destructuring on the LHS,
+            // recursively create variable declarations
+            for pattern_struct_field in fields.into_inner().into_iter() {
+                let (field, recursive_pattern) = match pattern_struct_field {
+                    PatternStructField::Field {
+                        field_name,
+                        pattern_opt,
+                    } => {
+                        let recursive_pattern = match pattern_opt {
+                            Some((_colon_token, box_pattern)) => *box_pattern,
+                            None => Pattern::Var {
+                                mutable: None,
+                                name: field_name.clone(),
+                            },
+                        };
+                        (field_name, recursive_pattern)
                     }
-                    None => (TypeInfo::Unknown, None),
-                };
-                let ast_node = AstNode {
-                    content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
-                        VariableDeclaration {
-                            name,
-                            type_ascription,
-                            type_ascription_span,
-                            body: expression,
-                            is_mutable: mutable.is_some(),
-                        },
-                    )),
-                    span,
-                };
-                vec![ast_node]
-            }
-            Pattern::Literal(..) => {
-                let error = ConvertParseTreeError::LiteralPatternsNotSupportedHere { span };
-                return Err(ec.error(error));
-            }
-            Pattern::Constant(..) => {
-                let error = ConvertParseTreeError::ConstantPatternsNotSupportedHere { span };
-                return Err(ec.error(error));
-            }
-            Pattern::Constructor { .. } => {
-                let error = ConvertParseTreeError::ConstructorPatternsNotSupportedHere { span };
-                return Err(ec.error(error));
-            }
-            Pattern::Struct { fields, .. } => {
-                let mut ast_nodes = Vec::new();
-
-                // Generate a deterministic name for the destructured struct
-                // Because the parser is single threaded, the name generated below will be stable.
-                static COUNTER: AtomicUsize = AtomicUsize::new(0);
-                let destructured_name = format!(
-                    "{}{}",
-                    crate::constants::DESTRUCTURE_PREFIX,
-                    COUNTER.load(Ordering::SeqCst)
-                );
-                COUNTER.fetch_add(1, Ordering::SeqCst);
-                let destructure_name = Ident::new_with_override(
-                    Box::leak(destructured_name.into_boxed_str()),
-                    span.clone(),
-                );
-
-                // Parse the type ascription and the type ascription span.
-                // In the event that the user did not provide a type ascription,
-                // it is set to TypeInfo::Unknown and the span to None.
-                let (type_ascription, type_ascription_span) = match &ty_opt {
-                    Some(ty) => {
-                        let type_ascription_span = ty.span();
-                        let type_ascription = ty_to_type_info(ec, ty.clone())?;
-                        (type_ascription, Some(type_ascription_span))
+                    PatternStructField::Rest { .. } => {
+                        continue;
                     }
-                    None => (TypeInfo::Unknown, None),
                 };
 
-                // Save the destructure to the new name as a new variable declaration
-                let save_body_first = VariableDeclaration {
-                    name: destructure_name.clone(),
-                    type_ascription,
-                    type_ascription_span,
-                    body: expression,
-                    is_mutable: false,
-                };
-                ast_nodes.push(AstNode {
-                    content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
-                        save_body_first,
-                    )),
-                    span: span.clone(),
-                });
+                // recursively create variable declarations for the subpatterns on the LHS
+                // and add them to the ast nodes
+                ast_nodes.extend(pattern_to_ast_nodes(
+                    ec,
+                    recursive_pattern,
+                    None,
+                    Expression::SubfieldExpression {
+                        prefix: Box::new(new_expr.clone()),
+                        span: span.clone(),
+                        field_to_access: field,
+                    },
+                    span.clone(),
+                )?);
+            }
+            ast_nodes
+        }
+        Pattern::Tuple(pat_tuple) => {
+            let mut ast_nodes = Vec::new();
 
-                // create a new variable expression that points to the new destructured struct name that we just created
-                let new_expr = Expression::VariableExpression {
-                    name: destructure_name,
-                    span: span.clone(),
-                };
+            // Generate a deterministic name for the tuple.
+            // Because the parser is single threaded, the name generated below will be stable.
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let tuple_name = format!(
+                "{}{}",
+                crate::constants::TUPLE_NAME_PREFIX,
+                COUNTER.load(Ordering::SeqCst)
+            );
+            COUNTER.fetch_add(1, Ordering::SeqCst);
+            let tuple_name =
+                Ident::new_with_override(Box::leak(tuple_name.into_boxed_str()), span.clone());
+
+            // Parse the type ascription and the type ascription span.
+            // In the event that the user did not provide a type ascription,
+            // it is set to TypeInfo::Unknown and the span to None.
+            let (type_ascription, type_ascription_span) = match &ty_opt {
+                Some(ty) => {
+                    let type_ascription_span = ty.span();
+                    let type_ascription = ty_to_type_info(ec, ty.clone())?;
+                    (type_ascription, Some(type_ascription_span))
+                }
+                None => (TypeInfo::Unknown, None),
+            };
 
-                // for all of the fields of the struct destructuring on the LHS,
-                // recursively create variable declarations
-                for pattern_struct_field in fields.into_inner().into_iter() {
-                    let (field, recursive_pattern) = match pattern_struct_field {
-                        PatternStructField::Field {
-                            field_name,
-                            pattern_opt,
-                        } => {
-                            let recursive_pattern = match pattern_opt {
-                                Some((_colon_token, box_pattern)) => *box_pattern,
-                                None => Pattern::Var {
-                                    mutable: None,
-                                    name: field_name.clone(),
-                                },
-                            };
-                            (field_name, recursive_pattern)
-                        }
-                        PatternStructField::Rest { .. } => {
-                            continue;
-                        }
-                    };
+            // Save the tuple to the new name as a new variable declaration.
+            let save_body_first = VariableDeclaration {
+                name: tuple_name.clone(),
+                type_ascription,
+                type_ascription_span,
+                body: expression,
+                is_mutable: false,
+            };
+            ast_nodes.push(AstNode {
+                content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
+                    save_body_first,
+                )),
+                span: span.clone(),
+            });
 
-                    // recursively create variable declarations for the subpatterns on the LHS
-                    // and add them to the ast nodes
-                    ast_nodes.extend(unfold(
-                        ec,
-                        recursive_pattern,
-                        None,
-                        Expression::SubfieldExpression {
-                            prefix: Box::new(new_expr.clone()),
-                            span: span.clone(),
-                            field_to_access: field,
-                        },
-                        span.clone(),
-                    )?);
-                }
-                ast_nodes
+            // create a variable expression that points to the new tuple name that we just created
+            let new_expr = Expression::VariableExpression {
+                name: tuple_name,
+                span: span.clone(),
+            };
+
+            // from the possible type annotation, if the annotation was a tuple annotation,
+            // extract the internal types of the annotation
+            let tuple_tys_opt = match ty_opt {
+                Some(Ty::Tuple(tys)) => Some(tys.into_inner().to_tys()),
+                _ => None,
+            };
+
+            // for all of the elements in the tuple destructuring on the LHS,
+            // recursively create variable declarations
+            for (index, pattern) in pat_tuple.into_inner().into_iter().enumerate() {
+                // from the possible type annotation, grab the type at the index of the current element
+                // we are processing
+                let ty_opt = tuple_tys_opt
+                    .as_ref()
+                    .and_then(|tys| tys.get(index).cloned());
+
+                // recursively create variable declarations for the subpatterns on the LHS
+                // and add them to the ast nodes
+                ast_nodes.extend(pattern_to_ast_nodes(
+                    ec,
+                    pattern,
+                    ty_opt,
+                    Expression::TupleIndex {
+                        prefix: Box::new(new_expr.clone()),
+                        index,
+                        index_span: span.clone(),
+                        span: span.clone(),
+                    },
+                    span.clone(),
+                )?);
             }
-            Pattern::Tuple(pat_tuple) => {
-                let mut ast_nodes = Vec::new();
-
-                // Generate a deterministic name for the tuple.
-                // Because the parser is single threaded, the name generated below will be stable.
-                static COUNTER: AtomicUsize = AtomicUsize::new(0);
-                let tuple_name = format!(
-                    "{}{}",
-                    crate::constants::TUPLE_NAME_PREFIX,
-                    COUNTER.load(Ordering::SeqCst)
-                );
-                COUNTER.fetch_add(1, Ordering::SeqCst);
-                let tuple_name =
-                    Ident::new_with_override(Box::leak(tuple_name.into_boxed_str()), span.clone());
-
-                // Parse the type ascription and the type ascription span.
-                // In the event that the user did not provide a type ascription,
-                // it is set to TypeInfo::Unknown and the span to None.
-                let (type_ascription, type_ascription_span) = match &ty_opt {
-                    Some(ty) => {
-                        let type_ascription_span = ty.span();
-                        let type_ascription = ty_to_type_info(ec, ty.clone())?;
-                        (type_ascription, Some(type_ascription_span))
-                    }
-                    None => (TypeInfo::Unknown, None),
-                };
+            ast_nodes
+        }
+    };
+    Ok(ast_nodes)
+}
 
-                // Save the tuple to the new name as a new variable declaration.
-                let save_body_first = VariableDeclaration {
-                    name: tuple_name.clone(),
-                    type_ascription,
-                    type_ascription_span,
-                    body: expression,
-                    is_mutable: false,
-                };
-                ast_nodes.push(AstNode {
-                    content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
-                        save_body_first,
-                    )),
-                    span: span.clone(),
-                });
+/// Creates the variable declarations for a pattern which can only be checked once the type of
+/// `expression` is known, such as an enum constructor pattern.
+///
+/// The pattern becomes the only arm of a match expression which evaluates to a tuple of the
+/// variables bound by the pattern, and each variable is then declared from that tuple. If the
+/// pattern is refutable it is reported when the match expression is type checked.
+fn refutable_pattern_to_ast_nodes(
+    ec: &mut ErrorContext,
+    pattern: Pattern,
+    ty_opt: Option<Ty>,
+    expression: Expression,
+    span: Span,
+) -> Result<Vec<AstNode>, ErrorEmitted> {
+    let mut ast_nodes = Vec::new();
+
+    // If there is a type ascription then save the value to a new variable declaration with that
+    // type, so that the match expression below is checked against it.
+    let value = match ty_opt {
+        Some(ty) => {
+            let value_name =
+                generate_unique_name(crate::constants::PATTERN_NAME_PREFIX, span.clone());
+            let type_ascription_span = ty.span();
+            let type_ascription = ty_to_type_info(ec, ty)?;
+            ast_nodes.push(AstNode {
+                content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
+                    VariableDeclaration {
+                        name: value_name.clone(),
+                        type_ascription,
+                        type_ascription_span: Some(type_ascription_span),
+                        body: expression,
+                        is_mutable: false,
+                    },
+                )),
+                span: span.clone(),
+            });
+            Expression::VariableExpression {
+                name: value_name,
+                span: span.clone(),
+            }
+        }
+        None => expression,
+    };
 
-                // create a variable expression that points to the new tuple name that we just created
-                let new_expr = Expression::VariableExpression {
-                    name: tuple_name,
+    // The mutability of each binding is lost when converting the pattern to a scrutinee, so
+    // collect the bindings first.
+    let mut bindings = Vec::new();
+    collect_pattern_bindings(&pattern, &mut bindings);
+
+    let scrutinee = pattern_to_scrutinee(ec, pattern)?;
+    let scrutinee_span = scrutinee.span();
+    let bindings_tuple_name =
+        generate_unique_name(crate::constants::PATTERN_NAME_PREFIX, span.clone());
+    ast_nodes.push(AstNode {
+        content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
+            VariableDeclaration {
+                name: bindings_tuple_name.clone(),
+                type_ascription: TypeInfo::Unknown,
+                type_ascription_span: None,
+                body: Expression::MatchExp {
+                    value: Box::new(value),
+                    branches: vec![MatchBranch {
+                        scrutinee,
+                        result: Expression::Tuple {
+                            fields: bindings
+                                .iter()
+                                .map(|(name, _is_mutable)| Expression::VariableExpression {
+                                    name: name.clone(),
+                                    span: name.span(),
+                                })
+                                .collect(),
+                            span: scrutinee_span.clone(),
+                        },
+                        span: scrutinee_span,
+                    }],
+                    source: MatchSource::Let,
                     span: span.clone(),
-                };
-
-                // from the possible type annotation, if the annotation was a tuple annotation,
-                // extract the internal types of the annotation
-                let tuple_tys_opt = match ty_opt {
-                    Some(Ty::Tuple(tys)) => Some(tys.into_inner().to_tys()),
-                    _ => None,
-                };
+                },
+                is_mutable: false,
+            },
+        )),
+        span: span.clone(),
+    });
 
-                // for all of the elements in the tuple destructuring on the LHS,
-                // recursively create variable declarations
-                for (index, pattern) in pat_tuple.into_inner().into_iter().enumerate() {
-                    // from the possible type annotation, grab the type at the index of the current element
-                    // we are processing
-                    let ty_opt = tuple_tys_opt
-                        .as_ref()
-                        .and_then(|tys| tys.get(index).cloned());
-
-                    // recursively create variable declarations for the subpatterns on the LHS
-                    // and add them to the ast nodes
-                    ast_nodes.extend(unfold(
-                        ec,
-                        pattern,
-                        ty_opt,
-                        Expression::TupleIndex {
-                            prefix: Box::new(new_expr.clone()),
-                            index,
-                            index_span: span.clone(),
+    // Declare each of the bound variables from the tuple.
+    for (index, (name, is_mutable)) in bindings.into_iter().enumerate() {
+        ast_nodes.push(AstNode {
+            content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
+                VariableDeclaration {
+                    name,
+                    type_ascription: TypeInfo::Unknown,
+                    type_ascription_span: None,
+                    body: Expression::TupleIndex {
+                        prefix: Box::new(Expression::VariableExpression {
+                            name: bindings_tuple_name.clone(),
                             span: span.clone(),
-                        },
-                        span.clone(),
-                    )?);
+                        }),
+                        index,
+                        index_span: span.clone(),
+                        span: span.clone(),
+                    },
+                    is_mutable,
+                },
+            )),
+            span: span.clone(),
+        });
+    }
+    Ok(ast_nodes)
+}
+
+/// Collects the name and mutability of every variable bound by `pattern`, in order.
+fn collect_pattern_bindings(pattern: &Pattern, bindings: &mut Vec<(Ident, bool)>) {
+    match pattern {
+        Pattern::Var { mutable, name } => bindings.push((name.clone(), mutable.is_some())),
+        Pattern::Wildcard { .. } | Pattern::Literal(..) | Pattern::Constant(..) => (),
+        Pattern::Constructor { args, .. } => {
+            for arg in args.get() {
+                collect_pattern_bindings(arg, bindings);
+            }
+        }
+        Pattern::Struct { fields, .. } => {
+            for field in fields.get() {
+                match field {
+                    PatternStructField::Field {
+                        pattern_opt: Some((_colon_token, pattern)),
+                        ..
+                    } => collect_pattern_bindings(pattern, bindings),
+                    PatternStructField::Field {
+                        field_name,
+                        pattern_opt: None,
+                    } => bindings.push((field_name.clone(), false)),
+                    PatternStructField::Rest { .. } => (),
                 }
-                ast_nodes
             }
-        };
-        Ok(ast_nodes)
+        }
+        Pattern::Tuple(pat_tuple) => {
+            for pattern in pat_tuple.get() {
+                collect_pattern_bindings(pattern, bindings);
+            }
+        }
     }
-    let span = statement_let.span();
-    let initial_expression = expr_to_expression(ec, statement_let.expr)?;
-    unfold(
-        ec,
-        statement_let.pattern,
-        statement_let.ty_opt.map(|(_colon_token, ty)| ty),
-        initial_expression,
-        span,
-    )
+}
+
+/// Generates a deterministic name, starting with `prefix`, for a value introduced when desugaring.
+/// Because the parser is single threaded, the name generated below will be stable.
+fn generate_unique_name(prefix: &str, span: Span) -> Ident {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let name = format!("{}{}", prefix, COUNTER.fetch_add(1, Ordering::SeqCst));
+    Ident::new_with_override(Box::leak(name.into_boxed_str()), span)
 }
 
 fn dependency_to_include_statement(dependency: &Dependency) -> IncludeStatement {
@@ -2962,6 +3473,7 @@ fn ty_to_type_parameter(ec: &mut ErrorContext, ty: Ty) -> Result<TypeParameter,
         Ty::Tuple(..) => panic!("tuple types are not allowed in this position"),
         Ty::Array(..) => panic!("array types are not allowed in this position"),
         Ty::Str { .. } => panic!("str types are not allowed in this position"),
+        Ty::Fn { .. } => panic!("function types are not allowed in this position"),
     };
     Ok(TypeParameter {
         type_id: insert_type(TypeInfo::Custom {
@@ -3162,6 +3674,18 @@ fn path_type_to_type_info(
         prefix,
         suffix,
     } = path_type;
+    // `Self::Item` refers to an associated type of the enclosing trait or impl, which is in scope
+    // under its own name there.
+    if root_opt.is_none() && prefix.name.as_str() == "Self" && prefix.generics_opt.is_none() {
+        if let [(_double_colon_token, segment)] = &suffix[..] {
+            if segment.fully_qualified.is_none() && segment.generics_opt.is_none() {
+                return Ok(TypeInfo::Custom {
+                    name: segment.name.clone(),
+                    type_arguments: None,
+                });
+            }
+        }
+    }
     if root_opt.is_some() || !suffix.is_empty() {
         let error = ConvertParseTreeError::FullySpecifiedTypesNotSupported { span };
         return Err(ec.error(error));
@@ -0,0 +1,140 @@
+//! The structured form of errors and warnings: a stable code, a primary label pointing at the
+//! offending code, secondary labels pointing at related code, and notes and help for the user.
+
+mod explanations;
+
+use sway_types::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A span of source code annotated with a message.
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// An edit of the source which addresses a diagnostic, for tools which apply fixes.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub message: String,
+    /// The code to replace.
+    pub span: Span,
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The stable code of the diagnostic, e.g. `E0001`, see [explain].
+    pub code: &'static str,
+    pub message: String,
+    /// The code the diagnostic is about. Unless a more specific label is given, it is labelled
+    /// with the message of the diagnostic.
+    pub primary: Label,
+    /// Related code, e.g. the first definition of a symbol which is defined twice.
+    pub secondary: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(severity: Severity, code: &'static str, message: String, span: Span) -> Self {
+        Diagnostic {
+            severity,
+            code,
+            primary: Label {
+                span,
+                message: message.clone(),
+            },
+            message,
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_label(mut self, message: impl Into<String>) -> Self {
+        self.primary.message = message.into();
+        self
+    }
+
+    pub(crate) fn with_secondary(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.secondary.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub(crate) fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub(crate) fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    pub(crate) fn with_suggestion(
+        mut self,
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.suggestions.push(Suggestion {
+            message: message.into(),
+            span,
+            replacement: replacement.into(),
+        });
+        self
+    }
+}
+
+/// Returns the long-form description of the error or warning with the given code, e.g. `E0001`.
+/// The code is matched case-insensitively.
+pub fn explain(code: &str) -> Option<&'static str> {
+    explanations::EXPLANATIONS
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code))
+        .map(|(_, explanation)| *explanation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{explain, explanations::EXPLANATIONS};
+    use crate::error::{ERROR_CODES, WARNING_CODES};
+
+    #[test]
+    fn explanations_are_sorted_and_unique() {
+        for pair in EXPLANATIONS.windows(2) {
+            let (a, b) = (pair[0].0, pair[1].0);
+            assert!(a < b, "{a} must come before {b}");
+        }
+        assert!(explain("e0001").is_some());
+        assert!(explain("E9999").is_none());
+    }
+
+    #[test]
+    fn every_code_is_explained() {
+        assert!(ERROR_CODES.iter().all(|code| code.starts_with('E')));
+        assert!(WARNING_CODES.iter().all(|code| code.starts_with('W')));
+        let codes = ERROR_CODES.iter().chain(WARNING_CODES).collect::<Vec<_>>();
+        for code in &codes {
+            assert!(explain(code).is_some(), "{code} has no explanation");
+        }
+        for (code, _) in EXPLANATIONS {
+            assert!(
+                codes.contains(&code),
+                "{code} is explained but never emitted"
+            );
+        }
+    }
+}
@@ -0,0 +1,1772 @@
+//! The long-form descriptions of every error and warning code, shown by `forc explain`.
+//!
+//! Entries are sorted by code. Codes are never removed or reused: when a diagnostic is removed
+//! from the compiler its explanation stays here, so that old codes can still be looked up.
+
+pub(super) const EXPLANATIONS: &[(&str, &str)] = &[
+    (
+        "E0001",
+        r#"A variable was used which is not declared in this scope.
+
+Variables must be declared with `let` before they are used, and are only visible
+in the block they are declared in and the blocks nested in it.
+
+    fn main() {
+        {
+            let x = 5;
+        }
+        let y = x; // `x` is not in scope here
+    }
+
+Check the spelling of the name, or move the declaration into an enclosing scope.
+"#,
+    ),
+    (
+        "E0002",
+        r#"A path refers to a variable which does not exist.
+
+The last segment of the path names a variable that could not be found in the
+module the path points to. Check the spelling of the path and that the module
+declares the variable.
+"#,
+    ),
+    (
+        "E0003",
+        r#"A function was called which is not declared in this scope.
+
+Functions declared in other modules must be imported with `use` before they can
+be called by their name alone, or called through their full path.
+
+    use std::hash::sha256;
+
+    fn main() {
+        let digest = sha256(42);
+    }
+"#,
+    ),
+    (
+        "E0004",
+        r#"A name was used as a variable, but it refers to something else.
+
+The name refers to a declaration which is not a variable, such as a function, a
+struct or an enum, which cannot be used as a value on its own. Check that the
+name is not shadowed by another declaration.
+"#,
+    ),
+    (
+        "E0005",
+        r#"A name was called as a function, but it refers to something else.
+
+Only functions and methods can be called. The name refers to another kind of
+declaration, such as a variable or a struct. To construct a struct, use the
+struct expression syntax instead:
+
+    let p = Point { x: 1, y: 2 };
+"#,
+    ),
+    (
+        "E0006",
+        r#"The program uses a language feature which the compiler does not implement yet.
+
+The message names the feature. Rewriting the code without the feature is the
+only workaround until support is added.
+"#,
+    ),
+    (
+        "E0007",
+        r#"A value of one type was used where another type was expected.
+
+Sway does not convert between types implicitly. The message shows the expected
+and the found type.
+
+    fn double(x: u64) -> u64 {
+        x * 2
+    }
+
+    fn main() {
+        let b: bool = double(2); // expected `bool`, found `u64`
+    }
+
+Change the value or the annotated type so that the two agree. This code is also
+used when the type of an expression cannot be determined, in which case adding a
+type annotation helps.
+"#,
+    ),
+    (
+        "E0008",
+        r#"The input could not be parsed.
+
+This error is emitted by older parts of the compiler which parse literals and
+other fragments of the source themselves. The message contains the underlying
+error.
+"#,
+    ),
+    (
+        "E0009",
+        r#"The compiler reached a state which it should never reach.
+
+This is a bug in the compiler, not in your program. Please file an issue on the
+Sway repository and include the code that triggered the error.
+"#,
+    ),
+    (
+        "E0010",
+        r#"The compiler reached a state which it should never reach.
+
+This is a bug in the compiler, not in your program. Please file an issue on the
+Sway repository and include the code that triggered the error.
+"#,
+    ),
+    (
+        "E0011",
+        r#"A byte literal has an invalid length.
+
+Byte literals must be either a single byte, written with 8 binary digits or 2 hex
+digits, or 32 bytes, written with 256 binary digits or 64 hex digits.
+
+    let byte = 0x2a;
+    let b256 = 0x0000000000000000000000000000000000000000000000000000000000000001;
+"#,
+    ),
+    (
+        "E0012",
+        r#"An operator is not followed by an expression.
+
+Binary operators such as `+` or `==` need an expression on both of their sides.
+"#,
+    ),
+    (
+        "E0013",
+        r#"An operator was expected, but the symbol found is not a recognized operator.
+
+Check for typos in the operator, e.g. `=<` instead of `<=`.
+"#,
+    ),
+    (
+        "E0014",
+        r#"The program declares more than one contract.
+
+A program is exactly one of a contract, a script, a predicate or a library. Move
+additional contracts into their own packages.
+"#,
+    ),
+    (
+        "E0015",
+        r#"The program declares more than one script.
+
+A program is exactly one of a contract, a script, a predicate or a library. Move
+additional scripts into their own packages.
+"#,
+    ),
+    (
+        "E0016",
+        r#"The program declares more than one predicate.
+
+A program is exactly one of a contract, a script, a predicate or a library. Move
+additional predicates into their own packages.
+"#,
+    ),
+    (
+        "E0017",
+        r#"A predicate has no `main` function.
+
+A predicate is evaluated by calling its `main` function, which must be declared
+and return a `bool`:
+
+    predicate;
+
+    fn main() -> bool {
+        true
+    }
+"#,
+    ),
+    (
+        "E0018",
+        r#"The `main` function of a predicate does not return a `bool`.
+
+A predicate either holds or it does not, so its `main` function must return
+`bool`:
+
+    predicate;
+
+    fn main() -> bool {
+        true
+    }
+"#,
+    ),
+    (
+        "E0019",
+        r#"A script has no `main` function.
+
+A script is run by calling its `main` function, which must be declared:
+
+    script;
+
+    fn main() {
+    }
+"#,
+    ),
+    (
+        "E0020",
+        r#"A function is defined more than once in the same scope.
+
+Every function in a module, and every method in an `impl` block, must have a
+unique name. Rename or remove one of the definitions.
+
+    fn foo() {}
+    fn foo() {} // `foo` is already defined
+"#,
+    ),
+    (
+        "E0021",
+        r#"A value was assigned to something which is not a variable.
+
+Only variables and their fields can be assigned to. The name on the left hand
+side of the assignment refers to another kind of declaration, such as a function
+or a constant.
+"#,
+    ),
+    (
+        "E0022",
+        r#"A value was assigned to a variable which is not mutable.
+
+Variables are immutable by default. To assign to a variable after it has been
+initialized, declare it with `let mut`:
+
+    let mut counter = 0;
+    counter = counter + 1;
+"#,
+    ),
+    (
+        "E0023",
+        r#"A method which takes `ref mut self` was called on an immutable variable.
+
+The method may modify the value it is called on, so the variable it is called on
+must be declared mutable:
+
+    let mut v = Vec::new();
+    v.push(42);
+"#,
+    ),
+    (
+        "E0024",
+        r#"An associated function was called as if it was a method.
+
+Functions in an `impl` block that do not take `self` as their first parameter
+are associated functions. They are called through the type, not through a value:
+
+    struct Point { x: u64, y: u64 }
+
+    impl Point {
+        fn origin() -> Self {
+            Point { x: 0, y: 0 }
+        }
+    }
+
+    let p = Point::origin();
+"#,
+    ),
+    (
+        "E0025",
+        r#"A generic type is used which is not declared as a type parameter.
+
+Type parameters must be declared in angle brackets after the name of the
+function before they can be used in its signature or body:
+
+    fn identity<T>(x: T) -> T {
+        x
+    }
+"#,
+    ),
+    (
+        "E0026",
+        r#"An assembly instruction is given more than one immediate value.
+
+Every opcode takes at most one immediate value, which is written after a `i` in
+the instruction, e.g. `addi r1 r2 i5`.
+"#,
+    ),
+    (
+        "E0027",
+        r#"The signature of a function in a trait implementation does not match the trait.
+
+The parameter and return types of every function in an `impl` block for a trait
+must be exactly the types declared in the trait. The message shows the expected
+and the found type.
+"#,
+    ),
+    (
+        "E0028",
+        r#"Something which is not a trait was implemented as a trait.
+
+The name after `impl` and before `for` must refer to a trait:
+
+    trait Foo {
+        fn foo(self) -> u64;
+    }
+
+    impl Foo for u64 {
+        fn foo(self) -> u64 {
+            self
+        }
+    }
+"#,
+    ),
+    (
+        "E0029",
+        r#"A trait was used which is not declared in this scope.
+
+Traits declared in other modules must be imported with `use` before they can be
+implemented or used as bounds.
+"#,
+    ),
+    (
+        "E0030",
+        r#"A trait implementation defines a function which is not part of the trait.
+
+An `impl` block for a trait can only define the functions which the trait
+declares. Move other functions into a separate `impl` block for the type:
+
+    impl MyType {
+        fn helper(self) {}
+    }
+"#,
+    ),
+    (
+        "E0031",
+        r#"A trait implementation is missing functions which the trait declares.
+
+Every function declared in the interface of a trait must be defined by every
+implementation of the trait. The message lists the missing functions.
+"#,
+    ),
+    (
+        "E0032",
+        r#"A trait implementation defines an associated item which is not part of the trait.
+
+An `impl` block for a trait can only define the associated items, such as
+constants, which the trait declares.
+"#,
+    ),
+    (
+        "E0033",
+        r#"An associated item is defined more than once in a trait implementation.
+
+Every associated item in an `impl` block must have a unique name. Remove one of
+the definitions.
+"#,
+    ),
+    (
+        "E0034",
+        r#"A trait implementation is missing associated items which the trait declares.
+
+Every associated item declared by a trait must be defined by every
+implementation of the trait. The message lists the missing items.
+"#,
+    ),
+    (
+        "E0035",
+        r#"A generic type was given the wrong number of type arguments.
+
+A type or function with type parameters must be given exactly one type argument
+per parameter:
+
+    struct Pair<A, B> { a: A, b: B }
+
+    let p: Pair<u64, bool> = Pair { a: 1, b: true };
+"#,
+    ),
+    (
+        "E0036",
+        r#"Type arguments were given to something which has no type parameters.
+
+Remove the type arguments in angle brackets.
+"#,
+    ),
+    (
+        "E0037",
+        r#"Type arguments were given to a type which cannot take them.
+
+Built-in types such as `u64` and `bool`, and types which have been fully
+resolved already, do not take type arguments.
+"#,
+    ),
+    (
+        "E0038",
+        r#"A generic type is used without type arguments, and they cannot be inferred.
+
+Give the type arguments explicitly in angle brackets, e.g. `Option<u64>`.
+"#,
+    ),
+    (
+        "E0039",
+        r#"A struct was used which is not declared in this scope.
+
+Structs declared in other modules must be imported with `use` before they can be
+used by their name alone.
+
+    use std::address::Address;
+"#,
+    ),
+    (
+        "E0040",
+        r#"An enum was used which is not declared in this scope.
+
+Enums declared in other modules must be imported with `use` before they can be
+used by their name alone.
+
+    use std::option::Option;
+"#,
+    ),
+    (
+        "E0041",
+        r#"A struct expression was used with a name which does not refer to a struct.
+
+The name before the braces of a struct expression must be the name of a struct.
+To construct an enum, use one of its variants instead, e.g. `Color::Red`.
+"#,
+    ),
+    (
+        "E0042",
+        r#"A field was accessed on a value which is not a struct.
+
+Only structs have named fields. Tuples have positional fields, which are accessed
+with a number, e.g. `t.0`.
+"#,
+    ),
+    (
+        "E0043",
+        r#"A method was called on something which has no methods.
+
+Methods are called on values, such as variables and the results of expressions.
+The name before the `.` refers to another kind of declaration.
+"#,
+    ),
+    (
+        "E0044",
+        r#"A struct expression does not initialize every field of the struct.
+
+Every field of a struct must be given a value when the struct is constructed:
+
+    struct Point { x: u64, y: u64 }
+
+    let p = Point { x: 1 }; // `y` is missing
+"#,
+    ),
+    (
+        "E0045",
+        r#"A struct expression initializes a field which the struct does not have.
+
+Check the spelling of the field and the declaration of the struct.
+"#,
+    ),
+    (
+        "E0046",
+        r#"A method was called which does not exist for the type of the value.
+
+Methods are found in the `impl` blocks of the type, and in the implementations of
+the traits which are in scope. If the method is defined by a trait, import the
+trait with `use`.
+"#,
+    ),
+    (
+        "E0047",
+        r#"A module could not be found.
+
+Modules of a library are declared with `dep` in the parent module, and must be
+found at the corresponding path relative to it. Dependencies of the package must
+be listed in `Forc.toml`.
+"#,
+    ),
+    (
+        "E0048",
+        r#"A field was accessed on a value which is not a struct.
+
+Only structs have named fields. The message shows what the value actually is.
+"#,
+    ),
+    (
+        "E0049",
+        r#"A tuple element was accessed on a value which is not a tuple.
+
+Positional fields such as `x.0` can only be accessed on tuples.
+"#,
+    ),
+    (
+        "E0050",
+        r#"An enum was expected, but the name refers to something else.
+
+The message shows what the name actually refers to.
+"#,
+    ),
+    (
+        "E0051",
+        r#"A struct was expected, but the name refers to something else.
+
+The message shows what the name actually refers to.
+"#,
+    ),
+    (
+        "E0052",
+        r#"A declaration was expected to be an enum, but it is something else.
+
+The message shows what the declaration actually is.
+"#,
+    ),
+    (
+        "E0053",
+        r#"A declaration was expected to be a struct, but it is something else.
+
+The message shows what the declaration actually is.
+"#,
+    ),
+    (
+        "E0054",
+        r#"A declaration was expected to be a function, but it is something else.
+
+The message shows what the declaration actually is.
+"#,
+    ),
+    (
+        "E0055",
+        r#"A declaration was expected to be a variable, but it is something else.
+
+The message shows what the declaration actually is.
+"#,
+    ),
+    (
+        "E0056",
+        r#"A declaration was expected to be an ABI, but it is something else.
+
+The message shows what the declaration actually is.
+"#,
+    ),
+    (
+        "E0057",
+        r#"A field was accessed which the struct does not have.
+
+The message lists the fields which the struct does have. Check the spelling of the
+field name.
+"#,
+    ),
+    (
+        "E0058",
+        r#"A name could not be found in this scope.
+
+The name is neither declared in this module nor imported into it. Check its
+spelling, or import it with `use`.
+"#,
+    ),
+    (
+        "E0059",
+        r#"A private item was imported from another module.
+
+Only items declared with `pub` can be used outside of the module which declares
+them:
+
+    library utils;
+
+    pub fn helper() {}
+"#,
+    ),
+    (
+        "E0060",
+        r#"An `if` expression whose value is used has no `else` branch.
+
+When the value of an `if` expression is used, it must have an `else` branch
+which produces a value of the same type, so that the expression has a value
+whichever branch is taken:
+
+    let max = if a > b { a } else { b };
+"#,
+    ),
+    (
+        "E0061",
+        r#"`Self` was used outside of a context where it refers to a type.
+
+`Self` refers to the implementing type inside of `impl` blocks and trait
+declarations only.
+"#,
+    ),
+    (
+        "E0062",
+        r#"A name was used as a type, but it refers to something else.
+
+The message shows what the name actually refers to. Types are structs, enums,
+built-in types and type parameters.
+"#,
+    ),
+    (
+        "E0063",
+        r#"An enum variant which holds a value was used without a value.
+
+Variants with a type other than `()` must be constructed with a value in
+parentheses:
+
+    enum Shape {
+        Circle: u64,
+        Nothing: (),
+    }
+
+    let s = Shape::Circle(5);
+"#,
+    ),
+    (
+        "E0064",
+        r#"A path through a function does not return a value.
+
+Every path through a function with a return type must end in an expression of
+that type, or in a `return` statement. The message points at the path which
+ends without a value.
+"#,
+    ),
+    (
+        "E0065",
+        r#"A block does not end in an expression of the expected type.
+
+The last expression of a block, without a trailing `;`, is the value of the
+block. Remove the `;` after the last expression, or add an expression of the
+expected type at the end of the block.
+"#,
+    ),
+    (
+        "E0066",
+        r#"A block does not end in an expression, but its value is used.
+
+The last expression of a block, without a trailing `;`, is the value of the
+block. Remove the `;` after the last expression.
+"#,
+    ),
+    (
+        "E0067",
+        r#"An `asm` block uses a register which it does not initialize.
+
+Registers used by the instructions of an `asm` block must be declared in its
+parentheses, optionally with an initial value, unless they are reserved
+registers such as `$zero`:
+
+    asm(r1: 5, r2) {
+        add r2 r1 r1;
+        r2: u64
+    }
+"#,
+    ),
+    (
+        "E0068",
+        r#"An assembly instruction which takes an immediate value has none.
+
+Give the instruction its immediate value, e.g. `addi r1 r2 i5`.
+"#,
+    ),
+    (
+        "E0069",
+        r#"An immediate value of an assembly instruction is invalid.
+
+Immediate values are written as `i` followed by a decimal number, e.g. `i42`.
+"#,
+    ),
+    (
+        "E0070",
+        r#"An `asm` block is expected to return a value but does not name a return register.
+
+The value of an `asm` block is the register written after its last instruction,
+together with its type:
+
+    asm(r1: 5) {
+        r1: u64
+    }
+"#,
+    ),
+    (
+        "E0071",
+        r#"A variant was used which the enum does not have.
+
+Check the spelling of the variant and the declaration of the enum.
+"#,
+    ),
+    (
+        "E0072",
+        r#"An `asm` block contains an instruction which is not a known opcode.
+
+Check the spelling of the opcode against the FuelVM instruction set.
+"#,
+    ),
+    (
+        "E0073",
+        r#"The type of a type parameter could not be inferred.
+
+The compiler could not work out which type to use for the type parameter from
+the way the value is used. Annotate the type explicitly:
+
+    let v: Vec<u64> = Vec::new();
+"#,
+    ),
+    (
+        "E0074",
+        r#"A type parameter is never used.
+
+Type parameters of an `impl` block must appear in the type which is implemented,
+otherwise they can never be determined.
+"#,
+    ),
+    (
+        "E0075",
+        r#"An immediate value does not fit in 6 bits.
+
+The instruction takes a 6-bit immediate value, the largest of which is 63. Load
+larger values into a register instead.
+"#,
+    ),
+    (
+        "E0076",
+        r#"An immediate value does not fit in 12 bits.
+
+The instruction takes a 12-bit immediate value, the largest of which is 4095.
+Load larger values into a register instead.
+"#,
+    ),
+    (
+        "E0077",
+        r#"An immediate value does not fit in 18 bits.
+
+The instruction takes an 18-bit immediate value, the largest of which is 262143.
+Load larger values into a register instead.
+"#,
+    ),
+    (
+        "E0078",
+        r#"An immediate value does not fit in 24 bits.
+
+The instruction takes a 24-bit immediate value, the largest of which is 16777215.
+Load larger values into a register instead.
+"#,
+    ),
+    (
+        "E0079",
+        r#"The `ji` opcode was used in an `asm` block.
+
+Jumps are generated by the compiler, and inline assembly cannot know the layout
+of the final program. Use a function call instead.
+"#,
+    ),
+    (
+        "E0080",
+        r#"The `jnei` opcode was used in an `asm` block.
+
+Jumps are generated by the compiler, and inline assembly cannot know the layout
+of the final program. Use an `if` expression around the `asm` block instead.
+"#,
+    ),
+    (
+        "E0081",
+        r#"The `jnzi` opcode was used in an `asm` block.
+
+Jumps are generated by the compiler, and inline assembly cannot know the layout
+of the final program. Use an `if` expression around the `asm` block instead.
+"#,
+    ),
+    (
+        "E0082",
+        r#"The `lw` opcode was used in an `asm` block.
+
+Loading words from the data section is handled by the compiler. Assign the value
+to a variable and pass the variable into the `asm` block instead.
+"#,
+    ),
+    (
+        "E0083",
+        r#"An assembly instruction has the wrong number of register arguments.
+
+The message shows how many registers the opcode expects.
+"#,
+    ),
+    (
+        "E0084",
+        r#"An assembly instruction which takes no immediate value was given one.
+
+Remove the immediate value from the instruction.
+"#,
+    ),
+    (
+        "E0085",
+        r#"A path could refer to more than one item.
+
+A module, an enum or a function have the same name, and the path could refer to
+any of them. Qualify the path further to make clear which one is meant.
+"#,
+    ),
+    (
+        "E0086",
+        r#"A `str` type is invalid.
+
+String types must give their length in bytes in square brackets, e.g. `str[5]`.
+"#,
+    ),
+    (
+        "E0087",
+        r#"A type could not be found.
+
+The type is neither declared in this module nor imported into it. Check its
+spelling, or import it with `use`.
+"#,
+    ),
+    (
+        "E0088",
+        r#"A type with the given name could not be found.
+
+The type is neither declared in this module nor imported into it. Check its
+spelling, or import it with `use`.
+"#,
+    ),
+    (
+        "E0089",
+        r#"The program is too large.
+
+The bytecode currently supports programs of up to 2^12 words of instructions.
+Split the program up, e.g. by moving code into other contracts and calling them.
+"#,
+    ),
+    (
+        "E0090",
+        r#"A source file could not be found.
+
+Modules declared with `dep` must be found at the corresponding path relative to
+the parent module, with a `.sw` extension.
+"#,
+    ),
+    (
+        "E0091",
+        r#"A source file could not be read.
+
+The message contains the error reported by the file system, e.g. missing
+permissions.
+"#,
+    ),
+    (
+        "E0092",
+        r#"A module imported with `dep` is not a library.
+
+Every module other than the root of a package must start with a library
+declaration giving its name:
+
+    library utils;
+"#,
+    ),
+    (
+        "E0093",
+        r#"An enum variant was constructed with more than one value.
+
+Every enum variant holds exactly one value. To store several values in a
+variant, give it a tuple or struct type:
+
+    enum Event {
+        Transfer: (Address, u64),
+    }
+
+    let e = Event::Transfer((to, amount));
+"#,
+    ),
+    (
+        "E0094",
+        r#"An enum variant of the unit type was constructed with a value.
+
+Variants of type `()` hold no value and are used without parentheses:
+
+    let o: Option<u64> = Option::None;
+"#,
+    ),
+    (
+        "E0095",
+        r#"A trait could not be found in this scope.
+
+The trait is neither declared in this module nor imported into it. Check its
+spelling, or import it with `use`.
+"#,
+    ),
+    (
+        "E0096",
+        r#"An expression was assigned to which cannot be assigned to.
+
+The left hand side of an assignment must be a mutable variable, one of its
+fields, or a storage field.
+"#,
+    ),
+    (
+        "E0097",
+        r#"A function was called with too many arguments.
+
+The number of arguments in a call must equal the number of parameters of the
+function. The message shows how many the function expects.
+"#,
+    ),
+    (
+        "E0098",
+        r#"A function was called with too few arguments.
+
+The number of arguments in a call must equal the number of parameters of the
+function. The message shows how many the function expects.
+"#,
+    ),
+    (
+        "E0099",
+        r#"A type is used in an ABI method which cannot be encoded.
+
+The parameter and return types of ABI methods must have a known size, so that
+their function selectors are well defined. Generic types are not allowed.
+"#,
+    ),
+    (
+        "E0100",
+        r#"An ABI cast names something which is not an ABI.
+
+The first argument of `abi(..)` must be the name of an ABI declaration, the second
+the address of the contract:
+
+    let caller = abi(MyContract, contract_id);
+"#,
+    ),
+    (
+        "E0101",
+        r#"An ABI was implemented for a type other than `Contract`.
+
+ABIs describe the interface of a contract, so they can only be implemented for
+`Contract`:
+
+    impl MyAbi for Contract {
+        ...
+    }
+"#,
+    ),
+    (
+        "E0102",
+        r#"A trait function is implemented with the wrong number of parameters.
+
+Every function in an `impl` block for a trait must take exactly the parameters
+which the trait declares.
+"#,
+    ),
+    (
+        "E0103",
+        r#"A function was called with an argument of the wrong type.
+
+The type of every argument must equal the declared type of the corresponding
+parameter. The message shows both types.
+"#,
+    ),
+    (
+        "E0104",
+        r#"A function calls itself.
+
+Recursion is not supported at this time, as the compiler needs to know the stack
+usage of every function statically. Rewrite the function with a `while` loop.
+"#,
+    ),
+    (
+        "E0105",
+        r#"A pointer to a generic function was created.
+
+Function pointers may only refer to functions without type parameters, since a
+generic function is compiled once per type it is used with.
+"#,
+    ),
+    (
+        "E0106",
+        r#"A pointer to a function which accesses storage was created.
+
+Function pointers may only refer to pure functions, so that the storage access
+of a call through the pointer is known.
+"#,
+    ),
+    (
+        "E0107",
+        r#"Functions call each other in a cycle.
+
+Recursion, including recursion through other functions, is not supported at
+this time. The message lists the functions in the cycle.
+"#,
+    ),
+    (
+        "E0108",
+        r#"A type contains itself.
+
+Recursive types are not supported at this time, as their size cannot be known.
+"#,
+    ),
+    (
+        "E0109",
+        r#"Types contain each other in a cycle.
+
+Recursive types, including recursion through other types, are not supported at
+this time, as their size cannot be known. The message lists the types in the
+cycle.
+"#,
+    ),
+    (
+        "E0110",
+        r#"The size of a type is not known.
+
+Values must have a size which is known at compile time.
+"#,
+    ),
+    (
+        "E0111",
+        r#"Modules depend on each other in a cycle.
+
+The modules declared with `dep` must form a tree. Move the items which both
+modules need into a third module.
+"#,
+    ),
+    (
+        "E0112",
+        r#"The `gm` opcode is used in code which can be called from another contract.
+
+Getting the metadata of the call frame from an external context makes the VM
+panic.
+"#,
+    ),
+    (
+        "E0113",
+        r#"The `mint` opcode is used outside of a contract.
+
+Only contracts can mint tokens.
+"#,
+    ),
+    (
+        "E0114",
+        r#"The `burn` opcode is used outside of a contract.
+
+Only contracts can burn tokens.
+"#,
+    ),
+    (
+        "E0115",
+        r#"Contract storage is used outside of a contract.
+
+Only contracts have storage. Scripts and predicates must call a contract to read
+or write its storage.
+"#,
+    ),
+    (
+        "E0116",
+        r#"An array is indexed with a constant index which is out of bounds.
+
+Array indices start at zero, so the largest valid index is the length of the
+array minus one.
+"#,
+    ),
+    (
+        "E0117",
+        r#"A tuple element was accessed which the tuple does not have.
+
+Tuple elements are numbered from zero, so the largest valid index is the number
+of elements minus one.
+"#,
+    ),
+    (
+        "E0118",
+        r#"A name is declared or imported twice in the same scope.
+
+Structs, enums and imported items cannot be shadowed. The first definition is
+shown alongside the error. Rename one of them, or import the item under another
+name with `as`:
+
+    use lib_a::Token;
+    use lib_b::Token as OtherToken;
+"#,
+    ),
+    (
+        "E0119",
+        r#"A type parameter has the same name as another type parameter in scope.
+
+The type parameters of a function must be distinct from each other and from the
+type parameters of the `impl` block it is in.
+
+    impl<T> Wrapper<T> {
+        fn convert<T>(self) {} // `T` is already declared by the `impl`
+    }
+"#,
+    ),
+    (
+        "E0120",
+        r#"A glob import brings in a name which is already imported.
+
+Two `use` statements ending in `*` import items with the same name. Import one of
+them explicitly, under another name with `as`.
+"#,
+    ),
+    (
+        "E0121",
+        r#"The arms of a `match` expression have different types.
+
+Every arm of a `match` expression must produce a value of the same type.
+"#,
+    ),
+    (
+        "E0122",
+        r#"A `match` expression does not handle every possible value.
+
+The arms of a `match` expression must cover every value of the matched
+expression. The message lists the patterns which are not covered. Add arms for
+them, or a catch-all arm:
+
+    match x {
+        0 => "zero",
+        _ => "other",
+    }
+"#,
+    ),
+    (
+        "E0123",
+        r#"A `let` binding uses a pattern which does not match every value.
+
+Patterns in `let` bindings must always match. Use a `match` or `if let`
+expression to handle the values which the pattern does not match.
+"#,
+    ),
+    (
+        "E0124",
+        r#"A struct pattern does not mention every field of the struct.
+
+List every field in the pattern, or end the pattern with `..` to ignore the
+others:
+
+    match p {
+        Point { x: 0, .. } => true,
+        _ => false,
+    }
+"#,
+    ),
+    (
+        "E0125",
+        r#"A function calls a function which accesses more storage than it declares.
+
+Functions must declare the storage they access, including through the functions
+they call, with the `#[storage(..)]` attribute:
+
+    #[storage(read, write)]
+    fn increment() {
+        store(KEY, get::<u64>(KEY) + 1);
+    }
+"#,
+    ),
+    (
+        "E0126",
+        r#"A trait implementation accesses storage, but the trait declares the function pure.
+
+The `#[storage(..)]` attributes of a function in an `impl` block must match those
+of the trait declaration. Remove the attribute from the implementation, or add
+it to the trait.
+"#,
+    ),
+    (
+        "E0127",
+        r#"The storage access of a trait implementation does not match the trait.
+
+The `#[storage(..)]` attributes of a function in an `impl` block must match those
+of the trait declaration.
+"#,
+    ),
+    (
+        "E0128",
+        r#"A function outside of a contract accesses storage.
+
+Only contracts have storage, so only functions in contracts can declare the
+`#[storage(..)]` attribute.
+"#,
+    ),
+    (
+        "E0129",
+        r#"A function accesses storage without declaring it.
+
+Functions must declare the storage they access with the `#[storage(..)]`
+attribute, using `read`, `write` or both:
+
+    #[storage(read)]
+    fn get_count() -> u64 {
+        storage.count
+    }
+"#,
+    ),
+    (
+        "E0130",
+        r#"An integer literal is too large for its type.
+
+The message names the type. Use a wider integer type, or a smaller literal.
+"#,
+    ),
+    (
+        "E0131",
+        r#"An integer literal is too small for its type.
+
+The message names the type. Unsigned integer types cannot hold negative values.
+"#,
+    ),
+    (
+        "E0132",
+        r#"An integer literal contains digits which are not valid for its type.
+
+Check the literal for typos, and that its prefix (`0x`, `0b`) matches its digits.
+"#,
+    ),
+    (
+        "E0133",
+        r#"A glob import was given an alias.
+
+`use` statements ending in `*` import many items at once and cannot be renamed
+with `as`. Import the items individually to rename them.
+"#,
+    ),
+    (
+        "E0134",
+        r#"An ABI was used as the supertrait of a trait.
+
+Only traits can be supertraits.
+"#,
+    ),
+    (
+        "E0135",
+        r#"A trait is implemented for a type which does not implement its supertrait.
+
+A trait with supertraits can only be implemented for types which implement each
+of the supertraits:
+
+    trait Eq {
+        fn eq(self, other: Self) -> bool;
+    }
+
+    trait Ord: Eq {
+        fn lt(self, other: Self) -> bool;
+    }
+
+Implementing `Ord` for a type requires implementing `Eq` for it as well.
+"#,
+    ),
+    (
+        "E0136",
+        r#"The implementation of a supertrait is required by this trait.
+
+This accompanies E0135 and points at the supertrait bound which requires the
+missing implementation.
+"#,
+    ),
+    (
+        "E0137",
+        r#"`if let` was used on a value which is not an enum.
+
+`if let` matches a value against an enum variant. Use a plain `if` expression to
+compare other values.
+"#,
+    ),
+    (
+        "E0138",
+        r#"A contract call parameter is given more than once.
+
+Each of the parameters in curly braces before the arguments of a contract call,
+such as `gas` or `coins`, can only be given once.
+"#,
+    ),
+    (
+        "E0139",
+        r#"A contract call parameter is not recognized.
+
+The parameters in curly braces before the arguments of a contract call can only
+be `gas`, `coins` and `asset_id`:
+
+    caller.deposit { gas: 10000, coins: 42, asset_id: BASE_ASSET_ID }();
+"#,
+    ),
+    (
+        "E0140",
+        r#"Contract call parameters were given to a call which is not a contract call.
+
+The parameters in curly braces before the arguments of a call, such as `gas` or
+`coins`, are only allowed when calling a method of a contract through an ABI
+cast.
+"#,
+    ),
+    (
+        "E0141",
+        r#"A storage field was used which is not declared.
+
+Check the spelling of the field and the `storage` declaration of the contract.
+"#,
+    ),
+    (
+        "E0142",
+        r#"Storage was used, but the contract does not declare any.
+
+Declare the storage fields of a contract in a `storage` block:
+
+    storage {
+        count: u64 = 0,
+    }
+"#,
+    ),
+    (
+        "E0143",
+        r#"A contract declares storage more than once.
+
+A contract has a single `storage` declaration. Merge the fields into one block.
+"#,
+    ),
+    (
+        "E0144",
+        r#"A storage-only type was used outside of a storage declaration.
+
+Types such as `StorageMap` can only be the type of a field declared directly in
+the `storage` block of a contract.
+"#,
+    ),
+    (
+        "E0145",
+        r#"A keyword was used as a variable name.
+
+Keywords cannot be used as names. Pick another name, or prefix the keyword with
+`r#` to use it as a raw identifier.
+"#,
+    ),
+    (
+        "E0146",
+        r#"The compiler found a declaration where it should never find one.
+
+This is a bug in the compiler, not in your program. Please file an issue on the
+Sway repository and include the code that triggered the error.
+"#,
+    ),
+    (
+        "E0147",
+        r#"A contract caller has no known address.
+
+ABI casts must be given the address of the contract they call:
+
+    let caller = abi(MyContract, 0x79fa8779bed2f36c3581d01c79df8da45eee09fac1fd76a5a656e16326317ef0);
+"#,
+    ),
+    (
+        "E0148",
+        r#"The program uses syntax which is parsed but not allowed in this position.
+
+The message describes the problem. This code covers constructs which are
+syntactically valid but rejected before type checking, such as out of range
+literals, duplicate struct fields or enum variants, and expressions which are
+not implemented yet.
+"#,
+    ),
+    (
+        "E0149",
+        r#"The source could not be split into tokens.
+
+This happens for unterminated comments and string literals, unbalanced
+delimiters, and characters which are not valid in Sway source.
+"#,
+    ),
+    (
+        "E0150",
+        r#"The source is not valid Sway syntax.
+
+The message describes what the parser expected. The parser recovers at the next
+item or statement, so further syntax errors are reported as well.
+"#,
+    ),
+    (
+        "E0151",
+        r#"A `where` clause was used.
+
+`where` clauses are not supported yet. Declare the trait bounds of type
+parameters inline instead:
+
+    fn sum<T: Add>(a: T, b: T) -> T {
+        a + b
+    }
+"#,
+    ),
+    (
+        "E0152",
+        r#"The value of a constant could not be evaluated at compile time.
+
+Constants must be initialized with expressions which the compiler can evaluate,
+such as literals and arithmetic on other constants.
+"#,
+    ),
+    (
+        "E0153",
+        r#"Storage was declared outside of a contract.
+
+Only contracts have storage. Move the `storage` declaration into a contract.
+"#,
+    ),
+    (
+        "E0154",
+        r#"An intrinsic function was called with an argument of an unsupported type.
+
+The message contains a hint about which types the intrinsic supports.
+"#,
+    ),
+    (
+        "E0155",
+        r#"An intrinsic function was called with the wrong number of arguments.
+
+The message shows how many arguments the intrinsic expects.
+"#,
+    ),
+    (
+        "E0156",
+        r#"An intrinsic function was called with the wrong number of type arguments.
+
+The message shows how many type arguments the intrinsic expects, e.g.
+`__size_of::<T>()` takes one.
+"#,
+    ),
+    (
+        "E0157",
+        r#"`break` was used outside of a loop.
+
+`break` exits the innermost `while` loop, so it can only be used inside of one.
+"#,
+    ),
+    (
+        "E0158",
+        r#"`continue` was used outside of a loop.
+
+`continue` starts the next iteration of the innermost `while` loop, so it can
+only be used inside of one.
+"#,
+    ),
+    (
+        "E0159",
+        r#"A warning was reported as an error, because its lint is denied.
+
+Every warning belongs to a lint, e.g. `dead_code`. A lint is denied by the
+`#[deny(..)]` attribute of the item or module which contains the warning, by the
+`[lints]` table of the package manifest, or for every lint at once by
+`forc build --deny-warnings`:
+
+```sway
+#[deny(dead_code)]
+library utils;
+```
+
+Fix the code which the warning is about, or allow the lint for the item with
+`#[allow(..)]` if the warning is expected.
+"#,
+    ),
+    (
+        "E0160",
+        r#"A method call matches the methods of several trait implementations.
+
+A type may implement a generic trait several times with different type
+arguments. The arguments of a call and the type it is expected to return decide
+which of the implementations is meant:
+
+```sway
+impl Into<u64> for Celsius { .. }
+impl Into<b256> for Celsius { .. }
+
+let a = c.into(); // error: both `Into<u64>` and `Into<b256>` apply
+let b: u64 = c.into(); // ok
+```
+
+Annotate the type of the value which the call produces.
+"#,
+    ),
+    (
+        "E0161",
+        r#"A trait is implemented for the same type more than once.
+
+Each combination of a trait, its type arguments and the implementing type may
+only be implemented once, as otherwise calls to its methods would be ambiguous:
+
+```sway
+impl From<u64> for Bar { .. }
+impl From<u64> for Bar { .. } // error
+impl From<b256> for Bar { .. } // ok, the type argument differs
+```
+
+Remove one of the implementations.
+"#,
+    ),
+    (
+        "E0162",
+        r#"A method call matches none of the methods of several trait implementations.
+
+When a type implements a generic trait several times with different type
+arguments, the arguments of a call and the type it is expected to return decide
+which of the implementations is meant. Here, none of them fit:
+
+```sway
+impl From<u64> for Bar { .. }
+impl From<b256> for Bar { .. }
+
+let b = Bar::from(true); // error: neither `From<u64>` nor `From<b256>` take a `bool`
+```
+
+Check the types of the arguments and of the value the call produces.
+"#,
+    ),
+    (
+        "W0001",
+        r#"A struct name is not in ClassCase.
+
+By convention, the names of structs are written in ClassCase, e.g. `TokenBalance`
+rather than `token_balance`.
+"#,
+    ),
+    (
+        "W0002",
+        r#"A type parameter name is not in ClassCase.
+
+By convention, the names of type parameters are written in ClassCase, and are
+usually short, e.g. `T` or `Item`.
+"#,
+    ),
+    (
+        "W0003",
+        r#"A trait name is not in ClassCase.
+
+By convention, the names of traits are written in ClassCase, e.g. `Ownable`.
+"#,
+    ),
+    (
+        "W0004",
+        r#"An enum name is not in ClassCase.
+
+By convention, the names of enums are written in ClassCase, e.g. `Identity`.
+"#,
+    ),
+    (
+        "W0005",
+        r#"An enum variant name is not in ClassCase.
+
+By convention, the names of enum variants are written in ClassCase, e.g.
+`ContractId`.
+"#,
+    ),
+    (
+        "W0006",
+        r#"A struct field name is not in snake_case.
+
+By convention, the names of struct fields are written in snake_case, e.g.
+`total_supply`.
+"#,
+    ),
+    (
+        "W0007",
+        r#"A function name is not in snake_case.
+
+By convention, the names of functions and methods are written in snake_case, e.g.
+`transfer_to_output`.
+"#,
+    ),
+    (
+        "W0008",
+        r#"A constant name is not in SCREAMING_SNAKE_CASE.
+
+By convention, the names of constants are written in SCREAMING_SNAKE_CASE, e.g.
+`MAX_SUPPLY`.
+"#,
+    ),
+    (
+        "W0009",
+        r#"An integer cast loses precision.
+
+The value is cast to an integer type which is narrower than its own type, so its
+upper bits are discarded if it is too large for the narrower type.
+"#,
+    ),
+    (
+        "W0010",
+        r#"The value of an expression is ignored.
+
+The expression produces a value which is neither assigned to anything nor
+returned. If this is deliberate, make it explicit by binding the value to `_`:
+
+    let _ = compute();
+"#,
+    ),
+    (
+        "W0011",
+        r#"A method with the called name exists, but its trait is not in scope.
+
+Methods defined by traits can only be called when the trait is imported. Import
+the trait named in the message with `use`.
+"#,
+    ),
+    (
+        "W0012",
+        r#"A declaration shadows another one with the same name.
+
+The earlier declaration, shown alongside the warning, can no longer be referred
+to by its name after this point. Rename one of them if both are needed.
+"#,
+    ),
+    (
+        "W0013",
+        r#"A trait implementation overrides an earlier implementation.
+
+The same trait is implemented twice for the same type, and the later
+implementation replaces the earlier one. Remove one of them.
+"#,
+    ),
+    (
+        "W0014",
+        r#"A declaration is never used.
+
+Remove the declaration if it is no longer needed.
+"#,
+    ),
+    (
+        "W0015",
+        r#"A function is never called.
+
+Remove the function if it is no longer needed.
+"#,
+    ),
+    (
+        "W0016",
+        r#"A struct is never instantiated.
+
+Remove the struct if it is no longer needed.
+"#,
+    ),
+    (
+        "W0017",
+        r#"A trait is never implemented.
+
+Remove the trait if it is no longer needed.
+"#,
+    ),
+    (
+        "W0018",
+        r#"Code is unreachable.
+
+The code follows an expression which never completes, such as `return` or
+`break`, so it is never executed.
+"#,
+    ),
+    (
+        "W0019",
+        r#"An enum variant is never constructed.
+
+Remove the variant if it is no longer needed.
+"#,
+    ),
+    (
+        "W0020",
+        r#"A method is never called.
+
+Remove the method if it is no longer needed.
+"#,
+    ),
+    (
+        "W0021",
+        r#"A struct field is never read.
+
+The field is written when the struct is constructed, but its value is never
+used.
+"#,
+    ),
+    (
+        "W0022",
+        r#"A register declared in an `asm` block has the name of a reserved register.
+
+Reserved registers such as `zero`, `one` or `sp` are written with a `$`, e.g.
+`$zero`. Declaring a register with the same name hides the reserved register.
+"#,
+    ),
+    (
+        "W0023",
+        r#"A storage field is never accessed.
+
+The field takes up space in the storage of the contract but is never read or
+written. Remove it.
+"#,
+    ),
+    (
+        "W0024",
+        r#"A function declares storage access which it does not need.
+
+The function is marked with `#[storage(read)]` or `#[storage(write)]`, but never
+reads or writes storage. Remove the unneeded access from the attribute.
+"#,
+    ),
+    (
+        "W0025",
+        r#"A `match` arm is unreachable.
+
+The values matched by the arm are all matched by earlier arms, so it is never
+taken. Remove the arm, or move it before the arms which cover it.
+"#,
+    ),
+    (
+        "W0026",
+        r#"A lint attribute names a lint which does not exist.
+
+The `#[allow(..)]`, `#[warn(..)]` and `#[deny(..)]` attributes take the names of
+lints, e.g. `dead_code`, or `warnings` for every lint at once. Check the name for
+typos.
+"#,
+    ),
+    (
+        "W0027",
+        r#"Storage is written after a call to another contract.
+
+The called contract can call back into this one before the call returns, and see
+storage which has not been updated yet. This is how reentrancy attacks work:
+
+    fn withdraw(amount: u64) {
+        let vault = abi(Vault, VAULT_ID);
+        vault.send(amount);
+        storage.balance = storage.balance - amount;
+    }
+
+Update storage before calling other contracts (checks, effects, interactions),
+or call `std::reentrancy::reentrancy_guard()` at the start of the method.
+"#,
+    ),
+    (
+        "W0028",
+        r#"An ABI method writes to storage without checking who called it.
+
+Anyone can call the method and change the storage of the contract. Check the
+caller with `std::chain::auth::msg_sender()`:
+
+    let sender = msg_sender().unwrap();
+    require(sender == storage.owner, AccessError::NotOwner);
+
+If anyone may call the method, allow the lint with
+`#[allow(unchecked_msg_sender)]`.
+"#,
+    ),
+    (
+        "W0029",
+        r#"The `Result` of a function which transfers tokens is ignored.
+
+The transfer may have failed, but the code carries on as if it had succeeded.
+Handle the `Result`, e.g. with `match`, or with `unwrap()` to revert on failure.
+
+A function transfers tokens if it calls one of the transfer functions of
+`std::token`, directly or through other functions, however they are imported.
+"#,
+    ),
+    (
+        "W0030",
+        r#"A symbol is imported by name but never used.
+
+Remove the import, or the whole `use` statement if it imports nothing else.
+Imports under a name starting with an underscore, e.g. `use foo::Bar as _Bar;`,
+are never reported.
+"#,
+    ),
+    (
+        "W0031",
+        r#"A local variable or function parameter is never used.
+
+Remove the variable, or start its name with an underscore if it is deliberately
+unused:
+
+    fn transfer(amount: u64, _memo: b256) { .. }
+
+Assigning to a variable does not count as using it.
+"#,
+    ),
+];
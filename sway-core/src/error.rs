@@ -3,6 +3,7 @@
 use crate::{
     constants::STORAGE_PURITY_ATTRIBUTE_NAME,
     convert_parse_tree::ConvertParseTreeError,
+    diagnostic::{Diagnostic, Severity},
     style::{to_screaming_snake_case, to_snake_case, to_upper_camel_case},
     type_engine::*,
     CallPath, VariableDeclaration,
@@ -41,6 +42,28 @@ macro_rules! assert_or_warn {
     }};
 }
 
+// Implements `code` for the error or warning type `$ty` from the table of its codes, which is
+// also collected into the slice `$codes` so that every code can be checked for an explanation.
+macro_rules! diagnostic_codes {
+    (
+        $(#[$attr: meta])*
+        impl $ty: ident as $codes: ident { $($pattern: pat => $code: literal),* $(,)? }
+    ) => {
+        impl $ty {
+            $(#[$attr])*
+            pub fn code(&self) -> &'static str {
+                use $ty::*;
+                match self {
+                    $($pattern => $code,)*
+                }
+            }
+        }
+
+        #[doc = concat!("Every code returned by [`", stringify!($ty), "::code`], in order.")]
+        pub const $codes: &[&str] = &[$($code),*];
+    };
+}
+
 /// Denotes a non-recoverable state
 pub(crate) fn err<T>(warnings: Vec<CompileWarning>, errors: Vec<CompileError>) -> CompileResult<T> {
     CompileResult {
@@ -230,6 +253,85 @@ impl CompileWarning {
         self.warning_content.to_string()
     }
 
+    /// The structured form of this warning, with its code, labels, notes and help.
+    pub fn diagnostic(&self) -> Diagnostic {
+        use Warning::*;
+        let diagnostic = Diagnostic::new(
+            Severity::Warning,
+            self.warning_content.code(),
+            self.to_friendly_warning_string(),
+            self.span.clone(),
+        );
+        match &self.warning_content {
+            NonClassCaseStructName { struct_name: name }
+            | NonClassCaseTypeParameter { name }
+            | NonClassCaseTraitName { name }
+            | NonClassCaseEnumName { enum_name: name }
+            | NonClassCaseEnumVariantName { variant_name: name } => {
+                let idiomatic = to_upper_camel_case(name.as_str());
+                diagnostic.with_suggestion(
+                    format!("rename to `{idiomatic}`"),
+                    name.span(),
+                    idiomatic,
+                )
+            }
+            NonSnakeCaseStructFieldName { field_name: name }
+            | NonSnakeCaseFunctionName { name } => {
+                let idiomatic = to_snake_case(name.as_str());
+                diagnostic.with_suggestion(
+                    format!("rename to `{idiomatic}`"),
+                    name.span(),
+                    idiomatic,
+                )
+            }
+            NonScreamingSnakeCaseConstName { name } => {
+                let idiomatic = to_screaming_snake_case(name.as_str());
+                diagnostic.with_suggestion(
+                    format!("rename to `{idiomatic}`"),
+                    name.span(),
+                    idiomatic,
+                )
+            }
+            ShadowsOtherSymbol { previous, .. } => {
+                diagnostic.with_secondary(previous.span(), "previously declared here")
+            }
+            UnusedReturnValue { .. } => {
+                diagnostic.with_help("if this is deliberate, bind the value to `_`: `let _ = ..`")
+            }
+            SimilarMethodFound { .. } => {
+                diagnostic.with_help("import the trait which defines the method with `use`")
+            }
+            UnreachableCode => diagnostic.with_label("unreachable"),
+            StorageWriteAfterContractCall { call } => diagnostic
+                .with_secondary(call.clone(), "another contract is called here")
+                .with_help(
+                    "write to storage before calling other contracts, or call \
+                     `std::reentrancy::reentrancy_guard()` first",
+                ),
+            UncheckedMsgSender { .. } => diagnostic.with_help(
+                "check the caller with `std::chain::auth::msg_sender()`, or allow this lint if \
+                 anyone may call the method",
+            ),
+            UncheckedTransferResult { .. } => {
+                diagnostic.with_help("handle the `Result`, e.g. with `match` or `unwrap()`")
+            }
+            UnusedImport {
+                use_statement: Some(use_statement),
+                ..
+            } => {
+                diagnostic.with_suggestion("remove the `use` statement", use_statement.clone(), "")
+            }
+            UnusedVariable { name } => diagnostic
+                .with_help("if this is deliberate, start the name with an underscore")
+                .with_suggestion(
+                    format!("rename to `_{name}`"),
+                    name.span(),
+                    format!("_{name}"),
+                ),
+            _ => diagnostic,
+        }
+    }
+
     pub fn path(&self) -> Option<Arc<PathBuf>> {
         self.span.path().cloned()
     }
@@ -298,6 +400,8 @@ pub enum Warning {
     },
     ShadowsOtherSymbol {
         name: Ident,
+        /// The name of the symbol which is shadowed.
+        previous: Ident,
     },
     OverridingTraitImplementation,
     DeadDeclaration,
@@ -318,6 +422,27 @@ pub enum Warning {
         unneeded_attrib: String,
     },
     MatchExpressionUnreachableArm,
+    UnknownLint {
+        name: Ident,
+    },
+    StorageWriteAfterContractCall {
+        /// The call of another contract which precedes the write.
+        call: Span,
+    },
+    UncheckedMsgSender {
+        method_name: Ident,
+    },
+    UncheckedTransferResult {
+        name: Ident,
+    },
+    UnusedImport {
+        name: Ident,
+        /// The `use` statement of the import, if it imports nothing else.
+        use_statement: Option<Span>,
+    },
+    UnusedVariable {
+        name: Ident,
+    },
 }
 
 impl fmt::Display for Warning {
@@ -407,7 +532,7 @@ impl fmt::Display for Warning {
                  Traits must be in scope in order to access their methods. ",
                 name, lib, module
             ),
-            ShadowsOtherSymbol { name } => write!(
+            ShadowsOtherSymbol { name, .. } => write!(
                 f,
                 "This shadows another symbol in this scope with the same name \"{}\".",
                 name
@@ -441,6 +566,101 @@ impl fmt::Display for Warning {
                 and can be removed."
             ),
             MatchExpressionUnreachableArm => write!(f, "This match arm is unreachable."),
+            UnknownLint { name } => write!(f, "Unknown lint \"{name}\"."),
+            StorageWriteAfterContractCall { .. } => write!(
+                f,
+                "This storage write follows a call to another contract, which could call back \
+                 into this contract before the write happens."
+            ),
+            UncheckedMsgSender { method_name } => write!(
+                f,
+                "ABI method \"{method_name}\" writes to storage, but never checks who called it."
+            ),
+            UncheckedTransferResult { name } => write!(
+                f,
+                "The `Result` returned by \"{name}\" is ignored, so a failed transfer goes \
+                 unnoticed."
+            ),
+            UnusedImport { name, .. } => write!(f, "Import \"{name}\" is never used."),
+            UnusedVariable { name } => write!(f, "Variable \"{name}\" is never used."),
+        }
+    }
+}
+
+diagnostic_codes! {
+    /// The stable code of this kind of warning, e.g. `W0001`. Codes are never reused, so they
+    /// can be looked up with `forc explain`.
+    impl Warning as WARNING_CODES {
+        NonClassCaseStructName { .. } => "W0001",
+        NonClassCaseTypeParameter { .. } => "W0002",
+        NonClassCaseTraitName { .. } => "W0003",
+        NonClassCaseEnumName { .. } => "W0004",
+        NonClassCaseEnumVariantName { .. } => "W0005",
+        NonSnakeCaseStructFieldName { .. } => "W0006",
+        NonSnakeCaseFunctionName { .. } => "W0007",
+        NonScreamingSnakeCaseConstName { .. } => "W0008",
+        LossOfPrecision { .. } => "W0009",
+        UnusedReturnValue { .. } => "W0010",
+        SimilarMethodFound { .. } => "W0011",
+        ShadowsOtherSymbol { .. } => "W0012",
+        OverridingTraitImplementation => "W0013",
+        DeadDeclaration => "W0014",
+        DeadFunctionDeclaration => "W0015",
+        DeadStructDeclaration => "W0016",
+        DeadTrait => "W0017",
+        UnreachableCode => "W0018",
+        DeadEnumVariant { .. } => "W0019",
+        DeadMethod => "W0020",
+        StructFieldNeverRead => "W0021",
+        ShadowingReservedRegister { .. } => "W0022",
+        DeadStorageDeclaration => "W0023",
+        DeadStorageDeclarationForFunction { .. } => "W0024",
+        MatchExpressionUnreachableArm => "W0025",
+        UnknownLint { .. } => "W0026",
+        StorageWriteAfterContractCall { .. } => "W0027",
+        UncheckedMsgSender { .. } => "W0028",
+        UncheckedTransferResult { .. } => "W0029",
+        UnusedImport { .. } => "W0030",
+        UnusedVariable { .. } => "W0031",
+    }
+}
+
+impl Warning {
+    /// The name of the lint which this kind of warning belongs to, see [crate::lint::LINTS].
+    pub fn lint(&self) -> &'static str {
+        use Warning::*;
+        match self {
+            NonClassCaseStructName { .. }
+            | NonClassCaseTypeParameter { .. }
+            | NonClassCaseTraitName { .. }
+            | NonClassCaseEnumName { .. }
+            | NonClassCaseEnumVariantName { .. } => "non_class_case_names",
+            NonSnakeCaseStructFieldName { .. } | NonSnakeCaseFunctionName { .. } => {
+                "non_snake_case_names"
+            }
+            NonScreamingSnakeCaseConstName { .. } => "non_screaming_snake_case_names",
+            LossOfPrecision { .. } => "loss_of_precision",
+            UnusedReturnValue { .. } => "unused_return_value",
+            SimilarMethodFound { .. } => "similar_method_found",
+            ShadowsOtherSymbol { .. } => "shadowing",
+            OverridingTraitImplementation => "overriding_trait_implementation",
+            DeadDeclaration
+            | DeadFunctionDeclaration
+            | DeadStructDeclaration
+            | DeadTrait
+            | DeadEnumVariant { .. }
+            | DeadMethod
+            | StructFieldNeverRead
+            | DeadStorageDeclaration => "dead_code",
+            UnreachableCode | MatchExpressionUnreachableArm => "unreachable_code",
+            ShadowingReservedRegister { .. } => "shadowing_reserved_register",
+            DeadStorageDeclarationForFunction { .. } => "unneeded_storage_attributes",
+            UnknownLint { .. } => "unknown_lints",
+            StorageWriteAfterContractCall { .. } => "storage_write_after_contract_call",
+            UncheckedMsgSender { .. } => "unchecked_msg_sender",
+            UncheckedTransferResult { .. } => "unchecked_transfer_result",
+            UnusedImport { .. } => "unused_imports",
+            UnusedVariable { .. } => "unused_variables",
         }
     }
 }
@@ -584,6 +804,16 @@ pub enum CompileError {
         missing_functions: String,
         span: Span,
     },
+    #[error("Associated item \"{name}\" is not a part of trait \"{trait_name}\".")]
+    AssociatedItemNotAPartOfTrait {
+        name: Ident,
+        trait_name: Ident,
+        span: Span,
+    },
+    #[error("Associated item \"{name}\" is defined more than once in this trait implementation.")]
+    MultipleDefinitionsOfAssociatedItem { name: Ident, span: Span },
+    #[error("Associated items are missing from this trait implementation: {missing_items}")]
+    MissingAssociatedItems { missing_items: String, span: Span },
     #[error("Expected {} type {}, but instead found {}.", expected, if *expected == 1usize { "argument" } else { "arguments" }, given)]
     IncorrectNumberOfTypeArguments {
         given: usize,
@@ -855,6 +1085,10 @@ pub enum CompileError {
     },
     #[error("Function {fn_name} is recursive, which is unsupported at this time.")]
     RecursiveCall { fn_name: Ident, span: Span },
+    #[error("Cannot create a pointer to generic function \"{name}\". Function pointers may only refer to functions without type parameters.")]
+    FunctionPointerToGenericFunction { name: Ident, span: Span },
+    #[error("Cannot create a pointer to function \"{name}\" because it accesses contract storage. Function pointers may only refer to pure functions.")]
+    FunctionPointerToImpureFunction { name: Ident, span: Span },
     #[error(
         "Function {fn_name} is recursive via {call_chain}, which is unsupported at this time."
     )]
@@ -894,7 +1128,11 @@ pub enum CompileError {
         span: Span,
     },
     #[error("The name \"{name}\" shadows another symbol with the same name.")]
-    ShadowsOtherSymbol { name: Ident },
+    ShadowsOtherSymbol {
+        name: Ident,
+        /// The name of the symbol which is shadowed.
+        previous: Ident,
+    },
     #[error("The name \"{name}\" is already used for a generic parameter in this scope.")]
     GenericShadowsGeneric { name: Ident },
     #[error("The name \"{name}\" imported through `*` shadows another symbol with the same name.")]
@@ -910,6 +1148,11 @@ pub enum CompileError {
         missing_patterns: String,
         span: Span,
     },
+    #[error("Refutable pattern in local binding. Missing patterns {missing_patterns}. Use a `match` or `if let` expression to handle the other cases.")]
+    RefutablePatternInLet {
+        missing_patterns: String,
+        span: Span,
+    },
     #[error("Pattern does not mention {}: {}",
         if missing_fields.len() == 1 { "field" } else { "fields" },
         missing_fields.join(", "))]
@@ -1046,6 +1289,33 @@ pub enum CompileError {
     BreakOutsideLoop { span: Span },
     #[error("\"continue\" used outside of a loop")]
     ContinueOutsideLoop { span: Span },
+    #[error("{warning}")]
+    DeniedLint {
+        /// The name of the denied lint which the warning belongs to.
+        lint: &'static str,
+        warning: Warning,
+        span: Span,
+    },
+    #[error("Multiple methods named \"{method_name}\" apply to this call on type \"{type_name}\": {candidates}.")]
+    AmbiguousMethodCall {
+        method_name: Ident,
+        type_name: String,
+        /// The traits which provide the applicable methods, e.g. `From<u64>, From<b256>`.
+        candidates: String,
+    },
+    #[error("Conflicting implementations of trait \"{trait_name}\" for type \"{type_name}\".")]
+    ConflictingImplsForTraitAndType {
+        trait_name: String,
+        type_name: String,
+        span: Span,
+    },
+    #[error("None of the methods named \"{method_name}\" for type \"{type_name}\" fit this call: {candidates}.")]
+    NoApplicableMethod {
+        method_name: Ident,
+        type_name: String,
+        /// The traits which provide the methods, e.g. `From<u64>, From<b256>`.
+        candidates: String,
+    },
 }
 
 impl std::convert::From<TypeError> for CompileError {
@@ -1089,6 +1359,9 @@ impl Spanned for CompileError {
             UnknownTrait { span, .. } => span.clone(),
             FunctionNotAPartOfInterfaceSurface { span, .. } => span.clone(),
             MissingInterfaceSurfaceMethods { span, .. } => span.clone(),
+            AssociatedItemNotAPartOfTrait { span, .. } => span.clone(),
+            MultipleDefinitionsOfAssociatedItem { span, .. } => span.clone(),
+            MissingAssociatedItems { span, .. } => span.clone(),
             IncorrectNumberOfTypeArguments { span, .. } => span.clone(),
             DoesNotTakeTypeArguments { span, .. } => span.clone(),
             TypeArgumentsNotAllowed { span } => span.clone(),
@@ -1152,6 +1425,8 @@ impl Spanned for CompileError {
             IncorrectNumberOfInterfaceSurfaceFunctionParameters { span, .. } => span.clone(),
             ArgumentParameterTypeMismatch { span, .. } => span.clone(),
             RecursiveCall { span, .. } => span.clone(),
+            FunctionPointerToGenericFunction { span, .. } => span.clone(),
+            FunctionPointerToImpureFunction { span, .. } => span.clone(),
             RecursiveCallChain { span, .. } => span.clone(),
             RecursiveType { span, .. } => span.clone(),
             RecursiveTypeChain { span, .. } => span.clone(),
@@ -1162,11 +1437,12 @@ impl Spanned for CompileError {
             BurnFromExternalContext { span, .. } => span.clone(),
             ContractStorageFromExternalContext { span, .. } => span.clone(),
             ArrayOutOfBounds { span, .. } => span.clone(),
-            ShadowsOtherSymbol { name } => name.span(),
+            ShadowsOtherSymbol { name, .. } => name.span(),
             GenericShadowsGeneric { name } => name.span(),
             StarImportShadowsOtherSymbol { name } => name.span(),
             MatchWrongType { span, .. } => span.clone(),
             MatchExpressionNonExhaustive { span, .. } => span.clone(),
+            RefutablePatternInLet { span, .. } => span.clone(),
             MatchStructPatternMissingFields { span, .. } => span.clone(),
             NotAnEnum { span, .. } => span.clone(),
             StorageAccessMismatch { span, .. } => span.clone(),
@@ -1210,15 +1486,267 @@ impl Spanned for CompileError {
             IntrinsicIncorrectNumTArgs { span, .. } => span.clone(),
             BreakOutsideLoop { span } => span.clone(),
             ContinueOutsideLoop { span } => span.clone(),
+            DeniedLint { span, .. } => span.clone(),
+            AmbiguousMethodCall { method_name, .. } => method_name.span(),
+            NoApplicableMethod { method_name, .. } => method_name.span(),
+            ConflictingImplsForTraitAndType { span, .. } => span.clone(),
         }
     }
 }
 
+diagnostic_codes! {
+    /// The stable code of this kind of error, e.g. `E0001`. Codes are never reused, so they
+    /// can be looked up with `forc explain`.
+    impl CompileError as ERROR_CODES {
+        UnknownVariable { .. } => "E0001",
+        UnknownVariablePath { .. } => "E0002",
+        UnknownFunction { .. } => "E0003",
+        NotAVariable { .. } => "E0004",
+        NotAFunction { .. } => "E0005",
+        Unimplemented(..) => "E0006",
+        TypeError(_) => "E0007",
+        ParseError { .. } => "E0008",
+        Internal(..) => "E0009",
+        InternalOwned(..) => "E0010",
+        InvalidByteLiteralLength { .. } => "E0011",
+        ExpectedExprAfterOp { .. } => "E0012",
+        ExpectedOp { .. } => "E0013",
+        MultipleContracts(_) => "E0014",
+        MultipleScripts(_) => "E0015",
+        MultiplePredicates(_) => "E0016",
+        NoPredicateMainFunction(_) => "E0017",
+        PredicateMainDoesNotReturnBool(_) => "E0018",
+        NoScriptMainFunction(_) => "E0019",
+        MultipleDefinitionsOfFunction { .. } => "E0020",
+        ReassignmentToNonVariable { .. } => "E0021",
+        AssignmentToNonMutable { .. } => "E0022",
+        MethodRequiresMutableSelf { .. } => "E0023",
+        AssociatedFunctionCalledAsMethod { .. } => "E0024",
+        TypeParameterNotInTypeScope { .. } => "E0025",
+        MultipleImmediates(_) => "E0026",
+        MismatchedTypeInTrait { .. } => "E0027",
+        NotATrait { .. } => "E0028",
+        UnknownTrait { .. } => "E0029",
+        FunctionNotAPartOfInterfaceSurface { .. } => "E0030",
+        MissingInterfaceSurfaceMethods { .. } => "E0031",
+        AssociatedItemNotAPartOfTrait { .. } => "E0032",
+        MultipleDefinitionsOfAssociatedItem { .. } => "E0033",
+        MissingAssociatedItems { .. } => "E0034",
+        IncorrectNumberOfTypeArguments { .. } => "E0035",
+        DoesNotTakeTypeArguments { .. } => "E0036",
+        TypeArgumentsNotAllowed { .. } => "E0037",
+        NeedsTypeArguments { .. } => "E0038",
+        StructNotFound { .. } => "E0039",
+        EnumNotFound { .. } => "E0040",
+        DeclaredNonStructAsStruct { .. } => "E0041",
+        AccessedFieldOfNonStruct { .. } => "E0042",
+        MethodOnNonValue { .. } => "E0043",
+        StructMissingField { .. } => "E0044",
+        StructDoesNotHaveField { .. } => "E0045",
+        MethodNotFound { .. } => "E0046",
+        ModuleNotFound { .. } => "E0047",
+        FieldAccessOnNonStruct { .. } => "E0048",
+        NotATuple { .. } => "E0049",
+        NotAnEnum { .. } => "E0050",
+        NotAStruct { .. } => "E0051",
+        DeclIsNotAnEnum { .. } => "E0052",
+        DeclIsNotAStruct { .. } => "E0053",
+        DeclIsNotAFunction { .. } => "E0054",
+        DeclIsNotAVariable { .. } => "E0055",
+        DeclIsNotAnAbi { .. } => "E0056",
+        FieldNotFound { .. } => "E0057",
+        SymbolNotFound { .. } => "E0058",
+        ImportPrivateSymbol { .. } => "E0059",
+        NoElseBranch { .. } => "E0060",
+        UnqualifiedSelfType { .. } => "E0061",
+        NotAType { .. } => "E0062",
+        MissingEnumInstantiator { .. } => "E0063",
+        PathDoesNotReturn { .. } => "E0064",
+        ExpectedImplicitReturnFromBlockWithType { .. } => "E0065",
+        ExpectedImplicitReturnFromBlock { .. } => "E0066",
+        UnknownRegister { .. } => "E0067",
+        MissingImmediate { .. } => "E0068",
+        InvalidImmediateValue { .. } => "E0069",
+        InvalidAssemblyMismatchedReturn { .. } => "E0070",
+        UnknownEnumVariant { .. } => "E0071",
+        UnrecognizedOp { .. } => "E0072",
+        UnableToInferGeneric { .. } => "E0073",
+        UnconstrainedGenericParameter { .. } => "E0074",
+        Immediate06TooLarge { .. } => "E0075",
+        Immediate12TooLarge { .. } => "E0076",
+        Immediate18TooLarge { .. } => "E0077",
+        Immediate24TooLarge { .. } => "E0078",
+        DisallowedJi { .. } => "E0079",
+        DisallowedJnei { .. } => "E0080",
+        DisallowedJnzi { .. } => "E0081",
+        DisallowedLw { .. } => "E0082",
+        IncorrectNumberOfAsmRegisters { .. } => "E0083",
+        UnnecessaryImmediate { .. } => "E0084",
+        AmbiguousPath { .. } => "E0085",
+        InvalidStrType { .. } => "E0086",
+        UnknownType { .. } => "E0087",
+        UnknownTypeName { .. } => "E0088",
+        TooManyInstructions { .. } => "E0089",
+        FileNotFound { .. } => "E0090",
+        FileCouldNotBeRead { .. } => "E0091",
+        ImportMustBeLibrary { .. } => "E0092",
+        MoreThanOneEnumInstantiator { .. } => "E0093",
+        UnnecessaryEnumInstantiator { .. } => "E0094",
+        TraitNotFound { .. } => "E0095",
+        InvalidExpressionOnLhs { .. } => "E0096",
+        TooManyArgumentsForFunction { .. } => "E0097",
+        TooFewArgumentsForFunction { .. } => "E0098",
+        InvalidAbiType { .. } => "E0099",
+        NotAnAbi { .. } => "E0100",
+        ImplAbiForNonContract { .. } => "E0101",
+        IncorrectNumberOfInterfaceSurfaceFunctionParameters { .. } => "E0102",
+        ArgumentParameterTypeMismatch { .. } => "E0103",
+        RecursiveCall { .. } => "E0104",
+        FunctionPointerToGenericFunction { .. } => "E0105",
+        FunctionPointerToImpureFunction { .. } => "E0106",
+        RecursiveCallChain { .. } => "E0107",
+        RecursiveType { .. } => "E0108",
+        RecursiveTypeChain { .. } => "E0109",
+        TypeWithUnknownSize { .. } => "E0110",
+        InfiniteDependencies { .. } => "E0111",
+        GMFromExternalContract { .. } => "E0112",
+        MintFromExternalContext { .. } => "E0113",
+        BurnFromExternalContext { .. } => "E0114",
+        ContractStorageFromExternalContext { .. } => "E0115",
+        ArrayOutOfBounds { .. } => "E0116",
+        TupleIndexOutOfBounds { .. } => "E0117",
+        ShadowsOtherSymbol { .. } => "E0118",
+        GenericShadowsGeneric { .. } => "E0119",
+        StarImportShadowsOtherSymbol { .. } => "E0120",
+        MatchWrongType { .. } => "E0121",
+        MatchExpressionNonExhaustive { .. } => "E0122",
+        RefutablePatternInLet { .. } => "E0123",
+        MatchStructPatternMissingFields { .. } => "E0124",
+        StorageAccessMismatch { .. } => "E0125",
+        TraitDeclPureImplImpure { .. } => "E0126",
+        TraitImplPurityMismatch { .. } => "E0127",
+        ImpureInNonContract { .. } => "E0128",
+        ImpureInPureContext { .. } => "E0129",
+        IntegerTooLarge { .. } => "E0130",
+        IntegerTooSmall { .. } => "E0131",
+        IntegerContainsInvalidDigit { .. } => "E0132",
+        AsteriskWithAlias { .. } => "E0133",
+        AbiAsSupertrait { .. } => "E0134",
+        SupertraitImplMissing { .. } => "E0135",
+        SupertraitImplRequired { .. } => "E0136",
+        IfLetNonEnum { .. } => "E0137",
+        ContractCallParamRepeated { .. } => "E0138",
+        UnrecognizedContractParam { .. } => "E0139",
+        CallParamForNonContractCallMethod { .. } => "E0140",
+        StorageFieldDoesNotExist { .. } => "E0141",
+        NoDeclaredStorage { .. } => "E0142",
+        MultipleStorageDeclarations { .. } => "E0143",
+        InvalidStorageOnlyTypeDecl { .. } => "E0144",
+        InvalidVariableName { .. } => "E0145",
+        UnexpectedDeclaration { .. } => "E0146",
+        ContractAddressMustBeKnown { .. } => "E0147",
+        ConvertParseTree { .. } => "E0148",
+        Lex { .. } => "E0149",
+        Parse { .. } => "E0150",
+        WhereClauseNotYetSupported { .. } => "E0151",
+        NonConstantDeclValue { .. } => "E0152",
+        StorageDeclarationInNonContract { .. } => "E0153",
+        IntrinsicUnsupportedArgType { .. } => "E0154",
+        IntrinsicIncorrectNumArgs { .. } => "E0155",
+        IntrinsicIncorrectNumTArgs { .. } => "E0156",
+        BreakOutsideLoop { .. } => "E0157",
+        ContinueOutsideLoop { .. } => "E0158",
+        DeniedLint { .. } => "E0159",
+        AmbiguousMethodCall { .. } => "E0160",
+        ConflictingImplsForTraitAndType { .. } => "E0161",
+        NoApplicableMethod { .. } => "E0162",
+    }
+}
+
 impl CompileError {
     pub fn path(&self) -> Option<Arc<PathBuf>> {
         self.span().path().cloned()
     }
 
+    /// The structured form of this error, with its code, labels, notes and help.
+    pub fn diagnostic(&self) -> Diagnostic {
+        use CompileError::*;
+        let diagnostic =
+            Diagnostic::new(Severity::Error, self.code(), self.to_string(), self.span());
+        match self {
+            Internal(..) | InternalOwned(..) | UnexpectedDeclaration { .. } => {
+                diagnostic.with_note("this is a bug in the compiler, not in your program")
+            }
+            MultipleDefinitionsOfFunction { .. } => diagnostic
+                .with_label("defined again here")
+                .with_help("rename or remove one of the definitions"),
+            AssignmentToNonMutable { name } => diagnostic
+                .with_label("cannot assign to an immutable variable")
+                .with_help(format!("declare the variable as mutable: `let mut {name}`")),
+            MethodRequiresMutableSelf { variable_name, .. } => diagnostic
+                .with_label("this method may modify its receiver")
+                .with_help(format!(
+                    "declare the variable as mutable: `let mut {variable_name}`"
+                )),
+            AssociatedFunctionCalledAsMethod { fn_name, .. } => diagnostic.with_help(format!(
+                "call the function through its type instead, e.g. `Type::{fn_name}(..)`"
+            )),
+            StructMissingField { field_name, .. } => {
+                diagnostic.with_label(format!("missing field `{field_name}`"))
+            }
+            ImportPrivateSymbol { .. } => diagnostic
+                .with_label("private")
+                .with_help("declare the item with `pub` in the module which declares it"),
+            NoElseBranch { .. } => diagnostic.with_help("add an `else` branch"),
+            UnableToInferGeneric { .. } => diagnostic
+                .with_help("annotate the type explicitly, e.g. `let x: Vec<u64> = Vec::new();`"),
+            RecursiveCall { .. } | RecursiveCallChain { .. } => diagnostic.with_note(
+                "the stack usage of every function must be known at compile time, so \
+                 recursion is not supported",
+            ),
+            ShadowsOtherSymbol { previous, .. } => diagnostic
+                .with_secondary(previous.span(), "first defined here")
+                .with_help("rename one of them, or import it under another name with `as`"),
+            GenericShadowsGeneric { .. } => {
+                diagnostic.with_label("already declared as a type parameter")
+            }
+            MatchExpressionNonExhaustive { .. } => {
+                diagnostic.with_help("add arms for the missing patterns, or a catch-all `_` arm")
+            }
+            StorageAccessMismatch { .. } | ImpureInPureContext { .. } => diagnostic.with_note(
+                "functions must declare the storage they access, including through the \
+                 functions they call",
+            ),
+            DeniedLint {
+                lint,
+                warning,
+                span,
+            } => {
+                let warning = CompileWarning {
+                    span: span.clone(),
+                    warning_content: warning.clone(),
+                };
+                let diagnostic = Diagnostic {
+                    severity: Severity::Error,
+                    code: self.code(),
+                    ..warning.diagnostic()
+                };
+                diagnostic.with_note(format!(
+                    "the `{lint}` lint is denied, which turns warning {} into an error",
+                    warning.warning_content.code()
+                ))
+            }
+            AmbiguousMethodCall { .. } => diagnostic.with_help(
+                "annotate the type which the call is expected to return, e.g. \
+                 `let x: u64 = a.into();`",
+            ),
+            ConflictingImplsForTraitAndType { .. } => diagnostic
+                .with_label("conflicting implementation")
+                .with_help("remove one of the implementations"),
+            _ => diagnostic,
+        }
+    }
+
     /// Returns the line and column start and end
     pub fn line_col(&self) -> (LineCol, LineCol) {
         (
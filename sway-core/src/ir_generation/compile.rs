@@ -173,6 +173,12 @@ fn compile_fn_with_args(
     args: Vec<(String, Type, Span)>,
     selector: Option<[u8; 4]>,
 ) -> Result<Function, CompileError> {
+    // The arguments are described for debuggers by their types as written in the source.
+    let arg_type_names = ast_fn_decl
+        .parameters
+        .iter()
+        .map(|param| param.type_id.to_string())
+        .collect::<Vec<_>>();
     let TypedFunctionDeclaration {
         name,
         body,
@@ -186,7 +192,13 @@ fn compile_fn_with_args(
 
     let args = args
         .into_iter()
-        .map(|(name, ty, span)| (name, ty, md_mgr.span_to_md(context, &span)))
+        .zip(arg_type_names)
+        .map(|((name, ty, span), type_name)| {
+            let span_md_idx = md_mgr.span_to_md(context, &span);
+            let var_decl_md_idx = md_mgr.var_decl_to_md(context, &name, &type_name, None);
+            let md_idx = md_combine(context, &span_md_idx, &var_decl_md_idx);
+            (name, ty, md_idx)
+        })
         .collect();
     let ret_type = convert_resolved_typeid(context, &return_type, &return_type_span)?;
     let span_md_idx = md_mgr.span_to_md(context, &span);
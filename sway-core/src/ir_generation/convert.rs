@@ -116,6 +116,8 @@ fn convert_resolved_type(
                 create_tuple_aggregate(context, new_fields).map(Type::Struct)?
             }
         }
+        // Function pointers are represented by the address of the function.
+        TypeInfo::Function { .. } => Type::Uint(64),
 
         // Unsupported types which shouldn't exist in the AST after type checking and
         // monomorphisation.
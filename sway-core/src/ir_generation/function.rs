@@ -34,6 +34,9 @@ pub(super) struct FnCompiler {
     pub(super) block_to_break_to: Option<Block>,
     pub(super) block_to_continue_to: Option<Block>,
     lexical_map: LexicalMap,
+    // The source offsets at which the code blocks being compiled end, innermost last, which is
+    // where the variables declared in them go out of scope.
+    scope_ends: Vec<usize>,
 }
 
 pub(super) enum StateAccessType {
@@ -55,6 +58,7 @@ impl FnCompiler {
             block_to_break_to: None,
             block_to_continue_to: None,
             lexical_map,
+            scope_ends: Vec::new(),
         }
     }
 
@@ -86,6 +90,8 @@ impl FnCompiler {
         ast_block: TypedCodeBlock,
     ) -> Result<Value, CompileError> {
         self.lexical_map.enter_scope();
+        let scope_end = ast_block.contents.last().map(|node| node.span.end());
+        self.scope_ends.extend(scope_end);
         let index_of_first_break_or_continue =
             ast_block.contents.clone().into_iter().position(|r| {
                 matches!(
@@ -230,6 +236,9 @@ impl FnCompiler {
             .map(|vals| vals.last().cloned())
             .transpose()
             .unwrap_or_else(|| Ok(Constant::get_unit(context)));
+        if scope_end.is_some() {
+            self.scope_ends.pop();
+        }
         self.lexical_map.leave_scope();
         value
     }
@@ -371,6 +380,19 @@ impl FnCompiler {
                 self.compile_unsafe_downcast(context, md_mgr, exp, variant)
             }
             TypedExpressionVariant::EnumTag { exp } => self.compile_enum_tag(context, md_mgr, exp),
+            TypedExpressionVariant::FunctionReference { function_decl, .. } => {
+                self.compile_fn_reference(context, md_mgr, function_decl, span_md_idx)
+            }
+            TypedExpressionVariant::IndirectFunctionApplication { callee, arguments } => self
+                .compile_indirect_fn_call(
+                    context,
+                    md_mgr,
+                    *callee,
+                    arguments,
+                    ast_expr.return_type,
+                    &ast_expr.span,
+                    span_md_idx,
+                ),
         }
     }
 
@@ -829,6 +851,54 @@ impl FnCompiler {
         }
     }
 
+    fn compile_fn_reference(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        callee: TypedFunctionDeclaration,
+        span_md_idx: Option<MetadataIndex>,
+    ) -> Result<Value, CompileError> {
+        // As with direct calls we create a single-use instantiation of the callee and take its
+        // address.  Calls through the pointer are devirtualised by the inliner when the target
+        // can be determined statically.
+        let callee_name = format!("{}_{}", callee.name, context.get_unique_id());
+
+        let mut callee_fn_decl = callee;
+        callee_fn_decl.type_parameters.clear();
+        callee_fn_decl.name = Ident::new(Span::from_string(callee_name));
+
+        let callee = compile_function(context, md_mgr, self.module, callee_fn_decl)?;
+        Ok(self
+            .current_block
+            .ins(context)
+            .fn_addr(callee.unwrap())
+            .add_metadatum(context, span_md_idx))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn compile_indirect_fn_call(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        ast_callee: TypedExpression,
+        ast_args: Vec<TypedExpression>,
+        return_type: TypeId,
+        span: &Span,
+        span_md_idx: Option<MetadataIndex>,
+    ) -> Result<Value, CompileError> {
+        let return_type = convert_resolved_typeid(context, &return_type, span)?;
+        let callee = self.compile_expression(context, md_mgr, ast_callee)?;
+        let args = ast_args
+            .into_iter()
+            .map(|expr| self.compile_expression(context, md_mgr, expr))
+            .collect::<Result<Vec<Value>, CompileError>>()?;
+        Ok(self
+            .current_block
+            .ins(context)
+            .call_indirect(callee, &args, return_type)
+            .add_metadatum(context, span_md_idx))
+    }
+
     fn compile_if(
         &mut self,
         context: &mut Context,
@@ -1077,6 +1147,12 @@ impl FnCompiler {
 
         // Grab these before we move body into compilation.
         let return_type = convert_resolved_typeid(context, &body.return_type, &body.span)?;
+        let var_decl_md_idx = md_mgr.var_decl_to_md(
+            context,
+            name.as_str(),
+            &body.return_type.to_string(),
+            self.scope_ends.last().copied(),
+        );
 
         // We must compile the RHS before checking for shadowing, as it will still be in the
         // previous scope.
@@ -1099,7 +1175,8 @@ impl FnCompiler {
             self.current_block
                 .ins(context)
                 .store(ptr_val, init_val)
-                .add_metadatum(context, span_md_idx);
+                .add_metadatum(context, span_md_idx)
+                .add_metadatum(context, var_decl_md_idx);
         }
         Ok(init_val)
     }
@@ -1118,6 +1195,12 @@ impl FnCompiler {
             compile_constant_expression(context, md_mgr, self.module, None, &value)?;
         let local_name = self.lexical_map.insert(name.as_str().to_owned());
         let return_type = convert_resolved_typeid(context, &value.return_type, &value.span)?;
+        let var_decl_md_idx = md_mgr.var_decl_to_md(
+            context,
+            name.as_str(),
+            &value.return_type.to_string(),
+            self.scope_ends.last().copied(),
+        );
 
         // We compile consts the same as vars are compiled. This is because ASM generation
         // cannot handle
@@ -1141,7 +1224,8 @@ impl FnCompiler {
             self.current_block
                 .ins(context)
                 .store(ptr_val, const_expr_val)
-                .add_metadatum(context, span_md_idx);
+                .add_metadatum(context, span_md_idx)
+                .add_metadatum(context, var_decl_md_idx);
         }
         Ok(const_expr_val)
     }
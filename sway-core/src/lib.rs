@@ -8,10 +8,13 @@ mod concurrent_slab;
 pub mod constants;
 mod control_flow_analysis;
 mod convert_parse_tree;
+pub mod diagnostic;
 pub mod ir_generation;
+pub mod lint;
 mod metadata;
 pub mod parse_tree;
 pub mod semantic_analysis;
+pub mod size_report;
 pub mod source_map;
 mod style;
 pub mod type_engine;
@@ -19,8 +22,9 @@ pub mod type_engine;
 use crate::{error::*, source_map::SourceMap};
 pub use asm_generation::from_ir::compile_ir_to_asm;
 use asm_generation::FinalizedAsm;
-pub use build_config::BuildConfig;
+pub use build_config::{BuildConfig, OptLevel};
 use control_flow_analysis::ControlFlowGraph;
+use lint::LintLevels;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -60,15 +64,13 @@ pub fn parse(input: Arc<str>, config: Option<&BuildConfig>) -> CompileResult<Par
 
 /// When no `BuildConfig` is given, we're assumed to be parsing in-memory with no submodules.
 fn parse_in_memory(src: Arc<str>) -> CompileResult<ParseProgram> {
-    let module = match sway_parse::parse_file(src, None) {
-        Ok(module) => module,
-        Err(error) => return err(vec![], parse_file_error_to_compile_errors(error)),
-    };
-    convert_parse_tree::convert_parse_tree(module).flat_map(|(kind, tree)| {
-        let submodules = Default::default();
-        let root = ParseModule { tree, submodules };
-        let program = ParseProgram { kind, root };
-        ok(program, vec![], vec![])
+    parse_module(src, None).flat_map(|module| {
+        convert_parse_tree::convert_parse_tree(module).flat_map(|(kind, tree)| {
+            let submodules = Default::default();
+            let root = ParseModule { tree, submodules };
+            let program = ParseProgram { kind, root };
+            ok(program, vec![], vec![])
+        })
     })
 }
 
@@ -86,9 +88,10 @@ fn parse_files(src: Arc<str>, config: &BuildConfig) -> CompileResult<ParseProgra
 /// submodules.
 fn parse_module_tree(src: Arc<str>, path: Arc<PathBuf>) -> CompileResult<(TreeType, ParseModule)> {
     // Parse this module first.
-    let module = match sway_parse::parse_file(src, Some(path.clone())) {
-        Ok(module) => module,
-        Err(error) => return err(vec![], parse_file_error_to_compile_errors(error)),
+    let mut errors = Vec::new();
+    let module = match parse_module(src, Some(path.clone())).ok(&mut vec![], &mut errors) {
+        Some(module) => module,
+        None => return err(vec![], errors),
     };
     let module_dir = path.parent().expect("module file has no parent directory");
 
@@ -133,12 +136,31 @@ fn parse_module_tree(src: Arc<str>, path: Arc<PathBuf>) -> CompileResult<(TreeTy
     });
 
     // Convert from the raw parsed module to the `ParseTree` ready for type-check.
-    convert_parse_tree::convert_parse_tree(module).flat_map(|(prog_kind, tree)| {
+    let mut res = convert_parse_tree::convert_parse_tree(module).flat_map(|(prog_kind, tree)| {
         submodules_res.flat_map(|submodules| {
             let parse_module = ParseModule { tree, submodules };
             ok((prog_kind, parse_module), vec![], vec![])
         })
-    })
+    });
+    // The syntax errors which the parser recovered from are reported first.
+    errors.append(&mut res.errors);
+    res.errors = errors;
+    res
+}
+
+/// Parse the source of a single module. When the parser recovers from syntax errors, the module
+/// is returned along with the errors, so that the rest of it can still be used by tooling.
+fn parse_module(src: Arc<str>, path: Option<Arc<PathBuf>>) -> CompileResult<sway_ast::Module> {
+    match sway_parse::parse_file_with_recovery(src, path) {
+        Ok((module, errors)) => {
+            let errors = errors
+                .into_iter()
+                .map(|error| CompileError::Parse { error })
+                .collect();
+            ok(module, vec![], errors)
+        }
+        Err(error) => err(vec![], parse_file_error_to_compile_errors(error)),
+    }
 }
 
 fn module_path(parent_module_dir: &Path, dep: &sway_ast::Dependency) -> PathBuf {
@@ -164,6 +186,8 @@ fn parse_file_error_to_compile_errors(error: sway_parse::ParseFileError) -> Vec<
 pub enum CompilationResult {
     Success {
         asm: FinalizedAsm,
+        /// The names and locations of the Sway functions which were compiled into `asm`.
+        functions: Vec<(String, span::Span)>,
         warnings: Vec<CompileWarning>,
     },
     Library {
@@ -204,9 +228,67 @@ pub enum BytecodeCompilationResult {
     },
 }
 
+/// Type check and analyze the parsed program. The warnings are subject to the lint levels set by
+/// the attributes of the program and by the `build_config`.
 pub fn parsed_to_ast(
     parse_program: &ParseProgram,
     initial_namespace: namespace::Module,
+    build_config: Option<&BuildConfig>,
+) -> CompileAstResult {
+    let lint_scopes = lint::program_scopes(parse_program);
+    let lint_levels = LintLevels::new(&lint_scopes, build_config);
+    match type_check_and_analyze(parse_program, initial_namespace) {
+        CompileAstResult::Success {
+            typed_program,
+            mut warnings,
+        } => {
+            let mut errors = vec![];
+            suppress_error_node_diagnostics(parse_program, &mut warnings, &mut errors);
+            let warnings = lint_levels.apply(warnings, &mut errors);
+            if !errors.is_empty() {
+                return CompileAstResult::Failure { warnings, errors };
+            }
+            CompileAstResult::Success {
+                typed_program,
+                warnings,
+            }
+        }
+        CompileAstResult::Failure {
+            mut warnings,
+            mut errors,
+        } => {
+            suppress_error_node_diagnostics(parse_program, &mut warnings, &mut errors);
+            let warnings = lint_levels.apply(warnings, &mut errors);
+            CompileAstResult::Failure { warnings, errors }
+        }
+    }
+}
+
+/// Drops the diagnostics of type checking which overlap the broken items and statements that the
+/// parser skipped over. Those only follow from the syntax errors, which are reported already.
+fn suppress_error_node_diagnostics(
+    parse_program: &ParseProgram,
+    warnings: &mut Vec<CompileWarning>,
+    errors: &mut Vec<CompileError>,
+) {
+    let error_spans = parse_program.root.error_spans();
+    if error_spans.is_empty() {
+        return;
+    }
+    let overlaps_error_node = |diagnostic_span: &span::Span| {
+        error_spans.iter().any(|error_span| {
+            error_span.path() == diagnostic_span.path()
+                && error_span.start() < diagnostic_span.end()
+                && diagnostic_span.start() < error_span.end()
+        })
+    };
+    warnings.retain(|warning| !overlaps_error_node(&warning.span));
+    errors.retain(|error| !overlaps_error_node(&error.span()));
+}
+
+fn type_check_and_analyze(
+    parse_program: &ParseProgram,
+    initial_namespace: namespace::Module,
 ) -> CompileAstResult {
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
@@ -276,6 +358,8 @@ pub fn compile_to_ast(
 
     warnings.extend(new_warnings);
     errors.extend(new_errors);
+    // The items and statements which did parse are type checked even if the parser skipped over
+    // others, so that the errors in them are reported along with the syntax errors.
     let parse_program = match parse_program_opt {
         Some(parse_program) => parse_program,
         None => {
@@ -285,13 +369,21 @@ pub fn compile_to_ast(
         }
     };
 
-    match parsed_to_ast(&parse_program, initial_namespace) {
+    // The warnings of the conversion to the parse tree are subject to the lint levels as well.
+    let lint_scopes = lint::program_scopes(&parse_program);
+    warnings = LintLevels::new(&lint_scopes, build_config).apply(warnings, &mut errors);
+
+    match parsed_to_ast(&parse_program, initial_namespace, build_config) {
         CompileAstResult::Success {
             typed_program,
             warnings: new_warnings,
         } => {
             warnings.extend(new_warnings);
             warnings = dedup_unsorted(warnings);
+            if !errors.is_empty() {
+                errors = dedup_unsorted(errors);
+                return CompileAstResult::Failure { errors, warnings };
+            }
             CompileAstResult::Success {
                 typed_program,
                 warnings,
@@ -336,16 +428,26 @@ pub fn ast_to_asm(ast_res: CompileAstResult, build_config: &BuildConfig) -> Comp
             let tree_type = typed_program.kind.tree_type();
             match tree_type {
                 TreeType::Contract | TreeType::Script | TreeType::Predicate => {
-                    let asm = check!(
+                    let lint_scopes = typed_program.lint_scopes.clone();
+                    let lint_levels = LintLevels::new(&lint_scopes, Some(build_config));
+                    let (asm, functions) = check!(
                         compile_ast_to_ir_to_asm(*typed_program, build_config),
-                        return CompilationResult::Failure { errors, warnings },
+                        return CompilationResult::Failure {
+                            warnings: lint_levels.apply(warnings, &mut errors),
+                            errors,
+                        },
                         warnings,
                         errors
                     );
+                    warnings = lint_levels.apply(warnings, &mut errors);
                     if !errors.is_empty() {
                         return CompilationResult::Failure { errors, warnings };
                     }
-                    CompilationResult::Success { asm, warnings }
+                    CompilationResult::Success {
+                        asm,
+                        functions,
+                        warnings,
+                    }
                 }
                 TreeType::Library { name } => CompilationResult::Library {
                     warnings,
@@ -362,7 +464,7 @@ use sway_ir::{context::Context, function::Function};
 pub(crate) fn compile_ast_to_ir_to_asm(
     program: TypedProgram,
     build_config: &BuildConfig,
-) -> CompileResult<FinalizedAsm> {
+) -> CompileResult<(FinalizedAsm, Vec<(String, span::Span)>)> {
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
 
@@ -423,48 +525,127 @@ pub(crate) fn compile_ast_to_ir_to_asm(
         errors
     );
 
-    // Inline function calls from the entry points.
-    check!(
+    // Specialise the functions called from the entry points for their constant arguments.  This
+    // must come before inlining, which leaves no direct calls behind.
+    if build_config.opt_level != OptLevel::Opt0 {
+        check!(
+            specialize_constant_calls(&mut ir, &entry_point_functions),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+    }
+
+    // Inline function calls from the entry points, and from the functions they call through
+    // function pointers.
+    let functions = check!(
         inline_function_calls(&mut ir, &entry_point_functions),
         return err(warnings, errors),
         warnings,
         errors
     );
 
-    // The only other optimisation we have at the moment is constant combining.  In lieu of a
-    // forthcoming pass manager we can just call it here now.
+    // In lieu of a forthcoming pass manager we can just call the other optimisations here now.
     check!(
-        combine_constants(&mut ir, &entry_point_functions),
+        combine_constants(&mut ir, &functions),
         return err(warnings, errors),
         warnings,
         errors
     );
+    if build_config.opt_level != OptLevel::Opt0 {
+        check!(
+            propagate_constants(&mut ir, &functions),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+        check!(
+            optimize_loops(&mut ir, &functions),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+    }
 
     if build_config.print_ir {
         tracing::info!("{}", ir);
     }
 
-    compile_ir_to_asm(&ir, Some(build_config))
+    let functions = asm_generation::from_ir::function_spans(&ir);
+    compile_ir_to_asm(&ir, Some(build_config)).map(|asm| (asm, functions))
+}
+
+/// Inlines the calls made from `functions`, and from the functions called through function
+/// pointers which can't be inlined, as those are compiled on their own.  Returns all of these
+/// functions.
+fn inline_function_calls(ir: &mut Context, functions: &[Function]) -> CompileResult<Vec<Function>> {
+    let mut functions = functions.to_vec();
+    let mut ix = 0;
+    while let Some(function) = functions.get(ix).copied() {
+        match sway_ir::optimize::inline_all_function_calls(ir, &function) {
+            Ok(true) => function.invalidate_analyses(ir),
+            Ok(false) => (),
+            Err(ir_error) => {
+                return err(
+                    Vec::new(),
+                    vec![CompileError::InternalOwned(
+                        ir_error.to_string(),
+                        span::Span::dummy(),
+                    )],
+                );
+            }
+        }
+        for callee in asm_generation::from_ir::indirect_callees(ir, function) {
+            if !functions.contains(&callee) {
+                functions.push(callee);
+            }
+        }
+        ix += 1;
+    }
+    ok(functions, Vec::new(), Vec::new())
 }
 
-fn inline_function_calls(ir: &mut Context, functions: &[Function]) -> CompileResult<()> {
+fn combine_constants(ir: &mut Context, functions: &[Function]) -> CompileResult<()> {
     for function in functions {
-        if let Err(ir_error) = sway_ir::optimize::inline_all_function_calls(ir, function) {
-            return err(
-                Vec::new(),
-                vec![CompileError::InternalOwned(
-                    ir_error.to_string(),
-                    span::Span::dummy(),
-                )],
-            );
+        match sway_ir::optimize::combine_constants(ir, function) {
+            Ok(true) => function.invalidate_analyses(ir),
+            Ok(false) => (),
+            Err(ir_error) => {
+                return err(
+                    Vec::new(),
+                    vec![CompileError::InternalOwned(
+                        ir_error.to_string(),
+                        span::Span::dummy(),
+                    )],
+                );
+            }
         }
     }
     ok((), Vec::new(), Vec::new())
 }
 
-fn combine_constants(ir: &mut Context, functions: &[Function]) -> CompileResult<()> {
+fn specialize_constant_calls(ir: &mut Context, functions: &[Function]) -> CompileResult<()> {
+    for function in functions {
+        match sway_ir::optimize::specialize_constant_calls(ir, function) {
+            Ok(true) => function.invalidate_analyses(ir),
+            Ok(false) => (),
+            Err(ir_error) => {
+                return err(
+                    Vec::new(),
+                    vec![CompileError::InternalOwned(
+                        ir_error.to_string(),
+                        span::Span::dummy(),
+                    )],
+                );
+            }
+        }
+    }
+    ok((), Vec::new(), Vec::new())
+}
+
+fn propagate_constants(ir: &mut Context, functions: &[Function]) -> CompileResult<()> {
     for function in functions {
-        if let Err(ir_error) = sway_ir::optimize::combine_constants(ir, function) {
+        if let Err(ir_error) = sway_ir::optimize::propagate_constants(ir, function) {
             return err(
                 Vec::new(),
                 vec![CompileError::InternalOwned(
@@ -477,6 +658,29 @@ fn combine_constants(ir: &mut Context, functions: &[Function]) -> CompileResult<
     ok((), Vec::new(), Vec::new())
 }
 
+fn optimize_loops(ir: &mut Context, functions: &[Function]) -> CompileResult<()> {
+    for function in functions {
+        let result = sway_ir::optimize::hoist_loop_invariants(ir, function).and_then(|hoisted| {
+            sway_ir::optimize::simplify_induction_variables(ir, function)
+                .map(|simplified| hoisted || simplified)
+        });
+        match result {
+            Ok(true) => function.invalidate_analyses(ir),
+            Ok(false) => (),
+            Err(ir_error) => {
+                return err(
+                    Vec::new(),
+                    vec![CompileError::InternalOwned(
+                        ir_error.to_string(),
+                        span::Span::dummy(),
+                    )],
+                );
+            }
+        }
+    }
+    ok((), Vec::new(), Vec::new())
+}
+
 /// Given input Sway source code, compile to a [BytecodeCompilationResult] which contains the asm in
 /// bytecode form.
 pub fn compile_to_bytecode(
@@ -500,8 +704,12 @@ pub fn asm_to_bytecode(
     match asm_res {
         CompilationResult::Success {
             mut asm,
+            functions,
             mut warnings,
         } => {
+            for (name, span) in &functions {
+                source_map.insert_function(name, span);
+            }
             let mut asm_res = asm.to_bytecode_mut(source_map);
             warnings.append(&mut asm_res.warnings);
             if asm_res.value.is_none() || !asm_res.errors.is_empty() {
@@ -0,0 +1,373 @@
+//! Lints are named groups of warnings. The level of a lint decides whether its warnings are
+//! reported, ignored or turned into errors. It can be set for an item or a whole module with the
+//! `#[allow(..)]`, `#[warn(..)]` and `#[deny(..)]` attributes, and for a whole package in the
+//! `[lints]` table of its manifest.
+
+use crate::{
+    constants::{ALLOW_ATTRIBUTE_NAME, DENY_ATTRIBUTE_NAME, WARN_ATTRIBUTE_NAME},
+    error::{CompileError, CompileWarning},
+    BuildConfig, ParseModule, ParseProgram,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+use sway_types::{Ident, Span};
+
+/// The name of the lint group which contains every lint, e.g. `#[deny(warnings)]`.
+pub const WARNINGS_GROUP: &str = "warnings";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The warnings of the lint are not reported.
+    Allow,
+    /// The warnings of the lint are reported as warnings. This is the default.
+    Warn,
+    /// The warnings of the lint are reported as errors, which fail the compilation.
+    Deny,
+}
+
+impl LintLevel {
+    /// The level set by the attribute with the given name, e.g. `allow`.
+    pub fn from_attribute_name(name: &str) -> Option<Self> {
+        match name {
+            ALLOW_ATTRIBUTE_NAME => Some(LintLevel::Allow),
+            WARN_ATTRIBUTE_NAME => Some(LintLevel::Warn),
+            DENY_ATTRIBUTE_NAME => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Lint {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// Every lint, by name. Each kind of warning belongs to exactly one of them, see
+/// [crate::error::Warning::lint].
+pub const LINTS: &[Lint] = &[
+    Lint {
+        name: "dead_code",
+        description: "declarations, methods, enum variants and struct fields which are never used",
+    },
+    Lint {
+        name: "loss_of_precision",
+        description: "casts to a smaller integer type",
+    },
+    Lint {
+        name: "non_class_case_names",
+        description: "structs, enums, enum variants, traits and type parameters whose names \
+                      aren't ClassCase",
+    },
+    Lint {
+        name: "non_screaming_snake_case_names",
+        description: "constants whose names aren't SCREAMING_SNAKE_CASE",
+    },
+    Lint {
+        name: "non_snake_case_names",
+        description: "functions and struct fields whose names aren't snake_case",
+    },
+    Lint {
+        name: "overriding_trait_implementation",
+        description: "implementations of a trait which override an earlier one",
+    },
+    Lint {
+        name: "shadowing",
+        description: "declarations which shadow another symbol",
+    },
+    Lint {
+        name: "shadowing_reserved_register",
+        description: "asm register declarations which shadow a reserved register",
+    },
+    Lint {
+        name: "similar_method_found",
+        description: "calls of a method which isn't in scope, when one with the same name is",
+    },
+    Lint {
+        name: "storage_write_after_contract_call",
+        description: "storage writes which follow a call to another contract, which could reenter \
+                      this one",
+    },
+    Lint {
+        name: "unchecked_msg_sender",
+        description: "ABI methods which write to storage without checking who called them",
+    },
+    Lint {
+        name: "unchecked_transfer_result",
+        description: "`Result`s of functions which transfer tokens, when they're ignored",
+    },
+    Lint {
+        name: "unknown_lints",
+        description: "lint attributes naming a lint which doesn't exist",
+    },
+    Lint {
+        name: "unneeded_storage_attributes",
+        description: "storage attributes of functions which don't access storage that way",
+    },
+    Lint {
+        name: "unreachable_code",
+        description: "code and match arms which can never be reached",
+    },
+    Lint {
+        name: "unused_imports",
+        description: "symbols imported by name which are never used",
+    },
+    Lint {
+        name: "unused_return_value",
+        description: "values returned by function calls which are never used",
+    },
+    Lint {
+        name: "unused_variables",
+        description: "local variables and function parameters which are never used",
+    },
+];
+
+/// Whether `name` is the name of a lint or of the [WARNINGS_GROUP].
+pub fn is_lint_name(name: &str) -> bool {
+    name == WARNINGS_GROUP || LINTS.iter().any(|lint| lint.name == name)
+}
+
+/// The lint levels set by the attributes of an item or a module, which apply to the code within
+/// `span`.
+#[derive(Debug, Clone)]
+pub struct LintScope {
+    pub span: Span,
+    /// The lints by name, in the order of the attributes.
+    pub levels: Vec<(Ident, LintLevel)>,
+}
+
+/// The lint levels which apply to a program.
+pub(crate) struct LintLevels<'a> {
+    config: BTreeMap<String, LintLevel>,
+    scopes: &'a [LintScope],
+}
+
+impl<'a> LintLevels<'a> {
+    pub(crate) fn new(scopes: &'a [LintScope], build_config: Option<&BuildConfig>) -> Self {
+        let mut config = BTreeMap::new();
+        if let Some(build_config) = build_config {
+            config.extend(build_config.lints.clone());
+            if build_config.deny_warnings {
+                config.insert(WARNINGS_GROUP.to_string(), LintLevel::Deny);
+            }
+        }
+        LintLevels { config, scopes }
+    }
+
+    /// Drops the warnings whose lint is allowed, and turns those whose lint is denied into errors.
+    pub(crate) fn apply(
+        &self,
+        warnings: Vec<CompileWarning>,
+        errors: &mut Vec<CompileError>,
+    ) -> Vec<CompileWarning> {
+        let mut reported = Vec::new();
+        for warning in warnings {
+            match self.level(&warning) {
+                LintLevel::Allow => (),
+                LintLevel::Warn => reported.push(warning),
+                LintLevel::Deny => errors.push(CompileError::DeniedLint {
+                    lint: warning.warning_content.lint(),
+                    warning: warning.warning_content,
+                    span: warning.span,
+                }),
+            }
+        }
+        reported
+    }
+
+    /// The level of the lint of the given warning. The attributes of the innermost item take
+    /// precedence over those of the items and module around it, which take precedence over the
+    /// levels of the build configuration.
+    fn level(&self, warning: &CompileWarning) -> LintLevel {
+        let lint = warning.warning_content.lint();
+        self.scopes
+            .iter()
+            .filter(|scope| contains(&scope.span, &warning.span))
+            .filter_map(|scope| {
+                let levels = scope
+                    .levels
+                    .iter()
+                    .map(|(name, level)| (name.as_str(), *level));
+                Some((scope, level_of(levels, lint)?))
+            })
+            .min_by_key(|(scope, _)| scope.span.end() - scope.span.start())
+            .map(|(_, level)| level)
+            .or_else(|| {
+                let levels = self
+                    .config
+                    .iter()
+                    .map(|(name, level)| (name.as_str(), *level));
+                level_of(levels, lint)
+            })
+            .unwrap_or(LintLevel::Warn)
+    }
+}
+
+/// The level which the given lint levels set for `lint`. A level set for the lint itself takes
+/// precedence over one set for the [WARNINGS_GROUP], and a later level over an earlier one.
+fn level_of<'a>(
+    levels: impl Iterator<Item = (&'a str, LintLevel)> + Clone,
+    lint: &str,
+) -> Option<LintLevel> {
+    let last_level_for = |name: &str| {
+        levels
+            .clone()
+            .filter(|(level_name, _)| *level_name == name)
+            .last()
+            .map(|(_, level)| level)
+    };
+    last_level_for(lint).or_else(|| last_level_for(WARNINGS_GROUP))
+}
+
+fn contains(outer: &Span, inner: &Span) -> bool {
+    Arc::ptr_eq(outer.src(), inner.src())
+        && outer.start() <= inner.start()
+        && inner.end() <= outer.end()
+}
+
+/// The lint levels set by the attributes of the program and all of its submodules.
+pub(crate) fn program_scopes(program: &ParseProgram) -> Vec<LintScope> {
+    let mut scopes = Vec::new();
+    collect_scopes(&program.root, &mut scopes);
+    scopes
+}
+
+fn collect_scopes(module: &ParseModule, scopes: &mut Vec<LintScope>) {
+    scopes.extend(module.tree.lint_scopes.iter().cloned());
+    for (_, submodule) in &module.submodules {
+        collect_scopes(&submodule.module, scopes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Warning;
+    use std::{ops::Range, path::PathBuf};
+
+    const SRC: &str = "fn outer() { fn inner() { let a = 1; } let b = 2; } fn other() {}";
+
+    fn span(src: &Arc<str>, range: Range<usize>) -> Span {
+        Span::new(src.clone(), range.start, range.end, None).unwrap()
+    }
+
+    fn scope(
+        src: &Arc<str>,
+        range: Range<usize>,
+        levels: &[(&'static str, LintLevel)],
+    ) -> LintScope {
+        LintScope {
+            span: span(src, range),
+            levels: levels
+                .iter()
+                .map(|(name, level)| (Ident::new_no_span(*name), *level))
+                .collect(),
+        }
+    }
+
+    fn warning(src: &Arc<str>, range: Range<usize>, warning_content: Warning) -> CompileWarning {
+        CompileWarning {
+            span: span(src, range),
+            warning_content,
+        }
+    }
+
+    fn build_config(lints: &[(&str, LintLevel)], deny_warnings: bool) -> BuildConfig {
+        BuildConfig::root_from_file_name_and_manifest_path(
+            PathBuf::from("/project/src/main.sw"),
+            PathBuf::from("/project"),
+        )
+        .lints(
+            lints
+                .iter()
+                .map(|(name, level)| (name.to_string(), *level))
+                .collect(),
+        )
+        .deny_warnings(deny_warnings)
+    }
+
+    #[test]
+    fn defaults_to_warn() {
+        let src: Arc<str> = Arc::from(SRC);
+        let levels = LintLevels::new(&[], None);
+        let dead = warning(&src, 3..8, Warning::DeadFunctionDeclaration);
+        assert_eq!(levels.level(&dead), LintLevel::Warn);
+    }
+
+    #[test]
+    fn inner_scope_takes_precedence_over_outer_scope() {
+        let src: Arc<str> = Arc::from(SRC);
+        let scopes = [
+            scope(&src, 0..51, &[("dead_code", LintLevel::Deny)]),
+            scope(&src, 13..38, &[("dead_code", LintLevel::Allow)]),
+        ];
+        let levels = LintLevels::new(&scopes, None);
+        let in_inner = warning(&src, 30..31, Warning::DeadDeclaration);
+        let in_outer = warning(&src, 43..44, Warning::DeadDeclaration);
+        let outside = warning(&src, 55..60, Warning::DeadFunctionDeclaration);
+        assert_eq!(levels.level(&in_inner), LintLevel::Allow);
+        assert_eq!(levels.level(&in_outer), LintLevel::Deny);
+        assert_eq!(levels.level(&outside), LintLevel::Warn);
+    }
+
+    #[test]
+    fn scope_without_the_lint_defers_to_outer_scope() {
+        let src: Arc<str> = Arc::from(SRC);
+        let scopes = [
+            scope(&src, 0..51, &[("dead_code", LintLevel::Deny)]),
+            scope(&src, 13..38, &[("unreachable_code", LintLevel::Allow)]),
+        ];
+        let levels = LintLevels::new(&scopes, None);
+        let dead = warning(&src, 30..31, Warning::DeadDeclaration);
+        let unreachable = warning(&src, 30..31, Warning::UnreachableCode);
+        assert_eq!(levels.level(&dead), LintLevel::Deny);
+        assert_eq!(levels.level(&unreachable), LintLevel::Allow);
+    }
+
+    #[test]
+    fn lint_takes_precedence_over_group_and_later_over_earlier() {
+        let src: Arc<str> = Arc::from(SRC);
+        let scopes = [scope(
+            &src,
+            0..51,
+            &[
+                ("dead_code", LintLevel::Allow),
+                ("warnings", LintLevel::Deny),
+                ("unreachable_code", LintLevel::Allow),
+                ("unreachable_code", LintLevel::Warn),
+            ],
+        )];
+        let levels = LintLevels::new(&scopes, None);
+        let dead = warning(&src, 30..31, Warning::DeadDeclaration);
+        let unreachable = warning(&src, 30..31, Warning::UnreachableCode);
+        let overriding = warning(&src, 30..31, Warning::OverridingTraitImplementation);
+        assert_eq!(levels.level(&dead), LintLevel::Allow);
+        assert_eq!(levels.level(&unreachable), LintLevel::Warn);
+        assert_eq!(levels.level(&overriding), LintLevel::Deny);
+    }
+
+    #[test]
+    fn attributes_take_precedence_over_build_config() {
+        let src: Arc<str> = Arc::from(SRC);
+        let scopes = [scope(&src, 13..38, &[("dead_code", LintLevel::Warn)])];
+        let config = build_config(&[("unreachable_code", LintLevel::Allow)], true);
+        let levels = LintLevels::new(&scopes, Some(&config));
+        let in_scope = warning(&src, 30..31, Warning::DeadDeclaration);
+        let out_of_scope = warning(&src, 43..44, Warning::DeadDeclaration);
+        let unreachable = warning(&src, 43..44, Warning::UnreachableCode);
+        assert_eq!(levels.level(&in_scope), LintLevel::Warn);
+        assert_eq!(levels.level(&out_of_scope), LintLevel::Deny);
+        assert_eq!(levels.level(&unreachable), LintLevel::Allow);
+    }
+
+    #[test]
+    fn scopes_of_other_files_dont_apply() {
+        let src: Arc<str> = Arc::from(SRC);
+        let other_src: Arc<str> = Arc::from(SRC);
+        let scopes = [scope(&other_src, 0..51, &[("dead_code", LintLevel::Deny)])];
+        let levels = LintLevels::new(&scopes, None);
+        let dead = warning(&src, 30..31, Warning::DeadDeclaration);
+        assert_eq!(levels.level(&dead), LintLevel::Warn);
+    }
+}
@@ -107,6 +107,32 @@ impl MetadataManager {
         })
     }
 
+    /// The name and the type, as written in the source, of the local variable declared by an
+    /// instruction, usually the store of its initial value, or of a function argument.  Local
+    /// variables also have the source offset at which the block they're declared in ends.
+    pub(crate) fn md_to_var_decl(
+        &mut self,
+        context: &Context,
+        md_idx: Option<MetadataIndex>,
+    ) -> Option<(String, String, Option<usize>)> {
+        Self::for_each_md_idx(context, md_idx, |md_idx| {
+            let fields = match &context.metadata[md_idx.0] {
+                Metadatum::Struct(tag, fields) if tag == "var_decl" => fields,
+                _otherwise => return None,
+            };
+            let (name, ty, scope_end) = match fields.as_slice() {
+                [name, ty] => (name, ty, None),
+                [name, ty, scope_end] => (name, ty, Some(scope_end.unwrap_integer()? as usize)),
+                _otherwise => return None,
+            };
+            Some((
+                name.unwrap_string()?.to_owned(),
+                ty.unwrap_string()?.to_owned(),
+                scope_end,
+            ))
+        })
+    }
+
     fn md_to_file_location(
         &mut self,
         context: &Context,
@@ -138,6 +164,14 @@ impl MetadataManager {
         self.md_to_storage_key(context, context.values[value.0].metadata)
     }
 
+    pub(crate) fn val_to_var_decl(
+        &mut self,
+        context: &Context,
+        value: Value,
+    ) -> Option<(String, String, Option<usize>)> {
+        self.md_to_var_decl(context, context.values[value.0].metadata)
+    }
+
     pub(crate) fn span_to_md(
         &mut self,
         context: &mut Context,
@@ -184,6 +218,26 @@ impl MetadataManager {
             })
     }
 
+    pub(crate) fn var_decl_to_md(
+        &mut self,
+        context: &mut Context,
+        name: &str,
+        ty: &str,
+        scope_end: Option<usize>,
+    ) -> Option<MetadataIndex> {
+        // Every declaration is unique so there's nothing worth caching.
+        let mut fields = vec![
+            Metadatum::String(name.to_owned()),
+            Metadatum::String(ty.to_owned()),
+        ];
+        fields.extend(scope_end.map(|end| Metadatum::Integer(end as u64)));
+        Some(MetadataIndex(
+            context
+                .metadata
+                .insert(Metadatum::Struct("var_decl".to_owned(), fields)),
+        ))
+    }
+
     pub(crate) fn purity_to_md(
         &mut self,
         context: &mut Context,
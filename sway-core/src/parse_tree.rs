@@ -12,6 +12,7 @@ mod use_statement;
 mod visibility;
 mod while_loop;
 
+use crate::lint::LintScope;
 pub use call_path::*;
 pub use code_block::*;
 pub use declaration::*;
@@ -34,6 +35,11 @@ pub struct ParseTree {
     pub root_nodes: Vec<AstNode>,
     /// The [Span] of the entire tree.
     pub span: Span,
+    /// The lint levels set by the attributes of the module and its items.
+    pub lint_scopes: Vec<LintScope>,
+    /// The spans of the items and statements which the parser skipped over to recover from
+    /// syntax errors, and which are missing from the tree.
+    pub error_spans: Vec<Span>,
 }
 
 /// A single [AstNode] represents a node in the parse tree. Note that [AstNode]
@@ -1,18 +1,23 @@
-use super::FunctionDeclaration;
+use super::{ConstantDeclaration, FunctionDeclaration};
 use crate::{
     parse_tree::CallPath,
-    type_engine::{TypeInfo, TypeParameter},
+    type_engine::{TypeArgument, TypeInfo, TypeParameter},
 };
 
-use sway_types::span::Span;
+use sway_types::{ident::Ident, span::Span};
 
 #[derive(Debug, Clone)]
 pub struct ImplTrait {
     pub trait_name: CallPath,
+    /// The type arguments given to the trait, e.g. `u64` in `impl From<u64> for Foo`.
+    pub(crate) trait_type_arguments: Vec<TypeArgument>,
     pub(crate) type_implementing_for: TypeInfo,
     pub(crate) type_implementing_for_span: Span,
     pub(crate) type_parameters: Vec<TypeParameter>,
     pub functions: Vec<FunctionDeclaration>,
+    /// The definitions of the trait's associated types, e.g. `type Item = u64;`.
+    pub(crate) associated_types: Vec<(Ident, TypeArgument)>,
+    pub(crate) associated_consts: Vec<ConstantDeclaration>,
     // the span of the whole impl trait and block
     pub(crate) block_span: Span,
 }
@@ -25,6 +30,7 @@ pub struct ImplSelf {
     pub(crate) type_implementing_for_span: Span,
     pub(crate) type_parameters: Vec<TypeParameter>,
     pub functions: Vec<FunctionDeclaration>,
+    pub(crate) associated_consts: Vec<ConstantDeclaration>,
     // the span of the whole impl trait and block
     pub(crate) block_span: Span,
 }
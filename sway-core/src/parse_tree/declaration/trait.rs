@@ -3,7 +3,7 @@ use super::{FunctionDeclaration, FunctionParameter};
 use crate::{
     function::Purity,
     parse_tree::{CallPath, Visibility},
-    type_engine::TypeInfo,
+    type_engine::{TypeInfo, TypeParameter},
 };
 
 use sway_types::{ident::Ident, span::Span};
@@ -11,6 +11,9 @@ use sway_types::{ident::Ident, span::Span};
 #[derive(Debug, Clone)]
 pub struct TraitDeclaration {
     pub name: Ident,
+    pub(crate) type_parameters: Vec<TypeParameter>,
+    pub(crate) associated_types: Vec<Ident>,
+    pub(crate) associated_consts: Vec<TraitConstant>,
     pub interface_surface: Vec<TraitFn>,
     pub methods: Vec<FunctionDeclaration>,
     pub(crate) supertraits: Vec<Supertrait>,
@@ -30,3 +33,12 @@ pub struct TraitFn {
     pub return_type: TypeInfo,
     pub(crate) return_type_span: Span,
 }
+
+/// An associated constant declared in a trait, e.g. `const ID: u64;`. Each implementation of the
+/// trait must provide a value for it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraitConstant {
+    pub name: Ident,
+    pub type_ascription: TypeInfo,
+    pub(crate) type_span: Span,
+}
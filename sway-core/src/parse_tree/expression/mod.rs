@@ -69,6 +69,7 @@ pub enum Expression {
     MatchExp {
         value: Box<Expression>,
         branches: Vec<MatchBranch>,
+        source: MatchSource,
         span: Span,
     },
     // separated into other struct for parsing reasons
@@ -146,6 +147,19 @@ pub enum LazyOp {
     Or,
 }
 
+/// The construct which a [Expression::MatchExp] was written as, or desugared from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchSource {
+    /// A `match` expression.
+    Match,
+    /// An `if let` expression.
+    IfLet,
+    /// A `while let` loop.
+    WhileLet,
+    /// A `let` statement or function parameter with a destructuring pattern.
+    Let,
+}
+
 #[derive(Debug, Clone)]
 pub struct StructExpressionField {
     pub name: Ident,
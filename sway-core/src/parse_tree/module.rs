@@ -1,5 +1,5 @@
 use super::ParseTree;
-use sway_types::Ident;
+use sway_types::{Ident, Span};
 
 /// A module and its submodules in the form of a tree.
 #[derive(Debug)]
@@ -10,6 +10,18 @@ pub struct ParseModule {
     pub submodules: Vec<(DepName, ParseSubmodule)>,
 }
 
+impl ParseModule {
+    /// The spans of the broken items and statements which the parser skipped over within this
+    /// module and its submodules.
+    pub fn error_spans(&self) -> Vec<&Span> {
+        let mut spans = self.tree.error_spans.iter().collect::<Vec<_>>();
+        for (_, submodule) in &self.submodules {
+            spans.extend(submodule.module.error_spans());
+        }
+        spans
+    }
+}
+
 /// The name used within a module to refer to one of its submodules.
 ///
 /// If an alias was given to the `dep`, this will be the alias. If not, this is the submodule's
@@ -332,14 +332,8 @@ impl TypedDeclaration {
             TypedDeclaration::VariableDeclaration(TypedVariableDeclaration { body, .. }) => {
                 body.return_type
             }
-            TypedDeclaration::FunctionDeclaration { .. } => {
-                return err(
-                    vec![],
-                    vec![CompileError::Unimplemented(
-                        "Function pointers have not yet been implemented.",
-                        self.span(),
-                    )],
-                )
+            TypedDeclaration::FunctionDeclaration(decl) => {
+                return decl.fn_pointer_type(&decl.name.span())
             }
             TypedDeclaration::StructDeclaration(decl) => decl.create_type_id(),
             TypedDeclaration::EnumDeclaration(decl) => decl.create_type_id(),
@@ -415,6 +409,9 @@ pub struct TypedTraitFn {
 
 impl CopyTypes for TypedTraitFn {
     fn copy_types(&mut self, type_mapping: &TypeMapping) {
+        self.parameters
+            .iter_mut()
+            .for_each(|x| x.copy_types(type_mapping));
         self.return_type
             .update_type(type_mapping, &self.return_type_span);
     }
@@ -242,6 +242,48 @@ impl TypedFunctionDeclaration {
         }
     }
 
+    /// Returns the type of a pointer to this function, i.e. `fn(params..) -> return_type`.
+    ///
+    /// Only non-generic, pure functions may be referred to by pointer.
+    pub(crate) fn fn_pointer_type(&self, span: &Span) -> CompileResult<TypeId> {
+        let mut errors = vec![];
+        if !self.type_parameters.is_empty() {
+            errors.push(CompileError::FunctionPointerToGenericFunction {
+                name: self.name.clone(),
+                span: span.clone(),
+            });
+        }
+        if self.purity != Purity::Pure {
+            errors.push(CompileError::FunctionPointerToImpureFunction {
+                name: self.name.clone(),
+                span: span.clone(),
+            });
+        }
+        if !errors.is_empty() {
+            return err(vec![], errors);
+        }
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|parameter| TypeArgument {
+                type_id: parameter.type_id,
+                span: parameter.type_span.clone(),
+            })
+            .collect();
+        let return_type = TypeArgument {
+            type_id: self.return_type,
+            span: self.return_type_span.clone(),
+        };
+        ok(
+            insert_type(TypeInfo::Function {
+                parameters,
+                return_type,
+            }),
+            vec![],
+            vec![],
+        )
+    }
+
     pub fn to_fn_selector_value_untruncated(&self) -> CompileResult<Vec<u8>> {
         let mut errors = vec![];
         let mut warnings = vec![];
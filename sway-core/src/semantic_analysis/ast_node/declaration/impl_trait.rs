@@ -1,26 +1,29 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use sway_types::{Ident, Span, Spanned};
 
 use crate::{
     error::{err, ok},
     semantic_analysis::{
-        Mode, TypeCheckContext, TypedAstNodeContent, TypedExpression, TypedExpressionVariant,
-        TypedIntrinsicFunctionKind, TypedReturnStatement, TypedWhileLoop,
+        Mode, TypeCheckContext, TypedAstNodeContent, TypedConstantDeclaration, TypedExpression,
+        TypedExpressionVariant, TypedIntrinsicFunctionKind, TypedReturnStatement, TypedWhileLoop,
     },
+    style::is_screaming_snake_case,
     type_engine::{
         insert_type, look_up_type_id, resolve_type, set_type_as_storage_only, unify_with_self,
-        CopyTypes, TypeId, TypeMapping, TypeParameter,
+        CopyTypes, TypeArgument, TypeId, TypeMapping, TypeParameter,
     },
-    CallPath, CompileError, CompileResult, FunctionDeclaration, ImplSelf, ImplTrait, Purity,
-    TypeInfo, TypedDeclaration, TypedFunctionDeclaration,
+    CallPath, CompileError, CompileResult, ConstantDeclaration, FunctionDeclaration, ImplSelf,
+    ImplTrait, Namespace, Purity, TypeInfo, TypedDeclaration, TypedFunctionDeclaration,
 };
 
-use super::TypedTraitFn;
+use super::{EnforceTypeArguments, TypedTraitConstant, TypedTraitDeclaration, TypedTraitFn};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TypedImplTrait {
     pub trait_name: CallPath,
+    /// The type arguments of the trait, e.g. `u64` in `impl From<u64> for Foo`.
+    pub(crate) trait_type_arguments: Vec<TypeId>,
     pub(crate) span: Span,
     pub methods: Vec<TypedFunctionDeclaration>,
     pub implementing_for_type_id: TypeId,
@@ -28,6 +31,9 @@ pub struct TypedImplTrait {
 
 impl CopyTypes for TypedImplTrait {
     fn copy_types(&mut self, type_mapping: &TypeMapping) {
+        self.trait_type_arguments
+            .iter_mut()
+            .for_each(|x| x.update_type(type_mapping, &self.span));
         self.methods
             .iter_mut()
             .for_each(|x| x.copy_types(type_mapping));
@@ -44,8 +50,11 @@ impl TypedImplTrait {
 
         let ImplTrait {
             trait_name,
+            trait_type_arguments,
             type_parameters,
             functions,
+            associated_types,
+            associated_consts,
             type_implementing_for,
             type_implementing_for_span,
             block_span,
@@ -57,7 +66,6 @@ impl TypedImplTrait {
 
         // type check the type parameters
         // insert them into the namespace
-        // TODO: eventually when we support generic traits, we will want to use this
         let mut new_type_parameters = vec![];
         for type_parameter in type_parameters.into_iter() {
             new_type_parameters.push(check!(
@@ -93,7 +101,7 @@ impl TypedImplTrait {
         );
 
         // Update the context with the new `self` type.
-        let ctx = ctx.with_self_type(implementing_for_type_id);
+        let mut ctx = ctx.with_self_type(implementing_for_type_id);
 
         let impl_trait = match ctx
             .namespace
@@ -102,13 +110,72 @@ impl TypedImplTrait {
             .cloned()
         {
             Some(TypedDeclaration::TraitDeclaration(tr)) => {
+                // determine the types this impl chooses for the trait's type parameters and
+                // associated types
+                let (type_parameter_args, associated_type_args) = check!(
+                    type_check_trait_type_arguments(
+                        ctx.by_ref(),
+                        &tr,
+                        &trait_name,
+                        trait_type_arguments,
+                        associated_types,
+                        &block_span
+                    ),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                );
+
+                // monomorphize the interface surface of the trait for this impl, so that e.g.
+                // `impl From<u64> for Foo` and `impl From<b256> for Foo` are each checked
+                // against their own instantiation of `From<T>`
+                let type_mapping = type_parameter_args
+                    .iter()
+                    .chain(associated_type_args.iter())
+                    .map(|(type_parameter, type_id)| (type_parameter.type_id, *type_id))
+                    .collect::<TypeMapping>();
+                let mut interface_surface = tr.interface_surface.clone();
+                interface_surface
+                    .iter_mut()
+                    .for_each(|x| x.copy_types(&type_mapping));
+
+                // the associated types and constants are available to the methods of the impl
+                for (associated_type, type_id) in associated_type_args.iter() {
+                    insert_type_alias(ctx.namespace, associated_type.name_ident.clone(), *type_id);
+                }
+                let typed_consts = check!(
+                    type_check_trait_associated_consts(
+                        ctx.by_ref(),
+                        &tr.associated_consts,
+                        associated_consts,
+                        &type_mapping,
+                        &trait_name,
+                        &block_span
+                    ),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                );
+                for decl in typed_consts.into_iter() {
+                    ctx.namespace.insert_associated_const(decl);
+                }
+
+                let trait_type_arguments = type_parameter_args
+                    .iter()
+                    .map(|(_, type_id)| *type_id)
+                    .collect::<Vec<_>>();
+                let type_parameter_aliases = type_parameter_args
+                    .into_iter()
+                    .map(|(type_parameter, type_id)| (type_parameter.name_ident, type_id))
+                    .collect::<Vec<_>>();
                 let functions_buf = check!(
                     type_check_trait_implementation(
                         ctx,
-                        &tr.interface_surface,
+                        &interface_surface,
                         &tr.methods,
                         &functions,
                         &trait_name,
+                        &type_parameter_aliases,
                         &type_implementing_for_span,
                         &block_span,
                     ),
@@ -118,6 +185,7 @@ impl TypedImplTrait {
                 );
                 let impl_trait = TypedImplTrait {
                     trait_name,
+                    trait_type_arguments,
                     span: block_span,
                     methods: functions_buf,
                     implementing_for_type_id,
@@ -145,6 +213,24 @@ impl TypedImplTrait {
                     });
                 }
 
+                if let Some(type_argument) = trait_type_arguments.first() {
+                    errors.push(CompileError::DoesNotTakeTypeArguments {
+                        name: trait_name.suffix.clone(),
+                        span: type_argument.span.clone(),
+                    });
+                }
+                let associated_item_names = associated_types
+                    .iter()
+                    .map(|(name, _)| name)
+                    .chain(associated_consts.iter().map(|decl| &decl.name));
+                for name in associated_item_names {
+                    errors.push(CompileError::AssociatedItemNotAPartOfTrait {
+                        name: name.clone(),
+                        trait_name: trait_name.suffix.clone(),
+                        span: name.span(),
+                    });
+                }
+
                 let ctx = ctx.with_mode(Mode::ImplAbiFn);
 
                 let functions_buf = check!(
@@ -154,6 +240,7 @@ impl TypedImplTrait {
                         &abi.methods,
                         &functions,
                         &trait_name,
+                        &[],
                         &type_implementing_for_span,
                         &block_span,
                     ),
@@ -163,6 +250,7 @@ impl TypedImplTrait {
                 );
                 let impl_trait = TypedImplTrait {
                     trait_name,
+                    trait_type_arguments: vec![],
                     span: block_span,
                     methods: functions_buf,
                     implementing_for_type_id,
@@ -207,7 +295,8 @@ impl TypedImplTrait {
                 | TypedExpressionVariant::FunctionParameter
                 | TypedExpressionVariant::AsmExpression { .. }
                 | TypedExpressionVariant::StorageAccess(_)
-                | TypedExpressionVariant::AbiName(_) => false,
+                | TypedExpressionVariant::AbiName(_)
+                | TypedExpressionVariant::FunctionReference { .. } => false,
                 TypedExpressionVariant::FunctionApplication { arguments, .. } => arguments
                     .iter()
                     .any(|f| expr_contains_get_storage_index(&f.1)),
@@ -253,6 +342,10 @@ impl TypedImplTrait {
                 TypedExpressionVariant::EnumInstantiation { contents, .. } => contents
                     .as_ref()
                     .map_or(false, |f| expr_contains_get_storage_index(&*f)),
+                TypedExpressionVariant::IndirectFunctionApplication { callee, arguments } => {
+                    expr_contains_get_storage_index(&*callee)
+                        || arguments.iter().any(expr_contains_get_storage_index)
+                }
 
                 TypedExpressionVariant::IntrinsicFunction(TypedIntrinsicFunctionKind {
                     kind,
@@ -313,6 +406,7 @@ impl TypedImplTrait {
             type_implementing_for_span,
             type_parameters,
             functions,
+            associated_consts,
             block_span,
         } = impl_self;
 
@@ -371,6 +465,17 @@ impl TypedImplTrait {
             .with_help_text("")
             .with_type_annotation(insert_type(TypeInfo::Unknown));
 
+        // type check the associated constants, making them available to the methods
+        for decl in associated_consts.into_iter() {
+            let decl = check!(
+                type_check_associated_const(ctx.by_ref(), decl, insert_type(TypeInfo::Unknown)),
+                continue,
+                warnings,
+                errors
+            );
+            ctx.namespace.insert_associated_const(decl);
+        }
+
         // type check the methods inside of the impl block
         let mut methods = vec![];
         for fn_decl in functions.into_iter() {
@@ -386,6 +491,7 @@ impl TypedImplTrait {
 
         let impl_trait = TypedImplTrait {
             trait_name,
+            trait_type_arguments: vec![],
             span: block_span,
             methods,
             implementing_for_type_id,
@@ -401,6 +507,7 @@ fn type_check_trait_implementation(
     trait_methods: &[FunctionDeclaration],
     functions: &[FunctionDeclaration],
     trait_name: &CallPath,
+    type_parameter_aliases: &[(Ident, TypeId)],
     self_type_span: &Span,
     block_span: &Span,
 ) -> CompileResult<Vec<TypedFunctionDeclaration>> {
@@ -552,6 +659,12 @@ fn type_check_trait_implementation(
     .concat();
     ctx.namespace.star_import(&trait_path);
 
+    // The methods of the trait refer to its type parameters by the names used in the trait
+    // declaration, so make those names refer to the type arguments of this impl.
+    for (name, type_id) in type_parameter_aliases {
+        insert_type_alias(ctx.namespace, name.clone(), *type_id);
+    }
+
     let self_type_id = insert_type(match resolve_type(ctx.self_type(), self_type_span) {
         Ok(o) => o,
         Err(e) => {
@@ -565,6 +678,10 @@ fn type_check_trait_implementation(
             suffix: trait_name.suffix.clone(),
             is_absolute: false,
         },
+        type_parameter_aliases
+            .iter()
+            .map(|(_, type_id)| *type_id)
+            .collect(),
         self_type_id,
         functions_buf.clone(),
     );
@@ -601,6 +718,261 @@ fn type_check_trait_implementation(
     ok(functions_buf, warnings, errors)
 }
 
+/// Type checks the type arguments of an impl's trait, e.g. `u64` in `impl From<u64> for Foo`, and
+/// its definitions of the trait's associated types, e.g. `type Item = u64;`.
+///
+/// Returns the type chosen by the impl for each of the trait's type parameters and associated
+/// types.
+#[allow(clippy::type_complexity)]
+fn type_check_trait_type_arguments(
+    mut ctx: TypeCheckContext,
+    trait_decl: &TypedTraitDeclaration,
+    trait_name: &CallPath,
+    mut trait_type_arguments: Vec<TypeArgument>,
+    associated_types: Vec<(Ident, TypeArgument)>,
+    block_span: &Span,
+) -> CompileResult<(Vec<(TypeParameter, TypeId)>, Vec<(TypeParameter, TypeId)>)> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    let expected = trait_decl.type_parameters.len();
+    let given = trait_type_arguments.len();
+    if expected == 0 && given > 0 {
+        errors.push(CompileError::DoesNotTakeTypeArguments {
+            name: trait_name.suffix.clone(),
+            span: trait_name.span(),
+        });
+        return err(warnings, errors);
+    } else if expected != given {
+        errors.push(CompileError::IncorrectNumberOfTypeArguments {
+            given,
+            expected,
+            span: trait_name.span(),
+        });
+        return err(warnings, errors);
+    }
+    for type_argument in trait_type_arguments.iter_mut() {
+        type_argument.type_id = check!(
+            ctx.resolve_type_with_self(
+                type_argument.type_id,
+                &type_argument.span,
+                EnforceTypeArguments::Yes,
+                None
+            ),
+            insert_type(TypeInfo::ErrorRecovery),
+            warnings,
+            errors
+        );
+    }
+    let type_parameter_args = trait_decl
+        .type_parameters
+        .iter()
+        .cloned()
+        .zip(trait_type_arguments.into_iter().map(|arg| arg.type_id))
+        .collect::<Vec<_>>();
+
+    // this map keeps track of the associated types that still need to be defined
+    let mut associated_type_checklist: BTreeMap<&Ident, &TypeParameter> = trait_decl
+        .associated_types
+        .iter()
+        .map(|type_parameter| (&type_parameter.name_ident, type_parameter))
+        .collect();
+    let mut associated_type_args = vec![];
+    for (name, type_argument) in associated_types.into_iter() {
+        let type_parameter = match associated_type_checklist.remove(&name) {
+            Some(type_parameter) => type_parameter.clone(),
+            None => {
+                errors.push(associated_item_error(
+                    &name,
+                    trait_decl
+                        .associated_types
+                        .iter()
+                        .any(|type_parameter| type_parameter.name_ident == name),
+                    trait_name,
+                ));
+                continue;
+            }
+        };
+        let type_id = check!(
+            ctx.resolve_type_with_self(
+                type_argument.type_id,
+                &type_argument.span,
+                EnforceTypeArguments::Yes,
+                None
+            ),
+            insert_type(TypeInfo::ErrorRecovery),
+            warnings,
+            errors
+        );
+        associated_type_args.push((type_parameter, type_id));
+    }
+    if !associated_type_checklist.is_empty() {
+        errors.push(CompileError::MissingAssociatedItems {
+            missing_items: associated_type_checklist
+                .into_keys()
+                .map(|name| format!("type {}", name))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            span: block_span.clone(),
+        });
+    }
+
+    if errors.is_empty() {
+        ok(
+            (type_parameter_args, associated_type_args),
+            warnings,
+            errors,
+        )
+    } else {
+        err(warnings, errors)
+    }
+}
+
+/// Type checks the values an impl gives to the associated constants of its trait, which must
+/// have the types declared in the trait after monomorphization with `type_mapping`.
+fn type_check_trait_associated_consts(
+    mut ctx: TypeCheckContext,
+    trait_consts: &[TypedTraitConstant],
+    consts: Vec<ConstantDeclaration>,
+    type_mapping: &TypeMapping,
+    trait_name: &CallPath,
+    block_span: &Span,
+) -> CompileResult<Vec<TypedConstantDeclaration>> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    // this map keeps track of the constants that still need to be given a value
+    let mut const_checklist: BTreeMap<&Ident, &TypedTraitConstant> = trait_consts
+        .iter()
+        .map(|trait_const| (&trait_const.name, trait_const))
+        .collect();
+    let mut typed_consts = vec![];
+    for decl in consts.into_iter() {
+        let mut trait_const = match const_checklist.remove(&decl.name) {
+            Some(trait_const) => trait_const.clone(),
+            None => {
+                errors.push(associated_item_error(
+                    &decl.name,
+                    trait_consts
+                        .iter()
+                        .any(|trait_const| trait_const.name == decl.name),
+                    trait_name,
+                ));
+                continue;
+            }
+        };
+        trait_const.copy_types(type_mapping);
+        let decl = check!(
+            type_check_associated_const(ctx.by_ref(), decl, trait_const.type_id),
+            continue,
+            warnings,
+            errors
+        );
+        let (mut new_warnings, new_errors) = unify_with_self(
+            decl.value.return_type,
+            trait_const.type_id,
+            ctx.self_type(),
+            &decl.value.span,
+            ctx.help_text(),
+        );
+        warnings.append(&mut new_warnings);
+        if !new_errors.is_empty() {
+            errors.push(CompileError::MismatchedTypeInTrait {
+                span: decl.value.span.clone(),
+                given: decl.value.return_type.to_string(),
+                expected: trait_const.type_id.to_string(),
+            });
+            continue;
+        }
+        typed_consts.push(decl);
+    }
+    if !const_checklist.is_empty() {
+        errors.push(CompileError::MissingAssociatedItems {
+            missing_items: const_checklist
+                .into_keys()
+                .map(|name| format!("const {}", name))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            span: block_span.clone(),
+        });
+    }
+    ok(typed_consts, warnings, errors)
+}
+
+/// Type checks an associated constant of an impl. Unless the constant has its own type
+/// ascription, its value is expected to be of type `expected`.
+fn type_check_associated_const(
+    mut ctx: TypeCheckContext,
+    decl: ConstantDeclaration,
+    expected: TypeId,
+) -> CompileResult<TypedConstantDeclaration> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    let ConstantDeclaration {
+        name,
+        type_ascription,
+        value,
+        visibility,
+    } = decl;
+    is_screaming_snake_case(&name).ok(&mut warnings, &mut errors);
+    let type_annotation = match type_ascription {
+        TypeInfo::Unknown => expected,
+        type_ascription => check!(
+            ctx.resolve_type_with_self(
+                insert_type(type_ascription),
+                &name.span(),
+                EnforceTypeArguments::No,
+                None
+            ),
+            insert_type(TypeInfo::ErrorRecovery),
+            warnings,
+            errors
+        ),
+    };
+    let ctx = ctx.with_type_annotation(type_annotation).with_help_text(
+        "This declaration's type annotation does not match up with the assigned expression's \
+            type.",
+    );
+    let value = check!(
+        TypedExpression::type_check(ctx, value),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+    let decl = TypedConstantDeclaration {
+        name,
+        value,
+        visibility,
+    };
+    ok(decl, warnings, errors)
+}
+
+fn associated_item_error(name: &Ident, is_duplicate: bool, trait_name: &CallPath) -> CompileError {
+    if is_duplicate {
+        CompileError::MultipleDefinitionsOfAssociatedItem {
+            name: name.clone(),
+            span: name.span(),
+        }
+    } else {
+        CompileError::AssociatedItemNotAPartOfTrait {
+            name: name.clone(),
+            trait_name: trait_name.suffix.clone(),
+            span: name.span(),
+        }
+    }
+}
+
+/// Makes `type_id` available under `name`, in the same way as a type parameter.
+fn insert_type_alias(namespace: &mut Namespace, name: Ident, type_id: TypeId) {
+    // An alias for a type parameter of the trait may shadow a type parameter of the impl with the
+    // same name, which is intended, so the shadowing diagnostics are dropped.
+    let _ = namespace.insert_symbol(
+        name.clone(),
+        TypedDeclaration::GenericTypeForFunctionScope { name, type_id },
+    );
+}
+
 fn check_for_unconstrained_type_parameters(
     type_parameters: &[TypeParameter],
     self_type: TypeId,
@@ -1,16 +1,18 @@
 use derivative::Derivative;
-use sway_types::{Ident, Spanned};
+use sway_types::{Ident, Span, Spanned};
 
 use crate::{
     error::{err, ok},
     semantic_analysis::{
         ast_node::{type_check_interface_surface, type_check_trait_methods},
-        Mode, TypeCheckContext, TypedCodeBlock,
+        IsConstant, Mode, TypeCheckContext, TypedCodeBlock, TypedConstantDeclaration,
+        TypedExpression, TypedExpressionVariant,
     },
-    style::is_upper_camel_case,
-    type_engine::{insert_type, CopyTypes, TypeMapping},
+    style::{is_screaming_snake_case, is_upper_camel_case},
+    type_engine::{insert_type, CopyTypes, TypeId, TypeMapping, TypeParameter},
     CallPath, CompileError, CompileResult, FunctionDeclaration, FunctionParameter, Namespace,
-    Supertrait, TraitDeclaration, TypeInfo, TypedDeclaration, TypedFunctionDeclaration, Visibility,
+    Supertrait, TraitConstant, TraitDeclaration, TypeInfo, TypedDeclaration,
+    TypedFunctionDeclaration, Visibility,
 };
 
 use super::{EnforceTypeArguments, TypedFunctionParameter, TypedTraitFn};
@@ -19,6 +21,11 @@ use super::{EnforceTypeArguments, TypedFunctionParameter, TypedTraitFn};
 #[derivative(PartialEq, Eq)]
 pub struct TypedTraitDeclaration {
     pub name: Ident,
+    pub(crate) type_parameters: Vec<TypeParameter>,
+    /// Associated types are checked like type parameters of the trait, except that every impl
+    /// chooses them in its body rather than in its header.
+    pub(crate) associated_types: Vec<TypeParameter>,
+    pub(crate) associated_consts: Vec<TypedTraitConstant>,
     pub interface_surface: Vec<TypedTraitFn>,
     // NOTE: deriving partialeq and hash on this element may be important in the
     // future, but I am not sure. For now, adding this would 2x the amount of
@@ -30,8 +37,24 @@ pub struct TypedTraitDeclaration {
     pub visibility: Visibility,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedTraitConstant {
+    pub name: Ident,
+    pub type_id: TypeId,
+    pub(crate) type_span: Span,
+}
+
+impl CopyTypes for TypedTraitConstant {
+    fn copy_types(&mut self, type_mapping: &TypeMapping) {
+        self.type_id.update_type(type_mapping, &self.type_span);
+    }
+}
+
 impl CopyTypes for TypedTraitDeclaration {
     fn copy_types(&mut self, type_mapping: &TypeMapping) {
+        self.associated_consts
+            .iter_mut()
+            .for_each(|x| x.copy_types(type_mapping));
         self.interface_surface
             .iter_mut()
             .for_each(|x| x.copy_types(type_mapping));
@@ -49,6 +72,81 @@ impl TypedTraitDeclaration {
 
         is_upper_camel_case(&trait_decl.name).ok(&mut warnings, &mut errors);
 
+        // A temporary namespace for checking within the trait's scope.
+        let mut trait_namespace = ctx.namespace.clone();
+        let mut ctx = ctx.scoped(&mut trait_namespace);
+
+        // type check the type parameters and the associated types
+        // insert them into the namespace
+        let mut type_parameters = vec![];
+        for type_parameter in trait_decl.type_parameters.into_iter() {
+            type_parameters.push(check!(
+                TypeParameter::type_check(ctx.by_ref(), type_parameter),
+                return err(warnings, errors),
+                warnings,
+                errors
+            ));
+        }
+        let mut associated_types = vec![];
+        for name in trait_decl.associated_types.into_iter() {
+            let type_parameter = TypeParameter {
+                type_id: insert_type(TypeInfo::Custom {
+                    name: name.clone(),
+                    type_arguments: None,
+                }),
+                name_ident: name,
+                trait_constraints: vec![],
+            };
+            associated_types.push(check!(
+                TypeParameter::type_check(ctx.by_ref(), type_parameter),
+                return err(warnings, errors),
+                warnings,
+                errors
+            ));
+        }
+
+        // type check the associated constants, and insert placeholders for them so that the
+        // methods may refer to them
+        let mut associated_consts = vec![];
+        for TraitConstant {
+            name,
+            type_ascription,
+            type_span,
+        } in trait_decl.associated_consts.into_iter()
+        {
+            is_screaming_snake_case(&name).ok(&mut warnings, &mut errors);
+            let type_id = check!(
+                ctx.namespace.resolve_type_with_self(
+                    insert_type(type_ascription),
+                    insert_type(TypeInfo::SelfType),
+                    &type_span,
+                    EnforceTypeArguments::Yes,
+                    None
+                ),
+                insert_type(TypeInfo::ErrorRecovery),
+                warnings,
+                errors
+            );
+            ctx.namespace
+                .insert_associated_const(TypedConstantDeclaration {
+                    name: name.clone(),
+                    value: TypedExpression {
+                        expression: TypedExpressionVariant::VariableExpression {
+                            name: name.clone(),
+                        },
+                        return_type: type_id,
+                        is_constant: IsConstant::Yes,
+                        span: name.span(),
+                    },
+                    visibility: Visibility::Public,
+                });
+            associated_consts.push(TypedTraitConstant {
+                name,
+                type_id,
+                type_span,
+            });
+        }
+
         // type check the interface surface
         let interface_surface = check!(
             type_check_interface_surface(trait_decl.interface_surface.to_vec(), ctx.namespace),
@@ -57,10 +155,6 @@ impl TypedTraitDeclaration {
             errors
         );
 
-        // A temporary namespace for checking within the trait's scope.
-        let mut trait_namespace = ctx.namespace.clone();
-        let ctx = ctx.scoped(&mut trait_namespace);
-
         // Recursively handle supertraits: make their interfaces and methods available to this trait
         check!(
             handle_supertraits(&trait_decl.supertraits, ctx.namespace),
@@ -77,6 +171,7 @@ impl TypedTraitDeclaration {
                 suffix: trait_decl.name.clone(),
                 is_absolute: false,
             },
+            type_parameters.iter().map(|x| x.type_id).collect(),
             insert_type(TypeInfo::SelfType),
             interface_surface
                 .iter()
@@ -93,6 +188,9 @@ impl TypedTraitDeclaration {
         );
         let typed_trait_decl = TypedTraitDeclaration {
             name: trait_decl.name.clone(),
+            type_parameters,
+            associated_types,
+            associated_consts,
             interface_surface,
             methods: trait_decl.methods.to_vec(),
             supertraits: trait_decl.supertraits.to_vec(),
@@ -126,6 +224,7 @@ fn handle_supertraits(
                 // insert dummy versions of the interfaces for all of the supertraits
                 trait_namespace.insert_trait_implementation(
                     supertrait.name.clone(),
+                    vec![],
                     insert_type(TypeInfo::SelfType),
                     interface_surface
                         .iter()
@@ -142,6 +241,7 @@ fn handle_supertraits(
                 );
                 trait_namespace.insert_trait_implementation(
                     supertrait.name.clone(),
+                    vec![],
                     insert_type(TypeInfo::SelfType),
                     dummy_funcs,
                 );
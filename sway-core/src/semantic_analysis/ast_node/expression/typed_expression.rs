@@ -202,6 +202,25 @@ impl UnresolvedTypeCheck for TypedExpression {
                 res.append(&mut exp.check_for_unresolved_types());
                 res.append(&mut variant.type_id.check_for_unresolved_types());
             }
+            FunctionReference { function_decl, .. } => {
+                res.append(
+                    &mut function_decl
+                        .body
+                        .contents
+                        .iter()
+                        .flat_map(UnresolvedTypeCheck::check_for_unresolved_types)
+                        .collect(),
+                );
+            }
+            IndirectFunctionApplication { callee, arguments } => {
+                res.append(&mut callee.check_for_unresolved_types());
+                res.append(
+                    &mut arguments
+                        .iter()
+                        .flat_map(UnresolvedTypeCheck::check_for_unresolved_types)
+                        .collect(),
+                );
+            }
             // storage access can never be generic
             // variable expressions don't ever have return types themselves, they're stored in
             // `TypedExpression::return_type`. Variable expressions are just names of variables.
@@ -282,6 +301,13 @@ impl DeterministicallyAborts for TypedExpression {
             AbiName(_) => false,
             EnumTag { exp } => exp.deterministically_aborts(),
             UnsafeDowncast { exp, .. } => exp.deterministically_aborts(),
+            // the callee of an indirect call is only known at runtime, so only the evaluation of
+            // the callee and its arguments is considered
+            FunctionReference { .. } => false,
+            IndirectFunctionApplication { callee, arguments } => {
+                callee.deterministically_aborts()
+                    || arguments.iter().any(|x| x.deterministically_aborts())
+            }
         }
     }
 }
@@ -387,7 +413,9 @@ impl TypedExpression {
             | TypedExpressionVariant::StorageAccess { .. }
             | TypedExpressionVariant::FunctionApplication { .. }
             | TypedExpressionVariant::EnumTag { .. }
-            | TypedExpressionVariant::UnsafeDowncast { .. } => vec![],
+            | TypedExpressionVariant::UnsafeDowncast { .. }
+            | TypedExpressionVariant::FunctionReference { .. }
+            | TypedExpressionVariant::IndirectFunctionApplication { .. } => vec![],
         }
     }
 
@@ -434,11 +462,13 @@ impl TypedExpression {
             Expression::MatchExp {
                 value,
                 branches,
+                source,
                 span,
             } => Self::type_check_match_expression(
                 ctx.by_ref().with_help_text(""),
                 *value,
                 branches,
+                source,
                 span,
             ),
             Expression::AsmExpression { asm, span, .. } => {
@@ -593,6 +623,7 @@ impl TypedExpression {
         name: Ident,
         span: Span,
     ) -> CompileResult<TypedExpression> {
+        let mut warnings = vec![];
         let mut errors = vec![];
         let exp = match namespace.resolve_symbol(&name).value {
             Some(TypedDeclaration::VariableDeclaration(TypedVariableDeclaration {
@@ -621,6 +652,23 @@ impl TypedExpression {
                 )),
                 span,
             },
+            Some(TypedDeclaration::FunctionDeclaration(decl)) => {
+                let return_type = check!(
+                    decl.fn_pointer_type(&name.span()),
+                    insert_type(TypeInfo::ErrorRecovery),
+                    warnings,
+                    errors
+                );
+                TypedExpression {
+                    return_type,
+                    is_constant: IsConstant::No,
+                    expression: TypedExpressionVariant::FunctionReference {
+                        call_path: name.clone().into(),
+                        function_decl: decl,
+                    },
+                    span,
+                }
+            }
             Some(a) => {
                 errors.push(CompileError::NotAVariable {
                     name: name.clone(),
@@ -635,14 +683,14 @@ impl TypedExpression {
                 error_recovery_expr(name.span())
             }
         };
-        ok(exp, vec![], errors)
+        ok(exp, warnings, errors)
     }
 
     fn type_check_function_application(
         ctx: TypeCheckContext,
         mut call_path_binding: TypeBinding<CallPath>,
         arguments: Vec<Expression>,
-        _span: Span,
+        span: Span,
     ) -> CompileResult<TypedExpression> {
         let mut warnings = vec![];
         let mut errors = vec![];
@@ -655,6 +703,16 @@ impl TypedExpression {
             errors
         );
 
+        // if the callee is a variable, this is a call through a function pointer
+        if let TypedDeclaration::VariableDeclaration(..) = unknown_decl {
+            return instantiate_indirect_function_application(
+                ctx,
+                call_path_binding.inner,
+                arguments,
+                span,
+            );
+        }
+
         // check that the decl is a function decl
         let function_decl = check!(
             unknown_decl.expect_function().cloned(),
@@ -793,6 +851,7 @@ impl TypedExpression {
         mut ctx: TypeCheckContext,
         value: Expression,
         branches: Vec<MatchBranch>,
+        source: MatchSource,
         span: Span,
     ) -> CompileResult<TypedExpression> {
         let mut warnings = vec![];
@@ -825,6 +884,9 @@ impl TypedExpression {
             .map(|branch| branch.scrutinee.clone())
             .collect::<Vec<_>>();
 
+        // a `let` pattern is desugared to a single match arm, so errors point at its pattern
+        let let_pattern_span = scrutinees.first().map(|scrutinee| scrutinee.span());
+
         // type check the match expression and create a TypedMatchExpression object
         let typed_match_expression = {
             let ctx = ctx.by_ref().with_help_text("");
@@ -852,9 +914,16 @@ impl TypedExpression {
             }
         }
         if witness_report.has_witnesses() {
-            errors.push(CompileError::MatchExpressionNonExhaustive {
-                missing_patterns: format!("{}", witness_report),
-                span,
+            let missing_patterns = format!("{}", witness_report);
+            errors.push(match (source, let_pattern_span) {
+                (MatchSource::Let, Some(span)) => CompileError::RefutablePatternInLet {
+                    missing_patterns,
+                    span,
+                },
+                _ => CompileError::MatchExpressionNonExhaustive {
+                    missing_patterns,
+                    span,
+                },
             });
             return err(warnings, errors);
         }
@@ -1186,6 +1255,14 @@ impl TypedExpression {
         let mut warnings = vec![];
         let mut errors = vec![];
 
+        // `Self::NAME` may refer to an associated constant of the enclosing impl. Associated
+        // constants don't exist as globals of the module, so their value is used directly.
+        if let Some(decl) = Self::find_associated_const(&ctx, &call_path_binding.inner) {
+            let mut value = decl.value.clone();
+            value.span = span;
+            return ok(value, warnings, errors);
+        }
+
         // The first step is to determine if the call path refers to a module, enum, or function.
         // If only one exists, then we use that one. Otherwise, if more than one exist, it is
         // an ambiguous reference error.
@@ -1292,6 +1369,18 @@ impl TypedExpression {
         ok(exp, warnings, errors)
     }
 
+    fn find_associated_const<'a>(
+        ctx: &'a TypeCheckContext,
+        call_path: &CallPath,
+    ) -> Option<&'a TypedConstantDeclaration> {
+        match &call_path.prefixes[..] {
+            [prefix] if !call_path.is_absolute && prefix.as_str() == "Self" => {
+                ctx.namespace.get_associated_const(&call_path.suffix)
+            }
+            _ => None,
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn type_check_abi_cast(
         mut ctx: TypeCheckContext,
@@ -1411,8 +1500,12 @@ impl TypedExpression {
         }
 
         functions_buf.append(&mut type_checked_fn_buf);
-        ctx.namespace
-            .insert_trait_implementation(abi_name.clone(), return_type, functions_buf);
+        ctx.namespace.insert_trait_implementation(
+            abi_name.clone(),
+            vec![],
+            return_type,
+            functions_buf,
+        );
         let exp = TypedExpression {
             expression: TypedExpressionVariant::AbiCast {
                 abi_name,
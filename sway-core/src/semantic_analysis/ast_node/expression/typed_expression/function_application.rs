@@ -70,6 +70,103 @@ pub(crate) fn instantiate_function_application(
     ok(exp, warnings, errors)
 }
 
+/// Instantiates a call through a function pointer held by the variable `call_path`.
+pub(crate) fn instantiate_indirect_function_application(
+    mut ctx: TypeCheckContext,
+    call_path: CallPath,
+    arguments: Vec<Expression>,
+    span: Span,
+) -> CompileResult<TypedExpression> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    let callee = check!(
+        TypedExpression::type_check_variable_expression(
+            ctx.namespace,
+            call_path.suffix.clone(),
+            call_path.span()
+        ),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+
+    let (parameters, return_type) = match resolve_type(callee.return_type, &call_path.span()) {
+        Ok(TypeInfo::Function {
+            parameters,
+            return_type,
+        }) => (parameters, return_type),
+        Ok(TypeInfo::ErrorRecovery) => return err(warnings, errors),
+        Ok(type_info) => {
+            errors.push(CompileError::DeclIsNotAFunction {
+                actually: format!("variable of type {}", type_info),
+                span: call_path.span(),
+            });
+            return err(warnings, errors);
+        }
+        Err(e) => {
+            errors.push(e.into());
+            return err(warnings, errors);
+        }
+    };
+
+    // check that the number of parameters and the number of the arguments is the same
+    match arguments.len().cmp(&parameters.len()) {
+        std::cmp::Ordering::Equal => (),
+        std::cmp::Ordering::Less => {
+            errors.push(CompileError::TooFewArgumentsForFunction {
+                span: call_path.span(),
+                method_name: call_path.suffix.clone(),
+                expected: parameters.len(),
+                received: arguments.len(),
+            });
+            return err(warnings, errors);
+        }
+        std::cmp::Ordering::Greater => {
+            errors.push(CompileError::TooManyArgumentsForFunction {
+                span: call_path.span(),
+                method_name: call_path.suffix.clone(),
+                expected: parameters.len(),
+                received: arguments.len(),
+            });
+            return err(warnings, errors);
+        }
+    }
+
+    // type check the arguments, using the parameter types of the function pointer type as
+    // annotations
+    let typed_arguments = arguments
+        .into_iter()
+        .zip(parameters.iter())
+        .map(|(arg, param)| {
+            let ctx = ctx
+                .by_ref()
+                .with_help_text(
+                    "The argument that has been provided to this function pointer does not \
+                    match the parameter type of the function pointer type.",
+                )
+                .with_type_annotation(param.type_id);
+            check!(
+                TypedExpression::type_check(ctx, arg.clone()),
+                error_recovery_expr(arg.span()),
+                warnings,
+                errors
+            )
+        })
+        .collect();
+
+    let exp = TypedExpression {
+        expression: TypedExpressionVariant::IndirectFunctionApplication {
+            callee: Box::new(callee),
+            arguments: typed_arguments,
+        },
+        return_type: return_type.type_id,
+        is_constant: IsConstant::No,
+        span,
+    };
+    ok(exp, warnings, errors)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn instantiate_function_application_simple(
     call_path: CallPath,
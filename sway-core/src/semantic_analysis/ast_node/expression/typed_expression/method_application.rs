@@ -350,7 +350,8 @@ pub(crate) fn resolve_method_name(
                     &type_info_prefix,
                     method_name,
                     ctx.self_type(),
-                    &arguments
+                    &arguments,
+                    ctx.type_annotation()
                 ),
                 return err(warnings, errors),
                 warnings,
@@ -374,7 +375,8 @@ pub(crate) fn resolve_method_name(
                     &module_path,
                     &call_path.suffix,
                     ctx.self_type(),
-                    &arguments
+                    &arguments,
+                    ctx.type_annotation()
                 ),
                 return err(warnings, errors),
                 warnings,
@@ -398,7 +400,8 @@ pub(crate) fn resolve_method_name(
                     &module_path,
                     method_name,
                     ctx.self_type(),
-                    &arguments
+                    &arguments,
+                    ctx.type_annotation()
                 ),
                 return err(warnings, errors),
                 warnings,
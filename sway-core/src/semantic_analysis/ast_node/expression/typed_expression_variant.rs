@@ -112,6 +112,16 @@ pub enum TypedExpressionVariant {
         exp: Box<TypedExpression>,
         variant: TypedEnumVariant,
     },
+    /// a reference to a function used as a value, i.e. a function pointer
+    FunctionReference {
+        call_path: CallPath,
+        function_decl: TypedFunctionDeclaration,
+    },
+    /// a call through a function pointer, where the callee is only known at runtime
+    IndirectFunctionApplication {
+        callee: Box<TypedExpression>,
+        arguments: Vec<TypedExpression>,
+    },
 }
 
 // NOTE: Hash and PartialEq must uphold the invariant:
@@ -316,6 +326,26 @@ impl PartialEq for TypedExpressionVariant {
                 },
             ) => *l_exp == *r_exp && l_variant == r_variant,
             (Self::EnumTag { exp: l_exp }, Self::EnumTag { exp: r_exp }) => *l_exp == *r_exp,
+            (
+                Self::FunctionReference {
+                    call_path: l_name,
+                    function_decl: l_function_decl,
+                },
+                Self::FunctionReference {
+                    call_path: r_name,
+                    function_decl: r_function_decl,
+                },
+            ) => l_name == r_name && l_function_decl.body == r_function_decl.body,
+            (
+                Self::IndirectFunctionApplication {
+                    callee: l_callee,
+                    arguments: l_arguments,
+                },
+                Self::IndirectFunctionApplication {
+                    callee: r_callee,
+                    arguments: r_arguments,
+                },
+            ) => (**l_callee) == (**r_callee) && l_arguments == r_arguments,
             _ => false,
         }
     }
@@ -417,6 +447,15 @@ impl CopyTypes for TypedExpressionVariant {
                 variant.copy_types(type_mapping);
             }
             AbiName(_) => (),
+            FunctionReference { function_decl, .. } => {
+                function_decl.copy_types(type_mapping);
+            }
+            IndirectFunctionApplication { callee, arguments } => {
+                callee.copy_types(type_mapping);
+                arguments
+                    .iter_mut()
+                    .for_each(|x| x.copy_types(type_mapping));
+            }
         }
     }
 }
@@ -503,6 +542,12 @@ impl fmt::Display for TypedExpressionVariant {
             TypedExpressionVariant::UnsafeDowncast { exp, variant } => {
                 format!("({} as {})", look_up_type_id(exp.return_type), variant.name)
             }
+            TypedExpressionVariant::FunctionReference { call_path, .. } => {
+                format!("\"{}\" fn reference", call_path.suffix.as_str())
+            }
+            TypedExpressionVariant::IndirectFunctionApplication { callee, .. } => {
+                format!("indirect call to {}", callee)
+            }
         };
         write!(f, "{}", s)
     }
@@ -15,8 +15,13 @@ pub(crate) use return_statement::*;
 pub(crate) use while_loop::*;
 
 use crate::{
-    error::*, parse_tree::*, semantic_analysis::*, style::*, type_engine::*,
-    types::DeterministicallyAborts, AstNode, AstNodeContent, Ident, ReturnStatement,
+    error::*,
+    parse_tree::*,
+    semantic_analysis::{namespace::trait_name_with_type_arguments, *},
+    style::*,
+    type_engine::*,
+    types::DeterministicallyAborts,
+    AstNode, AstNodeContent, Ident, ReturnStatement,
 };
 
 use sway_types::{span::Span, state::StateIndex, Spanned};
@@ -248,9 +253,17 @@ impl TypedAstNode {
                         ctx.namespace.find_module_path(&a.call_path)
                     };
                     let mut res = match a.import_type {
-                        ImportType::Star => ctx.namespace.star_import(&path),
-                        ImportType::SelfImport => ctx.namespace.self_import(&path, a.alias),
-                        ImportType::Item(s) => ctx.namespace.item_import(&path, &s, a.alias),
+                        ImportType::Star => {
+                            ctx.namespace.insert_untracked_import(node.span.clone());
+                            ctx.namespace.star_import(&path)
+                        }
+                        ImportType::SelfImport => {
+                            ctx.namespace.self_import(&path, a.alias, node.span.clone())
+                        }
+                        ImportType::Item(s) => {
+                            ctx.namespace
+                                .item_import(&path, &s, a.alias, node.span.clone())
+                        }
                     };
                     warnings.append(&mut res.warnings);
                     errors.append(&mut res.errors);
@@ -376,8 +389,23 @@ impl TypedAstNode {
                                 warnings,
                                 errors
                             );
+                            if ctx.namespace.has_trait_implementation(
+                                &impl_trait.trait_name,
+                                &impl_trait.trait_type_arguments,
+                                implementing_for_type_id,
+                            ) {
+                                errors.push(CompileError::ConflictingImplsForTraitAndType {
+                                    trait_name: trait_name_with_type_arguments(
+                                        &impl_trait.trait_name,
+                                        &impl_trait.trait_type_arguments,
+                                    ),
+                                    type_name: implementing_for_type_id.to_string(),
+                                    span: impl_trait.span.clone(),
+                                });
+                            }
                             ctx.namespace.insert_trait_implementation(
                                 impl_trait.trait_name.clone(),
+                                impl_trait.trait_type_arguments.clone(),
                                 implementing_for_type_id,
                                 impl_trait.methods.clone(),
                             );
@@ -392,6 +420,7 @@ impl TypedAstNode {
                             );
                             ctx.namespace.insert_trait_implementation(
                                 impl_trait.trait_name.clone(),
+                                vec![],
                                 impl_trait.implementing_for_type_id,
                                 impl_trait.methods.clone(),
                             );
@@ -43,7 +43,8 @@ impl TypedModule {
 
         let validated_nodes_res = typed_nodes_res.flat_map(|typed_nodes| {
             let errors = check_supertraits(&typed_nodes, ctx.namespace);
-            ok(typed_nodes, vec![], errors)
+            let warnings = ctx.namespace.unused_imports();
+            ok(typed_nodes, warnings, errors)
         });
 
         submodules_res.flat_map(|submodules| {
@@ -4,11 +4,36 @@ use super::TraitMap;
 
 use sway_types::{span::Span, Spanned};
 
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 type SymbolMap = im::OrdMap<Ident, TypedDeclaration>;
 type UseSynonyms = im::HashMap<Ident, Vec<Ident>>;
 type UseAliases = im::HashMap<String, Ident>;
+type AssociatedConsts = im::HashMap<Ident, TypedConstantDeclaration>;
+type ItemImports = im::HashMap<Ident, ItemImport>;
+
+/// A symbol imported by name, as in `use foo::bar;` or `use foo::bar as baz;`.
+#[derive(Clone, Debug)]
+pub(crate) struct ItemImport {
+    /// The name the symbol is imported under, i.e. its alias if it has one.
+    pub(crate) name: Ident,
+    /// The span of the `use` statement which imports the symbol.
+    pub(crate) use_span: Span,
+    /// Whether symbol resolution went through this import.
+    ///
+    /// Symbols are resolved through a shared reference to the namespace and modules are cloned
+    /// while type checking, so the flag is shared between all the clones of the import.
+    used: Arc<AtomicBool>,
+}
+
+impl PartialEq for ItemImport {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.use_span == other.use_span
+    }
+}
 
 /// The set of items that exist within some lexical scope via declaration or importing.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -28,6 +53,19 @@ pub struct Items {
     pub(crate) use_aliases: UseAliases,
     /// If there is a storage declaration (which are only valid in contracts), store it here.
     pub(crate) declared_storage: Option<TypedStorageDeclaration>,
+    /// The associated constants of the `impl` or `trait` currently being type checked.
+    ///
+    /// These are kept apart from `symbols` as they may only be referred to through `Self`, as in
+    /// `Self::ID`.
+    pub(crate) associated_consts: AssociatedConsts,
+    /// The symbols imported by name, keyed by the name they are imported under.
+    ///
+    /// Traits and constants are not included. The methods of a trait are in scope once it is
+    /// imported without its name ever being resolved, and constants are copied into `symbols`.
+    pub(crate) item_imports: ItemImports,
+    /// The spans of the `use` statements of the imports which are not in `item_imports`, i.e. of
+    /// traits, constants and globs, one per import.
+    pub(crate) untracked_imports: im::Vector<Span>,
 }
 
 impl Items {
@@ -76,11 +114,14 @@ impl Items {
         let mut errors = vec![];
         // purposefully do not preemptively return errors so that the
         // new definiton allows later usages to compile
-        if self.symbols.get(&name).is_some() {
+        if let Some(previous) = self.declared_symbol(&name).cloned() {
             match item {
                 TypedDeclaration::EnumDeclaration { .. }
                 | TypedDeclaration::StructDeclaration { .. } => {
-                    errors.push(CompileError::ShadowsOtherSymbol { name: name.clone() });
+                    errors.push(CompileError::ShadowsOtherSymbol {
+                        name: name.clone(),
+                        previous,
+                    });
                 }
                 TypedDeclaration::GenericTypeForFunctionScope { .. } => {
                     errors.push(CompileError::GenericShadowsGeneric { name: name.clone() });
@@ -88,7 +129,10 @@ impl Items {
                 _ => {
                     warnings.push(CompileWarning {
                         span: name.span(),
-                        warning_content: Warning::ShadowsOtherSymbol { name: name.clone() },
+                        warning_content: Warning::ShadowsOtherSymbol {
+                            name: name.clone(),
+                            previous,
+                        },
                     });
                 }
             }
@@ -97,6 +141,73 @@ impl Items {
         ok((), warnings, errors)
     }
 
+    /// The name under which a symbol with the same name as `name` was declared, if any.
+    fn declared_symbol(&self, name: &Ident) -> Option<&Ident> {
+        self.symbols.get_key_value(name).map(|(symbol, _)| symbol)
+    }
+
+    pub(crate) fn insert_item_import(&mut self, name: Ident, use_span: Span) {
+        let import = ItemImport {
+            name: name.clone(),
+            use_span,
+            used: Arc::default(),
+        };
+        self.item_imports.insert(name, import);
+    }
+
+    pub(crate) fn insert_untracked_import(&mut self, use_span: Span) {
+        self.untracked_imports.push_back(use_span);
+    }
+
+    /// Records that `name` was resolved through the import it names, if there is one.
+    pub(crate) fn mark_import_used(&self, name: &Ident) {
+        if let Some(import) = self.item_imports.get(name) {
+            import.used.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Warns about the symbols imported by name which were never resolved through their import.
+    ///
+    /// Imports under a name starting with `_` are exempt.
+    pub(crate) fn unused_imports(&self) -> Vec<CompileWarning> {
+        let mut unused = self
+            .item_imports
+            .values()
+            .filter(|import| {
+                !import.name.as_str().starts_with('_') && !import.used.load(Ordering::Relaxed)
+            })
+            .collect::<Vec<_>>();
+        unused.sort_by_key(|import| import.name.span().start());
+        unused
+            .into_iter()
+            .map(|import| {
+                // The whole statement can only be removed if it imports nothing else.
+                let imports_in_statement = self
+                    .item_imports
+                    .values()
+                    .map(|other| &other.use_span)
+                    .chain(&self.untracked_imports)
+                    .filter(|use_span| **use_span == import.use_span)
+                    .count();
+                CompileWarning {
+                    span: import.name.span(),
+                    warning_content: Warning::UnusedImport {
+                        name: import.name.clone(),
+                        use_statement: (imports_in_statement == 1).then(|| import.use_span.clone()),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn insert_associated_const(&mut self, decl: TypedConstantDeclaration) {
+        self.associated_consts.insert(decl.name.clone(), decl);
+    }
+
+    pub(crate) fn get_associated_const(&self, name: &Ident) -> Option<&TypedConstantDeclaration> {
+        self.associated_consts.get(name)
+    }
+
     pub(crate) fn check_symbol(&self, name: &Ident) -> Result<&TypedDeclaration, CompileError> {
         self.symbols
             .get(name)
@@ -106,9 +217,47 @@ impl Items {
     pub(crate) fn insert_trait_implementation(
         &mut self,
         trait_name: CallPath,
+        trait_type_arguments: Vec<TypeId>,
         implementing_for_type_id: TypeId,
         functions_buf: Vec<TypedFunctionDeclaration>,
     ) {
+        let trait_name = self.canonical_trait_name(trait_name);
+        self.implemented_traits.insert(
+            trait_name,
+            trait_type_arguments,
+            implementing_for_type_id,
+            functions_buf,
+        );
+    }
+
+    /// Whether `trait_name` with the type arguments `trait_type_arguments` is already implemented
+    /// for `implementing_for_type_id` in this module.
+    pub(crate) fn has_trait_implementation(
+        &self,
+        trait_name: &CallPath,
+        trait_type_arguments: &[TypeId],
+        implementing_for_type_id: TypeId,
+    ) -> bool {
+        let trait_name = self.canonical_trait_name(trait_name.clone());
+        self.implemented_traits.contains(
+            &trait_name,
+            trait_type_arguments,
+            implementing_for_type_id,
+        )
+    }
+
+    /// Returns the trait implementations for `implementing_for_type_id`, each with the name and
+    /// type arguments of its trait.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn get_impls_for_type(
+        &self,
+        implementing_for_type_id: TypeId,
+    ) -> Vec<((CallPath, Vec<TypeId>), Vec<TypedFunctionDeclaration>)> {
+        self.implemented_traits
+            .get_impls_for_type(implementing_for_type_id)
+    }
+
+    fn canonical_trait_name(&self, trait_name: CallPath) -> CallPath {
         let new_prefixes = if trait_name.prefixes.is_empty() {
             self.use_synonyms
                 .get(&trait_name.suffix)
@@ -117,21 +266,11 @@ impl Items {
         } else {
             trait_name.prefixes
         };
-        let trait_name = CallPath {
+        CallPath {
             suffix: trait_name.suffix,
             prefixes: new_prefixes,
             is_absolute: trait_name.is_absolute,
-        };
-        self.implemented_traits
-            .insert(trait_name, implementing_for_type_id, functions_buf);
-    }
-
-    pub(crate) fn get_methods_for_type(
-        &self,
-        implementing_for_type_id: TypeId,
-    ) -> Vec<TypedFunctionDeclaration> {
-        self.implemented_traits
-            .get_methods_for_type(implementing_for_type_id)
+        }
     }
 
     pub(crate) fn get_canonical_path(&self, symbol: &Ident) -> &[Ident] {
@@ -11,6 +11,7 @@ pub use module::Module;
 pub use namespace::Namespace;
 pub use root::Root;
 
+pub(crate) use trait_map::trait_name_with_type_arguments;
 use trait_map::TraitMap;
 
 use sway_types::Ident;
@@ -1,6 +1,6 @@
 use crate::{
     error::*,
-    parse_tree::Visibility,
+    parse_tree::{CallPath, Visibility},
     semantic_analysis::{ast_node::TypedVariableDeclaration, declaration::VariableMutability},
     CompileResult, Ident, TypedDeclaration,
 };
@@ -76,6 +76,37 @@ impl Module {
         }
     }
 
+    /// The path of the declaration which `call_path` refers to within the submodule at `mod_path`,
+    /// e.g. `std::token::transfer` for `transfer` imported with `use std::token::transfer;`.
+    ///
+    /// Imports are followed back to the module which declares the symbol, as with
+    /// [Root::resolve_call_path], but the symbol needn't exist.
+    pub(crate) fn canonical_call_path(&self, mod_path: &Path, call_path: &CallPath) -> Vec<Ident> {
+        let mod_path: &Path = if call_path.is_absolute { &[] } else { mod_path };
+        let mut path: Vec<Ident> = mod_path
+            .iter()
+            .chain(&call_path.prefixes)
+            .cloned()
+            .collect();
+        let mut symbol = call_path.suffix.clone();
+        while let Some(module) = self.submodule(&path) {
+            let true_symbol = module
+                .use_aliases
+                .get(symbol.as_str())
+                .unwrap_or(&symbol)
+                .clone();
+            match module.use_synonyms.get(&symbol) {
+                Some(src_path) if *src_path != path => {
+                    path = src_path.clone();
+                    symbol = true_symbol;
+                }
+                _ => break,
+            }
+        }
+        path.push(symbol);
+        path
+    }
+
     /// Given a path to a `src` module, create synonyms to every symbol in that module to the given
     /// `dst` module.
     ///
@@ -126,20 +157,23 @@ impl Module {
         src: &Path,
         dst: &Path,
         alias: Option<Ident>,
+        use_span: Span,
     ) -> CompileResult<()> {
         let (last_item, src) = src.split_last().expect("guaranteed by grammar");
-        self.item_import(src, last_item, dst, alias)
+        self.item_import(src, last_item, dst, alias, use_span)
     }
 
     /// Pull a single `item` from the given `src` module and import it into the `dst` module.
     ///
-    /// Paths are assumed to be relative to `self`.
+    /// Paths are assumed to be relative to `self`. `use_span` is the span of the `use` statement,
+    /// which is recorded so that the import can be reported if it turns out to be unused.
     pub(crate) fn item_import(
         &mut self,
         src: &Path,
         item: &Ident,
         dst: &Path,
         alias: Option<Ident>,
+        use_span: Span,
     ) -> CompileResult<()> {
         let mut warnings = vec![];
         let mut errors = vec![];
@@ -162,7 +196,9 @@ impl Module {
                     ..
                 }) = decl
                 {
-                    self[dst].insert_symbol(alias.unwrap_or_else(|| name.clone()), decl.clone());
+                    let dst_ns = &mut self[dst];
+                    dst_ns.insert_symbol(alias.unwrap_or_else(|| name.clone()), decl.clone());
+                    dst_ns.insert_untracked_import(use_span);
                     return ok((), warnings, errors);
                 }
                 let a = decl.return_type().value;
@@ -172,13 +208,23 @@ impl Module {
                     None => vec![],
                 };
                 impls_to_insert.append(&mut res);
+                // the methods of a trait are used without resolving its name, so there is no
+                // telling whether its import is used
+                let is_trait = matches!(decl, TypedDeclaration::TraitDeclaration(_));
                 // no matter what, import it this way though.
                 let dst_ns = &mut self[dst];
+                if is_trait {
+                    dst_ns.insert_untracked_import(use_span);
+                } else {
+                    let name = alias.clone().unwrap_or_else(|| item.clone());
+                    dst_ns.insert_item_import(name, use_span);
+                }
                 match alias {
                     Some(alias) => {
-                        if dst_ns.use_synonyms.contains_key(&alias) {
+                        if let Some(previous) = dst_ns.use_synonyms.keys().find(|s| **s == alias) {
                             errors.push(CompileError::ShadowsOtherSymbol {
                                 name: alias.clone(),
+                                previous: previous.clone(),
                             });
                         }
                         dst_ns.use_synonyms.insert(alias.clone(), src.to_vec());
@@ -187,8 +233,11 @@ impl Module {
                             .insert(alias.as_str().to_string(), item.clone());
                     }
                     None => {
-                        if dst_ns.use_synonyms.contains_key(item) {
-                            errors.push(CompileError::ShadowsOtherSymbol { name: item.clone() });
+                        if let Some(previous) = dst_ns.use_synonyms.keys().find(|s| *s == item) {
+                            errors.push(CompileError::ShadowsOtherSymbol {
+                                name: item.clone(),
+                                previous: previous.clone(),
+                            });
                         }
                         dst_ns.use_synonyms.insert(item.clone(), src.to_vec());
                     }
@@ -201,13 +250,13 @@ impl Module {
         };
 
         let dst_ns = &mut self[dst];
-        impls_to_insert
-            .into_iter()
-            .for_each(|((call_path, type_info), methods)| {
+        impls_to_insert.into_iter().for_each(
+            |((call_path, type_arguments, type_info), methods)| {
                 dst_ns
                     .implemented_traits
-                    .insert(call_path, type_info, methods);
-            });
+                    .insert(call_path, type_arguments, type_info, methods);
+            },
+        );
 
         ok((), warnings, errors)
     }
@@ -140,6 +140,7 @@ impl Namespace {
         method_name: &Ident,
         self_type: TypeId,
         args_buf: &VecDeque<TypedExpression>,
+        type_annotation: TypeId,
     ) -> CompileResult<TypedFunctionDeclaration> {
         self.root.find_method_for_type(
             &self.mod_path,
@@ -148,6 +149,7 @@ impl Namespace {
             method_name,
             self_type,
             args_buf,
+            type_annotation,
         )
     }
 
@@ -157,8 +159,13 @@ impl Namespace {
     }
 
     /// Short-hand for performing a [Module::self_import] with `mod_path` as the destination.
-    pub(crate) fn self_import(&mut self, src: &Path, alias: Option<Ident>) -> CompileResult<()> {
-        self.root.self_import(src, &self.mod_path, alias)
+    pub(crate) fn self_import(
+        &mut self,
+        src: &Path,
+        alias: Option<Ident>,
+        use_span: Span,
+    ) -> CompileResult<()> {
+        self.root.self_import(src, &self.mod_path, alias, use_span)
     }
 
     /// Short-hand for performing a [Module::item_import] with `mod_path` as the destination.
@@ -167,8 +174,10 @@ impl Namespace {
         src: &Path,
         item: &Ident,
         alias: Option<Ident>,
+        use_span: Span,
     ) -> CompileResult<()> {
-        self.root.item_import(src, item, &self.mod_path, alias)
+        self.root
+            .item_import(src, item, &self.mod_path, alias, use_span)
     }
 
     /// "Enter" the submodule at the given path by returning a new [SubmoduleNamespace].
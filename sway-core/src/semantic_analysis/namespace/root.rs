@@ -3,7 +3,9 @@ use crate::{
     TypedDeclaration, TypedFunctionDeclaration,
 };
 
-use super::{module::Module, namespace::Namespace, Path};
+use super::{
+    module::Module, namespace::Namespace, trait_map::trait_name_with_type_arguments, Path,
+};
 
 use sway_types::{span::Span, Spanned};
 
@@ -57,6 +59,7 @@ impl Root {
                 .unwrap_or(symbol);
             match module.use_synonyms.get(symbol) {
                 Some(src_path) if mod_path != src_path => {
+                    module.mark_import_used(symbol);
                     self.resolve_symbol(src_path, true_symbol)
                 }
                 _ => CompileResult::from(module.check_symbol(true_symbol)),
@@ -178,6 +181,32 @@ impl Root {
                 }
                 insert_type(TypeInfo::Tuple(type_arguments))
             }
+            TypeInfo::Function {
+                mut parameters,
+                mut return_type,
+            } => {
+                for type_argument in parameters
+                    .iter_mut()
+                    .chain(std::iter::once(&mut return_type))
+                {
+                    type_argument.type_id = check!(
+                        self.resolve_type(
+                            type_argument.type_id,
+                            span,
+                            enforce_type_arguments,
+                            None,
+                            mod_path
+                        ),
+                        insert_type(TypeInfo::ErrorRecovery),
+                        warnings,
+                        errors
+                    );
+                }
+                insert_type(TypeInfo::Function {
+                    parameters,
+                    return_type,
+                })
+            }
             o => insert_type(o),
         };
         ok(type_id, warnings, errors)
@@ -185,9 +214,11 @@ impl Root {
 
     /// Given a method and a type (plus a `self_type` to potentially resolve it), find that method
     /// in the namespace. Requires `args_buf` because of some special casing for the standard
-    /// library where we pull the type from the arguments buffer.
+    /// library where we pull the type from the arguments buffer. `type_annotation` is the type which
+    /// the caller expects the method to return, if it is known.
     ///
-    /// This function will generate a missing method error if the method is not found.
+    /// This function will generate a missing method error if the method is not found, and an
+    /// ambiguity error if several implementations provide a method that fits the call.
     ///
     /// This method should only be called on the root namespace. `mod_path` is the current module,
     /// `method_path` is assumed to be absolute.
@@ -199,6 +230,7 @@ impl Root {
         method_name: &Ident,
         self_type: TypeId,
         args_buf: &VecDeque<TypedExpression>,
+        type_annotation: TypeId,
     ) -> CompileResult<TypedFunctionDeclaration> {
         let mut warnings = vec![];
         let mut errors = vec![];
@@ -212,7 +244,7 @@ impl Root {
         );
 
         // grab the local methods from the local module
-        let local_methods = local_module.get_methods_for_type(type_id);
+        let local_impls = local_module.get_impls_for_type(type_id);
 
         type_id.replace_self_type(self_type);
 
@@ -230,6 +262,13 @@ impl Root {
             errors
         );
 
+        // importing a struct or enum also imports its methods, which counts as using the import
+        if let TypeInfo::Struct { name, .. } | TypeInfo::Enum { name, .. } =
+            look_up_type_id(type_id)
+        {
+            self[mod_path].mark_import_used(&name);
+        }
+
         // grab the module where the type itself is declared
         let type_module = check!(
             self.check_submodule(method_prefix),
@@ -239,15 +278,59 @@ impl Root {
         );
 
         // grab the methods from where the type is declared
-        let mut type_methods = type_module.get_methods_for_type(type_id);
+        let mut type_impls = type_module.get_impls_for_type(type_id);
 
-        let mut methods = local_methods;
-        methods.append(&mut type_methods);
+        let mut impls = local_impls;
+        impls.append(&mut type_impls);
 
-        match methods
-            .into_iter()
-            .find(|TypedFunctionDeclaration { name, .. }| name == method_name)
-        {
+        // The local module may be the module of the type, or import the same implementations, so
+        // each method of an implementation is only a candidate once.
+        let mut candidates: Vec<(String, TypedFunctionDeclaration)> = vec![];
+        for ((trait_name, type_arguments), methods) in impls.into_iter() {
+            let trait_name = trait_name_with_type_arguments(&trait_name, &type_arguments);
+            for method in methods
+                .into_iter()
+                .filter(|method| method.name == *method_name)
+            {
+                if !candidates
+                    .iter()
+                    .any(|(name, candidate)| *name == trait_name && *candidate == method)
+                {
+                    candidates.push((trait_name.clone(), method));
+                }
+            }
+        }
+
+        // A type may implement the same generic trait several times with different type
+        // arguments, e.g. `impl From<u64> for A` and `impl From<b256> for A`, in which case the
+        // arguments and the expected return type decide which of the methods is meant.
+        if candidates.len() > 1 {
+            let (applicable, inapplicable): (Vec<_>, Vec<_>) =
+                candidates.into_iter().partition(|(_, method)| {
+                    method_accepts_arguments(method, args_buf)
+                        && method_returns_type(method, type_annotation)
+                });
+            if applicable.is_empty() {
+                errors.push(CompileError::NoApplicableMethod {
+                    method_name: method_name.clone(),
+                    type_name: type_id.to_string(),
+                    candidates: quoted_trait_names(&inapplicable),
+                });
+                return err(warnings, errors);
+            }
+            if applicable.len() > 1 {
+                errors.push(CompileError::AmbiguousMethodCall {
+                    method_name: method_name.clone(),
+                    type_name: type_id.to_string(),
+                    candidates: quoted_trait_names(&applicable),
+                });
+                return err(warnings, errors);
+            }
+            candidates = applicable;
+        }
+        let method = candidates.pop().map(|(_, method)| method);
+
+        match method {
             Some(o) => ok(o, warnings, errors),
             None => {
                 if args_buf.get(0).map(|x| look_up_type_id(x.return_type))
@@ -288,3 +371,49 @@ impl From<Namespace> for Root {
         namespace.root
     }
 }
+
+fn quoted_trait_names(candidates: &[(String, TypedFunctionDeclaration)]) -> String {
+    candidates
+        .iter()
+        .map(|(trait_name, _)| format!("`{}`", trait_name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether the types of `args` are compatible with the parameters of `method`. Arguments whose
+/// type is not known yet are compatible with any parameter.
+fn method_accepts_arguments(
+    method: &TypedFunctionDeclaration,
+    args: &VecDeque<TypedExpression>,
+) -> bool {
+    method.parameters.len() == args.len()
+        && method
+            .parameters
+            .iter()
+            .zip(args.iter())
+            .all(|(param, arg)| {
+                let param_type = look_up_type_id(param.type_id);
+                match look_up_type_id(arg.return_type) {
+                    TypeInfo::Unknown
+                    | TypeInfo::UnknownGeneric { .. }
+                    | TypeInfo::ErrorRecovery => true,
+                    TypeInfo::Numeric => matches!(param_type, TypeInfo::UnsignedInteger(_)),
+                    arg_type => arg_type.is_subset_of(&param_type),
+                }
+            })
+}
+
+/// Whether `method` may return a value of the type `type_annotation`, which the caller expects of
+/// the call. Every method is compatible with an annotation whose type is not known yet.
+fn method_returns_type(method: &TypedFunctionDeclaration, type_annotation: TypeId) -> bool {
+    let return_type = look_up_type_id(method.return_type);
+    match look_up_type_id(type_annotation) {
+        TypeInfo::Unknown | TypeInfo::UnknownGeneric { .. } | TypeInfo::ErrorRecovery => true,
+        _ if matches!(return_type, TypeInfo::UnknownGeneric { .. }) => true,
+        TypeInfo::Numeric => matches!(
+            return_type,
+            TypeInfo::UnsignedInteger(_) | TypeInfo::Numeric
+        ),
+        annotation => return_type.is_subset_of(&annotation),
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{
-    type_engine::{create_type_mapping, look_up_type_id, CopyTypes, TypeId},
+    type_engine::{create_type_mapping, look_up_type_id, monomorphize_type_ids, CopyTypes, TypeId},
     CallPath, TypeInfo, TypedFunctionDeclaration,
 };
 
 type TraitName = CallPath;
+/// The type arguments of a trait implementation, e.g. `u64` in `impl From<u64> for Bar`. A type
+/// may implement the same generic trait once for every combination of type arguments.
+type TraitTypeArguments = Vec<TypeId>;
 
 // This cannot be a HashMap because of how TypeInfo's are handled.
 //
@@ -27,7 +30,7 @@ type TraitName = CallPath;
 // However, we need this structure to be able to maintain the
 // difference between 3 and 4, as in practice, 1 and 2 might not yet
 // be resolved.
-type TraitMapInner = im::Vector<((TraitName, TypeId), TraitMethods)>;
+type TraitMapInner = im::Vector<((TraitName, TraitTypeArguments, TypeId), TraitMethods)>;
 type TraitMethods = im::HashMap<String, TypedFunctionDeclaration>;
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -39,6 +42,7 @@ impl TraitMap {
     pub(crate) fn insert(
         &mut self,
         trait_name: TraitName,
+        trait_type_arguments: TraitTypeArguments,
         incoming_type_id: TypeId,
         methods: Vec<TypedFunctionDeclaration>,
     ) {
@@ -46,29 +50,59 @@ impl TraitMap {
         for method in methods.into_iter() {
             methods_map.insert(method.name.as_str().to_string(), method);
         }
-        self.trait_map
-            .push_back(((trait_name, incoming_type_id), methods_map));
+        self.trait_map.push_back((
+            (trait_name, trait_type_arguments, incoming_type_id),
+            methods_map,
+        ));
     }
 
     pub(crate) fn extend(&mut self, other: TraitMap) {
-        for ((trait_name, type_implementing_for), methods) in other.trait_map.into_iter() {
+        for ((trait_name, trait_type_arguments, type_implementing_for), methods) in
+            other.trait_map.into_iter()
+        {
             self.insert(
                 trait_name,
+                trait_type_arguments,
                 type_implementing_for,
                 methods.values().cloned().collect(),
             );
         }
     }
 
+    /// Whether `trait_name` with exactly the type arguments `trait_type_arguments` is already
+    /// implemented for exactly the type `type_id`.
+    pub(crate) fn contains(
+        &self,
+        trait_name: &TraitName,
+        trait_type_arguments: &[TypeId],
+        type_id: TypeId,
+    ) -> bool {
+        self.trait_map
+            .iter()
+            .any(|((map_trait_name, map_type_arguments, map_type_id), _)| {
+                map_trait_name == trait_name
+                    && map_type_arguments.len() == trait_type_arguments.len()
+                    && map_type_arguments
+                        .iter()
+                        .zip(trait_type_arguments.iter())
+                        .all(|(a, b)| look_up_type_id(*a) == look_up_type_id(*b))
+                    && look_up_type_id(*map_type_id) == look_up_type_id(type_id)
+            })
+    }
+
+    #[allow(clippy::type_complexity)]
     pub(crate) fn get_call_path_and_type_info(
         &self,
         incoming_type_id: TypeId,
-    ) -> Vec<((TraitName, TypeId), Vec<TypedFunctionDeclaration>)> {
+    ) -> Vec<(
+        (TraitName, TraitTypeArguments, TypeId),
+        Vec<TypedFunctionDeclaration>,
+    )> {
         let mut ret = vec![];
-        for ((call_path, map_type_id), methods) in self.trait_map.iter() {
+        for ((call_path, type_arguments, map_type_id), methods) in self.trait_map.iter() {
             if look_up_type_id(incoming_type_id).is_subset_of(&look_up_type_id(*map_type_id)) {
                 ret.push((
-                    (call_path.clone(), *map_type_id),
+                    (call_path.clone(), type_arguments.clone(), *map_type_id),
                     methods.values().cloned().collect(),
                 ));
             }
@@ -76,25 +110,48 @@ impl TraitMap {
         ret
     }
 
-    pub(crate) fn get_methods_for_type(
+    /// Returns the trait implementations which apply to `incoming_type_id`, with their methods
+    /// and trait type arguments monomorphized for it.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn get_impls_for_type(
         &self,
         incoming_type_id: TypeId,
-    ) -> Vec<TypedFunctionDeclaration> {
-        let mut methods = vec![];
+    ) -> Vec<(
+        (TraitName, TraitTypeArguments),
+        Vec<TypedFunctionDeclaration>,
+    )> {
+        let mut impls = vec![];
         // small performance gain in bad case
         if look_up_type_id(incoming_type_id) == TypeInfo::ErrorRecovery {
-            return methods;
+            return impls;
         }
-        for ((_, map_type_id), trait_methods) in self.trait_map.iter() {
+        for ((trait_name, type_arguments, map_type_id), trait_methods) in self.trait_map.iter() {
             if look_up_type_id(incoming_type_id).is_subset_of(&look_up_type_id(*map_type_id)) {
                 let type_mapping = create_type_mapping(*map_type_id, incoming_type_id);
+                let type_arguments = monomorphize_type_ids(type_arguments, &type_mapping);
                 let mut trait_methods = trait_methods.values().cloned().collect::<Vec<_>>();
                 trait_methods
                     .iter_mut()
                     .for_each(|x| x.copy_types(&type_mapping));
-                methods.append(&mut trait_methods);
+                impls.push(((trait_name.clone(), type_arguments), trait_methods));
             }
         }
-        methods
+        impls
+    }
+}
+
+/// Displays the name of a trait together with its type arguments, e.g. `From<u64>`.
+pub(crate) fn trait_name_with_type_arguments(
+    trait_name: &TraitName,
+    type_arguments: &[TypeId],
+) -> String {
+    if type_arguments.is_empty() {
+        trait_name.suffix.to_string()
+    } else {
+        let type_arguments = type_arguments
+            .iter()
+            .map(|type_id| type_id.to_string())
+            .collect::<Vec<_>>();
+        format!("{}<{}>", trait_name.suffix, type_arguments.join(", "))
     }
 }
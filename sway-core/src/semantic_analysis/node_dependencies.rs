@@ -319,11 +319,17 @@ impl Dependencies {
                 interface_surface,
                 methods,
                 supertraits,
+                type_parameters,
+                associated_consts,
                 ..
             }) => self
+                .gather_from_type_parameters(type_parameters)
                 .gather_from_iter(supertraits.iter(), |deps, sup| {
                     deps.gather_from_call_path(&sup.name, false, false)
                 })
+                .gather_from_iter(associated_consts.iter(), |deps, trait_const| {
+                    deps.gather_from_typeinfo(&trait_const.type_ascription)
+                })
                 .gather_from_iter(interface_surface.iter(), |deps, sig| {
                     deps.gather_from_iter(sig.parameters.iter(), |deps, param| {
                         deps.gather_from_typeinfo(&look_up_type_id(param.type_id))
@@ -335,23 +341,41 @@ impl Dependencies {
                 }),
             Declaration::ImplTrait(ImplTrait {
                 trait_name,
+                trait_type_arguments,
                 type_implementing_for,
                 type_parameters: type_arguments,
                 functions,
+                associated_types,
+                associated_consts,
                 ..
             }) => self
                 .gather_from_call_path(trait_name, false, false)
+                .gather_from_iter(trait_type_arguments.iter(), |deps, type_argument| {
+                    deps.gather_from_typeinfo(&look_up_type_id(type_argument.type_id))
+                })
                 .gather_from_typeinfo(type_implementing_for)
                 .gather_from_type_parameters(type_arguments)
+                .gather_from_iter(associated_types.iter(), |deps, (_, type_argument)| {
+                    deps.gather_from_typeinfo(&look_up_type_id(type_argument.type_id))
+                })
+                .gather_from_iter(associated_consts.iter(), |deps, const_decl| {
+                    deps.gather_from_typeinfo(&const_decl.type_ascription)
+                        .gather_from_expr(&const_decl.value)
+                })
                 .gather_from_iter(functions.iter(), |deps, fn_decl| {
                     deps.gather_from_fn_decl(fn_decl)
                 }),
             Declaration::ImplSelf(ImplSelf {
                 type_implementing_for,
                 functions,
+                associated_consts,
                 ..
             }) => self
                 .gather_from_typeinfo(type_implementing_for)
+                .gather_from_iter(associated_consts.iter(), |deps, const_decl| {
+                    deps.gather_from_typeinfo(&const_decl.type_ascription)
+                        .gather_from_expr(&const_decl.value)
+                })
                 .gather_from_iter(functions.iter(), |deps, fn_decl| {
                     deps.gather_from_fn_decl(fn_decl)
                 }),
@@ -588,6 +612,12 @@ impl Dependencies {
                 deps.gather_from_typeinfo(&look_up_type_id(elem.type_id))
             }),
             TypeInfo::Array(type_id, _) => self.gather_from_typeinfo(&look_up_type_id(*type_id)),
+            TypeInfo::Function {
+                parameters,
+                return_type,
+            } => self
+                .gather_from_type_arguments(parameters)
+                .gather_from_typeinfo(&look_up_type_id(return_type.type_id)),
             TypeInfo::Struct { fields, .. } => self
                 .gather_from_iter(fields.iter(), |deps, field| {
                     deps.gather_from_typeinfo(&look_up_type_id(field.type_id))
@@ -656,7 +686,8 @@ fn decl_name(decl: &Declaration) -> Option<DependentSymbol> {
     let dep_sym = |name| Some(DependentSymbol::Symbol(name));
     // `method_names` is the concatenation of all the method names defined in an impl block.
     // This is needed because there can exist multiple impl self blocks for a single type in a
-    // file and we need some way to disambiguate them.
+    // file and we need some way to disambiguate them. Impls of a generic trait are further
+    // disambiguated by prefixing the trait's type arguments.
     let impl_sym = |trait_name, type_info: &TypeInfo, method_names| {
         Some(DependentSymbol::Impl(
             trait_name,
@@ -693,12 +724,19 @@ fn decl_name(decl: &Declaration) -> Option<DependentSymbol> {
         }
         Declaration::ImplTrait(decl) => {
             if decl.trait_name.prefixes.is_empty() {
+                let type_argument_names = decl
+                    .trait_type_arguments
+                    .iter()
+                    .map(|arg| type_info_name(&look_up_type_id(arg.type_id)))
+                    .collect::<Vec<String>>();
+                let method_names = decl.functions.iter().map(|x| x.name.as_str());
                 impl_sym(
                     decl.trait_name.suffix.clone(),
                     &decl.type_implementing_for,
-                    decl.functions
+                    type_argument_names
                         .iter()
-                        .map(|x| x.name.as_str())
+                        .map(String::as_str)
+                        .chain(method_names)
                         .collect::<Vec<&str>>()
                         .join(""),
                 )
@@ -749,6 +787,7 @@ fn type_info_name(type_info: &TypeInfo) -> String {
         TypeInfo::Enum { .. } => "enum",
         TypeInfo::Array(..) => "array",
         TypeInfo::Storage { .. } => "contract storage",
+        TypeInfo::Function { .. } => "function",
     }
     .to_string()
 }
@@ -1,9 +1,10 @@
 use super::{
-    storage_only_types, TypedAstNode, TypedAstNodeContent, TypedDeclaration,
+    security_lints, storage_only_types, TypedAstNode, TypedAstNodeContent, TypedDeclaration,
     TypedFunctionDeclaration, TypedImplTrait, TypedStorageDeclaration,
 };
 use crate::{
     error::*,
+    lint::{self, LintScope},
     parse_tree::{ParseProgram, Purity, TreeType},
     semantic_analysis::{
         namespace::{self, Namespace},
@@ -20,6 +21,8 @@ pub struct TypedProgram {
     pub kind: TypedProgramKind,
     pub root: TypedModule,
     pub storage_slots: Vec<StorageSlot>,
+    /// The lint levels set by the attributes of the program.
+    pub lint_scopes: Vec<LintScope>,
 }
 
 impl TypedProgram {
@@ -42,6 +45,7 @@ impl TypedProgram {
                 kind,
                 root,
                 storage_slots: vec![],
+                lint_scopes: lint::program_scopes(parsed),
             })
         })
     }
@@ -121,6 +125,9 @@ impl TypedProgram {
             );
         }
 
+        // Lint the functions of the program for common security mistakes.
+        warnings.extend(security_lints::lint_module(root));
+
         // Some checks that are specific to non-contracts
         if kind != TreeType::Contract {
             // impure functions are disallowed in non-contracts
@@ -262,6 +269,7 @@ impl TypedProgram {
                                 kind: self.kind.clone(),
                                 root: self.root.clone(),
                                 storage_slots,
+                                lint_scopes: self.lint_scopes.clone(),
                             },
                             warnings,
                             errors,
@@ -272,6 +280,7 @@ impl TypedProgram {
                             kind: self.kind.clone(),
                             root: self.root.clone(),
                             storage_slots: vec![],
+                            lint_scopes: self.lint_scopes.clone(),
                         },
                         warnings,
                         errors,
@@ -283,6 +292,7 @@ impl TypedProgram {
                     kind: self.kind.clone(),
                     root: self.root.clone(),
                     storage_slots: vec![],
+                    lint_scopes: self.lint_scopes.clone(),
                 },
                 warnings,
                 errors,
@@ -0,0 +1,360 @@
+//! Lints for common security mistakes in contracts: storage writes which follow a call to another
+//! contract, ABI methods which write to storage without checking who called them, and ignored
+//! results of token transfers.
+
+use crate::{
+    error::{CompileWarning, Warning},
+    parse_tree::{CallPath, Purity},
+    semantic_analysis::{
+        namespace, TypedAstNode, TypedAstNodeContent, TypedCodeBlock, TypedConstantDeclaration,
+        TypedDeclaration, TypedExpression, TypedExpressionVariant, TypedFunctionDeclaration,
+        TypedImplTrait, TypedIntrinsicFunctionKind, TypedModule, TypedReassignment,
+        TypedReturnStatement, TypedVariableDeclaration, TypedWhileLoop,
+    },
+    type_engine::{look_up_type_id, TypeId, TypeInfo},
+};
+use sway_types::{Ident, Span, Spanned};
+
+/// The functions which tell who called the contract.
+const CALLER_FUNCTIONS: &[&str] = &[
+    "std::chain::auth::msg_sender",
+    "std::chain::auth::caller_contract_id",
+    "std::chain::auth::caller_is_external",
+];
+
+/// The function which reverts when the contract is reentered.
+const REENTRANCY_GUARD: &str = "std::reentrancy::reentrancy_guard";
+
+/// The token transfer functions.  None of them return a `Result`, but the `Result` of a function
+/// which calls one of them must be handled.
+const TRANSFER_FUNCTIONS: &[&str] = &[
+    "std::token::transfer",
+    "std::token::force_transfer_to_contract",
+    "std::token::transfer_to_output",
+];
+
+/// Lints the functions and methods declared in the module and its submodules.
+pub(crate) fn lint_module(module: &TypedModule) -> Vec<CompileWarning> {
+    let mut warnings = vec![];
+    lint_submodule(&module.namespace, module, &[], &mut warnings);
+    warnings
+}
+
+/// `root` is the namespace of the program, in which the module is at `mod_path`.
+fn lint_submodule(
+    root: &namespace::Module,
+    module: &TypedModule,
+    mod_path: &[Ident],
+    warnings: &mut Vec<CompileWarning>,
+) {
+    for (name, submodule) in &module.submodules {
+        let submodule_path: Vec<Ident> = mod_path.iter().chain(Some(name)).cloned().collect();
+        lint_submodule(root, &submodule.module, &submodule_path, warnings);
+    }
+    for node in &module.all_nodes {
+        match &node.content {
+            TypedAstNodeContent::Declaration(TypedDeclaration::FunctionDeclaration(decl)) => {
+                lint_function(root, mod_path, decl, false, warnings)
+            }
+            TypedAstNodeContent::Declaration(TypedDeclaration::ImplTrait(TypedImplTrait {
+                methods,
+                implementing_for_type_id,
+                ..
+            })) => {
+                // ABI methods are implemented for the contract type itself.
+                let is_abi_method = matches!(
+                    look_up_type_id(*implementing_for_type_id),
+                    TypeInfo::Contract
+                );
+                for method in methods {
+                    lint_function(root, mod_path, method, is_abi_method, warnings);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn lint_function(
+    root: &namespace::Module,
+    mod_path: &[Ident],
+    decl: &TypedFunctionDeclaration,
+    is_abi_method: bool,
+    warnings: &mut Vec<CompileWarning>,
+) {
+    let mut effects = Effects::new(root, mod_path.to_vec());
+    effects.code_block(&decl.body, None);
+
+    if !effects.guards_reentrancy {
+        for (write, call) in effects.writes_after_call {
+            warnings.push(CompileWarning {
+                span: write,
+                warning_content: Warning::StorageWriteAfterContractCall { call },
+            });
+        }
+    }
+    let writes_storage = matches!(decl.purity, Purity::Writes | Purity::ReadsWrites);
+    if is_abi_method && writes_storage && !effects.checks_caller {
+        warnings.push(CompileWarning {
+            span: decl.name.span(),
+            warning_content: Warning::UncheckedMsgSender {
+                method_name: decl.name.clone(),
+            },
+        });
+    }
+    for (name, span) in effects.unchecked_transfers {
+        warnings.push(CompileWarning {
+            span,
+            warning_content: Warning::UncheckedTransferResult { name },
+        });
+    }
+}
+
+/// What a function body does that the lints care about, collected in evaluation order.
+///
+/// The bodies of called functions are walked as well, with everything they do attributed to the
+/// call. Storage writes within them are not collected though, since a call of a function which
+/// writes to storage is a storage write itself.
+///
+/// Called functions are identified by their path, with imports followed from the module at
+/// `mod_path` within `root`, in which the walked code is declared.
+struct Effects<'a> {
+    root: &'a namespace::Module,
+    mod_path: Vec<Ident>,
+    /// The first call of another contract.
+    first_contract_call: Option<Span>,
+    /// The storage writes which follow a call of another contract, with the span of that call.
+    writes_after_call: Vec<(Span, Span)>,
+    /// Whether one of [CALLER_FUNCTIONS] is called.
+    checks_caller: bool,
+    /// Whether [REENTRANCY_GUARD] is called.
+    guards_reentrancy: bool,
+    /// Whether one of [TRANSFER_FUNCTIONS] is called.
+    transfers: bool,
+    /// The calls of functions which transfer tokens and whose `Result` is dropped, by the name of
+    /// the called function.
+    unchecked_transfers: Vec<(Ident, Span)>,
+}
+
+impl<'a> Effects<'a> {
+    fn new(root: &'a namespace::Module, mod_path: Vec<Ident>) -> Self {
+        Effects {
+            root,
+            mod_path,
+            first_contract_call: None,
+            writes_after_call: vec![],
+            checks_caller: false,
+            guards_reentrancy: false,
+            transfers: false,
+            unchecked_transfers: vec![],
+        }
+    }
+
+    /// The path of the function called by `call_path`, and the path of the module to walk its
+    /// body in.  Methods are walked in the module of their caller.
+    fn resolve(&self, call_path: &CallPath) -> (String, Vec<Ident>) {
+        let path = self.root.canonical_call_path(&self.mod_path, call_path);
+        let (_, callee_mod_path) = path.split_last().unwrap();
+        let callee_mod_path = match self.root.submodule(callee_mod_path) {
+            Some(_) => callee_mod_path.to_vec(),
+            None => self.mod_path.clone(),
+        };
+        let path: Vec<&str> = path.iter().map(Ident::as_str).collect();
+        (path.join("::"), callee_mod_path)
+    }
+
+    /// Walks the body of a called function in its own module.
+    fn callee_body(&mut self, body: &TypedCodeBlock, callee_mod_path: Vec<Ident>, site: &Span) {
+        let caller_mod_path = std::mem::replace(&mut self.mod_path, callee_mod_path);
+        self.code_block(body, Some(site));
+        self.mod_path = caller_mod_path;
+    }
+
+    /// `call_site` is the span of the call whose callee `block` belongs to, if it isn't the body
+    /// of the linted function itself.
+    fn code_block(&mut self, block: &TypedCodeBlock, call_site: Option<&Span>) {
+        for node in &block.contents {
+            self.node(node, call_site);
+        }
+    }
+
+    fn node(&mut self, node: &TypedAstNode, call_site: Option<&Span>) {
+        match &node.content {
+            TypedAstNodeContent::ReturnStatement(TypedReturnStatement { expr })
+            | TypedAstNodeContent::ImplicitReturnExpression(expr) => self.expr(expr, call_site),
+            TypedAstNodeContent::Expression(expr) => {
+                self.expr(expr, call_site);
+                if call_site.is_none() {
+                    self.dropped_value(expr);
+                }
+            }
+            TypedAstNodeContent::Declaration(decl) => self.decl(decl, &node.span, call_site),
+            TypedAstNodeContent::WhileLoop(TypedWhileLoop { condition, body }) => {
+                self.expr(condition, call_site);
+                self.code_block(body, call_site);
+            }
+            TypedAstNodeContent::SideEffect => (),
+        }
+    }
+
+    fn decl(&mut self, decl: &TypedDeclaration, span: &Span, call_site: Option<&Span>) {
+        match decl {
+            TypedDeclaration::VariableDeclaration(TypedVariableDeclaration {
+                name, body, ..
+            }) => {
+                self.expr(body, call_site);
+                if call_site.is_none() && name.as_str() == "_" {
+                    self.dropped_value(body);
+                }
+            }
+            TypedDeclaration::ConstantDeclaration(TypedConstantDeclaration {
+                value: expr, ..
+            })
+            | TypedDeclaration::Reassignment(TypedReassignment { rhs: expr, .. }) => {
+                self.expr(expr, call_site)
+            }
+            TypedDeclaration::StorageReassignment(reassignment) => {
+                self.expr(&reassignment.rhs, call_site);
+                if call_site.is_none() {
+                    self.storage_write(span);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn expr(&mut self, expr: &TypedExpression, call_site: Option<&Span>) {
+        match &expr.expression {
+            TypedExpressionVariant::Literal(_)
+            | TypedExpressionVariant::VariableExpression { .. }
+            | TypedExpressionVariant::FunctionParameter
+            | TypedExpressionVariant::StorageAccess(_)
+            | TypedExpressionVariant::AbiName(_)
+            | TypedExpressionVariant::FunctionReference { .. } => (),
+            TypedExpressionVariant::FunctionApplication {
+                call_path,
+                arguments,
+                contract_call_params,
+                function_decl,
+                selector,
+                ..
+            } => {
+                for (_, arg) in arguments {
+                    self.expr(arg, call_site);
+                }
+                let site = call_site.unwrap_or(&expr.span);
+                if selector.is_some() {
+                    for param in contract_call_params.values() {
+                        self.expr(param, call_site);
+                    }
+                    self.first_contract_call.get_or_insert_with(|| site.clone());
+                    return;
+                }
+                let (path, callee_mod_path) = self.resolve(call_path);
+                match path.as_str() {
+                    path if CALLER_FUNCTIONS.contains(&path) => self.checks_caller = true,
+                    REENTRANCY_GUARD => self.guards_reentrancy = true,
+                    path if TRANSFER_FUNCTIONS.contains(&path) => self.transfers = true,
+                    _ => (),
+                }
+                if call_site.is_none()
+                    && matches!(function_decl.purity, Purity::Writes | Purity::ReadsWrites)
+                {
+                    self.storage_write(&expr.span);
+                }
+                self.callee_body(&function_decl.body, callee_mod_path, site);
+            }
+            TypedExpressionVariant::LazyOperator { lhs, rhs, .. } => {
+                self.expr(lhs, call_site);
+                self.expr(rhs, call_site);
+            }
+            TypedExpressionVariant::ArrayIndex { prefix, index } => {
+                self.expr(prefix, call_site);
+                self.expr(index, call_site);
+            }
+            TypedExpressionVariant::IntrinsicFunction(TypedIntrinsicFunctionKind {
+                arguments: exprs,
+                ..
+            })
+            | TypedExpressionVariant::Tuple { fields: exprs }
+            | TypedExpressionVariant::Array { contents: exprs } => {
+                for expr in exprs {
+                    self.expr(expr, call_site);
+                }
+            }
+            TypedExpressionVariant::StructExpression { fields, .. } => {
+                for field in fields {
+                    self.expr(&field.value, call_site);
+                }
+            }
+            TypedExpressionVariant::CodeBlock(block) => self.code_block(block, call_site),
+            TypedExpressionVariant::IfExp {
+                condition,
+                then,
+                r#else,
+            } => {
+                self.expr(condition, call_site);
+                self.expr(then, call_site);
+                if let Some(r#else) = r#else {
+                    self.expr(r#else, call_site);
+                }
+            }
+            TypedExpressionVariant::AsmExpression { registers, .. } => {
+                let initializers = registers
+                    .iter()
+                    .filter_map(|register| register.initializer.as_ref());
+                for initializer in initializers {
+                    self.expr(initializer, call_site);
+                }
+            }
+            TypedExpressionVariant::StructFieldAccess { prefix: exp, .. }
+            | TypedExpressionVariant::TupleElemAccess { prefix: exp, .. }
+            | TypedExpressionVariant::AbiCast { address: exp, .. }
+            | TypedExpressionVariant::EnumTag { exp }
+            | TypedExpressionVariant::UnsafeDowncast { exp, .. } => self.expr(exp, call_site),
+            TypedExpressionVariant::EnumInstantiation { contents, .. } => {
+                if let Some(contents) = contents {
+                    self.expr(contents, call_site);
+                }
+            }
+            // The callee is only known at runtime, so only its arguments can be walked.
+            TypedExpressionVariant::IndirectFunctionApplication { callee, arguments } => {
+                self.expr(callee, call_site);
+                for arg in arguments {
+                    self.expr(arg, call_site);
+                }
+            }
+        }
+    }
+
+    fn storage_write(&mut self, span: &Span) {
+        if let Some(call) = &self.first_contract_call {
+            self.writes_after_call.push((span.clone(), call.clone()));
+        }
+    }
+
+    /// Called for the expressions of the linted function whose value is dropped.
+    fn dropped_value(&mut self, expr: &TypedExpression) {
+        if let TypedExpressionVariant::FunctionApplication {
+            call_path,
+            function_decl,
+            selector: None,
+            ..
+        } = &expr.expression
+        {
+            if is_result(expr.return_type) {
+                let (_, callee_mod_path) = self.resolve(call_path);
+                let mut callee = Effects::new(self.root, callee_mod_path);
+                callee.code_block(&function_decl.body, Some(&expr.span));
+                if callee.transfers {
+                    self.unchecked_transfers
+                        .push((function_decl.name.clone(), expr.span.clone()));
+                }
+            }
+        }
+    }
+}
+
+fn is_result(type_id: TypeId) -> bool {
+    matches!(look_up_type_id(type_id), TypeInfo::Enum { name, .. } if name.as_str() == "Result")
+}
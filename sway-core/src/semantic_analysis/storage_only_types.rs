@@ -45,7 +45,8 @@ fn expr_validate(expr: &TypedExpression) -> CompileResult<()> {
         | TypedExpressionVariant::FunctionParameter
         | TypedExpressionVariant::AsmExpression { .. }
         | TypedExpressionVariant::StorageAccess(_)
-        | TypedExpressionVariant::AbiName(_) => (),
+        | TypedExpressionVariant::AbiName(_)
+        | TypedExpressionVariant::FunctionReference { .. } => (),
         TypedExpressionVariant::FunctionApplication { arguments, .. } => {
             for f in arguments {
                 check!(expr_validate(&f.1), continue, warnings, errors);
@@ -109,6 +110,12 @@ fn expr_validate(expr: &TypedExpression) -> CompileResult<()> {
                 check!(expr_validate(&*f), (), warnings, errors);
             }
         }
+        TypedExpressionVariant::IndirectFunctionApplication { callee, arguments } => {
+            check!(expr_validate(&*callee), (), warnings, errors);
+            for f in arguments {
+                check!(expr_validate(f), continue, warnings, errors);
+            }
+        }
     }
     ok((), warnings, errors)
 }
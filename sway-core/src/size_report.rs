@@ -0,0 +1,381 @@
+//! Attribution of the bytes and gas of a compiled program to the Sway functions they came from.
+//!
+//! All function calls are inlined before code generation, so the attribution is done through the
+//! [SourceMap]: each instruction belongs to the innermost function whose source contains the
+//! source of the instruction. Instructions without a source location belong to the same function
+//! as the instruction preceding them.
+
+use crate::{
+    asm_generation::compiler_constants::{DATA_SECTION_REGISTER, SCRATCH_REGISTER},
+    source_map::SourceMap,
+};
+
+use fuel_asm::Opcode;
+use serde::{Deserialize, Serialize};
+
+use std::{collections::HashMap, fmt, path::PathBuf};
+
+/// The name under which the code that does not belong to any Sway function is reported, such as
+/// the program preamble and the contract ABI switch.
+pub const ENTRY_NAME: &str = "<entry>";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SizeReport {
+    /// The size of the whole bytecode in bytes.
+    pub bytecode_size: usize,
+    /// The size of the data section in bytes.
+    pub data_section_size: usize,
+    /// The functions which make up the bytecode, largest first.
+    pub functions: Vec<FunctionSize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FunctionSize {
+    pub name: String,
+    pub path: Option<PathBuf>,
+    /// The number of instructions, including the copies inlined into other functions.
+    pub instructions: usize,
+    pub instruction_bytes: usize,
+    /// The bytes of the data section which are first referenced by this function, plus any words
+    /// among its instructions which do not decode to an instruction, such as the data section
+    /// offset in the preamble.
+    pub data_bytes: usize,
+    /// The base gas of executing each of the instructions once. Instructions which are also
+    /// charged for the memory they use, such as `MCP`, only count their base cost, so this is a
+    /// lower bound of the gas used by a single pass through the function.
+    pub gas: u64,
+    /// Whether the function jumps backwards, in which case its instructions may be executed more
+    /// than once.
+    pub has_loops: bool,
+}
+
+impl FunctionSize {
+    pub fn total_bytes(&self) -> usize {
+        self.instruction_bytes + self.data_bytes
+    }
+}
+
+impl SizeReport {
+    /// Builds the report for `bytecode`, using the function locations and the instruction
+    /// locations recorded in `source_map` when it was compiled.
+    pub fn new(bytecode: &[u8], source_map: &SourceMap) -> SizeReport {
+        // The third and fourth half-words of the preamble hold the offset of the data section.
+        let data_offset = bytecode
+            .get(8..16)
+            .map(|bytes| u64::from_be_bytes(bytes.try_into().unwrap()) as usize)
+            .unwrap_or(bytecode.len())
+            .min(bytecode.len());
+        let data = &bytecode[data_offset..];
+        let read_word = |offset: usize| {
+            data.get(offset..offset + 8)
+                .map(|bytes| u64::from_be_bytes(bytes.try_into().unwrap()))
+        };
+
+        let mut functions = vec![FunctionSize {
+            name: ENTRY_NAME.to_owned(),
+            ..FunctionSize::default()
+        }];
+        let mut function_ixs: HashMap<(String, PathBuf), usize> = HashMap::new();
+        let mut current = 0;
+
+        // The offsets of the referenced data, with the function which referenced them first.
+        let mut data_refs: HashMap<usize, usize> = HashMap::new();
+        // The last instruction, to recognise pointer loads, which are `LW` followed by an `ADD`
+        // of `$ds`.
+        let mut prev_op = Opcode::Undefined;
+        // The target of the next long jump, which is loaded from the data section.
+        let mut long_jump_target = None;
+
+        for (pc, raw) in bytecode[..data_offset].chunks_exact(4).enumerate() {
+            if let Some(function) = source_map.addr_to_function(pc) {
+                let key = (
+                    function.name.clone(),
+                    source_map.path(function.span.path).to_owned(),
+                );
+                current = *function_ixs.entry(key).or_insert_with_key(|(name, path)| {
+                    functions.push(FunctionSize {
+                        name: name.clone(),
+                        path: Some(path.clone()),
+                        ..FunctionSize::default()
+                    });
+                    functions.len() - 1
+                });
+            }
+
+            let function = &mut functions[current];
+            // The data section offset itself is data, as is any other word which does not decode
+            // to an instruction.
+            let op = match Opcode::from_bytes(raw) {
+                Ok(op) if pc != 2 && pc != 3 && !matches!(op, Opcode::Undefined) => op,
+                _ => {
+                    function.data_bytes += 4;
+                    prev_op = Opcode::Undefined;
+                    continue;
+                }
+            };
+            function.instructions += 1;
+            function.instruction_bytes += 4;
+            function.gas += gas_cost(&op);
+
+            let jump_target = match op {
+                Opcode::LW(ra, rb, imm) if rb == DATA_SECTION_REGISTER as usize => {
+                    let offset = imm as usize * 8;
+                    data_refs.entry(offset).or_insert(current);
+                    if ra == SCRATCH_REGISTER as usize {
+                        long_jump_target = read_word(offset);
+                    }
+                    None
+                }
+                Opcode::ADD(ra, rb, rc) if ra == rb && rc == DATA_SECTION_REGISTER as usize => {
+                    if let Opcode::LW(prev_ra, _, imm) = prev_op {
+                        if prev_ra == ra {
+                            if let Some(pointee) = read_word(imm as usize * 8) {
+                                data_refs.entry(pointee as usize).or_insert(current);
+                            }
+                        }
+                    }
+                    None
+                }
+                Opcode::JI(imm) => Some(imm as u64),
+                Opcode::JNEI(_, _, imm) => Some(imm as u64),
+                Opcode::JNZI(_, imm) => Some(imm as u64),
+                Opcode::JMP(ra) | Opcode::JNE(_, _, ra) if ra == SCRATCH_REGISTER as usize => {
+                    long_jump_target
+                }
+                _ => None,
+            };
+            if jump_target.map_or(false, |target| target <= pc as u64) {
+                function.has_loops = true;
+            }
+            prev_op = op;
+        }
+
+        // Each piece of data runs up to the next referenced offset. Anything before the first
+        // reference is not used by any function, so it is attributed to the entry.
+        let mut offsets = data_refs
+            .keys()
+            .copied()
+            .filter(|offset| *offset < data.len())
+            .collect::<Vec<_>>();
+        offsets.sort_unstable();
+        functions[0].data_bytes += offsets.first().copied().unwrap_or(data.len());
+        for (ix, offset) in offsets.iter().enumerate() {
+            let end = offsets.get(ix + 1).copied().unwrap_or(data.len());
+            functions[data_refs[offset]].data_bytes += end - offset;
+        }
+
+        functions.sort_by_key(|function| std::cmp::Reverse(function.total_bytes()));
+        SizeReport {
+            bytecode_size: bytecode.len(),
+            data_section_size: data.len(),
+            functions,
+        }
+    }
+}
+
+impl fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name_width = self
+            .functions
+            .iter()
+            .map(|function| function.name.len())
+            .chain(std::iter::once("function".len()))
+            .max()
+            .unwrap_or_default();
+        writeln!(
+            f,
+            "{:<name_width$} {:>8} {:>8} {:>8} {:>8} {:>10}",
+            "function", "instrs", "code", "data", "total", "base gas"
+        )?;
+        for function in &self.functions {
+            writeln!(
+                f,
+                "{:<name_width$} {:>8} {:>8} {:>8} {:>8} {:>10}",
+                function.name,
+                function.instructions,
+                function.instruction_bytes,
+                function.data_bytes,
+                function.total_bytes(),
+                if function.has_loops {
+                    format!("{}*", function.gas)
+                } else {
+                    function.gas.to_string()
+                }
+            )?;
+        }
+        writeln!(
+            f,
+            "{:<name_width$} {:>8} {:>8} {:>8} {:>8}",
+            "total",
+            "",
+            self.bytecode_size - self.data_section_size,
+            self.data_section_size,
+            self.bytecode_size
+        )?;
+        writeln!(
+            f,
+            "base gas excludes the cost of the memory used by instructions such as MCP, so it is \
+             a lower bound"
+        )?;
+        if self.functions.iter().any(|function| function.has_loops) {
+            writeln!(
+                f,
+                "* contains loops, the gas is for executing each instruction once"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The base gas charged for executing `op`. The cost of opcodes which are also charged for the
+/// memory they use, such as `MCP` or `LDC`, depends on a length which is usually only known at
+/// run time, so they only count their base cost and the result is a lower bound.
+fn gas_cost(op: &Opcode) -> u64 {
+    use fuel_vm::gas::consts::*;
+    use Opcode::*;
+    match op {
+        ADD(..) => GAS_ADD,
+        ADDI(..) => GAS_ADDI,
+        ALOC(..) => GAS_ALOC,
+        AND(..) => GAS_AND,
+        ANDI(..) => GAS_ANDI,
+        BAL(..) => GAS_BAL,
+        BHEI(..) => GAS_BHEI,
+        BHSH(..) => GAS_BHSH,
+        BURN(..) => GAS_BURN,
+        CALL(..) => GAS_CALL,
+        CB(..) => GAS_CB,
+        CCP(..) => GAS_CCP,
+        CFEI(..) => GAS_CFEI,
+        CFSI(..) => GAS_CFSI,
+        CROO(..) => GAS_CROO,
+        CSIZ(..) => GAS_CSIZ,
+        DIV(..) => GAS_DIV,
+        DIVI(..) => GAS_DIVI,
+        ECR(..) => GAS_ECR,
+        EQ(..) => GAS_EQ,
+        EXP(..) => GAS_EXP,
+        EXPI(..) => GAS_EXPI,
+        FLAG(..) => GAS_FLAG,
+        GM(..) => GAS_GM,
+        GT(..) => GAS_GT,
+        GTF(..) => GAS_GTF,
+        JI(..) => GAS_JI,
+        JMP(..) => GAS_JMP,
+        JNE(..) => GAS_JNE,
+        JNEI(..) => GAS_JNEI,
+        JNZI(..) => GAS_JNZI,
+        K256(..) => GAS_K256,
+        LB(..) => GAS_LB,
+        LDC(..) => GAS_LDC,
+        LOG(..) => GAS_LOG,
+        LOGD(..) => GAS_LOGD,
+        LT(..) => GAS_LT,
+        LW(..) => GAS_LW,
+        MCL(..) => GAS_MCL,
+        MCLI(..) => GAS_MCLI,
+        MCP(..) => GAS_MCP,
+        MCPI(..) => GAS_MCPI,
+        MEQ(..) => GAS_MEQ,
+        MINT(..) => GAS_MINT,
+        MLOG(..) => GAS_MLOG,
+        MOD(..) => GAS_MOD,
+        MODI(..) => GAS_MODI,
+        MOVE(..) => GAS_MOVE,
+        MOVI(..) => GAS_MOVI,
+        MROO(..) => GAS_MROO,
+        MUL(..) => GAS_MUL,
+        MULI(..) => GAS_MULI,
+        NOOP => GAS_NOOP,
+        NOT(..) => GAS_NOT,
+        OR(..) => GAS_OR,
+        ORI(..) => GAS_ORI,
+        RET(..) => GAS_RET,
+        RETD(..) => GAS_RETD,
+        RVRT(..) => GAS_RVRT,
+        S256(..) => GAS_S256,
+        SB(..) => GAS_SB,
+        SLL(..) => GAS_SLL,
+        SLLI(..) => GAS_SLLI,
+        SMO(..) => GAS_SMO,
+        SRL(..) => GAS_SRL,
+        SRLI(..) => GAS_SRLI,
+        SRW(..) => GAS_SRW,
+        SRWQ(..) => GAS_SRWQ,
+        SUB(..) => GAS_SUB,
+        SUBI(..) => GAS_SUBI,
+        SW(..) => GAS_SW,
+        SWW(..) => GAS_SWW,
+        SWWQ(..) => GAS_SWWQ,
+        TR(..) => GAS_TR,
+        TRO(..) => GAS_TRO,
+        XIL(..) => GAS_XIL,
+        XIS(..) => GAS_XIS,
+        XOL(..) => GAS_XOL,
+        XOR(..) => GAS_XOR,
+        XORI(..) => GAS_XORI,
+        XOS(..) => GAS_XOS,
+        XWL(..) => GAS_XWL,
+        XWS(..) => GAS_XWS,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Read, path::PathBuf, sync::Arc};
+    use sway_types::span::Span;
+
+    #[test]
+    fn instructions_and_data_are_attributed_to_functions() {
+        let ds = DATA_SECTION_REGISTER as usize;
+        let ops = [
+            Opcode::JI(4),
+            Opcode::NOOP,
+            Opcode::Undefined,
+            Opcode::Undefined,
+            Opcode::LW(0x10, ds, 0),
+            Opcode::JI(4),
+            Opcode::RET(0x10),
+            Opcode::NOOP,
+        ];
+        let mut bytecode = vec![0; ops.len() * 4];
+        for (ix, mut op) in ops.into_iter().enumerate() {
+            op.read_exact(&mut bytecode[ix * 4..ix * 4 + 4]).unwrap();
+        }
+        bytecode[8..16].copy_from_slice(&32u64.to_be_bytes());
+        // A word in `main` which is not an instruction.
+        bytecode[28..32].copy_from_slice(&[0xff; 4]);
+        bytecode.extend(42u64.to_be_bytes());
+
+        let src: Arc<str> = " ".repeat(100).into();
+        let path = Some(Arc::new(PathBuf::from("main.sw")));
+        let span = |start, end| Span::new(src.clone(), start, end, path.clone()).unwrap();
+        let mut source_map = SourceMap::new();
+        source_map.insert_function("main", &span(0, 100));
+        source_map.insert_function("helper", &span(10, 20));
+        source_map.insert(4, &span(12, 14));
+        source_map.insert(5, &span(50, 60));
+        source_map.insert(6, &span(50, 60));
+
+        let report = SizeReport::new(&bytecode, &source_map);
+        assert_eq!(report.bytecode_size, 40);
+        assert_eq!(report.data_section_size, 8);
+        let function = |name| {
+            report
+                .functions
+                .iter()
+                .find(|function| function.name == name)
+                .unwrap()
+        };
+        let entry = function(ENTRY_NAME);
+        assert_eq!((entry.instructions, entry.data_bytes), (2, 8));
+        let helper = function("helper");
+        assert_eq!((helper.instructions, helper.data_bytes), (1, 8));
+        assert!(!helper.has_loops);
+        let main = function("main");
+        assert_eq!((main.instructions, main.data_bytes), (2, 4));
+        assert!(main.has_loops);
+    }
+}
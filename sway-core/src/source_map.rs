@@ -1,5 +1,6 @@
 use dirs::home_dir;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
@@ -20,6 +21,14 @@ pub struct SourceMap {
     paths: Vec<PathBuf>,
     /// Mapping from opcode index to source location
     map: HashMap<usize, SourceMapSpan>,
+    /// Source locations of the functions compiled into the program, used to attribute opcodes
+    /// to the function they came from
+    #[serde(default)]
+    functions: Vec<SourceMapFunction>,
+    /// The local variables of the program, where they are stored and the opcodes for which they
+    /// are in scope
+    #[serde(default)]
+    variables: Vec<SourceMapVariable>,
 }
 impl SourceMap {
     pub fn new() -> Self {
@@ -38,28 +47,107 @@ impl SourceMap {
     }
 
     pub fn insert(&mut self, pc: usize, span: &Span) {
-        if let Some(path) = span.path() {
-            let path_index = self
-                .paths
-                .iter()
-                .position(|p| *p == **path)
-                .unwrap_or_else(|| {
-                    self.paths.push((**path).to_owned());
-                    self.paths.len() - 1
+        if let Some(sms) = self.to_source_map_span(span) {
+            self.map.insert(pc, sms);
+        }
+    }
+
+    /// Inserts the location of a function. Functions sharing a location, such as the
+    /// monomorphized copies of a generic function, are only recorded once.
+    pub fn insert_function(&mut self, name: &str, span: &Span) {
+        if let Some(sms) = self.to_source_map_span(span) {
+            if !self.functions.iter().any(|f| f.span == sms) {
+                self.functions.push(SourceMapFunction {
+                    name: name.to_owned(),
+                    span: sms,
                 });
-            self.map.insert(
-                pc,
-                SourceMapSpan {
-                    path: PathIndex(path_index),
-                    range: LocationRange {
-                        start: span.start(),
-                        end: span.end(),
-                    },
-                },
-            );
+            }
+        }
+    }
+
+    /// Inserts a local variable declared at `span`, which is in scope for the opcodes in `pcs`.
+    pub fn insert_variable(
+        &mut self,
+        name: &str,
+        ty: &str,
+        span: &Span,
+        location: VariableLocation,
+        pcs: Range<usize>,
+    ) {
+        if let Some(sms) = self.to_source_map_span(span) {
+            self.variables.push(SourceMapVariable {
+                name: name.to_owned(),
+                ty: ty.to_owned(),
+                span: sms,
+                location,
+                pcs,
+            });
         }
     }
 
+    fn to_source_map_span(&mut self, span: &Span) -> Option<SourceMapSpan> {
+        let path = span.path()?;
+        let path_index = self
+            .paths
+            .iter()
+            .position(|p| *p == **path)
+            .unwrap_or_else(|| {
+                self.paths.push((**path).to_owned());
+                self.paths.len() - 1
+            });
+        Some(SourceMapSpan {
+            path: PathIndex(path_index),
+            range: LocationRange {
+                start: span.start(),
+                end: span.end(),
+            },
+        })
+    }
+
+    /// The innermost function whose location contains the source location of the opcode at `pc`.
+    pub fn addr_to_function(&self, pc: usize) -> Option<&SourceMapFunction> {
+        let sms = self.map.get(&pc)?;
+        self.functions
+            .iter()
+            .filter(|f| {
+                f.span.path == sms.path
+                    && f.span.range.start <= sms.range.start
+                    && sms.range.end <= f.span.range.end
+            })
+            .min_by_key(|f| f.span.range.end - f.span.range.start)
+    }
+
+    /// Inverse of [SourceMap::addr_to_span]: the opcodes, in order, whose source locations start
+    /// within `range` of the file at `path`.  The path only has to match the end of the paths
+    /// given to the compiler, so it may be relative to the project.
+    pub fn span_to_addrs(&self, path: &Path, range: LocationRange) -> Vec<usize> {
+        let mut pcs = self
+            .map
+            .keys()
+            .copied()
+            .filter(|pc| match self.addr_to_span(*pc) {
+                Some((pc_path, pc_range)) => {
+                    pc_path.ends_with(path)
+                        && range.start <= pc_range.start
+                        && pc_range.start < range.end
+                }
+                None => false,
+            })
+            .collect::<Vec<_>>();
+        pcs.sort_unstable();
+        pcs
+    }
+
+    /// The local variables which are in scope at the opcode at `pc`.
+    pub fn addr_to_variables(&self, pc: usize) -> impl Iterator<Item = &SourceMapVariable> {
+        self.variables.iter().filter(move |v| v.pcs.contains(&pc))
+    }
+
+    /// The path of a source file, as it was given to the compiler.
+    pub fn path(&self, path_index: PathIndex) -> &Path {
+        &self.paths[path_index.0]
+    }
+
     /// Inverse source mapping
     pub fn addr_to_span(&self, pc: usize) -> Option<(PathBuf, LocationRange)> {
         self.map.get(&pc).map(|sms| {
@@ -81,13 +169,42 @@ impl SourceMap {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SourceMapSpan {
     pub path: PathIndex,
     pub range: LocationRange,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceMapFunction {
+    pub name: String,
+    pub span: SourceMapSpan,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceMapVariable {
+    pub name: String,
+    /// The type of the variable, as written in the source.
+    pub ty: String,
+    /// The declaration of the variable.
+    pub span: SourceMapSpan,
+    pub location: VariableLocation,
+    /// The opcodes for which the variable is in scope.
+    pub pcs: Range<usize>,
+}
+
+/// Where the value of a variable is kept at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VariableLocation {
+    /// In memory, `offset` bytes above the stack start pointer `$ssp`.
+    Stack { offset: u64 },
+    /// In memory, `offset` bytes above the address held in the register with the id `register`.
+    Memory { register: u8, offset: u64 },
+    /// In the register with the id `register`.
+    Register { register: u8 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LocationRange {
     pub start: usize,
     pub end: usize,
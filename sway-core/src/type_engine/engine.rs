@@ -215,6 +215,41 @@ impl Engine {
                 (warnings, errors)
             }
 
+            (
+                Function {
+                    parameters: received_parameters,
+                    return_type: received_return_type,
+                },
+                Function {
+                    parameters: expected_parameters,
+                    return_type: expected_return_type,
+                },
+            ) if received_parameters.len() == expected_parameters.len() => {
+                let mut warnings = vec![];
+                let mut errors = vec![];
+                for (received_parameter, expected_parameter) in
+                    received_parameters.iter().zip(expected_parameters.iter())
+                {
+                    let (new_warnings, new_errors) = self.unify(
+                        received_parameter.type_id,
+                        expected_parameter.type_id,
+                        &received_parameter.span,
+                        help_text.clone(),
+                    );
+                    warnings.extend(new_warnings);
+                    errors.extend(new_errors);
+                }
+                let (new_warnings, new_errors) = self.unify(
+                    received_return_type.type_id,
+                    expected_return_type.type_id,
+                    &received_return_type.span,
+                    help_text,
+                );
+                warnings.extend(new_warnings);
+                errors.extend(new_errors);
+                (warnings, errors)
+            }
+
             (UnsignedInteger(received_width), UnsignedInteger(expected_width)) => {
                 // E.g., in a variable declaration `let a: u32 = 10u64` the 'expected' type will be
                 // the annotation `u32`, and the 'received' type is 'self' of the initialiser, or
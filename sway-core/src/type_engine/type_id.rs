@@ -139,6 +139,15 @@ impl ReplaceSelfType for TypeId {
                     field.replace_self_type(self_type);
                 }
             }
+            TypeInfo::Function {
+                mut parameters,
+                mut return_type,
+            } => {
+                for parameter in parameters.iter_mut() {
+                    parameter.replace_self_type(self_type);
+                }
+                return_type.replace_self_type(self_type);
+            }
             TypeInfo::Unknown
             | TypeInfo::UnknownGeneric { .. }
             | TypeInfo::Str(_)
@@ -86,6 +86,11 @@ pub enum TypeInfo {
     Storage {
         fields: Vec<TypedStructField>,
     },
+    /// A function pointer type, e.g. `fn(u64, bool) -> u64`.
+    Function {
+        parameters: Vec<TypeArgument>,
+        return_type: TypeArgument,
+    },
 }
 
 // NOTE: Hash and PartialEq must uphold the invariant:
@@ -184,6 +189,14 @@ impl Hash for TypeInfo {
                 state.write_u8(19);
                 fields.hash(state);
             }
+            TypeInfo::Function {
+                parameters,
+                return_type,
+            } => {
+                state.write_u8(20);
+                parameters.hash(state);
+                return_type.hash(state);
+            }
         }
     }
 }
@@ -265,6 +278,16 @@ impl PartialEq for TypeInfo {
             (TypeInfo::Storage { fields: l_fields }, TypeInfo::Storage { fields: r_fields }) => {
                 l_fields == r_fields
             }
+            (
+                Self::Function {
+                    parameters: l_parameters,
+                    return_type: l_return_type,
+                },
+                Self::Function {
+                    parameters: r_parameters,
+                    return_type: r_return_type,
+                },
+            ) => l_parameters == r_parameters && l_return_type == r_return_type,
             _ => false,
         }
     }
@@ -329,6 +352,16 @@ impl fmt::Display for TypeInfo {
             }
             Array(elem_ty, count) => format!("[{}; {}]", elem_ty, count),
             Storage { .. } => "contract storage".into(),
+            Function {
+                parameters,
+                return_type,
+            } => {
+                let parameter_strs = parameters
+                    .iter()
+                    .map(|parameter| parameter.to_string())
+                    .collect::<Vec<String>>();
+                format!("fn({}) -> {}", parameter_strs.join(", "), return_type)
+            }
         };
         write!(f, "{}", s)
     }
@@ -375,6 +408,20 @@ impl JsonAbiString for TypeInfo {
             }
             Array(elem_ty, count) => format!("[{}; {}]", elem_ty.json_abi_str(), count),
             Storage { .. } => "contract storage".into(),
+            Function {
+                parameters,
+                return_type,
+            } => {
+                let parameter_strs = parameters
+                    .iter()
+                    .map(|parameter| parameter.json_abi_str())
+                    .collect::<Vec<String>>();
+                format!(
+                    "fn({}) -> {}",
+                    parameter_strs.join(", "),
+                    return_type.json_abi_str()
+                )
+            }
         }
     }
 }
@@ -620,7 +667,10 @@ impl TypeInfo {
     }
 
     pub fn is_copy_type(&self) -> bool {
-        matches!(self, TypeInfo::Boolean | TypeInfo::UnsignedInteger(_)) || self.is_unit()
+        matches!(
+            self,
+            TypeInfo::Boolean | TypeInfo::UnsignedInteger(_) | TypeInfo::Function { .. }
+        ) || self.is_unit()
     }
 
     pub(crate) fn apply_type_arguments(
@@ -673,7 +723,8 @@ impl TypeInfo {
             | TypeInfo::Contract
             | TypeInfo::ErrorRecovery
             | TypeInfo::Array(_, _)
-            | TypeInfo::Storage { .. } => {
+            | TypeInfo::Storage { .. }
+            | TypeInfo::Function { .. } => {
                 errors.push(CompileError::TypeArgumentsNotAllowed { span: span.clone() });
                 err(warnings, errors)
             }
@@ -802,6 +853,39 @@ impl TypeInfo {
                     Some(insert_type(TypeInfo::Tuple(new_fields)))
                 }
             }
+            TypeInfo::Function {
+                parameters,
+                return_type,
+            } => {
+                let map_type_argument = |type_argument: &TypeArgument| {
+                    look_up_type_id(type_argument.type_id)
+                        .matches_type_parameter(mapping)
+                        .map(|matching_id| TypeArgument {
+                            type_id: insert_type(TypeInfo::Ref(
+                                matching_id,
+                                type_argument.span.clone(),
+                            )),
+                            span: type_argument.span.clone(),
+                        })
+                };
+                let new_parameters = parameters
+                    .iter()
+                    .map(&map_type_argument)
+                    .collect::<Vec<_>>();
+                let new_return_type = map_type_argument(return_type);
+                if new_return_type.is_none() && new_parameters.iter().all(Option::is_none) {
+                    None
+                } else {
+                    Some(insert_type(TypeInfo::Function {
+                        parameters: new_parameters
+                            .into_iter()
+                            .zip(parameters.iter())
+                            .map(|(new, old)| new.unwrap_or_else(|| old.clone()))
+                            .collect(),
+                        return_type: new_return_type.unwrap_or_else(|| return_type.clone()),
+                    }))
+                }
+            }
             Unknown
             | Str(..)
             | UnsignedInteger(..)
@@ -847,7 +931,8 @@ impl TypeInfo {
             | TypeInfo::Contract
             | TypeInfo::ErrorRecovery
             | TypeInfo::Array(_, _)
-            | TypeInfo::Storage { .. } => {
+            | TypeInfo::Storage { .. }
+            | TypeInfo::Function { .. } => {
                 errors.push(CompileError::Unimplemented(
                     "matching on this type is unsupported right now",
                     span.clone(),
@@ -952,6 +1037,20 @@ impl TypeInfo {
                     all_nested_types.append(&mut nested_types);
                 }
             }
+            TypeInfo::Function {
+                parameters,
+                return_type,
+            } => {
+                for type_argument in parameters.iter().chain(std::iter::once(&return_type)) {
+                    let mut nested_types = check!(
+                        look_up_type_id(type_argument.type_id).extract_nested_types(span),
+                        return err(warnings, errors),
+                        warnings,
+                        errors
+                    );
+                    all_nested_types.append(&mut nested_types);
+                }
+            }
             TypeInfo::Unknown
             | TypeInfo::UnknownGeneric { .. }
             | TypeInfo::Str(_)
@@ -104,6 +104,27 @@ pub(crate) fn create_type_mapping(superset_type: TypeId, subset_type: TypeId) ->
             let type_arguments = type_arguments.iter().map(|x| x.type_id).collect::<Vec<_>>();
             insert_type_parameters_with_type_arguments(type_parameters, type_arguments)
         }
+        (
+            TypeInfo::Function {
+                parameters: type_parameters,
+                return_type: return_type_parameter,
+            },
+            TypeInfo::Function {
+                parameters: type_arguments,
+                return_type: return_type_argument,
+            },
+        ) => insert_type_parameters_with_type_arguments(
+            type_parameters
+                .iter()
+                .chain(std::iter::once(&return_type_parameter))
+                .map(|x| x.type_id)
+                .collect::<Vec<_>>(),
+            type_arguments
+                .iter()
+                .chain(std::iter::once(&return_type_argument))
+                .map(|x| x.type_id)
+                .collect::<Vec<_>>(),
+        ),
         (TypeInfo::Unknown, TypeInfo::Unknown)
         | (TypeInfo::Boolean, TypeInfo::Boolean)
         | (TypeInfo::SelfType, TypeInfo::SelfType)
@@ -119,6 +140,22 @@ pub(crate) fn create_type_mapping(superset_type: TypeId, subset_type: TypeId) ->
     }
 }
 
+/// Monomorphizes `type_ids` with `type_mapping`, e.g. the type arguments of the trait in
+/// `impl<T> Into<T> for Wrapper<T>` when the impl is used for `Wrapper<u64>`.
+pub(crate) fn monomorphize_type_ids(
+    type_ids: &[TypeId],
+    type_mapping: &TypeMapping,
+) -> Vec<TypeId> {
+    type_ids
+        .iter()
+        .map(|type_id| {
+            look_up_type_id(*type_id)
+                .matches_type_parameter(type_mapping)
+                .unwrap_or(*type_id)
+        })
+        .collect()
+}
+
 fn insert_type_parameters_with_type_arguments(
     type_parameters: Vec<TypeId>,
     type_arguments: Vec<TypeId>,
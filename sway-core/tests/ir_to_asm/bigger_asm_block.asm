@@ -21,3 +21,4 @@ noop                          ; word-alignment of data section
 .data:
 data_0 .b256 0x0202020202020202020202020202020202020202020202020202020202020202
 data_1 .b256 0x0303030303030303030303030303030303030303030303030303030303030303
+;; 2 entries (0 word-sized), 64 bytes
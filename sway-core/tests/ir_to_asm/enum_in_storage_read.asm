@@ -85,3 +85,4 @@ data_4 .b256 0x12ea9b9b05214a0d64996d259c59202b80a21415bb68b83121353e2a5925ec47
 data_5 .b256 0x12ea9b9b05214a0d64996d259c59202b80a21415bb68b83121353e2a5925ec48
 data_6 .u64 0x60
 data_7 .u32 0x1665bf4
+;; 8 entries (2 word-sized), 208 bytes
@@ -92,3 +92,4 @@ data_5 .b256 0x2817e0819d6fcad797114fbcf350fa281aca33a39b0abf977797bddd69b8e7af
 data_6 .b256 0x12ea9b9b05214a0d64996d259c59202b80a21415bb68b83121353e2a5925ec47
 data_7 .b256 0x12ea9b9b05214a0d64996d259c59202b80a21415bb68b83121353e2a5925ec48
 data_8 .u32 0xc1c7877c
+;; 9 entries (3 word-sized), 216 bytes
@@ -27,3 +27,4 @@ data_0 .u64 0x01
 data_1 .u64 0x2a
 data_2 .u64 0x42
 data_3 .u64 0x48
+;; 4 entries (4 word-sized), 32 bytes
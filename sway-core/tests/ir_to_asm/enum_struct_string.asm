@@ -24,7 +24,7 @@ addi $r0 $r1 i0               ; get struct field(s) 0 offset
 mcpi $r0 $r2 i32              ; store struct field value
 lw   $r0 data_3               ; literal instantiation
 sw   $r1 $r0 i4               ; insert_value @ 1
-lw   $r0 data_4               ; literal instantiation
+lw   $r0 data_0               ; literal instantiation
 sw   $r1 $r0 i5               ; insert_value @ 2
 addi $r0 $r3 i8               ; get struct field(s) 1 offset
 mcpi $r0 $r1 i48              ; store struct field value
@@ -47,4 +47,4 @@ data_0 .u64 0x00
 data_1 .str " an odd length"
 data_2 .u64 0x14
 data_3 .u64 0x0a
-data_4 .bool 0x00
+;; 4 entries (3 word-sized), 48 bytes
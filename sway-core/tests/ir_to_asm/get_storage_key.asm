@@ -26,3 +26,4 @@ data_1 .u64 0x20
 data_2 .b256 0xde9090cb50e71c2588c773487d1da7066d0c719849a7e58dc8b6397a25c567c0
 data_3 .u32 0x2994c98e
 data_4 .u32 0xf57bdec8
+;; 5 entries (3 word-sized), 88 bytes
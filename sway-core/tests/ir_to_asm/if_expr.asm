@@ -16,3 +16,4 @@ ret  $r0
 data_0 .bool 0x00
 data_1 .u64 0xf4240
 data_2 .u64 0x2a
+;; 3 entries (3 word-sized), 24 bytes
@@ -10,3 +10,4 @@ ret  $r0
 noop                          ; word-alignment of data section
 .data:
 data_0 .u64 0x2a
+;; 1 entries (1 word-sized), 8 bytes
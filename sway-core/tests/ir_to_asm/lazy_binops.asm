@@ -19,3 +19,4 @@ noop                          ; word-alignment of data section
 .data:
 data_0 .bool 0x00
 data_1 .bool 0x01
+;; 2 entries (2 word-sized), 16 bytes
@@ -29,3 +29,4 @@ noop                          ; word-alignment of data section
 .data:
 data_0 .bool 0x01
 data_1 .bool 0x00
+;; 2 entries (2 word-sized), 16 bytes
@@ -26,3 +26,4 @@ ret  $r0
 data_0 .u64 0x28
 data_1 .u64 0x02
 data_2 .u64 0x32
+;; 3 entries (3 word-sized), 24 bytes
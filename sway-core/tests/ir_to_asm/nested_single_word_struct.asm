@@ -16,3 +16,4 @@ lw   $r0 $r0 i0               ; extract_value @ 0
 ret  $r0
 .data:
 data_0 .u32 0x495d4a23
+;; 1 entries (1 word-sized), 8 bytes
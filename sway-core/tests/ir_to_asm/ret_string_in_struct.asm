@@ -35,3 +35,4 @@ data_2 .str "foobarbaz"
 data_3 .u64 0x10
 data_4 .u32 0x4a13be00
 data_5 .u32 0x29ea7974
+;; 6 entries (4 word-sized), 56 bytes
@@ -38,3 +38,4 @@ data_1 .u64 0x28
 data_2 .u32 0x9890aef4
 data_3 .u32 0x42123b96
 data_4 .u32 0xfc62d029
+;; 5 entries (5 word-sized), 40 bytes
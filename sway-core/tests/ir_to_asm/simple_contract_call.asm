@@ -85,3 +85,4 @@ data_8 .u64 0x4e20
 data_9 .u64 0x15b3
 data_10 .b256 0x5555555555555555555555555555555555555555555555555555555555555555
 data_11 .u64 0xfc62d029
+;; 12 entries (8 word-sized), 192 bytes
@@ -26,3 +26,4 @@ ret  $zero                    ; returning unit as zero
 data_0 .u64 0x01
 data_1 .u64 0x02
 data_2 .u64 0x03
+;; 3 entries (3 word-sized), 24 bytes
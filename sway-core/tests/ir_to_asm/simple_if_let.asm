@@ -18,7 +18,7 @@ addi $r0 $r3 i8               ; get store offset
 mcpi $r0 $r1 i16              ; store value
 addi $r2 $r3 i8               ; get offset reg for get_ptr
 lw   $r1 $r2 i0               ; extract_value @ 0
-lw   $r0 data_2               ; literal instantiation
+lw   $r0 data_1               ; literal instantiation
 eq   $r0 $r1 $r0
 jnzi $r0 i23
 ji   i29
@@ -33,4 +33,4 @@ ret  $r0
 .data:
 data_0 .u64 0x00
 data_1 .bool 0x01
-data_2 .u64 0x01
+;; 2 entries (2 word-sized), 16 bytes
@@ -23,3 +23,4 @@ noop                          ; word-alignment of data section
 .data:
 data_0 .u64 0x28
 data_1 .u64 0x02
+;; 2 entries (2 word-sized), 16 bytes
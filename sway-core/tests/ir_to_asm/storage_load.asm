@@ -46,3 +46,4 @@ data_1 .b256 0xa15d6d36b54df993ed1fbe4544a45d4c4f70d81b4229861dfde0e20eb652202c
 data_2 .u64 0x20
 data_3 .u32 0x8e277065
 data_4 .u32 0x449e8e93
+;; 5 entries (3 word-sized), 88 bytes
@@ -49,3 +49,4 @@ data_2 .b256 0x0000000000000000000000000000000000000000000000000000000000000000
 data_3 .b256 0xa15d6d36b54df993ed1fbe4544a45d4c4f70d81b4229861dfde0e20eb652202c
 data_4 .u32 0x1b9b478f
 data_5 .u32 0x858a3d18
+;; 6 entries (3 word-sized), 120 bytes
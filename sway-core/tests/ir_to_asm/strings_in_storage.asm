@@ -64,3 +64,4 @@ data_1 .b256 0xf383b0ce51358be57daa3b725fe44acdb2d880604e367199080b4379c41bb6ee
 data_2 .u64 0x28
 data_3 .u32 0xe63a9733
 data_4 .u32 0xb8c27db9
+;; 5 entries (3 word-sized), 88 bytes
@@ -36,3 +36,4 @@ noop                          ; word-alignment of data section
 data_0 .u64 0x2a
 data_1 .u64 0x01
 data_2 .u64 0x00
+;; 3 entries (3 word-sized), 24 bytes
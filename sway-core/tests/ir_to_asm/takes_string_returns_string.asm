@@ -25,3 +25,4 @@ data_0 .u64 0x08
 data_1 .u64 0x10
 data_2 .u32 0x80da70e2
 data_3 .u32 0x28c0f699
+;; 4 entries (4 word-sized), 32 bytes
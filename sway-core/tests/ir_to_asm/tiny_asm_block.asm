@@ -9,3 +9,4 @@ bhei $r0                      ; asm block
 ret  $r0
 noop                          ; word-alignment of data section
 .data:
+;; 0 entries (0 word-sized), 0 bytes
@@ -1,8 +1,12 @@
 use crate::utils::{
-    comments::handle_comments, indent_style::Shape, newline_style::apply_newline_style,
-    program_type::insert_program_type,
+    comments::handle_comments,
+    diff::{line_edits, TextEdit},
+    imports::{ends_import_group, rewrite_imports},
+    indent_style::Shape,
+    newline_style::apply_newline_style,
+    program_type::{insert_dependencies, insert_module_attributes, insert_program_type},
 };
-use std::{path::Path, sync::Arc};
+use std::{ops::Range, path::Path, sync::Arc};
 use sway_core::BuildConfig;
 
 pub use crate::{
@@ -44,6 +48,15 @@ impl Formatter {
         let path = build_config.map(|build_config| build_config.canonical_root_module());
         let src_len = src.len();
         let module = sway_parse::parse_file(src.clone(), path.clone())?;
+        // Sort, group and merge the imports, then format the rewritten source
+        let (src, module) = match rewrite_imports(&src, &module, &self.config)? {
+            Some(rewritten) => {
+                let src = Arc::from(rewritten);
+                let module = sway_parse::parse_file(src.clone(), path.clone())?;
+                (src, module)
+            }
+            None => (src, module),
+        };
         // Get parsed items
         let items = &module.items;
         // Get the program type (script, predicate, contract or library)
@@ -55,8 +68,11 @@ impl Formatter {
         // which will reduce the number of reallocations
         let mut raw_formatted_code = String::with_capacity(src_len);
 
-        // Insert program type to the formatted code.
+        // Insert the attributes of the module and its program type to the formatted code.
+        insert_module_attributes(&mut raw_formatted_code, &module.attribute_list, self)?;
         insert_program_type(&mut raw_formatted_code, program_type)?;
+        // Insert the dependencies of the module.
+        insert_dependencies(&mut raw_formatted_code, &module.dependencies)?;
 
         // Insert parsed & formatted items into the formatted code.
         let mut iter = items.iter().peekable();
@@ -65,6 +81,10 @@ impl Formatter {
             item.format(&mut raw_formatted_code, self)?;
             if iter.peek().is_some() {
                 raw_formatted_code.push('\n');
+                // Keep the blank lines separating groups of imports.
+                if ends_import_group(&src, item) {
+                    raw_formatted_code.push('\n');
+                }
             }
         }
 
@@ -89,13 +109,36 @@ impl Formatter {
 
         Ok(formatted_code)
     }
+    /// Formats `src`, but only returns the changes to the lines touching the byte `range`.
+    ///
+    /// The edits are sorted and refer to byte ranges of `src`, so they can be applied in reverse
+    /// order. An empty `range` formats the line it is on, which is useful for on-type formatting.
+    pub fn format_range(
+        &mut self,
+        src: Arc<str>,
+        build_config: Option<&BuildConfig>,
+        range: Range<usize>,
+    ) -> Result<Vec<TextEdit>, FormatterError> {
+        let formatted_code = self.format(src.clone(), build_config)?;
+        Ok(line_edits(&src, &formatted_code)
+            .into_iter()
+            .filter(|edit| edit.overlaps(&range))
+            .collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Formatter;
-    use crate::config::user_def::FieldAlignment;
-    use std::sync::Arc;
+    use super::{Formatter, TextEdit};
+    use crate::config::{
+        imports::{GroupImports, ImportGranularity},
+        items::ItemBraceStyle,
+        user_def::FieldAlignment,
+    };
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
 
     #[test]
     fn test_const() {
@@ -425,6 +468,31 @@ fn goodbye() -> usize {
         assert_eq!(correct_sway_code, formatted_sway_code)
     }
     #[test]
+    fn test_format_range() {
+        let sway_code_to_format = r#"contract;
+
+struct Foo {
+    x: u64,
+}
+struct Bar { y:u64 }
+struct Baz {
+    z:bool,
+}"#;
+        let start = sway_code_to_format.find("struct Bar").unwrap();
+        let range = start..start + "struct Bar { y:u64 }".len();
+        let mut formatter = Formatter::default();
+        let edits = formatter
+            .format_range(Arc::from(sway_code_to_format), None, range)
+            .unwrap();
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: start..start + "struct Bar { y:u64 }\n".len(),
+                new_text: "struct Bar {\n    y: u64,\n}\n".to_owned(),
+            }]
+        )
+    }
+    #[test]
     fn test_same_line_where() {
         let sway_code_to_format = r#"contract;
 
@@ -725,4 +793,228 @@ impl<A, B> Qux<A, B> for Foo where
             Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
         assert_eq!(correct_sway_code, formatted_sway_code)
     }
+    #[test]
+    fn test_use() {
+        let sway_code_to_format = r#"contract;
+
+use std::{address::*,assert::assert,hash::{sha256,keccak256 as k,}};
+use ::core::ops::Eq;
+pub   use foo::bar  ;"#;
+        let correct_sway_code = r#"contract;
+
+use std::{address::*, assert::assert, hash::{sha256, keccak256 as k}};
+use ::core::ops::Eq;
+pub use foo::bar;"#;
+        let mut formatter = Formatter::default();
+        formatter.config.ordering.reorder_imports = false;
+        let formatted_sway_code =
+            Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
+        assert_eq!(correct_sway_code, formatted_sway_code)
+    }
+    #[test]
+    fn test_reorder_imports() {
+        let sway_code_to_format = r#"library test;
+
+use std::hash::sha256;
+// The storage helpers.
+use std::storage::*;
+use foo::{b, a};
+use std::hash::sha256;"#;
+        let correct_sway_code = r#"library test;
+
+use foo::{a, b};
+use std::hash::sha256;
+// The storage helpers.
+use std::storage::*;"#;
+        let mut formatter = Formatter::default();
+        let formatted_sway_code =
+            Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
+        assert_eq!(correct_sway_code, formatted_sway_code)
+    }
+    #[test]
+    fn test_group_imports_std_external_crate() {
+        let sway_code_to_format = r#"library test;
+
+dep utils;
+
+use utils::helper;
+use foo::Bar;
+use std::hash::sha256;"#;
+        let correct_sway_code = r#"library test;
+
+dep utils;
+
+use std::hash::sha256;
+
+use foo::Bar;
+
+use utils::helper;"#;
+        let mut formatter = Formatter::default();
+        formatter.config.imports.group_imports = GroupImports::StdExternalCrate;
+        let formatted_sway_code =
+            Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
+        assert_eq!(correct_sway_code, formatted_sway_code)
+    }
+    #[test]
+    fn test_imports_granularity() {
+        let sway_code_to_format = r#"library test;
+
+use std::hash::sha256;
+use std::{hash::keccak256, storage::*};"#;
+        let expected = [
+            (
+                ImportGranularity::Crate,
+                r#"library test;
+
+use std::{hash::{keccak256, sha256}, storage::*};"#,
+            ),
+            (
+                ImportGranularity::Module,
+                r#"library test;
+
+use std::hash::{keccak256, sha256};
+use std::storage::*;"#,
+            ),
+            (
+                ImportGranularity::Item,
+                r#"library test;
+
+use std::hash::keccak256;
+use std::hash::sha256;
+use std::storage::*;"#,
+            ),
+        ];
+        for (granularity, correct_sway_code) in expected {
+            let mut formatter = Formatter::default();
+            formatter.config.imports.imports_granularity = granularity;
+            let formatted_sway_code =
+                Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
+            assert_eq!(correct_sway_code, formatted_sway_code)
+        }
+    }
+    #[test]
+    fn test_prefer_same_line_where() {
+        let sway_code_to_format = r#"contract;
+
+pub fn hello( person: String ) -> String where T: Eq,{let greeting = 42;greeting.to_string()}"#;
+        let correct_sway_code = r#"contract;
+
+pub fn hello(person: String) -> String
+where
+    T: Eq, {
+    let greeting = 42;
+    greeting.to_string()
+}"#;
+        let mut formatter = Formatter::default();
+        formatter.config.items.item_brace_style = ItemBraceStyle::PreferSameLine;
+        let formatted_sway_code =
+            Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
+        assert_eq!(correct_sway_code, formatted_sway_code)
+    }
+    #[test]
+    fn test_exprs() {
+        let sway_code_to_format = r#"script;
+
+fn main() -> u64 {
+let mut x=1+2*3;
+let v=[1,2,3];
+let t=(x,true);
+while x<10{x+=1;if x==5{break;}else if x==6 {continue;}else{x=x*2;}}
+let y=match x{1=>{x}_=>t.0,};
+foo( y,v [0] ,);
+asm(r1:x,r2){add r2 r1 r1;r2:u64}
+}"#;
+        let correct_sway_code = r#"script;
+
+fn main() -> u64 {
+    let mut x = 1 + 2 * 3;
+    let v = [1, 2, 3];
+    let t = (x, true);
+    while x < 10 {
+        x += 1;
+        if x == 5 {
+            break;
+        } else if x == 6 {
+            continue;
+        } else {
+            x = x * 2;
+        }
+    }
+    let y = match x {
+        1 => {
+            x
+        }
+        _ => t.0,
+    };
+    foo(y, v[0]);
+    asm(r1: x, r2) {
+        add r2 r1 r1;
+        r2: u64
+    }
+}"#;
+        let mut formatter = Formatter::default();
+        let formatted_sway_code =
+            Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
+        assert_eq!(correct_sway_code, formatted_sway_code)
+    }
+
+    /// Collects the paths of all Sway source files under `dir`.
+    fn sway_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.append(&mut sway_files(&path));
+            } else if path.extension().map_or(false, |ext| ext == "sw") {
+                files.push(path);
+            }
+        }
+        files
+    }
+
+    /// Formats every Sway source file under `dir` and checks that formatting the result again
+    /// leaves it unchanged. Files that don't parse, e.g. those of `should_fail` tests, are skipped,
+    /// but any other formatter error is a failure.
+    fn check_idempotency(dir: &Path) {
+        let mut failures = Vec::new();
+        for path in sway_files(dir) {
+            let src: Arc<str> = Arc::from(std::fs::read_to_string(&path).unwrap());
+            if sway_parse::parse_file(src.clone(), None).is_err() {
+                continue;
+            }
+            let formatted = match Formatter::default().format(src, None) {
+                Ok(formatted) => formatted,
+                Err(err) => {
+                    failures.push(format!("{}: {}", path.display(), err));
+                    continue;
+                }
+            };
+            match Formatter::default().format(Arc::from(formatted.as_str()), None) {
+                Ok(reformatted) if reformatted == formatted => (),
+                Ok(_) => failures.push(format!("{}: formatting is not idempotent", path.display())),
+                Err(err) => failures.push(format!(
+                    "{}: failed to format the formatted code: {}",
+                    path.display(),
+                    err
+                )),
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "formatting failed for: {:#?}",
+            failures
+        );
+    }
+
+    #[test]
+    fn test_idempotency_examples() {
+        check_idempotency(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples"));
+    }
+
+    #[test]
+    fn test_idempotency_e2e_test_programs() {
+        check_idempotency(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("../test/src/e2e_vm_tests/test_programs"),
+        );
+    }
 }
@@ -53,8 +53,15 @@ impl CurlyBrace for ItemFn {
                     formatter.shape.block_indent(&formatter.config);
                 }
             },
-            _ => {
-                // TODO: implement PreferSameLine
+            ItemBraceStyle::PreferSameLine => {
+                if formatter.shape.has_where_clause {
+                    // The where clause ends with a newline, pull the brace up onto
+                    // the line of its last bound.
+                    if line.ends_with('\n') {
+                        line.pop();
+                    }
+                    formatter.shape.update_where_clause();
+                }
                 writeln!(line, " {}", open_brace)?;
                 formatter.shape.block_indent(&formatter.config);
             }
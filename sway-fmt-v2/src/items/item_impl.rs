@@ -7,7 +7,7 @@ use crate::{
     },
 };
 use std::fmt::Write;
-use sway_ast::{token::Delimiter, ItemImpl};
+use sway_ast::{keywords::Token, token::Delimiter, ImplType, ItemImpl, ItemImplItem};
 use sway_types::Spanned;
 
 impl Format for ItemImpl {
@@ -48,6 +48,49 @@ impl Format for ItemImpl {
     }
 }
 
+impl Format for ItemImplItem {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        match self {
+            ItemImplItem::Fn(item_fn) => item_fn.format(formatted_code, formatter),
+            ItemImplItem::Const(item_const) => {
+                item_const.format(formatted_code, formatter)?;
+                writeln!(formatted_code)?;
+                Ok(())
+            }
+            ItemImplItem::Type(impl_type) => {
+                impl_type.format(formatted_code, formatter)?;
+                writeln!(formatted_code)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Format for ImplType {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        // `type Name = `
+        write!(
+            formatted_code,
+            "{} {} {} ",
+            self.type_token.span().as_str(),
+            self.name.as_str(),
+            self.eq_token.ident().as_str()
+        )?;
+        self.ty.format(formatted_code, formatter)?;
+        write!(formatted_code, "{}", self.semicolon_token.ident().as_str())?;
+
+        Ok(())
+    }
+}
+
 impl CurlyBrace for ItemImpl {
     fn open_curly_brace(
         line: &mut FormattedCode,
@@ -72,8 +115,15 @@ impl CurlyBrace for ItemImpl {
                     formatter.shape.block_indent(&formatter.config);
                 }
             },
-            _ => {
-                // TODO: implement PreferSameLine
+            ItemBraceStyle::PreferSameLine => {
+                if formatter.shape.has_where_clause {
+                    // The where clause ends with a newline, pull the brace up onto
+                    // the line of its last bound.
+                    if line.ends_with('\n') {
+                        line.pop();
+                    }
+                    formatter.shape.update_where_clause();
+                }
                 writeln!(line, " {}", open_brace)?;
                 formatter.shape.block_indent(&formatter.config);
             }
@@ -108,3 +158,26 @@ impl LeafSpans for ItemImpl {
         collected_spans
     }
 }
+
+impl LeafSpans for ItemImplItem {
+    fn leaf_spans(&self) -> Vec<ByteSpan> {
+        match self {
+            ItemImplItem::Fn(item_fn) => item_fn.leaf_spans(),
+            ItemImplItem::Const(item_const) => item_const.leaf_spans(),
+            ItemImplItem::Type(impl_type) => impl_type.leaf_spans(),
+        }
+    }
+}
+
+impl LeafSpans for ImplType {
+    fn leaf_spans(&self) -> Vec<ByteSpan> {
+        let mut collected_spans = vec![
+            ByteSpan::from(self.type_token.span()),
+            ByteSpan::from(self.name.span()),
+            ByteSpan::from(self.eq_token.span()),
+        ];
+        collected_spans.append(&mut self.ty.leaf_spans());
+        collected_spans.push(ByteSpan::from(self.semicolon_token.span()));
+        collected_spans
+    }
+}
@@ -7,7 +7,9 @@ use crate::{
     },
 };
 use std::fmt::Write;
-use sway_ast::{keywords::Token, token::Delimiter, ItemTrait, Traits};
+use sway_ast::{
+    keywords::Token, token::Delimiter, ItemTrait, ItemTraitItem, TraitConst, TraitType, Traits,
+};
 use sway_types::Spanned;
 
 impl Format for ItemTrait {
@@ -27,6 +29,10 @@ impl Format for ItemTrait {
             self.trait_token.span().as_str(),
             self.name.span().as_str()
         )?;
+        // `<T>`
+        if let Some(generics) = &self.generics {
+            generics.format(formatted_code, formatter)?;
+        }
         // `: super_trait + super_trait`
         if let Some((colon_token, traits)) = &self.super_traits {
             write!(formatted_code, "{} ", colon_token.ident().as_str())?;
@@ -34,9 +40,9 @@ impl Format for ItemTrait {
         }
         write!(formatted_code, " ")?;
         Self::open_curly_brace(formatted_code, formatter)?;
-        for (fn_signature, semicolon_token) in self.trait_items.clone().into_inner() {
-            // format `Annotated<FnSignature>`
-            fn_signature.format(formatted_code, formatter)?;
+        for (trait_item, semicolon_token) in self.trait_items.clone().into_inner() {
+            // format `Annotated<ItemTraitItem>`
+            trait_item.format(formatted_code, formatter)?;
             writeln!(formatted_code, "{}\n", semicolon_token.ident().as_str())?;
         }
         formatted_code.pop(); // pop last ending newline
@@ -59,6 +65,58 @@ impl Format for ItemTrait {
     }
 }
 
+impl Format for ItemTraitItem {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        match self {
+            ItemTraitItem::Fn(fn_signature) => fn_signature.format(formatted_code, formatter),
+            ItemTraitItem::Const(trait_const) => trait_const.format(formatted_code, formatter),
+            ItemTraitItem::Type(trait_type) => trait_type.format(formatted_code, formatter),
+        }
+    }
+}
+
+impl Format for TraitConst {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        // `const NAME: `
+        write!(
+            formatted_code,
+            "{} {}{} ",
+            self.const_token.span().as_str(),
+            self.name.as_str(),
+            self.colon_token.ident().as_str()
+        )?;
+        self.ty.format(formatted_code, formatter)?;
+
+        Ok(())
+    }
+}
+
+impl Format for TraitType {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        _formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        // `type Name`
+        write!(
+            formatted_code,
+            "{} {}",
+            self.type_token.span().as_str(),
+            self.name.as_str()
+        )?;
+
+        Ok(())
+    }
+}
+
 impl CurlyBrace for ItemTrait {
     fn open_curly_brace(
         line: &mut FormattedCode,
@@ -118,6 +176,9 @@ impl LeafSpans for ItemTrait {
         }
         collected_spans.push(ByteSpan::from(self.trait_token.span()));
         collected_spans.push(ByteSpan::from(self.name.span()));
+        if let Some(generics) = &self.generics {
+            collected_spans.push(ByteSpan::from(generics.parameters.span()));
+        }
         if let Some(super_traits) = &self.super_traits {
             collected_spans.append(&mut super_traits.leaf_spans());
         }
@@ -129,6 +190,37 @@ impl LeafSpans for ItemTrait {
     }
 }
 
+impl LeafSpans for ItemTraitItem {
+    fn leaf_spans(&self) -> Vec<ByteSpan> {
+        match self {
+            ItemTraitItem::Fn(fn_signature) => fn_signature.leaf_spans(),
+            ItemTraitItem::Const(trait_const) => trait_const.leaf_spans(),
+            ItemTraitItem::Type(trait_type) => trait_type.leaf_spans(),
+        }
+    }
+}
+
+impl LeafSpans for TraitConst {
+    fn leaf_spans(&self) -> Vec<ByteSpan> {
+        let mut collected_spans = vec![
+            ByteSpan::from(self.const_token.span()),
+            ByteSpan::from(self.name.span()),
+            ByteSpan::from(self.colon_token.span()),
+        ];
+        collected_spans.append(&mut self.ty.leaf_spans());
+        collected_spans
+    }
+}
+
+impl LeafSpans for TraitType {
+    fn leaf_spans(&self) -> Vec<ByteSpan> {
+        vec![
+            ByteSpan::from(self.type_token.span()),
+            ByteSpan::from(self.name.span()),
+        ]
+    }
+}
+
 impl LeafSpans for Traits {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
         let mut collected_spans = self.prefix.leaf_spans();
@@ -1,20 +1,82 @@
-use std::vec;
+use std::{fmt::Write, vec};
 
 use crate::{
     fmt::{Format, FormattedCode, Formatter},
-    utils::comments::{ByteSpan, LeafSpans},
+    utils::{
+        comments::{ByteSpan, LeafSpans},
+        punctuated::format_comma_separated,
+    },
     FormatterError,
 };
-use sway_ast::{ItemUse, UseTree};
+use sway_ast::{keywords::Token, token::Delimiter, ItemUse, UseTree};
 use sway_types::Spanned;
 
 impl Format for ItemUse {
     fn format(
         &self,
-        _formatted_code: &mut FormattedCode,
-        _formatter: &mut Formatter,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
-        todo!()
+        // `pub `
+        if let Some(visibility_token) = &self.visibility {
+            write!(formatted_code, "{} ", visibility_token.span().as_str())?;
+        }
+        // `use `
+        write!(formatted_code, "{} ", self.use_token.span().as_str())?;
+        // `::`
+        if let Some(root_import) = &self.root_import {
+            write!(formatted_code, "{}", root_import.ident().as_str())?;
+        }
+        // `path::to::{Item, OtherItem}`
+        self.tree.format(formatted_code, formatter)?;
+        // `;`
+        write!(formatted_code, "{}", self.semicolon_token.ident().as_str())?;
+
+        Ok(())
+    }
+}
+
+impl Format for UseTree {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        match self {
+            Self::Group { imports } => {
+                write!(formatted_code, "{}", Delimiter::Brace.as_open_char())?;
+                format_comma_separated(imports.get(), formatted_code, formatter)?;
+                write!(formatted_code, "{}", Delimiter::Brace.as_close_char())?;
+            }
+            Self::Name { name } => write!(formatted_code, "{}", name.span().as_str())?,
+            Self::Rename {
+                name,
+                as_token,
+                alias,
+            } => write!(
+                formatted_code,
+                "{} {} {}",
+                name.span().as_str(),
+                as_token.span().as_str(),
+                alias.span().as_str()
+            )?,
+            Self::Glob { star_token } => write!(formatted_code, "{}", star_token.ident().as_str())?,
+            Self::Path {
+                prefix,
+                double_colon_token,
+                suffix,
+            } => {
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    prefix.span().as_str(),
+                    double_colon_token.ident().as_str()
+                )?;
+                suffix.format(formatted_code, formatter)?;
+            }
+        }
+
+        Ok(())
     }
 }
 
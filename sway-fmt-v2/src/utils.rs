@@ -1,8 +1,10 @@
 pub(crate) mod attribute;
 pub(crate) mod bracket;
 pub(crate) mod comments;
+pub(crate) mod diff;
 pub(crate) mod expr;
 pub(crate) mod generics;
+pub(crate) mod imports;
 pub(crate) mod indent_style;
 pub(crate) mod item;
 pub(crate) mod literal;
@@ -21,8 +21,12 @@ impl<T: Parse + Format> Format for Annotated<T> {
         formatted_code: &mut FormattedCode,
         formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
-        // format each `Attribute`
-        for attr in &self.attribute_list {
+        // format each `Attribute`, leaving doc comments to be written back as comments
+        for attr in self
+            .attribute_list
+            .iter()
+            .filter(|attr| attr.doc_comment().is_none())
+        {
             write!(
                 formatted_code,
                 "{}",
@@ -192,7 +192,13 @@ where
     T: LeafSpans + Parse,
 {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
-        let mut collected_spans = self.attribute_list.leaf_spans();
+        // Doc comments are comments as far as the formatter is concerned.
+        let mut collected_spans = Vec::new();
+        for attr in &self.attribute_list {
+            if attr.doc_comment().is_none() {
+                collected_spans.append(&mut attr.leaf_spans());
+            }
+        }
         collected_spans.append(&mut self.value.leaf_spans());
         collected_spans
     }
@@ -0,0 +1,112 @@
+//! Line based diffing of the unformatted and formatted code, used to limit formatting to a range
+//! of the source.
+use prettydiff::basic::DiffOp;
+use std::ops::Range;
+
+/// A replacement of a range of bytes of the unformatted code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The byte range of the unformatted code that is replaced.
+    pub range: Range<usize>,
+    /// The text to replace the range with.
+    pub new_text: String,
+}
+
+impl TextEdit {
+    /// Returns `true` if the edit touches the given byte range. An insertion touches the range
+    /// if it happens anywhere inside of it, including at its ends.
+    pub fn overlaps(&self, range: &Range<usize>) -> bool {
+        if self.range.is_empty() {
+            range.start <= self.range.start && self.range.start <= range.end
+        } else {
+            self.range.start < range.end.max(range.start + 1) && range.start < self.range.end
+        }
+    }
+}
+
+/// A run of changed lines: the lines `old` of the unformatted code are replaced by the lines
+/// `new` of the formatted code.
+#[derive(Debug, PartialEq, Eq)]
+struct Hunk {
+    old: Range<usize>,
+    new: Range<usize>,
+}
+
+/// Returns the edits which turn `src` into `formatted`, one per run of changed lines.
+pub(crate) fn line_edits(src: &str, formatted: &str) -> Vec<TextEdit> {
+    let old_lines: Vec<&str> = src.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = formatted.split_inclusive('\n').collect();
+    // The byte offset of the start of every line, and of the end of the code.
+    let mut line_starts = Vec::with_capacity(old_lines.len() + 1);
+    let mut offset = 0;
+    for line in &old_lines {
+        line_starts.push(offset);
+        offset += line.len();
+    }
+    line_starts.push(offset);
+
+    diff(&old_lines, &new_lines)
+        .into_iter()
+        .map(|hunk| TextEdit {
+            range: line_starts[hunk.old.start]..line_starts[hunk.old.end],
+            new_text: new_lines[hunk.new].concat(),
+        })
+        .collect()
+}
+
+/// Computes the changed lines between `old` and `new`, merging the adjacent changes into a
+/// single hunk.
+fn diff(old: &[&str], new: &[&str]) -> Vec<Hunk> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let (mut x, mut y) = (0, 0);
+    for op in prettydiff::basic::diff(old, new) {
+        let (old_len, new_len) = match op {
+            DiffOp::Equal(lines) => {
+                x += lines.len();
+                y += lines.len();
+                continue;
+            }
+            DiffOp::Insert(new_lines) => (0, new_lines.len()),
+            DiffOp::Remove(old_lines) => (old_lines.len(), 0),
+            DiffOp::Replace(old_lines, new_lines) => (old_lines.len(), new_lines.len()),
+        };
+        match hunks.last_mut() {
+            Some(hunk) if hunk.old.end == x && hunk.new.end == y => {
+                hunk.old.end += old_len;
+                hunk.new.end += new_len;
+            }
+            _ => hunks.push(Hunk {
+                old: x..x + old_len,
+                new: y..y + new_len,
+            }),
+        }
+        x += old_len;
+        y += new_len;
+    }
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_edits, TextEdit};
+
+    #[test]
+    fn test_line_edits() {
+        let src = "a\nb\nc\nd\ne\n";
+        let formatted = "a\nB\nc\nd\ne\nf\n";
+        assert_eq!(
+            line_edits(src, formatted),
+            vec![
+                TextEdit {
+                    range: 2..4,
+                    new_text: "B\n".to_owned(),
+                },
+                TextEdit {
+                    range: 10..10,
+                    new_text: "f\n".to_owned(),
+                },
+            ]
+        );
+        assert!(line_edits(src, src).is_empty());
+    }
+}
@@ -1,22 +1,26 @@
 use crate::{
-    config::items::ItemBraceStyle,
+    config::{expr::ExprBraceStyle, items::ItemBraceStyle},
     fmt::*,
-    utils::comments::{ByteSpan, LeafSpans},
+    utils::{
+        comments::{ByteSpan, LeafSpans},
+        punctuated::{format_comma_separated, is_empty},
+    },
 };
-use std::{fmt::Write, vec};
+use std::{fmt::Write, ops::ControlFlow, vec};
 use sway_ast::{
+    brackets::Parens,
     expr::asm::{AsmBlockContents, AsmFinalExpr},
-    keywords::Token,
+    keywords::{CommaToken, Token},
+    punctuated::Punctuated,
     token::{Delimiter, PunctKind},
-    AbiCastArgs, AsmBlock, AsmRegisterDeclaration, Assignable, Expr, ExprArrayDescriptor,
-    ExprStructField, ExprTupleDescriptor, IfCondition, IfExpr, Instruction, MatchBranch,
-    MatchBranchKind,
+    AbiCastArgs, AsmBlock, AsmRegisterDeclaration, Assignable, Braces, CodeBlockContents, Expr,
+    ExprArrayDescriptor, ExprStructField, ExprTupleDescriptor, IfCondition, IfExpr, Instruction,
+    MatchBranch, MatchBranchKind,
 };
-use sway_types::Spanned;
+use sway_types::{Span, Spanned};
 
 use super::bracket::CurlyBrace;
 
-// TODO:
 impl Format for Expr {
     fn format(
         &self,
@@ -24,14 +28,26 @@ impl Format for Expr {
         formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
         match self {
-            //     Self::Path(path) => {}
-            //     Self::Literal(lit) => {}
-            //     Self::AbiCast { abi_token, args } => {}
+            Self::Path(path) => path.format(formatted_code, formatter)?,
+            Self::Literal(lit) => lit.format(formatted_code, formatter)?,
+            Self::AbiCast { abi_token, args } => {
+                write!(formatted_code, "{}", abi_token.span().as_str())?;
+                args.get().format(formatted_code, formatter)?;
+            }
             Self::Struct { path, fields } => {
                 path.format(formatted_code, formatter)?;
+                let fields = fields.get();
+                if is_empty(fields) {
+                    write!(
+                        formatted_code,
+                        " {}{}",
+                        Delimiter::Brace.as_open_char(),
+                        Delimiter::Brace.as_close_char()
+                    )?;
+                    return Ok(());
+                }
                 ExprStructField::open_curly_brace(formatted_code, formatter)?;
                 writeln!(formatted_code)?;
-                let fields = fields.clone().into_inner();
                 let mut value_pairs_iter = fields.value_separator_pairs.iter().peekable();
                 for (expr_struct_field, comma_token) in value_pairs_iter.clone() {
                     // TypeField
@@ -42,155 +58,746 @@ impl Format for Expr {
                     }
                 }
                 if let Some(final_value) = &fields.final_value_opt {
+                    final_value.format(formatted_code, formatter)?;
+                    writeln!(formatted_code, "{}", PunctKind::Comma.as_char())?;
+                }
+                ExprStructField::close_curly_brace(formatted_code, formatter)?;
+            }
+            Self::Tuple(tuple_descriptor) => {
+                write!(formatted_code, "{}", Delimiter::Parenthesis.as_open_char())?;
+                tuple_descriptor.get().format(formatted_code, formatter)?;
+                write!(formatted_code, "{}", Delimiter::Parenthesis.as_close_char())?;
+            }
+            Self::Parens(expr) => {
+                write!(formatted_code, "{}", Delimiter::Parenthesis.as_open_char())?;
+                expr.get().format(formatted_code, formatter)?;
+                write!(formatted_code, "{}", Delimiter::Parenthesis.as_close_char())?;
+            }
+            Self::Block(code_block) => format_code_block(code_block, formatted_code, formatter)?,
+            Self::Array(array_descriptor) => {
+                write!(formatted_code, "{}", Delimiter::Bracket.as_open_char())?;
+                array_descriptor.get().format(formatted_code, formatter)?;
+                write!(formatted_code, "{}", Delimiter::Bracket.as_close_char())?;
+            }
+            Self::Asm(asm_block) => asm_block.format(formatted_code, formatter)?,
+            Self::Return {
+                return_token,
+                expr_opt,
+            } => {
+                write!(formatted_code, "{}", return_token.span().as_str())?;
+                if let Some(expr) = expr_opt {
+                    write!(formatted_code, " ")?;
+                    expr.format(formatted_code, formatter)?;
+                }
+            }
+            Self::If(if_expr) => if_expr.format(formatted_code, formatter)?,
+            Self::Match {
+                match_token,
+                value,
+                branches,
+            } => {
+                write!(formatted_code, "{} ", match_token.span().as_str())?;
+                value.format(formatted_code, formatter)?;
+                format_expr_brace_prefix(formatted_code, formatter)?;
+                let branches = branches.get();
+                if branches.is_empty() {
+                    write!(
+                        formatted_code,
+                        "{}{}",
+                        Delimiter::Brace.as_open_char(),
+                        Delimiter::Brace.as_close_char()
+                    )?;
+                    return Ok(());
+                }
+                writeln!(formatted_code, "{}", Delimiter::Brace.as_open_char())?;
+                formatter.shape.block_indent(&formatter.config);
+                for branch in branches {
                     write!(
                         formatted_code,
                         "{}",
-                        &formatter.shape.indent.to_string(&formatter.config)?
+                        formatter.shape.indent.to_string(&formatter.config)?
                     )?;
-                    final_value.format(formatted_code, formatter)?;
-                    writeln!(formatted_code, "{}", PunctKind::Comma.as_char())?;
+                    branch.format(formatted_code, formatter)?;
+                    writeln!(formatted_code)?;
                 }
-                ExprStructField::close_curly_brace(formatted_code, formatter)?;
+                formatter.shape.block_unindent(&formatter.config);
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    formatter.shape.indent.to_string(&formatter.config)?,
+                    Delimiter::Brace.as_close_char()
+                )?;
+            }
+            Self::While {
+                while_token,
+                condition,
+                block,
+            } => {
+                write!(formatted_code, "{} ", while_token.span().as_str())?;
+                condition.format(formatted_code, formatter)?;
+                format_expr_brace_prefix(formatted_code, formatter)?;
+                format_code_block(block, formatted_code, formatter)?;
+            }
+            Self::For {
+                for_token,
+                value_pattern,
+                in_token,
+                iterator,
+                block,
+            } => {
+                write!(formatted_code, "{} ", for_token.span().as_str())?;
+                value_pattern.format(formatted_code, formatter)?;
+                write!(formatted_code, " {} ", in_token.span().as_str())?;
+                iterator.format(formatted_code, formatter)?;
+                format_expr_brace_prefix(formatted_code, formatter)?;
+                format_code_block(block, formatted_code, formatter)?;
+            }
+            Self::FuncApp { func, args } => {
+                func.format(formatted_code, formatter)?;
+                format_call_args(args, formatted_code, formatter)?;
+            }
+            Self::Index { target, arg } => {
+                target.format(formatted_code, formatter)?;
+                write!(formatted_code, "{}", Delimiter::Bracket.as_open_char())?;
+                arg.get().format(formatted_code, formatter)?;
+                write!(formatted_code, "{}", Delimiter::Bracket.as_close_char())?;
+            }
+            Self::MethodCall {
+                target,
+                dot_token,
+                name,
+                contract_args_opt,
+                args,
+            } => {
+                target.format(formatted_code, formatter)?;
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    dot_token.span().as_str(),
+                    name.span().as_str()
+                )?;
+                if let Some(contract_args) = contract_args_opt {
+                    format_contract_args(contract_args.get(), formatted_code, formatter)?;
+                }
+                format_call_args(args, formatted_code, formatter)?;
+            }
+            Self::FieldProjection {
+                target,
+                dot_token,
+                name,
+            } => {
+                target.format(formatted_code, formatter)?;
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    dot_token.span().as_str(),
+                    name.span().as_str()
+                )?;
+            }
+            Self::TupleFieldProjection {
+                target,
+                dot_token,
+                field: _field,
+                field_span,
+            } => {
+                target.format(formatted_code, formatter)?;
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    dot_token.span().as_str(),
+                    field_span.as_str()
+                )?;
+            }
+            Self::Ref { ref_token, expr } => {
+                write!(formatted_code, "{} ", ref_token.span().as_str())?;
+                expr.format(formatted_code, formatter)?;
+            }
+            Self::Deref { deref_token, expr } => {
+                write!(formatted_code, "{} ", deref_token.span().as_str())?;
+                expr.format(formatted_code, formatter)?;
+            }
+            Self::Not { bang_token, expr } => {
+                write!(formatted_code, "{}", bang_token.span().as_str())?;
+                expr.format(formatted_code, formatter)?;
+            }
+            Self::Mul {
+                lhs,
+                star_token,
+                rhs,
+            } => format_binary_op(lhs, star_token.span(), rhs, formatted_code, formatter)?,
+            Self::Div {
+                lhs,
+                forward_slash_token,
+                rhs,
+            } => format_binary_op(
+                lhs,
+                forward_slash_token.span(),
+                rhs,
+                formatted_code,
+                formatter,
+            )?,
+            Self::Modulo {
+                lhs,
+                percent_token,
+                rhs,
+            } => format_binary_op(lhs, percent_token.span(), rhs, formatted_code, formatter)?,
+            Self::Add {
+                lhs,
+                add_token,
+                rhs,
+            } => format_binary_op(lhs, add_token.span(), rhs, formatted_code, formatter)?,
+            Self::Sub {
+                lhs,
+                sub_token,
+                rhs,
+            } => format_binary_op(lhs, sub_token.span(), rhs, formatted_code, formatter)?,
+            Self::Shl {
+                lhs,
+                shl_token,
+                rhs,
+            } => format_binary_op(lhs, shl_token.span(), rhs, formatted_code, formatter)?,
+            Self::Shr {
+                lhs,
+                shr_token,
+                rhs,
+            } => format_binary_op(lhs, shr_token.span(), rhs, formatted_code, formatter)?,
+            Self::BitAnd {
+                lhs,
+                ampersand_token,
+                rhs,
+            } => format_binary_op(lhs, ampersand_token.span(), rhs, formatted_code, formatter)?,
+            Self::BitXor {
+                lhs,
+                caret_token,
+                rhs,
+            } => format_binary_op(lhs, caret_token.span(), rhs, formatted_code, formatter)?,
+            Self::BitOr {
+                lhs,
+                pipe_token,
+                rhs,
+            } => format_binary_op(lhs, pipe_token.span(), rhs, formatted_code, formatter)?,
+            Self::Equal {
+                lhs,
+                double_eq_token,
+                rhs,
+            } => format_binary_op(lhs, double_eq_token.span(), rhs, formatted_code, formatter)?,
+            Self::NotEqual {
+                lhs,
+                bang_eq_token,
+                rhs,
+            } => format_binary_op(lhs, bang_eq_token.span(), rhs, formatted_code, formatter)?,
+            Self::LessThan {
+                lhs,
+                less_than_token,
+                rhs,
+            } => format_binary_op(lhs, less_than_token.span(), rhs, formatted_code, formatter)?,
+            Self::GreaterThan {
+                lhs,
+                greater_than_token,
+                rhs,
+            } => format_binary_op(
+                lhs,
+                greater_than_token.span(),
+                rhs,
+                formatted_code,
+                formatter,
+            )?,
+            Self::LessThanEq {
+                lhs,
+                less_than_eq_token,
+                rhs,
+            } => format_binary_op(
+                lhs,
+                less_than_eq_token.span(),
+                rhs,
+                formatted_code,
+                formatter,
+            )?,
+            Self::GreaterThanEq {
+                lhs,
+                greater_than_eq_token,
+                rhs,
+            } => format_binary_op(
+                lhs,
+                greater_than_eq_token.span(),
+                rhs,
+                formatted_code,
+                formatter,
+            )?,
+            Self::LogicalAnd {
+                lhs,
+                double_ampersand_token,
+                rhs,
+            } => format_binary_op(
+                lhs,
+                double_ampersand_token.span(),
+                rhs,
+                formatted_code,
+                formatter,
+            )?,
+            Self::LogicalOr {
+                lhs,
+                double_pipe_token,
+                rhs,
+            } => format_binary_op(
+                lhs,
+                double_pipe_token.span(),
+                rhs,
+                formatted_code,
+                formatter,
+            )?,
+            Self::Range {
+                start,
+                double_dot_token,
+                end,
+            } => {
+                start.format(formatted_code, formatter)?;
+                match formatter.config.expressions.spaces_around_ranges {
+                    true => write!(formatted_code, " {} ", double_dot_token.span().as_str())?,
+                    false => write!(formatted_code, "{}", double_dot_token.span().as_str())?,
+                }
+                end.format(formatted_code, formatter)?;
+            }
+            Self::Reassignment {
+                assignable,
+                reassignment_op,
+                expr,
+            } => {
+                assignable.format(formatted_code, formatter)?;
+                write!(formatted_code, " {} ", reassignment_op.span.as_str())?;
+                expr.format(formatted_code, formatter)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats `lhs op rhs`, with a single space on either side of the operator.
+fn format_binary_op(
+    lhs: &Expr,
+    op: Span,
+    rhs: &Expr,
+    formatted_code: &mut FormattedCode,
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    lhs.format(formatted_code, formatter)?;
+    write!(formatted_code, " {} ", op.as_str())?;
+    rhs.format(formatted_code, formatter)?;
+
+    Ok(())
+}
+
+/// Formats the arguments of a function or method call, e.g. `(a, b)`.
+fn format_call_args(
+    args: &Parens<Punctuated<Expr, CommaToken>>,
+    formatted_code: &mut FormattedCode,
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    write!(formatted_code, "{}", Delimiter::Parenthesis.as_open_char())?;
+    format_comma_separated(args.get(), formatted_code, formatter)?;
+    write!(formatted_code, "{}", Delimiter::Parenthesis.as_close_char())?;
+
+    Ok(())
+}
+
+/// Formats the contract call parameters of a method call on a single line,
+/// e.g. `{ gas: 10000, coins: 0 }`.
+fn format_contract_args(
+    contract_args: &Punctuated<ExprStructField, CommaToken>,
+    formatted_code: &mut FormattedCode,
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    write!(formatted_code, " {}", Delimiter::Brace.as_open_char())?;
+    let mut fields = contract_args.into_iter().peekable();
+    if fields.peek().is_some() {
+        write!(formatted_code, " ")?;
+    }
+    while let Some(field) = fields.next() {
+        write!(formatted_code, "{}", field.field_name.span().as_str())?;
+        if let Some((colon_token, expr)) = &field.expr_opt {
+            write!(formatted_code, "{} ", colon_token.ident().as_str())?;
+            expr.format(formatted_code, formatter)?;
+        }
+        match fields.peek() {
+            Some(_) => write!(formatted_code, "{} ", PunctKind::Comma.as_char())?,
+            None => write!(formatted_code, " ")?,
+        }
+    }
+    write!(formatted_code, "{}", Delimiter::Brace.as_close_char())?;
+
+    Ok(())
+}
+
+/// Writes what goes between the head of a control flow expression, e.g. `while x`, and its
+/// opening brace, according to the `expr_brace_style` config.
+fn format_expr_brace_prefix(
+    formatted_code: &mut FormattedCode,
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    match formatter.config.expressions.expr_brace_style {
+        ExprBraceStyle::AlwaysNextLine => write!(
+            formatted_code,
+            "\n{}",
+            formatter.shape.indent.to_string(&formatter.config)?
+        )?,
+        ExprBraceStyle::AlwaysSameLine | ExprBraceStyle::ClosingNextLine => {
+            write!(formatted_code, " ")?
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats a block of statements, e.g. the body of an `if`, `while` or `for`.
+/// The statements are put on their own lines, one level of indentation deeper than
+/// the braces. An empty block is formatted as `{}`.
+pub(crate) fn format_code_block(
+    block: &Braces<CodeBlockContents>,
+    formatted_code: &mut FormattedCode,
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    let contents = block.get();
+    if contents.statements.is_empty() && contents.final_expr_opt.is_none() {
+        write!(
+            formatted_code,
+            "{}{}",
+            Delimiter::Brace.as_open_char(),
+            Delimiter::Brace.as_close_char()
+        )?;
+        return Ok(());
+    }
+    writeln!(formatted_code, "{}", Delimiter::Brace.as_open_char())?;
+    formatter.shape.block_indent(&formatter.config);
+    contents.format(formatted_code, formatter)?;
+    formatter.shape.block_unindent(&formatter.config);
+    write!(
+        formatted_code,
+        "{}{}",
+        formatter.shape.indent.to_string(&formatter.config)?,
+        Delimiter::Brace.as_close_char()
+    )?;
+
+    Ok(())
+}
+
+impl Format for AbiCastArgs {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        write!(formatted_code, "{}", Delimiter::Parenthesis.as_open_char())?;
+        self.name.format(formatted_code, formatter)?;
+        write!(formatted_code, "{} ", self.comma_token.ident().as_str())?;
+        self.address.format(formatted_code, formatter)?;
+        write!(formatted_code, "{}", Delimiter::Parenthesis.as_close_char())?;
+
+        Ok(())
+    }
+}
+
+impl Format for ExprTupleDescriptor {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        if let Self::Cons {
+            head,
+            comma_token,
+            tail,
+        } = self
+        {
+            head.format(formatted_code, formatter)?;
+            // A single element tuple keeps its comma, e.g. `(a,)`.
+            write!(formatted_code, "{}", comma_token.ident().as_str())?;
+            if !is_empty(tail) {
+                write!(formatted_code, " ")?;
+                format_comma_separated(tail, formatted_code, formatter)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Format for ExprArrayDescriptor {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        match self {
+            Self::Sequence(exprs) => format_comma_separated(exprs, formatted_code, formatter)?,
+            Self::Repeat {
+                value,
+                semicolon_token,
+                length,
+            } => {
+                value.format(formatted_code, formatter)?;
+                write!(formatted_code, "{} ", semicolon_token.ident().as_str())?;
+                length.format(formatted_code, formatter)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Format for AsmBlock {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        // `asm(r1: x, r2) `
+        write!(
+            formatted_code,
+            "{}{}",
+            self.asm_token.span().as_str(),
+            Delimiter::Parenthesis.as_open_char()
+        )?;
+        format_comma_separated(self.registers.get(), formatted_code, formatter)?;
+        write!(
+            formatted_code,
+            "{} ",
+            Delimiter::Parenthesis.as_close_char()
+        )?;
+
+        let contents = self.contents.get();
+        if contents.instructions.is_empty() && contents.final_expr_opt.is_none() {
+            write!(
+                formatted_code,
+                "{}{}",
+                Delimiter::Brace.as_open_char(),
+                Delimiter::Brace.as_close_char()
+            )?;
+            return Ok(());
+        }
+        writeln!(formatted_code, "{}", Delimiter::Brace.as_open_char())?;
+        formatter.shape.block_indent(&formatter.config);
+        for (instruction, semicolon_token) in &contents.instructions {
+            write!(
+                formatted_code,
+                "{}",
+                formatter.shape.indent.to_string(&formatter.config)?
+            )?;
+            instruction.format(formatted_code, formatter)?;
+            writeln!(formatted_code, "{}", semicolon_token.ident().as_str())?;
+        }
+        if let Some(final_expr) = &contents.final_expr_opt {
+            write!(
+                formatted_code,
+                "{}",
+                formatter.shape.indent.to_string(&formatter.config)?
+            )?;
+            final_expr.format(formatted_code, formatter)?;
+            writeln!(formatted_code)?;
+        }
+        formatter.shape.block_unindent(&formatter.config);
+        write!(
+            formatted_code,
+            "{}{}",
+            formatter.shape.indent.to_string(&formatter.config)?,
+            Delimiter::Brace.as_close_char()
+        )?;
+
+        Ok(())
+    }
+}
+
+impl Format for AsmRegisterDeclaration {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        write!(formatted_code, "{}", self.register.span().as_str())?;
+        if let Some((colon_token, value)) = &self.value_opt {
+            write!(formatted_code, "{} ", colon_token.ident().as_str())?;
+            value.format(formatted_code, formatter)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Format for Instruction {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        _formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        // `op_code r1 r2 imm`
+        write!(formatted_code, "{}", self.op_code_ident().as_str())?;
+        for register in self.register_arg_idents() {
+            write!(formatted_code, " {}", register.as_str())?;
+        }
+        if let Some(immediate) = self.immediate_ident_opt() {
+            write!(formatted_code, " {}", immediate.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Format for AsmFinalExpr {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        write!(formatted_code, "{}", self.register.span().as_str())?;
+        if let Some((colon_token, ty)) = &self.ty_opt {
+            write!(formatted_code, "{} ", colon_token.ident().as_str())?;
+            ty.format(formatted_code, formatter)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Format for IfExpr {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        // `if condition {..}`
+        write!(formatted_code, "{} ", self.if_token.span().as_str())?;
+        self.condition.format(formatted_code, formatter)?;
+        format_expr_brace_prefix(formatted_code, formatter)?;
+        format_code_block(&self.then_block, formatted_code, formatter)?;
+
+        // `else {..}` or `else if ..`
+        if let Some((else_token, else_body)) = &self.else_opt {
+            match formatter.config.expressions.expr_brace_style {
+                ExprBraceStyle::AlwaysSameLine => {
+                    write!(formatted_code, " {} ", else_token.span().as_str())?
+                }
+                ExprBraceStyle::ClosingNextLine | ExprBraceStyle::AlwaysNextLine => write!(
+                    formatted_code,
+                    "\n{}{} ",
+                    formatter.shape.indent.to_string(&formatter.config)?,
+                    else_token.span().as_str()
+                )?,
+            }
+            match else_body {
+                ControlFlow::Continue(if_expr) => if_expr.format(formatted_code, formatter)?,
+                ControlFlow::Break(else_block) => {
+                    if let ExprBraceStyle::AlwaysNextLine =
+                        formatter.config.expressions.expr_brace_style
+                    {
+                        // `else` has been followed by a space, the brace goes on its own line.
+                        formatted_code.pop();
+                        format_expr_brace_prefix(formatted_code, formatter)?;
+                    }
+                    format_code_block(else_block, formatted_code, formatter)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Format for IfCondition {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        match self {
+            Self::Expr(expr) => expr.format(formatted_code, formatter)?,
+            Self::Let {
+                let_token,
+                lhs,
+                eq_token,
+                rhs,
+            } => {
+                write!(formatted_code, "{} ", let_token.span().as_str())?;
+                lhs.format(formatted_code, formatter)?;
+                write!(formatted_code, " {} ", eq_token.ident().as_str())?;
+                rhs.format(formatted_code, formatter)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Format for MatchBranch {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        // `pattern => `
+        self.pattern.format(formatted_code, formatter)?;
+        write!(
+            formatted_code,
+            " {} ",
+            self.fat_right_arrow_token.ident().as_str()
+        )?;
+        match &self.kind {
+            MatchBranchKind::Block { block, .. } => {
+                format_code_block(block, formatted_code, formatter)?;
+                if formatter.config.expressions.match_block_trailing_comma {
+                    write!(formatted_code, "{}", PunctKind::Comma.as_char())?;
+                }
+            }
+            MatchBranchKind::Expr { expr, comma_token } => {
+                expr.format(formatted_code, formatter)?;
+                write!(formatted_code, "{}", comma_token.ident().as_str())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Format for Assignable {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        match self {
+            Self::Var(name) => write!(formatted_code, "{}", name.span().as_str())?,
+            Self::Index { target, arg } => {
+                target.format(formatted_code, formatter)?;
+                write!(formatted_code, "{}", Delimiter::Bracket.as_open_char())?;
+                arg.get().format(formatted_code, formatter)?;
+                write!(formatted_code, "{}", Delimiter::Bracket.as_close_char())?;
+            }
+            Self::FieldProjection {
+                target,
+                dot_token,
+                name,
+            } => {
+                target.format(formatted_code, formatter)?;
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    dot_token.span().as_str(),
+                    name.span().as_str()
+                )?;
+            }
+            Self::TupleFieldProjection {
+                target,
+                dot_token,
+                field: _field,
+                field_span,
+            } => {
+                target.format(formatted_code, formatter)?;
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    dot_token.span().as_str(),
+                    field_span.as_str()
+                )?;
             }
-            //     Self::Tuple(tuple_descriptor) => {}
-            //     Self::Parens(expr) => {}
-            //     Self::Block(code_block) => {}
-            //     Self::Array(array_descriptor) => {}
-            //     Self::Asm(asm_block) => {}
-            //     Self::Return {
-            //         return_token,
-            //         expr_opt,
-            //     } => {}
-            //     Self::If(if_expr) => {}
-            //     Self::Match {
-            //         match_token,
-            //         value,
-            //         branches,
-            //     } => {}
-            //     Self::While {
-            //         while_token,
-            //         condition,
-            //         block,
-            //     } => {}
-            //     Self::FuncApp { func, args } => {}
-            //     Self::Index { target, arg } => {}
-            //     Self::MethodCall {
-            //         target,
-            //         dot_token,
-            //         name,
-            //         contract_args_opt,
-            //         args,
-            //     } => {}
-            //     Self::FieldProjection {
-            //         target,
-            //         dot_token,
-            //         name,
-            //     } => {}
-            //     Self::TupleFieldProjection {
-            //         target,
-            //         dot_token,
-            //         field,
-            //         field_span,
-            //     } => {}
-            //     Self::Ref { ref_token, expr } => {}
-            //     Self::Deref { deref_token, expr } => {}
-            //     Self::Not { bang_token, expr } => {}
-            //     Self::Mul {
-            //         lhs,
-            //         star_token,
-            //         rhs,
-            //     } => {}
-            //     Self::Div {
-            //         lhs,
-            //         forward_slash_token,
-            //         rhs,
-            //     } => {}
-            //     Self::Modulo {
-            //         lhs,
-            //         percent_token,
-            //         rhs,
-            //     } => {}
-            //     Self::Add {
-            //         lhs,
-            //         add_token,
-            //         rhs,
-            //     } => {}
-            //     Self::Sub {
-            //         lhs,
-            //         sub_token,
-            //         rhs,
-            //     } => {}
-            //     Self::Shl {
-            //         lhs,
-            //         shl_token,
-            //         rhs,
-            //     } => {}
-            //     Self::Shr {
-            //         lhs,
-            //         shr_token,
-            //         rhs,
-            //     } => {}
-            //     Self::BitAnd {
-            //         lhs,
-            //         ampersand_token,
-            //         rhs,
-            //     } => {}
-            //     Self::BitXor {
-            //         lhs,
-            //         caret_token,
-            //         rhs,
-            //     } => {}
-            //     Self::BitOr {
-            //         lhs,
-            //         pipe_token,
-            //         rhs,
-            //     } => {}
-            //     Self::Equal {
-            //         lhs,
-            //         double_eq_token,
-            //         rhs,
-            //     } => {}
-            //     Self::NotEqual {
-            //         lhs,
-            //         bang_eq_token,
-            //         rhs,
-            //     } => {}
-            //     Self::LessThan {
-            //         lhs,
-            //         less_than_token,
-            //         rhs,
-            //     } => {}
-            //     Self::GreaterThan {
-            //         lhs,
-            //         greater_than_token,
-            //         rhs,
-            //     } => {}
-            //     Self::LessThanEq {
-            //         lhs,
-            //         less_than_eq_token,
-            //         rhs,
-            //     } => {}
-            //     Self::GreaterThanEq {
-            //         lhs,
-            //         greater_than_eq_token,
-            //         rhs,
-            //     } => {}
-            //     Self::LogicalAnd {
-            //         lhs,
-            //         double_ampersand_token,
-            //         rhs,
-            //     } => {}
-            //     Self::LogicalOr {
-            //         lhs,
-            //         double_pipe_token,
-            //         rhs,
-            //     } => {}
-            //     Self::Reassignment {
-            //         assignable,
-            //         reassignment_op,
-            //         expr,
-            //     } => {}
-            _ => write!(formatted_code, "{}", self.span().as_str())?,
         }
 
         Ok(())
@@ -320,6 +927,20 @@ fn visit_expr(expr: &Expr) -> Vec<ByteSpan> {
             collected_spans.append(&mut block.leaf_spans());
             collected_spans
         }
+        Expr::For {
+            for_token,
+            value_pattern,
+            in_token,
+            iterator,
+            block,
+        } => {
+            let mut collected_spans = vec![ByteSpan::from(for_token.span())];
+            collected_spans.append(&mut value_pattern.leaf_spans());
+            collected_spans.push(ByteSpan::from(in_token.span()));
+            collected_spans.append(&mut iterator.leaf_spans());
+            collected_spans.append(&mut block.leaf_spans());
+            collected_spans
+        }
         Expr::FuncApp { func, args } => {
             let mut collected_spans = Vec::new();
             collected_spans.append(&mut func.leaf_spans());
@@ -567,6 +1188,16 @@ fn visit_expr(expr: &Expr) -> Vec<ByteSpan> {
             collected_spans.append(&mut rhs.leaf_spans());
             collected_spans
         }
+        Expr::Range {
+            start,
+            double_dot_token,
+            end,
+        } => {
+            let mut collected_spans = start.leaf_spans();
+            collected_spans.push(ByteSpan::from(double_dot_token.span()));
+            collected_spans.append(&mut end.leaf_spans());
+            collected_spans
+        }
         Expr::Reassignment {
             assignable,
             reassignment_op,
@@ -731,14 +1362,10 @@ impl LeafSpans for MatchBranchKind {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
         let mut collected_spans = Vec::new();
         match self {
-            MatchBranchKind::Block {
-                block,
-                comma_token_opt,
-            } => {
+            // The comma after a block is added or removed according to
+            // `match_block_trailing_comma`, so it is not visited.
+            MatchBranchKind::Block { block, .. } => {
                 collected_spans.append(&mut block.leaf_spans());
-                if let Some(comma_token) = comma_token_opt {
-                    collected_spans.push(ByteSpan::from(comma_token.span()));
-                }
             }
             MatchBranchKind::Expr { expr, comma_token } => {
                 collected_spans.append(&mut expr.leaf_spans());
@@ -0,0 +1,574 @@
+//! Sorting, grouping and merging of `use` items according to the `Imports` and `Ordering`
+//! configs.
+//!
+//! The `use` items are rewritten in the source before it is formatted. The comments attached to
+//! an import move along with it and are then placed like any other comment by `handle_comments`.
+use crate::{
+    config::{
+        imports::{GroupImports, ImportGranularity},
+        manifest::Config,
+    },
+    fmt::FormatterError,
+    utils::comments::{comment_map_from_src, ByteSpan, CommentMap},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+use sway_ast::{Item, ItemKind, ItemUse, Module, UseTree};
+use sway_types::Spanned;
+
+/// An owned copy of a `UseTree`, which can be sorted and merged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Tree {
+    Name(String),
+    Rename(String, String),
+    Glob,
+    Path(String, Box<Tree>),
+    Group(Vec<Tree>),
+}
+
+/// The last segment of an import path, i.e. what is actually imported.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Leaf {
+    Name(String),
+    Rename(String, String),
+    Glob,
+}
+
+impl From<Leaf> for Tree {
+    fn from(leaf: Leaf) -> Self {
+        match leaf {
+            Leaf::Name(name) => Tree::Name(name),
+            Leaf::Rename(name, alias) => Tree::Rename(name, alias),
+            Leaf::Glob => Tree::Glob,
+        }
+    }
+}
+
+impl From<&UseTree> for Tree {
+    fn from(use_tree: &UseTree) -> Self {
+        match use_tree {
+            UseTree::Group { imports } => {
+                Tree::Group(imports.get().into_iter().map(Tree::from).collect())
+            }
+            UseTree::Name { name } => Tree::Name(name.as_str().to_owned()),
+            UseTree::Rename { name, alias, .. } => {
+                Tree::Rename(name.as_str().to_owned(), alias.as_str().to_owned())
+            }
+            UseTree::Glob { .. } => Tree::Glob,
+            UseTree::Path { prefix, suffix, .. } => {
+                Tree::Path(prefix.as_str().to_owned(), Box::new(Tree::from(&**suffix)))
+            }
+        }
+    }
+}
+
+impl Tree {
+    /// Calls `f` with every path imported by the tree, as the modules leading to the import and
+    /// the import itself.
+    fn for_each_path(&self, prefix: &mut Vec<String>, f: &mut impl FnMut(&[String], Leaf)) {
+        match self {
+            Tree::Name(name) => f(prefix.as_slice(), Leaf::Name(name.clone())),
+            Tree::Rename(name, alias) => {
+                f(prefix.as_slice(), Leaf::Rename(name.clone(), alias.clone()))
+            }
+            Tree::Glob => f(prefix.as_slice(), Leaf::Glob),
+            Tree::Path(name, suffix) => {
+                prefix.push(name.clone());
+                suffix.for_each_path(prefix, f);
+                prefix.pop();
+            }
+            Tree::Group(trees) => {
+                for tree in trees {
+                    tree.for_each_path(prefix, f);
+                }
+            }
+        }
+    }
+
+    /// Sorts the contents of every group in the tree and removes duplicates from them.
+    fn normalize(&mut self, reorder: bool) {
+        match self {
+            Tree::Path(_, suffix) => {
+                suffix.normalize(reorder);
+                // `a::{b}` is just `a::b`.
+                if let Tree::Group(trees) = &mut **suffix {
+                    if trees.len() == 1 {
+                        **suffix = trees.pop().unwrap();
+                    }
+                }
+            }
+            Tree::Group(trees) => {
+                for tree in trees.iter_mut() {
+                    tree.normalize(reorder);
+                }
+                if reorder {
+                    trees.sort_by_key(sort_key);
+                }
+                dedup(trees);
+            }
+            Tree::Name(_) | Tree::Rename(..) | Tree::Glob => (),
+        }
+    }
+
+    /// The name of the first segment of the tree, e.g. `std` for `std::hash::sha256`.
+    fn first_segment(&self) -> Option<&str> {
+        match self {
+            Tree::Name(name) | Tree::Rename(name, _) | Tree::Path(name, _) => Some(name),
+            Tree::Glob | Tree::Group(_) => None,
+        }
+    }
+
+    fn render(&self, out: &mut String) {
+        match self {
+            Tree::Name(name) => out.push_str(name),
+            Tree::Rename(name, alias) => {
+                out.push_str(name);
+                out.push_str(" as ");
+                out.push_str(alias);
+            }
+            Tree::Glob => out.push('*'),
+            Tree::Path(name, suffix) => {
+                out.push_str(name);
+                out.push_str("::");
+                suffix.render(out);
+            }
+            Tree::Group(trees) => {
+                out.push('{');
+                for (ix, tree) in trees.iter().enumerate() {
+                    if ix > 0 {
+                        out.push_str(", ");
+                    }
+                    tree.render(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// Orders a segment of a path: `self`, `super` and `crate` come first, then modules and
+/// functions, then types, each in alphabetical order.
+fn segment_key(name: &str) -> (u8, String) {
+    let category = match name {
+        "self" => 0,
+        "super" => 1,
+        "crate" => 2,
+        _ if name.starts_with(char::is_uppercase) => 4,
+        _ => 3,
+    };
+    (category, name.to_owned())
+}
+
+/// The key which imports are sorted by. Paths are compared segment by segment, and a glob
+/// comes after the names imported from the same module.
+fn sort_key(tree: &Tree) -> Vec<(u8, String)> {
+    match tree {
+        Tree::Name(name) => vec![segment_key(name)],
+        Tree::Rename(name, alias) => vec![segment_key(name), (7, alias.clone())],
+        Tree::Glob => vec![(5, "*".to_owned())],
+        Tree::Path(name, suffix) => {
+            let mut key = vec![segment_key(name)];
+            key.append(&mut sort_key(suffix));
+            key
+        }
+        Tree::Group(trees) => {
+            let mut key = vec![(6, "{".to_owned())];
+            for tree in trees {
+                key.append(&mut sort_key(tree));
+                key.push((8, ",".to_owned()));
+            }
+            key
+        }
+    }
+}
+
+/// Removes duplicates from `trees`, keeping the first occurrence of each tree.
+fn dedup(trees: &mut Vec<Tree>) {
+    let mut unique: Vec<Tree> = Vec::with_capacity(trees.len());
+    for tree in trees.drain(..) {
+        if !unique.contains(&tree) {
+            unique.push(tree);
+        }
+    }
+    *trees = unique;
+}
+
+/// Import paths merged into nested groups, e.g. `a::b`, `a::c::d` and `a::c::e` become
+/// `a::{b, c::{d, e}}`.
+#[derive(Default)]
+struct Trie {
+    leaves: BTreeSet<Leaf>,
+    children: BTreeMap<String, Trie>,
+}
+
+impl Trie {
+    fn insert(&mut self, prefix: &[String], leaf: Leaf) {
+        match prefix.split_first() {
+            Some((first, rest)) => self
+                .children
+                .entry(first.clone())
+                .or_default()
+                .insert(rest, leaf),
+            None => {
+                self.leaves.insert(leaf);
+            }
+        }
+    }
+
+    fn into_trees(self) -> Vec<Tree> {
+        let mut trees: Vec<Tree> = self.leaves.into_iter().map(Tree::from).collect();
+        for (name, child) in self.children {
+            let mut suffixes = child.into_trees();
+            let tree = match suffixes.len() {
+                // `a::{self}` is just `a`.
+                1 => match suffixes.pop().unwrap() {
+                    Tree::Name(suffix) if suffix == "self" => Tree::Name(name),
+                    Tree::Rename(suffix, alias) if suffix == "self" => Tree::Rename(name, alias),
+                    suffix => Tree::Path(name, Box::new(suffix)),
+                },
+                _ => Tree::Path(name, Box::new(Tree::Group(suffixes))),
+            };
+            trees.push(tree);
+        }
+        trees.sort_by_key(sort_key);
+        trees
+    }
+
+    fn into_tree(self) -> Tree {
+        let mut trees = self.into_trees();
+        match trees.len() {
+            1 => trees.pop().unwrap(),
+            _ => Tree::Group(trees),
+        }
+    }
+}
+
+/// A `use` item being rewritten, with the comments attached to it.
+struct Import {
+    is_pub: bool,
+    /// Whether the path starts with `::`.
+    is_root: bool,
+    tree: Tree,
+    /// Comments on the lines before the item, or inside of it.
+    leading_comments: Vec<String>,
+    /// Comments after the item, on the same line.
+    trailing_comments: Vec<String>,
+}
+
+impl Import {
+    fn is_same_import(&self, other: &Import) -> bool {
+        self.is_pub == other.is_pub && self.is_root == other.is_root && self.tree == other.tree
+    }
+
+    fn render(&self, out: &mut String) {
+        for comment in &self.leading_comments {
+            out.push_str(comment);
+            out.push('\n');
+        }
+        if self.is_pub {
+            out.push_str("pub ");
+        }
+        out.push_str("use ");
+        if self.is_root {
+            out.push_str("::");
+        }
+        self.tree.render(out);
+        out.push(';');
+        for comment in &self.trailing_comments {
+            out.push(' ');
+            out.push_str(comment);
+        }
+    }
+}
+
+/// Merges or splits the imports of a group to the configured granularity.
+fn apply_granularity(imports: Vec<Import>, config: &Config) -> Vec<Import> {
+    let granularity = config.imports.imports_granularity;
+    if let ImportGranularity::Preserve = granularity {
+        return imports
+            .into_iter()
+            .map(|mut import| {
+                import.tree.normalize(config.ordering.reorder_imports);
+                import
+            })
+            .collect();
+    }
+
+    // Imports which end up in the same item share a key, and the key of the first path of each
+    // original item is where its comments go.
+    type Key = (bool, bool, Vec<String>);
+    let mut merged: BTreeMap<Key, (Trie, Vec<String>, Vec<String>)> = BTreeMap::new();
+    let mut order: Vec<Key> = Vec::new();
+    for import in imports {
+        let mut first_key = None;
+        import
+            .tree
+            .for_each_path(&mut Vec::new(), &mut |prefix, leaf| {
+                let mut key_path = match granularity {
+                    ImportGranularity::Item | ImportGranularity::Module => prefix.to_vec(),
+                    ImportGranularity::Crate => prefix.iter().take(1).cloned().collect(),
+                    ImportGranularity::One | ImportGranularity::Preserve => Vec::new(),
+                };
+                if let ImportGranularity::Item = granularity {
+                    let mut leaf_name = String::new();
+                    Tree::from(leaf.clone()).render(&mut leaf_name);
+                    key_path.push(leaf_name);
+                }
+                let key = (import.is_pub, import.is_root, key_path);
+                let entry = merged.entry(key.clone()).or_insert_with(|| {
+                    order.push(key.clone());
+                    Default::default()
+                });
+                entry.0.insert(prefix, leaf);
+                first_key.get_or_insert(key);
+            });
+        if let Some(key) = first_key {
+            let entry = merged.get_mut(&key).unwrap();
+            entry.1.extend(import.leading_comments);
+            entry.2.extend(import.trailing_comments);
+        }
+    }
+
+    order
+        .into_iter()
+        .map(|key| {
+            let (trie, leading_comments, trailing_comments) = merged.remove(&key).unwrap();
+            Import {
+                is_pub: key.0,
+                is_root: key.1,
+                tree: trie.into_tree(),
+                leading_comments,
+                trailing_comments,
+            }
+        })
+        .collect()
+}
+
+/// Sorts the imports of a group, if configured to, and removes duplicate imports.
+fn sort_and_dedup(mut imports: Vec<Import>, config: &Config) -> Vec<Import> {
+    if config.ordering.reorder_imports {
+        imports.sort_by_key(|import| (sort_key(&import.tree), import.is_root, import.is_pub));
+    }
+    let mut unique: Vec<Import> = Vec::with_capacity(imports.len());
+    for import in imports {
+        match unique
+            .iter_mut()
+            .find(|other| other.is_same_import(&import))
+        {
+            Some(other) => {
+                other.leading_comments.extend(import.leading_comments);
+                other.trailing_comments.extend(import.trailing_comments);
+            }
+            None => unique.push(import),
+        }
+    }
+    unique
+}
+
+/// For `GroupImports::StdExternalCrate`: `std` and `core` imports come first, then the imports
+/// of other packages, then those of the modules of this package.
+fn std_external_crate_group(import: &Import, local_modules: &BTreeSet<String>) -> usize {
+    if import.is_root {
+        return 2;
+    }
+    match import.tree.first_segment() {
+        Some("std") | Some("core") => 0,
+        Some("self") | Some("super") | Some("crate") => 2,
+        Some(name) if local_modules.contains(name) => 2,
+        _ => 1,
+    }
+}
+
+/// Returns `true` if there is a blank line in `gap`, the text between two items.
+fn has_blank_line(gap: &str) -> bool {
+    let lines: Vec<&str> = gap.split('\n').collect();
+    lines.len() > 2
+        && lines[1..lines.len() - 1]
+            .iter()
+            .any(|line| line.trim().is_empty())
+}
+
+/// Returns `true` if `item` is a `use` item which is followed by a blank line, which should be
+/// kept to separate it from the next item.
+pub(crate) fn ends_import_group(src: &str, item: &Item) -> bool {
+    if !matches!(item.value, ItemKind::Use(_)) {
+        return false;
+    }
+    let rest = &src[item.value.span().end()..];
+    let whitespace_len = rest.len() - rest.trim_start().len();
+    // Blank lines before a comment are written back along with the comment.
+    rest[..whitespace_len].matches('\n').count() >= 2 && !rest[whitespace_len..].starts_with('/')
+}
+
+/// Rewrites a run of consecutive `use` items in `src`, returning the byte range it spans in `src`
+/// and the text to replace it with.
+fn rewrite_run(
+    src: &str,
+    uses: &[&ItemUse],
+    comment_map: &CommentMap,
+    local_modules: &BTreeSet<String>,
+    config: &Config,
+) -> (usize, usize, String) {
+    let spans: Vec<(usize, usize)> = uses
+        .iter()
+        .map(|item_use| (item_use.span().start(), item_use.span().end()))
+        .collect();
+    let mut imports: Vec<Import> = uses
+        .iter()
+        .map(|item_use| Import {
+            is_pub: item_use.visibility.is_some(),
+            is_root: item_use.root_import.is_some(),
+            tree: Tree::from(&item_use.tree),
+            leading_comments: Vec::new(),
+            trailing_comments: Vec::new(),
+        })
+        .collect();
+
+    // Comments following an item on the same line stay with it.
+    let mut ends = Vec::with_capacity(spans.len());
+    for (ix, &(_, end)) in spans.iter().enumerate() {
+        let mut end = end;
+        let next_start = spans.get(ix + 1).map_or(src.len(), |span| span.0);
+        let from = ByteSpan {
+            start: end,
+            end: usize::MAX,
+        };
+        for (span, comment) in comment_map.range(from..) {
+            if span.start >= next_start || src[end..span.start].contains('\n') {
+                break;
+            }
+            imports[ix]
+                .trailing_comments
+                .push(comment.span.as_str().to_owned());
+            end = span.end;
+        }
+        ends.push(end);
+    }
+    let run_start = spans[0].0;
+    let run_end = *ends.last().unwrap();
+
+    // Other comments go with the item they are in or come before.
+    let from = ByteSpan {
+        start: run_start,
+        end: usize::MAX,
+    };
+    for (span, comment) in comment_map.range(from..) {
+        if span.start >= run_end {
+            break;
+        }
+        let is_trailing =
+            (0..spans.len()).any(|ix| span.start >= spans[ix].1 && span.start < ends[ix]);
+        if is_trailing {
+            continue;
+        }
+        if let Some(ix) = (0..spans.len()).find(|&ix| span.start < spans[ix].1) {
+            imports[ix]
+                .leading_comments
+                .push(comment.span.as_str().to_owned());
+        }
+    }
+
+    // Split the imports into groups.
+    let groups: Vec<Vec<Import>> = match config.imports.group_imports {
+        GroupImports::Preserve => {
+            let mut groups = vec![Vec::new()];
+            for (ix, import) in imports.into_iter().enumerate() {
+                if ix > 0 && has_blank_line(&src[ends[ix - 1]..spans[ix].0]) {
+                    groups.push(Vec::new());
+                }
+                groups.last_mut().unwrap().push(import);
+            }
+            groups
+                .into_iter()
+                .map(|group| sort_and_dedup(apply_granularity(group, config), config))
+                .collect()
+        }
+        GroupImports::StdExternalCrate => {
+            let mut groups: Vec<Vec<Import>> = vec![Vec::new(), Vec::new(), Vec::new()];
+            for import in sort_and_dedup(apply_granularity(imports, config), config) {
+                groups[std_external_crate_group(&import, local_modules)].push(import);
+            }
+            groups
+        }
+        GroupImports::One => vec![sort_and_dedup(apply_granularity(imports, config), config)],
+    };
+
+    let mut text = String::new();
+    for group in groups.iter().filter(|group| !group.is_empty()) {
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        for (ix, import) in group.iter().enumerate() {
+            if ix > 0 {
+                text.push('\n');
+            }
+            import.render(&mut text);
+        }
+    }
+    (run_start, run_end, text)
+}
+
+/// Sorts, groups and merges the `use` items of `module`, which was parsed from `src`.
+/// Returns the rewritten source, or `None` if the imports are already as configured.
+pub(crate) fn rewrite_imports(
+    src: &Arc<str>,
+    module: &Module,
+    config: &Config,
+) -> Result<Option<String>, FormatterError> {
+    let imports_config = &config.imports;
+    if !config.ordering.reorder_imports
+        && matches!(imports_config.group_imports, GroupImports::Preserve)
+        && matches!(
+            imports_config.imports_granularity,
+            ImportGranularity::Preserve
+        )
+    {
+        return Ok(None);
+    }
+
+    // Runs of `use` items without attributes, which are the imports that get rewritten.
+    let mut runs: Vec<Vec<&ItemUse>> = Vec::new();
+    let mut in_run = false;
+    for item in &module.items {
+        match &item.value {
+            ItemKind::Use(item_use) if item.attribute_list.is_empty() => {
+                if !in_run {
+                    runs.push(Vec::new());
+                }
+                runs.last_mut().unwrap().push(item_use);
+                in_run = true;
+            }
+            _ => in_run = false,
+        }
+    }
+    if runs.is_empty() {
+        return Ok(None);
+    }
+
+    let local_modules: BTreeSet<String> = module
+        .dependencies
+        .iter()
+        .map(|dependency| match dependency.path.suffixes.last() {
+            Some((_, name)) => name.as_str().to_owned(),
+            None => dependency.path.prefix.as_str().to_owned(),
+        })
+        .collect();
+    let comment_map = comment_map_from_src(src.clone())?;
+
+    let mut rewritten = String::with_capacity(src.len());
+    let mut copied_up_to = 0;
+    for run in &runs {
+        let (start, end, text) = rewrite_run(src, run, &comment_map, &local_modules, config);
+        rewritten.push_str(&src[copied_up_to..start]);
+        rewritten.push_str(&text);
+        copied_up_to = end;
+    }
+    rewritten.push_str(&src[copied_up_to..]);
+
+    Ok(match rewritten == **src {
+        true => None,
+        false => Some(rewritten),
+    })
+}
@@ -2,7 +2,9 @@ use crate::{
     fmt::{Format, FormattedCode, Formatter, FormatterError},
     utils::comments::{ByteSpan, LeafSpans},
 };
+use std::fmt::Write;
 use sway_ast::{Item, ItemKind::*};
+use sway_types::Spanned;
 
 impl LeafSpans for Item {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
@@ -16,8 +18,15 @@ impl LeafSpans for Item {
             Trait(item_trait) => item_trait.leaf_spans(),
             Impl(item_impl) => item_impl.leaf_spans(),
             Use(item_use) => item_use.leaf_spans(),
-            Break(_) => todo!(),
-            Continue(_) => todo!(),
+            Break(item_break) => vec![
+                ByteSpan::from(item_break.break_token.span()),
+                ByteSpan::from(item_break.semicolon_token.span()),
+            ],
+            Continue(item_continue) => vec![
+                ByteSpan::from(item_continue.break_token.span()),
+                ByteSpan::from(item_continue.semicolon_token.span()),
+            ],
+            Error(span) => vec![ByteSpan::from(span.clone())],
         }
     }
 }
@@ -38,8 +47,29 @@ impl Format for Item {
             Abi(item_abi) => item_abi.format(formatted_code, formatter),
             Const(item_const) => item_const.format(formatted_code, formatter),
             Storage(item_storage) => item_storage.format(formatted_code, formatter),
-            Break(_item_break) => todo!(),
-            Continue(_item_continue) => todo!(),
+            Break(item_break) => {
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    item_break.break_token.span().as_str(),
+                    item_break.semicolon_token.span().as_str()
+                )?;
+                Ok(())
+            }
+            Continue(item_continue) => {
+                write!(
+                    formatted_code,
+                    "{}{}",
+                    item_continue.break_token.span().as_str(),
+                    item_continue.semicolon_token.span().as_str()
+                )?;
+                Ok(())
+            }
+            // Source that doesn't parse is kept as it is.
+            Error(span) => {
+                write!(formatted_code, "{}", span.as_str())?;
+                Ok(())
+            }
         }
     }
 }
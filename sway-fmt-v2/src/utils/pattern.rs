@@ -1,6 +1,9 @@
 use crate::{
     fmt::*,
-    utils::comments::{ByteSpan, LeafSpans},
+    utils::{
+        comments::{ByteSpan, LeafSpans},
+        punctuated::{format_comma_separated, is_empty},
+    },
 };
 use std::fmt::Write;
 use sway_ast::{token::Delimiter, Pattern, PatternStructField};
@@ -30,20 +33,19 @@ impl Format for Pattern {
             Self::Constructor { path, args } => {
                 path.format(formatted_code, formatter)?;
                 Self::open_parenthesis(formatted_code, formatter)?;
-                // need to add `<Pattern, CommaToken>` to `Punctuated::format()`
-                args.clone()
-                    .into_inner()
-                    .format(formatted_code, formatter)?;
+                format_comma_separated(args.get(), formatted_code, formatter)?;
                 Self::close_parenthesis(formatted_code, formatter)?;
             }
             Self::Struct { path, fields } => {
+                // `Path { field, other: pattern }`
                 path.format(formatted_code, formatter)?;
+                write!(formatted_code, " ")?;
                 Self::open_curly_brace(formatted_code, formatter)?;
-                // need to add `<PatternStructField, CommaToken>` to `Punctuated::format()`
-                fields
-                    .clone()
-                    .into_inner()
-                    .format(formatted_code, formatter)?;
+                if !is_empty(fields.get()) {
+                    write!(formatted_code, " ")?;
+                    format_comma_separated(fields.get(), formatted_code, formatter)?;
+                    write!(formatted_code, " ")?;
+                }
                 Self::close_curly_brace(formatted_code, formatter)?;
             }
             Self::Tuple(args) => {
@@ -109,7 +111,7 @@ impl Format for PatternStructField {
             } => {
                 write!(formatted_code, "{}", field_name.span().as_str())?;
                 if let Some(pattern) = pattern_opt {
-                    write!(formatted_code, "{}", pattern.0.span().as_str())?;
+                    write!(formatted_code, "{} ", pattern.0.span().as_str())?;
                     pattern.1.format(formatted_code, formatter)?;
                 }
             }
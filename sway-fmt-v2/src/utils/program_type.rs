@@ -1,10 +1,31 @@
 use std::fmt::Write;
-use sway_ast::{dependency::DependencyPath, token::PunctKind, Dependency, Module, ModuleKind};
+use sway_ast::{
+    dependency::DependencyPath, token::PunctKind, AttributeDecl, Dependency, Module, ModuleKind,
+};
 use sway_types::Spanned;
 
-use crate::FormatterError;
+use crate::{fmt::Formatter, FormatterError};
 
-use super::comments::{ByteSpan, LeafSpans};
+use super::{
+    attribute::FormatDecl,
+    comments::{ByteSpan, LeafSpans},
+};
+
+/// Insert the attributes which apply to the whole module, e.g. `#[allow(dead_code)]`, one per
+/// line. Doc comments are left to be written back as comments.
+pub(crate) fn insert_module_attributes(
+    formatted_code: &mut String,
+    attribute_list: &[AttributeDecl],
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    for attr in attribute_list
+        .iter()
+        .filter(|attr| attr.doc_comment().is_none())
+    {
+        attr.format(formatted_code, formatter)?;
+    }
+    Ok(())
+}
 
 /// Insert the program type without applying a formatting to it.
 ///
@@ -43,6 +64,39 @@ pub(crate) fn insert_program_type(
     Ok(())
 }
 
+/// Insert the module's dependencies, e.g. `dep foo/bar;`, one per line.
+pub(crate) fn insert_dependencies(
+    formatted_code: &mut String,
+    dependencies: &[Dependency],
+) -> Result<(), FormatterError> {
+    for dependency in dependencies {
+        write!(
+            formatted_code,
+            "{} {}",
+            dependency.dep_token.span().as_str(),
+            dependency.path.prefix.as_str()
+        )?;
+        for (forward_slash_token, suffix) in &dependency.path.suffixes {
+            write!(
+                formatted_code,
+                "{}{}",
+                forward_slash_token.span().as_str(),
+                suffix.as_str()
+            )?;
+        }
+        writeln!(
+            formatted_code,
+            "{}",
+            dependency.semicolon_token.span().as_str()
+        )?;
+    }
+    if !dependencies.is_empty() {
+        writeln!(formatted_code)?;
+    }
+
+    Ok(())
+}
+
 impl LeafSpans for ModuleKind {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
         match self {
@@ -70,7 +124,13 @@ impl LeafSpans for ModuleKind {
 
 impl LeafSpans for Module {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
-        let mut collected_spans = self.kind.leaf_spans();
+        let mut collected_spans: Vec<ByteSpan> = self
+            .attribute_list
+            .iter()
+            .filter(|attr| attr.doc_comment().is_none())
+            .flat_map(|attr| attr.leaf_spans())
+            .collect();
+        collected_spans.append(&mut self.kind.leaf_spans());
         collected_spans.push(ByteSpan::from(self.semicolon_token.span()));
         collected_spans.append(&mut self.dependencies.leaf_spans());
         collected_spans.append(&mut self.items.leaf_spans());
@@ -4,7 +4,9 @@ use crate::{
     FormatterError,
 };
 use std::fmt::Write;
-use sway_ast::{keywords::CommaToken, punctuated::Punctuated, StorageField, TypeField};
+use sway_ast::{
+    keywords::CommaToken, punctuated::Punctuated, token::PunctKind, StorageField, TypeField,
+};
 use sway_types::{Ident, Spanned};
 
 impl<T, P> LeafSpans for Punctuated<T, P>
@@ -67,6 +69,29 @@ where
     }
 }
 
+/// Formats the values of a comma separated list on a single line, e.g. `a, b, c`.
+/// A trailing comma, if any, is dropped.
+pub(crate) fn format_comma_separated<T: Format>(
+    punctuated: &Punctuated<T, CommaToken>,
+    formatted_code: &mut FormattedCode,
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    let mut values = punctuated.into_iter().peekable();
+    while let Some(value) = values.next() {
+        value.format(formatted_code, formatter)?;
+        if values.peek().is_some() {
+            write!(formatted_code, "{} ", PunctKind::Comma.as_char())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the list has no values at all.
+pub(crate) fn is_empty<T, P>(punctuated: &Punctuated<T, P>) -> bool {
+    punctuated.value_separator_pairs.is_empty() && punctuated.final_value_opt.is_none()
+}
+
 impl Format for Ident {
     fn format(
         &self,
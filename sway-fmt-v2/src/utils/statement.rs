@@ -14,15 +14,36 @@ impl Format for Statement {
     ) -> Result<(), FormatterError> {
         match self {
             Self::Let(let_stmt) => let_stmt.format(formatted_code, formatter)?,
-            Self::Item(item) => item.format(formatted_code, formatter)?,
+            Self::Item(item) => {
+                item.format(formatted_code, formatter)?;
+                // Only some items end their last line themselves.
+                if !formatted_code.ends_with('\n') {
+                    writeln!(formatted_code)?;
+                }
+            }
             Self::Expr {
                 expr,
                 semicolon_token_opt,
             } => {
+                write!(
+                    formatted_code,
+                    "{}",
+                    formatter.shape.indent.to_string(&formatter.config)?
+                )?;
                 expr.format(formatted_code, formatter)?;
                 if let Some(semicolon) = semicolon_token_opt {
                     write!(formatted_code, "{}", semicolon.span().as_str())?;
                 }
+                writeln!(formatted_code)?;
+            }
+            // Source that doesn't parse is kept as it is.
+            Self::Error(span) => {
+                writeln!(
+                    formatted_code,
+                    "{}{}",
+                    formatter.shape.indent.to_string(&formatter.config)?,
+                    span.as_str()
+                )?;
             }
         }
         Ok(())
@@ -75,6 +96,7 @@ impl LeafSpans for Statement {
                 }
                 collected_spans
             }
+            Statement::Error(span) => vec![ByteSpan::from(span.clone())],
         }
     }
 }
@@ -4,9 +4,10 @@ use crate::{
 };
 use std::fmt::Write;
 use sway_ast::{
-    brackets::SquareBrackets,
+    brackets::{Parens, SquareBrackets},
     expr::Expr,
-    keywords::{StrToken, Token, UnderscoreToken},
+    keywords::{CommaToken, FnToken, RightArrowToken, StrToken, Token, UnderscoreToken},
+    punctuated::Punctuated,
     token::Delimiter,
     ty::{Ty, TyArrayDescriptor, TyTupleDescriptor},
 };
@@ -42,10 +43,47 @@ impl Format for Ty {
                 write!(formatted_code, "{}", Delimiter::Parenthesis.as_close_char())?;
                 Ok(())
             }
+            Self::Fn {
+                fn_token,
+                arguments,
+                return_type_opt,
+            } => format_fn(
+                formatted_code,
+                formatter,
+                fn_token,
+                arguments,
+                return_type_opt,
+            ),
         }
     }
 }
 
+/// Formats a function pointer type, e.g. `fn(u64, bool) -> u64`.
+fn format_fn(
+    formatted_code: &mut FormattedCode,
+    formatter: &mut Formatter,
+    fn_token: &FnToken,
+    arguments: &Parens<Punctuated<Ty, CommaToken>>,
+    return_type_opt: &Option<(RightArrowToken, Box<Ty>)>,
+) -> Result<(), FormatterError> {
+    write!(
+        formatted_code,
+        "{}{}",
+        fn_token.span().as_str(),
+        Delimiter::Parenthesis.as_open_char()
+    )?;
+    arguments
+        .clone()
+        .into_inner()
+        .format(formatted_code, formatter)?;
+    write!(formatted_code, "{}", Delimiter::Parenthesis.as_close_char())?;
+    if let Some((right_arrow_token, ty)) = return_type_opt {
+        write!(formatted_code, " {} ", right_arrow_token.ident().as_str())?;
+        ty.format(formatted_code, formatter)?;
+    }
+    Ok(())
+}
+
 /// Simply inserts a `_` token to the `formatted_code`.
 fn format_infer(
     formatted_code: &mut FormattedCode,
@@ -116,6 +154,19 @@ impl LeafSpans for Ty {
                 collected_spans
             }
             Ty::Infer { underscore_token } => vec![ByteSpan::from(underscore_token.span())],
+            Ty::Fn {
+                fn_token,
+                arguments,
+                return_type_opt,
+            } => {
+                let mut collected_spans = vec![ByteSpan::from(fn_token.span())];
+                collected_spans.append(&mut arguments.leaf_spans());
+                if let Some((right_arrow_token, ty)) = return_type_opt {
+                    collected_spans.push(ByteSpan::from(right_arrow_token.span()));
+                    collected_spans.append(&mut ty.leaf_spans());
+                }
+                collected_spans
+            }
         }
     }
 }
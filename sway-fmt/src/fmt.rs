@@ -18,7 +18,8 @@ pub fn get_formatted_data(
     let parsed_res = sway_core::parse(file.clone(), build_config);
 
     match parsed_res.value {
-        Some(parse_program) => {
+        // A partially parsed program can't be formatted, as the broken parts would be lost.
+        Some(parse_program) if parsed_res.errors.is_empty() => {
             // 1 Step: get all individual changes/updates of a Sway file
             let changes = traverse_for_changes(&parse_program.root.tree);
             let mut rope_file = Rope::from_str(&file);
@@ -47,7 +48,7 @@ pub fn get_formatted_data(
 
             Ok(code_builder.get_final_edits())
         }
-        None => Err(parsed_res
+        _ => Err(parsed_res
             .errors
             .iter()
             .map(|e| format!("{} at line: {}", e, e.line_col().0.line,))
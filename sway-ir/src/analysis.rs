@@ -0,0 +1,56 @@
+//! A collection of analyses over the control flow and values of a function.
+//!
+//! Analyses are computed on demand via the accessors on [`Function`](crate::function::Function),
+//! e.g., [`Function::dominator_tree`](crate::function::Function::dominator_tree), and are then
+//! cached on the function.  A pass which modifies a function must call
+//! [`Function::invalidate_analyses`](crate::function::Function::invalidate_analyses) before any
+//! further analyses are requested, which is done by the pass manager for passes which report
+//! they've made a modification.
+
+use std::rc::Rc;
+
+pub mod dominator;
+pub use dominator::*;
+pub mod liveness;
+pub use liveness::*;
+pub mod loops;
+pub use loops::*;
+pub mod memory;
+pub use memory::*;
+pub mod predecessors;
+pub use predecessors::*;
+
+/// The analyses cached for a single function.
+#[derive(Clone, Default)]
+pub(crate) struct AnalysisCache {
+    pub(crate) predecessors: Option<Rc<PredecessorMap>>,
+    pub(crate) dom_tree: Option<Rc<DomTree>>,
+    pub(crate) post_dom_tree: Option<Rc<DomTree>>,
+    pub(crate) loops: Option<Rc<LoopForest>>,
+    pub(crate) liveness: Option<Rc<Liveness>>,
+    pub(crate) memory_effects: Option<Rc<MemoryEffects>>,
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{block::Block, context::Context, function::Function};
+
+    /// Parse `ir`, which must be a module with a single function, and return that function.
+    pub(crate) fn parse_function(ir: &str) -> (Context, Function) {
+        let context = crate::parser::parse(ir).unwrap();
+        let function = context
+            .module_iter()
+            .flat_map(|module| module.function_iter(&context))
+            .next()
+            .unwrap();
+        (context, function)
+    }
+
+    /// Return the block in `function` with `label`.
+    pub(crate) fn block(context: &Context, function: &Function, label: &str) -> Block {
+        function
+            .block_iter(context)
+            .find(|block| block.get_label(context) == label)
+            .unwrap()
+    }
+}
@@ -0,0 +1,332 @@
+//! ## Dominator Trees
+//!
+//! Block `a` dominates block `b` if every path from the entry block to `b` passes through `a`.
+//! Likewise `a` post-dominates `b` if every path from `b` to an exit, i.e., a block which has no
+//! successors, passes through `a`.  Every block (post-)dominates itself.
+//!
+//! The trees are built using the iterative algorithm from Cooper, Harvey and Kennedy, 'A Simple,
+//! Fast Dominance Algorithm'.  Blocks which are unreachable, from the entry block or backwards
+//! from an exit respectively, are not in the tree.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{analysis::PredecessorMap, block::Block, context::Context, function::Function};
+
+pub struct DomTree {
+    roots: Vec<Block>,
+    idoms: HashMap<Block, Option<Block>>,
+    children: HashMap<Block, Vec<Block>>,
+
+    // The blocks in a pre-order walk of the tree and, for each block, its pre-order index and the
+    // largest pre-order index within its subtree.  Dominance is then just a range check.
+    pre_order: Vec<Block>,
+    order_ranges: HashMap<Block, (usize, usize)>,
+}
+
+impl DomTree {
+    /// Build the dominator tree for `function`, rooted at its entry block.
+    pub fn new(context: &Context, function: &Function, preds: &PredecessorMap) -> Self {
+        DomTree::build(
+            &[function.get_entry_block(context)],
+            |block| preds.successors(block),
+            |block| preds.predecessors(block),
+        )
+    }
+
+    /// Build the post-dominator tree for `function`.  Each exit block is a root of the tree.
+    pub fn new_post_dominator(
+        context: &Context,
+        function: &Function,
+        preds: &PredecessorMap,
+    ) -> Self {
+        let exits = function
+            .block_iter(context)
+            .filter(|block| preds.successors(block).is_empty())
+            .collect::<Vec<_>>();
+        DomTree::build(
+            &exits,
+            |block| preds.predecessors(block),
+            |block| preds.successors(block),
+        )
+    }
+
+    /// Return the roots of the tree; the entry block for a dominator tree or the exit blocks for
+    /// a post-dominator tree.
+    pub fn roots(&self) -> &[Block] {
+        &self.roots
+    }
+
+    /// Return whether `block` is in the tree, i.e., whether it is reachable.
+    pub fn contains(&self, block: &Block) -> bool {
+        self.idoms.contains_key(block)
+    }
+
+    /// Return the immediate dominator of `block`, which is `None` for a root or a block which is
+    /// not in the tree.
+    pub fn immediate_dominator(&self, block: &Block) -> Option<Block> {
+        self.idoms.get(block).copied().flatten()
+    }
+
+    /// Return the blocks which `block` immediately dominates.
+    pub fn children(&self, block: &Block) -> &[Block] {
+        self.children
+            .get(block)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Return whether `dominator` dominates `block`.  This is always true if they're the same
+    /// block, and always false if either isn't in the tree.
+    pub fn dominates(&self, dominator: &Block, block: &Block) -> bool {
+        match (
+            self.order_ranges.get(dominator),
+            self.order_ranges.get(block),
+        ) {
+            (Some((dom_first, dom_last)), Some((block_first, _))) => {
+                dom_first <= block_first && block_first <= dom_last
+            }
+            _otherwise => false,
+        }
+    }
+
+    /// Return whether `dominator` dominates `block` and they are different blocks.
+    pub fn strictly_dominates(&self, dominator: &Block, block: &Block) -> bool {
+        dominator != block && self.dominates(dominator, block)
+    }
+
+    /// Return the blocks in the tree in pre-order, so that every block follows its dominators.
+    pub fn pre_order(&self) -> &[Block] {
+        &self.pre_order
+    }
+
+    // `forward` returns the blocks which follow a block in the direction of the analysis and
+    // `backward` those which precede it.  These are successors and predecessors respectively for
+    // a dominator tree and vice versa for a post-dominator tree.
+    fn build<'a, F, B>(roots: &[Block], forward: F, backward: B) -> Self
+    where
+        F: Fn(&Block) -> &'a [Block],
+        B: Fn(&Block) -> &'a [Block],
+    {
+        // Number the reachable blocks in reverse post-order, starting at 1.  Index 0 is a virtual
+        // block which precedes each of the roots, so there is always a single root.
+        let mut post_order = Vec::new();
+        let mut visited = HashSet::new();
+        for root in roots {
+            if !visited.insert(*root) {
+                continue;
+            }
+            let mut stack = vec![(*root, 0)];
+            while let Some((block, next_idx)) = stack.last_mut() {
+                match forward(block).get(*next_idx) {
+                    Some(next_block) => {
+                        *next_idx += 1;
+                        if visited.insert(*next_block) {
+                            stack.push((*next_block, 0));
+                        }
+                    }
+                    None => {
+                        post_order.push(*block);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        let rev_post_order = post_order.into_iter().rev().collect::<Vec<_>>();
+        let order_idcs = rev_post_order
+            .iter()
+            .enumerate()
+            .map(|(idx, block)| (*block, idx + 1))
+            .collect::<HashMap<_, _>>();
+
+        let mut idoms: Vec<Option<usize>> = vec![None; rev_post_order.len() + 1];
+        idoms[0] = Some(0);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (idx, block) in rev_post_order.iter().enumerate() {
+                let mut new_idom = if roots.contains(block) { Some(0) } else { None };
+                for prev_idx in backward(block)
+                    .iter()
+                    .filter_map(|prev_block| order_idcs.get(prev_block).copied())
+                    .filter(|prev_idx| idoms[*prev_idx].is_some())
+                {
+                    new_idom = Some(match new_idom {
+                        Some(cur_idom) => intersect(&idoms, prev_idx, cur_idom),
+                        None => prev_idx,
+                    });
+                }
+                if idoms[idx + 1] != new_idom {
+                    idoms[idx + 1] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        // Convert the indices back into blocks.
+        let mut tree = DomTree {
+            roots: Vec::new(),
+            idoms: HashMap::new(),
+            children: HashMap::new(),
+            pre_order: Vec::new(),
+            order_ranges: HashMap::new(),
+        };
+        for (idx, block) in rev_post_order.iter().enumerate() {
+            let idom = match idoms[idx + 1] {
+                Some(0) | None => {
+                    tree.roots.push(*block);
+                    None
+                }
+                Some(idom_idx) => {
+                    let idom = rev_post_order[idom_idx - 1];
+                    tree.children.entry(idom).or_default().push(*block);
+                    Some(idom)
+                }
+            };
+            tree.idoms.insert(*block, idom);
+        }
+
+        // Walk the tree to number the blocks for dominance queries.
+        let mut stack = tree.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(block) = stack.pop() {
+            tree.order_ranges.insert(block, (tree.pre_order.len(), 0));
+            tree.pre_order.push(block);
+            stack.extend(tree.children(&block).iter().rev());
+        }
+        for block in tree.pre_order.iter().rev() {
+            let last = tree
+                .children(block)
+                .iter()
+                .map(|child| tree.order_ranges[child].1)
+                .max()
+                .unwrap_or(tree.order_ranges[block].0);
+            tree.order_ranges.get_mut(block).unwrap().1 = last;
+        }
+
+        tree
+    }
+}
+
+// Find the nearest common dominator of two blocks, by their reverse post-order indices.
+fn intersect(idoms: &[Option<usize>], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while a > b {
+            a = idoms[a].unwrap();
+        }
+        while b > a {
+            b = idoms[b].unwrap();
+        }
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{block, parse_function};
+
+    const DIAMOND: &str = r#"
+script {
+    fn main(c: bool) -> u64 {
+        entry:
+        cbr c, left, right
+
+        left:
+        br join
+
+        right:
+        br join
+
+        join:
+        v0 = const u64 0
+        ret u64 v0
+    }
+}
+"#;
+
+    // Both `left` and `right` branch to each other, so the cycle they form has two entries and
+    // neither dominates the other.
+    const IRREDUCIBLE: &str = r#"
+script {
+    fn main(c: bool) -> u64 {
+        entry:
+        cbr c, left, right
+
+        left:
+        cbr c, right, exit
+
+        right:
+        br left
+
+        exit:
+        v0 = const u64 0
+        ret u64 v0
+
+        dead:
+        br exit
+    }
+}
+"#;
+
+    #[test]
+    fn diamond() {
+        let (context, function) = parse_function(DIAMOND);
+        let [entry, left, right, join] =
+            ["entry", "left", "right", "join"].map(|label| block(&context, &function, label));
+
+        let dom_tree = function.dominator_tree(&context);
+        assert_eq!(dom_tree.roots(), &[entry]);
+        assert_eq!(dom_tree.immediate_dominator(&entry), None);
+        assert_eq!(dom_tree.immediate_dominator(&left), Some(entry));
+        assert_eq!(dom_tree.immediate_dominator(&right), Some(entry));
+        assert_eq!(dom_tree.immediate_dominator(&join), Some(entry));
+        assert_eq!(dom_tree.children(&entry).len(), 3);
+        assert!(dom_tree.dominates(&entry, &join));
+        assert!(dom_tree.dominates(&join, &join));
+        assert!(!dom_tree.strictly_dominates(&join, &join));
+        assert!(!dom_tree.dominates(&left, &join));
+        assert!(!dom_tree.dominates(&right, &join));
+        assert_eq!(dom_tree.pre_order()[0], entry);
+
+        let post_dom_tree = function.post_dominator_tree(&context);
+        assert_eq!(post_dom_tree.roots(), &[join]);
+        assert_eq!(post_dom_tree.immediate_dominator(&join), None);
+        assert_eq!(post_dom_tree.immediate_dominator(&left), Some(join));
+        assert_eq!(post_dom_tree.immediate_dominator(&right), Some(join));
+        assert_eq!(post_dom_tree.immediate_dominator(&entry), Some(join));
+        assert!(post_dom_tree.dominates(&join, &entry));
+        assert!(!post_dom_tree.dominates(&left, &entry));
+        assert!(!post_dom_tree.dominates(&entry, &left));
+    }
+
+    #[test]
+    fn irreducible() {
+        let (context, function) = parse_function(IRREDUCIBLE);
+        let [entry, left, right, exit, dead] = ["entry", "left", "right", "exit", "dead"]
+            .map(|label| block(&context, &function, label));
+
+        let dom_tree = function.dominator_tree(&context);
+        assert_eq!(dom_tree.roots(), &[entry]);
+        assert_eq!(dom_tree.immediate_dominator(&left), Some(entry));
+        assert_eq!(dom_tree.immediate_dominator(&right), Some(entry));
+        assert_eq!(dom_tree.immediate_dominator(&exit), Some(left));
+        assert!(!dom_tree.dominates(&left, &right));
+        assert!(!dom_tree.dominates(&right, &left));
+        assert!(dom_tree.dominates(&left, &exit));
+
+        // The dead block can't be reached from the entry, so it has no dominators and dominates
+        // nothing.
+        assert!(!dom_tree.contains(&dead));
+        assert_eq!(dom_tree.immediate_dominator(&dead), None);
+        assert!(!dom_tree.dominates(&dead, &exit));
+        assert!(!dom_tree.dominates(&entry, &dead));
+
+        // Every path from `right` to the exit passes through `left`, but not vice versa.
+        let post_dom_tree = function.post_dominator_tree(&context);
+        assert_eq!(post_dom_tree.roots(), &[exit]);
+        assert_eq!(post_dom_tree.immediate_dominator(&left), Some(exit));
+        assert_eq!(post_dom_tree.immediate_dominator(&right), Some(left));
+        assert_eq!(post_dom_tree.immediate_dominator(&entry), Some(left));
+        assert_eq!(post_dom_tree.immediate_dominator(&dead), Some(exit));
+        assert!(post_dom_tree.dominates(&left, &right));
+        assert!(!post_dom_tree.dominates(&right, &left));
+    }
+}
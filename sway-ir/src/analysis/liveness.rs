@@ -0,0 +1,209 @@
+//! ## Value Liveness
+//!
+//! A value is live at a point in a function if it may still be used afterwards.  Only arguments
+//! and instructions are tracked; constants have no definition and so are never live.
+//!
+//! A phi is treated as defining its value at the start of its block while its incoming values are
+//! used at the end of the corresponding predecessors, i.e., they are live out of the predecessor
+//! but not live into the phi block.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    analysis::PredecessorMap,
+    block::Block,
+    context::Context,
+    function::Function,
+    instruction::Instruction,
+    value::{Value, ValueDatum},
+};
+
+pub struct Liveness {
+    live_in: HashMap<Block, HashSet<Value>>,
+    live_out: HashMap<Block, HashSet<Value>>,
+
+    // The number of values live immediately after each instruction.
+    pressure: HashMap<Value, usize>,
+}
+
+impl Liveness {
+    /// Compute the liveness of the values in `function`.
+    pub fn new(context: &Context, function: &Function, preds: &PredecessorMap) -> Self {
+        let is_tracked = |value: &Value| {
+            matches!(
+                context.values[value.0].value,
+                ValueDatum::Argument(_) | ValueDatum::Instruction(_)
+            )
+        };
+
+        let def_blocks = function
+            .instruction_iter(context)
+            .map(|(block, instr_val)| (instr_val, block))
+            .collect::<HashMap<_, _>>();
+        let defines = |block: &Block, value: &Value| def_blocks.get(value) == Some(block);
+
+        // Gather the values used in each block before they're defined there, which in SSA means
+        // those defined elsewhere, and the phi values used at the end of each block.
+        let mut uses: HashMap<Block, HashSet<Value>> = HashMap::new();
+        let mut phi_uses: HashMap<Block, HashSet<Value>> = HashMap::new();
+        for block in function.block_iter(context) {
+            let mut block_uses = HashSet::new();
+            for instr_val in block.instruction_iter(context) {
+                match &context.values[instr_val.0].value {
+                    ValueDatum::Instruction(Instruction::Phi(pairs)) => {
+                        for (from_block, phi_val) in pairs.iter().filter(|(_, v)| is_tracked(v)) {
+                            phi_uses.entry(*from_block).or_default().insert(*phi_val);
+                        }
+                    }
+                    ValueDatum::Instruction(instruction) => block_uses.extend(
+                        instruction
+                            .get_operands()
+                            .into_iter()
+                            .filter(|operand| is_tracked(operand) && !defines(&block, operand)),
+                    ),
+                    _otherwise => unreachable!("Block contains a non-instruction value."),
+                }
+            }
+            uses.insert(block, block_uses);
+        }
+
+        // Iterate to a fixed point, visiting the blocks in reverse since liveness flows backwards.
+        let blocks = function.block_iter(context).collect::<Vec<_>>();
+        let mut live_in: HashMap<Block, HashSet<Value>> = blocks
+            .iter()
+            .map(|block| (*block, HashSet::new()))
+            .collect();
+        let mut live_out = live_in.clone();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in blocks.iter().rev() {
+                let mut block_live_out = phi_uses.get(block).cloned().unwrap_or_default();
+                for succ in preds.successors(block) {
+                    block_live_out.extend(live_in[succ].iter().copied());
+                }
+                let mut block_live_in = uses[block].clone();
+                block_live_in.extend(
+                    block_live_out
+                        .iter()
+                        .filter(|value| !defines(block, value))
+                        .copied(),
+                );
+                if block_live_in != live_in[block] || block_live_out != live_out[block] {
+                    live_in.insert(*block, block_live_in);
+                    live_out.insert(*block, block_live_out);
+                    changed = true;
+                }
+            }
+        }
+
+        // Walk backwards through each block to find the values live after each instruction.
+        let mut pressure = HashMap::new();
+        for block in &blocks {
+            let mut live = live_out[block].clone();
+            let instrs = block.instruction_iter(context).collect::<Vec<_>>();
+            for instr_val in instrs.iter().rev() {
+                pressure.insert(*instr_val, live.len());
+                live.remove(instr_val);
+                match &context.values[instr_val.0].value {
+                    ValueDatum::Instruction(Instruction::Phi(_)) => (),
+                    ValueDatum::Instruction(instruction) => {
+                        live.extend(instruction.get_operands().into_iter().filter(is_tracked))
+                    }
+                    _otherwise => unreachable!("Block contains a non-instruction value."),
+                }
+            }
+        }
+
+        Liveness {
+            live_in,
+            live_out,
+            pressure,
+        }
+    }
+
+    /// Return the values live on entry to `block`, not including its phi.
+    pub fn live_in(&self, block: &Block) -> &HashSet<Value> {
+        &self.live_in[block]
+    }
+
+    /// Return the values live on exit from `block`, including those it passes to phis.
+    pub fn live_out(&self, block: &Block) -> &HashSet<Value> {
+        &self.live_out[block]
+    }
+
+    /// Return the number of values live immediately after `instr_val`, which is a rough measure
+    /// of how many registers are needed at that point.
+    pub fn register_pressure(&self, instr_val: &Value) -> usize {
+        self.pressure.get(instr_val).copied().unwrap_or(0)
+    }
+
+    /// Return the greatest register pressure anywhere in the function.
+    pub fn max_register_pressure(&self) -> usize {
+        self.pressure.values().copied().max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::analysis::tests::{block, parse_function};
+
+    #[test]
+    fn loop_carried_value() {
+        let (context, function) = parse_function(
+            r#"
+script {
+    fn main(n: u64) -> u64 {
+        entry:
+        v0 = const u64 0
+        br header
+
+        header:
+        v1 = phi(entry: v0, body: v3)
+        v2 = cmp eq v1 n
+        cbr v2, exit, body
+
+        body:
+        v3 = asm(a: v1, r) -> u64 r {
+            addi   r a i1
+        }
+        br header
+
+        exit:
+        ret u64 v1
+    }
+}
+"#,
+        );
+        let [entry, header, body, exit] =
+            ["entry", "header", "body", "exit"].map(|label| block(&context, &function, label));
+        let n = function.get_arg(&context, "n").unwrap();
+        let counter = header.get_phi(&context);
+        let cond = header.instruction_iter(&context).nth(1).unwrap();
+        let next = body.instruction_iter(&context).nth(1).unwrap();
+
+        let liveness = function.liveness(&context);
+
+        // The argument is used on every iteration, so it's live throughout the loop.
+        assert_eq!(liveness.live_in(&entry), &HashSet::from([n]));
+        assert_eq!(liveness.live_out(&entry), &HashSet::from([n]));
+        assert_eq!(liveness.live_in(&header), &HashSet::from([n]));
+        assert_eq!(liveness.live_out(&header), &HashSet::from([n, counter]));
+
+        // The next value of the counter is passed back to the phi along the back edge, so it's
+        // live out of the body but not into the header.
+        assert_eq!(liveness.live_in(&body), &HashSet::from([n, counter]));
+        assert_eq!(liveness.live_out(&body), &HashSet::from([n, next]));
+        assert!(!liveness.live_in(&header).contains(&next));
+
+        assert_eq!(liveness.live_in(&exit), &HashSet::from([counter]));
+        assert!(liveness.live_out(&exit).is_empty());
+
+        // The condition, the counter and the argument are all live after the comparison.
+        assert_eq!(liveness.register_pressure(&cond), 3);
+        assert_eq!(liveness.register_pressure(&next), 2);
+        assert_eq!(liveness.max_register_pressure(), 3);
+    }
+}
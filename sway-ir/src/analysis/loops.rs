@@ -0,0 +1,268 @@
+//! ## Natural Loops
+//!
+//! A back edge is a branch from a block to one of its dominators, which is the loop header.  The
+//! natural loop of a back edge is the header plus every block which can reach the source of the
+//! edge, the latch, without passing through the header.  Loops which share a header are merged
+//! into a single loop with multiple latches.
+//!
+//! Two natural loops with different headers are either disjoint or one is nested within the
+//! other, so the loops form a forest.
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{
+    analysis::{DomTree, PredecessorMap},
+    block::Block,
+    context::Context,
+    function::Function,
+};
+
+pub struct Loop {
+    /// The single entry into the loop, which dominates every block in it.
+    pub header: Block,
+    /// The blocks which branch back to the header.
+    pub latches: Vec<Block>,
+    /// Every block in the loop, including those in nested loops, in function block order.
+    pub blocks: Vec<Block>,
+    /// The index of the immediately enclosing loop in the [`LoopForest`], if any.
+    pub parent: Option<usize>,
+    /// The number of loops this loop is nested in, plus one.
+    pub depth: usize,
+}
+
+impl Loop {
+    /// Return whether `block` is in this loop.
+    pub fn contains(&self, block: &Block) -> bool {
+        self.blocks.contains(block)
+    }
+
+    /// Return the edges which leave this loop, as `(from_block, to_block)` pairs.
+    pub fn exit_edges(&self, preds: &PredecessorMap) -> Vec<(Block, Block)> {
+        self.blocks
+            .iter()
+            .flat_map(|block| {
+                preds
+                    .successors(block)
+                    .iter()
+                    .filter(|succ| !self.contains(succ))
+                    .map(|succ| (*block, *succ))
+            })
+            .collect()
+    }
+}
+
+pub struct LoopForest {
+    loops: Vec<Loop>,
+    innermost: HashMap<Block, usize>,
+}
+
+impl LoopForest {
+    /// Find the natural loops in `function`.
+    pub fn new(
+        context: &Context,
+        function: &Function,
+        preds: &PredecessorMap,
+        dom_tree: &DomTree,
+    ) -> Self {
+        let mut loops = Vec::new();
+        for header in function.block_iter(context) {
+            let latches = preds
+                .predecessors(&header)
+                .iter()
+                .filter(|pred| dom_tree.dominates(&header, pred))
+                .copied()
+                .collect::<Vec<_>>();
+            if latches.is_empty() {
+                continue;
+            }
+
+            // Walk backwards from the latches until we reach the header.  Unreachable blocks may
+            // branch into the loop but they are not part of it.
+            let mut body = HashSet::from([header]);
+            let mut worklist = latches.clone();
+            while let Some(block) = worklist.pop() {
+                if body.insert(block) {
+                    worklist.extend(
+                        preds
+                            .predecessors(&block)
+                            .iter()
+                            .filter(|pred| dom_tree.contains(pred)),
+                    );
+                }
+            }
+
+            loops.push(Loop {
+                header,
+                latches,
+                blocks: function
+                    .block_iter(context)
+                    .filter(|block| body.contains(block))
+                    .collect(),
+                parent: None,
+                depth: 1,
+            });
+        }
+
+        // An enclosing loop is always bigger than the loops nested within it, so sorting by size
+        // puts every loop after its parent.  Its parent is then the last loop before it which
+        // contains its header.
+        loops.sort_by_key(|a_loop| Reverse(a_loop.blocks.len()));
+        let mut innermost = HashMap::new();
+        for idx in 0..loops.len() {
+            let parent = (0..idx)
+                .rev()
+                .find(|parent_idx| loops[*parent_idx].contains(&loops[idx].header));
+            loops[idx].parent = parent;
+            loops[idx].depth = parent
+                .map(|parent_idx| loops[parent_idx].depth + 1)
+                .unwrap_or(1);
+            for block in &loops[idx].blocks {
+                innermost.insert(*block, idx);
+            }
+        }
+
+        LoopForest { loops, innermost }
+    }
+
+    /// Return every loop, with each outer loop before the loops nested within it.
+    pub fn loops(&self) -> &[Loop] {
+        &self.loops
+    }
+
+    /// Return the innermost loop containing `block`, if any.
+    pub fn innermost_loop(&self, block: &Block) -> Option<&Loop> {
+        self.innermost.get(block).map(|idx| &self.loops[*idx])
+    }
+
+    /// Return the number of loops which contain `block`.
+    pub fn loop_depth(&self, block: &Block) -> usize {
+        self.innermost_loop(block)
+            .map(|inner_loop| inner_loop.depth)
+            .unwrap_or(0)
+    }
+
+    /// Return the loop which immediately encloses `inner_loop`, if any.
+    pub fn parent(&self, inner_loop: &Loop) -> Option<&Loop> {
+        inner_loop.parent.map(|idx| &self.loops[idx])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{block, parse_function};
+
+    #[test]
+    fn nested_loops() {
+        let (context, function) = parse_function(
+            r#"
+script {
+    fn main(c: bool) -> u64 {
+        entry:
+        br outer
+
+        outer:
+        cbr c, inner, exit
+
+        inner:
+        cbr c, inner_body, outer_latch
+
+        inner_body:
+        br inner
+
+        outer_latch:
+        br outer
+
+        exit:
+        v0 = const u64 0
+        ret u64 v0
+    }
+}
+"#,
+        );
+        let [entry, outer, inner, inner_body, outer_latch, exit] = [
+            "entry",
+            "outer",
+            "inner",
+            "inner_body",
+            "outer_latch",
+            "exit",
+        ]
+        .map(|label| block(&context, &function, label));
+
+        let loop_forest = function.loop_forest(&context);
+        let loops = loop_forest.loops();
+        assert_eq!(loops.len(), 2);
+
+        let outer_loop = &loops[0];
+        assert_eq!(outer_loop.header, outer);
+        assert_eq!(outer_loop.latches, vec![outer_latch]);
+        assert_eq!(
+            outer_loop.blocks,
+            vec![outer, inner, inner_body, outer_latch]
+        );
+        assert_eq!(outer_loop.parent, None);
+        assert_eq!(outer_loop.depth, 1);
+        assert!(loop_forest.parent(outer_loop).is_none());
+
+        let inner_loop = &loops[1];
+        assert_eq!(inner_loop.header, inner);
+        assert_eq!(inner_loop.latches, vec![inner_body]);
+        assert_eq!(inner_loop.blocks, vec![inner, inner_body]);
+        assert_eq!(inner_loop.parent, Some(0));
+        assert_eq!(inner_loop.depth, 2);
+        assert_eq!(loop_forest.parent(inner_loop).unwrap().header, outer);
+
+        assert_eq!(loop_forest.loop_depth(&entry), 0);
+        assert_eq!(loop_forest.loop_depth(&outer), 1);
+        assert_eq!(loop_forest.loop_depth(&outer_latch), 1);
+        assert_eq!(loop_forest.loop_depth(&inner), 2);
+        assert_eq!(loop_forest.loop_depth(&inner_body), 2);
+        assert_eq!(loop_forest.loop_depth(&exit), 0);
+        assert_eq!(
+            loop_forest.innermost_loop(&inner_body).unwrap().header,
+            inner
+        );
+        assert_eq!(
+            loop_forest.innermost_loop(&outer_latch).unwrap().header,
+            outer
+        );
+        assert!(loop_forest.innermost_loop(&exit).is_none());
+
+        let preds = function.predecessor_map(&context);
+        assert_eq!(outer_loop.exit_edges(&preds), vec![(outer, exit)]);
+        assert_eq!(inner_loop.exit_edges(&preds), vec![(inner, outer_latch)]);
+    }
+
+    #[test]
+    fn irreducible_cycle() {
+        // The cycle between `left` and `right` has two entries, so neither is a loop header.
+        let (context, function) = parse_function(
+            r#"
+script {
+    fn main(c: bool) -> u64 {
+        entry:
+        cbr c, left, right
+
+        left:
+        cbr c, right, exit
+
+        right:
+        br left
+
+        exit:
+        v0 = const u64 0
+        ret u64 v0
+    }
+}
+"#,
+        );
+        let left = block(&context, &function, "left");
+
+        let loop_forest = function.loop_forest(&context);
+        assert!(loop_forest.loops().is_empty());
+        assert_eq!(loop_forest.loop_depth(&left), 0);
+    }
+}
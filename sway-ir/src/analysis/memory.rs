@@ -0,0 +1,386 @@
+//! ## Memory Effects
+//!
+//! The memory read and written by each instruction, approximated by [`MemoryRegion`].  A region
+//! is either a single local variable or constant aggregate, which are private to the function
+//! until their address escapes, or one of a few broader classes of memory.
+//!
+//! The regions match how the values are compiled rather than the IR's value semantics: a `load`
+//! of an aggregate, or an `extract_value` of a nested aggregate, is just the address of the
+//! original memory, and an `insert_value` modifies its aggregate in place.  So these results
+//! alias the region they were derived from.
+//!
+//! Call and contract call instructions and ASM blocks which aren't understood may access any
+//! memory which isn't private, and any region they're given the address of escapes.
+
+use std::collections::{HashMap, HashSet};
+
+use sway_types::ident::Ident;
+
+use crate::{
+    asm::{AsmArg, AsmBlock, AsmInstruction},
+    context::Context,
+    function::Function,
+    instruction::Instruction,
+    pointer::Pointer,
+    value::{Value, ValueDatum},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MemoryRegion {
+    /// The memory of a local variable.
+    Local(Pointer),
+    /// The memory holding a constant aggregate value.
+    Constant(Value),
+    /// Memory passed in by reference to the function's arguments.
+    Argument,
+    /// Contract storage.
+    Storage,
+    /// Any memory at all.
+    Unknown,
+}
+
+impl MemoryRegion {
+    /// Return the region `value` points to, or `None` if it isn't an address.  Values of copy
+    /// types are not addresses, unless they're a `get_ptr` or `int_to_ptr`.
+    pub fn of_value(context: &Context, value: &Value) -> Option<MemoryRegion> {
+        match &context.values[value.0].value {
+            ValueDatum::Instruction(Instruction::GetPointer { base_ptr, .. }) => {
+                return Some(MemoryRegion::Local(*base_ptr))
+            }
+            ValueDatum::Instruction(Instruction::IntToPtr(..)) => {
+                return Some(MemoryRegion::Unknown)
+            }
+            _otherwise => (),
+        }
+        if value.get_type(context).map_or(true, |ty| ty.is_copy_type()) {
+            return None;
+        }
+
+        Some(match &context.values[value.0].value {
+            ValueDatum::Argument(_) => MemoryRegion::Argument,
+            ValueDatum::Constant(_) => MemoryRegion::Constant(*value),
+            ValueDatum::Instruction(instruction) => match instruction {
+                Instruction::Load(src_val) => MemoryRegion::of_pointer(context, src_val),
+                Instruction::ExtractElement { array, .. } => {
+                    MemoryRegion::of_pointer(context, array)
+                }
+                Instruction::ExtractValue { aggregate, .. } => {
+                    MemoryRegion::of_pointer(context, aggregate)
+                }
+                Instruction::InsertElement { array, .. } => {
+                    MemoryRegion::of_pointer(context, array)
+                }
+                Instruction::InsertValue { aggregate, .. } => {
+                    MemoryRegion::of_pointer(context, aggregate)
+                }
+                Instruction::AsmBlock(asm, args) => {
+                    // An ASM block may return one of its arguments unchanged, e.g., the buffer
+                    // a hash was written to.
+                    let asm_content = &context.asm_blocks[asm.0];
+                    asm_content
+                        .return_name
+                        .as_ref()
+                        .map(|ret_name| asm_register_region(context, asm, args, ret_name.as_str()))
+                        .unwrap_or(MemoryRegion::Unknown)
+                }
+                // The key is written to memory which is never modified.
+                Instruction::GetStorageKey => MemoryRegion::Constant(*value),
+                _otherwise => MemoryRegion::Unknown,
+            },
+        })
+    }
+
+    // The region of a value which is expected to be an address, but which may not be.
+    fn of_pointer(context: &Context, value: &Value) -> MemoryRegion {
+        MemoryRegion::of_value(context, value).unwrap_or(MemoryRegion::Unknown)
+    }
+
+    /// Return whether this region is only accessible within the function, assuming its address
+    /// doesn't escape.
+    pub fn is_private(&self) -> bool {
+        matches!(self, MemoryRegion::Local(_) | MemoryRegion::Constant(_))
+    }
+}
+
+pub struct MemoryEffects {
+    reads: HashMap<Value, Vec<MemoryRegion>>,
+    writes: HashMap<Value, Vec<MemoryRegion>>,
+    escaped: HashSet<MemoryRegion>,
+    opaque: HashSet<Value>,
+    may_trap: HashSet<Value>,
+}
+
+impl MemoryEffects {
+    /// Find the memory effects of every instruction in `function`.
+    pub fn new(context: &Context, function: &Function) -> Self {
+        let mut effects = MemoryEffects {
+            reads: HashMap::new(),
+            writes: HashMap::new(),
+            escaped: HashSet::new(),
+            opaque: HashSet::new(),
+            may_trap: HashSet::new(),
+        };
+        for (_, instr_val) in function.instruction_iter(context) {
+            if let ValueDatum::Instruction(instruction) = &context.values[instr_val.0].value {
+                effects.add_instruction(context, instr_val, instruction);
+            }
+        }
+        effects
+    }
+
+    /// Return the regions `instr_val` may read from.
+    pub fn reads(&self, instr_val: &Value) -> &[MemoryRegion] {
+        self.reads
+            .get(instr_val)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Return the regions `instr_val` may write to.
+    pub fn writes(&self, instr_val: &Value) -> &[MemoryRegion] {
+        self.writes
+            .get(instr_val)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Return whether `region` is private to the function and its address never escapes, so
+    /// that it may only be accessed by instructions which refer to it directly.
+    pub fn is_private(&self, region: &MemoryRegion) -> bool {
+        region.is_private() && !self.escaped.contains(region)
+    }
+
+    /// Return whether an access to `a` may access the same memory as an access to `b`.
+    pub fn may_alias(&self, a: &MemoryRegion, b: &MemoryRegion) -> bool {
+        match (a, b) {
+            (MemoryRegion::Unknown, other) | (other, MemoryRegion::Unknown) => {
+                !self.is_private(other)
+            }
+            _otherwise => a == b,
+        }
+    }
+
+    /// Return whether `instr_val` is an ASM block whose effects couldn't be determined, either
+    /// because it uses instructions which aren't understood or it uses reserved registers.
+    pub fn is_opaque(&self, instr_val: &Value) -> bool {
+        self.opaque.contains(instr_val)
+    }
+
+    /// Return whether `instr_val` is an ASM block which may trap, e.g., by dividing by zero.
+    pub fn may_trap(&self, instr_val: &Value) -> bool {
+        self.may_trap.contains(instr_val)
+    }
+
+    fn add_instruction(&mut self, context: &Context, instr_val: Value, instruction: &Instruction) {
+        let region = |value: &Value| MemoryRegion::of_value(context, value);
+        let pointer = |value: &Value| MemoryRegion::of_pointer(context, value);
+        let mut reads = Vec::new();
+        let mut writes = Vec::new();
+        match instruction {
+            Instruction::AsmBlock(asm, args) => {
+                self.add_asm_block(context, instr_val, asm, args, &mut reads, &mut writes)
+            }
+            Instruction::Call(_, args) => {
+                self.escape_all(context, args);
+                reads.push(MemoryRegion::Unknown);
+                writes.push(MemoryRegion::Unknown);
+            }
+            Instruction::CallIndirect { callee, args, .. } => {
+                self.escape_all(context, &[*callee]);
+                self.escape_all(context, args);
+                reads.push(MemoryRegion::Unknown);
+                writes.push(MemoryRegion::Unknown);
+            }
+            Instruction::ContractCall {
+                params,
+                coins,
+                asset_id,
+                gas,
+                ..
+            } => {
+                self.escape_all(context, &[*params, *coins, *asset_id, *gas]);
+                reads.push(MemoryRegion::Unknown);
+                writes.push(MemoryRegion::Unknown);
+            }
+            Instruction::AddrOf(value)
+            | Instruction::BitCast(value, _)
+            | Instruction::IntToPtr(value, _)
+            | Instruction::Ret(value, _) => self.escape_all(context, &[*value]),
+            Instruction::Phi(pairs) => {
+                let values = pairs.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+                self.escape_all(context, &values);
+            }
+            Instruction::Cmp(_, lhs_val, rhs_val) => {
+                reads.extend(region(lhs_val));
+                reads.extend(region(rhs_val));
+            }
+            Instruction::ExtractElement { array, .. } => reads.push(pointer(array)),
+            Instruction::ExtractValue { aggregate, .. } => reads.push(pointer(aggregate)),
+            Instruction::InsertElement { array, value, .. } => {
+                reads.extend(region(value));
+                writes.push(pointer(array));
+            }
+            Instruction::InsertValue {
+                aggregate, value, ..
+            } => {
+                reads.extend(region(value));
+                writes.push(pointer(aggregate));
+            }
+            Instruction::Load(src_val) => reads.push(pointer(src_val)),
+            Instruction::StateLoadQuadWord { load_val, key } => {
+                reads.extend([pointer(key), MemoryRegion::Storage]);
+                writes.push(pointer(load_val));
+            }
+            Instruction::StateLoadWord(key) => {
+                reads.extend([pointer(key), MemoryRegion::Storage]);
+            }
+            Instruction::StateStoreQuadWord { stored_val, key } => {
+                reads.extend([pointer(stored_val), pointer(key)]);
+                writes.push(MemoryRegion::Storage);
+            }
+            Instruction::StateStoreWord { key, .. } => {
+                reads.push(pointer(key));
+                writes.push(MemoryRegion::Storage);
+            }
+            Instruction::Store {
+                dst_val,
+                stored_val,
+            } => {
+                reads.extend(region(stored_val));
+                writes.push(pointer(dst_val));
+            }
+            Instruction::Branch(_)
+            | Instruction::ConditionalBranch { .. }
+            | Instruction::FnAddr(_)
+            | Instruction::GetPointer { .. }
+            | Instruction::GetStorageKey
+            | Instruction::Gtf { .. }
+            | Instruction::Nop
+            | Instruction::ReadRegister(_) => (),
+        }
+        if !reads.is_empty() {
+            self.reads.insert(instr_val, reads);
+        }
+        if !writes.is_empty() {
+            self.writes.insert(instr_val, writes);
+        }
+    }
+
+    // The ASM instructions which are understood are those which only use registers, and those
+    // which hash or read storage into memory given by an argument.
+    fn add_asm_block(
+        &mut self,
+        context: &Context,
+        instr_val: Value,
+        asm: &AsmBlock,
+        args: &[AsmArg],
+        reads: &mut Vec<MemoryRegion>,
+        writes: &mut Vec<MemoryRegion>,
+    ) {
+        let asm_content = &context.asm_blocks[asm.0];
+        let is_known_register = |name: &str| {
+            matches!(name, "zero" | "one") || args.iter().any(|arg| arg.name.as_str() == name)
+        };
+        let region = |reg_idx: usize, op: &AsmInstruction| {
+            op.args
+                .get(reg_idx)
+                .map(|reg_name| asm_register_region(context, asm, args, reg_name.as_str()))
+                .unwrap_or(MemoryRegion::Unknown)
+        };
+
+        let mut is_opaque = false;
+        for op in &asm_content.body {
+            if !op
+                .args
+                .iter()
+                .all(|reg_name| is_known_register(reg_name.as_str()))
+            {
+                is_opaque = true;
+                break;
+            }
+            match op.name.as_str() {
+                "add" | "addi" | "sub" | "subi" | "mul" | "muli" | "exp" | "expi" | "eq" | "gt"
+                | "lt" | "and" | "andi" | "or" | "ori" | "xor" | "xori" | "not" | "sll"
+                | "slli" | "srl" | "srli" | "move" | "movi" | "noop" => {
+                    // Arithmetic on an address could create an alias we can't track.
+                    self.escape_registers(context, args, op.args.get(1..).unwrap_or_default());
+                }
+                "div" | "divi" | "mod" | "modi" => {
+                    self.escape_registers(context, args, op.args.get(1..).unwrap_or_default());
+                    self.may_trap.insert(instr_val);
+                }
+                "s256" | "k256" => {
+                    writes.push(region(0, op));
+                    reads.push(region(1, op));
+                }
+                "srw" => reads.extend([region(1, op), MemoryRegion::Storage]),
+                "srwq" => {
+                    writes.push(region(0, op));
+                    reads.extend([region(1, op), MemoryRegion::Storage]);
+                }
+                "meq" => reads.extend([region(1, op), region(2, op)]),
+                _otherwise => {
+                    is_opaque = true;
+                    break;
+                }
+            }
+        }
+
+        if is_opaque {
+            self.opaque.insert(instr_val);
+            let values = args
+                .iter()
+                .filter_map(|arg| arg.initializer)
+                .collect::<Vec<_>>();
+            self.escape_all(context, &values);
+            reads.push(MemoryRegion::Unknown);
+            writes.push(MemoryRegion::Unknown);
+        }
+    }
+
+    fn escape_all(&mut self, context: &Context, values: &[Value]) {
+        self.escaped.extend(
+            values
+                .iter()
+                .filter_map(|value| MemoryRegion::of_value(context, value)),
+        );
+    }
+
+    fn escape_registers(&mut self, context: &Context, args: &[AsmArg], reg_names: &[Ident]) {
+        let values = args
+            .iter()
+            .filter(|arg| {
+                reg_names
+                    .iter()
+                    .any(|reg_name| reg_name.as_str() == arg.name.as_str())
+            })
+            .filter_map(|arg| arg.initializer)
+            .collect::<Vec<_>>();
+        self.escape_all(context, &values);
+    }
+}
+
+// The region addressed by an ASM register, which is only known if it's an initialised argument
+// which isn't modified within the block.
+fn asm_register_region(
+    context: &Context,
+    asm: &AsmBlock,
+    args: &[AsmArg],
+    reg_name: &str,
+) -> MemoryRegion {
+    let is_modified = context.asm_blocks[asm.0].body.iter().any(|op| {
+        !matches!(op.name.as_str(), "s256" | "k256" | "srwq" | "meq")
+            && op
+                .args
+                .first()
+                .map_or(false, |dst_name| dst_name.as_str() == reg_name)
+    });
+    if is_modified {
+        return MemoryRegion::Unknown;
+    }
+    args.iter()
+        .find(|arg| arg.name.as_str() == reg_name)
+        .and_then(|arg| arg.initializer)
+        .map(|init_val| MemoryRegion::of_pointer(context, &init_val))
+        .unwrap_or(MemoryRegion::Unknown)
+}
@@ -0,0 +1,101 @@
+//! ## Predecessor Map
+//!
+//! The predecessors and successors of each block in a function, derived from the block
+//! terminators.  Each list is in function block order and has no duplicates, so a conditional
+//! branch to the same block twice is only a single edge.
+
+use std::collections::HashMap;
+
+use crate::{block::Block, context::Context, function::Function, instruction::Instruction};
+
+pub struct PredecessorMap {
+    preds: HashMap<Block, Vec<Block>>,
+    succs: HashMap<Block, Vec<Block>>,
+}
+
+impl PredecessorMap {
+    /// Build the map for the blocks in `function`.
+    pub fn new(context: &Context, function: &Function) -> Self {
+        let mut preds: HashMap<Block, Vec<Block>> = function
+            .block_iter(context)
+            .map(|block| (block, Vec::new()))
+            .collect();
+        let mut succs = HashMap::new();
+        for block in function.block_iter(context) {
+            let block_succs = terminator_successors(context, &block);
+            for succ in &block_succs {
+                preds.entry(*succ).or_default().push(block);
+            }
+            succs.insert(block, block_succs);
+        }
+        PredecessorMap { preds, succs }
+    }
+
+    /// Return the blocks which branch to `block`.
+    pub fn predecessors(&self, block: &Block) -> &[Block] {
+        self.preds.get(block).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Return the blocks which `block` branches to.
+    pub fn successors(&self, block: &Block) -> &[Block] {
+        self.succs.get(block).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+fn terminator_successors(context: &Context, block: &Block) -> Vec<Block> {
+    match block.get_terminator(context) {
+        Some(Instruction::Branch(to_block)) => vec![*to_block],
+        Some(Instruction::ConditionalBranch {
+            true_block,
+            false_block,
+            ..
+        }) => {
+            if true_block == false_block {
+                vec![*true_block]
+            } else {
+                vec![*true_block, *false_block]
+            }
+        }
+        _otherwise => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{block, parse_function};
+
+    #[test]
+    fn edges() {
+        let (context, function) = parse_function(
+            r#"
+script {
+    fn main(c: bool) -> u64 {
+        entry:
+        cbr c, left, right
+
+        left:
+        cbr c, join, join
+
+        right:
+        br join
+
+        join:
+        v0 = const u64 0
+        ret u64 v0
+    }
+}
+"#,
+        );
+        let [entry, left, right, join] =
+            ["entry", "left", "right", "join"].map(|label| block(&context, &function, label));
+
+        let preds = function.predecessor_map(&context);
+        assert_eq!(preds.successors(&entry), &[left, right]);
+        assert!(preds.predecessors(&entry).is_empty());
+
+        // Both targets of the conditional branch from `left` are the same, which is one edge.
+        assert_eq!(preds.successors(&left), &[join]);
+        assert_eq!(preds.predecessors(&join), &[left, right]);
+        assert!(preds.successors(&join).is_empty());
+    }
+}
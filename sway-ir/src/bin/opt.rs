@@ -4,7 +4,7 @@ use std::{
 };
 
 use anyhow::anyhow;
-use sway_ir::{error::IrError, function::Function, optimize, Context};
+use sway_ir::{error::IrError, function::Function, interpreter, optimize, Context};
 
 // -------------------------------------------------------------------------------------------------
 
@@ -13,8 +13,12 @@ fn main() -> Result<(), anyhow::Error> {
     let mut pass_mgr = PassManager::default();
 
     pass_mgr.register::<ConstCombinePass>();
+    pass_mgr.register::<IndVarsPass>();
     pass_mgr.register::<InlinePass>();
+    pass_mgr.register::<LicmPass>();
+    pass_mgr.register::<SccpPass>();
     pass_mgr.register::<SimplifyCfgPass>();
+    pass_mgr.register::<SpecializePass>();
 
     // Build the config from the command line.
     let config = ConfigBuilder::build(&pass_mgr, std::env::args())?;
@@ -25,9 +29,18 @@ fn main() -> Result<(), anyhow::Error> {
     // Parse it. XXX Improve this error message too.
     let mut ir = sway_ir::parser::parse(&input_str)?;
 
-    // Perform optimisation passes in order.
+    // Perform optimisation passes in order, checking that each one preserves the behaviour of
+    // every function if asked to.
     for pass in config.passes {
+        let before = if config.check_runs > 0 {
+            Some(sway_ir::parser::parse(&sway_ir::printer::to_string(&ir))?)
+        } else {
+            None
+        };
         pass_mgr.run(pass.name.as_ref(), &mut ir)?;
+        if let Some(before) = before {
+            check_behaviour(&pass.name, &before, &ir, config.check_runs)?;
+        }
     }
 
     // Write the output file or standard out.
@@ -65,6 +78,56 @@ fn write_to_output<S: Into<String>>(ir_str: S, path_str: &Option<String>) -> std
     }
 }
 
+// Run each function before and after a pass on `runs` sets of generated arguments and compare
+// the outcomes.  Inputs for which the function fails before the pass are skipped, since the pass
+// is free to change what happens then.
+fn check_behaviour(
+    pass_name: &str,
+    before: &Context,
+    after: &Context,
+    runs: u64,
+) -> Result<(), anyhow::Error> {
+    for (before_idx, before_fn) in before.functions.iter() {
+        let after_fn = match after.functions.iter().find_map(|(idx, fc)| {
+            if fc.name == before_fn.name {
+                Some(Function(idx))
+            } else {
+                None
+            }
+        }) {
+            Some(after_fn) => after_fn,
+            // The function may have been removed, e.g., once it was inlined everywhere.
+            None => continue,
+        };
+        let before_fn = Function(before_idx);
+        let runs = if before_fn.args_iter(before).next().is_none() {
+            1
+        } else {
+            runs
+        };
+        for seed in 0..runs {
+            let args = interpreter::generate_args(before, &before_fn, seed);
+            let expected = match interpreter::Interpreter::new(before).execute(&before_fn, &args) {
+                Ok(outcome) => outcome,
+                Err(_) => continue,
+            };
+            let actual = interpreter::Interpreter::new(after).execute(&after_fn, &args);
+            if !matches!(&actual, Ok(outcome) if *outcome == expected) {
+                let actual = match actual {
+                    Ok(outcome) => outcome.to_string(),
+                    Err(err) => format!("{err}\n"),
+                };
+                return Err(anyhow!(
+                    "Pass '{pass_name}' changed the behaviour of '{}' for seed {seed}.\n\n\
+                    Before:\n{expected}\nAfter:\n{actual}",
+                    before.functions[before_idx].name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 // -------------------------------------------------------------------------------------------------
 
 trait NamedPass {
@@ -100,7 +163,14 @@ impl PassManager {
     }
 
     fn run(&self, name: &str, ir: &mut Context) -> Result<bool, IrError> {
-        self.passes.get(name).expect("Unknown pass name!").1(ir)
+        let modified = self.passes.get(name).expect("Unknown pass name!").1(ir)?;
+        if modified {
+            // Any analyses cached by or before the pass may now be stale.
+            for (idx, _) in ir.functions.iter() {
+                Function(idx).invalidate_analyses(ir);
+            }
+        }
+        Ok(modified)
     }
 
     fn contains(&self, name: &str) -> bool {
@@ -163,6 +233,78 @@ impl NamedPass for ConstCombinePass {
 
 // -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -
 
+struct LicmPass;
+
+impl NamedPass for LicmPass {
+    fn name() -> &'static str {
+        "licm"
+    }
+
+    fn descr() -> &'static str {
+        "hoist loop invariant instructions to loop preheaders."
+    }
+
+    fn run(ir: &mut Context) -> Result<bool, IrError> {
+        Self::run_on_all_fns(ir, optimize::hoist_loop_invariants)
+    }
+}
+
+// -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -
+
+struct IndVarsPass;
+
+impl NamedPass for IndVarsPass {
+    fn name() -> &'static str {
+        "indvars"
+    }
+
+    fn descr() -> &'static str {
+        "strength reduce multiplications of induction variables."
+    }
+
+    fn run(ir: &mut Context) -> Result<bool, IrError> {
+        Self::run_on_all_fns(ir, optimize::simplify_induction_variables)
+    }
+}
+
+// -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -
+
+struct SccpPass;
+
+impl NamedPass for SccpPass {
+    fn name() -> &'static str {
+        "sccp"
+    }
+
+    fn descr() -> &'static str {
+        "sparse conditional constant propagation."
+    }
+
+    fn run(ir: &mut Context) -> Result<bool, IrError> {
+        Self::run_on_all_fns(ir, optimize::propagate_constants)
+    }
+}
+
+// -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -
+
+struct SpecializePass;
+
+impl NamedPass for SpecializePass {
+    fn name() -> &'static str {
+        "specialize"
+    }
+
+    fn descr() -> &'static str {
+        "specialize called functions for their constant arguments."
+    }
+
+    fn run(ir: &mut Context) -> Result<bool, IrError> {
+        Self::run_on_all_fns(ir, optimize::specialize_constant_calls)
+    }
+}
+
+// -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -  -
+
 struct SimplifyCfgPass;
 
 impl NamedPass for SimplifyCfgPass {
@@ -187,6 +329,7 @@ struct Config {
     input_path: Option<String>,
     output_path: Option<String>,
 
+    check_runs: u64,
     _verify_each: bool,
     _time_passes: bool,
     _stats: bool,
@@ -240,6 +383,7 @@ impl<'a, I: Iterator<Item = String>> ConfigBuilder<'a, I> {
                 match opt.as_str() {
                     "-i" => self.build_input(),
                     "-o" => self.build_output(),
+                    "-check" => self.build_check(),
 
                     name => {
                         if matches!(opt.chars().next(), Some('-')) {
@@ -275,6 +419,19 @@ impl<'a, I: Iterator<Item = String>> ConfigBuilder<'a, I> {
         }
     }
 
+    fn build_check(mut self) -> Result<Config, anyhow::Error> {
+        match self.next {
+            None => Err(anyhow!("-check option requires an argument.")),
+            Some(runs) => {
+                self.cfg.check_runs = runs
+                    .parse()
+                    .map_err(|_| anyhow!("-check option requires a number of runs."))?;
+                self.next = self.rest.next();
+                self.build_root()
+            }
+        }
+    }
+
     fn build_pass(mut self, name: &str) -> Result<Config, anyhow::Error> {
         if self.pass_mgr.contains(name) {
             self.cfg.passes.push(name.into());
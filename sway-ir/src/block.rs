@@ -112,6 +112,18 @@ impl Block {
         }
     }
 
+    /// Remove the value from the phi instruction which correlates to `from_block`, if any.
+    pub fn remove_phi_val_coming_from(&self, context: &mut Context, from_block: &Block) {
+        let phi_val = self.get_phi(context);
+        if let ValueDatum::Instruction(Instruction::Phi(pairs)) =
+            &mut context.values[phi_val.0].value
+        {
+            pairs.retain(|(block, _)| block != from_block);
+        } else {
+            unreachable!("Phi value must be a PHI instruction.");
+        }
+    }
+
     /// Replace a block reference in the phi instruction.
     ///
     /// Any reference to `old_source` will be replace with `new_source` in the list of phi values.
@@ -217,6 +229,48 @@ impl Block {
         }
     }
 
+    /// Insert an instruction into this block just before its terminator.
+    ///
+    /// The instruction must not already be in a block.  If this block isn't terminated then the
+    /// instruction is appended.
+    pub fn insert_before_terminator(&self, context: &mut Context, instr_val: Value) {
+        let pos = if self.is_terminated(context) {
+            context.blocks[self.0].instructions.len() - 1
+        } else {
+            context.blocks[self.0].instructions.len()
+        };
+        context.blocks[self.0].instructions.insert(pos, instr_val);
+    }
+
+    /// Redirect any branch from this block to `old_succ` to go to `new_succ` instead.
+    ///
+    /// The phi instructions of the successors are not updated.
+    pub fn replace_successor(&self, context: &mut Context, old_succ: Block, new_succ: Block) {
+        let term_val = match context.blocks[self.0].instructions.last() {
+            Some(term_val) => *term_val,
+            None => return,
+        };
+        match &mut context.values[term_val.0].value {
+            ValueDatum::Instruction(Instruction::Branch(to_block)) => {
+                if *to_block == old_succ {
+                    *to_block = new_succ;
+                }
+            }
+            ValueDatum::Instruction(Instruction::ConditionalBranch {
+                true_block,
+                false_block,
+                ..
+            }) => {
+                for to_block in [true_block, false_block] {
+                    if *to_block == old_succ {
+                        *to_block = new_succ;
+                    }
+                }
+            }
+            _otherwise => (),
+        }
+    }
+
     /// Split the block into two.
     ///
     /// This will create a new block and move the instructions at and following `split_idx` to it.
@@ -299,28 +353,6 @@ impl BlockContent {
     pub(super) fn num_predecessors(&self, context: &Context) -> usize {
         self.predecessors(context).count()
     }
-
-    pub(super) fn successors<'a>(
-        &'a self,
-        context: &'a Context,
-    ) -> impl Iterator<Item = Block> + 'a {
-        self.function.block_iter(context).flat_map(|block| {
-            block
-                .get_terminator(context)
-                .map(|term_inst| match term_inst {
-                    Instruction::ConditionalBranch {
-                        true_block,
-                        false_block,
-                        ..
-                    } => vec![*true_block, *false_block],
-
-                    Instruction::Branch(block) => vec![*block],
-
-                    _otherwise => Vec::new(),
-                })
-                .unwrap_or_default()
-        })
-    }
 }
 
 /// An iterator over each block in a [`Function`].
@@ -6,6 +6,8 @@
 #[derive(Debug)]
 pub enum IrError {
     FunctionLocalClobbered(String, String),
+    InterpreterTrap(String),
+    InterpreterUnsupported(String),
     InvalidMetadatum(String),
     InvalidPhi,
     MisplacedTerminator(String),
@@ -30,6 +32,7 @@ pub enum IrError {
     VerifyBitcastBetweenInvalidTypes(String, String),
     VerifyBranchToMissingBlock(String),
     VerifyCallArgTypeMismatch(String),
+    VerifyCallIndirectNonIntCallee(String),
     VerifyCallToMissingFunction(String),
     VerifyCmpBadTypes(String, String),
     VerifyCmpTypeMismatch(String, String),
@@ -69,6 +72,10 @@ impl fmt::Display for IrError {
                 f,
                 "Local storage for function {fn_str} already has an entry for variable {var_str}."
             ),
+            IrError::InterpreterTrap(why_str) => write!(f, "Execution trapped: {why_str}."),
+            IrError::InterpreterUnsupported(what_str) => {
+                write!(f, "Unable to interpret {what_str}.")
+            }
             IrError::InvalidMetadatum(why_str) => {
                 write!(f, "Unable to convert from invalid metadatum: {why_str}.")
             }
@@ -170,6 +177,13 @@ impl fmt::Display for IrError {
                     "Verification failed: Type mismatch found for call to '{callee}'."
                 )
             }
+            IrError::VerifyCallIndirectNonIntCallee(ty) => {
+                write!(
+                    f,
+                    "Verification failed: Indirect call callee must be a u64 function address, \
+                    found '{ty}'."
+                )
+            }
             IrError::VerifyCallToMissingFunction(callee) => {
                 write!(
                     f,
@@ -6,9 +6,14 @@
 //! It also maintains a collection of local values which can be typically regarded as variables
 //! existing in the function scope.
 
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
 
 use crate::{
+    analysis::{AnalysisCache, DomTree, Liveness, LoopForest, MemoryEffects, PredecessorMap},
     block::{Block, BlockIterator, Label},
     constant::Constant,
     context::Context,
@@ -38,6 +43,7 @@ pub struct FunctionContent {
     pub local_storage: BTreeMap<String, Pointer>, // BTree rather than Hash for deterministic ordering.
 
     next_label_idx: u64,
+    analyses: RefCell<AnalysisCache>,
 }
 
 impl Function {
@@ -78,6 +84,7 @@ impl Function {
             metadata,
             local_storage: BTreeMap::new(),
             next_label_idx: 0,
+            analyses: RefCell::new(AnalysisCache::default()),
         };
         let func = Function(context.functions.insert(content));
 
@@ -403,6 +410,89 @@ impl Function {
             block.replace_value(context, old_val, new_val);
         }
     }
+
+    /// Return the predecessors and successors of each block in this function.
+    pub fn predecessor_map(&self, context: &Context) -> Rc<PredecessorMap> {
+        self.cached_analysis(
+            context,
+            |analyses| &mut analyses.predecessors,
+            || PredecessorMap::new(context, self),
+        )
+    }
+
+    /// Return the dominator tree for this function.
+    pub fn dominator_tree(&self, context: &Context) -> Rc<DomTree> {
+        self.cached_analysis(
+            context,
+            |analyses| &mut analyses.dom_tree,
+            || DomTree::new(context, self, &self.predecessor_map(context)),
+        )
+    }
+
+    /// Return the post-dominator tree for this function.
+    pub fn post_dominator_tree(&self, context: &Context) -> Rc<DomTree> {
+        self.cached_analysis(
+            context,
+            |analyses| &mut analyses.post_dom_tree,
+            || DomTree::new_post_dominator(context, self, &self.predecessor_map(context)),
+        )
+    }
+
+    /// Return the natural loops in this function.
+    pub fn loop_forest(&self, context: &Context) -> Rc<LoopForest> {
+        self.cached_analysis(
+            context,
+            |analyses| &mut analyses.loops,
+            || {
+                LoopForest::new(
+                    context,
+                    self,
+                    &self.predecessor_map(context),
+                    &self.dominator_tree(context),
+                )
+            },
+        )
+    }
+
+    /// Return the liveness of the values in this function.
+    pub fn liveness(&self, context: &Context) -> Rc<Liveness> {
+        self.cached_analysis(
+            context,
+            |analyses| &mut analyses.liveness,
+            || Liveness::new(context, self, &self.predecessor_map(context)),
+        )
+    }
+
+    /// Return the memory read and written by each instruction in this function.
+    pub fn memory_effects(&self, context: &Context) -> Rc<MemoryEffects> {
+        self.cached_analysis(
+            context,
+            |analyses| &mut analyses.memory_effects,
+            || MemoryEffects::new(context, self),
+        )
+    }
+
+    /// Discard the cached analyses for this function.  This must be called after the function is
+    /// modified.
+    pub fn invalidate_analyses(&self, context: &Context) {
+        *context.functions[self.0].analyses.borrow_mut() = AnalysisCache::default();
+    }
+
+    fn cached_analysis<T, G, F>(&self, context: &Context, get: G, compute: F) -> Rc<T>
+    where
+        G: Fn(&mut AnalysisCache) -> &mut Option<Rc<T>>,
+        F: FnOnce() -> T,
+    {
+        let analyses = &context.functions[self.0].analyses;
+        let cached = get(&mut *analyses.borrow_mut()).clone();
+        cached.unwrap_or_else(|| {
+            // The analysis may itself depend on other cached analyses, so the cache must not be
+            // borrowed while it is computed.
+            let result = Rc::new(compute());
+            *get(&mut *analyses.borrow_mut()) = Some(result.clone());
+            result
+        })
+    }
 }
 
 /// An iterator over each [`Function`] in a [`Module`].
@@ -32,6 +32,12 @@ pub enum Instruction {
     Branch(Block),
     /// A function call with a list of arguments.
     Call(Function, Vec<Value>),
+    /// A call through a function pointer with a list of arguments and the callee return type.
+    CallIndirect {
+        callee: Value,
+        args: Vec<Value>,
+        return_type: Type,
+    },
     /// Comparison between two values using various comparators and returning a boolean.
     Cmp(Predicate, Value, Value),
     /// A conditional jump with the boolean condition value and true or false destinations.
@@ -61,6 +67,8 @@ pub enum Instruction {
         ty: Aggregate,
         indices: Vec<u64>,
     },
+    /// The address of a function, i.e. a function pointer.
+    FnAddr(Function),
     /// Generate a unique integer value
     GetStorageKey,
     Gtf {
@@ -176,10 +184,12 @@ impl Instruction {
             Instruction::AsmBlock(asm_block, _) => asm_block.get_type(context),
             Instruction::BitCast(_, ty) => Some(*ty),
             Instruction::Call(function, _) => Some(context.functions[function.0].return_type),
+            Instruction::CallIndirect { return_type, .. } => Some(*return_type),
             Instruction::Cmp(..) => Some(Type::Bool),
             Instruction::ContractCall { return_type, .. } => Some(*return_type),
             Instruction::ExtractElement { ty, .. } => ty.get_elem_type(context),
             Instruction::ExtractValue { ty, indices, .. } => ty.get_field_type(context, indices),
+            Instruction::FnAddr(_) => Some(Type::Uint(64)),
             Instruction::GetStorageKey => Some(Type::B256),
             Instruction::Gtf { .. } => Some(Type::Uint(64)),
             Instruction::InsertElement { array, .. } => array.get_type(context),
@@ -229,6 +239,11 @@ impl Instruction {
                 Type::Struct(aggregate) => Some(*aggregate),
                 _otherwise => None,
             },
+            Instruction::CallIndirect { return_type, .. } => match return_type {
+                Type::Array(aggregate) => Some(*aggregate),
+                Type::Struct(aggregate) => Some(*aggregate),
+                _otherwise => None,
+            },
             Instruction::GetPointer { ptr_ty, .. } => match ptr_ty {
                 Type::Array(aggregate) => Some(*aggregate),
                 Type::Struct(aggregate) => Some(*aggregate),
@@ -255,6 +270,64 @@ impl Instruction {
         }
     }
 
+    /// Return the values used by this instruction, including the pointers it loads from or
+    /// stores to and the incoming values of a phi.
+    pub fn get_operands(&self) -> Vec<Value> {
+        match self {
+            Instruction::AddrOf(arg) => vec![*arg],
+            Instruction::AsmBlock(_, args) => args
+                .iter()
+                .filter_map(|asm_arg| asm_arg.initializer)
+                .collect(),
+            Instruction::BitCast(value, _) => vec![*value],
+            Instruction::Branch(_) => vec![],
+            Instruction::Call(_, args) => args.clone(),
+            Instruction::CallIndirect { callee, args, .. } => std::iter::once(*callee)
+                .chain(args.iter().copied())
+                .collect(),
+            Instruction::Cmp(_, lhs_val, rhs_val) => vec![*lhs_val, *rhs_val],
+            Instruction::ConditionalBranch { cond_value, .. } => vec![*cond_value],
+            Instruction::ContractCall {
+                params,
+                coins,
+                asset_id,
+                gas,
+                ..
+            } => vec![*params, *coins, *asset_id, *gas],
+            Instruction::GetPointer { .. } => vec![],
+            Instruction::InsertElement {
+                array,
+                value,
+                index_val,
+                ..
+            } => vec![*array, *value, *index_val],
+            Instruction::InsertValue {
+                aggregate, value, ..
+            } => vec![*aggregate, *value],
+            Instruction::ExtractElement {
+                array, index_val, ..
+            } => vec![*array, *index_val],
+            Instruction::ExtractValue { aggregate, .. } => vec![*aggregate],
+            Instruction::FnAddr(_) => vec![],
+            Instruction::GetStorageKey => vec![],
+            Instruction::Gtf { index, .. } => vec![*index],
+            Instruction::IntToPtr(value, _) => vec![*value],
+            Instruction::Load(src_val) => vec![*src_val],
+            Instruction::Nop => vec![],
+            Instruction::Phi(pairs) => pairs.iter().map(|(_, val)| *val).collect(),
+            Instruction::ReadRegister(_) => vec![],
+            Instruction::Ret(ret_val, _) => vec![*ret_val],
+            Instruction::StateLoadQuadWord { load_val, key } => vec![*load_val, *key],
+            Instruction::StateLoadWord(key) => vec![*key],
+            Instruction::StateStoreQuadWord { stored_val, key } => vec![*stored_val, *key],
+            Instruction::StateStoreWord { stored_val, key } => vec![*stored_val, *key],
+            Instruction::Store {
+                dst_val,
+                stored_val,
+            } => vec![*dst_val, *stored_val],
+        }
+    }
+
     /// Replace `old_val` with `new_val` if it is referenced by this instruction's arguments.
     pub fn replace_value(&mut self, old_val: Value, new_val: Value) {
         let replace = |val: &mut Value| {
@@ -273,6 +346,10 @@ impl Instruction {
             Instruction::BitCast(value, _) => replace(value),
             Instruction::Branch(_) => (),
             Instruction::Call(_, args) => args.iter_mut().for_each(replace),
+            Instruction::CallIndirect { callee, args, .. } => {
+                replace(callee);
+                args.iter_mut().for_each(replace);
+            }
             Instruction::Cmp(_, lhs_val, rhs_val) => {
                 replace(lhs_val);
                 replace(rhs_val);
@@ -314,6 +391,7 @@ impl Instruction {
                 replace(index_val);
             }
             Instruction::ExtractValue { aggregate, .. } => replace(aggregate),
+            Instruction::FnAddr(_) => (),
             Instruction::GetStorageKey => (),
             Instruction::Gtf { index, .. } => replace(index),
             Instruction::IntToPtr(value, _) => replace(value),
@@ -463,6 +541,21 @@ impl<'a> InstructionInserter<'a> {
         call_val
     }
 
+    pub fn call_indirect(self, callee: Value, args: &[Value], return_type: Type) -> Value {
+        let call_indirect_val = Value::new_instruction(
+            self.context,
+            Instruction::CallIndirect {
+                callee,
+                args: args.to_vec(),
+                return_type,
+            },
+        );
+        self.context.blocks[self.block.0]
+            .instructions
+            .push(call_indirect_val);
+        call_indirect_val
+    }
+
     pub fn cmp(self, pred: Predicate, lhs_value: Value, rhs_value: Value) -> Value {
         let cmp_val =
             Value::new_instruction(self.context, Instruction::Cmp(pred, lhs_value, rhs_value));
@@ -549,6 +642,14 @@ impl<'a> InstructionInserter<'a> {
         extract_value_val
     }
 
+    pub fn fn_addr(self, function: Function) -> Value {
+        let fn_addr_val = Value::new_instruction(self.context, Instruction::FnAddr(function));
+        self.context.blocks[self.block.0]
+            .instructions
+            .push(fn_addr_val);
+        fn_addr_val
+    }
+
     pub fn get_storage_key(self) -> Value {
         let get_storage_key_val = Value::new_instruction(self.context, Instruction::GetStorageKey);
         self.context.blocks[self.block.0]
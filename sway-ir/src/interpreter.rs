@@ -0,0 +1,1132 @@
+//! An interpreter for the IR, used to check that optimization passes preserve the behaviour of
+//! the functions they transform.
+//!
+//! Values are represented much like they are in the generated code.  A copy type value is held in
+//! a single 64-bit word and any other value lives in memory and is referred to by its address.
+//! Memory is a flat array of bytes and values use the same layout as code generation.  Unlike the
+//! generated code though, aggregates are never modified in place: `insert_value`,
+//! `insert_element` and `load` all produce a new copy, as SSA requires.
+//!
+//! There is no blockchain to run against, so contract storage is an in-memory map, contract calls
+//! are recorded and return a zeroed value, and transaction fields and VM registers have fixed
+//! values.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use crate::{
+    asm::{AsmArg, AsmBlock, AsmInstruction},
+    block::Block,
+    constant::{Constant, ConstantValue},
+    context::Context,
+    error::IrError,
+    function::Function,
+    instruction::{Instruction, Predicate, Register},
+    irtype::{Aggregate, Type},
+    metadata::Metadatum,
+    pointer::Pointer,
+    value::{Value, ValueDatum},
+};
+
+/// The number of instructions, including those in ASM blocks, which may be executed before
+/// giving up.  This stops non-terminating loops from hanging the interpreter.
+const DEFAULT_STEP_LIMIT: u64 = 1_000_000;
+
+/// The maximum depth of nested function calls.
+const MAX_CALL_DEPTH: usize = 256;
+
+/// The maximum size of the memory, matching the VM.
+const MAX_MEMORY: u64 = 64 * 1024 * 1024;
+
+/// Nothing is ever allocated below this address, so that a zero pointer is always invalid.
+const NULL_PAGE_SIZE: u64 = 8;
+
+/// Function addresses, from `fn_addr`, are offset so that they can't be mistaken for memory.
+const FN_ADDR_BASE: u64 = 0xf0f0_0000_0000_0000;
+
+/// The value of both the global and the context gas registers.
+const GAS_STUB: u64 = 1_000_000_000;
+
+/// A call to another contract made by the interpreted function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractCallRecord {
+    pub name: String,
+    /// The call parameters, as laid out in memory.
+    pub params: Vec<u8>,
+    pub coins: u64,
+    pub asset_id: [u8; 32],
+    pub gas: u64,
+}
+
+/// The observable result of executing a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    /// The return value as it is laid out in memory.  Copy types are a single big-endian word.
+    pub value: Vec<u8>,
+    /// The contents of the storage once the function has returned.
+    pub storage: BTreeMap<[u8; 32], [u8; 32]>,
+    /// The contract calls made, in order.
+    pub contract_calls: Vec<ContractCallRecord>,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "value 0x{}", to_hex(&self.value))?;
+        for (key, value) in &self.storage {
+            writeln!(f, "storage 0x{} = 0x{}", to_hex(key), to_hex(value))?;
+        }
+        for call in &self.contract_calls {
+            writeln!(
+                f,
+                "contract_call {}, coins {}, asset_id 0x{}, gas {}, params 0x{}",
+                call.name,
+                call.coins,
+                to_hex(&call.asset_id),
+                call.gas,
+                to_hex(&call.params)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Executes functions within a [`Context`].
+///
+/// An interpreter is consumed by [`Interpreter::execute`], so that each execution starts from
+/// the same state.
+pub struct Interpreter<'a> {
+    context: &'a Context,
+    memory: Vec<u8>,
+    storage: BTreeMap<[u8; 32], [u8; 32]>,
+    contract_calls: Vec<ContractCallRecord>,
+    fn_addrs: Vec<Function>,
+    heap_ptr: u64,
+    overflow: u64,
+    steps: u64,
+    step_limit: u64,
+}
+
+/// The state local to a single function call.
+#[derive(Default)]
+struct Frame {
+    values: HashMap<Value, u64>,
+    locals: HashMap<Pointer, u64>,
+}
+
+impl<'a> Interpreter<'a> {
+    /// Return a new [`Interpreter`] for the functions in `context`, with empty storage.
+    pub fn new(context: &'a Context) -> Self {
+        Interpreter {
+            context,
+            memory: vec![0; NULL_PAGE_SIZE as usize],
+            storage: BTreeMap::new(),
+            contract_calls: Vec::new(),
+            fn_addrs: Vec::new(),
+            heap_ptr: MAX_MEMORY - 1,
+            overflow: 0,
+            steps: 0,
+            step_limit: DEFAULT_STEP_LIMIT,
+        }
+    }
+
+    /// Start the execution with `storage` rather than an empty storage.
+    pub fn with_storage(mut self, storage: BTreeMap<[u8; 32], [u8; 32]>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Limit the execution to `step_limit` instructions.
+    pub fn with_step_limit(mut self, step_limit: u64) -> Self {
+        self.step_limit = step_limit;
+        self
+    }
+
+    /// Execute `function` with `args`, which must match the types of its arguments.
+    ///
+    /// Returns [`IrError::InterpreterTrap`] if the execution fails at run time, e.g., it reverts,
+    /// and [`IrError::InterpreterUnsupported`] if it can't be interpreted at all.
+    pub fn execute(mut self, function: &Function, args: &[Constant]) -> Result<Outcome, IrError> {
+        let context = self.context;
+        let arg_types = function
+            .args_iter(context)
+            .map(|(_, arg_val)| arg_val.get_type(context).unwrap_or(Type::Unit))
+            .collect::<Vec<_>>();
+        if arg_types.len() != args.len() {
+            return Err(unsupported(format!(
+                "a call to {} with {} arguments",
+                function.get_name(context),
+                args.len()
+            )));
+        }
+        let args = args
+            .iter()
+            .zip(arg_types.iter())
+            .map(|(arg, ty)| self.materialize(arg, ty))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let ret_val = self.call(function, args, 0)?;
+        let value = self.observe(ret_val, &context.functions[function.0].return_type)?;
+        Ok(Outcome {
+            value,
+            storage: self.storage,
+            contract_calls: self.contract_calls,
+        })
+    }
+
+    fn call(&mut self, function: &Function, args: Vec<u64>, depth: usize) -> Result<u64, IrError> {
+        if depth > MAX_CALL_DEPTH {
+            return Err(trap("call stack overflow"));
+        }
+
+        let context = self.context;
+        let mut frame = Frame::default();
+        for ((_, arg_val), arg) in function.args_iter(context).zip(args) {
+            frame.values.insert(*arg_val, arg);
+        }
+        for (_, ptr) in function.locals_iter(context) {
+            let ptr_content = &context.pointers[ptr.0];
+            let addr = self.alloc(self.size_of(&ptr_content.ty))?;
+            if let Some(initializer) = &ptr_content.initializer {
+                self.write_constant(addr, initializer, &ptr_content.ty)?;
+            }
+            frame.locals.insert(*ptr, addr);
+        }
+
+        let mut prev_block: Option<Block> = None;
+        let mut block = function.get_entry_block(context);
+        loop {
+            let mut next_block = None;
+            for instr_val in block.instruction_iter(context) {
+                self.step()?;
+                let instruction = match &context.values[instr_val.0].value {
+                    ValueDatum::Instruction(instruction) => instruction,
+                    _otherwise => unreachable!("Block contains a non-instruction value."),
+                };
+                match instruction {
+                    Instruction::Branch(to_block) => {
+                        next_block = Some(*to_block);
+                        break;
+                    }
+                    Instruction::ConditionalBranch {
+                        cond_value,
+                        true_block,
+                        false_block,
+                    } => {
+                        next_block = Some(if self.value(&mut frame, cond_value)? != 0 {
+                            *true_block
+                        } else {
+                            *false_block
+                        });
+                        break;
+                    }
+                    Instruction::Ret(ret_val, _) => return self.value(&mut frame, ret_val),
+                    Instruction::Phi(pairs) => {
+                        let word = if pairs.is_empty() {
+                            0
+                        } else {
+                            let incoming = pairs
+                                .iter()
+                                .find(|(from_block, _)| Some(*from_block) == prev_block)
+                                .map(|(_, phi_val)| *phi_val)
+                                .ok_or_else(|| {
+                                    unsupported(format!(
+                                        "a phi in block {} with no value for its predecessor",
+                                        block.get_label(context)
+                                    ))
+                                })?;
+                            self.value(&mut frame, &incoming)?
+                        };
+                        frame.values.insert(instr_val, word);
+                    }
+                    _otherwise => {
+                        let word = self.instruction(&mut frame, &instr_val, instruction, depth)?;
+                        frame.values.insert(instr_val, word);
+                    }
+                }
+            }
+            match next_block {
+                Some(to_block) => {
+                    prev_block = Some(block);
+                    block = to_block;
+                }
+                None => {
+                    return Err(unsupported(format!(
+                        "block {} without a terminator",
+                        block.get_label(context)
+                    )))
+                }
+            }
+        }
+    }
+
+    fn instruction(
+        &mut self,
+        frame: &mut Frame,
+        instr_val: &Value,
+        instruction: &Instruction,
+        depth: usize,
+    ) -> Result<u64, IrError> {
+        let context = self.context;
+        match instruction {
+            Instruction::AddrOf(arg) => self.value(frame, arg),
+            Instruction::AsmBlock(asm, args) => self.asm_block(frame, asm, args),
+            Instruction::BitCast(value, ty) => {
+                let word = self.value(frame, value)?;
+                Ok(if matches!(ty, Type::Bool) {
+                    (word != 0) as u64
+                } else {
+                    word
+                })
+            }
+            Instruction::Call(function, args) => {
+                let args = self.values(frame, args)?;
+                self.call(function, args, depth + 1)
+            }
+            Instruction::CallIndirect { callee, args, .. } => {
+                let addr = self.value(frame, callee)?;
+                let function = addr
+                    .checked_sub(FN_ADDR_BASE)
+                    .and_then(|ix| self.fn_addrs.get(ix as usize))
+                    .copied()
+                    .ok_or_else(|| trap(format!("call to invalid function address {addr:#x}")))?;
+                let args = self.values(frame, args)?;
+                self.call(&function, args, depth + 1)
+            }
+            Instruction::Cmp(Predicate::Equal, lhs_val, rhs_val) => {
+                let ty = lhs_val.get_type(context);
+                let lhs = self.value(frame, lhs_val)?;
+                let rhs = self.value(frame, rhs_val)?;
+                match ty {
+                    Some(ty) if !ty.is_copy_type() => {
+                        let size = self.size_of(&ty);
+                        Ok((self.read(lhs, size)? == self.read(rhs, size)?) as u64)
+                    }
+                    _otherwise => Ok((lhs == rhs) as u64),
+                }
+            }
+            Instruction::ContractCall {
+                return_type,
+                name,
+                params,
+                coins,
+                asset_id,
+                gas,
+            } => {
+                let params_ty = params.get_type(context).unwrap_or(Type::Unit);
+                let params = self.value(frame, params)?;
+                let params = self.observe(params, &params_ty)?;
+                let coins = self.value(frame, coins)?;
+                let asset_id = self.value(frame, asset_id)?;
+                let asset_id = self.read_b256(asset_id)?;
+                let gas = self.value(frame, gas)?;
+                self.contract_calls.push(ContractCallRecord {
+                    name: name.clone(),
+                    params,
+                    coins,
+                    asset_id,
+                    gas,
+                });
+                self.zeroed(return_type)
+            }
+            Instruction::ExtractElement {
+                array,
+                ty,
+                index_val,
+            } => {
+                let (elem_ty, count) = array_type(context, ty);
+                let array = self.value(frame, array)?;
+                let index = self.value(frame, index_val)?;
+                if index >= count {
+                    return Err(trap(format!("array index {index} is out of bounds")));
+                }
+                self.read_value(array + index * self.size_of(&elem_ty), &elem_ty)
+            }
+            Instruction::ExtractValue {
+                aggregate,
+                ty,
+                indices,
+            } => {
+                let aggregate_ty = aggregate.get_type(context).unwrap_or(Type::Struct(*ty));
+                let (offset, field_ty) = self.field_layout(&aggregate_ty, indices)?;
+                let aggregate = self.value(frame, aggregate)?;
+                self.read_value(aggregate + offset, &field_ty)
+            }
+            Instruction::FnAddr(function) => {
+                let ix = match self.fn_addrs.iter().position(|f| f == function) {
+                    Some(ix) => ix,
+                    None => {
+                        self.fn_addrs.push(*function);
+                        self.fn_addrs.len() - 1
+                    }
+                };
+                Ok(FN_ADDR_BASE + ix as u64)
+            }
+            Instruction::GetStorageKey => {
+                let mut key = [0; 32];
+                key[24..].copy_from_slice(&self.storage_index(instr_val).to_be_bytes());
+                let addr = self.alloc(32)?;
+                self.write(addr, &key)?;
+                Ok(addr)
+            }
+            Instruction::Gtf { index, tx_field_id } => {
+                let index = self.value(frame, index)?;
+                Ok(gtf_stub(index, *tx_field_id))
+            }
+            Instruction::GetPointer {
+                base_ptr,
+                ptr_ty,
+                offset,
+            } => {
+                let base = frame
+                    .locals
+                    .get(base_ptr)
+                    .copied()
+                    .ok_or_else(|| unsupported("a pointer to a local of another function"))?;
+                Ok(base + offset * self.size_of(ptr_ty))
+            }
+            Instruction::InsertElement {
+                array,
+                ty,
+                value,
+                index_val,
+            } => {
+                let (elem_ty, count) = array_type(context, ty);
+                let array = self.value(frame, array)?;
+                let index = self.value(frame, index_val)?;
+                if index >= count {
+                    return Err(trap(format!("array index {index} is out of bounds")));
+                }
+                let array = self.copy(array, self.size_of(&Type::Array(*ty)))?;
+                let value_ty = value.get_type(context).unwrap_or(elem_ty);
+                let value = self.value(frame, value)?;
+                self.write_value(
+                    array + index * self.size_of(&elem_ty),
+                    value,
+                    &value_ty,
+                    &elem_ty,
+                )?;
+                Ok(array)
+            }
+            Instruction::InsertValue {
+                aggregate,
+                ty,
+                value,
+                indices,
+            } => {
+                let aggregate_ty = aggregate.get_type(context).unwrap_or(Type::Struct(*ty));
+                let (offset, field_ty) = self.field_layout(&aggregate_ty, indices)?;
+                let aggregate = self.value(frame, aggregate)?;
+                let aggregate = self.copy(aggregate, self.size_of(&aggregate_ty))?;
+                let value_ty = value.get_type(context).unwrap_or(field_ty);
+                let value = self.value(frame, value)?;
+                self.write_value(aggregate + offset, value, &value_ty, &field_ty)?;
+                Ok(aggregate)
+            }
+            Instruction::IntToPtr(value, _) => self.value(frame, value),
+            Instruction::Load(src_val) => {
+                let ty = instr_val
+                    .get_type(context)
+                    .ok_or_else(|| unsupported("a load from an untyped pointer"))?;
+                let src = self.value(frame, src_val)?;
+                if ty.is_copy_type() {
+                    self.read_word(src)
+                } else {
+                    self.copy(src, self.size_of(&ty))
+                }
+            }
+            Instruction::Nop => Ok(0),
+            Instruction::ReadRegister(reg) => Ok(self.read_register(reg)),
+            Instruction::StateLoadQuadWord { load_val, key } => {
+                let key = self.value(frame, key)?;
+                let slot = self.load_slot(key)?;
+                let dst = self.value(frame, load_val)?;
+                self.write(dst, &slot)?;
+                Ok(0)
+            }
+            Instruction::StateLoadWord(key) => {
+                let key = self.value(frame, key)?;
+                let slot = self.load_slot(key)?;
+                Ok(u64::from_be_bytes(slot[..8].try_into().unwrap()))
+            }
+            Instruction::StateStoreQuadWord { stored_val, key } => {
+                let key = self.value(frame, key)?;
+                let src = self.value(frame, stored_val)?;
+                let slot = self.read_b256(src)?;
+                self.store_slot(key, slot)?;
+                Ok(0)
+            }
+            Instruction::StateStoreWord { stored_val, key } => {
+                let key = self.value(frame, key)?;
+                let mut slot = [0; 32];
+                slot[..8].copy_from_slice(&self.value(frame, stored_val)?.to_be_bytes());
+                self.store_slot(key, slot)?;
+                Ok(0)
+            }
+            Instruction::Store {
+                dst_val,
+                stored_val,
+            } => {
+                let (dst_ty, value_ty) =
+                    match (dst_val.get_type(context), stored_val.get_type(context)) {
+                        (Some(dst_ty), Some(value_ty)) => (dst_ty, value_ty),
+                        _otherwise => return Err(unsupported("a store of an untyped value")),
+                    };
+                let dst = self.value(frame, dst_val)?;
+                let value = self.value(frame, stored_val)?;
+                self.write_value(dst, value, &value_ty, &dst_ty)?;
+                Ok(0)
+            }
+            Instruction::Branch(_)
+            | Instruction::ConditionalBranch { .. }
+            | Instruction::Phi(_)
+            | Instruction::Ret(..) => unreachable!("Control flow is handled by the caller."),
+        }
+    }
+
+    fn value(&mut self, frame: &mut Frame, value: &Value) -> Result<u64, IrError> {
+        if let Some(word) = frame.values.get(value) {
+            return Ok(*word);
+        }
+        match &self.context.values[value.0].value {
+            ValueDatum::Constant(constant) => {
+                // Constants are materialized once per call, on first use.
+                let word = self.materialize(constant, &constant.ty)?;
+                frame.values.insert(*value, word);
+                Ok(word)
+            }
+            _otherwise => Err(unsupported("a value used before it is defined")),
+        }
+    }
+
+    fn values(&mut self, frame: &mut Frame, values: &[Value]) -> Result<Vec<u64>, IrError> {
+        values
+            .iter()
+            .map(|value| self.value(frame, value))
+            .collect()
+    }
+
+    fn step(&mut self) -> Result<(), IrError> {
+        self.steps += 1;
+        if self.steps > self.step_limit {
+            Err(trap(format!(
+                "the limit of {} executed instructions was reached",
+                self.step_limit
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // ASM blocks.
+
+    fn asm_block(
+        &mut self,
+        frame: &mut Frame,
+        asm: &AsmBlock,
+        args: &[AsmArg],
+    ) -> Result<u64, IrError> {
+        let mut registers = HashMap::new();
+        for AsmArg { name, initializer } in args {
+            let word = match initializer {
+                Some(init_val) => self.value(frame, init_val)?,
+                None => 0,
+            };
+            registers.insert(name.as_str().to_owned(), word);
+        }
+
+        let asm_content = &self.context.asm_blocks[asm.0];
+        for op in &asm_content.body {
+            self.step()?;
+            self.asm_instruction(&mut registers, op)?;
+        }
+
+        match &asm_content.return_name {
+            Some(ret_reg_name) => self.read_asm_register(&registers, ret_reg_name.as_str()),
+            None => Ok(0),
+        }
+    }
+
+    fn asm_instruction(
+        &mut self,
+        registers: &mut HashMap<String, u64>,
+        op: &AsmInstruction,
+    ) -> Result<(), IrError> {
+        let name = op.name.as_str();
+        let regs = op
+            .args
+            .iter()
+            .map(|reg_name| self.read_asm_register(registers, reg_name.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let reg = |ix: usize| regs.get(ix).copied().unwrap_or(0);
+        let (a, b, c, d) = (reg(0), reg(1), reg(2), reg(3));
+        let imm = || {
+            op.immediate
+                .as_ref()
+                .and_then(|imm| imm.as_str().get(1..))
+                .and_then(|imm| imm.parse::<u64>().ok())
+                .ok_or_else(|| unsupported(format!("`{name}` without a valid immediate")))
+        };
+
+        // The value to write to the first register, if the instruction has a result.
+        let result = match name {
+            "add" => Some(self.overflowing(b.overflowing_add(c))),
+            "addi" => Some(self.overflowing(b.overflowing_add(imm()?))),
+            "sub" => Some(self.overflowing(b.overflowing_sub(c))),
+            "subi" => Some(self.overflowing(b.overflowing_sub(imm()?))),
+            "mul" => Some(self.widening_mul(b, c)),
+            "muli" => Some(self.widening_mul(b, imm()?)),
+            "exp" => Some(self.exp(b, c)),
+            "expi" => Some(self.exp(b, imm()?)),
+            "div" => Some(b.checked_div(c).ok_or_else(|| trap("division by zero"))?),
+            "divi" => Some(
+                b.checked_div(imm()?)
+                    .ok_or_else(|| trap("division by zero"))?,
+            ),
+            "mod" => Some(b.checked_rem(c).ok_or_else(|| trap("modulo by zero"))?),
+            "modi" => Some(
+                b.checked_rem(imm()?)
+                    .ok_or_else(|| trap("modulo by zero"))?,
+            ),
+            "eq" => Some((b == c) as u64),
+            "gt" => Some((b > c) as u64),
+            "lt" => Some((b < c) as u64),
+            "and" => Some(b & c),
+            "andi" => Some(b & imm()?),
+            "or" => Some(b | c),
+            "ori" => Some(b | imm()?),
+            "xor" => Some(b ^ c),
+            "xori" => Some(b ^ imm()?),
+            "not" => Some(!b),
+            "sll" => Some(if c < 64 { b << c } else { 0 }),
+            "slli" => Some(b.checked_shl(imm()? as u32).unwrap_or(0)),
+            "srl" => Some(if c < 64 { b >> c } else { 0 }),
+            "srli" => Some(b.checked_shr(imm()? as u32).unwrap_or(0)),
+            "move" => Some(b),
+            "movi" => Some(imm()?),
+            "noop" | "log" | "logd" | "flag" | "cfsi" => None,
+
+            "lb" => Some(self.read(b.wrapping_add(imm()?), 1)?[0] as u64),
+            "lw" => Some(self.read_word(b.wrapping_add(imm()? * 8))?),
+            "sb" => {
+                self.write(a.wrapping_add(imm()?), &[b as u8])?;
+                None
+            }
+            "sw" => {
+                self.write_word(a.wrapping_add(imm()? * 8), b)?;
+                None
+            }
+            "mcl" => {
+                self.write(a, &vec![0; b as usize])?;
+                None
+            }
+            "mcli" => {
+                self.write(a, &vec![0; imm()? as usize])?;
+                None
+            }
+            "mcp" => {
+                let bytes = self.read(b, c)?.to_vec();
+                self.write(a, &bytes)?;
+                None
+            }
+            "mcpi" => {
+                let bytes = self.read(b, imm()?)?.to_vec();
+                self.write(a, &bytes)?;
+                None
+            }
+            "meq" => Some((self.read(b, d)? == self.read(c, d)?) as u64),
+            "aloc" => {
+                // The heap pointer points to the byte before the newly allocated memory.
+                self.heap_ptr = self.alloc(a)? - 1;
+                None
+            }
+            "cfei" => {
+                self.alloc(imm()?)?;
+                None
+            }
+
+            "gtf" => Some(gtf_stub(b, imm()?)),
+            "srw" => {
+                let slot = self.load_slot(b)?;
+                Some(u64::from_be_bytes(slot[..8].try_into().unwrap()))
+            }
+            "srwq" => {
+                let slot = self.load_slot(b)?;
+                self.write(a, &slot)?;
+                None
+            }
+            "sww" => {
+                let mut slot = [0; 32];
+                slot[..8].copy_from_slice(&b.to_be_bytes());
+                self.store_slot(a, slot)?;
+                None
+            }
+            "swwq" => {
+                let slot = self.read_b256(b)?;
+                self.store_slot(a, slot)?;
+                None
+            }
+            "rvrt" => return Err(trap(format!("revert with {a:#x}"))),
+
+            _otherwise => return Err(unsupported(format!("the ASM instruction `{name}`"))),
+        };
+
+        if let Some(result) = result {
+            let dst_name = op
+                .args
+                .first()
+                .map(|reg_name| reg_name.as_str())
+                .ok_or_else(|| unsupported(format!("`{name}` without a destination")))?;
+            match registers.get_mut(dst_name) {
+                Some(dst) => *dst = result,
+                None => {
+                    return Err(unsupported(format!(
+                        "a write to the reserved register `{dst_name}`"
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_asm_register(
+        &self,
+        registers: &HashMap<String, u64>,
+        reg_name: &str,
+    ) -> Result<u64, IrError> {
+        if let Some(word) = registers.get(reg_name) {
+            return Ok(*word);
+        }
+        let reg = match reg_name {
+            "zero" => return Ok(0),
+            "one" => return Ok(1),
+            "ds" => return Ok(0),
+            "of" => Register::Of,
+            "pc" => Register::Pc,
+            "ssp" => Register::Ssp,
+            "sp" => Register::Sp,
+            "fp" => Register::Fp,
+            "hp" => Register::Hp,
+            "err" => Register::Error,
+            "ggas" => Register::Ggas,
+            "cgas" => Register::Cgas,
+            "bal" => Register::Bal,
+            "is" => Register::Is,
+            "ret" => Register::Ret,
+            "retl" => Register::Retl,
+            "flag" => Register::Flag,
+            _otherwise => return Err(unsupported(format!("the unknown register `{reg_name}`"))),
+        };
+        Ok(self.read_register(&reg))
+    }
+
+    fn read_register(&self, reg: &Register) -> u64 {
+        match reg {
+            Register::Of => self.overflow,
+            Register::Sp => self.memory.len() as u64,
+            Register::Hp => self.heap_ptr,
+            Register::Ggas | Register::Cgas => GAS_STUB,
+            Register::Pc
+            | Register::Ssp
+            | Register::Fp
+            | Register::Error
+            | Register::Bal
+            | Register::Is
+            | Register::Ret
+            | Register::Retl
+            | Register::Flag => 0,
+        }
+    }
+
+    fn overflowing(&mut self, (result, overflow): (u64, bool)) -> u64 {
+        self.overflow = overflow as u64;
+        result
+    }
+
+    fn widening_mul(&mut self, lhs: u64, rhs: u64) -> u64 {
+        let result = lhs as u128 * rhs as u128;
+        self.overflow = (result >> 64) as u64;
+        result as u64
+    }
+
+    fn exp(&mut self, base: u64, exponent: u64) -> u64 {
+        match u32::try_from(exponent)
+            .ok()
+            .and_then(|exponent| base.checked_pow(exponent))
+        {
+            Some(result) => {
+                self.overflow = 0;
+                result
+            }
+            None => {
+                self.overflow = 1;
+                0
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // Storage.
+
+    /// The index given to a storage key by its `state_index` metadatum.  The actual keys are
+    /// hashes computed by the compiler, but any unique key will do here.
+    fn storage_index(&self, value: &Value) -> u64 {
+        let context = self.context;
+        let md_idcs = match value.get_metadata(context) {
+            None => Vec::new(),
+            Some(md_idx) => match &context.metadata[md_idx.0] {
+                Metadatum::List(md_idcs) => md_idcs.clone(),
+                _otherwise => vec![md_idx],
+            },
+        };
+        md_idcs
+            .iter()
+            .find_map(|md_idx| {
+                context.metadata[md_idx.0]
+                    .unwrap_struct("state_index", 1)
+                    .and_then(|fields| fields[0].unwrap_integer())
+            })
+            .unwrap_or(0)
+    }
+
+    fn load_slot(&self, key_addr: u64) -> Result<[u8; 32], IrError> {
+        let key = self.read_b256(key_addr)?;
+        Ok(self.storage.get(&key).copied().unwrap_or_default())
+    }
+
+    fn store_slot(&mut self, key_addr: u64, slot: [u8; 32]) -> Result<(), IrError> {
+        let key = self.read_b256(key_addr)?;
+        self.storage.insert(key, slot);
+        Ok(())
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // Memory.
+
+    fn alloc(&mut self, size: u64) -> Result<u64, IrError> {
+        let addr = self.memory.len() as u64;
+        if addr + size > MAX_MEMORY {
+            return Err(trap("out of memory"));
+        }
+        self.memory.resize((addr + size) as usize, 0);
+        Ok(addr)
+    }
+
+    fn range(&self, addr: u64, size: u64) -> Result<std::ops::Range<usize>, IrError> {
+        match addr.checked_add(size) {
+            Some(end) if addr >= NULL_PAGE_SIZE && end <= self.memory.len() as u64 => {
+                Ok(addr as usize..end as usize)
+            }
+            _otherwise => Err(trap(format!(
+                "invalid access of {size} bytes at address {addr:#x}"
+            ))),
+        }
+    }
+
+    fn read(&self, addr: u64, size: u64) -> Result<&[u8], IrError> {
+        let range = self.range(addr, size)?;
+        Ok(&self.memory[range])
+    }
+
+    fn write(&mut self, addr: u64, bytes: &[u8]) -> Result<(), IrError> {
+        let range = self.range(addr, bytes.len() as u64)?;
+        self.memory[range].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn read_word(&self, addr: u64) -> Result<u64, IrError> {
+        Ok(u64::from_be_bytes(self.read(addr, 8)?.try_into().unwrap()))
+    }
+
+    fn write_word(&mut self, addr: u64, word: u64) -> Result<(), IrError> {
+        self.write(addr, &word.to_be_bytes())
+    }
+
+    fn read_b256(&self, addr: u64) -> Result<[u8; 32], IrError> {
+        Ok(self.read(addr, 32)?.try_into().unwrap())
+    }
+
+    /// Copy `size` bytes from `src` to newly allocated memory.
+    fn copy(&mut self, src: u64, size: u64) -> Result<u64, IrError> {
+        let bytes = self.read(src, size)?.to_vec();
+        let dst = self.alloc(size)?;
+        self.write(dst, &bytes)?;
+        Ok(dst)
+    }
+
+    /// Read a value of type `ty` from `addr`.  Non-copy values are referred to in place.
+    fn read_value(&self, addr: u64, ty: &Type) -> Result<u64, IrError> {
+        if ty.is_copy_type() {
+            self.read_word(addr)
+        } else {
+            Ok(addr)
+        }
+    }
+
+    /// Write `value` of type `value_ty` to `addr`, which holds a value of type `dst_ty`.  They
+    /// only differ when writing a variant to a union, which is padded on the left.
+    fn write_value(
+        &mut self,
+        addr: u64,
+        value: u64,
+        value_ty: &Type,
+        dst_ty: &Type,
+    ) -> Result<(), IrError> {
+        if matches!(dst_ty, Type::Union(_)) && !matches!(value_ty, Type::Union(_)) {
+            let padding = self.size_of(dst_ty).saturating_sub(self.size_of(value_ty));
+            self.write(addr, &vec![0; padding as usize])?;
+            return self.write_value(addr + padding, value, value_ty, value_ty);
+        }
+        if value_ty.is_copy_type() {
+            self.write_word(addr, value)
+        } else {
+            let bytes = self.read(value, self.size_of(value_ty))?.to_vec();
+            self.write(addr, &bytes)
+        }
+    }
+
+    /// Return the word holding `constant` as a value of type `ty`, allocating memory for it if
+    /// it isn't a copy type.
+    fn materialize(&mut self, constant: &Constant, ty: &Type) -> Result<u64, IrError> {
+        if ty.is_copy_type() {
+            match constant.value {
+                ConstantValue::Undef | ConstantValue::Unit => Ok(0),
+                ConstantValue::Bool(b) => Ok(b as u64),
+                ConstantValue::Uint(n) => Ok(n),
+                _otherwise => Err(unsupported("a constant which does not match its type")),
+            }
+        } else {
+            let addr = self.alloc(self.size_of(ty))?;
+            self.write_constant(addr, constant, ty)?;
+            Ok(addr)
+        }
+    }
+
+    /// Write `constant` to `addr`, which holds a value of type `ty`.  The memory is expected to be
+    /// zeroed, as undefined values are skipped.
+    fn write_constant(&mut self, addr: u64, constant: &Constant, ty: &Type) -> Result<(), IrError> {
+        let context = self.context;
+        if matches!(ty, Type::Union(_)) && !matches!(constant.ty, Type::Union(_)) {
+            let padding = self.size_of(ty).saturating_sub(self.size_of(&constant.ty));
+            return self.write_constant(addr + padding, constant, &constant.ty);
+        }
+        match (&constant.value, ty) {
+            (ConstantValue::Undef, _) => Ok(()),
+            (ConstantValue::Unit, _) => self.write_word(addr, 0),
+            (ConstantValue::Bool(b), _) => self.write_word(addr, *b as u64),
+            (ConstantValue::Uint(n), _) => self.write_word(addr, *n),
+            (ConstantValue::B256(bytes), _) => self.write(addr, bytes),
+            (ConstantValue::String(bytes), _) => self.write(addr, bytes),
+            (ConstantValue::Array(elems), Type::Array(aggregate)) => {
+                let (elem_ty, _) = array_type(context, aggregate);
+                let elem_size = self.size_of(&elem_ty);
+                for (ix, elem) in elems.iter().enumerate() {
+                    self.write_constant(addr + ix as u64 * elem_size, elem, &elem_ty)?;
+                }
+                Ok(())
+            }
+            (ConstantValue::Struct(fields), Type::Struct(aggregate)) => {
+                let mut offset = 0;
+                for (field, field_ty) in fields
+                    .iter()
+                    .zip(context.aggregates[aggregate.0].field_types())
+                {
+                    self.write_constant(addr + offset, field, field_ty)?;
+                    offset += self.size_of(field_ty);
+                }
+                Ok(())
+            }
+            _otherwise => Err(unsupported("a constant which does not match its type")),
+        }
+    }
+
+    /// Return the bytes of `value` of type `ty`, as it is laid out in memory.
+    fn observe(&self, value: u64, ty: &Type) -> Result<Vec<u8>, IrError> {
+        if ty.is_copy_type() {
+            Ok(value.to_be_bytes().to_vec())
+        } else {
+            Ok(self.read(value, self.size_of(ty))?.to_vec())
+        }
+    }
+
+    fn zeroed(&mut self, ty: &Type) -> Result<u64, IrError> {
+        if ty.is_copy_type() {
+            Ok(0)
+        } else {
+            self.alloc(self.size_of(ty))
+        }
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // Layout, which must match code generation.
+
+    fn size_of(&self, ty: &Type) -> u64 {
+        let context = self.context;
+        match ty {
+            Type::Unit | Type::Bool | Type::Uint(_) => 8,
+            Type::B256 => 32,
+            Type::String(n) => (n + 7) / 8 * 8,
+            Type::Array(aggregate) => {
+                let (elem_ty, count) = array_type(context, aggregate);
+                count * self.size_of(&elem_ty)
+            }
+            Type::Struct(aggregate) => context.aggregates[aggregate.0]
+                .field_types()
+                .iter()
+                .map(|field_ty| self.size_of(field_ty))
+                .sum(),
+            Type::Union(aggregate) => context.aggregates[aggregate.0]
+                .field_types()
+                .iter()
+                .map(|field_ty| self.size_of(field_ty))
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// The offset in bytes and the type of the (nested) field at `indices` within `ty`.  Union
+    /// variants are padded on the left to the size of the union.
+    fn field_layout(&self, ty: &Type, indices: &[u64]) -> Result<(u64, Type), IrError> {
+        let context = self.context;
+        indices.iter().try_fold((0, *ty), |(offset, ty), idx| {
+            let field_types = match ty {
+                Type::Struct(aggregate) | Type::Union(aggregate) => {
+                    context.aggregates[aggregate.0].field_types()
+                }
+                _otherwise => return Err(unsupported("a field access into a non-aggregate")),
+            };
+            let field_ty = *field_types
+                .get(*idx as usize)
+                .ok_or_else(|| unsupported(format!("an access to non-existent field {idx}")))?;
+            let field_offset = if matches!(ty, Type::Union(_)) {
+                self.size_of(&ty) - self.size_of(&field_ty)
+            } else {
+                field_types
+                    .iter()
+                    .take(*idx as usize)
+                    .map(|field_ty| self.size_of(field_ty))
+                    .sum()
+            };
+            Ok((offset + field_offset, field_ty))
+        })
+    }
+}
+
+fn array_type(context: &Context, aggregate: &Aggregate) -> (Type, u64) {
+    let (elem_ty, count) = context.aggregates[aggregate.0].array_type();
+    (*elem_ty, *count)
+}
+
+/// Transaction fields are derived from the `gtf` operands, so that mixing them up is noticed.
+fn gtf_stub(index: u64, tx_field_id: u64) -> u64 {
+    (tx_field_id << 32) | (index & 0xffff_ffff)
+}
+
+fn trap<S: Into<String>>(why: S) -> IrError {
+    IrError::InterpreterTrap(why.into())
+}
+
+fn unsupported<S: Into<String>>(what: S) -> IrError {
+    IrError::InterpreterUnsupported(what.into())
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Generate arguments for `function`.
+///
+/// The same `seed` always generates the same arguments for the same argument types, even in
+/// another [`Context`], so that a function can be run on the same inputs before and after an
+/// optimization pass.
+pub fn generate_args(context: &Context, function: &Function, seed: u64) -> Vec<Constant> {
+    let mut rng = XorShift::new(seed);
+    function
+        .args_iter(context)
+        .map(|(_, arg_val)| {
+            let ty = arg_val.get_type(context).unwrap_or(Type::Unit);
+            generate_constant(context, &ty, &mut rng)
+        })
+        .collect()
+}
+
+fn generate_constant(context: &Context, ty: &Type, rng: &mut XorShift) -> Constant {
+    match ty {
+        Type::Unit => Constant::new_unit(),
+        Type::Bool => Constant::new_bool(rng.next_u64() & 1 == 1),
+        Type::Uint(nbits) => {
+            let mask = if *nbits >= 64 {
+                u64::MAX
+            } else {
+                (1 << nbits) - 1
+            };
+            // Favour the edge cases.
+            let n = match rng.next_u64() % 8 {
+                0 => 0,
+                1 => 1,
+                2 => mask,
+                3 => rng.next_u64() % 16,
+                _otherwise => rng.next_u64(),
+            };
+            Constant::new_uint(*nbits, n & mask)
+        }
+        Type::B256 => {
+            let mut bytes = [0; 32];
+            for chunk in bytes.chunks_mut(8) {
+                chunk.copy_from_slice(&rng.next_u64().to_be_bytes());
+            }
+            Constant::new_b256(bytes)
+        }
+        Type::String(n) => Constant::new_string(
+            (0..*n)
+                .map(|_| b'a' + (rng.next_u64() % 26) as u8)
+                .collect(),
+        ),
+        Type::Array(aggregate) => {
+            let (elem_ty, count) = array_type(context, aggregate);
+            let elems = (0..count)
+                .map(|_| generate_constant(context, &elem_ty, rng))
+                .collect();
+            Constant::new_array(aggregate, elems)
+        }
+        Type::Struct(aggregate) => {
+            let fields = context.aggregates[aggregate.0]
+                .field_types()
+                .iter()
+                .map(|field_ty| generate_constant(context, field_ty, rng))
+                .collect();
+            Constant::new_struct(aggregate, fields)
+        }
+        Type::Union(aggregate) => {
+            // A union is given the value of one of its variants.
+            let variants = context.aggregates[aggregate.0].field_types();
+            if variants.is_empty() {
+                Constant::new_undef(context, *ty)
+            } else {
+                let variant_ty = variants[(rng.next_u64() % variants.len() as u64) as usize];
+                generate_constant(context, &variant_ty, rng)
+            }
+        }
+    }
+}
+
+/// A small deterministic pseudo-random number generator, xorshift64*.
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // The state must never be zero.
+        XorShift(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
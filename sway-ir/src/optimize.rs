@@ -15,7 +15,13 @@
 
 pub mod constants;
 pub use constants::*;
+pub mod induction;
+pub use induction::*;
 pub mod inline;
 pub use inline::*;
+pub mod licm;
+pub use licm::*;
+pub mod sccp;
+pub use sccp::*;
 pub mod simplify_cfg;
 pub use simplify_cfg::*;
@@ -0,0 +1,456 @@
+//! ## Induction Variable Simplification
+//!
+//! A basic induction variable is a local variable which is incremented by the same loop invariant
+//! amount on every iteration of a loop, like the index of a `while` loop.  A value found by
+//! multiplying it by a loop invariant, e.g., the byte offset of an element, then also changes by
+//! a fixed amount on every iteration.  Strength reduction replaces the multiplication with an
+//! addition to the value from the previous iteration, which is carried by the loop header's phi.
+//!
+//! Since a block has a single phi, only one multiplication may be reduced per loop, and only if
+//! the header doesn't already use its phi.  Loops compiled from `while` never do.
+
+use std::collections::{HashMap, HashSet};
+
+use sway_types::ident::Ident;
+
+use crate::{
+    analysis::{Loop, LoopForest, MemoryRegion, PredecessorMap},
+    asm::{AsmArg, AsmBlock, AsmInstruction},
+    block::Block,
+    constant::{Constant, ConstantValue},
+    context::Context,
+    error::IrError,
+    function::Function,
+    instruction::Instruction,
+    irtype::Type,
+    optimize::get_or_create_preheader,
+    pointer::Pointer,
+    value::{Value, ValueDatum},
+};
+
+/// Replace multiplications of induction variables with additions.
+pub fn simplify_induction_variables(
+    context: &mut Context,
+    function: &Function,
+) -> Result<bool, IrError> {
+    let mut modified = false;
+    while reduce_a_multiplication(context, function)? {
+        modified = true;
+        function.invalidate_analyses(context);
+    }
+    Ok(modified)
+}
+
+// An operand of an arithmetic ASM instruction.
+#[derive(Clone, Copy)]
+enum Operand {
+    Value(Value),
+    Immediate(u64),
+}
+
+// A multiplication of a basic induction variable which may be reduced.
+struct Candidate {
+    mul_block: Block,
+    mul_val: Value,
+    // The value loaded from the induction variable which is multiplied, and the pointer it's
+    // loaded from.
+    ind_var_load: Value,
+    ind_var_ptr: Pointer,
+    factor: Operand,
+    step: Operand,
+}
+
+fn reduce_a_multiplication(context: &mut Context, function: &Function) -> Result<bool, IrError> {
+    let loops = function.loop_forest(context);
+    for a_loop in loops.loops().iter().rev() {
+        if let Some(candidate) = find_candidate(context, function, &loops, a_loop) {
+            reduce(context, function, a_loop, candidate)?;
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn find_candidate(
+    context: &Context,
+    function: &Function,
+    loops: &LoopForest,
+    a_loop: &Loop,
+) -> Option<Candidate> {
+    // The header's phi must be free and every latch must be able to pass a value to it.
+    let header = a_loop.header;
+    let is_phi_free = matches!(
+        instruction(context, &header.get_phi(context)),
+        Instruction::Phi(pairs) if pairs.is_empty()
+    );
+    let latches_branch = a_loop
+        .latches
+        .iter()
+        .all(|latch| matches!(latch.get_terminator(context), Some(Instruction::Branch(_))));
+    if !is_phi_free || !latches_branch {
+        return None;
+    }
+
+    // Only instructions which are in this loop but not in a nested loop are executed exactly
+    // once per iteration.
+    let in_loop_body =
+        |block: &Block| loops.innermost_loop(block).map(|inner| inner.header) == Some(header);
+    let def_blocks = function
+        .instruction_iter(context)
+        .map(|(block, instr_val)| (instr_val, block))
+        .collect::<HashMap<_, _>>();
+    let is_invariant = |operand: &Operand| match operand {
+        Operand::Value(value) => def_blocks
+            .get(value)
+            .map_or(true, |block| !a_loop.contains(block)),
+        Operand::Immediate(_) => true,
+    };
+    let ind_var_load = |operand: &Operand| match operand {
+        Operand::Value(value) => match (def_blocks.get(value), instruction_opt(context, value)) {
+            (Some(block), Some(Instruction::Load(src_val))) if in_loop_body(block) => {
+                match MemoryRegion::of_value(context, src_val) {
+                    Some(MemoryRegion::Local(ptr)) => Some((*value, *block, ptr)),
+                    _otherwise => None,
+                }
+            }
+            _otherwise => None,
+        },
+        Operand::Immediate(_) => None,
+    };
+
+    a_loop
+        .blocks
+        .iter()
+        .filter(|block| in_loop_body(block))
+        .flat_map(|block| block.instruction_iter(context).map(|val| (*block, val)))
+        .find_map(|(mul_block, mul_val)| {
+            let (lhs, rhs) = as_binary_asm(context, &mul_val, &["mul", "muli"])?;
+            [(lhs, rhs), (rhs, lhs)]
+                .into_iter()
+                .find_map(|(ind_var_operand, factor)| {
+                    let (load_val, load_block, ind_var_ptr) = ind_var_load(&ind_var_operand)?;
+                    if !is_invariant(&factor) {
+                        return None;
+                    }
+                    let (store_pos, step) =
+                        find_basic_step(context, function, a_loop, &in_loop_body, ind_var_ptr)?;
+                    let preds = function.predecessor_map(context);
+                    let load_pos = (load_block, position(context, &load_block, &load_val));
+                    if is_reachable_in_iteration(&preds, a_loop, store_pos, load_pos) {
+                        return None;
+                    }
+                    Some(Candidate {
+                        mul_block,
+                        mul_val,
+                        ind_var_load: load_val,
+                        ind_var_ptr,
+                        factor,
+                        step,
+                    })
+                })
+        })
+}
+
+// Check that `ind_var_ptr` is a basic induction variable of `a_loop`, i.e., that the only write
+// to it is a store of its previous value plus a loop invariant step, which happens exactly once
+// per iteration.  Returns the position of the store and the step.
+fn find_basic_step<F: Fn(&Block) -> bool>(
+    context: &Context,
+    function: &Function,
+    a_loop: &Loop,
+    in_loop_body: &F,
+    ind_var_ptr: Pointer,
+) -> Option<((Block, usize), Operand)> {
+    let region = MemoryRegion::Local(ind_var_ptr);
+    let effects = function.memory_effects(context);
+    if !effects.is_private(&region) || !matches!(ind_var_ptr.get_type(context), Type::Uint(64)) {
+        return None;
+    }
+
+    let writes = a_loop
+        .blocks
+        .iter()
+        .flat_map(|block| block.instruction_iter(context).map(|val| (*block, val)))
+        .filter(|(_, instr_val)| {
+            effects
+                .writes(instr_val)
+                .iter()
+                .any(|write| effects.may_alias(write, &region))
+        })
+        .collect::<Vec<_>>();
+    let (store_block, store_val) = match writes.as_slice() {
+        [write] => *write,
+        _otherwise => return None,
+    };
+    let dom_tree = function.dominator_tree(context);
+    if !in_loop_body(&store_block)
+        || !a_loop
+            .latches
+            .iter()
+            .all(|latch| dom_tree.dominates(&store_block, latch))
+    {
+        return None;
+    }
+
+    let stored_val = match instruction(context, &store_val) {
+        Instruction::Store { stored_val, .. } => *stored_val,
+        _otherwise => return None,
+    };
+    let (lhs, rhs) = as_binary_asm(context, &stored_val, &["add", "addi"])?;
+    let store_pos = (store_block, position(context, &store_block, &store_val));
+    let preds = function.predecessor_map(context);
+    [(lhs, rhs), (rhs, lhs)]
+        .into_iter()
+        .find_map(|(prev_operand, step)| {
+            let prev_val = match prev_operand {
+                Operand::Value(prev_val) => prev_val,
+                Operand::Immediate(_) => return None,
+            };
+            let prev_block = a_loop.blocks.iter().find(|block| {
+                in_loop_body(block) && block.instruction_iter(context).any(|val| val == prev_val)
+            })?;
+            let is_prev_load = matches!(
+                instruction(context, &prev_val),
+                Instruction::Load(src_val)
+                    if MemoryRegion::of_value(context, src_val) == Some(region)
+            );
+            let is_step_invariant = match step {
+                Operand::Value(step_val) => !a_loop
+                    .blocks
+                    .iter()
+                    .any(|block| block.instruction_iter(context).any(|val| val == step_val)),
+                Operand::Immediate(_) => true,
+            };
+            let prev_pos = (*prev_block, position(context, prev_block, &prev_val));
+            if is_prev_load
+                && is_step_invariant
+                && !is_reachable_in_iteration(&preds, a_loop, store_pos, prev_pos)
+            {
+                Some(step)
+            } else {
+                None
+            }
+        })
+        .map(|step| (store_pos, step))
+}
+
+fn reduce(
+    context: &mut Context,
+    function: &Function,
+    a_loop: &Loop,
+    candidate: Candidate,
+) -> Result<(), IrError> {
+    let Candidate {
+        mul_block,
+        mul_val,
+        ind_var_load,
+        ind_var_ptr,
+        factor,
+        step,
+    } = candidate;
+    let header = a_loop.header;
+    let mul_md = mul_val.get_metadata(context);
+    let preheader = get_or_create_preheader(context, function, a_loop)?;
+
+    // Compute the initial value in the preheader, by repeating the multiplication with the
+    // induction variable's initial value.
+    let ptr_ty = *ind_var_ptr.get_type(context);
+    let ptr_val = Value::new_instruction(
+        context,
+        Instruction::GetPointer {
+            base_ptr: ind_var_ptr,
+            ptr_ty,
+            offset: 0,
+        },
+    );
+    preheader.insert_before_terminator(context, ptr_val);
+    let init_ind_var = Value::new_instruction(context, Instruction::Load(ptr_val));
+    preheader.insert_before_terminator(context, init_ind_var);
+    let (mul_asm, mul_args) = match instruction(context, &mul_val) {
+        Instruction::AsmBlock(asm, args) => (*asm, args.clone()),
+        _otherwise => unreachable!("Candidate multiplication must be an ASM block."),
+    };
+    let init_args = mul_args
+        .into_iter()
+        .map(|AsmArg { name, initializer }| AsmArg {
+            name,
+            initializer: initializer.map(|value| {
+                if value == ind_var_load {
+                    init_ind_var
+                } else {
+                    value
+                }
+            }),
+        })
+        .collect();
+    let init_mul = Value::new_instruction(context, Instruction::AsmBlock(mul_asm, init_args))
+        .add_metadatum(context, mul_md);
+    preheader.insert_before_terminator(context, init_mul);
+
+    // The product changes by `step * factor` each iteration.
+    let increment = match (
+        constant_operand(context, &step),
+        constant_operand(context, &factor),
+    ) {
+        (Some(step), Some(factor)) => Constant::get_uint(context, 64, step.wrapping_mul(factor)),
+        _otherwise => {
+            let step_val = operand_value(context, step);
+            let factor_val = operand_value(context, factor);
+            let increment = binary_asm(context, "mul", step_val, factor_val);
+            preheader.insert_before_terminator(context, increment);
+            increment
+        }
+    };
+
+    // Carry the product in the header's phi, adding the increment at the end of each iteration.
+    let phi_val = header.get_phi(context);
+    header.add_phi(context, preheader, init_mul);
+    for latch in &a_loop.latches {
+        let next_val =
+            binary_asm(context, "add", phi_val, increment).add_metadatum(context, mul_md);
+        latch.insert_before_terminator(context, next_val);
+        header.add_phi(context, *latch, next_val);
+    }
+
+    function.replace_value(context, mul_val, phi_val, None);
+    mul_block.remove_instruction(context, mul_val);
+    Ok(())
+}
+
+// Return whether the instruction at `to` may be executed after the instruction at `from` in the
+// same iteration of `a_loop`, i.e., without passing through its header.
+fn is_reachable_in_iteration(
+    preds: &PredecessorMap,
+    a_loop: &Loop,
+    (from_block, from_idx): (Block, usize),
+    (to_block, to_idx): (Block, usize),
+) -> bool {
+    if from_block == to_block && from_idx < to_idx {
+        return true;
+    }
+    let mut visited = HashSet::new();
+    let mut worklist = preds.successors(&from_block).to_vec();
+    while let Some(block) = worklist.pop() {
+        if block == a_loop.header || !a_loop.contains(&block) || !visited.insert(block) {
+            continue;
+        }
+        if block == to_block {
+            return true;
+        }
+        worklist.extend(preds.successors(&block));
+    }
+    false
+}
+
+// Match an ASM block with a single instruction named in `op_names` which returns its result, e.g.,
+// `asm(a: v0, b: v1, r) -> u64 r { add r a b }` or `asm(a: v0, r) -> u64 r { addi r a i1 }`.
+fn as_binary_asm(
+    context: &Context,
+    instr_val: &Value,
+    op_names: &[&str],
+) -> Option<(Operand, Operand)> {
+    let (asm, args) = match instruction_opt(context, instr_val)? {
+        Instruction::AsmBlock(asm, args) => (asm, args),
+        _otherwise => return None,
+    };
+    let asm_content = &context.asm_blocks[asm.0];
+    let op = match asm_content.body.as_slice() {
+        [op] if op_names.contains(&op.name.as_str()) => op,
+        _otherwise => return None,
+    };
+    if !matches!(asm_content.return_type, Type::Uint(64))
+        || asm_content.return_name.as_ref().map(|name| name.as_str())
+            != op.args.first().map(|name| name.as_str())
+    {
+        return None;
+    }
+
+    let reg_operand = |reg_name: &Ident| {
+        args.iter()
+            .find(|arg| arg.name.as_str() == reg_name.as_str())
+            .and_then(|arg| arg.initializer)
+            .map(Operand::Value)
+    };
+    match (&op.args[..], &op.immediate) {
+        ([_, lhs, rhs], None) => Some((reg_operand(lhs)?, reg_operand(rhs)?)),
+        ([_, lhs], Some(imm)) => {
+            let imm = imm.as_str().strip_prefix('i')?.parse().ok()?;
+            Some((reg_operand(lhs)?, Operand::Immediate(imm)))
+        }
+        _otherwise => None,
+    }
+}
+
+// Create `asm(a: lhs, b: rhs, r) -> u64 r { <op_name> r a b }`, without adding it to a block.
+fn binary_asm(context: &mut Context, op_name: &'static str, lhs: Value, rhs: Value) -> Value {
+    let args = vec![
+        AsmArg {
+            name: Ident::new_no_span("a"),
+            initializer: Some(lhs),
+        },
+        AsmArg {
+            name: Ident::new_no_span("b"),
+            initializer: Some(rhs),
+        },
+        AsmArg {
+            name: Ident::new_no_span("r"),
+            initializer: None,
+        },
+    ];
+    let body = vec![AsmInstruction {
+        name: Ident::new_no_span(op_name),
+        args: vec![
+            Ident::new_no_span("r"),
+            Ident::new_no_span("a"),
+            Ident::new_no_span("b"),
+        ],
+        immediate: None,
+        metadata: None,
+    }];
+    let asm = AsmBlock::new(
+        context,
+        args.iter().map(|arg| arg.name.clone()).collect(),
+        body,
+        Type::Uint(64),
+        Some(Ident::new_no_span("r")),
+    );
+    Value::new_instruction(context, Instruction::AsmBlock(asm, args))
+}
+
+fn constant_operand(context: &Context, operand: &Operand) -> Option<u64> {
+    match operand {
+        Operand::Value(value) => match &context.values[value.0].value {
+            ValueDatum::Constant(Constant {
+                value: ConstantValue::Uint(n),
+                ..
+            }) => Some(*n),
+            _otherwise => None,
+        },
+        Operand::Immediate(n) => Some(*n),
+    }
+}
+
+fn operand_value(context: &mut Context, operand: Operand) -> Value {
+    match operand {
+        Operand::Value(value) => value,
+        Operand::Immediate(n) => Constant::get_uint(context, 64, n),
+    }
+}
+
+fn position(context: &Context, block: &Block, instr_val: &Value) -> usize {
+    block
+        .instruction_iter(context)
+        .position(|val| val == *instr_val)
+        .unwrap()
+}
+
+fn instruction<'a>(context: &'a Context, instr_val: &Value) -> &'a Instruction {
+    instruction_opt(context, instr_val).expect("Block contains a non-instruction value.")
+}
+
+fn instruction_opt<'a>(context: &'a Context, value: &Value) -> Option<&'a Instruction> {
+    match &context.values[value.0].value {
+        ValueDatum::Instruction(instruction) => Some(instruction),
+        _otherwise => None,
+    }
+}
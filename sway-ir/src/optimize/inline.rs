@@ -2,7 +2,7 @@
 //!
 //! Function inlining is pretty hairy so these passes must be maintained with care.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     asm::AsmArg,
@@ -11,6 +11,7 @@ use crate::{
     error::IrError,
     function::Function,
     instruction::Instruction,
+    irtype::Type,
     metadata::{combine, MetadataIndex},
     pointer::Pointer,
     value::{Value, ValueContent, ValueDatum},
@@ -20,6 +21,9 @@ use crate::{
 ///
 /// e.g., If this is applied to main() then all calls in the program are removed.  This is
 /// obviously dangerous for recursive functions, in which case this pass would inline forever.
+///
+/// `CallIndirect` instructions whose callee can be determined statically are converted to direct
+/// calls and inlined too.  Any others are left in place.
 pub fn inline_all_function_calls(
     context: &mut Context,
     function: &Function,
@@ -29,14 +33,21 @@ pub fn inline_all_function_calls(
         // Find the next call site.
         let call_data = function
             .instruction_iter(context)
-            .find_map(|(block, call_val)| match context.values[call_val.0].value {
-                ValueDatum::Instruction(Instruction::Call(inlined_function, _)) => {
-                    Some((block, call_val, inlined_function))
-                }
-                _ => None,
-            });
+            .find_map(
+                |(block, call_val)| match &context.values[call_val.0].value {
+                    ValueDatum::Instruction(Instruction::Call(inlined_function, _)) => {
+                        Some((block, call_val, *inlined_function))
+                    }
+                    ValueDatum::Instruction(Instruction::CallIndirect { callee, .. }) => {
+                        resolve_fn_addr(context, function, callee, &mut HashSet::new())
+                            .map(|inlined_function| (block, call_val, inlined_function))
+                    }
+                    _ => None,
+                },
+            );
         match call_data {
             Some((block, call_val, inlined_function)) => {
+                devirtualize_call(context, call_val, inlined_function);
                 inline_function_call(context, *function, block, call_val, inlined_function)?;
                 modified = true;
             }
@@ -46,6 +57,175 @@ pub fn inline_all_function_calls(
     Ok(modified)
 }
 
+/// Replace a `CallIndirect` at `call_site` with a direct `Call` to `callee`.  Direct calls are
+/// left unchanged.
+fn devirtualize_call(context: &mut Context, call_site: Value, callee: Function) {
+    if let Some(Instruction::CallIndirect { args, .. }) = call_site.get_instruction_mut(context) {
+        let args = std::mem::take(args);
+        *call_site.get_instruction_mut(context).unwrap() = Instruction::Call(callee, args);
+    }
+}
+
+/// Attempt to determine statically which function a function pointer `value` refers to.
+///
+/// The value is traced through `phi` instructions and through loads from locals, as long as every
+/// store to the loaded location writes a pointer to the same function and the local isn't written
+/// any other way.
+fn resolve_fn_addr(
+    context: &Context,
+    function: &Function,
+    value: &Value,
+    visiting: &mut HashSet<Value>,
+) -> Option<Function> {
+    // Give up on cycles, e.g., a `phi` in a loop which refers to itself.
+    if !visiting.insert(*value) {
+        return None;
+    }
+
+    // All of `values` must resolve to the same single function.
+    fn resolve_all(
+        context: &Context,
+        function: &Function,
+        mut values: impl Iterator<Item = Value>,
+        visiting: &mut HashSet<Value>,
+    ) -> Option<Function> {
+        let first = resolve_fn_addr(context, function, &values.next()?, visiting)?;
+        values
+            .all(|value| resolve_fn_addr(context, function, &value, visiting) == Some(first))
+            .then(|| first)
+    }
+
+    let resolved = match &context.values[value.0].value {
+        ValueDatum::Instruction(Instruction::FnAddr(target)) => Some(*target),
+        ValueDatum::Instruction(Instruction::Phi(pairs)) => resolve_all(
+            context,
+            function,
+            pairs.iter().map(|(_, phi_val)| *phi_val),
+            visiting,
+        ),
+        ValueDatum::Instruction(Instruction::Load(src_val)) => {
+            match &context.values[src_val.0].value {
+                ValueDatum::Instruction(Instruction::GetPointer {
+                    base_ptr,
+                    ptr_ty,
+                    offset,
+                }) => stored_values(context, function, base_ptr, ptr_ty, *offset).and_then(
+                    |stored_vals| resolve_all(context, function, stored_vals.into_iter(), visiting),
+                ),
+                _otherwise => None,
+            }
+        }
+        _otherwise => None,
+    };
+
+    visiting.remove(value);
+    resolved
+}
+
+/// The values stored by `function` to the location in the local `base_ptr` given by `ptr_ty` and
+/// `offset`, or `None` if the local may be written in any other way, e.g., at another location,
+/// by an ASM block or through an address which has escaped.
+///
+/// Only the exact location may be addressed, and its address may only be loaded from or stored
+/// to, so no other instruction can write to it.
+fn stored_values(
+    context: &Context,
+    function: &Function,
+    base_ptr: &Pointer,
+    ptr_ty: &Type,
+    offset: u64,
+) -> Option<Vec<Value>> {
+    let mut ptr_vals = HashSet::new();
+    for (_, instr_val) in function.instruction_iter(context) {
+        if let ValueDatum::Instruction(Instruction::GetPointer {
+            base_ptr: other_ptr,
+            ptr_ty: other_ty,
+            offset: other_offset,
+        }) = &context.values[instr_val.0].value
+        {
+            if other_ptr == base_ptr {
+                if !other_ty.eq(context, ptr_ty) || *other_offset != offset {
+                    return None;
+                }
+                ptr_vals.insert(instr_val);
+            }
+        }
+    }
+
+    let mut stored_vals = Vec::new();
+    for (_, instr_val) in function.instruction_iter(context) {
+        match &context.values[instr_val.0].value {
+            ValueDatum::Instruction(Instruction::Load(_)) => (),
+            ValueDatum::Instruction(Instruction::Store {
+                dst_val,
+                stored_val,
+            }) => {
+                if ptr_vals.contains(stored_val) {
+                    return None;
+                }
+                if ptr_vals.contains(dst_val) {
+                    stored_vals.push(*stored_val);
+                }
+            }
+            ValueDatum::Instruction(instruction) => {
+                if escaping_operands(instruction)
+                    .iter()
+                    .any(|operand| ptr_vals.contains(operand))
+                {
+                    return None;
+                }
+            }
+            _otherwise => (),
+        }
+    }
+    Some(stored_vals)
+}
+
+/// The operands of `instruction` through which it may write to memory or pass an address on.
+/// Only the address a `load` reads from and a `store` writes to are excluded.
+fn escaping_operands(instruction: &Instruction) -> Vec<Value> {
+    match instruction {
+        Instruction::AddrOf(value)
+        | Instruction::BitCast(value, _)
+        | Instruction::IntToPtr(value, _)
+        | Instruction::Ret(value, _) => vec![*value],
+        Instruction::AsmBlock(_, args) => args.iter().filter_map(|arg| arg.initializer).collect(),
+        Instruction::Call(_, args) => args.clone(),
+        Instruction::CallIndirect { callee, args, .. } => std::iter::once(*callee)
+            .chain(args.iter().copied())
+            .collect(),
+        Instruction::Cmp(_, lhs, rhs) => vec![*lhs, *rhs],
+        Instruction::ContractCall {
+            params,
+            coins,
+            asset_id,
+            gas,
+            ..
+        } => vec![*params, *coins, *asset_id, *gas],
+        Instruction::ExtractElement { array, .. } => vec![*array],
+        Instruction::ExtractValue { aggregate, .. } => vec![*aggregate],
+        Instruction::InsertElement { array, value, .. } => vec![*array, *value],
+        Instruction::InsertValue {
+            aggregate, value, ..
+        } => vec![*aggregate, *value],
+        Instruction::Phi(pairs) => pairs.iter().map(|(_, value)| *value).collect(),
+        Instruction::StateLoadQuadWord { load_val, .. } => vec![*load_val],
+        Instruction::StateStoreQuadWord { stored_val, .. } => vec![*stored_val],
+        Instruction::Store { stored_val, .. } => vec![*stored_val],
+        Instruction::Branch(_)
+        | Instruction::ConditionalBranch { .. }
+        | Instruction::FnAddr(_)
+        | Instruction::GetStorageKey
+        | Instruction::Gtf { .. }
+        | Instruction::GetPointer { .. }
+        | Instruction::Load(_)
+        | Instruction::Nop
+        | Instruction::ReadRegister(_)
+        | Instruction::StateLoadWord(_)
+        | Instruction::StateStoreWord { .. } => Vec::new(),
+    }
+}
+
 /// Inline a function to a specific call site within another function.
 ///
 /// The destination function, block and call site must be specified along with the function to
@@ -234,6 +414,18 @@ fn inline_instruction(
                     .collect::<Vec<Value>>()
                     .as_slice(),
             ),
+            Instruction::CallIndirect {
+                callee,
+                args,
+                return_type,
+            } => new_block.ins(context).call_indirect(
+                map_value(callee),
+                args.iter()
+                    .map(|old_val: &Value| map_value(*old_val))
+                    .collect::<Vec<Value>>()
+                    .as_slice(),
+                return_type,
+            ),
             Instruction::Cmp(pred, lhs_value, rhs_value) => {
                 new_block
                     .ins(context)
@@ -278,6 +470,7 @@ fn inline_instruction(
             } => new_block
                 .ins(context)
                 .extract_value(map_value(aggregate), ty, indices),
+            Instruction::FnAddr(f) => new_block.ins(context).fn_addr(f),
             Instruction::GetStorageKey => new_block.ins(context).get_storage_key(),
             Instruction::GetPointer {
                 base_ptr,
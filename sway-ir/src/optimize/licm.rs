@@ -0,0 +1,321 @@
+//! ## Loop Invariant Code Motion
+//!
+//! Instructions in a loop which compute the same value on every iteration are moved to the loop's
+//! preheader, the single block which branches into the loop from outside it, so that they're only
+//! executed once.
+//!
+//! An instruction is hoisted if each of its operands is defined outside the loop or is hoisted
+//! too, and if it's safe to execute it speculatively, since the loop body may never have been
+//! entered.  So it mustn't trap and it may only read memory which is known to be valid.  Any
+//! memory it reads mustn't be written within the loop, unless that write is hoisted too and is
+//! always executed before the read, or the memory is entirely overwritten before it's used on
+//! each iteration.
+//!
+//! Writes are only hoisted to memory which is private to the loop; a local variable whose address
+//! doesn't escape, which isn't accessed outside of the loop and whose every access within the
+//! loop is also hoisted.  This catches the temporary buffers which storage keys are hashed into.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    analysis::{Loop, MemoryEffects, MemoryRegion},
+    block::Block,
+    constant::{Constant, ConstantValue},
+    context::Context,
+    error::IrError,
+    function::Function,
+    instruction::Instruction,
+    irtype::{Aggregate, AggregateContent},
+    value::{Value, ValueDatum},
+};
+
+/// Hoist loop invariant instructions out of every loop in `function`.
+pub fn hoist_loop_invariants(context: &mut Context, function: &Function) -> Result<bool, IrError> {
+    let mut modified = false;
+    while hoist_from_a_loop(context, function)? {
+        modified = true;
+        function.invalidate_analyses(context);
+    }
+    Ok(modified)
+}
+
+/// Return the preheader of `a_loop`, which is the single block outside the loop which branches
+/// to its header.  If there isn't one then a new block is inserted before the header, and any
+/// blocks which branched into the loop now branch to it instead.
+///
+/// If a block is created then the analyses of `function` are invalidated.
+pub fn get_or_create_preheader(
+    context: &mut Context,
+    function: &Function,
+    a_loop: &Loop,
+) -> Result<Block, IrError> {
+    let header = a_loop.header;
+    let preds = function.predecessor_map(context);
+    let entries = preds
+        .predecessors(&header)
+        .iter()
+        .filter(|pred| !a_loop.contains(pred))
+        .copied()
+        .collect::<Vec<_>>();
+    if let [entry] = entries.as_slice() {
+        if preds.successors(entry) == [header] {
+            return Ok(*entry);
+        }
+    }
+
+    // Values passed to the header's phi from outside the loop are now passed to the preheader's
+    // phi instead, and then on to the header.
+    let preheader = function.create_block_before(context, &header, Some("preheader".into()))?;
+    let mut has_phi_values = false;
+    for entry in &entries {
+        entry.replace_successor(context, header, preheader);
+        if let Some(phi_val) = header.get_phi_val_coming_from(context, entry) {
+            header.remove_phi_val_coming_from(context, entry);
+            preheader.add_phi(context, *entry, phi_val);
+            has_phi_values = true;
+        }
+    }
+    let phi_value = if has_phi_values {
+        Some(preheader.get_phi(context))
+    } else {
+        None
+    };
+    preheader.ins(context).branch(header, phi_value);
+
+    function.invalidate_analyses(context);
+    Ok(preheader)
+}
+
+fn hoist_from_a_loop(context: &mut Context, function: &Function) -> Result<bool, IrError> {
+    // Nested loops follow the loops which contain them, so by going in reverse the instructions
+    // hoisted from an inner loop may then be hoisted from the outer loop too.
+    let loops = function.loop_forest(context);
+    for a_loop in loops.loops().iter().rev() {
+        let invariants = find_invariants(context, function, a_loop);
+        if !invariants.is_empty() {
+            let preheader = get_or_create_preheader(context, function, a_loop)?;
+            for (block, instr_val) in invariants {
+                block.remove_instruction(context, instr_val);
+                preheader.insert_before_terminator(context, instr_val);
+            }
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Return the instructions which may be hoisted from `a_loop`, in the order they must be placed in
+// the preheader.
+fn find_invariants(context: &Context, function: &Function, a_loop: &Loop) -> Vec<(Block, Value)> {
+    let dom_tree = function.dominator_tree(context);
+    let effects = function.memory_effects(context);
+
+    // Every instruction in the loop, after those which dominate it.
+    let loop_instrs = dom_tree
+        .pre_order()
+        .iter()
+        .filter(|block| a_loop.contains(block))
+        .flat_map(|block| {
+            block
+                .instruction_iter(context)
+                .enumerate()
+                .map(move |(idx, instr_val)| (instr_val, (*block, idx)))
+        })
+        .collect::<Vec<_>>();
+    let positions = loop_instrs.iter().copied().collect::<HashMap<_, _>>();
+    let instr_dominates = |a: &Value, b: &Value| {
+        let (a_block, a_idx) = positions[a];
+        let (b_block, b_idx) = positions[b];
+        if a_block == b_block {
+            a_idx < b_idx
+        } else {
+            dom_tree.dominates(&a_block, &b_block)
+        }
+    };
+
+    let outside_accesses = function
+        .instruction_iter(context)
+        .filter(|(block, _)| !a_loop.contains(block))
+        .flat_map(|(_, instr_val)| {
+            effects
+                .reads(&instr_val)
+                .iter()
+                .chain(effects.writes(&instr_val))
+                .copied()
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let accesses = |instr_val: &Value, region: &MemoryRegion| {
+        effects
+            .reads(instr_val)
+            .iter()
+            .chain(effects.writes(instr_val))
+            .any(|access| effects.may_alias(access, region))
+    };
+
+    // A private local which is entirely overwritten before it's otherwise accessed holds the same
+    // value on every iteration, so its accesses may be hoisted together regardless of their order.
+    let reset_regions = loop_instrs
+        .iter()
+        .filter_map(|(instr_val, _)| {
+            let region = overwritten_region(context, instr_val)?;
+            let is_first_access = loop_instrs.iter().all(|(other_val, _)| {
+                other_val == instr_val
+                    || !accesses(other_val, &region)
+                    || instr_dominates(instr_val, other_val)
+            });
+            if effects.is_private(&region) && is_first_access {
+                Some(region)
+            } else {
+                None
+            }
+        })
+        .collect::<HashSet<_>>();
+
+    // Start with every instruction which could be hoisted and remove those which can't be until
+    // there are no more changes.
+    let mut invariants = loop_instrs
+        .iter()
+        .filter(|(instr_val, _)| is_speculatable(context, instr_val, &effects))
+        .map(|(instr_val, _)| *instr_val)
+        .collect::<HashSet<_>>();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (instr_val, (block, _)) in &loop_instrs {
+            if !invariants.contains(instr_val) {
+                continue;
+            }
+
+            let operands_are_invariant = instruction(context, instr_val)
+                .get_operands()
+                .iter()
+                .all(|operand| !positions.contains_key(operand) || invariants.contains(operand));
+
+            let reads_are_invariant = effects.reads(instr_val).iter().all(|read| {
+                *read != MemoryRegion::Unknown
+                    && !effects
+                        .writes(instr_val)
+                        .iter()
+                        .any(|write| effects.may_alias(read, write))
+                    && loop_instrs.iter().all(|(other_val, _)| {
+                        other_val == instr_val
+                            || !effects
+                                .writes(other_val)
+                                .iter()
+                                .any(|write| effects.may_alias(read, write))
+                            || (invariants.contains(other_val)
+                                && (reset_regions.contains(read)
+                                    || instr_dominates(other_val, instr_val)))
+                    })
+            });
+
+            let writes_are_private = effects.writes(instr_val).iter().all(|write| {
+                effects.is_private(write)
+                    && !outside_accesses
+                        .iter()
+                        .any(|access| effects.may_alias(access, write))
+                    && loop_instrs.iter().all(|(other_val, _)| {
+                        invariants.contains(other_val) || !accesses(other_val, write)
+                    })
+                    && a_loop
+                        .latches
+                        .iter()
+                        .all(|latch| dom_tree.dominates(block, latch))
+            });
+
+            if !(operands_are_invariant && reads_are_invariant && writes_are_private) {
+                invariants.remove(instr_val);
+                changed = true;
+            }
+        }
+    }
+
+    loop_instrs
+        .into_iter()
+        .filter(|(instr_val, _)| invariants.contains(instr_val))
+        .map(|(instr_val, (block, _))| (block, instr_val))
+        .collect()
+}
+
+// The local which `instr_val` entirely overwrites, if it's a store to the whole of one.
+fn overwritten_region(context: &Context, instr_val: &Value) -> Option<MemoryRegion> {
+    let dst_val = match instruction(context, instr_val) {
+        Instruction::Store { dst_val, .. } => dst_val,
+        _otherwise => return None,
+    };
+    match &context.values[dst_val.0].value {
+        ValueDatum::Instruction(Instruction::GetPointer {
+            base_ptr,
+            ptr_ty,
+            offset: 0,
+        }) if ptr_ty.eq(context, base_ptr.get_type(context)) => {
+            Some(MemoryRegion::Local(*base_ptr))
+        }
+        _otherwise => None,
+    }
+}
+
+// Whether the instruction may be executed when it otherwise wouldn't have been, ignoring its
+// operands and the memory it accesses.
+fn is_speculatable(context: &Context, instr_val: &Value, effects: &MemoryEffects) -> bool {
+    match instruction(context, instr_val) {
+        Instruction::AddrOf(_)
+        | Instruction::BitCast(..)
+        | Instruction::Cmp(..)
+        | Instruction::ExtractValue { .. }
+        | Instruction::FnAddr(_)
+        | Instruction::GetPointer { .. }
+        | Instruction::GetStorageKey
+        | Instruction::Gtf { .. }
+        | Instruction::InsertValue { .. }
+        | Instruction::IntToPtr(..)
+        | Instruction::Load(_)
+        | Instruction::StateLoadQuadWord { .. }
+        | Instruction::StateLoadWord(_)
+        | Instruction::Store { .. } => true,
+
+        // Array indices aren't checked, so only constant indices are known to be in bounds.
+        Instruction::ExtractElement { ty, index_val, .. }
+        | Instruction::InsertElement { ty, index_val, .. } => {
+            is_index_in_bounds(context, ty, index_val)
+        }
+
+        Instruction::AsmBlock(..) => !effects.is_opaque(instr_val) && !effects.may_trap(instr_val),
+
+        Instruction::Branch(_)
+        | Instruction::Call(..)
+        | Instruction::CallIndirect { .. }
+        | Instruction::ConditionalBranch { .. }
+        | Instruction::ContractCall { .. }
+        | Instruction::Nop
+        | Instruction::Phi(_)
+        | Instruction::ReadRegister(_)
+        | Instruction::Ret(..)
+        | Instruction::StateStoreQuadWord { .. }
+        | Instruction::StateStoreWord { .. } => false,
+    }
+}
+
+fn is_index_in_bounds(context: &Context, ty: &Aggregate, index_val: &Value) -> bool {
+    match (
+        &context.aggregates[ty.0],
+        &context.values[index_val.0].value,
+    ) {
+        (
+            AggregateContent::ArrayType(_, count),
+            ValueDatum::Constant(Constant {
+                value: ConstantValue::Uint(index),
+                ..
+            }),
+        ) => index < count,
+        _otherwise => false,
+    }
+}
+
+fn instruction<'a>(context: &'a Context, instr_val: &Value) -> &'a Instruction {
+    match &context.values[instr_val.0].value {
+        ValueDatum::Instruction(instruction) => instruction,
+        _otherwise => unreachable!("Block contains a non-instruction value."),
+    }
+}
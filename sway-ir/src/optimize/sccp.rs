@@ -0,0 +1,602 @@
+//! ## Sparse Conditional Constant Propagation
+//!
+//! Values which are known to be constant are found by optimistically assuming that every value
+//! is constant and that every block is unreachable until proven otherwise.  Only the edges out of
+//! a conditional branch whose condition may take the matching value are followed, and a `phi`
+//! only combines the values coming from the edges which are followed.  So a constant can pass
+//! through a `phi` whose other incoming values are from blocks which are never executed.
+//!
+//! Each constant value then replaces its instruction, conditional branches on a constant become
+//! unconditional and the blocks which can never be executed are removed.
+//!
+//! Arithmetic in Sway is written in ASM blocks, so those which only use the simple ALU
+//! instructions are evaluated too, with the same wrapping semantics as the VM.
+//!
+//! The interprocedural mode specialises a function for the constant arguments passed to it at a
+//! call site.  The specialised copy has the constant arguments removed and the constants
+//! propagated through its body, and the call is redirected to it.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    asm::{AsmArg, AsmBlock},
+    block::Block,
+    constant::{Constant, ConstantValue},
+    context::Context,
+    error::IrError,
+    function::Function,
+    instruction::{Instruction, Predicate, Register},
+    irtype::Type,
+    module::Module,
+    optimize::inline_function_call,
+    value::{Value, ValueDatum},
+};
+
+/// Find the values in `function` which are always constant and replace them, and remove any
+/// blocks which can never be executed.
+pub fn propagate_constants(context: &mut Context, function: &Function) -> Result<bool, IrError> {
+    let solution = Solver::new(context, function).solve(context, function);
+
+    // Collect the changes to make before making them, since we'll be replacing the values which
+    // the solution refers to.
+    let mut folded = Vec::new();
+    let mut branches = Vec::new();
+    for block in function.block_iter(context) {
+        if !solution.executable_blocks.contains(&block) {
+            continue;
+        }
+        for instr_val in block.instruction_iter(context) {
+            match &context.values[instr_val.0].value {
+                ValueDatum::Instruction(Instruction::ConditionalBranch {
+                    cond_value,
+                    true_block,
+                    false_block,
+                }) => {
+                    if let Some(cond) = solution.lattice(context, cond_value).as_bool() {
+                        let (taken, not_taken) = if cond {
+                            (*true_block, *false_block)
+                        } else {
+                            (*false_block, *true_block)
+                        };
+                        branches.push((block, instr_val, taken, not_taken));
+                    }
+                }
+                ValueDatum::Instruction(instruction) if is_foldable(instruction) => {
+                    if let Lattice::Constant(constant) = solution.lattice(context, &instr_val) {
+                        folded.push((block, instr_val, constant));
+                    }
+                }
+                _otherwise => (),
+            }
+        }
+    }
+    let preds = function.predecessor_map(context);
+    let dead_blocks = function
+        .block_iter(context)
+        .filter(|block| !solution.executable_blocks.contains(block))
+        .collect::<Vec<_>>();
+
+    let modified = !folded.is_empty() || !branches.is_empty() || !dead_blocks.is_empty();
+
+    for (block, instr_val, constant) in folded {
+        let metadata = context.values[instr_val.0].metadata;
+        let const_val = Value::new_constant(context, constant).add_metadatum(context, metadata);
+        function.replace_value(context, instr_val, const_val, None);
+        if block.get_phi(context) == instr_val {
+            // Every block must have a phi, so it's emptied rather than removed.
+            context.values[instr_val.0].value =
+                ValueDatum::Instruction(Instruction::Phi(Vec::new()));
+        } else {
+            block.remove_instruction(context, instr_val);
+        }
+    }
+
+    for (block, branch_val, taken, not_taken) in branches {
+        context.values[branch_val.0].value = ValueDatum::Instruction(Instruction::Branch(taken));
+        if not_taken != taken {
+            not_taken.remove_phi_val_coming_from(context, &block);
+        }
+    }
+
+    for dead_block in &dead_blocks {
+        for succ in preds.successors(dead_block) {
+            succ.remove_phi_val_coming_from(context, dead_block);
+        }
+        function.remove_block(context, dead_block)?;
+    }
+
+    if modified {
+        function.invalidate_analyses(context);
+    }
+    Ok(modified)
+}
+
+/// Specialise the functions called from `function` for the constant arguments they're passed.
+///
+/// Each specialisation is a new function named after the callee, with a suffix which makes the
+/// name unique within the module.  The calls from `function` with the same callee and constant
+/// arguments share one.
+pub fn specialize_constant_calls(
+    context: &mut Context,
+    function: &Function,
+) -> Result<bool, IrError> {
+    let call_sites = function
+        .instruction_iter(context)
+        .filter_map(|(_, instr_val)| match &context.values[instr_val.0].value {
+            ValueDatum::Instruction(Instruction::Call(callee, args))
+                if callee != function
+                    && args
+                        .iter()
+                        .any(|arg| scalar_constant(context, arg).is_some()) =>
+            {
+                Some((instr_val, *callee, args.clone()))
+            }
+            _otherwise => None,
+        })
+        .collect::<Vec<_>>();
+
+    let modified = !call_sites.is_empty();
+    let mut specializations = HashMap::new();
+    for (call_val, callee, args) in call_sites {
+        let key = (callee, constant_args_key(context, &args));
+        let specialized = match specializations.get(&key) {
+            Some(specialized) => *specialized,
+            None => {
+                let specialized = create_specialization(context, function, callee, &args)?;
+                specializations.insert(key, specialized);
+                specialized
+            }
+        };
+        let remaining_args = args
+            .into_iter()
+            .filter(|arg| scalar_constant(context, arg).is_none())
+            .collect();
+        context.values[call_val.0].value =
+            ValueDatum::Instruction(Instruction::Call(specialized, remaining_args));
+    }
+
+    if modified {
+        function.invalidate_analyses(context);
+    }
+    Ok(modified)
+}
+
+// The constant arguments of a call, which identify its specialisation along with the callee.  The
+// type of each argument is fixed by the callee, so the constant values alone are distinct.
+fn constant_args_key(context: &Context, args: &[Value]) -> Vec<Option<u64>> {
+    args.iter()
+        .map(|arg| {
+            scalar_constant(context, arg).map(|constant| match constant.value {
+                ConstantValue::Unit => 0,
+                ConstantValue::Bool(b) => b as u64,
+                ConstantValue::Uint(n) => n,
+                _otherwise => unreachable!("Scalar constants are only unit, bool or uint."),
+            })
+        })
+        .collect()
+}
+
+fn create_specialization(
+    context: &mut Context,
+    caller: &Function,
+    callee: Function,
+    args: &[Value],
+) -> Result<Function, IrError> {
+    let module = context
+        .modules
+        .iter()
+        .find(|(_, module)| module.functions.contains(caller))
+        .map(|(idx, _)| Module(idx))
+        .expect("Function must belong to a module.");
+
+    let symbols = module
+        .function_iter(context)
+        .map(|func| func.get_name(context).to_owned())
+        .collect::<HashSet<_>>();
+    let callee_name = callee.get_name(context);
+    let name = (0..)
+        .map(|idx| format!("{callee_name}_spec{idx}"))
+        .find(|name| !symbols.contains(name))
+        .unwrap();
+
+    // The new function's body is just a call to the original with the constant arguments, which
+    // is then inlined and the constants propagated through it.
+    let callee_args = context.functions[callee.0].arguments.clone();
+    let spec_args = callee_args
+        .iter()
+        .zip(args)
+        .filter(|(_, arg)| scalar_constant(context, arg).is_none())
+        .map(|((arg_name, arg_val), _)| {
+            (
+                arg_name.clone(),
+                arg_val.get_type(context).unwrap(),
+                context.values[arg_val.0].metadata,
+            )
+        })
+        .collect();
+    let return_type = context.functions[callee.0].return_type;
+    let metadata = context.functions[callee.0].metadata;
+    let specialized = Function::new(
+        context,
+        module,
+        name,
+        spec_args,
+        return_type,
+        None,
+        false,
+        metadata,
+    );
+
+    let mut spec_arg_vals = specialized
+        .args_iter(context)
+        .map(|(_, arg_val)| *arg_val)
+        .collect::<Vec<_>>()
+        .into_iter();
+    let call_args = args
+        .iter()
+        .map(|arg| match scalar_constant(context, arg) {
+            Some(constant) => Value::new_constant(context, constant),
+            None => spec_arg_vals.next().unwrap(),
+        })
+        .collect::<Vec<_>>();
+    let entry_block = specialized.get_entry_block(context);
+    let call_val = entry_block.ins(context).call(callee, &call_args);
+    entry_block.ins(context).ret(call_val, return_type);
+    inline_function_call(context, specialized, entry_block, call_val, callee)?;
+    propagate_constants(context, &specialized)?;
+
+    Ok(specialized)
+}
+
+// The constant value of a value which is itself a constant of a scalar type.
+fn scalar_constant(context: &Context, value: &Value) -> Option<Constant> {
+    match &context.values[value.0].value {
+        ValueDatum::Constant(constant) if is_scalar(constant) => Some(constant.clone()),
+        _otherwise => None,
+    }
+}
+
+fn is_scalar(constant: &Constant) -> bool {
+    matches!(
+        constant.value,
+        ConstantValue::Unit | ConstantValue::Bool(_) | ConstantValue::Uint(_)
+    )
+}
+
+// The instructions which are evaluated by the solver and may be replaced by a constant.
+fn is_foldable(instruction: &Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::AsmBlock(..)
+            | Instruction::Cmp(..)
+            | Instruction::ExtractValue { .. }
+            | Instruction::Phi(_)
+    )
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// The value of an instruction as it's known so far.  Values only move down the lattice, from
+// `Undefined` to `Constant` to `Overdefined`.
+#[derive(Clone, Debug)]
+enum Lattice {
+    // Not yet known to be executed with any value.
+    Undefined,
+    // Always has this scalar constant value.
+    Constant(Constant),
+    // May have more than one value, or a value which isn't tracked.
+    Overdefined,
+}
+
+impl Lattice {
+    fn meet(self, other: Lattice) -> Lattice {
+        match (self, other) {
+            (Lattice::Undefined, other) | (other, Lattice::Undefined) => other,
+            (Lattice::Constant(a), Lattice::Constant(b)) if same_constant(&a, &b) => {
+                Lattice::Constant(a)
+            }
+            _otherwise => Lattice::Overdefined,
+        }
+    }
+
+    fn is_same(&self, other: &Lattice) -> bool {
+        match (self, other) {
+            (Lattice::Undefined, Lattice::Undefined)
+            | (Lattice::Overdefined, Lattice::Overdefined) => true,
+            (Lattice::Constant(a), Lattice::Constant(b)) => same_constant(a, b),
+            _otherwise => false,
+        }
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            Lattice::Constant(Constant {
+                value: ConstantValue::Bool(b),
+                ..
+            }) => Some(*b),
+            _otherwise => None,
+        }
+    }
+
+    // The value as it would be held in a register.
+    fn as_word(&self) -> Option<u64> {
+        match self {
+            Lattice::Constant(Constant {
+                value: ConstantValue::Bool(b),
+                ..
+            }) => Some(*b as u64),
+            Lattice::Constant(Constant {
+                value: ConstantValue::Uint(n),
+                ..
+            }) => Some(*n),
+            _otherwise => None,
+        }
+    }
+}
+
+fn same_constant(a: &Constant, b: &Constant) -> bool {
+    match (&a.value, &b.value) {
+        (ConstantValue::Unit, ConstantValue::Unit) => true,
+        (ConstantValue::Bool(a), ConstantValue::Bool(b)) => a == b,
+        (ConstantValue::Uint(a_n), ConstantValue::Uint(b_n)) => {
+            a_n == b_n
+                && matches!((a.ty, b.ty), (Type::Uint(a_bits), Type::Uint(b_bits)) if a_bits == b_bits)
+        }
+        _otherwise => false,
+    }
+}
+
+struct Solver {
+    values: HashMap<Value, Lattice>,
+    executable_blocks: HashSet<Block>,
+    executable_edges: HashSet<(Block, Block)>,
+    // The blocks which use each instruction value, to be revisited when the value changes.
+    users: HashMap<Value, Vec<Block>>,
+    // ASM blocks which set the overflow register can't be removed if it's read.
+    reads_overflow: bool,
+}
+
+impl Solver {
+    fn new(context: &Context, function: &Function) -> Self {
+        let mut users: HashMap<Value, Vec<Block>> = HashMap::new();
+        let mut reads_overflow = false;
+        for (block, instr_val) in function.instruction_iter(context) {
+            if let ValueDatum::Instruction(instruction) = &context.values[instr_val.0].value {
+                for operand in instruction.get_operands() {
+                    users.entry(operand).or_default().push(block);
+                }
+                reads_overflow |= match instruction {
+                    Instruction::ReadRegister(Register::Of) => true,
+                    Instruction::AsmBlock(asm, _) => context.asm_blocks[asm.0]
+                        .body
+                        .iter()
+                        .any(|op| op.args.iter().any(|reg_name| reg_name.as_str() == "of")),
+                    _otherwise => false,
+                };
+            }
+        }
+        Solver {
+            values: HashMap::new(),
+            executable_blocks: HashSet::new(),
+            executable_edges: HashSet::new(),
+            users,
+            reads_overflow,
+        }
+    }
+
+    fn solve(mut self, context: &Context, function: &Function) -> Self {
+        let entry_block = function.get_entry_block(context);
+        self.executable_blocks.insert(entry_block);
+        let mut worklist = vec![entry_block];
+        while let Some(block) = worklist.pop() {
+            for instr_val in block.instruction_iter(context) {
+                let instruction = match &context.values[instr_val.0].value {
+                    ValueDatum::Instruction(instruction) => instruction,
+                    _otherwise => continue,
+                };
+
+                // Mark the edges out of a terminator which may be taken.
+                let succs = match instruction {
+                    Instruction::Branch(to_block) => vec![*to_block],
+                    Instruction::ConditionalBranch {
+                        cond_value,
+                        true_block,
+                        false_block,
+                    } => match self.lattice(context, cond_value) {
+                        Lattice::Undefined => vec![],
+                        lattice => match lattice.as_bool() {
+                            Some(true) => vec![*true_block],
+                            Some(false) => vec![*false_block],
+                            None => vec![*true_block, *false_block],
+                        },
+                    },
+                    _otherwise => vec![],
+                };
+                for succ in succs {
+                    if self.executable_edges.insert((block, succ)) {
+                        // The successor's phi needs revisiting even if it was already executable.
+                        self.executable_blocks.insert(succ);
+                        worklist.push(succ);
+                    }
+                }
+
+                let old = self
+                    .values
+                    .get(&instr_val)
+                    .cloned()
+                    .unwrap_or(Lattice::Undefined);
+                let new = old
+                    .clone()
+                    .meet(self.evaluate(context, &block, instruction));
+                if !new.is_same(&old) {
+                    self.values.insert(instr_val, new);
+                    worklist.extend(
+                        self.users
+                            .get(&instr_val)
+                            .into_iter()
+                            .flatten()
+                            .filter(|user| self.executable_blocks.contains(user)),
+                    );
+                }
+            }
+        }
+        self
+    }
+
+    fn lattice(&self, context: &Context, value: &Value) -> Lattice {
+        match &context.values[value.0].value {
+            ValueDatum::Constant(constant) if is_scalar(constant) => {
+                Lattice::Constant(constant.clone())
+            }
+            ValueDatum::Instruction(_) => self
+                .values
+                .get(value)
+                .cloned()
+                .unwrap_or(Lattice::Undefined),
+            _otherwise => Lattice::Overdefined,
+        }
+    }
+
+    fn evaluate(&self, context: &Context, block: &Block, instruction: &Instruction) -> Lattice {
+        match instruction {
+            Instruction::Phi(pairs) => pairs
+                .iter()
+                .filter(|(from_block, _)| self.executable_edges.contains(&(*from_block, *block)))
+                .fold(Lattice::Undefined, |acc, (_, value)| {
+                    acc.meet(self.lattice(context, value))
+                }),
+            Instruction::Cmp(Predicate::Equal, lhs_val, rhs_val) => {
+                match (
+                    self.lattice(context, lhs_val),
+                    self.lattice(context, rhs_val),
+                ) {
+                    (Lattice::Constant(lhs), Lattice::Constant(rhs)) => {
+                        Lattice::Constant(Constant::new_bool(same_constant(&lhs, &rhs)))
+                    }
+                    (Lattice::Overdefined, _) | (_, Lattice::Overdefined) => Lattice::Overdefined,
+                    _otherwise => Lattice::Undefined,
+                }
+            }
+            Instruction::ExtractValue {
+                aggregate, indices, ..
+            } => match &context.values[aggregate.0].value {
+                ValueDatum::Constant(constant) => indices
+                    .iter()
+                    .try_fold(constant, |constant, idx| match &constant.value {
+                        ConstantValue::Struct(fields) => fields.get(*idx as usize),
+                        _otherwise => None,
+                    })
+                    .filter(|field| is_scalar(field))
+                    .map(|field| Lattice::Constant(field.clone()))
+                    .unwrap_or(Lattice::Overdefined),
+                _otherwise => Lattice::Overdefined,
+            },
+            Instruction::AsmBlock(asm, args) => self.evaluate_asm_block(context, asm, args),
+            _otherwise => Lattice::Overdefined,
+        }
+    }
+
+    fn evaluate_asm_block(&self, context: &Context, asm: &AsmBlock, args: &[AsmArg]) -> Lattice {
+        if self.reads_overflow {
+            return Lattice::Overdefined;
+        }
+
+        let mut registers = HashMap::new();
+        for AsmArg { name, initializer } in args {
+            if let Some(init_val) = initializer {
+                match self.lattice(context, init_val) {
+                    Lattice::Undefined => return Lattice::Undefined,
+                    lattice => match lattice.as_word() {
+                        Some(word) => {
+                            registers.insert(name.as_str(), word);
+                        }
+                        None => return Lattice::Overdefined,
+                    },
+                }
+            }
+        }
+
+        let asm_content = &context.asm_blocks[asm.0];
+        let result = asm_content
+            .body
+            .iter()
+            .try_for_each(|op| {
+                let reg = |idx: usize| match op.args.get(idx)?.as_str() {
+                    "zero" => Some(0),
+                    "one" => Some(1),
+                    reg_name => registers.get(reg_name).copied(),
+                };
+                let imm = || {
+                    op.immediate
+                        .as_ref()
+                        .and_then(|imm| imm.as_str().get(1..))
+                        .and_then(|imm| imm.parse::<u64>().ok())
+                };
+                let result = match op.name.as_str() {
+                    "add" => reg(1)?.wrapping_add(reg(2)?),
+                    "addi" => reg(1)?.wrapping_add(imm()?),
+                    "sub" => reg(1)?.wrapping_sub(reg(2)?),
+                    "subi" => reg(1)?.wrapping_sub(imm()?),
+                    "mul" => reg(1)?.wrapping_mul(reg(2)?),
+                    "muli" => reg(1)?.wrapping_mul(imm()?),
+                    "exp" => exp(reg(1)?, reg(2)?),
+                    "expi" => exp(reg(1)?, imm()?),
+                    "div" => reg(1)?.checked_div(reg(2)?)?,
+                    "divi" => reg(1)?.checked_div(imm()?)?,
+                    "mod" => reg(1)?.checked_rem(reg(2)?)?,
+                    "modi" => reg(1)?.checked_rem(imm()?)?,
+                    "eq" => (reg(1)? == reg(2)?) as u64,
+                    "gt" => (reg(1)? > reg(2)?) as u64,
+                    "lt" => (reg(1)? < reg(2)?) as u64,
+                    "and" => reg(1)? & reg(2)?,
+                    "andi" => reg(1)? & imm()?,
+                    "or" => reg(1)? | reg(2)?,
+                    "ori" => reg(1)? | imm()?,
+                    "xor" => reg(1)? ^ reg(2)?,
+                    "xori" => reg(1)? ^ imm()?,
+                    "not" => !reg(1)?,
+                    "sll" => reg(1)?
+                        .checked_shl(u32::try_from(reg(2)?).ok()?)
+                        .unwrap_or(0),
+                    "slli" => reg(1)?.checked_shl(imm()? as u32).unwrap_or(0),
+                    "srl" => reg(1)?
+                        .checked_shr(u32::try_from(reg(2)?).ok()?)
+                        .unwrap_or(0),
+                    "srli" => reg(1)?.checked_shr(imm()? as u32).unwrap_or(0),
+                    "move" => reg(1)?,
+                    "movi" => imm()?,
+                    "noop" => return Some(()),
+                    _otherwise => return None,
+                };
+                match op.args.first()?.as_str() {
+                    "zero" | "one" => None,
+                    dst_name => {
+                        registers.insert(dst_name, result);
+                        Some(())
+                    }
+                }
+            })
+            .and_then(|()| {
+                let ret_name = asm_content.return_name.as_ref()?;
+                registers.get(ret_name.as_str()).copied()
+            });
+
+        match (result, asm_content.return_type) {
+            (Some(word), Type::Bool) if word <= 1 => {
+                Lattice::Constant(Constant::new_bool(word == 1))
+            }
+            (Some(word), Type::Uint(nbits)) if nbits == 64 || word >> nbits == 0 => {
+                Lattice::Constant(Constant::new_uint(nbits, word))
+            }
+            _otherwise => Lattice::Overdefined,
+        }
+    }
+}
+
+// Matches the VM, which has a zero result for an exponent which overflows.
+fn exp(base: u64, exponent: u64) -> u64 {
+    u32::try_from(exponent)
+        .ok()
+        .and_then(|exponent| base.checked_pow(exponent))
+        .unwrap_or(0)
+}
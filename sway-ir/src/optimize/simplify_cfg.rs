@@ -1,8 +1,9 @@
 //! ## Simplify Control Flow Graph
 //!
 //! The optimizations here aim to reduce the complexity in control flow by removing basic blocks.
-//! This may be done by removing 'dead' blocks which are no longer called (or in other words, have
-//! no predecessors) or by merging blocks which are linked by a single unconditional branch.
+//! This may be done by removing 'dead' blocks which are no longer called (or in other words, are
+//! unreachable from the entry block) or by merging blocks which are linked by a single
+//! unconditional branch.
 //!
 //! Removing blocks will make the IR neater and more efficient but will also remove indirection of
 //! data flow via PHI instructions which in turn can make analyses for passes like constant folding
@@ -18,11 +19,13 @@ pub fn simplify_cfg(context: &mut Context, function: &Function) -> Result<bool,
     loop {
         if remove_dead_blocks(context, function)? {
             modified = true;
+            function.invalidate_analyses(context);
             continue;
         }
 
         if merge_blocks(context, function)? {
             modified = true;
+            function.invalidate_analyses(context);
             continue;
         }
 
@@ -33,14 +36,22 @@ pub fn simplify_cfg(context: &mut Context, function: &Function) -> Result<bool,
 }
 
 fn remove_dead_blocks(context: &mut Context, function: &Function) -> Result<bool, IrError> {
-    // Find a block other than 'entry' which has no predecessors, remove it if found.
-    function
+    // Find every block which isn't reachable from 'entry', including cycles of blocks which only
+    // branch to each other, and remove them.
+    let dom_tree = function.dominator_tree(context);
+    let preds = function.predecessor_map(context);
+    let dead_blocks = function
         .block_iter(context)
-        .skip(1)
-        .find(|block| block.num_predecessors(context) == 0)
-        .map(|dead_block| function.remove_block(context, &dead_block))
-        .transpose()
-        .map(|result| result.is_some())
+        .filter(|block| !dom_tree.contains(block))
+        .collect::<Vec<_>>();
+    for dead_block in &dead_blocks {
+        // Any phi in a live successor mustn't refer to the dead block.
+        for succ in preds.successors(dead_block) {
+            succ.remove_phi_val_coming_from(context, dead_block);
+        }
+        function.remove_block(context, dead_block)?;
+    }
+    Ok(!dead_blocks.is_empty())
 }
 
 fn merge_blocks(context: &mut Context, function: &Function) -> Result<bool, IrError> {
@@ -56,10 +67,10 @@ fn merge_blocks(context: &mut Context, function: &Function) -> Result<bool, IrEr
     };
 
     // Check whether a pair of blocks are singly paired.  i.e., `from_block` is the only
-    // predecessor of `to_block`.
+    // predecessor of `to_block`, and isn't `to_block` itself.
+    let preds = function.predecessor_map(context);
     let are_uniquely_paired = |(from_block, to_block): &(Block, Block)| -> bool {
-        let mut preds = context.blocks[to_block.0].predecessors(context);
-        preds.next() == Some(*from_block) && preds.next().is_none()
+        from_block != to_block && preds.predecessors(to_block) == [*from_block]
     };
 
     // Find a block with an unconditional branch terminator which branches to a block with that
@@ -138,10 +149,7 @@ fn merge_blocks(context: &mut Context, function: &Function) -> Result<bool, IrEr
 
     // Adjust the successors to the final `to_block` to now be successors of the fully merged
     // `from_block`.
-    let succs = context.blocks[final_to_block.0]
-        .successors(context)
-        .collect::<Vec<_>>();
-    for succ in succs {
+    for succ in preds.successors(&final_to_block) {
         succ.update_phi_source_block(context, final_to_block, from_block)
     }
 
@@ -126,6 +126,7 @@ mod ir_builder {
                 / op_asm()
                 / op_branch()
                 / op_bitcast()
+                / op_call_indirect()
                 / op_call()
                 / op_cbr()
                 / op_cmp()
@@ -133,6 +134,7 @@ mod ir_builder {
                 / op_contract_call()
                 / op_extract_element()
                 / op_extract_value()
+                / op_fn_addr()
                 / op_get_storage_key()
                 / op_get_ptr()
                 / op_gtf()
@@ -183,6 +185,11 @@ mod ir_builder {
                     IrAstOperation::Call(callee, args)
             }
 
+            rule op_call_indirect() -> IrAstOperation
+                = "call_indirect" _ ty:ast_ty() callee:id() "(" _ args:(id() ** comma()) ")" _ {
+                    IrAstOperation::CallIndirect(ty, callee, args)
+            }
+
             rule op_cbr() -> IrAstOperation
                 = "cbr" _ cond:id() comma() tblock:id() comma() fblock:id() {
                     IrAstOperation::Cbr(cond, tblock, fblock)
@@ -215,6 +222,11 @@ mod ir_builder {
                     IrAstOperation::ExtractValue(name, ty, idcs)
                 }
 
+            rule op_fn_addr() -> IrAstOperation
+                = "fn_addr" _ callee:id() {
+                    IrAstOperation::FnAddr(callee)
+                }
+
             rule op_get_storage_key() -> IrAstOperation
                 = "get_storage_key" _ {
                     IrAstOperation::GetStorageKey()
@@ -599,12 +611,14 @@ mod ir_builder {
         BitCast(String, IrAstTy),
         Br(String),
         Call(String, Vec<String>),
+        CallIndirect(IrAstTy, String, Vec<String>),
         Cbr(String, String, String),
         Cmp(String, String, String),
         Const(IrAstTy, IrAstConst),
         ContractCall(IrAstTy, String, String, String, String, String),
         ExtractElement(String, IrAstTy, String),
         ExtractValue(String, IrAstTy, Vec<u64>),
+        FnAddr(String),
         GetStorageKey(),
         GetPtr(String, IrAstTy, u64),
         Gtf(String, u64),
@@ -858,8 +872,21 @@ mod ir_builder {
                 )
             }));
 
+            // A phi may refer to values which are defined in later blocks, e.g., in a loop, so its
+            // incoming values are only added once every block has been built.
+            let mut pending_phis = Vec::new();
             for block in fn_decl.blocks {
-                self.add_block_instructions(context, block, &named_blocks, &ptr_map, &mut arg_map);
+                self.add_block_instructions(
+                    context,
+                    block,
+                    &named_blocks,
+                    &ptr_map,
+                    &mut arg_map,
+                    &mut pending_phis,
+                );
+            }
+            for (block, from_block, val_name) in pending_phis {
+                block.add_phi(context, from_block, *arg_map.get(&val_name).unwrap());
             }
             Ok(())
         }
@@ -871,6 +898,7 @@ mod ir_builder {
             named_blocks: &HashMap<String, Block>,
             ptr_map: &HashMap<String, Pointer>,
             val_map: &mut HashMap<String, Value>,
+            pending_phis: &mut Vec<(Block, Block, String)>,
         ) {
             let block = named_blocks.get(&ir_block.label).unwrap();
             for ins in ir_block.instructions {
@@ -961,6 +989,21 @@ mod ir_builder {
                         self.unresolved_calls.push(PendingCall { call_val, callee });
                         call_val
                     }
+                    IrAstOperation::CallIndirect(ty, callee, args) => {
+                        let ir_ty = ty.to_ir_type(context);
+                        block
+                            .ins(context)
+                            .call_indirect(
+                                *val_map.get(&callee).unwrap(),
+                                &args
+                                    .iter()
+                                    .map(|arg_name| val_map.get(arg_name).unwrap())
+                                    .cloned()
+                                    .collect::<Vec<Value>>(),
+                                ir_ty,
+                            )
+                            .add_metadatum(context, opt_metadata)
+                    }
                     IrAstOperation::Cbr(cond_val_name, true_block_name, false_block_name) => block
                         .ins(context)
                         .conditional_branch(
@@ -1024,6 +1067,20 @@ mod ir_builder {
                             .extract_value(*val_map.get(&val).unwrap(), ir_ty, idcs)
                             .add_metadatum(context, opt_metadata)
                     }
+                    IrAstOperation::FnAddr(callee) => {
+                        // As with calls the function may not have been created yet, so use the
+                        // current function as a placeholder and resolve it in a second pass.
+                        let dummy_func = block.get_function(context);
+                        let fn_addr_val = block
+                            .ins(context)
+                            .fn_addr(dummy_func)
+                            .add_metadatum(context, opt_metadata);
+                        self.unresolved_calls.push(PendingCall {
+                            call_val: fn_addr_val,
+                            callee,
+                        });
+                        fn_addr_val
+                    }
                     IrAstOperation::GetStorageKey() => block
                         .ins(context)
                         .get_storage_key()
@@ -1077,11 +1134,11 @@ mod ir_builder {
                     IrAstOperation::Nop => block.ins(context).nop(),
                     IrAstOperation::Phi(pairs) => {
                         for (block_name, val_name) in pairs {
-                            block.add_phi(
-                                context,
+                            pending_phis.push((
+                                *block,
                                 *named_blocks.get(&block_name).unwrap(),
-                                *val_map.get(&val_name).unwrap(),
-                            );
+                                val_name,
+                            ));
                         }
                         block.get_phi(context)
                     }
@@ -1165,10 +1222,12 @@ mod ir_builder {
                     })
                     .unwrap();
 
-                if let Some(Instruction::Call(dummy_func, _args)) =
-                    pending_call.call_val.get_instruction_mut(context)
-                {
-                    *dummy_func = call_func;
+                match pending_call.call_val.get_instruction_mut(context) {
+                    Some(Instruction::Call(dummy_func, _args))
+                    | Some(Instruction::FnAddr(dummy_func)) => {
+                        *dummy_func = call_func;
+                    }
+                    _otherwise => (),
                 }
             }
             Ok(())
@@ -360,6 +360,32 @@ fn instruction_to_doc<'a>(
                     ))
                     .append(md_namer.md_idx_to_doc(context, metadata)),
                 )),
+            Instruction::CallIndirect {
+                callee,
+                args,
+                return_type,
+            } => args
+                .iter()
+                .fold(
+                    maybe_constant_to_doc(context, md_namer, namer, callee),
+                    |doc, arg_val| {
+                        doc.append(maybe_constant_to_doc(context, md_namer, namer, arg_val))
+                    },
+                )
+                .append(Doc::line(
+                    Doc::text(format!(
+                        "{} = call_indirect {} {}",
+                        namer.name(context, ins_value),
+                        return_type.as_string(context),
+                        namer.name(context, callee),
+                    ))
+                    .append(Doc::in_parens_comma_sep(
+                        args.iter()
+                            .map(|arg_val| Doc::text(namer.name(context, arg_val)))
+                            .collect(),
+                    ))
+                    .append(md_namer.md_idx_to_doc(context, metadata)),
+                )),
             Instruction::Cmp(pred, lhs_value, rhs_value) => {
                 let pred_str = match pred {
                     Predicate::Equal => "eq",
@@ -450,6 +476,14 @@ fn instruction_to_doc<'a>(
                 ))
                 .append(md_namer.md_idx_to_doc(context, metadata)),
             )),
+            Instruction::FnAddr(func) => Doc::line(
+                Doc::text(format!(
+                    "{} = fn_addr {}",
+                    namer.name(context, ins_value),
+                    context.functions[func.0].name
+                ))
+                .append(md_namer.md_idx_to_doc(context, metadata)),
+            ),
             Instruction::GetStorageKey => Doc::line(
                 Doc::text(format!(
                     "{} = get_storage_key",
@@ -134,6 +134,9 @@ impl<'a> InstructionVerifier<'a> {
                     Instruction::BitCast(value, ty) => self.verify_bitcast(value, ty)?,
                     Instruction::Branch(block) => self.verify_br(block)?,
                     Instruction::Call(func, args) => self.verify_call(func, args)?,
+                    Instruction::CallIndirect { callee, .. } => {
+                        self.verify_call_indirect(callee)?
+                    }
                     Instruction::Cmp(pred, lhs_value, rhs_value) => {
                         self.verify_cmp(pred, lhs_value, rhs_value)?
                     }
@@ -159,6 +162,7 @@ impl<'a> InstructionVerifier<'a> {
                         ty,
                         indices,
                     } => self.verify_extract_value(aggregate, ty, indices)?,
+                    Instruction::FnAddr(func) => self.verify_fn_addr(func)?,
                     Instruction::GetStorageKey => (),
                     Instruction::GetPointer {
                         base_ptr,
@@ -270,6 +274,30 @@ impl<'a> InstructionVerifier<'a> {
         }
     }
 
+    fn verify_call_indirect(&self, callee: &Value) -> Result<(), IrError> {
+        // The argument types can't be checked against the callee until it is resolved, which is
+        // done by the inliner when possible.
+        match callee.get_type(self.context) {
+            Some(Type::Uint(64)) => Ok(()),
+            Some(ty) => Err(IrError::VerifyCallIndirectNonIntCallee(
+                ty.as_string(self.context),
+            )),
+            None => Err(IrError::VerifyCallIndirectNonIntCallee(
+                "untyped".to_owned(),
+            )),
+        }
+    }
+
+    fn verify_fn_addr(&self, callee: &Function) -> Result<(), IrError> {
+        if !self.cur_module.functions.contains(callee) {
+            Err(IrError::VerifyCallToMissingFunction(
+                self.context.functions[callee.0].name.clone(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     fn verify_call(&self, callee: &Function, args: &[Value]) -> Result<(), IrError> {
         let callee_content = &self.context.functions[callee.0];
         if !self.cur_module.functions.contains(callee) {
@@ -1,27 +1,10 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // -------------------------------------------------------------------------------------------------
 // Utility for finding test files and running FileCheck.  See actual pass invocations below.
 
 fn run_tests<F: Fn(&mut sway_ir::Context) -> bool>(sub_dir: &str, opt_fn: F) {
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let dir: PathBuf = format!("{}/tests/{}", manifest_dir, sub_dir).into();
-    for entry in std::fs::read_dir(dir).unwrap() {
-        let path = entry.unwrap().path();
-
-        let input_bytes = std::fs::read(&path).unwrap();
-        let input = String::from_utf8_lossy(&input_bytes);
-
-        let chkr = filecheck::CheckerBuilder::new()
-            .text(&input)
-            .unwrap()
-            .finish();
-        assert!(
-            !chkr.is_empty(),
-            "No filecheck directives found in test: {}",
-            path.display()
-        );
-
+    for (path, input, chkr) in read_test_files(sub_dir) {
         let mut ir = sway_ir::parser::parse(&input).unwrap_or_else(|parse_err| {
             println!("{parse_err}");
             panic!()
@@ -39,18 +22,44 @@ fn run_tests<F: Fn(&mut sway_ir::Context) -> bool>(sub_dir: &str, opt_fn: F) {
         });
 
         let output = sway_ir::printer::to_string(&ir);
+        check_output(&chkr, &output, &path);
+    }
+}
+
+fn read_test_files(sub_dir: &str) -> impl Iterator<Item = (PathBuf, String, filecheck::Checker)> {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let dir: PathBuf = format!("{}/tests/{}", manifest_dir, sub_dir).into();
+    std::fs::read_dir(dir).unwrap().map(|entry| {
+        let path = entry.unwrap().path();
+
+        let input_bytes = std::fs::read(&path).unwrap();
+        let input = String::from_utf8_lossy(&input_bytes).to_string();
+
+        let chkr = filecheck::CheckerBuilder::new()
+            .text(&input)
+            .unwrap()
+            .finish();
+        assert!(
+            !chkr.is_empty(),
+            "No filecheck directives found in test: {}",
+            path.display()
+        );
+
+        (path, input, chkr)
+    })
+}
 
-        match chkr.explain(&output, filecheck::NO_VARIABLES) {
-            Ok((success, report)) if !success => {
-                println!("--- FILECHECK FAILED FOR {}", path.display());
-                println!("{report}");
-                panic!()
-            }
-            Err(e) => {
-                panic!("filecheck directive error while checking: {e}");
-            }
-            _ => (),
+fn check_output(chkr: &filecheck::Checker, output: &str, path: &Path) {
+    match chkr.explain(output, filecheck::NO_VARIABLES) {
+        Ok((success, report)) if !success => {
+            println!("--- FILECHECK FAILED FOR {}", path.display());
+            println!("{report}");
+            panic!()
         }
+        Err(e) => {
+            panic!("filecheck directive error while checking: {e}");
+        }
+        _ => (),
     }
 }
 
@@ -101,6 +110,69 @@ fn simplify_cfg() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[allow(clippy::needless_collect)]
+#[test]
+fn licm() {
+    run_tests("licm", |ir: &mut sway_ir::Context| {
+        let fn_idcs: Vec<_> = ir.functions.iter().map(|func| func.0).collect();
+        fn_idcs.into_iter().fold(false, |acc, fn_idx| {
+            sway_ir::optimize::hoist_loop_invariants(ir, &sway_ir::function::Function(fn_idx))
+                .unwrap()
+                || acc
+        })
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn indvars() {
+    run_tests("indvars", |ir: &mut sway_ir::Context| {
+        let fn_idcs: Vec<_> = ir.functions.iter().map(|func| func.0).collect();
+        fn_idcs.into_iter().fold(false, |acc, fn_idx| {
+            sway_ir::optimize::simplify_induction_variables(
+                ir,
+                &sway_ir::function::Function(fn_idx),
+            )
+            .unwrap()
+                || acc
+        })
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn sccp() {
+    run_tests("sccp", |ir: &mut sway_ir::Context| {
+        let fn_idcs: Vec<_> = ir.functions.iter().map(|func| func.0).collect();
+        fn_idcs.into_iter().fold(false, |acc, fn_idx| {
+            sway_ir::optimize::propagate_constants(ir, &sway_ir::function::Function(fn_idx))
+                .unwrap()
+                || acc
+        })
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn specialize() {
+    run_tests("specialize", |ir: &mut sway_ir::Context| {
+        let fn_idcs: Vec<_> = ir.functions.iter().map(|func| func.0).collect();
+        fn_idcs.into_iter().fold(false, |acc, fn_idx| {
+            sway_ir::optimize::specialize_constant_calls(ir, &sway_ir::function::Function(fn_idx))
+                .unwrap()
+                || acc
+        })
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[test]
 fn serialize() {
     // This isn't running a pass, it's just confirming that the IR can be loaded and printed, and
@@ -109,3 +181,30 @@ fn serialize() {
 }
 
 // -------------------------------------------------------------------------------------------------
+
+#[test]
+fn interpret() {
+    // Rather than running a pass, run `main()` and check its outcome.
+    for (path, input, chkr) in read_test_files("interpret") {
+        let ir = sway_ir::parser::parse(&input).unwrap_or_else(|parse_err| {
+            println!("{parse_err}");
+            panic!()
+        });
+        let main_fn = ir
+            .functions
+            .iter()
+            .find_map(|(idx, fc)| if fc.name == "main" { Some(idx) } else { None })
+            .unwrap();
+
+        let outcome = sway_ir::interpreter::Interpreter::new(&ir)
+            .execute(&sway_ir::function::Function(main_fn), &[])
+            .unwrap_or_else(|err| {
+                println!("{err}");
+                panic!("Failed to interpret {}.", path.display());
+            });
+
+        check_output(&chkr, &outcome.to_string(), &path);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
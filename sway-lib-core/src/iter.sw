@@ -0,0 +1,40 @@
+library iter;
+
+use ::ops::*;
+
+/// A sequence of values which can be iterated over with a `for` loop.
+///
+/// A loop such as `for value in iterator { ... }` is equivalent to:
+///
+/// ```
+/// let mut iterator = iterator;
+/// while iterator.has_next() {
+///     let value = iterator.next();
+///     ...
+/// }
+/// ```
+pub trait Iterator {
+    /// The type of the values in the sequence.
+    type Item;
+
+    /// Returns `true` if there are values remaining in the sequence.
+    fn has_next(self) -> bool;
+
+    /// Returns the next value in the sequence and advances past it. It is only called when
+    /// `has_next` returns `true`.
+    fn next(mut self) -> Self::Item;
+}
+
+impl Iterator for Range {
+    type Item = u64;
+
+    fn has_next(self) -> bool {
+        self.start.lt(self.end)
+    }
+
+    fn next(mut self) -> u64 {
+        let value = self.start;
+        self.start = self.start.add(1);
+        value
+    }
+}
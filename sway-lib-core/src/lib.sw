@@ -2,3 +2,4 @@ library core;
 
 dep num;
 dep ops;
+dep iter;
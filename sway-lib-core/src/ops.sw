@@ -663,3 +663,10 @@ fn decompose(val: b256) -> (u64, u64, u64, u64) {
     let w4 = get_word_from_b256(val, 24);
     (w1, w2, w3, w4)
 }
+
+/// A half-open range of `u64` values, `start..end`, which contains every value from `start`
+/// up to but excluding `end`.
+pub struct Range {
+    start: u64,
+    end: u64,
+}
@@ -4,7 +4,6 @@ use ::assert::assert;
 use ::context::registers::stack_ptr;
 use ::hash::sha256;
 use ::option::Option;
-use ::result::Result;
 
 /// Store a stack variable in storage.
 #[storage(write)]pub fn store<T>(key: b256, value: T) {
@@ -316,4 +315,37 @@ impl<V> StorageVec<V> {
     #[storage(write)]pub fn clear(self) {
         store(__get_storage_key(), 0);
     }
+
+    /// Returns an iterator over the elements of the vector, for use in a `for` loop.
+    /// Elements pushed to the vector after the iterator is created are not visited.
+    #[storage(read)]pub fn iter(self) -> StorageVecIter<V> {
+        StorageVecIter {
+            key: __get_storage_key(),
+            index: 0,
+            len: get::<u64>(__get_storage_key()),
+        }
+    }
+}
+
+/// An iterator over the elements of a `StorageVec`, created by `StorageVec::iter`.
+pub struct StorageVecIter<V> {
+    key: b256,
+    index: u64,
+    len: u64,
+}
+
+/// `StorageVecIter` cannot implement `Iterator`, whose `next` method may not read from storage,
+/// but a `for` loop only needs `has_next` and `next` methods with the same signatures.
+impl<V> StorageVecIter<V> {
+    /// Returns `true` if there are elements of the vector which have not been visited.
+    pub fn has_next(self) -> bool {
+        self.index < self.len
+    }
+
+    /// Returns the next element of the vector. It is only called when `has_next` returns `true`.
+    #[storage(read)]pub fn next(mut self) -> V {
+        let key = sha256((self.index, self.key));
+        self.index = self.index + 1;
+        get::<V>(key)
+    }
 }
@@ -4,8 +4,6 @@ library tx;
 
 use ::address::Address;
 use ::context::registers::instrs_start;
-use ::contract_id::ContractId;
-use ::intrinsics::is_reference_type;
 use ::mem::read;
 use ::option::Option;
 
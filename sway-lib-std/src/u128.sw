@@ -17,16 +17,18 @@ pub enum U128Error {
     LossOfPrecision: (),
 }
 
-pub trait From {
-    /// Function for creating U128 from its u64 components.
-    fn from(upper: u64, lower: u64) -> Self;
-    fn into(self) -> (u64, u64);
+pub trait From<T> {
+    /// Function for creating a value from a `T`.
+    fn from(value: T) -> Self;
+    fn into(self) -> T;
 }
 
-impl From for U128 {
-    fn from(upper: u64, lower: u64) -> U128 {
+/// Creates a U128 from its u64 components, `(upper, lower)`.
+impl From<(u64, u64)> for U128 {
+    fn from(components: (u64, u64)) -> U128 {
         U128 {
-            upper, lower, 
+            upper: components.0,
+            lower: components.1,
         }
     }
 
@@ -146,13 +148,13 @@ impl U128 {
 
 impl core::ops::BitwiseAnd for U128 {
     fn binary_and(self, other: Self) -> Self {
-        ~U128::from(self.upper & other.upper, self.lower & other.lower)
+        ~U128::from((self.upper & other.upper, self.lower & other.lower))
     }
 }
 
 impl core::ops::BitwiseOr for U128 {
     fn binary_or(self, other: Self) -> Self {
-        ~U128::from(self.upper | other.upper, self.lower | other.lower)
+        ~U128::from((self.upper | other.upper, self.lower | other.lower))
     }
 }
 
@@ -259,8 +261,8 @@ impl core::ops::Subtract for U128 {
 impl core::ops::Multiply for U128 {
     /// Multiply a U128 with a U128. Panics of overflow.
     fn multiply(self, other: Self) -> Self {
-        let zero = ~U128::from(0, 0);
-        let one = ~U128::from(0, 1);
+        let zero = ~U128::from((0, 0));
+        let one = ~U128::from((0, 1));
 
         let mut total = ~U128::new();
         let mut i = 128 - 1;
@@ -284,8 +286,8 @@ impl core::ops::Multiply for U128 {
 impl core::ops::Divide for U128 {
     /// Divide a U128 by a U128. Panics if divisor is zero.
     fn divide(self, divisor: Self) -> Self {
-        let zero = ~U128::from(0, 0);
-        let one = ~U128::from(0, 1);
+        let zero = ~U128::from((0, 0));
+        let one = ~U128::from((0, 1));
 
         assert(divisor != zero);
 
@@ -5,6 +5,7 @@ use ::assert::assert;
 use ::intrinsics::size_of;
 use ::mem::{copy, read, write};
 use ::option::Option;
+use core::iter::Iterator;
 
 struct RawVec<T> {
     ptr: u64,
@@ -143,6 +144,14 @@ impl<T> Vec<T> {
         self.len == 0
     }
 
+    /// Returns an iterator over the elements of the vector, for use in a `for` loop.
+    pub fn iter(self) -> VecIter<T> {
+        VecIter {
+            vec: self,
+            index: 0,
+        }
+    }
+
     /// Removes and returns the element at position `index` within the vector,
     /// shifting all elements after it to the left.
     /// Panics if `index >= self.len`
@@ -237,3 +246,23 @@ impl<T> Vec<T> {
         write(element2_ptr, element1_val);
     }
 }
+
+/// An iterator over the elements of a `Vec`, created by `Vec::iter`.
+pub struct VecIter<T> {
+    vec: Vec<T>,
+    index: u64,
+}
+
+impl<T> Iterator for VecIter<T> {
+    type Item = T;
+
+    fn has_next(self) -> bool {
+        self.index < self.vec.len()
+    }
+
+    fn next(mut self) -> T {
+        let value = self.vec.get(self.index).unwrap();
+        self.index = self.index + 1;
+        value
+    }
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use sway_core::CompileWarning;
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionResponse,
+    TextEdit, WorkspaceEdit,
+};
+
+use crate::utils::common::get_range_from_span;
+
+/// A fix suggested by the compiler, carried in the `data` of the diagnostic it addresses so that
+/// it can be offered as a quick fix without compiling the document again.
+#[derive(Debug, Deserialize, Serialize)]
+struct QuickFix {
+    title: String,
+    edit: TextEdit,
+}
+
+/// The `data` of the diagnostic of `warning`, if the compiler suggests any fixes for it.
+pub(crate) fn quick_fixes(warning: &CompileWarning) -> Option<serde_json::Value> {
+    let fixes: Vec<_> = warning
+        .diagnostic()
+        .suggestions
+        .into_iter()
+        .map(|suggestion| QuickFix {
+            title: suggestion.message,
+            edit: TextEdit::new(
+                get_range_from_span(&suggestion.span),
+                suggestion.replacement,
+            ),
+        })
+        .collect();
+    if fixes.is_empty() {
+        return None;
+    }
+    serde_json::to_value(fixes).ok()
+}
+
+pub fn code_actions(params: CodeActionParams) -> Option<CodeActionResponse> {
+    let url = params.text_document.uri;
+
+    let mut actions = Vec::new();
+    for diagnostic in params.context.diagnostics {
+        let fixes: Vec<QuickFix> = match diagnostic.data.clone() {
+            Some(data) => serde_json::from_value(data).unwrap_or_default(),
+            None => continue,
+        };
+        for fix in fixes {
+            let mut map_of_changes = HashMap::new();
+            map_of_changes.insert(url.clone(), vec![fix.edit]);
+
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: fix.title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(WorkspaceEdit::new(map_of_changes)),
+                ..Default::default()
+            }));
+        }
+    }
+
+    if actions.is_empty() {
+        None
+    } else {
+        Some(actions)
+    }
+}
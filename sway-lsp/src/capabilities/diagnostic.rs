@@ -1,7 +1,9 @@
-use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
 
 use sway_core::{CompileError, CompileWarning};
 
+use crate::capabilities::code_actions::quick_fixes;
+
 pub fn get_diagnostics(
     warnings: Vec<CompileWarning>,
     errors: Vec<CompileError>,
@@ -13,6 +15,7 @@ pub fn get_diagnostics(
             Diagnostic {
                 range,
                 severity: Some(DiagnosticSeverity::ERROR),
+                code: Some(NumberOrString::String(error.code().to_string())),
                 message: format!("{}", error),
                 ..Default::default()
             }
@@ -26,7 +29,11 @@ pub fn get_diagnostics(
             Diagnostic {
                 range,
                 severity: Some(DiagnosticSeverity::WARNING),
+                code: Some(NumberOrString::String(
+                    warning.warning_content.code().to_string(),
+                )),
                 message: warning.to_friendly_warning_string(),
+                data: quick_fixes(warning),
                 ..Default::default()
             }
         })
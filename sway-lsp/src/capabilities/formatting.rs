@@ -1,5 +1,7 @@
-use std::sync::Arc;
+use ropey::Rope;
+use std::{path::Path, sync::Arc};
 use sway_fmt::{get_formatted_data, FormattingOptions};
+use sway_fmt_v2::Formatter;
 use tower_lsp::lsp_types::{Position, Range, TextEdit};
 
 pub fn get_format_text_edits(text: Arc<str>, options: FormattingOptions) -> Option<Vec<TextEdit>> {
@@ -20,3 +22,45 @@ pub fn get_format_text_edits(text: Arc<str>, options: FormattingOptions) -> Opti
         _ => None,
     }
 }
+
+/// Formats the lines of `text` which touch `range`, using the `swayfmt.toml` of the project at
+/// `path` if there is one. An empty range formats the line it is on.
+pub fn get_range_format_text_edits(
+    text: Arc<str>,
+    path: &Path,
+    range: Range,
+) -> Option<Vec<TextEdit>> {
+    let mut formatter = Formatter::from_dir(path.parent()?).ok()?;
+    let rope = Rope::from_str(&text);
+    let byte_range = position_to_byte(&rope, range.start)..position_to_byte(&rope, range.end);
+    // we only format if code is correct
+    let edits = formatter.format_range(text, None, byte_range).ok()?;
+    let edits = edits
+        .into_iter()
+        .map(|edit| TextEdit {
+            range: Range::new(
+                byte_to_position(&rope, edit.range.start),
+                byte_to_position(&rope, edit.range.end),
+            ),
+            new_text: edit.new_text,
+        })
+        .collect();
+    Some(edits)
+}
+
+fn position_to_byte(rope: &Rope, position: Position) -> usize {
+    let line_char_index = rope.line_to_char((position.line as usize).min(rope.len_lines()));
+    let utf16_cu_index = rope.char_to_utf16_cu(line_char_index) + position.character as usize;
+    let char_index = rope.utf16_cu_to_char(utf16_cu_index.min(rope.len_utf16_cu()));
+    rope.char_to_byte(char_index)
+}
+
+fn byte_to_position(rope: &Rope, byte_index: usize) -> Position {
+    let line_index = rope.byte_to_line(byte_index);
+    let line_utf16_cu_index = rope.char_to_utf16_cu(rope.line_to_char(line_index));
+    let utf16_cu_index = rope.char_to_utf16_cu(rope.byte_to_char(byte_index));
+    Position::new(
+        line_index as u32,
+        (utf16_cu_index - line_utf16_cu_index) as u32,
+    )
+}
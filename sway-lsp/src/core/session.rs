@@ -1,7 +1,7 @@
 use crate::{
     capabilities::{
         self,
-        formatting::get_format_text_edits,
+        formatting::{get_format_text_edits, get_range_format_text_edits},
         runnable::{Runnable, RunnableType},
     },
     core::{
@@ -16,7 +16,7 @@ use dashmap::DashMap;
 use forc_pkg::{self as pkg};
 use serde_json::Value;
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, LockResult, RwLock},
 };
 use sway_core::{CompileAstResult, CompileResult, ParseProgram, TypeInfo, TypedProgramKind};
@@ -166,12 +166,29 @@ impl Session {
             if let Ok(plan) = pkg::BuildPlan::from_lock_and_manifest(&manifest, locked, offline) {
                 //we can then use them directly to convert them to a Vec<Diagnostic>
                 if let Ok((parsed_res, ast_res)) = pkg::check(&plan, silent_mode) {
+                    let syntax_errors = parsed_res.errors.clone();
                     // First, populate our token_map with un-typed ast nodes
-                    let _ = self.parse_ast_to_tokens(parsed_res);
-                    // Next, populate our token_map with typed ast nodes
-                    let res = self.parse_ast_to_typed_tokens(ast_res);
+                    let parse_res = self.parse_ast_to_tokens(parsed_res);
+                    if parse_res.is_err() {
+                        return parse_res;
+                    }
+                    // Next, populate our token_map with typed ast nodes. The parts of a program
+                    // which parsed are type checked even if the parser skipped over others, and
+                    // the syntax errors are reported to the client along with the type errors.
+                    let mut diagnostics =
+                        capabilities::diagnostic::get_diagnostics(vec![], syntax_errors);
                     //self.test_typed_parse(ast_res);
-                    return res;
+                    return match self.parse_ast_to_typed_tokens(ast_res) {
+                        Ok(typed_diagnostics) => {
+                            diagnostics.extend(typed_diagnostics);
+                            Ok(diagnostics)
+                        }
+                        Err(DocumentError::FailedToParse(typed_diagnostics)) => {
+                            diagnostics.extend(typed_diagnostics);
+                            Err(DocumentError::FailedToParse(diagnostics))
+                        }
+                        Err(error) => Err(error),
+                    };
                 }
             }
         }
@@ -362,4 +379,9 @@ impl Session {
             None
         }
     }
+
+    pub fn format_range(&self, url: &Url, range: Range) -> Option<Vec<TextEdit>> {
+        let document = self.documents.get(url.path())?;
+        get_range_format_text_edits(Arc::from(document.get_text()), Path::new(url.path()), range)
+    }
 }
@@ -359,6 +359,21 @@ fn handle_expression(expression: &TypedExpression, tokens: &TokenMap) {
                 token.typed = Some(TypedAstToken::TypedExpression(expression.clone()));
             }
         }
+        TypedExpressionVariant::FunctionReference {
+            call_path,
+            function_decl,
+        } => {
+            if let Some(mut token) = tokens.get_mut(&to_ident_key(&call_path.suffix)) {
+                token.typed = Some(TypedAstToken::TypedExpression(expression.clone()));
+                token.type_def = Some(TypeDefinition::Ident(function_decl.name.clone()));
+            }
+        }
+        TypedExpressionVariant::IndirectFunctionApplication { callee, arguments } => {
+            handle_expression(callee, tokens);
+            for exp in arguments {
+                handle_expression(exp, tokens);
+            }
+        }
     }
 }
 
@@ -68,7 +68,13 @@ fn capabilities() -> ServerCapabilities {
             ..Default::default()
         }),
         document_formatting_provider: Some(OneOf::Left(true)),
+        document_range_formatting_provider: Some(OneOf::Left(true)),
+        document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+            first_trigger_character: "}".to_string(),
+            more_trigger_character: Some(vec![";".to_string()]),
+        }),
         definition_provider: Some(OneOf::Left(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
         ..ServerCapabilities::default()
     }
 }
@@ -239,6 +245,29 @@ impl LanguageServer for Backend {
         Ok(self.session.format_text(&params.text_document.uri))
     }
 
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> jsonrpc::Result<Option<Vec<TextEdit>>> {
+        Ok(self
+            .session
+            .format_range(&params.text_document.uri, params.range))
+    }
+
+    async fn on_type_formatting(
+        &self,
+        params: DocumentOnTypeFormattingParams,
+    ) -> jsonrpc::Result<Option<Vec<TextEdit>>> {
+        // format the line which the character was typed on
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params.text_document_position;
+        Ok(self
+            .session
+            .format_range(&text_document.uri, Range::new(position, position)))
+    }
+
     async fn rename(&self, params: RenameParams) -> jsonrpc::Result<Option<WorkspaceEdit>> {
         Ok(capabilities::rename::rename(&self.session, params))
     }
@@ -249,6 +278,13 @@ impl LanguageServer for Backend {
     ) -> jsonrpc::Result<Option<PrepareRenameResponse>> {
         Ok(capabilities::rename::prepare_rename(&self.session, params))
     }
+
+    async fn code_action(
+        &self,
+        params: CodeActionParams,
+    ) -> jsonrpc::Result<Option<CodeActionResponse>> {
+        Ok(capabilities::code_actions::code_actions(params))
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -567,4 +603,141 @@ mod tests {
         // send "exit" request
         exit_notification(&mut service).await;
     }
+
+    #[tokio::test]
+    async fn code_action() {
+        let (mut service, _) = LspService::new(|client| Backend::new(client, config()));
+
+        // send "initialize" request
+        let _ = initialize_request(&mut service).await;
+
+        // send "initialized" notification
+        initialized_notification(&mut service).await;
+
+        let uri = Url::parse("inmemory:///test").unwrap();
+        let range = json!({
+            "start": { "line": 3, "character": 16 },
+            "end": { "line": 3, "character": 17 }
+        });
+        let edit = json!({ "range": range, "newText": "_x" });
+        let diagnostic = json!({
+            "range": range,
+            "severity": 2,
+            "code": "W0031",
+            "message": "Variable \"x\" is never used.",
+            "data": [{ "title": "rename to `_x`", "edit": edit }]
+        });
+
+        // send "textDocument/codeAction" request for the diagnostic
+        let params = json!({
+            "textDocument": { "uri": uri },
+            "range": range,
+            "context": { "diagnostics": [diagnostic] }
+        });
+        let code_action = Request::build("textDocument/codeAction")
+            .params(params)
+            .id(2)
+            .finish();
+        let response = service.ready().await.unwrap().call(code_action).await;
+        let ok = Response::from_ok(
+            2.into(),
+            json!([{
+                "title": "rename to `_x`",
+                "kind": "quickfix",
+                "diagnostics": [diagnostic],
+                "edit": { "changes": { uri.as_str(): [edit] } }
+            }]),
+        );
+        assert_eq!(response, Ok(Some(ok)));
+
+        // send "shutdown" request
+        let _ = shutdown_request(&mut service).await;
+
+        // send "exit" request
+        exit_notification(&mut service).await;
+    }
+
+    #[tokio::test]
+    async fn range_and_on_type_formatting() {
+        let (mut service, _) = LspService::new(|client| Backend::new(client, config()));
+
+        // send "initialize" request
+        let _ = initialize_request(&mut service).await;
+
+        // send "initialized" notification
+        initialized_notification(&mut service).await;
+
+        let dir = env::temp_dir().join("sway_lsp_range_formatting");
+        fs::create_dir_all(&dir).unwrap();
+        let src_path = dir.join("main.sw");
+        let text = r#"contract;
+
+struct Foo {
+    x: u64,
+}
+struct Bar { y:u64 }
+struct Baz {
+    z:bool,
+}"#;
+        fs::write(&src_path, text).unwrap();
+        let uri = Url::from_file_path(&src_path).unwrap();
+
+        // send "textDocument/didOpen" notification for `uri`
+        did_open_notification(&mut service, &uri, text).await;
+
+        // only the line of `Bar` is formatted, not `Baz`
+        let edits = json!([{
+            "range": {
+                "start": { "line": 5, "character": 0 },
+                "end": { "line": 6, "character": 0 }
+            },
+            "newText": "struct Bar {\n    y: u64,\n}\n"
+        }]);
+        let options = json!({ "tabSize": 4, "insertSpaces": true });
+
+        // send "textDocument/rangeFormatting" request for the line of `Bar`
+        let params = json!({
+            "textDocument": { "uri": uri },
+            "range": {
+                "start": { "line": 5, "character": 0 },
+                "end": { "line": 5, "character": 20 }
+            },
+            "options": options
+        });
+        let range_formatting = Request::build("textDocument/rangeFormatting")
+            .params(params)
+            .id(2)
+            .finish();
+        let response = service.ready().await.unwrap().call(range_formatting).await;
+        let ok = Response::from_ok(2.into(), edits.clone());
+        assert_eq!(response, Ok(Some(ok)));
+
+        // send "textDocument/onTypeFormatting" request for typing the `}` of `Bar`
+        let params = json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 5, "character": 20 },
+            "ch": "}",
+            "options": options
+        });
+        let on_type_formatting = Request::build("textDocument/onTypeFormatting")
+            .params(params)
+            .id(3)
+            .finish();
+        let response = service
+            .ready()
+            .await
+            .unwrap()
+            .call(on_type_formatting)
+            .await;
+        let ok = Response::from_ok(3.into(), edits);
+        assert_eq!(response, Ok(Some(ok)));
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        // send "shutdown" request
+        let _ = shutdown_request(&mut service).await;
+
+        // send "exit" request
+        exit_notification(&mut service).await;
+    }
 }
@@ -3,12 +3,15 @@ use crate::{Parse, ParseBracket, ParseErrorKind, ParseResult, ParseToEnd, Parser
 use sway_ast::attribute::{Annotated, Attribute, AttributeDecl};
 use sway_ast::brackets::Parens;
 use sway_ast::keywords::{HashToken, StorageToken};
+use sway_ast::token::DocStyle;
 use sway_types::Ident;
 
 impl<T: Parse> Parse for Annotated<T> {
     fn parse(parser: &mut Parser) -> ParseResult<Self> {
         let mut attribute_list = Vec::new();
         loop {
+            // Doc comments may be written before or between the other attributes.
+            attribute_list.append(&mut parser.doc_attributes(DocStyle::Outer));
             if parser.peek::<HashToken>().is_some() {
                 attribute_list.push(parser.parse()?);
             } else {
@@ -1,3 +1,4 @@
+use crate::item::at_item_start;
 use crate::{Parse, ParseBracket, ParseErrorKind, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
 use core::ops::ControlFlow;
@@ -5,7 +6,7 @@ use sway_ast::brackets::{Braces, Parens, SquareBrackets};
 use sway_ast::expr::{ReassignmentOp, ReassignmentOpVariant};
 use sway_ast::keywords::{
     AbiToken, AddEqToken, AsmToken, BreakToken, CommaToken, ConstToken, ContinueToken, DivEqToken,
-    DoubleColonToken, EnumToken, EqToken, FalseToken, FnToken, IfToken, ImplToken,
+    DoubleColonToken, EnumToken, EqToken, FalseToken, FnToken, IfToken, ImplToken, LetToken,
     OpenAngleBracketToken, PubToken, SemicolonToken, ShlEqToken, ShrEqToken, StarEqToken,
     StorageToken, StructToken, SubEqToken, TildeToken, TraitToken, TrueToken, UseToken,
 };
@@ -17,7 +18,7 @@ use sway_ast::{
     ExprTupleDescriptor, IfCondition, IfExpr, LitInt, Literal, MatchBranch, MatchBranchKind,
     Statement, StatementLet,
 };
-use sway_types::{Ident, Spanned};
+use sway_types::{Ident, Span, Spanned};
 
 mod asm;
 pub mod op_code;
@@ -107,73 +108,21 @@ impl ParseToEnd for CodeBlockContents {
             if let Some(consumed) = parser.check_empty() {
                 break (None, consumed);
             }
-            if parser.peek::<UseToken>().is_some()
-                || parser.peek::<StructToken>().is_some()
-                || parser.peek::<EnumToken>().is_some()
-                || parser.peek::<FnToken>().is_some()
-                || parser.peek::<PubToken>().is_some()
-                || parser.peek::<TraitToken>().is_some()
-                || parser.peek::<ImplToken>().is_some()
-                || parser.peek2::<AbiToken, Ident>().is_some()
-                || parser.peek::<ConstToken>().is_some()
-                || parser.peek::<BreakToken>().is_some()
-                || parser.peek::<ContinueToken>().is_some()
-                || matches!(
-                    parser.peek2::<StorageToken, Delimiter>(),
-                    Some((_, Delimiter::Brace))
-                )
-            {
-                let item = parser.parse()?;
-                let statement = Statement::Item(item);
-                statements.push(statement);
-                continue;
-            }
-            if let Some(let_token) = parser.take() {
-                let pattern = parser.parse()?;
-                let ty_opt = match parser.take() {
-                    Some(colon_token) => {
-                        let ty = parser.parse()?;
-                        Some((colon_token, ty))
-                    }
-                    None => None,
-                };
-                let eq_token = parser.parse()?;
-                let expr = parser.parse()?;
-                let semicolon_token = parser.parse()?;
-                let statement_let = StatementLet {
-                    let_token,
-                    pattern,
-                    ty_opt,
-                    eq_token,
-                    expr,
-                    semicolon_token,
-                };
-                let statement = Statement::Let(statement_let);
-                statements.push(statement);
-                continue;
-            }
-            let expr = parse_statement_expr(&mut parser)?;
-            if let Some(semicolon_token) = parser.take() {
-                let statement = Statement::Expr {
-                    expr,
-                    semicolon_token_opt: Some(semicolon_token),
-                };
-                statements.push(statement);
-                continue;
-            }
-            if let Some(consumed) = parser.check_empty() {
-                break (Some(Box::new(expr)), consumed);
-            }
-            if expr.is_control_flow() {
-                let statement = Statement::Expr {
-                    expr,
-                    semicolon_token_opt: None,
-                };
-                statements.push(statement);
-                continue;
+            // After a syntax error, skip to the end of the statement and keep going, so that the
+            // rest of the block is still parsed.
+            match parser.parse_or_recover(parse_statement, at_statement_boundary) {
+                Ok(StatementOrFinalExpr::Statement(statement)) => statements.push(statement),
+                Ok(StatementOrFinalExpr::FinalExpr(expr, consumed)) => {
+                    break (Some(Box::new(expr)), consumed);
+                }
+                Err(span) => {
+                    let span = match parser.take::<SemicolonToken>() {
+                        Some(semicolon_token) => Span::join(span, semicolon_token.span()),
+                        None => span,
+                    };
+                    statements.push(Statement::Error(span));
+                }
             }
-
-            return Err(parser.emit_error(ParseErrorKind::UnexpectedTokenInStatement));
         };
         let code_block_contents = CodeBlockContents {
             statements,
@@ -183,6 +132,84 @@ impl ParseToEnd for CodeBlockContents {
     }
 }
 
+enum StatementOrFinalExpr<'a> {
+    Statement(Statement),
+    FinalExpr(Expr, ParserConsumed<'a>),
+}
+
+fn parse_statement<'a>(parser: &mut Parser<'a, '_>) -> ParseResult<StatementOrFinalExpr<'a>> {
+    if parser.peek::<UseToken>().is_some()
+        || parser.peek::<StructToken>().is_some()
+        || parser.peek::<EnumToken>().is_some()
+        || parser.peek::<FnToken>().is_some()
+        || parser.peek::<PubToken>().is_some()
+        || parser.peek::<TraitToken>().is_some()
+        || parser.peek::<ImplToken>().is_some()
+        || parser.peek2::<AbiToken, Ident>().is_some()
+        || parser.peek::<ConstToken>().is_some()
+        || parser.peek::<BreakToken>().is_some()
+        || parser.peek::<ContinueToken>().is_some()
+        || matches!(
+            parser.peek2::<StorageToken, Delimiter>(),
+            Some((_, Delimiter::Brace))
+        )
+    {
+        let item = parser.parse()?;
+        let statement = Statement::Item(item);
+        return Ok(StatementOrFinalExpr::Statement(statement));
+    }
+    if let Some(let_token) = parser.take() {
+        let pattern = parser.parse()?;
+        let ty_opt = match parser.take() {
+            Some(colon_token) => {
+                let ty = parser.parse()?;
+                Some((colon_token, ty))
+            }
+            None => None,
+        };
+        let eq_token = parser.parse()?;
+        let expr = parser.parse()?;
+        let semicolon_token = parser.parse()?;
+        let statement_let = StatementLet {
+            let_token,
+            pattern,
+            ty_opt,
+            eq_token,
+            expr,
+            semicolon_token,
+        };
+        let statement = Statement::Let(statement_let);
+        return Ok(StatementOrFinalExpr::Statement(statement));
+    }
+    let expr = parse_statement_expr(parser)?;
+    if let Some(semicolon_token) = parser.take() {
+        let statement = Statement::Expr {
+            expr,
+            semicolon_token_opt: Some(semicolon_token),
+        };
+        return Ok(StatementOrFinalExpr::Statement(statement));
+    }
+    if let Some(consumed) = parser.check_empty() {
+        return Ok(StatementOrFinalExpr::FinalExpr(expr, consumed));
+    }
+    if expr.is_control_flow() {
+        let statement = Statement::Expr {
+            expr,
+            semicolon_token_opt: None,
+        };
+        return Ok(StatementOrFinalExpr::Statement(statement));
+    }
+
+    Err(parser.emit_error(ParseErrorKind::UnexpectedTokenInStatement))
+}
+
+/// Whether a statement could start at, or the current one ends at, the next token.
+fn at_statement_boundary(parser: &Parser) -> bool {
+    parser.peek::<SemicolonToken>().is_some()
+        || parser.peek::<LetToken>().is_some()
+        || at_item_start(parser)
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct ParseExprCtx {
     pub parsing_conditional: bool,
@@ -215,7 +242,7 @@ fn parse_statement_expr(parser: &mut Parser) -> ParseResult<Expr> {
 }
 
 fn parse_reassignment(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
-    let expr = parse_logical_or(parser, ctx)?;
+    let expr = parse_range(parser, ctx)?;
     let mut reassignment_op = None;
     if parser.peek::<AddEqToken>().is_some() {
         if let Some(add_eq_token) = parser.take::<AddEqToken>() {
@@ -293,6 +320,23 @@ fn parse_reassignment(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Exp
     Ok(expr)
 }
 
+fn parse_range(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
+    let expr = parse_logical_or(parser, ctx)?;
+    if expr.is_control_flow() && ctx.at_start_of_statement {
+        return Ok(expr);
+    }
+    if let Some(double_dot_token) = parser.take() {
+        let start = Box::new(expr);
+        let end = Box::new(parse_logical_or(parser, ctx.not_statement())?);
+        return Ok(Expr::Range {
+            start,
+            double_dot_token,
+            end,
+        });
+    }
+    Ok(expr)
+}
+
 fn parse_logical_or(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
     let mut expr = parse_logical_and(parser, ctx)?;
     if expr.is_control_flow() && ctx.at_start_of_statement {
@@ -747,7 +791,7 @@ fn parse_atom(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
         });
     }
     if let Some(while_token) = parser.take() {
-        let condition = Box::new(parse_condition(parser)?);
+        let condition = parser.parse()?;
         let block = parser.parse()?;
         return Ok(Expr::While {
             while_token,
@@ -755,6 +799,19 @@ fn parse_atom(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
             block,
         });
     }
+    if let Some(for_token) = parser.take() {
+        let value_pattern = parser.parse()?;
+        let in_token = parser.parse()?;
+        let iterator = Box::new(parse_condition(parser)?);
+        let block = parser.parse()?;
+        return Ok(Expr::For {
+            for_token,
+            value_pattern,
+            in_token,
+            iterator,
+            block,
+        });
+    }
     if parser.peek::<OpenAngleBracketToken>().is_some()
         || parser.peek::<DoubleColonToken>().is_some()
         || parser.peek::<TildeToken>().is_some()
@@ -1,8 +1,10 @@
 use crate::{Parse, ParseErrorKind, ParseResult, Parser};
 
 use sway_ast::attribute::Annotated;
-use sway_ast::keywords::{Keyword, OpenAngleBracketToken, WhereToken};
-use sway_ast::{Braces, ItemFn, ItemImpl, Ty};
+use sway_ast::keywords::{
+    ConstToken, Keyword, OpenAngleBracketToken, PubToken, TypeToken, WhereToken,
+};
+use sway_ast::{Braces, ImplType, ItemImpl, ItemImplItem, Ty};
 use sway_types::Spanned;
 
 impl Parse for ItemImpl {
@@ -30,10 +32,15 @@ impl Parse for ItemImpl {
             }
             None => None,
         };
-        let contents: Braces<Vec<Annotated<ItemFn>>> = parser.parse()?;
+        let contents: Braces<Vec<Annotated<ItemImplItem>>> = parser.parse()?;
         if trait_opt.is_some() {
-            for item_fn in contents.get().iter() {
-                if let Some(token) = &item_fn.value.fn_signature.visibility {
+            for item in contents.get().iter() {
+                let visibility = match &item.value {
+                    ItemImplItem::Fn(item_fn) => &item_fn.fn_signature.visibility,
+                    ItemImplItem::Const(item_const) => &item_const.visibility,
+                    ItemImplItem::Type(_) => &None,
+                };
+                if let Some(token) = visibility {
                     return Err(parser.emit_error_with_span(
                         ParseErrorKind::UnnecessaryVisibilityQualifier {
                             visibility: token.ident(),
@@ -53,3 +60,36 @@ impl Parse for ItemImpl {
         })
     }
 }
+
+impl Parse for ItemImplItem {
+    fn parse(parser: &mut Parser) -> ParseResult<ItemImplItem> {
+        if parser.peek::<ConstToken>().is_some() || parser.peek2::<PubToken, ConstToken>().is_some()
+        {
+            let item_const = parser.parse()?;
+            return Ok(ItemImplItem::Const(item_const));
+        }
+        if parser.peek::<TypeToken>().is_some() {
+            let impl_type = parser.parse()?;
+            return Ok(ItemImplItem::Type(impl_type));
+        }
+        let item_fn = parser.parse()?;
+        Ok(ItemImplItem::Fn(item_fn))
+    }
+}
+
+impl Parse for ImplType {
+    fn parse(parser: &mut Parser) -> ParseResult<ImplType> {
+        let type_token = parser.parse()?;
+        let name = parser.parse()?;
+        let eq_token = parser.parse()?;
+        let ty = parser.parse()?;
+        let semicolon_token = parser.parse()?;
+        Ok(ImplType {
+            type_token,
+            name,
+            eq_token,
+            ty,
+            semicolon_token,
+        })
+    }
+}
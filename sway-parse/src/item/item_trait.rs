@@ -1,8 +1,8 @@
 use crate::{Parse, ParseBracket, ParseErrorKind, ParseResult, Parser};
 
 use sway_ast::attribute::Annotated;
-use sway_ast::keywords::Keyword;
-use sway_ast::{Braces, FnSignature, ItemFn, ItemTrait, Traits};
+use sway_ast::keywords::{ConstToken, Keyword, OpenAngleBracketToken, TypeToken};
+use sway_ast::{Braces, ItemFn, ItemTrait, ItemTraitItem, TraitConst, TraitType, Traits};
 use sway_types::Spanned;
 
 impl Parse for ItemTrait {
@@ -10,6 +10,13 @@ impl Parse for ItemTrait {
         let visibility = parser.take();
         let trait_token = parser.parse()?;
         let name = parser.parse()?;
+        let generics = match parser.peek::<OpenAngleBracketToken>() {
+            Some(_open_angle_bracket_token) => {
+                let generic_params = parser.parse()?;
+                Some(generic_params)
+            }
+            None => None,
+        };
         let super_traits = match parser.take() {
             Some(colon_token) => {
                 let traits = parser.parse()?;
@@ -18,16 +25,18 @@ impl Parse for ItemTrait {
             None => None,
         };
 
-        let trait_items: Braces<Vec<(Annotated<FnSignature>, _)>> = parser.parse()?;
+        let trait_items: Braces<Vec<(Annotated<ItemTraitItem>, _)>> = parser.parse()?;
         for item in trait_items.get().iter() {
-            let (fn_sig, _) = item;
-            if let Some(token) = &fn_sig.value.visibility {
-                return Err(parser.emit_error_with_span(
-                    ParseErrorKind::UnnecessaryVisibilityQualifier {
-                        visibility: token.ident(),
-                    },
-                    token.span(),
-                ));
+            let (trait_item, _) = item;
+            if let ItemTraitItem::Fn(fn_sig) = &trait_item.value {
+                if let Some(token) = &fn_sig.visibility {
+                    return Err(parser.emit_error_with_span(
+                        ParseErrorKind::UnnecessaryVisibilityQualifier {
+                            visibility: token.ident(),
+                        },
+                        token.span(),
+                    ));
+                }
             }
         }
 
@@ -49,6 +58,7 @@ impl Parse for ItemTrait {
             visibility,
             trait_token,
             name,
+            generics,
             super_traits,
             trait_items,
             trait_defs_opt,
@@ -56,6 +66,44 @@ impl Parse for ItemTrait {
     }
 }
 
+impl Parse for ItemTraitItem {
+    fn parse(parser: &mut Parser) -> ParseResult<ItemTraitItem> {
+        if parser.peek::<ConstToken>().is_some() {
+            let trait_const = parser.parse()?;
+            return Ok(ItemTraitItem::Const(trait_const));
+        }
+        if parser.peek::<TypeToken>().is_some() {
+            let trait_type = parser.parse()?;
+            return Ok(ItemTraitItem::Type(trait_type));
+        }
+        let fn_signature = parser.parse()?;
+        Ok(ItemTraitItem::Fn(fn_signature))
+    }
+}
+
+impl Parse for TraitConst {
+    fn parse(parser: &mut Parser) -> ParseResult<TraitConst> {
+        let const_token = parser.parse()?;
+        let name = parser.parse()?;
+        let colon_token = parser.parse()?;
+        let ty = parser.parse()?;
+        Ok(TraitConst {
+            const_token,
+            name,
+            colon_token,
+            ty,
+        })
+    }
+}
+
+impl Parse for TraitType {
+    fn parse(parser: &mut Parser) -> ParseResult<TraitType> {
+        let type_token = parser.parse()?;
+        let name = parser.parse()?;
+        Ok(TraitType { type_token, name })
+    }
+}
+
 impl Parse for Traits {
     fn parse(parser: &mut Parser) -> ParseResult<Traits> {
         let prefix = parser.parse()?;
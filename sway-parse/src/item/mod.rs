@@ -1,8 +1,9 @@
 use crate::{Parse, ParseErrorKind, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
 use sway_ast::keywords::{
-    AbiToken, BreakToken, ConstToken, ContinueToken, EnumToken, FnToken, ImplToken, MutToken,
-    OpenAngleBracketToken, PubToken, StorageToken, StructToken, TraitToken, UseToken, WhereToken,
+    AbiToken, BreakToken, ConstToken, ContinueToken, EnumToken, FnToken, HashToken, ImplToken,
+    MutToken, OpenAngleBracketToken, PubToken, StorageToken, StructToken, TraitToken, UseToken,
+    WhereToken,
 };
 use sway_ast::{FnArg, FnArgs, FnSignature, ItemKind, TypeField};
 use sway_types::Ident;
@@ -72,6 +73,24 @@ impl Parse for ItemKind {
     }
 }
 
+/// Whether an item could start at the next token, which is where parsing resumes after an item
+/// fails to parse.
+pub(crate) fn at_item_start(parser: &Parser) -> bool {
+    parser.peek::<HashToken>().is_some()
+        || parser.peek::<PubToken>().is_some()
+        || parser.peek::<UseToken>().is_some()
+        || parser.peek::<StructToken>().is_some()
+        || parser.peek::<EnumToken>().is_some()
+        || parser.peek::<FnToken>().is_some()
+        || parser.peek::<TraitToken>().is_some()
+        || parser.peek::<ImplToken>().is_some()
+        || parser.peek::<AbiToken>().is_some()
+        || parser.peek::<ConstToken>().is_some()
+        || parser.peek::<StorageToken>().is_some()
+        || parser.peek::<BreakToken>().is_some()
+        || parser.peek::<ContinueToken>().is_some()
+}
+
 impl Parse for TypeField {
     fn parse(parser: &mut Parser) -> ParseResult<TypeField> {
         let name = parser.parse()?;
@@ -639,4 +658,61 @@ mod tests {
             panic!("Parsed ABI is not an ABI.");
         }
     }
+
+    #[test]
+    fn parse_attributes_doc_comments() {
+        let item = parse_item(
+            r#"
+            /// Does a thing.
+            ///
+            // Not a doc comment.
+            //// Nor is this.
+            #[foo]
+            /// More.
+            fn f() -> bool {
+                false
+            }
+            "#,
+        );
+
+        assert!(matches!(item.value, ItemKind::Fn(_)));
+
+        let docs = item
+            .attribute_list
+            .iter()
+            .map(|attrib| attrib.doc_comment())
+            .collect::<Vec<_>>();
+        assert_eq!(docs, [Some("Does a thing."), Some(""), None, Some("More.")]);
+
+        let attrib = item.attribute_list.get(0).unwrap();
+        assert_eq!(attrib.attribute.get().name.as_str(), "doc");
+        let attrib = item.attribute_list.get(2).unwrap();
+        assert_eq!(attrib.attribute.get().name.as_str(), "foo");
+    }
+
+    #[test]
+    fn parse_attributes_abi_doc_comments() {
+        let item = parse_item(
+            r#"
+            abi A {
+                /// Does a thing.
+                fn f();
+                fn g();
+            }
+            "#,
+        );
+
+        if let ItemKind::Abi(item_abi) = item.value {
+            let mut decls = item_abi.abi_items.get().iter();
+
+            let f_sig = &decls.next().unwrap().0;
+            assert_eq!(f_sig.attribute_list.len(), 1);
+            assert_eq!(f_sig.attribute_list[0].doc_comment(), Some("Does a thing."));
+
+            let g_sig = &decls.next().unwrap().0;
+            assert!(g_sig.attribute_list.is_empty());
+        } else {
+            panic!("Parsed ABI is not an ABI.");
+        }
+    }
 }
@@ -67,7 +67,9 @@ keyword_impls! {
     TrueToken,
     FalseToken,
     BreakToken,
-    ContinueToken
+    ContinueToken,
+    InToken,
+    TypeToken
 }
 
 fn peek_token<T: Token>(peeker: Peeker<'_>) -> Option<T> {
@@ -183,4 +185,5 @@ pub(crate) const RESERVED_KEYWORDS: phf::Set<&'static str> = phf::phf_set! {
     "false",
     "break",
     "continue",
+    "in",
 };
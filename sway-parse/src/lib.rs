@@ -39,7 +39,24 @@ pub enum ParseFileError {
     Parse(Vec<ParseError>),
 }
 
+/// Parses a file, failing if it has any syntax errors.
 pub fn parse_file(src: Arc<str>, path: Option<Arc<PathBuf>>) -> Result<Module, ParseFileError> {
+    let (module, errors) = parse_file_with_recovery(src, path)?;
+    if !errors.is_empty() {
+        return Err(ParseFileError::Parse(errors));
+    }
+    Ok(module)
+}
+
+/// Parses a file, recovering from syntax errors in its items and statements. The items and
+/// statements which failed to parse are replaced by `ItemKind::Error` and `Statement::Error`
+/// nodes in the returned module, and the errors are returned alongside it.
+///
+/// Errors which can't be recovered from, such as a missing module kind, still fail the parse.
+pub fn parse_file_with_recovery(
+    src: Arc<str>,
+    path: Option<Arc<PathBuf>>,
+) -> Result<(Module, Vec<ParseError>), ParseFileError> {
     let token_stream = match lex(&src, 0, src.len(), path) {
         Ok(token_stream) => token_stream,
         Err(error) => return Err(ParseFileError::Lex(error)),
@@ -50,5 +67,56 @@ pub fn parse_file(src: Arc<str>, path: Option<Arc<PathBuf>>) -> Result<Module, P
         Ok((module, _parser_consumed)) => module,
         Err(_error_emitted) => return Err(ParseFileError::Parse(errors)),
     };
-    Ok(module)
+    Ok((module, errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sway_ast::{ItemKind, Statement};
+
+    #[test]
+    fn parse_file_recovers_from_errors() {
+        let src = r#"
+            script;
+
+            struct S {
+                x: ,
+            }
+
+            fn f() -> u64 {
+                let a = ;
+                let b = 1;
+                b
+            }
+
+            fn main() {}
+        "#;
+        let (module, errors) = parse_file_with_recovery(Arc::from(src), None).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(module.items.len(), 3);
+
+        match &module.items[0].value {
+            ItemKind::Error(span) => assert!(span.as_str().starts_with("struct S")),
+            _ => panic!("Broken struct is not an error item."),
+        }
+        match &module.items[1].value {
+            ItemKind::Fn(item_fn) => {
+                let body = item_fn.body.get();
+                match &body.statements[0] {
+                    Statement::Error(span) => assert_eq!(span.as_str(), "let a = ;"),
+                    _ => panic!("Broken statement is not an error statement."),
+                }
+                assert!(matches!(body.statements[1], Statement::Let(_)));
+                assert!(body.final_expr_opt.is_some());
+            }
+            _ => panic!("Parsed function is not a function."),
+        }
+        assert!(matches!(module.items[2].value, ItemKind::Fn(_)));
+
+        assert!(matches!(
+            parse_file(Arc::from(src), None),
+            Err(ParseFileError::Parse(errors)) if errors.len() == 2
+        ));
+    }
 }
@@ -1,7 +1,9 @@
+use crate::item::at_item_start;
 use crate::{Parse, ParseErrorKind, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
-use sway_ast::keywords::DepToken;
-use sway_ast::{Module, ModuleKind};
+use sway_ast::keywords::{DepToken, HashToken};
+use sway_ast::token::DocStyle;
+use sway_ast::{Item, ItemKind, Module, ModuleKind};
 
 impl Parse for ModuleKind {
     fn parse(parser: &mut Parser) -> ParseResult<Self> {
@@ -25,15 +27,41 @@ impl Parse for ModuleKind {
 
 impl ParseToEnd for Module {
     fn parse_to_end<'a, 'e>(mut parser: Parser<'a, 'e>) -> ParseResult<(Self, ParserConsumed<'a>)> {
+        // `//!` comments document the module, and are usually written around its declaration.
+        // Attributes written before the declaration, e.g. `#[allow(dead_code)]`, apply to the
+        // whole module.
+        let mut attribute_list = parser.doc_attributes(DocStyle::Inner);
+        while parser.peek::<HashToken>().is_some() {
+            attribute_list.push(parser.parse()?);
+            attribute_list.append(&mut parser.doc_attributes(DocStyle::Inner));
+        }
         let kind = parser.parse()?;
         let semicolon_token = parser.parse()?;
+        attribute_list.append(&mut parser.doc_attributes(DocStyle::Inner));
         let mut dependencies = Vec::new();
         while let Some(..) = parser.peek::<DepToken>() {
             let dependency = parser.parse()?;
             dependencies.push(dependency);
+            attribute_list.append(&mut parser.doc_attributes(DocStyle::Inner));
         }
-        let (items, consumed) = parser.parse_to_end()?;
+        let mut items = Vec::new();
+        let consumed = loop {
+            if let Some(consumed) = parser.check_empty() {
+                break consumed;
+            }
+            // After a syntax error, skip to the next item and keep going, so that every error in
+            // the module is reported and the items which did parse can still be used.
+            let item = match parser.parse_or_recover(|parser| parser.parse(), at_item_start) {
+                Ok(item) => item,
+                Err(span) => Item {
+                    attribute_list: Vec::new(),
+                    value: ItemKind::Error(span),
+                },
+            };
+            items.push(item);
+        };
         let module = Self {
+            attribute_list,
             kind,
             semicolon_token,
             dependencies,
@@ -1,13 +1,17 @@
 use crate::{Parse, ParseError, ParseErrorKind, ParseToEnd, Peek};
 
 use core::marker::PhantomData;
+use sway_ast::attribute::AttributeDecl;
 use sway_ast::literal::Literal;
-use sway_ast::token::{Delimiter, Group, Punct, PunctKind, Spacing, TokenStream, TokenTree};
+use sway_ast::token::{
+    Delimiter, DocComment, DocStyle, Group, Punct, PunctKind, Spacing, TokenStream, TokenTree,
+};
 use sway_types::{Ident, Span, Spanned};
 
 pub struct Parser<'a, 'e> {
     token_trees: &'a [TokenTree],
     full_span: Span,
+    doc_comments: &'a [DocComment],
     errors: &'e mut Vec<ParseError>,
 }
 
@@ -16,10 +20,25 @@ impl<'a, 'e> Parser<'a, 'e> {
         Parser {
             token_trees: token_stream.token_trees(),
             full_span: token_stream.span(),
+            doc_comments: token_stream.doc_comments(),
             errors,
         }
     }
 
+    /// The doc comments of the given style which are written just before the current token,
+    /// desugared into `#[doc(..)]` attributes.
+    pub fn doc_attributes(&self, style: DocStyle) -> Vec<AttributeDecl> {
+        let target = match self.token_trees.first() {
+            Some(token_tree) => token_tree.span().start(),
+            None => return Vec::new(),
+        };
+        self.doc_comments
+            .iter()
+            .filter(|doc_comment| doc_comment.target == target && doc_comment.style == style)
+            .map(AttributeDecl::from_doc_comment)
+            .collect()
+    }
+
     pub fn emit_error(&mut self, kind: ParseErrorKind) -> ErrorEmitted {
         let span = match self.token_trees.first() {
             Some(token_tree) => token_tree.span(),
@@ -107,6 +126,36 @@ impl<'a, 'e> Parser<'a, 'e> {
         T::parse(self)
     }
 
+    /// Parses a value with `parse`, recovering from a syntax error by skipping tokens until
+    /// `at_boundary` holds or there are no tokens left. The error has already been emitted, and
+    /// the span of the skipped tokens is returned in place of the value.
+    ///
+    /// Tokens are skipped a whole token tree at a time, so recovery never stops inside of
+    /// delimiters. At least one token is skipped, so that parsing always makes progress.
+    pub fn parse_or_recover<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Parser<'a, 'e>) -> ParseResult<T>,
+        at_boundary: impl Fn(&Parser<'a, 'e>) -> bool,
+    ) -> Result<T, Span> {
+        let start = self.token_trees;
+        let _error_emitted = match parse(self) {
+            Ok(value) => return Ok(value),
+            Err(error_emitted) => error_emitted,
+        };
+        if self.token_trees.len() == start.len() && !start.is_empty() {
+            self.token_trees = &start[1..];
+        }
+        while !self.token_trees.is_empty() && !at_boundary(self) {
+            self.token_trees = &self.token_trees[1..];
+        }
+        let skipped = &start[..start.len() - self.token_trees.len()];
+        let span = match (skipped.first(), skipped.last()) {
+            (Some(first), Some(last)) => Span::join(first.span(), last.span()),
+            _ => self.full_span.clone(),
+        };
+        Err(span)
+    }
+
     pub fn parse_to_end<T: ParseToEnd>(self) -> ParseResult<(T, ParserConsumed<'a>)> {
         T::parse_to_end(self)
     }
@@ -137,6 +186,7 @@ impl<'a, 'e> Parser<'a, 'e> {
                 let parser = Parser {
                     token_trees: token_stream.token_trees(),
                     full_span: token_stream.span(),
+                    doc_comments: token_stream.doc_comments(),
                     errors: self.errors,
                 };
                 Some((parser, span.clone()))
@@ -1,7 +1,7 @@
 use crate::{Parse, ParseBracket, ParseErrorKind, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
 use sway_ast::brackets::{Parens, SquareBrackets};
-use sway_ast::keywords::{DoubleColonToken, OpenAngleBracketToken};
+use sway_ast::keywords::{DoubleColonToken, FnToken, OpenAngleBracketToken};
 use sway_ast::token::Delimiter;
 use sway_ast::ty::{Ty, TyArrayDescriptor, TyTupleDescriptor};
 use sway_types::Ident;
@@ -42,6 +42,21 @@ impl Parse for Ty {
         if let Some(underscore_token) = parser.take() {
             return Ok(Ty::Infer { underscore_token });
         }
+        if let Some(fn_token) = parser.take::<FnToken>() {
+            let arguments = parser.parse()?;
+            let return_type_opt = match parser.take() {
+                Some(right_arrow_token) => {
+                    let ty = parser.parse()?;
+                    Some((right_arrow_token, ty))
+                }
+                None => None,
+            };
+            return Ok(Ty::Fn {
+                fn_token,
+                arguments,
+                return_type_opt,
+            });
+        }
         if parser.peek::<OpenAngleBracketToken>().is_some()
             || parser.peek::<DoubleColonToken>().is_some()
             || parser.peek::<Ident>().is_some()
@@ -100,10 +100,12 @@ pub(crate) fn runs_in_vm(file_name: &str, locked: bool) -> (ProgramState, Compil
 pub(crate) fn compile_and_capture_output(
     file_name: &str,
     locked: bool,
+    deny_warnings: bool,
 ) -> (Result<Compiled>, String) {
     tracing::info!(" Compiling {}", file_name);
 
-    let (result, mut output) = compile_to_bytes_verbose(file_name, locked, true, true);
+    let (result, mut output) =
+        compile_to_bytes_verbose(file_name, locked, deny_warnings, true, true);
 
     // If verbosity is requested then print it out.
     if get_test_config_from_env() {
@@ -121,12 +123,13 @@ pub(crate) fn compile_and_capture_output(
 
 /// Compiles the code and returns a result of the compilation,
 pub(crate) fn compile_to_bytes(file_name: &str, locked: bool) -> Result<Compiled> {
-    compile_to_bytes_verbose(file_name, locked, get_test_config_from_env(), false).0
+    compile_to_bytes_verbose(file_name, locked, false, get_test_config_from_env(), false).0
 }
 
 pub(crate) fn compile_to_bytes_verbose(
     file_name: &str,
     locked: bool,
+    deny_warnings: bool,
     verbose: bool,
     capture_output: bool,
 ) -> (Result<Compiled>, String) {
@@ -147,6 +150,7 @@ pub(crate) fn compile_to_bytes_verbose(
         )),
         locked,
         silent_mode: !verbose,
+        deny_warnings,
         ..Default::default()
     });
 
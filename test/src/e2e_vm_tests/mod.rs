@@ -35,6 +35,7 @@ struct TestDescription {
     contract_paths: Vec<String>,
     validate_abi: bool,
     validate_storage_slots: bool,
+    deny_warnings: bool,
     checker: filecheck::Checker,
 }
 
@@ -58,6 +59,7 @@ pub fn run(locked: bool, filter_regex: Option<&regex::Regex>) {
         contract_paths,
         validate_abi,
         validate_storage_slots,
+        deny_warnings,
         checker,
     } in configured_tests
     {
@@ -90,8 +92,11 @@ pub fn run(locked: bool, filter_regex: Option<&regex::Regex>) {
             }
 
             TestCategory::Compiles => {
-                let (result, output) =
-                    crate::e2e_vm_tests::harness::compile_and_capture_output(&name, locked);
+                let (result, output) = crate::e2e_vm_tests::harness::compile_and_capture_output(
+                    &name,
+                    locked,
+                    deny_warnings,
+                );
 
                 assert!(result.is_ok());
                 check_file_checker(checker, &name, &output);
@@ -110,8 +115,11 @@ pub fn run(locked: bool, filter_regex: Option<&regex::Regex>) {
             }
 
             TestCategory::FailsToCompile => {
-                let (result, output) =
-                    crate::e2e_vm_tests::harness::compile_and_capture_output(&name, locked);
+                let (result, output) = crate::e2e_vm_tests::harness::compile_and_capture_output(
+                    &name,
+                    locked,
+                    deny_warnings,
+                );
                 match result {
                     Ok(_) => {
                         panic!("For {name}:\nFailing test did not fail.");
@@ -315,6 +323,11 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription, String> {
         .map(|v| v.as_bool().unwrap_or(false))
         .unwrap_or(false);
 
+    let deny_warnings = toml_content
+        .get("deny_warnings")
+        .map(|v| v.as_bool().unwrap_or(false))
+        .unwrap_or(false);
+
     // We need to adjust the path to start relative to `test_programs`.
     let name = path
         .iter()
@@ -337,6 +350,7 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription, String> {
         contract_paths,
         validate_abi,
         validate_storage_slots,
+        deny_warnings,
         checker,
     })
 }
@@ -0,0 +1,15 @@
+script;
+
+#[deny(dead_code)]
+fn denied() -> u64 {
+    1
+}
+
+#[allow(dead_code)]
+fn allowed() -> u64 {
+    2
+}
+
+fn main() -> u64 {
+    42
+}
@@ -0,0 +1,15 @@
+script;
+
+fn unused() -> u64 {
+    1
+}
+
+// Lints which are allowed explicitly aren't denied by `--deny-warnings`.
+#[allow(dead_code)]
+fn allowed() -> u64 {
+    2
+}
+
+fn main() -> u64 {
+    42
+}
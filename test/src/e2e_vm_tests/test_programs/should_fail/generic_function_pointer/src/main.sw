@@ -0,0 +1,10 @@
+script;
+
+fn identity<T>(x: T) -> T {
+    x
+}
+
+fn main() -> u64 {
+    let f: fn(u64) -> u64 = identity;
+    f(0)
+}
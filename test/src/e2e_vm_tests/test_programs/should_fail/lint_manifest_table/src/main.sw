@@ -0,0 +1,15 @@
+script;
+
+fn unused() -> u64 {
+    1
+}
+
+// The attribute takes precedence over the `[lints]` table of the manifest.
+#[allow(dead_code)]
+fn allowed() -> u64 {
+    2
+}
+
+fn main() -> u64 {
+    42
+}
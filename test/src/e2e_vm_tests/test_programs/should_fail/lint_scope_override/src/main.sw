@@ -0,0 +1,34 @@
+#[deny(dead_code)]
+script;
+
+struct S {
+    x: u64,
+}
+
+// The attributes of an item take precedence over those of the module.
+#[allow(dead_code)]
+impl S {
+    fn used(self) -> u64 {
+        self.x + 42
+    }
+
+    fn allowed(self) -> u64 {
+        1
+    }
+
+    // And those of a method take precedence over those of its impl.
+    #[deny(dead_code)]
+    fn denied(self) -> u64 {
+        2
+    }
+}
+
+#[allow(dead_code)]
+fn allowed_fn() -> u64 {
+    3
+}
+
+fn main() -> u64 {
+    let s = S { x: 0 };
+    s.used()
+}
@@ -0,0 +1,11 @@
+script;
+
+enum Number {
+    One: (),
+    Other: u64,
+}
+
+fn main() -> u64 {
+    let Number::Other(n) = Number::One;
+    n
+}
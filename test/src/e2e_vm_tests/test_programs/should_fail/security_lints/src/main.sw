@@ -0,0 +1,104 @@
+#[deny(storage_write_after_contract_call, unchecked_msg_sender, unchecked_transfer_result)]
+contract;
+
+use std::{
+    assert::assert,
+    chain::auth::msg_sender,
+    contract_id::ContractId,
+    identity::Identity,
+    reentrancy::reentrancy_guard,
+    result::Result,
+    token::transfer as send_tokens,
+};
+
+abi Vault {
+    fn send(amount: u64);
+}
+
+abi Bank {
+    #[storage(read, write)]
+    fn withdraw(amount: u64);
+    #[storage(read, write)]
+    fn guarded_withdraw(amount: u64);
+    #[storage(write)]
+    fn deposit(amount: u64);
+    #[storage(write)]
+    fn close();
+    #[storage(write)]
+    fn reset();
+    fn pay(amount: u64);
+}
+
+// Only the `Result`s of functions which call the token transfer functions of `std::token` are
+// checked, not those of any function with "transfer" in its name.
+enum TransferError {
+    Failed: (),
+}
+
+fn checked_transfer(amount: u64) -> Result<(), TransferError> {
+    if amount == 0 {
+        return Result::Err(TransferError::Failed);
+    }
+    let asset_id = ~ContractId::from(0x0000000000000000000000000000000000000000000000000000000000000000);
+    let to = ~ContractId::from(0x0000000000000000000000000000000000000000000000000000000000000001);
+    send_tokens(amount, asset_id, Identity::ContractId(to));
+    Result::Ok(())
+}
+
+// Not `std::chain::auth::caller_is_external`, so it doesn't check who called the contract.
+fn caller_is_external() -> bool {
+    false
+}
+
+fn try_transfer(amount: u64) -> Result<(), TransferError> {
+    if amount == 0 {
+        Result::Err(TransferError::Failed)
+    } else {
+        Result::Ok(())
+    }
+}
+
+storage {
+    balance: u64 = 0,
+}
+
+impl Bank for Contract {
+    #[storage(read, write)]
+    fn withdraw(amount: u64) {
+        assert(msg_sender().is_ok());
+        let vault = abi(Vault, 0x0000000000000000000000000000000000000000000000000000000000000001);
+        vault.send(amount);
+        storage.balance = storage.balance - amount;
+    }
+
+    #[storage(read, write)]
+    fn guarded_withdraw(amount: u64) {
+        reentrancy_guard();
+        assert(msg_sender().is_ok());
+        let guarded_vault = abi(Vault, 0x0000000000000000000000000000000000000000000000000000000000000001);
+        guarded_vault.send(amount);
+        storage.balance = storage.balance - amount;
+    }
+
+    #[storage(write)]
+    fn deposit(amount: u64) {
+        storage.balance = amount;
+    }
+
+    #[storage(write)]
+    fn close() {
+        assert(!caller_is_external());
+        storage.balance = 0;
+    }
+
+    #[storage(write)]
+    #[allow(unchecked_msg_sender)]
+    fn reset() {
+        storage.balance = 0;
+    }
+
+    fn pay(amount: u64) {
+        let _ = try_transfer(amount);
+        checked_transfer(amount);
+    }
+}
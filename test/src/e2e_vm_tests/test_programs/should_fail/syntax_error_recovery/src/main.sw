@@ -0,0 +1,15 @@
+script;
+
+struct Broken {
+    x: ,
+}
+
+// The body has no value only because its statement is broken, so that isn't reported.
+fn missing_value() -> u64 {
+    let a = ;
+}
+
+fn main() {
+    // The rest of the program is still type checked.
+    let b: u64 = true;
+}
@@ -0,0 +1,28 @@
+// This should result in errors for associated items that are missing from, or unknown to, the
+// trait being implemented, and for type arguments the trait does not expect.
+
+library test;
+
+trait Container {
+    type Item;
+    const CAPACITY: u64;
+}
+
+trait Convert<T> {
+    fn convert(self) -> T;
+}
+
+struct S {
+    x: u64,
+}
+
+impl Container for S {
+    type Item = u64;
+    type Key = u64;
+}
+
+impl Convert<u64, bool> for S {
+    fn convert(self) -> u64 {
+        self.x
+    }
+}
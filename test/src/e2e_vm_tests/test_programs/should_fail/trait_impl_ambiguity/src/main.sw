@@ -0,0 +1,59 @@
+// This should result in errors for a trait implemented twice with the same type arguments, and
+// for calls which fit the methods of several implementations of a generic trait, or none of them.
+
+library test;
+
+trait Convert<T> {
+    fn convert(self) -> T;
+}
+
+trait MyFrom<T> {
+    fn my_from(value: T) -> Self;
+}
+
+pub struct S {
+    value: u64,
+}
+
+impl Convert<u64> for S {
+    fn convert(self) -> u64 {
+        self.value
+    }
+}
+
+impl Convert<bool> for S {
+    fn convert(self) -> bool {
+        true
+    }
+}
+
+impl MyFrom<u64> for S {
+    fn my_from(value: u64) -> S {
+        S { value }
+    }
+}
+
+impl MyFrom<u64> for S {
+    fn my_from(value: u64) -> S {
+        S { value: value + 1 }
+    }
+}
+
+impl MyFrom<bool> for S {
+    fn my_from(value: bool) -> S {
+        S { value: 1 }
+    }
+}
+
+pub fn ambiguous(s: S) {
+    let x = s.convert();
+}
+
+pub fn no_applicable(s: S) {
+    let x: b256 = s.convert();
+}
+
+pub fn unambiguous(s: S) -> u64 {
+    let x: u64 = s.convert();
+    x
+}
@@ -0,0 +1,32 @@
+#[deny(unused_imports, unused_variables)]
+script;
+
+use std::{assert::assert, option::Option};
+use std::hash::sha256;
+use std::result::Result as _Result;
+// Only `keccak256` is unused, but the constant and the trait are imported by the same statement.
+use std::{constants::ZERO_B256, hash::keccak256, math::Exponentiate};
+// Only `log` is unused, but `Address` is imported by the glob in the same statement.
+use std::{address::*, logging::log};
+
+fn double(x: u64, _unit: u64) -> u64 {
+    let y = x * 2;
+    let z = 3;
+    y
+}
+
+fn main() -> u64 {
+    let mut total = 0;
+    let _ignored = double(1, 1);
+    let _address = ~Address::from(ZERO_B256);
+    let two: u64 = 2;
+    let count = two.pow(2);
+    while total < count {
+        total = total + 1;
+    }
+    let opt = Option::Some(total);
+    match opt {
+        Option::Some(value) => value,
+        Option::None => 0,
+    }
+}
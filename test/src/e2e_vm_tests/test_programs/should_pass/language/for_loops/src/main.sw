@@ -0,0 +1,39 @@
+script;
+
+use std::vec::Vec;
+
+fn main() -> u64 {
+    // 0 + 1 + 2 + 3 + 4
+    let mut sum = 0;
+    for i in 0..5 {
+        sum = sum + i;
+    }
+
+    // Skip 2 and stop at 100, adding 1 + 3.
+    let mut values = ~Vec::new();
+    values.push(1);
+    values.push(2);
+    values.push(3);
+    values.push(100);
+    for value in values.iter() {
+        if value == 2 {
+            continue;
+        }
+        if value == 100 {
+            break;
+        }
+        sum = sum + value;
+    }
+
+    // Each inner range has a single value, adding 14 twice.
+    let mut pairs = ~Vec::new();
+    pairs.push((1, 2));
+    pairs.push((3, 4));
+    for (start, end) in pairs.iter() {
+        for _ in start..end {
+            sum = sum + 14;
+        }
+    }
+
+    sum
+}
@@ -0,0 +1,24 @@
+script;
+
+fn double(x: u64) -> u64 {
+    asm(r1: x, r2) {
+        add r2 r1 r1;
+        r2: u64
+    }
+}
+
+fn apply(f: fn(u64) -> u64, x: u64) -> u64 {
+    f(x)
+}
+
+fn main() -> u64 {
+    let f: fn(u64) -> u64 = double;
+    let a = f(20);
+
+    let b = apply(double, 1);
+
+    asm(r1: a, r2: b, r3) {
+        add r3 r1 r2;
+        r3: u64
+    }
+}
@@ -0,0 +1,92 @@
+script;
+
+use std::assert::assert;
+
+trait Convert<T> {
+    fn convert(self) -> T;
+}
+
+trait MyFrom<T> {
+    fn my_from(value: T) -> Self;
+}
+
+trait Shape {
+    type Unit;
+    const SIDES: u64;
+
+    fn area(self) -> Self::Unit;
+} {
+    fn sides(self) -> u64 {
+        Self::SIDES
+    }
+}
+
+struct Square {
+    side: u64,
+}
+
+struct Wrapper {
+    value: u64,
+}
+
+impl Convert<u64> for Wrapper {
+    fn convert(self) -> u64 {
+        self.value
+    }
+}
+
+// differs from the impl above only in the return type of `convert`
+impl Convert<bool> for Wrapper {
+    fn convert(self) -> bool {
+        self.value != 0
+    }
+}
+
+impl MyFrom<u64> for Wrapper {
+    fn my_from(value: u64) -> Wrapper {
+        Wrapper { value }
+    }
+}
+
+impl MyFrom<bool> for Wrapper {
+    fn my_from(value: bool) -> Wrapper {
+        if value {
+            Wrapper { value: 1 }
+        } else {
+            Wrapper { value: 0 }
+        }
+    }
+}
+
+impl Shape for Square {
+    type Unit = u64;
+    const SIDES = 4;
+
+    fn area(self) -> u64 {
+        self.side * self.side
+    }
+}
+
+impl Wrapper {
+    const OFFSET: u64 = 2;
+
+    fn offset(self) -> u64 {
+        self.value + Self::OFFSET
+    }
+}
+
+fn main() -> u64 {
+    let w = Wrapper::my_from(10);
+    let n: u64 = w.convert();
+    assert(n == 10);
+    let t = Wrapper::my_from(true);
+    let one: u64 = t.convert();
+    assert(one == 1);
+    let is_set: bool = t.convert();
+    assert(is_set);
+    assert(t.offset() == 3);
+
+    let s = Square { side: 5 };
+    assert(s.sides() == 4);
+    s.area() + n + t.offset() + s.sides()
+}
@@ -0,0 +1,60 @@
+script;
+
+struct Pair {
+    a: u64,
+    b: u64,
+}
+
+fn add(x: u64, y: u64) -> u64 {
+    asm(r1: x, r2: y, r3) {
+        add r3 r1 r2;
+        r3: u64
+    }
+}
+
+fn lt(x: u64, y: u64) -> bool {
+    asm(r1: x, r2: y, r3) {
+        lt r3 r1 r2;
+        r3: bool
+    }
+}
+
+fn eq(x: u64, y: u64) -> bool {
+    asm(r1: x, r2: y, r3) {
+        eq r3 r1 r2;
+        r3: bool
+    }
+}
+
+fn next_pair(x: u64) -> Pair {
+    let pair = Pair {
+        a: x,
+        b: add(x, 1),
+    };
+    pair
+}
+
+fn same_pair(x: u64) -> Pair {
+    let pair = Pair { a: x, b: x };
+    pair
+}
+
+fn main() -> u64 {
+    // The callee is only known at runtime, and returns an aggregate from its locals.
+    let flag = true;
+    let f = if flag { next_pair } else { same_pair };
+
+    // The callee's locals must be freed on every return, so the stack pointer is the same after
+    // the loop as before it.
+    let sp_before = asm() { sp };
+    let mut i = 0;
+    let mut sum = 0;
+    while lt(i, 10) {
+        let pair = f(i);
+        sum = add(sum, pair.b);
+        i = add(i, 1);
+    }
+    let sp_after = asm() { sp };
+
+    if eq(sp_before, sp_after) { sum } else { 0 }
+}
@@ -0,0 +1,40 @@
+script;
+
+struct Callback {
+    f: fn(u64) -> u64,
+}
+
+fn double(x: u64) -> u64 {
+    asm(r1: x, r2) {
+        add r2 r1 r1;
+        r2: u64
+    }
+}
+
+fn triple(x: u64) -> u64 {
+    asm(r1: x, r2) {
+        muli r2 r1 i3;
+        r2: u64
+    }
+}
+
+fn apply_twice(f: fn(u64) -> u64, x: u64) -> u64 {
+    f(f(x))
+}
+
+fn main() -> u64 {
+    // The callee is only known at runtime.
+    let flag = true;
+    let f = if flag { double } else { triple };
+    let a = f(5);
+
+    // The callee is read from a struct.
+    let callback = Callback { f: triple };
+    let g = callback.f;
+    let b = apply_twice(g, 2);
+
+    asm(r1: a, r2: b, r3) {
+        add r3 r1 r2;
+        r3: u64
+    }
+}
@@ -0,0 +1,35 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+enum Wrapper {
+    Value: (u64, u64),
+}
+
+fn sum_point(Point { x, y }: Point) -> u64 {
+    x + y
+}
+
+fn first((a, _): (u64, u64)) -> u64 {
+    a
+}
+
+fn unwrap(Wrapper::Value((a, b)): Wrapper, _: bool) -> u64 {
+    a + b
+}
+
+fn main() -> u64 {
+    let Point { x, y } = Point { x: 1, y: 2 };
+
+    let (mut a, (b, c)) = (3, (4, 5));
+    a = a + b + c;
+
+    let Wrapper::Value((d, mut e)): Wrapper = Wrapper::Value((6, 7));
+    e = e + d;
+
+    // 3 + 12 + 13 + 9 + 2 + 3
+    x + y + a + e + sum_point(Point { x: 4, y: 5 }) + first((2, 100)) + unwrap(Wrapper::Value((1, 2)), true)
+}
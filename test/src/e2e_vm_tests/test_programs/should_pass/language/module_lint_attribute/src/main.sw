@@ -0,0 +1,10 @@
+#[allow(dead_code)]
+script;
+
+fn unused() -> u64 {
+    1
+}
+
+fn main() -> u64 {
+    42
+}
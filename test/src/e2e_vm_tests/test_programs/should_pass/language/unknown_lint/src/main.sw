@@ -0,0 +1,6 @@
+script;
+
+#[allow(not_a_lint)]
+fn main() -> u64 {
+    42
+}
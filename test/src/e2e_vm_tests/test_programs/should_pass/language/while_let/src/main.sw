@@ -0,0 +1,40 @@
+script;
+
+use std::option::Option;
+
+fn next_value(counter: u64) -> Option<u64> {
+    if counter < 5 {
+        Option::Some(counter * 2)
+    } else {
+        Option::None
+    }
+}
+
+fn main() -> u64 {
+    // 0 + 2 + 4 + 6 + 8
+    let mut counter = 0;
+    let mut sum = 0;
+    while let Option::Some(value) = next_value(counter) {
+        sum = sum + value;
+        counter = counter + 1;
+    }
+
+    // Skip 2 and stop at 6, adding 0 + 4.
+    let mut i = 0;
+    while let Option::Some(value) = next_value(i) {
+        i = i + 1;
+        if value == 2 {
+            continue;
+        }
+        if value == 6 {
+            break;
+        }
+        sum = sum + value;
+    }
+
+    // 24 + 8
+    if let Option::Some(value) = next_value(i) {
+        sum = sum + value;
+    }
+    sum
+}
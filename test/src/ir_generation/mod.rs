@@ -250,6 +250,7 @@ fn compile_core() -> namespace::Module {
         path: Some(libcore_root_dir),
         offline_mode: true,
         silent_mode: true,
+        message_format: Default::default(),
         locked: false,
     };
 
@@ -23,6 +23,8 @@ abi MyContract {
     fn u64_is_empty() -> bool;
     #[storage(write)]
     fn u64_clear();
+    #[storage(read)]
+    fn u64_sum() -> u64;
 }
 
 storage {
@@ -70,4 +72,12 @@ impl MyContract for Contract {
     fn u64_clear() {
         storage.my_vec.clear();
     }
+    #[storage(read)]
+    fn u64_sum() -> u64 {
+        let mut sum = 0;
+        for value in storage.my_vec.iter() {
+            sum = sum + value;
+        }
+        sum
+    }
 }
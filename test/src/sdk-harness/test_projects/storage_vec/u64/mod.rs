@@ -2,7 +2,7 @@ mod utils;
 
 use utils::{
     setup::get_contract_instance,
-    wrappers::{clear, get, insert, is_empty, len, pop, push, remove, set, swap_remove},
+    wrappers::{clear, get, insert, is_empty, len, pop, push, remove, set, sum, swap_remove},
 };
 
 // TODO: Replace many of the get calls with direct storage values
@@ -231,6 +231,19 @@ mod success {
         assert_eq!(100, get(&instance, 2).await);
         assert_eq!(50, get(&instance, 3).await);
     }
+
+    #[tokio::test]
+    async fn can_iterate() {
+        let (instance, _id) = get_contract_instance().await;
+
+        assert_eq!(0, sum(&instance).await);
+
+        push(&instance, 50).await;
+        push(&instance, 100).await;
+        push(&instance, 150).await;
+
+        assert_eq!(300, sum(&instance).await);
+    }
 }
 
 // Some of these are meant to be tests for None returns but since the SDK doesnt support options;
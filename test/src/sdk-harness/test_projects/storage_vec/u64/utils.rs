@@ -72,4 +72,8 @@ pub mod wrappers {
     pub async fn clear(instance: &MyContract) {
         instance.u64_clear().call().await.unwrap();
     }
+
+    pub async fn sum(instance: &MyContract) -> u64 {
+        instance.u64_sum().call().await.unwrap().value
+    }
 }